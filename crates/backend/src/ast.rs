@@ -12,6 +12,10 @@ pub struct NapiFn {
   pub is_ret_result: bool,
   pub is_async: bool,
   pub within_async_runtime: bool,
+  /// Run the fn on the named background thread, and return a `Promise`
+  pub thread: Option<String>,
+  /// Emit a one-time `DeprecationWarning` with the message of `#[napi(deprecated)]` when first called
+  pub deprecation_warning: Option<String>,
   pub fn_self: Option<FnSelf>,
   pub kind: FnKind,
   pub vis: syn::Visibility,
@@ -119,6 +123,7 @@ pub struct NapiObject {
   pub object_from_js: bool,
   pub object_to_js: bool,
   pub is_tuple: bool,
+  pub cache_keys: bool,
 }
 
 #[derive(Debug, Clone)]
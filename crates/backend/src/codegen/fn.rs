@@ -116,7 +116,23 @@ impl TryToTokens for NapiFn {
     } else {
       quote! {}
     };
-    let native_call = if !self.is_async {
+    let native_call = if let Some(thread) = &self.thread {
+      let call = if self.is_ret_result {
+        quote! { #receiver(#(#arg_names),*) }
+      } else {
+        let ret_type = if let Some(t) = &self.ret {
+          quote! { #t }
+        } else {
+          quote! { () }
+        };
+        quote! { Ok::<#ret_type, napi::Error>(#receiver(#(#arg_names),*)) }
+      };
+      quote! {
+        napi::bindgen_prelude::execute_on_thread(env, #thread, move || { #call }, move |env, #receiver_ret_name| {
+          #ret
+        })
+      }
+    } else if !self.is_async {
       if self.within_async_runtime {
         quote! {
           napi::bindgen_prelude::within_runtime_if_available(move || {
@@ -145,11 +161,45 @@ impl TryToTokens for NapiFn {
         };
         quote! { Ok::<#ret_type, napi::Error>(#receiver(#(#arg_names),*).await) }
       };
-      quote! {
-        napi::bindgen_prelude::execute_tokio_future(env, async move { #call }, move |env, #receiver_ret_name| {
-          _args_ref.drop(env);
-          #ret
-        })
+      // the future is dropped once the `AbortSignal` argument is aborted
+      let abort_signal = self
+        .args
+        .iter()
+        .zip(arg_names.iter())
+        .find_map(|(arg, name)| match &arg.kind {
+          NapiFnArgKind::PatType(path) => {
+            abort_signal_arg(&path.ty).map(|optional| (name, optional))
+          }
+          NapiFnArgKind::Callback(_) => None,
+        });
+      if let Some((abort_signal, optional)) = abort_signal {
+        let abort_token = if optional {
+          // a missing signal is never aborted
+          quote! {
+            #abort_signal
+              .as_ref()
+              .map(napi::bindgen_prelude::AbortSignal::token)
+              .unwrap_or_default()
+          }
+        } else {
+          quote! { napi::bindgen_prelude::AbortSignal::token(&#abort_signal) }
+        };
+        quote! {
+          {
+            let __abort_token = #abort_token;
+            napi::bindgen_prelude::execute_future(env, __abort_token.run_until_cancelled(async move { #call }), move |env, #receiver_ret_name| {
+              _args_ref.drop(env);
+              #ret
+            })
+          }
+        }
+      } else {
+        quote! {
+          napi::bindgen_prelude::execute_future(env, async move { #call }, move |env, #receiver_ret_name| {
+            _args_ref.drop(env);
+            #ret
+          })
+        }
       }
     };
 
@@ -219,6 +269,24 @@ impl TryToTokens for NapiFn {
       }
     };
 
+    let call_name = match &self.parent {
+      Some(parent) => format!("{}.{}", parent, self.js_name),
+      None => self.js_name.clone(),
+    };
+
+    let deprecation_warning = match &self.deprecation_warning {
+      Some(message) => {
+        let message = format!("{} is deprecated: {}", call_name, message);
+        quote! {
+          static __DEPRECATION_WARNED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+          if !__DEPRECATION_WARNED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            napi::bindgen_prelude::emit_deprecation_warning(env, #message);
+          }
+        }
+      }
+      None => quote! {},
+    };
+
     (quote! {
       #(#attrs)*
       #[doc(hidden)]
@@ -228,6 +296,9 @@ impl TryToTokens for NapiFn {
         env: napi::bindgen_prelude::sys::napi_env,
         cb: napi::bindgen_prelude::sys::napi_callback_info
       ) -> napi::bindgen_prelude::sys::napi_value {
+        let _watchdog = napi::bindgen_prelude::LoopWatchdogScope::enter(#call_name);
+        let _slice_borrows = napi::bindgen_prelude::SliceBorrowScope::enter(#call_name);
+        #deprecation_warning
         unsafe {
           #function_call.unwrap_or_else(|e| {
             napi::bindgen_prelude::JsError::from(e).throw_into(env);
@@ -683,7 +754,7 @@ impl NapiFn {
           Ok(quote! { cb.factory(#js_name, #ret) })
         }
       } else if self.is_ret_result {
-        if self.is_async {
+        if self.is_async || self.thread.is_some() {
           Ok(quote! {
             <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
           })
@@ -727,6 +798,15 @@ impl NapiFn {
       let intermediate_ident = get_intermediate_ident(&name_str);
       let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
       let cb_name = Ident::new(&format!("{}_js_function", name_str), Span::call_site());
+      #[cfg(feature = "type-def")]
+      let register_signature = {
+        let signature = self.gen_ts_signature();
+        quote! {
+          napi::bindgen_prelude::register_module_export_signature(#js_mod_ident, #js_name, #signature);
+        }
+      };
+      #[cfg(not(feature = "type-def"))]
+      let register_signature = quote! {};
 
       if self.module_exports {
         return quote! {
@@ -783,6 +863,7 @@ impl NapiFn {
         #[napi::ctor::ctor(crate_path=::napi::ctor)]
         fn #module_register_name() {
           napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name);
+          #register_signature
         }
 
         #[allow(clippy::all)]
@@ -791,6 +872,7 @@ impl NapiFn {
         #[no_mangle]
         extern "C" fn #module_register_name() {
           napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name);
+          #register_signature
         }
       }
     }
@@ -822,6 +904,44 @@ fn hidden_ty_lifetime(ty: &mut syn::Type) -> BindgenResult<()> {
   Ok(())
 }
 
+// `Some(optional)` if `ty` is the `AbortSignal` of napi, imported from the prelude or named by its path,
+// or an `Option` of it
+fn abort_signal_arg(ty: &syn::Type) -> Option<bool> {
+  let Type::Path(TypePath { path, qself: None }) = ty else {
+    return None;
+  };
+  let segments = path
+    .segments
+    .iter()
+    .map(|segment| segment.ident.to_string())
+    .collect::<Vec<_>>();
+  let is_abort_signal = match segments.as_slice() {
+    [name] => name == "AbortSignal",
+    [.., module, name] => {
+      name == "AbortSignal" && (module == "bindgen_prelude" || module == "napi")
+    }
+    [] => false,
+  };
+  if is_abort_signal {
+    return Some(false);
+  }
+  let last = path.segments.last()?;
+  if last.ident != "Option" {
+    return None;
+  }
+  let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments { args, .. }) =
+    &last.arguments
+  else {
+    return None;
+  };
+  match args.first() {
+    Some(syn::GenericArgument::Type(inner)) => abort_signal_arg(inner)
+      .filter(|optional| !optional)
+      .map(|_| true),
+    _ => None,
+  }
+}
+
 fn make_ref(input: TokenStream) -> TokenStream {
   quote! {
     _args_array[_arg_write_index] = _make_ref(
@@ -102,6 +102,7 @@ fn gen_napi_value_map_impl(
       ) -> napi::bindgen_prelude::Result<&'static Self> {
         let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
 
+        napi::bindgen_prelude::check_class_instance::<#name>(env, napi_val, #name_str)?;
         napi::bindgen_prelude::check_status!(
           napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
           "Failed to recover `{}` type from napi value",
@@ -120,6 +121,7 @@ fn gen_napi_value_map_impl(
       ) -> napi::bindgen_prelude::Result<&'static mut Self> {
         let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
 
+        napi::bindgen_prelude::check_class_instance::<#name>(env, napi_val, #name_str)?;
         napi::bindgen_prelude::check_status!(
           napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
           "Failed to recover `{}` type from napi value",
@@ -148,6 +150,7 @@ fn gen_napi_value_map_impl(
       ) -> napi::bindgen_prelude::Result<Self> {
         let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
 
+        napi::bindgen_prelude::check_class_instance::<#name>(env, napi_val, #name_str)?;
         napi::bindgen_prelude::check_status!(
         napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
           "Failed to recover `{}` type from napi value",
@@ -509,10 +512,14 @@ impl NapiStruct {
     let name_str = self.name.to_string();
 
     let mut obj_field_setters = vec![];
+    let mut obj_property_pushers = vec![];
     let mut obj_field_getters = vec![];
     let mut field_destructions = vec![];
+    let mut schema_fields = vec![];
+    let mut schema_field_readers = vec![];
+    let mut schema_field_writers = vec![];
 
-    for field in obj.fields.iter() {
+    for (index, field) in obj.fields.iter().enumerate() {
       let field_js_name = &field.js_name;
       let mut ty = field.ty.clone();
       remove_lifetime_in_type(&mut ty);
@@ -529,38 +536,89 @@ impl NapiStruct {
       } else {
         false
       };
+      let property_key =
+        syn::LitByteStr::new(format!("{}\0", field_js_name).as_bytes(), Span::call_site());
+      let property_key = quote! { napi::bindgen_prelude::object_property_key(#property_key) };
+      let gen_property_pusher = |value: &Ident| match (is_optional_field, self.use_nullable) {
+        (true, false) => quote! {
+          if #value.is_some() {
+            props.push(#property_key, #value)?;
+          }
+        },
+        (true, true) => quote! {
+          if let Some(#value) = #value {
+            props.push(#property_key, #value)?;
+          } else {
+            props.push(#property_key, napi::bindgen_prelude::Null)?;
+          }
+        },
+        (false, _) => quote! { props.push(#property_key, #value)?; },
+      };
+      schema_fields.push(quote! {
+        napi::bindgen_prelude::SchemaField {
+          key: #property_key,
+          name: #field_js_name,
+        }
+      });
+      let is_required_field = !is_optional_field || self.use_nullable;
+      let mut gen_schema_field_accessors = |value: &Ident| {
+        schema_field_readers.push(quote! {
+          let #value: #ty = napi::bindgen_prelude::read_schema_field(
+            env,
+            values[#index],
+            #is_required_field,
+            #name_str,
+            #field_js_name,
+          )?;
+        });
+        schema_field_writers.push(if is_optional_field && !self.use_nullable {
+          quote! {
+            if #value.is_some() {
+              values[#index] = napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #value)?;
+            }
+          }
+        } else {
+          quote! {
+            values[#index] = napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #value)?;
+          }
+        });
+      };
       match &field.name {
         syn::Member::Named(ident) => {
           let alias_ident = format_ident!("{}_", ident);
+          obj_property_pushers.push(gen_property_pusher(&alias_ident));
+          gen_schema_field_accessors(&alias_ident);
           field_destructions.push(quote! { #ident: #alias_ident });
           if is_optional_field {
             obj_field_setters.push(match self.use_nullable {
               false => quote! {
                 if #alias_ident.is_some() {
-                  obj.set(#field_js_name, #alias_ident)?;
+                  obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #alias_ident)?;
                 }
               },
               true => quote! {
                 if let Some(#alias_ident) = #alias_ident {
-                  obj.set(#field_js_name, #alias_ident)?;
+                  obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #alias_ident)?;
                 } else {
-                  obj.set(#field_js_name, napi::bindgen_prelude::Null)?;
+                  obj.set_key(&env_wrapper.intern_key(#field_js_name)?, napi::bindgen_prelude::Null)?;
                 }
               },
             });
           } else {
-            obj_field_setters.push(quote! { obj.set(#field_js_name, #alias_ident)?; });
+            obj_field_setters.push(
+              quote! { obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #alias_ident)?; },
+            );
           }
           if is_optional_field && !self.use_nullable {
             obj_field_getters.push(quote! {
-              let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+              let #alias_ident: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?).map_err(|mut err| {
                 err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                 err
               })?;
             });
           } else {
             obj_field_getters.push(quote! {
-              let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+              let #alias_ident: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?).map_err(|mut err| {
                 err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                 err
               })?.ok_or_else(|| napi::bindgen_prelude::Error::new(
@@ -572,30 +630,33 @@ impl NapiStruct {
         }
         syn::Member::Unnamed(i) => {
           let arg_name = format_ident!("arg{}", i);
+          obj_property_pushers.push(gen_property_pusher(&arg_name));
+          gen_schema_field_accessors(&arg_name);
           field_destructions.push(quote! { #arg_name });
           if is_optional_field {
             obj_field_setters.push(match self.use_nullable {
               false => quote! {
                 if #arg_name.is_some() {
-                  obj.set(#field_js_name, #arg_name)?;
+                  obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #arg_name)?;
                 }
               },
               true => quote! {
                 if let Some(#arg_name) = #arg_name {
-                  obj.set(#field_js_name, #arg_name)?;
+                  obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #arg_name)?;
                 } else {
-                  obj.set(#field_js_name, napi::bindgen_prelude::Null)?;
+                  obj.set_key(&env_wrapper.intern_key(#field_js_name)?, napi::bindgen_prelude::Null)?;
                 }
               },
             });
           } else {
-            obj_field_setters.push(quote! { obj.set(#field_js_name, #arg_name)?; });
+            obj_field_setters
+              .push(quote! { obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #arg_name)?; });
           }
           if is_optional_field && !self.use_nullable {
-            obj_field_getters.push(quote! { let #arg_name: #ty = obj.get(#field_js_name)?; });
+            obj_field_getters.push(quote! { let #arg_name: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?)?; });
           } else {
             obj_field_getters.push(quote! {
-              let #arg_name: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+              let #arg_name: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
                 napi::bindgen_prelude::Status::InvalidArg,
                 format!("Missing field `{}`", #field_js_name),
               ))?;
@@ -620,24 +681,50 @@ impl NapiStruct {
     } else {
       quote! { #name }
     };
-    let (from_napi_value_impl, to_napi_value_impl, validate_napi_value_impl, type_name_impl) =
-      if self.has_lifetime {
-        (
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::FromNapiValue for #name<'_javascript_function_scope> },
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ToNapiValue for #name<'_javascript_function_scope> },
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ValidateNapiValue for #name<'_javascript_function_scope> },
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::TypeName for #name<'_javascript_function_scope> },
-        )
-      } else {
-        (
-          quote! { impl napi::bindgen_prelude::FromNapiValue for #name },
-          quote! { impl napi::bindgen_prelude::ToNapiValue for #name },
-          quote! { impl napi::bindgen_prelude::ValidateNapiValue for #name },
-          quote! { impl napi::bindgen_prelude::TypeName for #name },
-        )
-      };
+    let (
+      from_napi_value_impl,
+      to_napi_value_impl,
+      validate_napi_value_impl,
+      type_name_impl,
+      object_schema_impl,
+    ) = if self.has_lifetime {
+      (
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::FromNapiValue for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ToNapiValue for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ValidateNapiValue for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::TypeName for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ObjectSchema for #name<'_javascript_function_scope> },
+      )
+    } else {
+      (
+        quote! { impl napi::bindgen_prelude::FromNapiValue for #name },
+        quote! { impl napi::bindgen_prelude::ToNapiValue for #name },
+        quote! { impl napi::bindgen_prelude::ValidateNapiValue for #name },
+        quote! { impl napi::bindgen_prelude::TypeName for #name },
+        quote! { impl napi::bindgen_prelude::ObjectSchema for #name },
+      )
+    };
+
+    let to_napi_value = if obj.object_to_js && obj.cache_keys {
+      quote! {
+        #[automatically_derived]
+        #to_napi_value_impl {
+          unsafe fn to_napi_value(env: napi::bindgen_prelude::sys::napi_env, val: #name_with_lifetime) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+            thread_local! {
+              static OBJECT_SCRATCH: napi::bindgen_prelude::ObjectScratch = const { napi::bindgen_prelude::ObjectScratch::new() };
+            }
 
-    let to_napi_value = if obj.object_to_js {
+            let #destructed_fields = val;
+            OBJECT_SCRATCH.with(|scratch| {
+              scratch.create_object(env, |props| {
+                #(#obj_property_pushers)*
+                Ok(())
+              })
+            })
+          }
+        }
+      }
+    } else if obj.object_to_js {
       quote! {
         #[automatically_derived]
         #to_napi_value_impl {
@@ -691,6 +778,41 @@ impl NapiStruct {
       quote! {}
     };
 
+    // the keys are built at compile time, so the fields with nul bytes in their names are not supported
+    let object_schema = if obj.object_to_js
+      && obj.object_from_js
+      && !obj.fields.iter().any(|field| field.js_name.contains('\0'))
+    {
+      quote! {
+        #[automatically_derived]
+        #object_schema_impl {
+          const FIELDS: &'static [napi::bindgen_prelude::SchemaField] = &[#(#schema_fields),*];
+
+          unsafe fn read_fields(
+            env: napi::bindgen_prelude::sys::napi_env,
+            values: &[napi::bindgen_prelude::sys::napi_value],
+          ) -> napi::bindgen_prelude::Result<Self> {
+            #(#schema_field_readers)*
+
+            Ok(#destructed_fields)
+          }
+
+          unsafe fn write_fields(
+            env: napi::bindgen_prelude::sys::napi_env,
+            val: Self,
+            values: &mut [napi::bindgen_prelude::sys::napi_value],
+          ) -> napi::bindgen_prelude::Result<()> {
+            let #destructed_fields = val;
+            #(#schema_field_writers)*
+
+            Ok(())
+          }
+        }
+      }
+    } else {
+      quote! {}
+    };
+
     quote! {
       #[automatically_derived]
       #type_name_impl {
@@ -706,6 +828,8 @@ impl NapiStruct {
       #to_napi_value
 
       #from_napi_value
+
+      #object_schema
     }
   }
 
@@ -899,7 +1023,7 @@ impl NapiStruct {
       let variant_name = &variant.name;
       let variant_name_str = variant_name.to_string();
       let mut obj_field_setters = vec![quote! {
-        obj.set(#discriminant, #variant_name_str)?;
+        obj.set_key(&env_wrapper.intern_key(#discriminant)?, #variant_name_str)?;
       }];
       let mut obj_field_getters = vec![];
       let mut field_destructions = vec![];
@@ -928,30 +1052,32 @@ impl NapiStruct {
               obj_field_setters.push(match self.use_nullable {
                 false => quote! {
                   if #alias_ident.is_some() {
-                    obj.set(#field_js_name, #alias_ident)?;
+                    obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #alias_ident)?;
                   }
                 },
                 true => quote! {
                   if let Some(#alias_ident) = #alias_ident {
-                    obj.set(#field_js_name, #alias_ident)?;
+                    obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #alias_ident)?;
                   } else {
-                    obj.set(#field_js_name, napi::bindgen_prelude::Null)?;
+                    obj.set_key(&env_wrapper.intern_key(#field_js_name)?, napi::bindgen_prelude::Null)?;
                   }
                 },
               });
             } else {
-              obj_field_setters.push(quote! { obj.set(#field_js_name, #alias_ident)?; });
+              obj_field_setters.push(
+                quote! { obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #alias_ident)?; },
+              );
             }
             if is_optional_field && !self.use_nullable {
               obj_field_getters.push(quote! {
-                let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+                let #alias_ident: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?).map_err(|mut err| {
                   err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                   err
                 })?;
               });
             } else {
               obj_field_getters.push(quote! {
-                let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+                let #alias_ident: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?).map_err(|mut err| {
                   err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                   err
                 })?.ok_or_else(|| napi::bindgen_prelude::Error::new(
@@ -968,25 +1094,27 @@ impl NapiStruct {
               obj_field_setters.push(match self.use_nullable {
                 false => quote! {
                   if #arg_name.is_some() {
-                    obj.set(#field_js_name, #arg_name)?;
+                    obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #arg_name)?;
                   }
                 },
                 true => quote! {
                   if let Some(#arg_name) = #arg_name {
-                    obj.set(#field_js_name, #arg_name)?;
+                    obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #arg_name)?;
                   } else {
-                    obj.set(#field_js_name, napi::bindgen_prelude::Null)?;
+                    obj.set_key(&env_wrapper.intern_key(#field_js_name)?, napi::bindgen_prelude::Null)?;
                   }
                 },
               });
             } else {
-              obj_field_setters.push(quote! { obj.set(#field_js_name, #arg_name)?; });
+              obj_field_setters.push(
+                quote! { obj.set_key(&env_wrapper.intern_key(#field_js_name)?, #arg_name)?; },
+              );
             }
             if is_optional_field && !self.use_nullable {
-              obj_field_getters.push(quote! { let #arg_name: #ty = obj.get(#field_js_name)?; });
+              obj_field_getters.push(quote! { let #arg_name: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?)?; });
             } else {
               obj_field_getters.push(quote! {
-              let #arg_name: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+              let #arg_name: #ty = obj.get_key(&env_wrapper.intern_key(#field_js_name)?)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
                 napi::bindgen_prelude::Status::InvalidArg,
                 format!("Missing field `{}`", #field_js_name),
               ))?;
@@ -1051,7 +1179,7 @@ impl NapiStruct {
             let env_wrapper = napi::bindgen_prelude::Env::from(env);
             #[allow(unused_mut)]
             let mut obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
-            let type_: String = obj.get(#discriminant).map_err(|mut err| {
+            let type_: String = obj.get_key(&env_wrapper.intern_key(#discriminant)?).map_err(|mut err| {
               err.reason = format!("{} on {}.{}", err.reason, #name_str, #discriminant);
               err
             })?.ok_or_else(|| napi::bindgen_prelude::Error::new(
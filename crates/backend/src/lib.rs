@@ -101,7 +101,15 @@ pub(crate) static PRIMITIVE_TYPES: &[(&str, (&str, bool, bool))] = &[
   ("RawCString", ("string", false, false)),
   ("str", ("string", false, false)),
   ("Latin1String", ("string", false, false)),
+  ("Latin1Str", ("string", false, false)),
   ("Utf16String", ("string", false, false)),
+  ("Utf16Slice", ("string", false, false)),
+  ("JsStringRef", ("string", false, false)),
+  ("JsStringBuilder", ("string", false, false)),
+  ("OsString", ("string", false, false)),
+  ("OsStr", ("string", false, false)),
+  ("PathBuf", ("string", false, false)),
+  ("Path", ("string", false, false)),
   ("char", ("string", false, false)),
   ("Null", ("null", false, false)),
   ("JsNull", ("null", false, false)),
@@ -8,9 +8,11 @@ use std::{
 mod r#const;
 mod r#enum;
 mod r#fn;
+mod metadata;
 pub(crate) mod r#struct;
 mod r#type;
 
+pub use metadata::METADATA_VERSION;
 use syn::{PathSegment, Type, TypePath, TypeSlice};
 
 #[derive(Default, Debug)]
@@ -143,6 +145,9 @@ static KNOWN_TYPES: LazyLock<HashMap<&'static str, (&'static str, bool, bool)>>
     ("BTreeSet", ("Set<{}>", false, false)),
     ("ArrayBuffer", ("ArrayBuffer", false, false)),
     ("JsArrayBuffer", ("ArrayBuffer", false, false)),
+    ("ResizableArrayBuffer", ("ArrayBuffer", false, false)),
+    ("SharedArrayBuffer", ("SharedArrayBuffer", false, false)),
+    ("SharedMemory", ("SharedArrayBuffer", false, false)),
     ("Int8Array", ("Int8Array", false, false)),
     ("Uint8Array", ("Uint8Array", false, false)),
     ("Uint8ClampedArray", ("Uint8ClampedArray", false, false)),
@@ -155,14 +160,25 @@ static KNOWN_TYPES: LazyLock<HashMap<&'static str, (&'static str, bool, bool)>>
     ("Float64Array", ("Float64Array", false, false)),
     ("BigInt64Array", ("BigInt64Array", false, false)),
     ("BigUint64Array", ("BigUint64Array", false, false)),
+    ("AnyTypedArray", ("Int8Array | Uint8Array | Uint8ClampedArray | Int16Array | Uint16Array | Int32Array | Uint32Array | Float32Array | Float64Array | BigInt64Array | BigUint64Array", false, false)),
     ("DataView", ("DataView", false, false)),
     ("DateTime", ("Date", false, false)),
     ("NaiveDateTime", ("Date", false ,false)),
+    ("OffsetDateTime", ("Date", false, false)),
     ("Date", ("Date", false, false)),
     ("JsDate", ("Date", false, false)),
     ("JsBuffer", ("Buffer", false, false)),
     ("BufferSlice", ("Buffer", false, false)),
+    ("PooledBuffer", ("Buffer", false, false)),
     ("Buffer", ("Buffer", false, false)),
+    ("SharedBytes", ("Buffer", false, false)),
+    ("PinnedBytes", ("ArrayBufferView", false, false)),
+    ("Blob", ("Blob", false, false)),
+    ("ByteReceiver", ("AsyncIterableIterator<Buffer>", false, false)),
+    ("Array1", ("{ data: ArrayBufferView; shape: Array<number> }", false, false)),
+    ("Array2", ("{ data: ArrayBufferView; shape: Array<number> }", false, false)),
+    ("Array3", ("{ data: ArrayBufferView; shape: Array<number> }", false, false)),
+    ("ArrayD", ("{ data: ArrayBufferView; shape: Array<number> }", false, false)),
     ("Vec", ("Array<{}>", false, false)),
     ("Result", ("Error | {}", false, true)),
     ("Error", ("Error", false, false)),
@@ -170,6 +186,7 @@ static KNOWN_TYPES: LazyLock<HashMap<&'static str, (&'static str, bool, bool)>>
     ("JsTypeError", ("TypeError", false, false)),
     ("JsRangeError", ("RangeError", false, false)),
     ("ClassInstance", ("{}", false, false)),
+    ("Frozen", ("Readonly<{}>", false, false)),
     ("Function", ("({}) => {}", true, false)),
     ("FunctionRef", ("({}) => {}", true, false)),
     ("ReadableStream", ("ReadableStream<{}>", false, false)),
@@ -211,6 +228,7 @@ static KNOWN_TYPES: LazyLock<HashMap<&'static str, (&'static str, bool, bool)>>
     ("This", ("this", false, false)),
     ("Rc", ("{}", false, false)),
     ("Arc", ("{}", false, false)),
+    ("Cow", ("{}", false, false)),
     ("Mutex", ("{}", false, false)),
   ]);
 
@@ -475,7 +493,8 @@ pub fn ty_to_ts_type(
         {
           Some((t, false))
         } else if rust_ty == TSFN_RUST_TY {
-          let fatal_tsfn = match args.last() {
+          // `CalleeHandled` is the fifth generic argument, followed by `Weak`
+          let fatal_tsfn = match args.get(4) {
             Some((arg, _)) => arg == "false",
             _ => false,
           };
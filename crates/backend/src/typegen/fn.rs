@@ -13,8 +13,8 @@ pub(crate) struct FnArg {
 }
 
 pub(crate) struct FnArgList {
-  this: Option<FnArg>,
-  args: Vec<FnArg>,
+  pub(crate) this: Option<FnArg>,
+  pub(crate) args: Vec<FnArg>,
   last_required: Option<usize>,
 }
 
@@ -71,28 +71,12 @@ impl ToTypeDef for NapiFn {
       return None;
     }
 
-    let prefix = self.gen_ts_func_prefix();
-    let def = match self.ts_type.as_ref() {
-      Some(ts_type) => format!("{prefix} {name}{ts_type}", name = self.js_name),
-      None => format!(
-        r#"{prefix} {name}{generic}({args}){ret}"#,
-        name = &self.js_name,
-        generic = &self
-          .ts_generic_types
-          .as_ref()
-          .map(|g| format!("<{}>", g))
-          .unwrap_or_default(),
-        args = self
-          .ts_args_type
-          .clone()
-          .unwrap_or_else(|| self.gen_ts_func_args()),
-        ret = self
-          .ts_return_type
-          .clone()
-          .map(|t| format!(": {}", t))
-          .unwrap_or_else(|| self.gen_ts_func_ret()),
-      ),
-    };
+    let def = format!(
+      "{prefix} {name}{signature}",
+      prefix = self.gen_ts_func_prefix(),
+      name = self.js_name,
+      signature = self.gen_ts_signature(),
+    );
 
     Some(TypeDef {
       kind: "fn".to_owned(),
@@ -176,108 +160,133 @@ fn gen_ts_func_arg(pat: &Pat) -> String {
 }
 
 impl NapiFn {
+  /// The TypeScript signature of the function without its name, like `(a: number): number`
+  pub(crate) fn gen_ts_signature(&self) -> String {
+    match self.ts_type.as_ref() {
+      Some(ts_type) => ts_type.clone(),
+      None => format!(
+        r#"{generic}({args}){ret}"#,
+        generic = &self
+          .ts_generic_types
+          .as_ref()
+          .map(|g| format!("<{}>", g))
+          .unwrap_or_default(),
+        args = self
+          .ts_args_type
+          .clone()
+          .unwrap_or_else(|| self.gen_ts_func_args()),
+        ret = self
+          .ts_return_type
+          .clone()
+          .map(|t| format!(": {}", t))
+          .unwrap_or_else(|| self.gen_ts_func_ret()),
+      ),
+    }
+  }
+
   fn gen_ts_func_args(&self) -> String {
-    format!(
-      "{}",
-      self
-        .args
-        .iter()
-        .filter_map(|arg| match &arg.kind {
-          crate::NapiFnArgKind::PatType(path) => {
-            let ty_string = path.ty.to_token_stream().to_string();
-            if ty_string == "Env" {
-              return None;
-            }
-            if let syn::Type::Reference(syn::TypeReference { elem, .. }) = &*path.ty {
-              if let syn::Type::Path(path) = elem.as_ref() {
-                if let Some(PathSegment { ident, .. }) = path.path.segments.last() {
-                  if ident == "Env" {
-                    return None;
-                  }
+    format!("{}", self.gen_ts_func_arg_list())
+  }
+
+  pub(crate) fn gen_ts_func_arg_list(&self) -> FnArgList {
+    self
+      .args
+      .iter()
+      .filter_map(|arg| match &arg.kind {
+        crate::NapiFnArgKind::PatType(path) => {
+          let ty_string = path.ty.to_token_stream().to_string();
+          if ty_string == "Env" {
+            return None;
+          }
+          if let syn::Type::Reference(syn::TypeReference { elem, .. }) = &*path.ty {
+            if let syn::Type::Path(path) = elem.as_ref() {
+              if let Some(PathSegment { ident, .. }) = path.path.segments.last() {
+                if ident == "Env" {
+                  return None;
                 }
               }
             }
-            if let syn::Type::Path(path) = path.ty.as_ref() {
-              if let Some(PathSegment { ident, arguments }) = path.path.segments.last() {
-                if ident == "Reference" || ident == "WeakReference" {
-                  if let Some(parent) = &self.parent {
-                    if let PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
-                      args: angle_bracketed_args,
+          }
+          if let syn::Type::Path(path) = path.ty.as_ref() {
+            if let Some(PathSegment { ident, arguments }) = path.path.segments.last() {
+              if ident == "Reference" || ident == "WeakReference" {
+                if let Some(parent) = &self.parent {
+                  if let PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
+                    args: angle_bracketed_args,
+                    ..
+                  }) = arguments
+                  {
+                    if let Some(syn::GenericArgument::Type(syn::Type::Path(syn::TypePath {
+                      path,
                       ..
-                    }) = arguments
+                    }))) = angle_bracketed_args.first()
                     {
-                      if let Some(syn::GenericArgument::Type(syn::Type::Path(syn::TypePath {
-                        path,
-                        ..
-                      }))) = angle_bracketed_args.first()
-                      {
-                        if let Some(segment) = path.segments.first() {
-                          if *parent == segment.ident {
-                            // If we have a Reference<A> in an impl A block, it shouldn't be an arg
-                            return None;
-                          }
+                      if let Some(segment) = path.segments.first() {
+                        if *parent == segment.ident {
+                          // If we have a Reference<A> in an impl A block, it shouldn't be an arg
+                          return None;
                         }
                       }
                     }
                   }
                 }
-                if ident == "This" || ident == "this" {
-                  if self.kind != FnKind::Normal {
-                    return None;
-                  }
-                  if let PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
-                    args: angle_bracketed_args,
-                    ..
-                  }) = arguments
-                  {
-                    if let Some(syn::GenericArgument::Type(ty)) = angle_bracketed_args.first() {
-                      let (ts_type, _) = ty_to_ts_type(ty, false, false, false);
-                      return Some(FnArg {
-                        arg: "this".to_owned(),
-                        ts_type,
-                        is_optional: false,
-                      });
-                    }
-                  } else {
+              }
+              if ident == "This" || ident == "this" {
+                if self.kind != FnKind::Normal {
+                  return None;
+                }
+                if let PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
+                  args: angle_bracketed_args,
+                  ..
+                }) = arguments
+                {
+                  if let Some(syn::GenericArgument::Type(ty)) = angle_bracketed_args.first() {
+                    let (ts_type, _) = ty_to_ts_type(ty, false, false, false);
                     return Some(FnArg {
                       arg: "this".to_owned(),
-                      ts_type: "this".to_owned(),
+                      ts_type,
                       is_optional: false,
                     });
                   }
-                  return None;
+                } else {
+                  return Some(FnArg {
+                    arg: "this".to_owned(),
+                    ts_type: "this".to_owned(),
+                    is_optional: false,
+                  });
                 }
+                return None;
               }
             }
-
-            let mut path = path.clone();
-            // remove mutability from PatIdent
-            if let Pat::Ident(i) = path.pat.as_mut() {
-              i.mutability = None;
-            }
-
-            let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false, false, false);
-            let ts_type = arg.use_overridden_type_or(|| ts_type);
-            let arg = gen_ts_func_arg(&path.pat);
-            Some(FnArg {
-              arg,
-              ts_type,
-              is_optional,
-            })
           }
-          crate::NapiFnArgKind::Callback(cb) => {
-            let ts_type = arg.use_overridden_type_or(|| gen_callback_type(cb));
-            let arg = cb.pat.to_token_stream().to_string().to_case(Case::Camel);
 
-            Some(FnArg {
-              arg,
-              ts_type,
-              is_optional: false,
-            })
+          let mut path = path.clone();
+          // remove mutability from PatIdent
+          if let Pat::Ident(i) = path.pat.as_mut() {
+            i.mutability = None;
           }
-        })
-        .collect::<FnArgList>()
-    )
+
+          let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false, false, false);
+          let ts_type = arg.use_overridden_type_or(|| ts_type);
+          let arg = gen_ts_func_arg(&path.pat);
+          Some(FnArg {
+            arg,
+            ts_type,
+            is_optional,
+          })
+        }
+        crate::NapiFnArgKind::Callback(cb) => {
+          let ts_type = arg.use_overridden_type_or(|| gen_callback_type(cb));
+          let arg = cb.pat.to_token_stream().to_string().to_case(Case::Camel);
+
+          Some(FnArg {
+            arg,
+            ts_type,
+            is_optional: false,
+          })
+        }
+      })
+      .collect::<FnArgList>()
   }
 
   fn gen_ts_func_prefix(&self) -> &'static str {
@@ -297,7 +306,7 @@ impl NapiFn {
     }
   }
 
-  fn gen_ts_func_ret(&self) -> String {
+  pub(crate) fn gen_ts_func_ret(&self) -> String {
     match self.kind {
       FnKind::Constructor | FnKind::Setter => "".to_owned(),
       FnKind::Factory => self
@@ -330,7 +339,7 @@ impl NapiFn {
           "void".to_owned()
         };
 
-        if self.is_async {
+        if self.is_async || self.thread.is_some() {
           format!(": Promise<{}>", ret)
         } else {
           format!(": {}", ret)
@@ -0,0 +1,309 @@
+use std::fmt::Write;
+
+use quote::ToTokens;
+
+pub use napi_build::metadata::METADATA_VERSION;
+
+use super::{escape_json, ty_to_ts_type, TypeDef};
+use crate::{
+  FnKind, Napi, NapiConst, NapiEnum, NapiEnumValue, NapiFn, NapiImpl, NapiItem, NapiStruct,
+  NapiStructField, NapiStructKind, NapiType,
+};
+
+impl Napi {
+  /// Describe the exported item as a single line JSON object for downstream code generators.
+  ///
+  /// `type_def` is the result of `to_type_def`, its `def` is emitted as `ts_def`.
+  pub fn to_metadata(&self, type_def: Option<&TypeDef>) -> String {
+    let mut json = JsonObject::default();
+    json.number("version", METADATA_VERSION.into());
+    match &self.item {
+      NapiItem::Fn(f) => {
+        json.string("kind", "fn");
+        fn_metadata(&mut json, f);
+      }
+      NapiItem::Struct(s) => {
+        json.string("kind", "struct");
+        struct_metadata(&mut json, s);
+      }
+      NapiItem::Impl(i) => {
+        json.string("kind", "impl");
+        impl_metadata(&mut json, i);
+      }
+      NapiItem::Enum(e) => {
+        json.string("kind", "enum");
+        enum_metadata(&mut json, e);
+      }
+      NapiItem::Const(c) => {
+        json.string("kind", "const");
+        const_metadata(&mut json, c);
+      }
+      NapiItem::Type(t) => {
+        json.string("kind", "type");
+        type_metadata(&mut json, t);
+      }
+    }
+    json.optional_string("ts_def", type_def.map(|def| def.def.as_str()));
+    json.finish()
+  }
+}
+
+fn common_metadata(
+  json: &mut JsonObject,
+  js_name: &str,
+  rust_name: &str,
+  js_mod: Option<&str>,
+  comments: &[String],
+) {
+  json.string("name", js_name);
+  json.string("rust_name", rust_name);
+  json.optional_string("js_mod", js_mod);
+  json.strings("docs", comments.iter().map(|c| c.trim()));
+}
+
+fn fn_metadata(json: &mut JsonObject, f: &NapiFn) {
+  common_metadata(
+    json,
+    &f.js_name,
+    &f.name.to_string(),
+    f.js_mod.as_deref(),
+    &f.comments,
+  );
+  json.string(
+    "fn_kind",
+    match f.kind {
+      FnKind::Normal => "normal",
+      FnKind::Constructor => "constructor",
+      FnKind::Factory => "factory",
+      FnKind::Getter => "getter",
+      FnKind::Setter => "setter",
+    },
+  );
+  json.boolean("is_async", f.is_async);
+  json.optional_string("thread", f.thread.as_deref());
+  json.boolean(
+    "is_static",
+    f.parent.is_some() && f.fn_self.is_none() && f.kind != FnKind::Constructor,
+  );
+  let args = f.gen_ts_func_arg_list();
+  json.objects(
+    "args",
+    args.this.iter().chain(args.args.iter()).map(|arg| {
+      let mut json = JsonObject::default();
+      json.string("name", &arg.arg);
+      json.string("ts_type", &arg.ts_type);
+      json.boolean("optional", arg.is_optional);
+      json.finish()
+    }),
+  );
+  let ts_return = f.ts_return_type.clone().unwrap_or_else(|| {
+    f.gen_ts_func_ret()
+      .trim_start_matches(':')
+      .trim()
+      .to_owned()
+  });
+  json.optional_string(
+    "ts_return_type",
+    (!ts_return.is_empty()).then_some(ts_return.as_str()),
+  );
+  json.optional_string(
+    "rust_return_type",
+    f.ret
+      .as_ref()
+      .map(|ty| ty.to_token_stream().to_string())
+      .as_deref(),
+  );
+}
+
+fn struct_metadata(json: &mut JsonObject, s: &NapiStruct) {
+  common_metadata(
+    json,
+    &s.js_name,
+    &s.name.to_string(),
+    s.js_mod.as_deref(),
+    &s.comments,
+  );
+  let (struct_kind, fields) = match &s.kind {
+    NapiStructKind::Transparent(transparent) => {
+      json.string("rust_type", &transparent.ty.to_token_stream().to_string());
+      ("transparent", &[][..])
+    }
+    NapiStructKind::Class(class) => ("class", &class.fields[..]),
+    NapiStructKind::Object(object) => ("object", &object.fields[..]),
+    NapiStructKind::Array(array) => ("array", &array.fields[..]),
+    NapiStructKind::StructuredEnum(structured_enum) => {
+      json.string("discriminant", &structured_enum.discriminant);
+      json.objects(
+        "variants",
+        structured_enum.variants.iter().map(|variant| {
+          let mut json = JsonObject::default();
+          json.string("name", &variant.name.to_string());
+          json.objects("fields", variant.fields.iter().map(field_metadata));
+          json.finish()
+        }),
+      );
+      ("structured_enum", &[][..])
+    }
+  };
+  json.string("struct_kind", struct_kind);
+  json.objects("fields", fields.iter().map(field_metadata));
+}
+
+fn field_metadata(field: &NapiStructField) -> String {
+  let mut json = JsonObject::default();
+  json.string("name", &field.js_name);
+  json.string("rust_name", &field.name.to_token_stream().to_string());
+  let (ts_type, is_optional) = ty_to_ts_type(&field.ty, false, true, false);
+  json.string("ts_type", field.ts_type.as_deref().unwrap_or(&ts_type));
+  json.string("rust_type", &field.ty.to_token_stream().to_string());
+  json.boolean("optional", is_optional);
+  json.boolean("readonly", !field.setter);
+  json.strings("docs", field.comments.iter().map(|c| c.trim()));
+  json.finish()
+}
+
+fn impl_metadata(json: &mut JsonObject, i: &NapiImpl) {
+  common_metadata(
+    json,
+    &i.js_name,
+    &i.name.to_string(),
+    i.js_mod.as_deref(),
+    &i.comments,
+  );
+  json.objects(
+    "methods",
+    i.items.iter().map(|f| {
+      let mut json = JsonObject::default();
+      fn_metadata(&mut json, f);
+      json.finish()
+    }),
+  );
+}
+
+fn enum_metadata(json: &mut JsonObject, e: &NapiEnum) {
+  common_metadata(
+    json,
+    &e.js_name,
+    &e.name.to_string(),
+    e.js_mod.as_deref(),
+    &e.comments,
+  );
+  json.boolean("is_string_enum", e.is_string_enum);
+  json.objects(
+    "variants",
+    e.variants.iter().map(|variant| {
+      let mut json = JsonObject::default();
+      json.string("name", &variant.name.to_string());
+      match &variant.val {
+        NapiEnumValue::String(value) => json.string("value", value),
+        NapiEnumValue::Number(value) => json.number("value", *value as i64),
+      }
+      json.strings("docs", variant.comments.iter().map(|c| c.trim()));
+      json.finish()
+    }),
+  );
+}
+
+fn const_metadata(json: &mut JsonObject, c: &NapiConst) {
+  common_metadata(
+    json,
+    &c.js_name,
+    &c.name.to_string(),
+    c.js_mod.as_deref(),
+    &c.comments,
+  );
+  json.string(
+    "ts_type",
+    &ty_to_ts_type(&c.type_name, false, false, false).0,
+  );
+  json.string("rust_type", &c.type_name.to_token_stream().to_string());
+}
+
+fn type_metadata(json: &mut JsonObject, t: &NapiType) {
+  common_metadata(
+    json,
+    &t.js_name,
+    &t.name.to_string(),
+    t.js_mod.as_deref(),
+    &t.comments,
+  );
+  json.string("ts_type", &ty_to_ts_type(&t.value, false, false, false).0);
+  json.string("rust_type", &t.value.to_token_stream().to_string());
+}
+
+#[derive(Default)]
+struct JsonObject {
+  buf: String,
+}
+
+impl JsonObject {
+  fn key(&mut self, key: &str) {
+    self.buf.push(if self.buf.is_empty() { '{' } else { ',' });
+    write_json_string(&mut self.buf, key);
+    self.buf.push(':');
+  }
+
+  fn string(&mut self, key: &str, value: &str) {
+    self.key(key);
+    write_json_string(&mut self.buf, value);
+  }
+
+  fn optional_string(&mut self, key: &str, value: Option<&str>) {
+    match value {
+      Some(value) => self.string(key, value),
+      None => {
+        self.key(key);
+        self.buf.push_str("null");
+      }
+    }
+  }
+
+  fn number(&mut self, key: &str, value: i64) {
+    self.key(key);
+    write!(self.buf, "{}", value).unwrap();
+  }
+
+  fn boolean(&mut self, key: &str, value: bool) {
+    self.key(key);
+    self.buf.push_str(if value { "true" } else { "false" });
+  }
+
+  fn strings<'a>(&mut self, key: &str, values: impl Iterator<Item = &'a str>) {
+    self.key(key);
+    self.buf.push('[');
+    for (i, value) in values.enumerate() {
+      if i != 0 {
+        self.buf.push(',');
+      }
+      write_json_string(&mut self.buf, value);
+    }
+    self.buf.push(']');
+  }
+
+  /// `values` are serialized JSON objects
+  fn objects(&mut self, key: &str, values: impl Iterator<Item = String>) {
+    self.key(key);
+    self.buf.push('[');
+    for (i, value) in values.enumerate() {
+      if i != 0 {
+        self.buf.push(',');
+      }
+      self.buf.push_str(&value);
+    }
+    self.buf.push(']');
+  }
+
+  fn finish(mut self) -> String {
+    if self.buf.is_empty() {
+      self.buf.push('{');
+    }
+    self.buf.push('}');
+    self.buf
+  }
+}
+
+fn write_json_string(buf: &mut String, value: &str) {
+  buf.push('"');
+  buf.push_str(&escape_json(value));
+  buf.push('"');
+}
@@ -2,6 +2,7 @@ use std::env;
 
 mod android;
 mod macos;
+pub mod metadata;
 mod wasi;
 mod windows;
 
@@ -0,0 +1,357 @@
+//! Metadata stream of the `#[napi]` exports, for generating GraphQL schemas, FFI stubs or docs
+//! from the same source of truth as the `.d.ts` file.
+//!
+//! Call [`emit_to`] in `build.rs`, the `#[napi]` macro then writes one JSON object per exported item
+//! into `<dir>/<CARGO_PKG_NAME>.jsonl` while compiling the crate:
+//!
+//! ```no_run
+//! // build.rs
+//! napi_build::setup();
+//! napi_build::metadata::emit_to(concat!(env!("CARGO_MANIFEST_DIR"), "/target/napi-metadata"));
+//! ```
+//!
+//! Every object has `version`, `kind` (`fn`, `struct`, `impl`, `enum`, `const` or `type`), `name`, `rust_name`,
+//! `js_mod`, `docs` and `ts_def`, plus the fields specific to the kind, for example `args` and `ts_return_type` of a `fn`.
+//! The stream is complete once the crate is compiled, use [`for_each_item`] in a tool or a dependent crate to consume it.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Version of the metadata stream format, written by the `#[napi]` macro and the newest one this crate understands.
+///
+/// Fields may be added in the same version, a field is only removed or changes its meaning with a new version.
+pub const METADATA_VERSION: u32 = 1;
+
+/// Ask the `#[napi]` macro to write the metadata stream into `dir`.
+///
+/// It must be called from the `build.rs` of the crate which uses `#[napi]`.
+pub fn emit_to<P: AsRef<Path>>(dir: P) {
+  println!(
+    "cargo::rustc-env=NAPI_METADATA_FOLDER={}",
+    dir.as_ref().display()
+  );
+}
+
+/// Call `callback` with every item in the metadata stream at `path`.
+///
+/// Items written by a newer, incompatible version of the macro are returned as an error.
+pub fn for_each_item<P, F>(path: P, mut callback: F) -> io::Result<()>
+where
+  P: AsRef<Path>,
+  F: FnMut(&JsonValue) -> io::Result<()>,
+{
+  let content = fs::read_to_string(path)?;
+  for line in content.lines().filter(|line| !line.trim().is_empty()) {
+    let item = JsonValue::parse(line)?;
+    match item.get("version").and_then(JsonValue::as_u64) {
+      Some(version) if version <= u64::from(METADATA_VERSION) => {}
+      version => {
+        return Err(io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!("Unsupported napi metadata version {:?}", version),
+        ))
+      }
+    }
+    callback(&item)?;
+  }
+  Ok(())
+}
+
+/// Read all the items in the metadata stream at `path`.
+pub fn read_items<P: AsRef<Path>>(path: P) -> io::Result<Vec<JsonValue>> {
+  let mut items = Vec::new();
+  for_each_item(path, |item| {
+    items.push(item.clone());
+    Ok(())
+  })?;
+  Ok(items)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A parsed JSON value of the metadata stream.
+pub enum JsonValue {
+  Null,
+  Bool(bool),
+  Number(f64),
+  String(String),
+  Array(Vec<JsonValue>),
+  /// Keys are kept in the order they are written.
+  Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+  pub fn parse(input: &str) -> io::Result<Self> {
+    let mut parser = Parser {
+      input: input.as_bytes(),
+      pos: 0,
+      depth: 0,
+    };
+    let value = parser.value()?;
+    parser.whitespace();
+    if parser.pos != parser.input.len() {
+      return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+  }
+
+  /// Get the field of an object.
+  pub fn get(&self, key: &str) -> Option<&JsonValue> {
+    match self {
+      JsonValue::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+      _ => None,
+    }
+  }
+
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      JsonValue::String(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  pub fn as_bool(&self) -> Option<bool> {
+    match self {
+      JsonValue::Bool(b) => Some(*b),
+      _ => None,
+    }
+  }
+
+  pub fn as_f64(&self) -> Option<f64> {
+    match self {
+      JsonValue::Number(n) => Some(*n),
+      _ => None,
+    }
+  }
+
+  pub fn as_u64(&self) -> Option<u64> {
+    self
+      .as_f64()
+      .filter(|n| *n >= 0.0 && n.fract() == 0.0)
+      .map(|n| n as u64)
+  }
+
+  pub fn as_array(&self) -> Option<&[JsonValue]> {
+    match self {
+      JsonValue::Array(items) => Some(items),
+      _ => None,
+    }
+  }
+
+  pub fn is_null(&self) -> bool {
+    matches!(self, JsonValue::Null)
+  }
+}
+
+/// The metadata nests a few levels only, deeper input is rejected instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+  input: &'a [u8],
+  pos: usize,
+  depth: usize,
+}
+
+impl Parser<'_> {
+  fn error(&self, message: &str) -> io::Error {
+    io::Error::new(
+      io::ErrorKind::InvalidData,
+      format!("Invalid napi metadata at {}: {}", self.pos, message),
+    )
+  }
+
+  fn whitespace(&mut self) {
+    while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+      self.pos += 1;
+    }
+  }
+
+  fn expect(&mut self, literal: &str) -> io::Result<()> {
+    if self.input[self.pos..].starts_with(literal.as_bytes()) {
+      self.pos += literal.len();
+      Ok(())
+    } else {
+      Err(self.error(&format!("expected `{}`", literal)))
+    }
+  }
+
+  /// Step over the opening bracket of an array or an object.
+  fn enter(&mut self) -> io::Result<()> {
+    self.depth += 1;
+    if self.depth > MAX_DEPTH {
+      return Err(self.error("nested too deeply"));
+    }
+    self.pos += 1;
+    Ok(())
+  }
+
+  /// Step over the closing bracket of an array or an object.
+  fn leave(&mut self) {
+    self.depth -= 1;
+    self.pos += 1;
+  }
+
+  fn value(&mut self) -> io::Result<JsonValue> {
+    self.whitespace();
+    match self.input.get(self.pos) {
+      Some(b'n') => self.expect("null").map(|_| JsonValue::Null),
+      Some(b't') => self.expect("true").map(|_| JsonValue::Bool(true)),
+      Some(b'f') => self.expect("false").map(|_| JsonValue::Bool(false)),
+      Some(b'"') => self.string().map(JsonValue::String),
+      Some(b'[') => {
+        self.enter()?;
+        let mut items = Vec::new();
+        self.whitespace();
+        if self.input.get(self.pos) == Some(&b']') {
+          self.leave();
+          return Ok(JsonValue::Array(items));
+        }
+        loop {
+          items.push(self.value()?);
+          self.whitespace();
+          match self.input.get(self.pos) {
+            Some(b',') => self.pos += 1,
+            Some(b']') => {
+              self.leave();
+              return Ok(JsonValue::Array(items));
+            }
+            _ => return Err(self.error("expected `,` or `]`")),
+          }
+        }
+      }
+      Some(b'{') => {
+        self.enter()?;
+        let mut fields = Vec::new();
+        self.whitespace();
+        if self.input.get(self.pos) == Some(&b'}') {
+          self.leave();
+          return Ok(JsonValue::Object(fields));
+        }
+        loop {
+          self.whitespace();
+          let key = self.string()?;
+          self.whitespace();
+          self.expect(":")?;
+          fields.push((key, self.value()?));
+          self.whitespace();
+          match self.input.get(self.pos) {
+            Some(b',') => self.pos += 1,
+            Some(b'}') => {
+              self.leave();
+              return Ok(JsonValue::Object(fields));
+            }
+            _ => return Err(self.error("expected `,` or `}`")),
+          }
+        }
+      }
+      Some(b'-' | b'0'..=b'9') => self.number(),
+      _ => Err(self.error("unexpected character")),
+    }
+  }
+
+  fn string(&mut self) -> io::Result<String> {
+    self.expect("\"")?;
+    let mut bytes = Vec::new();
+    loop {
+      match self.input.get(self.pos) {
+        Some(b'"') => {
+          self.pos += 1;
+          return String::from_utf8(bytes).map_err(|_| self.error("invalid utf-8"));
+        }
+        Some(b'\\') => {
+          self.pos += 1;
+          let escaped = match self.input.get(self.pos) {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\x08',
+            Some(b'f') => '\x0c',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+              let mut code = self.hex4()?;
+              if (0xD800..0xDC00).contains(&code) {
+                self.pos += 1;
+                self.expect("\\u")?;
+                self.pos -= 1;
+                let low = self.hex4()?;
+                if !(0xDC00..0xE000).contains(&low) {
+                  return Err(self.error("invalid unicode surrogate pair"));
+                }
+                code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+              }
+              char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))?
+            }
+            _ => return Err(self.error("invalid escape")),
+          };
+          self.pos += 1;
+          let mut buf = [0; 4];
+          bytes.extend_from_slice(escaped.encode_utf8(&mut buf).as_bytes());
+        }
+        Some(0..0x20) => return Err(self.error("unescaped control character")),
+        Some(b) => {
+          bytes.push(*b);
+          self.pos += 1;
+        }
+        None => return Err(self.error("unterminated string")),
+      }
+    }
+  }
+
+  /// `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+  fn number(&mut self) -> io::Result<JsonValue> {
+    let start = self.pos;
+    if self.input.get(self.pos) == Some(&b'-') {
+      self.pos += 1;
+    }
+    match self.input.get(self.pos) {
+      Some(b'0') => self.pos += 1,
+      Some(b'1'..=b'9') => {
+        self.digits();
+      }
+      _ => return Err(self.error("invalid number")),
+    }
+    if self.input.get(self.pos) == Some(&b'.') {
+      self.pos += 1;
+      if !self.digits() {
+        return Err(self.error("invalid number"));
+      }
+    }
+    if let Some(b'e' | b'E') = self.input.get(self.pos) {
+      self.pos += 1;
+      if let Some(b'+' | b'-') = self.input.get(self.pos) {
+        self.pos += 1;
+      }
+      if !self.digits() {
+        return Err(self.error("invalid number"));
+      }
+    }
+    std::str::from_utf8(&self.input[start..self.pos])
+      .ok()
+      .and_then(|n| n.parse().ok())
+      .map(JsonValue::Number)
+      .ok_or_else(|| self.error("invalid number"))
+  }
+
+  /// Skip the ASCII digits, returns whether there is any.
+  fn digits(&mut self) -> bool {
+    let start = self.pos;
+    while let Some(b'0'..=b'9') = self.input.get(self.pos) {
+      self.pos += 1;
+    }
+    self.pos != start
+  }
+
+  /// Parse the 4 hex digits after `u`, leaving `pos` on the last digit.
+  fn hex4(&mut self) -> io::Result<u32> {
+    let digits = self
+      .input
+      .get(self.pos + 1..self.pos + 5)
+      .and_then(|digits| std::str::from_utf8(digits).ok())
+      .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+      .ok_or_else(|| self.error("invalid unicode escape"))?;
+    self.pos += 4;
+    Ok(digits)
+  }
+}
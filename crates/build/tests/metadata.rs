@@ -0,0 +1,160 @@
+use std::fs;
+
+use napi_build::metadata::{for_each_item, read_items, JsonValue, METADATA_VERSION};
+
+#[test]
+fn parse_values() {
+  let value = JsonValue::parse(
+    r#" {"name":"add","args":[{"optional":false,"ts_type":"number"}],"js_mod":null,"size":-1.5e2} "#,
+  )
+  .unwrap();
+  assert_eq!(value.get("name").and_then(JsonValue::as_str), Some("add"));
+  assert!(value.get("js_mod").unwrap().is_null());
+  assert_eq!(value.get("size").and_then(JsonValue::as_f64), Some(-150.0));
+  assert_eq!(value.get("size").and_then(JsonValue::as_u64), None);
+  let args = value.get("args").and_then(JsonValue::as_array).unwrap();
+  assert_eq!(args.len(), 1);
+  assert_eq!(
+    args[0].get("optional").and_then(JsonValue::as_bool),
+    Some(false)
+  );
+  assert_eq!(
+    args[0].get("ts_type").and_then(JsonValue::as_str),
+    Some("number")
+  );
+  assert_eq!(value.get("missing"), None);
+}
+
+#[test]
+fn parse_keeps_key_order() {
+  let value = JsonValue::parse(r#"{"b":1,"a":2}"#).unwrap();
+  let JsonValue::Object(fields) = value else {
+    panic!("expected an object");
+  };
+  let keys = fields
+    .iter()
+    .map(|(key, _)| key.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(keys, ["b", "a"]);
+}
+
+#[test]
+fn parse_string_escapes() {
+  let value = JsonValue::parse(r#""a\"b\\c\/d\n\té😀""#).unwrap();
+  assert_eq!(value.as_str(), Some("a\"b\\c/d\n\té😀"));
+  let value = JsonValue::parse(r#""\b\f\r\u0041\u00e9\ud83d\ude00""#).unwrap();
+  assert_eq!(value.as_str(), Some("\x08\x0c\rAé😀"));
+}
+
+#[test]
+fn parse_nested_values() {
+  let value =
+    JsonValue::parse(r#"{"a":[[],{},[{"b":[1,[true,null]]}]],"c":{"d":{"e":"f"}}}"#).unwrap();
+  let a = value.get("a").and_then(JsonValue::as_array).unwrap();
+  assert_eq!(a[0], JsonValue::Array(vec![]));
+  assert_eq!(a[1], JsonValue::Object(vec![]));
+  let b = a[2].as_array().unwrap()[0]
+    .get("b")
+    .and_then(JsonValue::as_array)
+    .unwrap();
+  assert_eq!(b[0].as_u64(), Some(1));
+  assert_eq!(
+    b[1],
+    JsonValue::Array(vec![JsonValue::Bool(true), JsonValue::Null])
+  );
+  assert_eq!(
+    value
+      .get("c")
+      .and_then(|c| c.get("d"))
+      .and_then(|d| d.get("e"))
+      .and_then(JsonValue::as_str),
+    Some("f")
+  );
+
+  let nested = format!("{}{}", "[".repeat(64), "]".repeat(64));
+  assert!(JsonValue::parse(&nested).is_ok());
+  let too_deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+  assert!(JsonValue::parse(&too_deep).is_err());
+}
+
+#[test]
+fn parse_numbers() {
+  for (input, expected) in [
+    ("0", 0.0),
+    ("-0.5", -0.5),
+    ("12", 12.0),
+    ("1e3", 1000.0),
+    ("2.5E-1", 0.25),
+    ("1e+2", 100.0),
+  ] {
+    assert_eq!(
+      JsonValue::parse(input).unwrap().as_f64(),
+      Some(expected),
+      "{input:?}"
+    );
+  }
+}
+
+#[test]
+fn parse_invalid_input() {
+  for input in [
+    "",
+    "{",
+    r#"{"a" 1}"#,
+    "[1,]",
+    r#""unterminated"#,
+    r#""\x""#,
+    "{} {}",
+    "nul",
+    "]",
+    "[1 2]",
+    r#"{"a":1,}"#,
+    r#"{1:2}"#,
+    "-",
+    "01",
+    "1.",
+    ".5",
+    "1e",
+    "+1",
+    "1-2",
+    r#""\u12""#,
+    r#""\ud83d""#,
+    r#""\ud83d\u0041""#,
+    r#""\ude00""#,
+    "\"a\nb\"",
+  ] {
+    assert!(JsonValue::parse(input).is_err(), "{input:?} should fail");
+  }
+}
+
+#[test]
+fn read_metadata_stream() {
+  let dir = std::env::temp_dir().join(format!("napi-build-metadata-{}", std::process::id()));
+  fs::create_dir_all(&dir).unwrap();
+  let file = dir.join("stream.jsonl");
+  fs::write(
+    &file,
+    format!(
+      "{{\"version\":{METADATA_VERSION},\"kind\":\"fn\",\"name\":\"add\"}}\n\n{{\"version\":{METADATA_VERSION},\"kind\":\"const\",\"name\":\"ANSWER\"}}\n"
+    ),
+  )
+  .unwrap();
+  let items = read_items(&file).unwrap();
+  let names = items
+    .iter()
+    .filter_map(|item| item.get("name").and_then(JsonValue::as_str))
+    .collect::<Vec<_>>();
+  assert_eq!(names, ["add", "ANSWER"]);
+
+  fs::write(
+    &file,
+    format!(
+      "{{\"version\":{},\"kind\":\"fn\",\"name\":\"add\"}}\n",
+      METADATA_VERSION + 1
+    ),
+  )
+  .unwrap();
+  let err = for_each_item(&file, |_| Ok(())).unwrap_err();
+  assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+  fs::remove_dir_all(&dir).unwrap();
+}
@@ -1,7 +1,7 @@
 use std::env;
 use std::fs;
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 use napi_derive_backend::{Napi, ToTypeDef};
@@ -10,6 +10,9 @@ static PKG_NAME: LazyLock<String> =
   LazyLock::new(|| env::var("CARGO_PKG_NAME").expect("Expected `CARGO_PKG_NAME` to be set"));
 static TYPE_DEF_FOLDER: LazyLock<Option<String>> =
   LazyLock::new(|| env::var("NAPI_TYPE_DEF_TMP_FOLDER").ok());
+// set by `napi_build::metadata::emit_to`
+static METADATA_FOLDER: LazyLock<Option<String>> =
+  LazyLock::new(|| env::var("NAPI_METADATA_FOLDER").ok());
 
 fn get_type_def_file() -> Option<PathBuf> {
   if let Some(folder) = TYPE_DEF_FOLDER.as_ref() {
@@ -24,12 +27,19 @@ fn get_type_def_file() -> Option<PathBuf> {
   }
 }
 
+fn get_metadata_file() -> Option<PathBuf> {
+  METADATA_FOLDER
+    .as_ref()
+    .map(|folder| PathBuf::from(folder).join(format!("{}.jsonl", &*PKG_NAME)))
+}
+
 pub fn prepare_type_def_file() {
-  remove_existed_def_file();
+  remove_existed_def_file(get_type_def_file());
+  remove_existed_def_file(get_metadata_file());
 }
 
-fn remove_existed_def_file() {
-  if let Some(file) = get_type_def_file() {
+fn remove_existed_def_file(file: Option<PathBuf>) {
+  if let Some(file) = file {
     if file.exists() {
       if let Err(_e) = fs::remove_file(&file) {
         #[cfg(debug_assertions)]
@@ -42,21 +52,116 @@ fn remove_existed_def_file() {
 }
 
 pub fn output_type_def(napi: &Napi) {
-  if let Some(file) = get_type_def_file() {
-    if let Some(type_def) = napi.to_type_def() {
-      fs::OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(&file)
-        .and_then(|file| {
-          let mut writer = BufWriter::<fs::File>::new(file);
-          writer.write_all(type_def.to_string().as_bytes())?;
-          writer.write_all("\n".as_bytes())?;
-          writer.flush()
-        })
-        .unwrap_or_else(|e| {
-          println!("Failed to write type def file: {:?}", e);
-        });
-    }
+  let type_def_file = get_type_def_file();
+  let metadata_file = get_metadata_file();
+  if type_def_file.is_none() && metadata_file.is_none() {
+    return;
+  }
+  let type_def = napi.to_type_def();
+  if let (Some(file), Some(type_def)) = (type_def_file, type_def.as_ref()) {
+    append_line(&file, &type_def.to_string()).unwrap_or_else(|e| {
+      println!("Failed to write type def file: {:?}", e);
+    });
+  }
+  if let Some(file) = metadata_file {
+    append_line(&file, &napi.to_metadata(type_def.as_ref())).unwrap_or_else(|e| {
+      println!("Failed to write metadata file: {:?}", e);
+    });
+  }
+}
+
+fn append_line(file: &Path, line: &str) -> std::io::Result<()> {
+  if let Some(dir) = file.parent() {
+    fs::create_dir_all(dir)?;
+  }
+  fs::OpenOptions::new()
+    .append(true)
+    .create(true)
+    .open(file)
+    .and_then(|file| {
+      let mut writer = BufWriter::<fs::File>::new(file);
+      writer.write_all(line.as_bytes())?;
+      writer.write_all("\n".as_bytes())?;
+      writer.flush()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use napi_build::metadata::{JsonValue, METADATA_VERSION};
+  use napi_derive_backend::{Napi, ToTypeDef};
+  use quote::quote;
+
+  use crate::parser::{attrs::BindgenAttrs, ParseNapi};
+
+  fn metadata(item: proc_macro2::TokenStream) -> JsonValue {
+    let mut item = syn::parse2::<syn::Item>(item).unwrap();
+    let opts: BindgenAttrs = syn::parse2(quote! {}).unwrap();
+    let napi: Napi = item
+      .parse_napi(&mut proc_macro2::TokenStream::new(), &opts)
+      .unwrap();
+    JsonValue::parse(&napi.to_metadata(napi.to_type_def().as_ref())).unwrap()
+  }
+
+  fn string<'a>(item: &'a JsonValue, key: &str) -> Option<&'a str> {
+    item.get(key).and_then(JsonValue::as_str)
+  }
+
+  #[test]
+  fn fn_metadata() {
+    let item = metadata(quote! {
+      #[doc = " Add \"two\" numbers\\"]
+      #[doc = " on a new line"]
+      #[doc = " café 😀"]
+      fn add_numbers(a: u32, b: Option<u32>) -> u32 {
+        a + b.unwrap_or_default()
+      }
+    });
+    assert_eq!(
+      item.get("version").and_then(JsonValue::as_u64),
+      Some(u64::from(METADATA_VERSION))
+    );
+    assert_eq!(string(&item, "kind"), Some("fn"));
+    assert_eq!(string(&item, "name"), Some("addNumbers"));
+    assert_eq!(string(&item, "rust_name"), Some("add_numbers"));
+    assert!(item.get("js_mod").unwrap().is_null());
+    let docs = item.get("docs").and_then(JsonValue::as_array).unwrap();
+    assert_eq!(docs[0].as_str(), Some("Add \"two\" numbers\\"));
+    assert_eq!(docs[1].as_str(), Some("on a new line"));
+    assert_eq!(docs[2].as_str(), Some("café 😀"));
+    assert_eq!(
+      item.get("is_async").and_then(JsonValue::as_bool),
+      Some(false)
+    );
+    let args = item.get("args").and_then(JsonValue::as_array).unwrap();
+    assert_eq!(args.len(), 2);
+    assert_eq!(string(&args[0], "name"), Some("a"));
+    assert_eq!(string(&args[0], "ts_type"), Some("number"));
+    assert_eq!(
+      args[1].get("optional").and_then(JsonValue::as_bool),
+      Some(true)
+    );
+    assert_eq!(string(&item, "ts_return_type"), Some("number"));
+    assert_eq!(string(&item, "rust_return_type"), Some("u32"));
+    assert_eq!(
+      string(&item, "ts_def"),
+      Some("function addNumbers(a: number, b?: number | undefined | null): number")
+    );
+  }
+
+  #[test]
+  fn const_metadata() {
+    let item = metadata(quote! {
+      pub const ANSWER: u32 = 42;
+    });
+    assert_eq!(string(&item, "kind"), Some("const"));
+    assert_eq!(string(&item, "name"), Some("ANSWER"));
+    assert_eq!(
+      item
+        .get("docs")
+        .and_then(JsonValue::as_array)
+        .map(<[_]>::len),
+      Some(0)
+    );
   }
 }
@@ -52,6 +52,10 @@ macro_rules! attrgen {
     $mac! {
       (catch_unwind, CatchUnwind(Span)),
       (async_runtime, AsyncRuntime(Span)),
+      (thread, Thread(Span, String, Span)),
+      (deprecated, Deprecated(Span, String, Span)),
+      (deprecation_warning, DeprecationWarning(Span)),
+      (since, Since(Span, String, Span)),
       (module_exports, ModuleExports(Span)),
       (js_name, JsName(Span, String, Span)),
       (constructor, Constructor(Span)),
@@ -68,6 +72,7 @@ macro_rules! attrgen {
       (object, Object(Span)),
       (object_from_js, ObjectFromJs(Span, Option<bool>), true),
       (object_to_js, ObjectToJs(Span, Option<bool>), true),
+      (cache_keys, CacheKeys(Span)),
       (custom_finalize, CustomFinalize(Span)),
       (namespace, Namespace(Span, String, Span)),
       (iterator, Iterator(Span)),
@@ -80,6 +85,7 @@ macro_rules! attrgen {
       (discriminant, Discriminant(Span, String, Span)),
       (transparent, Transparent(Span)),
       (array, Array(Span)),
+      (multiversion, Multiversion(Span, Vec<Ident>)),
 
       // impl later
       // (inspectable, Inspectable(Span)),
@@ -496,6 +502,13 @@ impl Parse for BindgenAttr {
           }
         });
 
+        (@parser $variant:ident(Span, Vec<Ident>)) => ({
+          let content;
+          syn::parenthesized!(content in input);
+          let idents = syn::punctuated::Punctuated::<AnyIdent, Token![,]>::parse_terminated(&content)?;
+          return Ok(BindgenAttr::$variant(attr_span, idents.into_iter().map(|ident| ident.0).collect()))
+        });
+
         (@parser $variant:ident(Span, Vec<String>, Vec<Span>)) => ({
           input.parse::<Token![=]>()?;
           let (vals, spans) = match input.parse::<syn::ExprArray>() {
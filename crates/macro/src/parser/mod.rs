@@ -592,6 +592,13 @@ fn napi_fn_from_decl(
     ..
   } = sig.clone();
 
+  if parent.is_some() && opts.multiversion().is_some() {
+    bail_span!(
+      ident,
+      "#[napi(multiversion)] can only be applied to free functions"
+    );
+  }
+
   let mut fn_self = None;
   let callback_traits = extract_fn_closure_generics(&generics)?;
 
@@ -867,6 +874,38 @@ fn napi_fn_from_decl(
       bail_span!(sig.ident, "Constructor don't support asynchronous function");
     }
 
+    if let Some((_, span)) = opts.thread() {
+      if asyncness.is_some() {
+        return Err(Diagnostic::span_error(
+          span,
+          "#[napi(thread)] can't be used with async fn",
+        ));
+      }
+      if !matches!(kind, FnKind::Normal) || fn_self.is_some() || opts.module_exports().is_some() {
+        return Err(Diagnostic::span_error(
+          span,
+          "#[napi(thread)] can only be used with fn and static methods without self",
+        ));
+      }
+    }
+
+    if let Some(span) = opts.deprecation_warning() {
+      if opts.deprecated().is_none() {
+        return Err(Diagnostic::span_error(
+          *span,
+          "#[napi(deprecation_warning)] requires #[napi(deprecated = \"...\")]",
+        ));
+      }
+    }
+
+    let mut comments = extract_doc_comments(&attrs);
+    if let Some((since, _)) = opts.since() {
+      comments.push(format!(" @since {}", since));
+    }
+    if let Some((deprecated, _)) = opts.deprecated() {
+      comments.push(format!(" @deprecated {}", deprecated));
+    }
+
     Ok(NapiFn {
       name: ident.clone(),
       js_name,
@@ -876,12 +915,17 @@ fn napi_fn_from_decl(
       is_ret_result,
       is_async: asyncness.is_some(),
       within_async_runtime: opts.async_runtime().is_some(),
+      thread: opts.thread().map(|(t, _)| t.to_owned()),
       vis,
       kind,
       fn_self,
       parent: parent.cloned(),
-      comments: extract_doc_comments(&attrs),
+      comments,
       attrs,
+      deprecation_warning: opts
+        .deprecation_warning()
+        .and(opts.deprecated())
+        .map(|(message, _)| message.to_owned()),
       strict: opts.strict().is_some(),
       return_if_invalid: opts.return_if_invalid().is_some(),
       js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
@@ -935,11 +979,181 @@ impl ParseNapi for syn::ItemFn {
       );
     }
     let napi = self.convert_to_ast(opts);
-    self.to_tokens(tokens);
+    if let Some(features) = opts.multiversion() {
+      expand_multiversion(self, features)?.to_tokens(tokens);
+    } else {
+      self.to_tokens(tokens);
+    }
 
     napi
   }
 }
+
+/// Map the `#[napi(multiversion(...))]` features to the `target_arch` they belong to,
+/// and the name accepted by `#[target_feature]` and the feature detection macros.
+fn multiversion_target_feature(feature: &Ident) -> Option<(&'static str, &'static str)> {
+  let target = match feature.to_string().as_str() {
+    "sse3" => ("x86", "sse3"),
+    "ssse3" => ("x86", "ssse3"),
+    "sse4_1" => ("x86", "sse4.1"),
+    "sse4_2" => ("x86", "sse4.2"),
+    "popcnt" => ("x86", "popcnt"),
+    "lzcnt" => ("x86", "lzcnt"),
+    "bmi1" => ("x86", "bmi1"),
+    "bmi2" => ("x86", "bmi2"),
+    "pclmulqdq" => ("x86", "pclmulqdq"),
+    "sha" => ("x86", "sha"),
+    "avx" => ("x86", "avx"),
+    "avx2" => ("x86", "avx2"),
+    "fma" => ("x86", "fma"),
+    "avx512f" => ("x86", "avx512f"),
+    "avx512bw" => ("x86", "avx512bw"),
+    "avx512vl" => ("x86", "avx512vl"),
+    "neon" => ("aarch64", "neon"),
+    "crc" => ("aarch64", "crc"),
+    "dotprod" => ("aarch64", "dotprod"),
+    "sha2" => ("aarch64", "sha2"),
+    "sha3" => ("aarch64", "sha3"),
+    "sve" => ("aarch64", "sve"),
+    "sve2" => ("aarch64", "sve2"),
+    _ => return None,
+  };
+  Some(target)
+}
+
+/// Expand `#[napi(multiversion(avx2, neon))]`
+///
+/// The body of the function is compiled once without any extra target feature and once per listed feature.
+/// The first listed feature supported by the running CPU is selected when the module is loaded,
+/// and the exported function calls through the selected function pointer.
+fn expand_multiversion(item: &syn::ItemFn, features: &[Ident]) -> BindgenResult<TokenStream> {
+  let sig = &item.sig;
+  if sig.asyncness.is_some() {
+    bail_span!(
+      sig.ident,
+      "#[napi(multiversion)] can't be applied to async fn"
+    );
+  }
+  if !sig.generics.params.is_empty() {
+    bail_span!(
+      sig.generics,
+      "#[napi(multiversion)] can't be applied to fn with generic parameters"
+    );
+  }
+  if features.is_empty() {
+    bail_span!(
+      sig.ident,
+      "#[napi(multiversion)] requires at least one target feature"
+    );
+  }
+
+  let name = &sig.ident;
+  let name_str = name.unraw().to_string();
+  let default_ident = format_ident!("__napi_multiversion_{}_default", name_str);
+  let select_ident = format_ident!("__napi_multiversion_{}_select", name_str);
+  let init_ident = format_ident!("__napi_multiversion_{}_init", name_str);
+  let static_ident = format_ident!("__NAPI_MULTIVERSION_{}", name_str.to_uppercase());
+
+  let mut arg_idents = vec![];
+  let mut arg_types = vec![];
+  for (index, arg) in sig.inputs.iter().enumerate() {
+    match arg {
+      syn::FnArg::Typed(pat) => {
+        arg_idents.push(format_ident!("__arg{}", index));
+        arg_types.push(pat.ty.as_ref().clone());
+      }
+      syn::FnArg::Receiver(r) => {
+        bail_span!(r, "#[napi(multiversion)] can't be applied to methods");
+      }
+    }
+  }
+  let output = &sig.output;
+  let unsafety = &sig.unsafety;
+  let fn_ptr_ty = quote! { unsafe fn(#(#arg_types),*) #output };
+
+  let mut variants = vec![];
+  let mut selections = vec![];
+  for feature in features {
+    let Some((arch, target_feature)) = multiversion_target_feature(feature) else {
+      bail_span!(
+        feature,
+        "unsupported #[napi(multiversion)] target feature `{}`",
+        feature
+      );
+    };
+    let variant_ident = format_ident!("__napi_multiversion_{}_{}", name_str, feature);
+    let (arch_cfg, detect) = if arch == "x86" {
+      (
+        quote! { #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] },
+        quote! { std::is_x86_feature_detected!(#target_feature) },
+      )
+    } else {
+      (
+        quote! { #[cfg(target_arch = "aarch64")] },
+        quote! { std::arch::is_aarch64_feature_detected!(#target_feature) },
+      )
+    };
+    variants.push(quote! {
+      #arch_cfg
+      #[target_feature(enable = #target_feature)]
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #[doc(hidden)]
+      unsafe fn #variant_ident(#(#arg_idents: #arg_types),*) #output {
+        #default_ident(#(#arg_idents),*)
+      }
+    });
+    selections.push(quote! {
+      #arch_cfg
+      if #detect {
+        return #variant_ident as #fn_ptr_ty;
+      }
+    });
+  }
+
+  let attrs = &item.attrs;
+  let vis = &item.vis;
+  let default_inputs = &sig.inputs;
+  let block = &item.block;
+
+  Ok(quote! {
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    #[doc(hidden)]
+    #unsafety fn #default_ident(#default_inputs) #output #block
+
+    #(#variants)*
+
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    static #static_ident: std::sync::OnceLock<#fn_ptr_ty> = std::sync::OnceLock::new();
+
+    #[allow(non_snake_case)]
+    #[allow(clippy::all)]
+    #[doc(hidden)]
+    fn #select_ident() -> #fn_ptr_ty {
+      *#static_ident.get_or_init(|| {
+        #(#selections)*
+        #default_ident as #fn_ptr_ty
+      })
+    }
+
+    #[allow(non_snake_case)]
+    #[allow(clippy::all)]
+    #[cfg(all(not(test), not(target_family = "wasm")))]
+    #[napi::ctor::ctor(crate_path=::napi::ctor)]
+    fn #init_ident() {
+      #select_ident();
+    }
+
+    #(#attrs)*
+    #vis #unsafety fn #name(#(#arg_idents: #arg_types),*) #output {
+      // SAFETY: the target feature of the selected variant is detected on the running CPU
+      unsafe { #select_ident()(#(#arg_idents),*) }
+    }
+  })
+}
+
 impl ParseNapi for syn::ItemStruct {
   fn parse_napi(&mut self, tokens: &mut TokenStream, opts: &BindgenAttrs) -> BindgenResult<Napi> {
     if opts.ts_args_type().is_some()
@@ -967,6 +1181,12 @@ impl ParseNapi for syn::ItemStruct {
     if opts.object().is_some() && opts.custom_finalize().is_some() {
       bail_span!(self, "Custom finalize is not supported for #[napi(object)]");
     }
+    if opts.object().is_none() && opts.cache_keys().is_some() {
+      bail_span!(
+        self,
+        "#[napi(cache_keys)] can only be applied to a #[napi(object)] struct"
+      );
+    }
     let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
 
@@ -1274,11 +1494,18 @@ impl ConvertToAST for syn::ItemStruct {
         object_to_js: opts.object_to_js(),
       })
     } else if opts.object().is_some() {
+      if opts.cache_keys().is_some() && fields.iter().any(|field| field.js_name.contains('\0')) {
+        bail_span!(
+          self,
+          "#[napi(cache_keys)] can't be applied to an object with nul bytes in its field names",
+        )
+      }
       NapiStructKind::Object(NapiObject {
         fields,
         object_from_js: opts.object_from_js(),
         object_to_js: opts.object_to_js(),
         is_tuple,
+        cache_keys: opts.cache_keys().is_some(),
       })
     } else {
       // field lifetime check, JsValue types with lifetime can't be assigned to a field of napi class struct
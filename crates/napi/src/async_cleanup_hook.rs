@@ -1,28 +1,229 @@
+use std::cell::Cell;
+use std::ffi::c_void;
 use std::mem;
+use std::ptr;
+use std::rc::Rc;
+use std::thread::{self, ThreadId};
 
-use crate::{sys, Status};
+use crate::{bindgen_runtime::NAPI_AUTO_LENGTH, check_status, sys, Env, Result, Status};
 
-/// Notice
-/// The hook will be removed if `AsyncCleanupHook` was `dropped`.
-/// If you want keep the hook until node process exited, call the `AsyncCleanupHook::forget`.
-#[repr(transparent)]
-pub struct AsyncCleanupHook(pub(crate) sys::napi_async_cleanup_hook_handle);
+/// The hook registered by `Env::add_async_cleanup_hook_with_handle`.
+///
+/// The hook is removed if it's dropped before the env is torn down,
+/// call [`AsyncCleanupHook::forget`] to keep it until then.
+pub struct AsyncCleanupHook {
+  handle: sys::napi_async_cleanup_hook_handle,
+  // freed by the hook if it's started, or by `Drop` if it's removed
+  data: *mut HookData,
+  started: Rc<Cell<bool>>,
+}
 
 impl AsyncCleanupHook {
   /// Safe to forget it.
   /// Things will be cleanup before process exited.
   pub fn forget(self) {
-    mem::forget(self);
+    let this = mem::ManuallyDrop::new(self);
+    drop(unsafe { ptr::read(&this.started) });
   }
 }
 
 impl Drop for AsyncCleanupHook {
   fn drop(&mut self) {
-    let status = unsafe { sys::napi_remove_async_cleanup_hook(self.0) };
+    // the handle is owned by the `CleanupHandle` once the hook is started
+    if self.started.get() {
+      return;
+    }
+    let status = unsafe { sys::napi_remove_async_cleanup_hook(self.handle) };
     assert!(
       status == sys::Status::napi_ok,
       "Delete async cleanup hook failed: {}",
       Status::from(status)
     );
+    drop(unsafe { Box::from_raw(self.data) });
+  }
+}
+
+struct HookData {
+  // the hook doesn't receive the env, so it's recorded when the hook is added
+  env: sys::napi_env,
+  hook: Box<dyn FnOnce(CleanupHandle)>,
+  started: Rc<Cell<bool>>,
+}
+
+/// Passed to the hook of `Env::add_async_cleanup_hook_with_handle`, the teardown of the env waits until it's completed.
+///
+/// Complete it with [`CleanupHandle::done`] or by dropping it, which can be done on another thread,
+/// for example after a background thread flushes its work.
+pub struct CleanupHandle {
+  handle: sys::napi_async_cleanup_hook_handle,
+  // completes the hook on the JavaScript thread if it's done on another thread
+  tsfn: sys::napi_threadsafe_function,
+  js_thread: ThreadId,
+}
+
+// The handle is only removed on the JavaScript thread, through the threadsafe function from the other threads
+unsafe impl Send for CleanupHandle {}
+
+impl CleanupHandle {
+  /// Signal that the cleanup is completed.
+  pub fn done(self) {}
+}
+
+impl Drop for CleanupHandle {
+  fn drop(&mut self) {
+    if thread::current().id() == self.js_thread || self.tsfn.is_null() {
+      remove_hook(self.handle);
+      if !self.tsfn.is_null() {
+        unsafe {
+          sys::napi_release_threadsafe_function(
+            self.tsfn,
+            sys::ThreadsafeFunctionReleaseMode::abort,
+          )
+        };
+      }
+      return;
+    }
+    unsafe {
+      sys::napi_call_threadsafe_function(
+        self.tsfn,
+        self.handle.cast(),
+        sys::ThreadsafeFunctionCallMode::nonblocking,
+      );
+      sys::napi_release_threadsafe_function(self.tsfn, sys::ThreadsafeFunctionReleaseMode::release);
+    }
+  }
+}
+
+fn remove_hook(handle: sys::napi_async_cleanup_hook_handle) {
+  let status = unsafe { sys::napi_remove_async_cleanup_hook(handle) };
+  debug_assert!(
+    status == sys::Status::napi_ok,
+    "Remove async cleanup hook failed after async cleanup: {}",
+    Status::from(status)
+  );
+}
+
+impl Env {
+  /// Registers `hook` to be run once the current Node.js environment exits, and the teardown waits until
+  /// the [`CleanupHandle`] passed to it is completed, so the addon can flush its background work.
+  ///
+  /// Unlike [`Env::add_env_cleanup_hook`], the cleanup can be completed asynchronously, on any thread.
+  pub fn add_async_cleanup_hook_with_handle<F>(&self, hook: F) -> Result<AsyncCleanupHook>
+  where
+    F: FnOnce(CleanupHandle) + 'static,
+  {
+    let started = Rc::new(Cell::new(false));
+    let data = Box::into_raw(Box::new(HookData {
+      env: self.0,
+      hook: Box::new(hook),
+      started: started.clone(),
+    }));
+    let mut handle = ptr::null_mut();
+    let status =
+      unsafe { sys::napi_add_async_cleanup_hook(self.0, Some(run_hook), data.cast(), &mut handle) };
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Box::from_raw(data) });
+    }
+    check_status!(status, "Add async cleanup hook failed")?;
+    Ok(AsyncCleanupHook {
+      handle,
+      data,
+      started,
+    })
+  }
+
+  /// Registers `cleanup_fn` to be run with `arg` once the current Node.js environment exits.
+  ///
+  /// It's completed as soon as `cleanup_fn` returns, use [`Env::add_async_cleanup_hook_with_handle`] to complete it later.
+  pub fn add_removable_async_cleanup_hook<Arg, F>(
+    &self,
+    arg: Arg,
+    cleanup_fn: F,
+  ) -> Result<AsyncCleanupHook>
+  where
+    F: FnOnce(Arg) + 'static,
+    Arg: 'static,
+  {
+    self.add_async_cleanup_hook_with_handle(move |handle| {
+      cleanup_fn(arg);
+      handle.done();
+    })
+  }
+
+  /// Like [`Env::add_removable_async_cleanup_hook`], but the hook can't be removed.
+  pub fn add_async_cleanup_hook<Arg, F>(&self, arg: Arg, cleanup_fn: F) -> Result<()>
+  where
+    F: FnOnce(Arg) + 'static,
+    Arg: 'static,
+  {
+    self
+      .add_removable_async_cleanup_hook(arg, cleanup_fn)?
+      .forget();
+    Ok(())
+  }
+}
+
+unsafe extern "C" fn run_hook(handle: sys::napi_async_cleanup_hook_handle, data: *mut c_void) {
+  let data = unsafe { Box::from_raw(data.cast::<HookData>()) };
+  data.started.set(true);
+  // the cleanup hooks are called without a handle scope
+  let mut scope = ptr::null_mut();
+  let tsfn = if unsafe { sys::napi_open_handle_scope(data.env, &mut scope) } == sys::Status::napi_ok
+  {
+    let tsfn = create_completion_tsfn(data.env).unwrap_or(ptr::null_mut());
+    unsafe { sys::napi_close_handle_scope(data.env, scope) };
+    tsfn
+  } else {
+    ptr::null_mut()
+  };
+  (data.hook)(CleanupHandle {
+    handle,
+    tsfn,
+    js_thread: thread::current().id(),
+  });
+}
+
+fn create_completion_tsfn(env: sys::napi_env) -> Result<sys::napi_threadsafe_function> {
+  let mut name = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_create_string_utf8(
+      env,
+      c"napi_rs_async_cleanup".as_ptr(),
+      NAPI_AUTO_LENGTH,
+      &mut name,
+    )
+  })?;
+  let mut tsfn = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_threadsafe_function(
+        env,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        name,
+        0,
+        1,
+        ptr::null_mut(),
+        None,
+        ptr::null_mut(),
+        Some(complete_on_js_thread),
+        &mut tsfn,
+      )
+    },
+    "Create the threadsafe function of CleanupHandle failed"
+  )?;
+  Ok(tsfn)
+}
+
+unsafe extern "C" fn complete_on_js_thread(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  _context: *mut c_void,
+  data: *mut c_void,
+) {
+  // the threadsafe function is torn down, it can't be completed anymore
+  if env.is_null() {
+    return;
   }
+  remove_hook(data.cast());
 }
@@ -4,10 +4,12 @@ use std::os::raw::c_void;
 use std::ptr;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
 
+use crate::error_sink::report_error;
 use crate::{
   bindgen_runtime::{PromiseRaw, ToNapiValue},
-  check_status, sys, Env, Error, JsError, Result, Status, Task,
+  check_status, sys, Env, Error, ErrorSink, JsError, Result, Status, Task,
 };
 
 struct AsyncWork<T: Task> {
@@ -15,7 +17,8 @@ struct AsyncWork<T: Task> {
   deferred: sys::napi_deferred,
   value: mem::MaybeUninit<Result<T::Output>>,
   napi_async_work: sys::napi_async_work,
-  status: Rc<AtomicU8>,
+  status: Arc<AtomicU8>,
+  error_sink: Option<Rc<dyn ErrorSink>>,
 }
 
 pub struct AsyncWorkPromise<T> {
@@ -26,7 +29,7 @@ pub struct AsyncWorkPromise<T> {
   /// 0: not started
   /// 1: completed
   /// 2: canceled
-  pub(crate) status: Rc<AtomicU8>,
+  pub(crate) status: Arc<AtomicU8>,
   _phantom: PhantomData<T>,
 }
 
@@ -48,7 +51,8 @@ impl<T> AsyncWorkPromise<T> {
 pub fn run<T: Task>(
   env: sys::napi_env,
   task: T,
-  abort_status: Option<Rc<AtomicU8>>,
+  abort_status: Option<Arc<AtomicU8>>,
+  error_sink: Option<Rc<dyn ErrorSink>>,
 ) -> Result<AsyncWorkPromise<T::JsValue>> {
   let mut undefined = ptr::null_mut();
   check_status!(
@@ -61,13 +65,14 @@ pub fn run<T: Task>(
     unsafe { sys::napi_create_promise(env, &mut deferred, &mut raw_promise) },
     "Create promise failed in async_work::run"
   )?;
-  let task_status = abort_status.unwrap_or_else(|| Rc::new(AtomicU8::new(0)));
+  let task_status = abort_status.unwrap_or_else(|| Arc::new(AtomicU8::new(0)));
   let result = Box::leak(Box::new(AsyncWork {
     inner_task: task,
     deferred,
     value: mem::MaybeUninit::uninit(),
     napi_async_work: ptr::null_mut(),
     status: task_status.clone(),
+    error_sink,
   }));
   check_status!(
     unsafe {
@@ -112,9 +117,10 @@ unsafe extern "C" fn complete<T: Task>(
   status: sys::napi_status,
   data: *mut c_void,
 ) {
+  // the work is dropped in `complete_impl`
+  let error_sink = unsafe { (*(data as *mut AsyncWork<T>)).error_sink.clone() };
   if let Err(e) = complete_impl::<T>(env, status, data) {
-    let js_err = JsError::from(e);
-    unsafe { js_err.throw_into(env) };
+    report_error(&Env::from_raw(env), error_sink.as_deref(), e);
   }
 }
 
@@ -116,6 +116,7 @@ impl<const N: usize> CallbackInfo<N> {
         ),
         "Failed to initialize class `{js_name}`",
       )?;
+      tag_class_instance::<T>(self.env, this)?;
     };
 
     Reference::<T>::add_ref(
@@ -221,6 +222,7 @@ impl<const N: usize> CallbackInfo<N> {
       "Failed to initialize class `{}`",
       js_name,
     )?;
+    unsafe { tag_class_instance::<T>(self.env, instance) }?;
 
     Reference::<T>::add_ref(
       self.env,
@@ -254,6 +256,7 @@ impl<const N: usize> CallbackInfo<N> {
     let mut wrapped_val: *mut c_void = std::ptr::null_mut();
 
     unsafe {
+      check_class_instance::<T>(self.env, self.this, T::type_name())?;
       check_status!(
         sys::napi_unwrap(self.env, self.this, &mut wrapped_val),
         "Failed to unwrap exclusive reference of `{}` type from napi value",
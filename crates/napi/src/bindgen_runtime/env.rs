@@ -1,6 +1,8 @@
-use crate::{sys, JsGlobal, Result};
+use std::ptr;
 
-use super::Array;
+use crate::{check_status, sys, Error, Global, JsGlobal, Result, Status};
+
+use super::{get_module_exports_ref, Array, FromNapiValue, JsObjectValue, Object, ToNapiValue};
 
 pub use crate::Env;
 
@@ -24,4 +26,89 @@ impl Env {
       std::marker::PhantomData,
     ))
   }
+
+  /// The global object with the typed accessors of `process`, `console`, `JSON`, `queueMicrotask` and `structuredClone`.
+  pub fn global_this(&self) -> Result<Global<'_>> {
+    Ok(Global::new(self.get_global()?))
+  }
+
+  /// Emit a warning by `process.emitWarning`, `warning_type` is the name of the warning, like `DeprecationWarning`.
+  pub fn emit_warning(&self, message: &str, warning_type: &str) -> Result<()> {
+    self
+      .global_this()?
+      .process()?
+      .emit_warning(message, warning_type)
+  }
+
+  /// Get the `exports` object of the module in the current env.
+  ///
+  /// It's available once the module is initialized, so you can add, remove or replace exports
+  /// after `napi_register_module_v1` has run, for example to swap implementations at startup.
+  /// The `exports` object is kept alive until the env is torn down.
+  pub fn get_module_exports(&self) -> Result<Object<'_>> {
+    let exports_ref = get_module_exports_ref().ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        "The module exports is not available before the module is initialized",
+      )
+    })?;
+    let mut exports = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.0, exports_ref, &mut exports) },
+      "Get module exports from reference failed"
+    )?;
+    if exports.is_null() {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "The module exports has been garbage collected",
+      ));
+    }
+    unsafe { Object::from_napi_value(self.0, exports) }
+  }
+
+  /// Add or replace an export of the module.
+  ///
+  /// If `js_mod` is provided, the export is set on the `js_mod` namespace object, which is created if it does not exist.
+  ///
+  /// The change is made on the `exports` object, which the generated `index.js` or `index.cjs` re-exports as is,
+  /// so it's visible as a property of the required module. But the ESM named imports of the binding are bound
+  /// when it's loaded, and the generated type definitions only list the `#[napi]` exports: a later export can't
+  /// be imported by name, and a named import keeps the value it had when the binding was loaded.
+  pub fn set_module_export<V: ToNapiValue>(
+    &self,
+    js_mod: Option<&str>,
+    name: &str,
+    value: V,
+  ) -> Result<()> {
+    let mut target = self.get_module_exports()?;
+    if let Some(js_mod) = js_mod {
+      target = match target.get::<Object>(js_mod)? {
+        Some(namespace) => namespace,
+        None => {
+          let namespace = Object::new(self)?;
+          target.set_named_property(js_mod, namespace)?;
+          namespace
+        }
+      };
+    }
+    target.set_named_property(name, value)
+  }
+
+  /// Remove an export of the module.
+  ///
+  /// Returns `false` if the export does not exist.
+  /// Like [`Env::set_module_export`], it's not reflected in the ESM named imports of the binding.
+  pub fn remove_module_export(&self, js_mod: Option<&str>, name: &str) -> Result<bool> {
+    let mut target = self.get_module_exports()?;
+    if let Some(js_mod) = js_mod {
+      match target.get::<Object>(js_mod)? {
+        Some(namespace) => target = namespace,
+        None => return Ok(false),
+      }
+    }
+    if !target.has_own_property(name)? {
+      return Ok(false);
+    }
+    target.delete_named_property(name)
+  }
 }
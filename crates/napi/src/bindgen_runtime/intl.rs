@@ -0,0 +1,206 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use crate::{sys, Env, Error, Result, Status};
+
+use super::{FnArgs, FromNapiValue, Function, JsObjectValue, Object, RefCache, ToNapiValue};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+// The bound `format` and `compare` functions of the created `Intl` instances are cached by their options
+enum IntlOptions {
+  NumberFormat(NumberFormatOptions),
+  Collator(CollatorOptions),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+/// Options of `Intl.NumberFormat`, the fields which are `None` are left to the defaults of the locale.
+pub struct NumberFormatOptions {
+  /// BCP 47 language tags, the default locale of the runtime is used if it's empty
+  pub locales: Vec<String>,
+  /// `decimal`, `percent`, `currency` or `unit`
+  pub style: Option<String>,
+  /// ISO 4217 currency code, required by the `currency` style
+  pub currency: Option<String>,
+  /// Unit identifier, required by the `unit` style
+  pub unit: Option<String>,
+  /// `standard`, `scientific`, `engineering` or `compact`
+  pub notation: Option<String>,
+  pub minimum_fraction_digits: Option<u32>,
+  pub maximum_fraction_digits: Option<u32>,
+  pub use_grouping: Option<bool>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+/// Options of `Intl.Collator`, the fields which are `None` are left to the defaults of the locale.
+pub struct CollatorOptions {
+  /// BCP 47 language tags, the default locale of the runtime is used if it's empty
+  pub locales: Vec<String>,
+  /// `sort` or `search`
+  pub usage: Option<String>,
+  /// `base`, `accent`, `case` or `variant`
+  pub sensitivity: Option<String>,
+  /// Compare the digits as numbers, so `"2" < "10"`
+  pub numeric: Option<bool>,
+  pub ignore_punctuation: Option<bool>,
+  /// `upper`, `lower` or `false`
+  pub case_first: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+/// A locale-aware number formatter backed by `Intl.NumberFormat`, created by [`Env::number_format`].
+pub struct NumberFormat<'env> {
+  env: sys::napi_env,
+  format: sys::napi_value,
+  _scope: PhantomData<&'env ()>,
+}
+
+impl NumberFormat<'_> {
+  /// Format `value` like `Intl.NumberFormat.prototype.format`.
+  pub fn format(&self, value: f64) -> Result<String> {
+    let format = unsafe { Function::<f64, String>::from_napi_value(self.env, self.format)? };
+    format.call(value)
+  }
+}
+
+#[derive(Clone, Copy)]
+/// A locale-aware string comparator backed by `Intl.Collator`, created by [`Env::collator`].
+pub struct Collator<'env> {
+  env: sys::napi_env,
+  compare: sys::napi_value,
+  _scope: PhantomData<&'env ()>,
+}
+
+impl Collator<'_> {
+  /// Compare `a` and `b` like `Intl.Collator.prototype.compare`.
+  pub fn compare(&self, a: &str, b: &str) -> Result<Ordering> {
+    let compare =
+      unsafe { Function::<FnArgs<(&str, &str)>, i32>::from_napi_value(self.env, self.compare)? };
+    Ok(compare.call((a, b).into())?.cmp(&0))
+  }
+
+  /// Sort `items` in the collation order, the sort is stable.
+  ///
+  /// The comparisons stop at the first error, which is returned with `items` partially sorted.
+  pub fn sort<S: AsRef<str>>(&self, items: &mut [S]) -> Result<()> {
+    let mut error = None;
+    items.sort_by(|a, b| {
+      if error.is_some() {
+        return Ordering::Equal;
+      }
+      self.compare(a.as_ref(), b.as_ref()).unwrap_or_else(|err| {
+        error = Some(err);
+        Ordering::Equal
+      })
+    });
+    error.map_or(Ok(()), Err)
+  }
+}
+
+impl Env {
+  /// Get an `Intl.NumberFormat` for `options`.
+  ///
+  /// The instances are cached per env and options, so calling it repeatedly with the same options is cheap.
+  pub fn number_format(&self, options: &NumberFormatOptions) -> Result<NumberFormat<'_>> {
+    let format = self.get_or_create_intl(IntlOptions::NumberFormat(options.clone()))?;
+    Ok(NumberFormat {
+      env: self.0,
+      format,
+      _scope: PhantomData,
+    })
+  }
+
+  /// Get an `Intl.Collator` for `options`.
+  ///
+  /// The instances are cached per env and options, so calling it repeatedly with the same options is cheap.
+  pub fn collator(&self, options: &CollatorOptions) -> Result<Collator<'_>> {
+    let compare = self.get_or_create_intl(IntlOptions::Collator(options.clone()))?;
+    Ok(Collator {
+      env: self.0,
+      compare,
+      _scope: PhantomData,
+    })
+  }
+
+  fn get_or_create_intl(&self, options: IntlOptions) -> Result<sys::napi_value> {
+    RefCache::get_or_create(self, options.clone(), || self.create_intl(&options))
+  }
+
+  fn create_intl(&self, options: &IntlOptions) -> Result<sys::napi_value> {
+    let (class_name, method, locales, options) = match options {
+      IntlOptions::NumberFormat(options) => (
+        "NumberFormat",
+        "format",
+        &options.locales,
+        self.number_format_options(options)?,
+      ),
+      IntlOptions::Collator(options) => (
+        "Collator",
+        "compare",
+        &options.locales,
+        self.collator_options(options)?,
+      ),
+    };
+    let intl = self
+      .get_global()?
+      .get_named_property_unchecked::<Object>("Intl")
+      .map_err(|_| Error::new(Status::GenericFailure, "Intl is not available"))?;
+    let class =
+      intl.get_named_property_unchecked::<Function<FnArgs<(Vec<String>, Object)>>>(class_name)?;
+    let instance = unsafe {
+      class
+        .new_instance((locales.clone(), options).into())?
+        .cast::<Object>()?
+    };
+    // the getter returns the function bound to the instance
+    Ok(
+      instance
+        .get_named_property_unchecked::<Function>(method)?
+        .value,
+    )
+  }
+
+  fn number_format_options(&self, options: &NumberFormatOptions) -> Result<Object<'_>> {
+    let mut object = Object::new(self)?;
+    set_if_some(&mut object, "style", &options.style)?;
+    set_if_some(&mut object, "currency", &options.currency)?;
+    set_if_some(&mut object, "unit", &options.unit)?;
+    set_if_some(&mut object, "notation", &options.notation)?;
+    set_if_some(
+      &mut object,
+      "minimumFractionDigits",
+      &options.minimum_fraction_digits,
+    )?;
+    set_if_some(
+      &mut object,
+      "maximumFractionDigits",
+      &options.maximum_fraction_digits,
+    )?;
+    set_if_some(&mut object, "useGrouping", &options.use_grouping)?;
+    Ok(object)
+  }
+
+  fn collator_options(&self, options: &CollatorOptions) -> Result<Object<'_>> {
+    let mut object = Object::new(self)?;
+    set_if_some(&mut object, "usage", &options.usage)?;
+    set_if_some(&mut object, "sensitivity", &options.sensitivity)?;
+    set_if_some(&mut object, "numeric", &options.numeric)?;
+    set_if_some(
+      &mut object,
+      "ignorePunctuation",
+      &options.ignore_punctuation,
+    )?;
+    set_if_some(&mut object, "caseFirst", &options.case_first)?;
+    Ok(object)
+  }
+}
+
+fn set_if_some<V: ToNapiValue + Clone>(
+  object: &mut Object,
+  name: &str,
+  value: &Option<V>,
+) -> Result<()> {
+  match value {
+    Some(value) => object.set_named_property(name, value.clone()),
+    None => Ok(()),
+  }
+}
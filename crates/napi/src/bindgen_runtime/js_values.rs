@@ -10,21 +10,40 @@ mod array;
 mod arraybuffer;
 #[cfg(feature = "napi6")]
 mod bigint;
+mod binary_fmt;
+mod blob;
 mod boolean;
 mod buffer;
+mod buffer_encoding;
+mod buffer_pool;
+mod callback_scope;
 mod class;
+mod data_view;
 #[cfg(all(feature = "chrono_date", feature = "napi5"))]
 mod date;
 mod either;
 mod external;
+mod external_memory;
+#[cfg(feature = "napi6")]
+mod frozen;
 mod function;
+#[cfg(feature = "napi7")]
+mod loaned_buffer;
 mod map;
+mod memory_sync;
+#[cfg(feature = "ndarray")]
+mod ndarray_interop;
 mod nil;
 mod number;
 mod object;
+mod object_schema;
+mod object_scratch;
+mod path;
+mod pinned_bytes;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 mod promise;
 mod promise_raw;
+mod property_key;
 mod scope;
 #[cfg(feature = "serde-json")]
 mod serde;
@@ -34,7 +53,14 @@ mod stream;
 mod string;
 mod symbol;
 mod task;
+#[cfg(all(feature = "time_date", feature = "napi5"))]
+mod time_date;
+#[cfg(feature = "napi8")]
+mod type_tag;
+mod typed_array_chunks;
 mod value_ref;
+#[cfg(feature = "napi8")]
+mod weak_cache;
 
 pub use crate::js_values::Unknown;
 #[cfg(feature = "napi5")]
@@ -43,23 +69,44 @@ pub use array::*;
 pub use arraybuffer::*;
 #[cfg(feature = "napi6")]
 pub use bigint::*;
+pub use binary_fmt::*;
+pub use blob::*;
 pub use buffer::*;
+pub use buffer_encoding::*;
+pub use buffer_pool::*;
+pub use callback_scope::*;
 pub use class::*;
+pub use data_view::*;
 pub use either::*;
 pub use external::*;
+pub use external_memory::*;
+#[cfg(feature = "napi6")]
+pub use frozen::*;
 pub use function::*;
+#[cfg(feature = "napi7")]
+pub use loaned_buffer::*;
+pub use memory_sync::*;
 pub use nil::*;
 pub use object::*;
+pub use object_schema::*;
+pub use object_scratch::*;
+pub use pinned_bytes::*;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 pub use promise::*;
 pub use promise_raw::*;
+pub use property_key::*;
 pub use scope::*;
 #[cfg(feature = "web_stream")]
 pub use stream::*;
 pub use string::*;
 pub use symbol::*;
 pub use task::*;
+#[cfg(feature = "napi8")]
+pub use type_tag::*;
+pub use typed_array_chunks::*;
 pub use value_ref::*;
+#[cfg(feature = "napi8")]
+pub use weak_cache::*;
 
 pub trait TypeName {
   fn type_name() -> &'static str;
@@ -73,6 +120,16 @@ pub trait ToNapiValue: Sized {
   /// this function called to convert rust values to napi values
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value>;
 
+  #[doc(hidden)]
+  /// The arguments of a `ThreadsafeFunction` call, a tuple is spread into one argument per element
+  ///
+  /// # Safety
+  ///
+  /// this function called to convert rust values to napi values
+  unsafe fn to_napi_args(env: sys::napi_env, val: Self) -> Result<Vec<sys::napi_value>> {
+    Ok(vec![unsafe { Self::to_napi_value(env, val)? }])
+  }
+
   fn into_unknown(self, env: &Env) -> Result<Unknown> {
     let napi_val = unsafe { Self::to_napi_value(env.0, self)? };
     Ok(Unknown(
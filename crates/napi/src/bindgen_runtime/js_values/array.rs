@@ -398,6 +398,12 @@ macro_rules! impl_to_tuple {
 
         unsafe { Array::to_napi_value(env, arr) }
       }
+
+      unsafe fn to_napi_args(env: sys::napi_env, val: Self) -> Result<Vec<sys::napi_value>> {
+        #[allow(non_snake_case)]
+        let ($($typs,)*) = val;
+        Ok(vec![$(unsafe { <$typs as ToNapiValue>::to_napi_value(env, $typs)? }),*])
+      }
     }
   };
 }
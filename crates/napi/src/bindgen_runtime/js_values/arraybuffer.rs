@@ -1,14 +1,31 @@
-use std::ffi::{c_void, CString};
+use std::ffi::{c_void, CStr, CString};
 use std::marker::PhantomData;
+use std::mem::MaybeUninit;
 use std::ops::{Deref, DerefMut};
 use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicI16, AtomicI32, AtomicI8, AtomicU16, AtomicU32, AtomicU8};
+#[cfg(target_has_atomic = "64")]
+use std::sync::atomic::{AtomicI64, AtomicU64};
+#[cfg(feature = "napi4")]
+use std::sync::Arc;
 use std::{mem, slice};
 
-#[cfg(all(debug_assertions, not(windows)))]
+use super::external_memory::adjust_external_memory;
+#[cfg(target_family = "wasm")]
+use super::memory_sync::emnapi_sync_memory;
+#[cfg(feature = "napi4")]
+use super::value_ref::RawReferenceSendGuard;
+#[cfg(not(feature = "minimal"))]
 use crate::bindgen_prelude::{register_backing_ptr, unregister_backing_ptr};
+#[cfg(feature = "napi7")]
+use crate::bindgen_runtime::slice_borrow_count;
 use crate::{
   bindgen_prelude::{
-    FromNapiValue, JsObjectValue, JsValue, This, ToNapiValue, TypeName, ValidateNapiValue,
+    Buffer, BufferSlice, FromNapiValue, Function, JsObjectValue, JsValue, Object, This,
+    ToNapiValue, TypeName, ValidateNapiValue,
+  },
+  bindgen_runtime::{
+    as_bytes, external_buffers_disallowed, impl_binary_fmt, track_slice_borrow, TypedArrayChunks,
   },
   check_status, sys, Env, Error, Result, Status, Value, ValueType,
 };
@@ -88,22 +105,14 @@ impl From<TypedArrayType> for sys::napi_typedarray_type {
   }
 }
 
-#[cfg(target_family = "wasm")]
-extern "C" {
-  fn emnapi_sync_memory(
-    env: crate::sys::napi_env,
-    js_to_wasm: bool,
-    arraybuffer_or_view: crate::sys::napi_value,
-    byte_offset: usize,
-    length: usize,
-  ) -> crate::sys::napi_status;
-}
-
 #[derive(Clone, Copy)]
 /// Represents a JavaScript ArrayBuffer
 pub struct ArrayBuffer<'env> {
   pub(crate) value: Value,
-  pub(crate) data: &'env [u8],
+  // a raw pointer rather than a `&[u8]`, the bytes are written through `DerefMut`
+  data: NonNull<u8>,
+  len: usize,
+  _marker: PhantomData<&'env ()>,
 }
 
 impl<'env> JsValue<'env> for ArrayBuffer<'env> {
@@ -126,26 +135,53 @@ impl FromNapiValue for ArrayBuffer<'_> {
     check_status!(unsafe {
       sys::napi_get_arraybuffer_info(env, napi_val, &mut data, &mut byte_length)
     })?;
-    Ok(ArrayBuffer {
-      value,
-      data: if data.is_null() {
-        &[]
-      } else {
-        unsafe { std::slice::from_raw_parts(data as *const u8, byte_length) }
-      },
-    })
+    Ok(ArrayBuffer::from_raw_parts(value, data.cast(), byte_length))
   }
 }
 
+impl_binary_fmt!(ArrayBuffer<'_>, "ArrayBuffer", |this| ownership: "JavaScript", length: this.len, bytes: &**this);
+
 impl Deref for ArrayBuffer<'_> {
   type Target = [u8];
 
   fn deref(&self) -> &Self::Target {
-    self.data
+    unsafe { slice::from_raw_parts(self.data.as_ptr(), self.len) }
+  }
+}
+
+impl AsMut<[u8]> for ArrayBuffer<'_> {
+  /// With the `napi7` feature, the `ArrayBuffer` detached by JavaScript yields an empty slice,
+  /// use [`ArrayBuffer::detach_guard`] to get an error instead.
+  fn as_mut(&mut self) -> &mut [u8] {
+    #[cfg(feature = "napi7")]
+    if self.is_detached().unwrap_or(true) {
+      self.data = NonNull::dangling();
+      self.len = 0;
+    }
+    unsafe { slice::from_raw_parts_mut(self.data.as_ptr(), self.len) }
+  }
+}
+
+impl DerefMut for ArrayBuffer<'_> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.as_mut()
   }
 }
 
 impl<'env> ArrayBuffer<'env> {
+  /// `data` may be null for the empty or detached `ArrayBuffer`.
+  fn from_raw_parts(value: Value, data: *mut u8, len: usize) -> Self {
+    Self {
+      value,
+      data: match NonNull::new(data) {
+        Some(data) if len != 0 => data,
+        _ => NonNull::dangling(),
+      },
+      len: if data.is_null() { 0 } else { len },
+      _marker: PhantomData,
+    }
+  }
+
   /// Create a new `ArrayBuffer` from a `Vec<u8>`.
   pub fn from_data<D: Into<Vec<u8>>>(env: &Env, data: D) -> Result<Self> {
     let mut buf = ptr::null_mut();
@@ -154,11 +190,7 @@ impl<'env> ArrayBuffer<'env> {
     let len = data.len();
 
     // Tell V8 how many bytes live outside the JS heap
-    let mut _dummy = 0;
-    check_status!(
-      unsafe { sys::napi_adjust_external_memory(env.0, len as i64, &mut _dummy) },
-      "adjust external memory"
-    )?;
+    adjust_external_memory(env.0, len as i64)?;
 
     let mut status = unsafe {
       let cap = data.capacity();
@@ -172,7 +204,7 @@ impl<'env> ArrayBuffer<'env> {
       )
     };
 
-    if status == napi_sys::Status::napi_no_external_buffers_allowed {
+    if external_buffers_disallowed(status) {
       let mut inner_data = unsafe { Vec::from_raw_parts(inner_ptr, len, len) };
       let mut underlying_data = ptr::null_mut();
       status = unsafe { sys::napi_create_arraybuffer(env.0, len, &mut underlying_data, &mut buf) };
@@ -182,26 +214,20 @@ impl<'env> ArrayBuffer<'env> {
       inner_ptr = underlying_data.cast(); // <- this is the real backing store now
     } else {
       mem::forget(data); // JS owns the original Vec’s memory
+      #[cfg(not(feature = "minimal"))]
+      register_backing_ptr(inner_ptr, len);
     }
     check_status!(status, "Failed to create buffer slice from data")?;
 
-    #[cfg(all(debug_assertions, not(windows)))]
-    if len != 0 {
-      register_backing_ptr(inner_ptr);
-    }
-
-    Ok(Self {
-      value: Value {
+    Ok(Self::from_raw_parts(
+      Value {
         env: env.0,
         value: buf,
         value_type: ValueType::Object,
       },
-      data: if len == 0 {
-        &[]
-      } else {
-        unsafe { slice::from_raw_parts(inner_ptr.cast(), len) }
-      },
-    })
+      inner_ptr.cast(),
+      len,
+    ))
   }
 
   /// ## Safety
@@ -251,7 +277,7 @@ impl<'env> ArrayBuffer<'env> {
       )
     };
 
-    if status == sys::Status::napi_no_external_buffers_allowed {
+    if external_buffers_disallowed(status) {
       let (hint, finalize) = *Box::from_raw(hint_ptr);
       status = unsafe {
         sys::napi_create_arraybuffer(
@@ -270,64 +296,182 @@ impl<'env> ArrayBuffer<'env> {
     check_status!(status, "Failed to create arraybuffer from data")?;
 
     // decide which pointer is really alive
-    let backing_ptr = if status == sys::Status::napi_no_external_buffers_allowed {
-      underlying_data.cast::<u8>()
-    } else {
+    let backing_ptr = if underlying_data.is_null() {
+      #[cfg(not(feature = "minimal"))]
+      register_backing_ptr(data, len);
       data
+    } else {
+      // the copy of the Electron fallback is owned by JavaScript
+      underlying_data.cast::<u8>()
     };
 
-    #[cfg(all(debug_assertions, not(windows)))]
-    register_backing_ptr(backing_ptr);
-
-    Ok(Self {
-      value: Value {
+    Ok(Self::from_raw_parts(
+      Value {
         env: env.0,
         value: arraybuffer_value,
         value_type: ValueType::Object,
       },
-      data: if len == 0 {
-        &[]
-      } else {
-        unsafe { std::slice::from_raw_parts(backing_ptr, len) }
+      backing_ptr,
+      len,
+    ))
+  }
+
+  #[cfg(feature = "bytes")]
+  /// Create an `ArrayBuffer` sharing the memory of `bytes::Bytes` without copying.
+  ///
+  /// The `Bytes` handle is dropped when the `ArrayBuffer` is garbage collected,
+  /// so the memory is released only after the last `Bytes` handle is gone.
+  ///
+  /// JavaScript writes to the `ArrayBuffer` are visible to all the `Bytes` sharing the memory,
+  /// the data is copied in the runtimes that don't support external buffers.
+  pub fn from_bytes(env: &Env, bytes: bytes::Bytes) -> Result<Self> {
+    if bytes.is_empty() {
+      return Self::copy_from(env, []);
+    }
+    let len = bytes.len();
+    let data = bytes.as_ptr();
+    let bytes = Box::into_raw(Box::new(bytes));
+    let mut arraybuffer_value = ptr::null_mut();
+    let status = unsafe {
+      sys::napi_create_external_arraybuffer(
+        env.0,
+        data.cast_mut().cast(),
+        len,
+        Some(finalize_bytes),
+        bytes.cast(),
+        &mut arraybuffer_value,
+      )
+    };
+    if external_buffers_disallowed(status) {
+      let bytes = unsafe { Box::from_raw(bytes) };
+      return Self::copy_from(env, bytes.as_ref());
+    }
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Box::from_raw(bytes) });
+    }
+    check_status!(status, "Failed to create ArrayBuffer from Bytes")?;
+
+    Ok(Self::from_raw_parts(
+      Value {
+        env: env.0,
+        value: arraybuffer_value,
+        value_type: ValueType::Object,
       },
-    })
+      data.cast_mut(),
+      len,
+    ))
   }
 
   /// Copy data from a `&[u8]` and create a `ArrayBuffer` from it.
   pub fn copy_from<D: AsRef<[u8]>>(env: &Env, data: D) -> Result<Self> {
     let data = data.as_ref();
-    let len = data.len();
-    let mut arraybuffer_value = ptr::null_mut();
-    let mut underlying_data = ptr::null_mut();
+    let mut buffer = Self::new_uninit(env, data.len())?;
+    buffer.copy_from_slice_at(0, data)?;
+    Ok(unsafe { buffer.assume_init() })
+  }
 
+  /// Create an `ArrayBuffer` of `byte_length` bytes for Rust to fill before exposing it to JavaScript.
+  ///
+  /// The memory is allocated by the engine, so no `Vec` is allocated and copied like [`ArrayBuffer::copy_from`].
+  /// Fill it with [`UninitArrayBuffer::copy_from_slice_at`] or [`UninitArrayBuffer::as_uninit_mut`] and then call [`UninitArrayBuffer::assume_init`].
+  pub fn new_uninit(env: &Env, byte_length: usize) -> Result<UninitArrayBuffer<'env>> {
+    let mut value = ptr::null_mut();
+    let mut data = ptr::null_mut();
     check_status!(
-      unsafe {
-        sys::napi_create_arraybuffer(env.0, len, &mut underlying_data, &mut arraybuffer_value)
-      },
+      unsafe { sys::napi_create_arraybuffer(env.0, byte_length, &mut data, &mut value) },
       "Failed to create ArrayBuffer"
     )?;
-
-    Ok(Self {
+    Ok(UninitArrayBuffer {
       value: Value {
         env: env.0,
-        value: arraybuffer_value,
+        value,
         value_type: ValueType::Object,
       },
-      data: if len == 0 {
-        &[]
+      data: if byte_length == 0 || data.is_null() {
+        &mut []
       } else {
-        unsafe { std::slice::from_raw_parts(underlying_data.cast(), len) }
+        unsafe { slice::from_raw_parts_mut(data.cast(), byte_length) }
       },
     })
   }
 
+  /// Create a new zero-filled resizable `ArrayBuffer` of `byte_length` bytes, which can be resized up to `max_byte_length` bytes.
+  ///
+  /// The same as `new ArrayBuffer(byteLength, { maxByteLength })` in JavaScript.
+  pub fn new_resizable(
+    env: &Env,
+    byte_length: usize,
+    max_byte_length: usize,
+  ) -> Result<ResizableArrayBuffer<'env>> {
+    if byte_length > max_byte_length {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "ArrayBuffer byte length {} exceeds the max byte length {}",
+          byte_length, max_byte_length
+        ),
+      ));
+    }
+    let constructor = get_global_constructor(env.0, c"ArrayBuffer")?;
+    let mut options = Object::new(env)?;
+    options.set("maxByteLength", max_byte_length as f64)?;
+    let args = [
+      unsafe { ToNapiValue::to_napi_value(env.0, byte_length as f64) }?,
+      options.0.value,
+    ];
+    let mut value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_new_instance(env.0, constructor, 2, args.as_ptr(), &mut value) },
+      "Create resizable ArrayBuffer failed"
+    )?;
+    Ok(ResizableArrayBuffer {
+      value: Value {
+        env: env.0,
+        value,
+        value_type: ValueType::Object,
+      },
+      _marker: PhantomData,
+    })
+  }
+
+  /// Whether the `ArrayBuffer` is created with `maxByteLength`.
+  pub fn is_resizable(&self) -> Result<bool> {
+    // engines without resizable `ArrayBuffer` don't have the property
+    Ok(
+      self
+        .get_named_property_unchecked::<Option<bool>>("resizable")?
+        .unwrap_or(false),
+    )
+  }
+
+  /// The max byte length the `ArrayBuffer` can be resized to, it's the byte length for the fixed-length `ArrayBuffer`.
+  pub fn max_byte_length(&self) -> Result<usize> {
+    Ok(
+      self
+        .get_named_property_unchecked::<Option<f64>>("maxByteLength")?
+        .map_or(self.len, |max_byte_length| max_byte_length as usize),
+    )
+  }
+
   #[cfg(feature = "napi7")]
   /// Generally, an ArrayBuffer is non-detachable if it has been detached before.
   ///
   /// The engine may impose additional conditions on whether an ArrayBuffer is detachable.
   ///
   /// For example, V8 requires that the ArrayBuffer be external, that is, created with napi_create_external_arraybuffer
+  ///
+  /// With the `slice-borrow-tracking` feature, it returns an error while the ArrayBuffer is borrowed by the Rust slices.
   pub fn detach(self) -> Result<()> {
+    let borrows = slice_borrow_count(self.data.as_ptr());
+    if borrows != 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "ArrayBuffer can't be detached while {} Rust slices borrow it",
+          borrows
+        ),
+      ));
+    }
     check_status!(unsafe { sys::napi_detach_arraybuffer(self.value.env, self.value.value) })
   }
 
@@ -342,6 +486,640 @@ impl<'env> ArrayBuffer<'env> {
     })?;
     Ok(is_detached)
   }
+
+  #[cfg(feature = "napi7")]
+  /// Move the bytes out of the `ArrayBuffer` into Rust, like transferring it by `postMessage`.
+  ///
+  /// The `ArrayBuffer` is detached, so JavaScript loses the access to the bytes and Rust owns them exclusively.
+  /// The backing store of the `ArrayBuffer` is owned by the engine, so the bytes are copied into the `Vec` once.
+  ///
+  /// Returns an error if the `ArrayBuffer` is detached already or can't be detached, it's left intact in that case.
+  pub fn transfer(self) -> Result<Vec<u8>> {
+    if self.is_detached()? {
+      return Err(Error::new(Status::InvalidArg, "ArrayBuffer is detached"));
+    }
+    let mut data = ptr::null_mut();
+    let mut len = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_arraybuffer_info(self.value.env, self.value.value, &mut data, &mut len)
+      },
+      "Get ArrayBuffer info failed"
+    )?;
+    let bytes = if len == 0 {
+      Vec::new()
+    } else {
+      unsafe { slice::from_raw_parts(data.cast::<u8>(), len) }.to_vec()
+    };
+    self.detach()?;
+    Ok(bytes)
+  }
+
+  /// Move `data` into a new `ArrayBuffer` without copying, the inverse of [`ArrayBuffer::transfer`].
+  ///
+  /// It's the same as [`ArrayBuffer::from_data`], the data is copied only if the runtime doesn't allow external buffers.
+  pub fn adopt(env: &Env, data: Vec<u8>) -> Result<Self> {
+    Self::from_data(env, data)
+  }
+
+  #[cfg(feature = "napi7")]
+  /// Wrap the `ArrayBuffer` into a [`DetachGuard`], which checks whether it is detached before each access.
+  pub fn detach_guard(self) -> DetachGuard<'env> {
+    DetachGuard { array_buffer: self }
+  }
+}
+
+/// A resizable `ArrayBuffer`, created by [`ArrayBuffer::new_resizable`] or converted from an `ArrayBuffer` created
+/// with `maxByteLength` in JavaScript.
+///
+/// Unlike [`ArrayBuffer`] it's not `Copy`, and the memory is looked up again for each access, so the slices taken from it
+/// can't outlive a resize, and the other handles of the same `ArrayBuffer` see the current length after it.
+pub struct ResizableArrayBuffer<'env> {
+  value: Value,
+  _marker: PhantomData<&'env ()>,
+}
+
+impl ResizableArrayBuffer<'_> {
+  /// Current byte length of the `ArrayBuffer`.
+  pub fn byte_length(&self) -> usize {
+    self.current().1
+  }
+
+  /// The max byte length the `ArrayBuffer` can be resized to.
+  pub fn max_byte_length(&self) -> Result<usize> {
+    Ok(self.get_named_property_unchecked::<f64>("maxByteLength")? as usize)
+  }
+
+  /// Resize the `ArrayBuffer` to `new_byte_length` bytes, the new bytes are zero-filled.
+  pub fn resize(&mut self, new_byte_length: usize) -> Result<()> {
+    let max_byte_length = self.max_byte_length()?;
+    if new_byte_length > max_byte_length {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "ArrayBuffer can't be resized to {} bytes, the max byte length is {}",
+          new_byte_length, max_byte_length
+        ),
+      ));
+    }
+    let this = Object::from_raw(self.value.env, self.value.value);
+    self
+      .get_named_property_unchecked::<Function<f64, ()>>("resize")?
+      .apply(this, new_byte_length as f64)
+  }
+
+  /// The data pointer and the byte length, an empty memory if it's detached.
+  fn current(&self) -> (*mut u8, usize) {
+    let mut data = ptr::null_mut();
+    let mut byte_length = 0;
+    let status = unsafe {
+      sys::napi_get_arraybuffer_info(
+        self.value.env,
+        self.value.value,
+        &mut data,
+        &mut byte_length,
+      )
+    };
+    if status != sys::Status::napi_ok || data.is_null() {
+      return (ptr::null_mut(), 0);
+    }
+    (data.cast(), byte_length)
+  }
+}
+
+impl<'env> JsValue<'env> for ResizableArrayBuffer<'env> {
+  fn value(&self) -> Value {
+    self.value
+  }
+}
+
+impl<'env> JsObjectValue<'env> for ResizableArrayBuffer<'env> {}
+
+impl_binary_fmt!(ResizableArrayBuffer<'_>, "ArrayBuffer", |this| ownership: "JavaScript", length: this.len(), bytes: this);
+
+impl Deref for ResizableArrayBuffer<'_> {
+  type Target = [u8];
+
+  fn deref(&self) -> &Self::Target {
+    let (data, byte_length) = self.current();
+    if byte_length == 0 {
+      return &[];
+    }
+    unsafe { slice::from_raw_parts(data, byte_length) }
+  }
+}
+
+impl DerefMut for ResizableArrayBuffer<'_> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    let (data, byte_length) = self.current();
+    if byte_length == 0 {
+      return &mut [];
+    }
+    unsafe { slice::from_raw_parts_mut(data, byte_length) }
+  }
+}
+
+impl TypeName for ResizableArrayBuffer<'_> {
+  fn type_name() -> &'static str {
+    "ArrayBuffer"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for ResizableArrayBuffer<'_> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    if !unsafe { ArrayBuffer::from_napi_value(env, napi_val) }?.is_resizable()? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "ArrayBuffer is not resizable".to_owned(),
+      ));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
+impl FromNapiValue for ResizableArrayBuffer<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { Self::validate(env, napi_val) }?;
+    Ok(Self {
+      value: Value {
+        env,
+        value: napi_val,
+        value_type: ValueType::Object,
+      },
+      _marker: PhantomData,
+    })
+  }
+}
+
+/// An `ArrayBuffer` created by [`ArrayBuffer::new_uninit`], which is filled by Rust before it's exposed to JavaScript.
+pub struct UninitArrayBuffer<'env> {
+  value: Value,
+  data: &'env mut [MaybeUninit<u8>],
+}
+
+impl<'env> UninitArrayBuffer<'env> {
+  pub fn len(&self) -> usize {
+    self.data.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.data.is_empty()
+  }
+
+  /// The bytes to fill.
+  pub fn as_uninit_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+    self.data
+  }
+
+  /// Copy `data` into the bytes starting at `offset`.
+  ///
+  /// Returns an error if `data` doesn't fit in the buffer, nothing is written in that case.
+  pub fn copy_from_slice_at(&mut self, offset: usize, data: &[u8]) -> Result<()> {
+    // `MaybeUninit<u8>` has the same layout as `u8`
+    let data =
+      unsafe { slice::from_raw_parts(data.as_ptr().cast::<MaybeUninit<u8>>(), data.len()) };
+    copy_from_slice_at(self.data, offset, data)
+  }
+
+  /// Finish filling the bytes and get the `ArrayBuffer`.
+  ///
+  /// # Safety
+  ///
+  /// All the bytes must be initialized.
+  pub unsafe fn assume_init(self) -> ArrayBuffer<'env> {
+    let data = self.data;
+    ArrayBuffer::from_raw_parts(self.value, data.as_mut_ptr().cast(), data.len())
+  }
+}
+
+#[cfg(feature = "napi7")]
+#[derive(Clone, Copy)]
+/// An `ArrayBuffer` which is re-validated before each access.
+///
+/// The `ArrayBuffer` may be detached by JavaScript between the accesses, for example transferred in a callback
+/// called from Rust. Instead of touching the freed memory, the accesses return an error once it is detached.
+/// The memory is looked up again for each access, so the accesses also see the current length of a resized `ArrayBuffer`.
+pub struct DetachGuard<'env> {
+  array_buffer: ArrayBuffer<'env>,
+}
+
+#[cfg(feature = "napi7")]
+impl<'env> DetachGuard<'env> {
+  /// The guarded `ArrayBuffer`.
+  pub fn array_buffer(&self) -> ArrayBuffer<'env> {
+    self.array_buffer
+  }
+
+  /// Current byte length of the `ArrayBuffer`.
+  pub fn len(&self) -> Result<usize> {
+    Ok(self.current()?.1)
+  }
+
+  pub fn is_empty(&self) -> Result<bool> {
+    Ok(self.len()? == 0)
+  }
+
+  /// Read the byte at `index`.
+  pub fn get(&self, index: usize) -> Result<u8> {
+    let data = self.bytes()?;
+    data
+      .get(index)
+      .copied()
+      .ok_or_else(|| out_of_bounds(index, data.len()))
+  }
+
+  /// Write the byte at `index`.
+  pub fn set(&mut self, index: usize, value: u8) -> Result<()> {
+    let data = self.bytes_mut()?;
+    let len = data.len();
+    *data
+      .get_mut(index)
+      .ok_or_else(|| out_of_bounds(index, len))? = value;
+    Ok(())
+  }
+
+  /// Read the memory in `f`.
+  ///
+  /// Don't call into JavaScript in `f`, the memory may be detached meanwhile.
+  pub fn with<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Result<R> {
+    Ok(f(self.bytes()?))
+  }
+
+  /// Write the memory in `f`.
+  ///
+  /// Don't call into JavaScript in `f`, the memory may be detached meanwhile.
+  pub fn with_mut<R>(&mut self, f: impl FnOnce(&mut [u8]) -> R) -> Result<R> {
+    Ok(f(self.bytes_mut()?))
+  }
+
+  fn bytes(&self) -> Result<&[u8]> {
+    let (data, byte_length) = self.current()?;
+    Ok(if byte_length == 0 {
+      &[]
+    } else {
+      unsafe { slice::from_raw_parts(data, byte_length) }
+    })
+  }
+
+  fn bytes_mut(&mut self) -> Result<&mut [u8]> {
+    let (data, byte_length) = self.current()?;
+    Ok(if byte_length == 0 {
+      &mut []
+    } else {
+      unsafe { slice::from_raw_parts_mut(data, byte_length) }
+    })
+  }
+
+  fn current(&self) -> Result<(*mut u8, usize)> {
+    if self.array_buffer.is_detached()? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "ArrayBuffer is detached".to_owned(),
+      ));
+    }
+    let mut data = ptr::null_mut();
+    let mut byte_length = 0;
+    check_status!(unsafe {
+      sys::napi_get_arraybuffer_info(
+        self.array_buffer.value.env,
+        self.array_buffer.value.value,
+        &mut data,
+        &mut byte_length,
+      )
+    })?;
+    if data.is_null() {
+      return Ok((ptr::null_mut(), 0));
+    }
+    Ok((data.cast(), byte_length))
+  }
+}
+
+#[cfg(feature = "napi7")]
+fn out_of_bounds(index: usize, byte_length: usize) -> Error {
+  Error::new(
+    Status::InvalidArg,
+    format!(
+      "Index {} is outside the bounds of the ArrayBuffer of {} bytes",
+      index, byte_length
+    ),
+  )
+}
+
+#[derive(Clone, Copy)]
+/// Zero copy JavaScript `SharedArrayBuffer` shared between Rust and Node.js.
+///
+/// It can only be used in non-async context and the lifetime is bound to the fn closure.
+/// If you want to share the memory with other threads, convert it into [`SharedMemory`].
+///
+/// The memory may be written by the other JavaScript or Rust threads concurrently,
+/// so it's accessed with the atomic views like [`SharedArrayBuffer::as_atomic_i32`].
+pub struct SharedArrayBuffer<'env> {
+  pub(crate) value: Value,
+  data: NonNull<u8>,
+  byte_length: usize,
+  _marker: PhantomData<&'env ()>,
+}
+
+/// Generate the atomic views of the shared memory, the integer types are the same as the JavaScript `Atomics` supported.
+macro_rules! impl_atomic_views {
+  ($($(#[$meta:meta])* ($name:ident, $atomic:ty)),* $(,)?) => {
+    $(
+      $(#[$meta])*
+      #[doc = concat!("View the memory as `&[", stringify!($atomic), "]`.")]
+      pub fn $name(&self) -> Result<&[$atomic]> {
+        let (data, byte_length) = self.raw_memory();
+        unsafe { atomic_view(data, byte_length) }
+      }
+    )*
+  };
+}
+
+/// SAFETY: `data` must be valid for `byte_length` bytes during `'a`.
+unsafe fn atomic_view<'a, A>(data: NonNull<u8>, byte_length: usize) -> Result<&'a [A]> {
+  let size = mem::size_of::<A>();
+  if byte_length % size != 0 || data.as_ptr().align_offset(mem::align_of::<A>()) != 0 {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "SharedArrayBuffer of {} bytes can't be viewed as {}",
+        byte_length,
+        std::any::type_name::<A>()
+      ),
+    ));
+  }
+  Ok(unsafe { slice::from_raw_parts(data.as_ptr().cast(), byte_length / size) })
+}
+
+pub(crate) fn get_global_constructor(env: sys::napi_env, name: &CStr) -> Result<sys::napi_value> {
+  let mut global = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_global(env, &mut global) },
+    "Get global object failed"
+  )?;
+  let mut constructor = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, global, name.as_ptr(), &mut constructor) },
+    "Get global {:?} failed",
+    name
+  )?;
+  Ok(constructor)
+}
+
+impl<'env> SharedArrayBuffer<'env> {
+  /// Create a new zero-filled `SharedArrayBuffer` of `byte_length` bytes.
+  pub fn new(env: &Env, byte_length: usize) -> Result<Self> {
+    let constructor = get_global_constructor(env.0, c"SharedArrayBuffer")?;
+    let mut length = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_double(env.0, byte_length as f64, &mut length) },
+      "Create SharedArrayBuffer length failed"
+    )?;
+    let mut value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_new_instance(env.0, constructor, 1, &length, &mut value) },
+      "Create SharedArrayBuffer failed"
+    )?;
+    unsafe { Self::from_napi_value(env.0, value) }
+  }
+
+  /// Number of bytes of the `SharedArrayBuffer`.
+  pub fn byte_length(&self) -> usize {
+    self.byte_length
+  }
+
+  /// View the memory as plain bytes.
+  ///
+  /// # Safety
+  ///
+  /// No other JavaScript or Rust thread may write the memory while the returned slice is alive,
+  /// use the atomic views otherwise.
+  pub unsafe fn as_slice(&self) -> &[u8] {
+    unsafe { slice::from_raw_parts(self.data.as_ptr(), self.byte_length) }
+  }
+
+  fn raw_memory(&self) -> (NonNull<u8>, usize) {
+    (self.data, self.byte_length)
+  }
+
+  impl_atomic_views!(
+    (as_atomic_u8, AtomicU8),
+    (as_atomic_i8, AtomicI8),
+    (as_atomic_u16, AtomicU16),
+    (as_atomic_i16, AtomicI16),
+    (as_atomic_u32, AtomicU32),
+    (as_atomic_i32, AtomicI32),
+    #[cfg(target_has_atomic = "64")]
+    (as_atomic_u64, AtomicU64),
+    #[cfg(target_has_atomic = "64")]
+    (as_atomic_i64, AtomicI64),
+  );
+
+  #[cfg(feature = "napi4")]
+  /// Convert into [`SharedMemory`], which can be sent to and used on other threads without copying.
+  pub fn into_shared(self) -> Result<SharedMemory> {
+    Ok(SharedMemory {
+      inner: Arc::new(SharedMemoryInner {
+        data: self.data,
+        byte_length: self.byte_length,
+        reference: RawReferenceSendGuard::new(self.value.env, self.value.value)?,
+      }),
+    })
+  }
+}
+
+impl<'env> JsValue<'env> for SharedArrayBuffer<'env> {
+  fn value(&self) -> Value {
+    self.value
+  }
+}
+
+impl<'env> JsObjectValue<'env> for SharedArrayBuffer<'env> {}
+
+// the bytes are not dumped, reading them may race with the writes of the other threads
+impl std::fmt::Debug for SharedArrayBuffer<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SharedArrayBuffer")
+      .field("byte_length", &self.byte_length)
+      .finish()
+  }
+}
+
+impl TypeName for SharedArrayBuffer<'_> {
+  fn type_name() -> &'static str {
+    "SharedArrayBuffer"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for SharedArrayBuffer<'_> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let constructor = get_global_constructor(env, c"SharedArrayBuffer")?;
+    let mut is_shared_array_buffer = false;
+    check_status!(
+      unsafe { sys::napi_instanceof(env, napi_val, constructor, &mut is_shared_array_buffer) },
+      "Failed to check if value is SharedArrayBuffer"
+    )?;
+    if !is_shared_array_buffer {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Expected a SharedArrayBuffer value".to_owned(),
+      ));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
+impl FromNapiValue for SharedArrayBuffer<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { Self::validate(env, napi_val) }?;
+    // `napi_get_arraybuffer_info` rejects the SharedArrayBuffer, read the memory through a `Uint8Array` view of it
+    let constructor = get_global_constructor(env, c"Uint8Array")?;
+    let mut view = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_new_instance(env, constructor, 1, &napi_val, &mut view) },
+      "Create Uint8Array view of SharedArrayBuffer failed"
+    )?;
+    let mut typed_array_type = 0;
+    let mut byte_length = 0;
+    let mut data = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_get_typedarray_info(
+          env,
+          view,
+          &mut typed_array_type,
+          &mut byte_length,
+          &mut data,
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Get SharedArrayBuffer info failed"
+    )?;
+    Ok(Self {
+      value: Value {
+        env,
+        value: napi_val,
+        value_type: ValueType::Object,
+      },
+      data: if byte_length == 0 {
+        NonNull::dangling()
+      } else {
+        unsafe { NonNull::new_unchecked(data.cast()) }
+      },
+      byte_length,
+      _marker: PhantomData,
+    })
+  }
+}
+
+#[cfg(feature = "napi4")]
+#[derive(Clone)]
+/// The memory of a JavaScript `SharedArrayBuffer` owned by an `Arc`.
+///
+/// It can be cloned and sent to other threads, the memory is accessed with the atomic views without copying.
+/// The `SharedArrayBuffer` is kept alive until the last clone is dropped on any thread.
+pub struct SharedMemory {
+  inner: Arc<SharedMemoryInner>,
+}
+
+#[cfg(feature = "napi4")]
+struct SharedMemoryInner {
+  data: NonNull<u8>,
+  byte_length: usize,
+  reference: RawReferenceSendGuard,
+}
+
+#[cfg(feature = "napi4")]
+/// SAFETY: the memory is only accessed through the atomic views, and the `SharedArrayBuffer` is referenced until it's dropped.
+unsafe impl Send for SharedMemoryInner {}
+#[cfg(feature = "napi4")]
+unsafe impl Sync for SharedMemoryInner {}
+
+#[cfg(feature = "napi4")]
+impl SharedMemory {
+  /// Create a new zero-filled `SharedArrayBuffer` of `byte_length` bytes owned by Rust.
+  pub fn new(env: &Env, byte_length: usize) -> Result<Self> {
+    SharedArrayBuffer::new(env, byte_length)?.into_shared()
+  }
+
+  /// Number of bytes of the memory.
+  pub fn byte_length(&self) -> usize {
+    self.inner.byte_length
+  }
+
+  fn raw_memory(&self) -> (NonNull<u8>, usize) {
+    (self.inner.data, self.inner.byte_length)
+  }
+
+  impl_atomic_views!(
+    (as_atomic_u8, AtomicU8),
+    (as_atomic_i8, AtomicI8),
+    (as_atomic_u16, AtomicU16),
+    (as_atomic_i16, AtomicI16),
+    (as_atomic_u32, AtomicU32),
+    (as_atomic_i32, AtomicI32),
+    #[cfg(target_has_atomic = "64")]
+    (as_atomic_u64, AtomicU64),
+    #[cfg(target_has_atomic = "64")]
+    (as_atomic_i64, AtomicI64),
+  );
+
+  /// Get the `SharedArrayBuffer`.
+  ///
+  /// Returns an error unless `env` is the env where the `SharedMemory` is created, on its JavaScript thread.
+  pub fn to_shared_array_buffer<'env>(&self, env: &'env Env) -> Result<SharedArrayBuffer<'env>> {
+    let value = self.inner.reference.get(env.0)?;
+    Ok(SharedArrayBuffer {
+      value: Value {
+        env: env.0,
+        value,
+        value_type: ValueType::Object,
+      },
+      data: self.inner.data,
+      byte_length: self.inner.byte_length,
+      _marker: PhantomData,
+    })
+  }
+}
+
+#[cfg(feature = "napi4")]
+impl TypeName for SharedMemory {
+  fn type_name() -> &'static str {
+    "SharedArrayBuffer"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+#[cfg(feature = "napi4")]
+impl ValidateNapiValue for SharedMemory {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { SharedArrayBuffer::validate(env, napi_val) }
+  }
+}
+
+#[cfg(feature = "napi4")]
+impl FromNapiValue for SharedMemory {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { SharedArrayBuffer::from_napi_value(env, napi_val) }?.into_shared()
+  }
+}
+
+#[cfg(feature = "napi4")]
+impl ToNapiValue for SharedMemory {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    val.inner.reference.get(env)
+  }
 }
 
 trait Finalizer {
@@ -386,11 +1164,10 @@ macro_rules! impl_typed_array {
     impl Drop for $name {
       fn drop(&mut self) {
         if self.owned_by_rust {
-          self.drop_callback();
-        }
-
-        if self.raw.is_none() && self.owned_by_rust {
-          if !self.data.is_null() {
+          if self.finalizer_notify.is_some() {
+            // the external data is released by its owner
+            self.drop_callback();
+          } else if self.raw.is_none() && !self.data.is_null() {
             unsafe { Vec::from_raw_parts(self.data, self.length, self.length) };
           }
         }
@@ -512,6 +1289,44 @@ macro_rules! impl_typed_array {
         ret
       }
 
+      /// Copy `data` into a new allocation aligned to `alignment` bytes.
+      ///
+      /// The backing store handed to JavaScript keeps the alignment,
+      /// so the data is still aligned when the TypedArray comes back from JavaScript, for example for the SIMD kernels.
+      /// `alignment` must be a power of two and not less than the alignment of the element type.
+      pub fn new_aligned<D>(data: D, alignment: usize) -> Result<Self>
+      where
+        D: AsRef<[$rust_type]>,
+      {
+        let data = data.as_ref();
+        let layout = std::alloc::Layout::array::<$rust_type>(data.len())
+          .and_then(|layout| layout.align_to(alignment))
+          .map_err(|err| {
+            Error::new(
+              Status::InvalidArg,
+              format!(
+                "Invalid alignment {} for {}: {}",
+                alignment,
+                stringify!($name),
+                err
+              ),
+            )
+          })?;
+        if data.is_empty() {
+          return Ok(Self::new(Vec::new()));
+        }
+        let ptr = unsafe { std::alloc::alloc(layout) }.cast::<$rust_type>();
+        if ptr.is_null() {
+          std::alloc::handle_alloc_error(layout);
+        }
+        unsafe { ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len()) };
+        Ok(unsafe {
+          Self::with_external_data(ptr, data.len(), move |ptr, _| {
+            std::alloc::dealloc(ptr.cast(), layout)
+          })
+        })
+      }
+
       /// # Safety
       ///
       /// The caller will be notified when the data is deallocated by vm
@@ -540,8 +1355,36 @@ macro_rules! impl_typed_array {
 
         unsafe { std::slice::from_raw_parts_mut(self.data, self.length) }
       }
+
+      /// Iterate over the elements in chunks of `chunk_len`, each chunk is processed in its own handle scope.
+      ///
+      /// The `TypedArray` doesn't hold its JavaScript value in general, so the chunks are copied when converted to JavaScript,
+      /// use the `iter_chunks` of the slice type to view them without copying. See [`TypedArrayChunks`] for the details.
+      pub fn iter_chunks(&self, env: &Env, chunk_len: usize) -> Result<TypedArrayChunks<'_, $rust_type>> {
+        let typed_array = match self.raw {
+          Some((reference, _)) if !reference.is_null() => {
+            let mut value = ptr::null_mut();
+            check_status!(
+              unsafe { sys::napi_get_reference_value(env.0, reference, &mut value) },
+              "Failed to get the value of {}",
+              stringify!($name)
+            )?;
+            Some(value)
+          }
+          _ => None,
+        };
+        TypedArrayChunks::new(env.0, self, chunk_len, $typed_array_type, typed_array)
+      }
     }
 
+    impl_binary_fmt!($name, stringify!($name), |this| ownership: if !this.owned_by_rust {
+      "JavaScript"
+    } else if this.finalizer_notify.is_some() {
+      "external"
+    } else {
+      "Rust"
+    }, length: this.length, bytes: as_bytes(this));
+
     impl Deref for $name {
       type Target = [$rust_type];
 
@@ -560,6 +1403,14 @@ macro_rules! impl_typed_array {
       }
     }
 
+    impl FromIterator<$rust_type> for $name {
+      /// Collect the elements into the `Vec` backing the TypedArray,
+      /// use the `from_iter` of the slice type to write them directly into memory allocated by the engine.
+      fn from_iter<I: IntoIterator<Item = $rust_type>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+      }
+    }
+
     impl TypeName for $name {
       fn type_name() -> &'static str {
         concat!("TypedArray<", stringify!($rust_type), ">")
@@ -687,7 +1538,7 @@ macro_rules! impl_typed_array {
                 &mut arraybuffer_value,
               )
             };
-            if status == napi_sys::Status::napi_no_external_buffers_allowed {
+            if external_buffers_disallowed(status) {
               let hint = unsafe { Box::from_raw(hint_ptr) };
               let mut underlying_data = ptr::null_mut();
               let status = unsafe {
@@ -701,6 +1552,10 @@ macro_rules! impl_typed_array {
               unsafe { std::ptr::copy_nonoverlapping(hint.data.cast(), underlying_data, length) };
               status
             } else {
+              if status == sys::Status::napi_ok {
+                // released by `finalizer`, the ArrayBuffer owns the data already so the failure is ignored
+                let _ = adjust_external_memory(env, length as i64);
+              }
               status
             }
           },
@@ -780,7 +1635,7 @@ macro_rules! impl_typed_array {
                 &mut arraybuffer_value,
               )
             };
-            if status == napi_sys::Status::napi_no_external_buffers_allowed {
+            if external_buffers_disallowed(status) {
               let hint = unsafe { Box::from_raw(hint_ptr) };
               let mut underlying_data = ptr::null_mut();
               let status = unsafe {
@@ -794,6 +1649,10 @@ macro_rules! impl_typed_array {
               unsafe { std::ptr::copy_nonoverlapping(hint.data.cast(), underlying_data, length) };
               status
             } else {
+              if status == sys::Status::napi_ok {
+                // released by `finalizer`, the ArrayBuffer owns the data already so the failure is ignored
+                let _ = adjust_external_memory(env, length as i64);
+              }
               status
             }
           },
@@ -865,11 +1724,7 @@ macro_rules! impl_from_slice {
         let len_bytes = len_elems * core::mem::size_of::<$rust_type>();
 
         // Tell V8 how many bytes live outside the JS heap
-        let mut _dummy = 0;
-        check_status!(
-          unsafe { sys::napi_adjust_external_memory(env.0, len_bytes as i64, &mut _dummy) },
-          "adjust external memory"
-        )?;
+        adjust_external_memory(env.0, len_bytes as i64)?;
 
         let mut status = unsafe {
           let cap = data.capacity();
@@ -883,7 +1738,7 @@ macro_rules! impl_from_slice {
           )
         };
 
-        if status == napi_sys::Status::napi_no_external_buffers_allowed {
+        if external_buffers_disallowed(status) {
           let mut inner_data = unsafe { Vec::from_raw_parts(inner_ptr, len_elems, len_elems) };
           let mut underlying_data: *mut c_void = ptr::null_mut();
           status = unsafe {
@@ -899,14 +1754,11 @@ macro_rules! impl_from_slice {
           inner_ptr = underlying_data.cast();
         } else {
           mem::forget(data);
+          #[cfg(not(feature = "minimal"))]
+          register_backing_ptr(inner_ptr.cast::<u8>(), len_bytes);
         }
         check_status!(status, "Failed to create buffer slice from data")?;
 
-        #[cfg(all(debug_assertions, not(windows)))]
-        if len_elems != 0 {
-          register_backing_ptr(inner_ptr.cast::<u8>());
-        }
-
         let mut napi_val = ptr::null_mut();
         check_status!(
           unsafe {
@@ -966,7 +1818,7 @@ macro_rules! impl_from_slice {
         };
 
         let mut underlying_data: *mut c_void = ptr::null_mut();
-        if status == sys::Status::napi_no_external_buffers_allowed {
+        if external_buffers_disallowed(status) {
           let (hint, finalize) = *Box::from_raw(hint_ptr);
           status = unsafe {
             sys::napi_create_arraybuffer(
@@ -980,14 +1832,10 @@ macro_rules! impl_from_slice {
           finalize(*env, hint);
         }
 
-        #[cfg(all(debug_assertions, not(windows)))]
-        {
-          let ptr_to_track = if status == sys::Status::napi_no_external_buffers_allowed {
-            underlying_data // new buffer allocated above
-          } else {
-            data.cast::<c_void>() // original external buffer
-          };
-          register_backing_ptr(ptr_to_track.cast::<u8>());
+        // the copy of the Electron fallback is owned by JavaScript
+        #[cfg(not(feature = "minimal"))]
+        if underlying_data.is_null() {
+          register_backing_ptr(data.cast::<u8>(), len_bytes);
         }
 
         check_status!(status, "Failed to create arraybuffer from data")?;
@@ -1011,56 +1859,11 @@ macro_rules! impl_from_slice {
           inner: if len == 0 {
             NonNull::dangling()
           } else {
-            NonNull::new_unchecked(if status == sys::Status::napi_no_external_buffers_allowed {
+            NonNull::new_unchecked(if external_buffers_disallowed(status) {
               underlying_data.cast()
-            } else {
-              data.cast()
-            })
-          },
-          length: len,
-          raw_value: napi_val,
-          env: env.0,
-          _marker: PhantomData,
-        })
-      }
-      #[doc = "Copy data from a `&["]
-      #[doc = stringify!($rust_type)]
-      #[doc = "]` and create a `"]
-      #[doc = stringify!($slice_type)]
-      #[doc = "` from it."]
-      pub fn copy_from<D: AsRef<[$rust_type]>>(env: &Env, data: D) -> Result<Self> {
-        let data = data.as_ref();
-        let len = data.len();
-        let mut arraybuffer_value = ptr::null_mut();
-        let mut underlying_data = ptr::null_mut();
-
-        check_status!(
-          unsafe {
-            sys::napi_create_arraybuffer(env.0, len, &mut underlying_data, &mut arraybuffer_value)
-          },
-          "Failed to create ArrayBuffer"
-        )?;
-
-        let mut napi_val = ptr::null_mut();
-        check_status!(
-          unsafe {
-            sys::napi_create_typedarray(
-              env.0,
-              $typed_array_type as i32,
-              len,
-              arraybuffer_value,
-              0,
-              &mut napi_val,
-            )
-          },
-          "Create TypedArray failed"
-        )?;
-
-        Ok(Self {
-          inner: if len == 0 {
-            NonNull::dangling()
-          } else {
-            unsafe { NonNull::new_unchecked(underlying_data.cast()) }
+            } else {
+              data.cast()
+            })
           },
           length: len,
           raw_value: napi_val,
@@ -1068,6 +1871,27 @@ macro_rules! impl_from_slice {
           _marker: PhantomData,
         })
       }
+      #[doc = "Copy data from a `&["]
+      #[doc = stringify!($rust_type)]
+      #[doc = "]` and create a `"]
+      #[doc = stringify!($slice_type)]
+      #[doc = "` from it."]
+      pub fn copy_from<D: AsRef<[$rust_type]>>(env: &Env, data: D) -> Result<Self> {
+        Self::from_iter(env, data.as_ref().iter().copied())
+      }
+
+      /// Create from the elements of `iter`.
+      ///
+      /// If the length is known from the `size_hint` of the iterator, like for the `ExactSizeIterator`s,
+      /// the elements are written directly into an `ArrayBuffer` allocated by the engine,
+      /// so there is no intermediate `Vec` nor the copy of the fallback for the runtimes disallowing external buffers.
+      /// Otherwise the elements are collected into a `Vec` first like [`Self::from_data`].
+      pub fn from_iter<I: IntoIterator<Item = $rust_type>>(env: &Env, iter: I) -> Result<Self> {
+        match collect_into_arraybuffer(env, iter.into_iter())? {
+          Collected::ArrayBuffer(arraybuffer, len) => Self::from_arraybuffer(&arraybuffer, 0, len),
+          Collected::Vec(data) => Self::from_data(env, data),
+        }
+      }
 
       /// Create from `ArrayBuffer`
       pub fn from_arraybuffer(
@@ -1094,6 +1918,41 @@ macro_rules! impl_from_slice {
         unsafe { FromNapiValue::from_napi_value(env, typed_array) }
       }
 
+      /// Create a view of the elements from `begin` to `end` (exclusive) on the same `ArrayBuffer`, without copying.
+      ///
+      /// Like `TypedArray.prototype.subarray` in JavaScript, but out of range indexes are errors instead of being clamped.
+      pub fn subarray(&self, begin: usize, end: usize) -> Result<$slice_type<'env>> {
+        let subarray = create_subarray(
+          self.env,
+          self.raw_value,
+          $typed_array_type,
+          self.length,
+          begin,
+          end,
+        )?;
+        unsafe { FromNapiValue::from_napi_value(self.env, subarray) }
+      }
+
+      /// Iterate over the elements in chunks of `chunk_len`, each chunk is processed in its own handle scope.
+      ///
+      /// The chunks are viewed by JavaScript without copying, see [`TypedArrayChunks`] for the details.
+      pub fn iter_chunks(&self, chunk_len: usize) -> Result<TypedArrayChunks<'_, $rust_type>> {
+        TypedArrayChunks::new(
+          self.env,
+          self,
+          chunk_len,
+          $typed_array_type,
+          Some(self.raw_value),
+        )
+      }
+
+      /// Copy `data` into the elements starting at `offset`, for example to fill an output array provided by the caller.
+      ///
+      /// Returns an error if `data` doesn't fit in the array, nothing is written in that case.
+      pub fn copy_from_slice_at(&mut self, offset: usize, data: &[$rust_type]) -> Result<()> {
+        copy_from_slice_at(self, offset, data)
+      }
+
       /// extends the lifetime of the `TypedArray` to the lifetime of the `This`
       pub fn assign_to_this<'a, U>(&self, this: This<'a, U>, name: &str) -> Result<$slice_type<'a>>
       where
@@ -1179,6 +2038,14 @@ macro_rules! impl_from_slice {
             format!("Expected $name, got {}", typed_array_type),
           ));
         }
+        if !data.is_null() {
+          track_slice_borrow(
+            env,
+            array_buffer,
+            data.cast::<u8>().wrapping_sub(byte_offset),
+            stringify!($slice_type),
+          );
+        }
         // From the docs of `napi_get_typedarray_info`:
         // > [out] data: The underlying data buffer of the node::Buffer. If length is 0, this may be
         // > NULL or any other pointer value.
@@ -1233,6 +2100,8 @@ macro_rules! impl_from_slice {
       }
     }
 
+    impl_binary_fmt!($slice_type<'_>, stringify!($slice_type), |this| ownership: "JavaScript", length: this.length, bytes: as_bytes(this));
+
     impl Deref for $slice_type<'_> {
       type Target = [$rust_type];
 
@@ -1386,18 +2255,15 @@ unsafe extern "C" fn finalizer<Data, T: Finalizer<RustType = Data>>(
 
   let data: T = *Box::from_raw(finalize_hint.cast::<T>());
 
-  if !env.is_null() {
-    let mut _dummy = 0;
-    // tell V8 the bytes are gone
-    sys::napi_adjust_external_memory(env, -(data.byte_len() as i64), &mut _dummy);
-  }
+  // tell V8 the bytes are gone
+  let _ = adjust_external_memory(env, -(data.byte_len() as i64));
   // now drop them
   drop(data);
   crate::bindgen_runtime::IN_FINALISER.with(|f| f.set(false));
 }
 
 unsafe extern "C" fn finalize_slice<Data>(
-  _env: sys::napi_env,
+  env: sys::napi_env,
   finalize_data: *mut c_void,
   finalize_hint: *mut c_void,
 ) {
@@ -1405,15 +2271,151 @@ unsafe extern "C" fn finalize_slice<Data>(
     return;
   }
 
-  #[cfg(all(debug_assertions, not(windows)))]
+  #[cfg(not(feature = "minimal"))]
   unregister_backing_ptr(finalize_data as *mut u8);
 
   let (length, cap) = *Box::from_raw(finalize_hint.cast::<(usize, usize)>());
   Vec::from_raw_parts(finalize_data.cast::<Data>(), length, cap);
 
   // balance external-memory counter
-  let mut _dummy = 0;
-  sys::napi_adjust_external_memory(_env, -(length as i64), &mut _dummy);
+  let _ = adjust_external_memory(env, -((length * mem::size_of::<Data>()) as i64));
+}
+
+enum Collected<'env, T> {
+  ArrayBuffer(ArrayBuffer<'env>, usize),
+  Vec(Vec<T>),
+}
+
+// Write the elements of `iter` into an `ArrayBuffer` allocated by the engine if the length is known from the `size_hint`,
+// otherwise or if the iterator doesn't yield the number of elements in its `size_hint` they're collected into a `Vec`.
+fn collect_into_arraybuffer<'env, T, I: Iterator<Item = T>>(
+  env: &Env,
+  mut iter: I,
+) -> Result<Collected<'env, T>> {
+  let len = match iter.size_hint() {
+    (lower, Some(upper)) if lower == upper => lower,
+    _ => return Ok(Collected::Vec(iter.collect())),
+  };
+  let byte_length = len.checked_mul(mem::size_of::<T>()).ok_or_else(|| {
+    Error::new(
+      Status::InvalidArg,
+      format!("Too many elements for a TypedArray: {}", len),
+    )
+  })?;
+  let mut arraybuffer = ArrayBuffer::new_uninit(env, byte_length)?;
+  let bytes = arraybuffer.as_uninit_mut();
+  // the memory of an `ArrayBuffer` is aligned for all the TypedArrays
+  let elements: &mut [MaybeUninit<T>] = if len == 0 {
+    &mut []
+  } else {
+    unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), len) }
+  };
+  let mut written = 0;
+  for (element, value) in elements.iter_mut().zip(&mut iter) {
+    element.write(value);
+    written += 1;
+  }
+  let extra = if written == len { iter.next() } else { None };
+  if written == len && extra.is_none() {
+    return Ok(Collected::ArrayBuffer(
+      unsafe { arraybuffer.assume_init() },
+      len,
+    ));
+  }
+  let mut data = Vec::with_capacity(written + extra.is_some() as usize + iter.size_hint().0);
+  data.extend(
+    elements[..written]
+      .iter()
+      .map(|element| unsafe { element.assume_init_read() }),
+  );
+  data.extend(extra);
+  data.extend(iter);
+  Ok(Collected::Vec(data))
+}
+
+pub(crate) fn copy_from_slice_at<T: Copy>(dest: &mut [T], offset: usize, data: &[T]) -> Result<()> {
+  let len = dest.len();
+  let range = offset
+    .checked_add(data.len())
+    .and_then(|end| dest.get_mut(offset..end))
+    .ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        format!(
+          "{} elements at offset {} are out of the bounds of the array of {} elements",
+          data.len(),
+          offset,
+          len
+        ),
+      )
+    })?;
+  range.copy_from_slice(data);
+  Ok(())
+}
+
+pub(crate) fn create_subarray(
+  env: sys::napi_env,
+  typed_array: sys::napi_value,
+  typed_array_type: TypedArrayType,
+  length: usize,
+  begin: usize,
+  end: usize,
+) -> Result<sys::napi_value> {
+  if begin > end || end > length {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Subarray {}..{} is out of the bounds of the TypedArray of {} elements",
+        begin, end, length
+      ),
+    ));
+  }
+  let mut arraybuffer = ptr::null_mut();
+  let mut byte_offset = 0;
+  check_status!(
+    unsafe {
+      sys::napi_get_typedarray_info(
+        env,
+        typed_array,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+        &mut arraybuffer,
+        &mut byte_offset,
+      )
+    },
+    "Get TypedArray info failed"
+  )?;
+  let element_size = match typed_array_type {
+    TypedArrayType::Int8 | TypedArrayType::Uint8 | TypedArrayType::Uint8Clamped => 1,
+    TypedArrayType::Int16 | TypedArrayType::Uint16 => 2,
+    TypedArrayType::Int32 | TypedArrayType::Uint32 | TypedArrayType::Float32 => 4,
+    _ => 8,
+  };
+  let mut subarray = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_typedarray(
+        env,
+        typed_array_type.into(),
+        end - begin,
+        arraybuffer,
+        byte_offset + begin * element_size,
+        &mut subarray,
+      )
+    },
+    "Failed to create subarray"
+  )?;
+  Ok(subarray)
+}
+
+#[cfg(feature = "bytes")]
+unsafe extern "C" fn finalize_bytes(
+  _env: sys::napi_env,
+  _finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_hint.cast::<bytes::Bytes>()) });
 }
 
 impl_typed_array!(Int8Array, i8, TypedArrayType::Int8);
@@ -1484,6 +2486,169 @@ impl Uint8Array {
     mem::forget(s);
     ret
   }
+
+  /// Take over `data` without shrinking it, unlike [`Uint8Array::new`] the spare capacity is kept and released with the data.
+  pub(crate) fn from_vec_with_capacity(mut data: Vec<u8>) -> Self {
+    if data.len() == data.capacity() {
+      return Self::new(data);
+    }
+    let capacity = data.capacity();
+    let (ptr, len) = (data.as_mut_ptr(), data.len());
+    mem::forget(data);
+    unsafe {
+      Self::with_external_data(ptr, len, move |data, len| {
+        drop(Vec::from_raw_parts(data, len, capacity))
+      })
+    }
+  }
+
+  /// Take over the reference of a `Buffer` from JavaScript, see [`Buffer::into_uint8array`].
+  pub(crate) fn from_reference(
+    data: *mut u8,
+    length: usize,
+    reference: sys::napi_ref,
+    env: sys::napi_env,
+  ) -> Self {
+    Self {
+      data,
+      length,
+      owned_by_rust: false,
+      finalizer_notify: None,
+      byte_offset: 0,
+      raw: Some((reference, env)),
+    }
+  }
+
+  /// Convert the `Uint8Array` into a [`Buffer`] without copying.
+  ///
+  /// A `Uint8Array` holding a JavaScript value is converted to a `Buffer` of the same value,
+  /// which is still a `Uint8Array` rather than a Node.js `Buffer` on the JavaScript side.
+  /// A `Uint8Array` created in Rust hands its memory over to the `Buffer`.
+  ///
+  /// The `Uint8Array` received as an argument doesn't hold its JavaScript value,
+  /// receive it as [`Uint8ArraySlice`] and use [`Uint8ArraySlice::into_buffer`] instead.
+  pub fn into_buffer(mut self, env: &Env) -> Result<Buffer> {
+    if let Some((reference, _)) = self.raw {
+      let mut value = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env.0, reference, &mut value) },
+        "Failed to get the value of Uint8Array reference"
+      )?;
+      // the `Buffer` creates its own reference, the one of `self` is released when it's dropped
+      return unsafe { Buffer::from_napi_value(env.0, value) };
+    }
+    if !self.owned_by_rust {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The Uint8Array received from JavaScript doesn't hold its JavaScript value, use Uint8ArraySlice::into_buffer instead",
+      ));
+    }
+    if self.finalizer_notify.is_some() {
+      // the external data can only be released by its finalizer, so hand it to JavaScript first
+      let value = unsafe { Uint8Array::to_napi_value(env.0, self)? };
+      return unsafe { Buffer::from_napi_value(env.0, value) };
+    }
+    let data = if self.data.is_null() {
+      Vec::new()
+    } else {
+      unsafe { Vec::from_raw_parts(self.data, self.length, self.length) }
+    };
+    // the memory is owned by `data` now
+    self.owned_by_rust = false;
+    Ok(data.into())
+  }
+}
+
+impl Uint8ArraySlice<'_> {
+  /// Convert the `Uint8ArraySlice` to a [`Buffer`] of the same JavaScript value.
+  ///
+  /// This will perform a `napi_create_reference` internally.
+  pub fn into_buffer(self, env: &Env) -> Result<Buffer> {
+    unsafe { Buffer::from_napi_value(env.0, self.raw_value) }
+  }
+}
+
+/// Assemble bytes in Rust and turn them into a single `Uint8Array`, for example for incremental serialization.
+///
+/// The bytes are appended into one growable allocation, which is taken over by the `Uint8Array` without copying or shrinking when built.
+#[derive(Debug, Default, Clone)]
+pub struct Uint8ArrayBuilder {
+  inner: Vec<u8>,
+}
+
+impl Uint8ArrayBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      inner: Vec::with_capacity(capacity),
+    }
+  }
+
+  /// Append `data` to the end of the builder.
+  pub fn append<D: AsRef<[u8]>>(&mut self, data: D) -> &mut Self {
+    self.inner.extend_from_slice(data.as_ref());
+    self
+  }
+
+  /// Append a single byte to the end of the builder.
+  pub fn push(&mut self, byte: u8) -> &mut Self {
+    self.inner.push(byte);
+    self
+  }
+
+  /// Reserve capacity for at least `additional` more bytes.
+  pub fn reserve(&mut self, additional: usize) -> &mut Self {
+    self.inner.reserve(additional);
+    self
+  }
+
+  pub fn len(&self) -> usize {
+    self.inner.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.inner.is_empty()
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.inner.capacity()
+  }
+
+  /// Create the JavaScript `Uint8Array` from the appended bytes.
+  pub fn build(self, env: &Env) -> Result<Uint8ArraySlice<'_>> {
+    Uint8ArraySlice::from_data(env, self.inner)
+  }
+
+  /// Create a JavaScript `Buffer` from the appended bytes.
+  pub fn build_buffer(self, env: &Env) -> Result<BufferSlice<'_>> {
+    BufferSlice::from_data(env, self.inner)
+  }
+}
+
+impl AsRef<[u8]> for Uint8ArrayBuilder {
+  fn as_ref(&self) -> &[u8] {
+    &self.inner
+  }
+}
+
+impl std::io::Write for Uint8ArrayBuilder {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.inner.extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+impl From<Uint8ArrayBuilder> for Uint8Array {
+  fn from(builder: Uint8ArrayBuilder) -> Self {
+    Uint8Array::from_vec_with_capacity(builder.inner)
+  }
 }
 
 #[derive(Clone, Copy)]
@@ -1525,6 +2690,14 @@ impl FromNapiValue for Uint8ClampedSlice<'_> {
         format!("Expected $name, got {}", typed_array_type),
       ));
     }
+    if !data.is_null() {
+      track_slice_borrow(
+        env,
+        array_buffer,
+        data.cast::<u8>().wrapping_sub(byte_offset),
+        "Uint8ClampedSlice",
+      );
+    }
     Ok(Self {
       inner: if length == 0 {
         NonNull::dangling()
@@ -1584,6 +2757,8 @@ impl AsRef<[u8]> for Uint8ClampedSlice<'_> {
   }
 }
 
+impl_binary_fmt!(Uint8ClampedSlice<'_>, "Uint8ClampedSlice", |this| ownership: "JavaScript", length: this.length, bytes: this);
+
 impl Deref for Uint8ClampedSlice<'_> {
   type Target = [u8];
 
@@ -1607,11 +2782,7 @@ impl<'env> Uint8ClampedSlice<'env> {
     let len = data.len();
 
     // tell V8 how many bytes live outside the JS heap
-    let mut _dummy = 0;
-    check_status!(
-      unsafe { sys::napi_adjust_external_memory(env.0, len as i64, &mut _dummy) },
-      "adjust external memory"
-    )?;
+    adjust_external_memory(env.0, len as i64)?;
 
     let mut status = unsafe {
       let cap = data.capacity();
@@ -1625,7 +2796,7 @@ impl<'env> Uint8ClampedSlice<'env> {
       )
     };
 
-    if status == napi_sys::Status::napi_no_external_buffers_allowed {
+    if external_buffers_disallowed(status) {
       // Fallback: allocate a fresh ArrayBuffer
       let mut inner_data = unsafe { Vec::from_raw_parts(inner_ptr, len, len) };
       let mut underlying_data = ptr::null_mut();
@@ -1636,14 +2807,11 @@ impl<'env> Uint8ClampedSlice<'env> {
       inner_ptr = underlying_data.cast();
     } else {
       mem::forget(data);
+      #[cfg(not(feature = "minimal"))]
+      register_backing_ptr(inner_ptr, len);
     }
     check_status!(status, "Failed to create buffer slice from data")?;
 
-    #[cfg(all(debug_assertions, not(windows)))]
-    {
-      register_backing_ptr(inner_ptr); // inner_ptr is final backing store
-    }
-
     // create TypedArray
     let mut napi_val = ptr::null_mut();
     check_status!(
@@ -1704,8 +2872,6 @@ impl<'env> Uint8ClampedSlice<'env> {
         "Borrowed data should not be null".to_owned(),
       ));
     }
-    #[cfg(all(debug_assertions, not(windows)))]
-    register_backing_ptr(data);
     let hint_ptr = Box::into_raw(Box::new((finalize_hint, finalize_callback)));
     let mut arraybuffer_value = ptr::null_mut();
     let mut status = unsafe {
@@ -1718,7 +2884,9 @@ impl<'env> Uint8ClampedSlice<'env> {
         &mut arraybuffer_value,
       )
     };
-    status = if status == sys::Status::napi_no_external_buffers_allowed {
+    // the copy of the Electron fallback is owned by JavaScript
+    let external = !external_buffers_disallowed(status);
+    status = if !external {
       let (hint, finalize) = *Box::from_raw(hint_ptr);
       let mut underlying_data = ptr::null_mut();
       let status = unsafe {
@@ -1731,6 +2899,10 @@ impl<'env> Uint8ClampedSlice<'env> {
       status
     };
     check_status!(status, "Failed to create arraybuffer from data")?;
+    #[cfg(not(feature = "minimal"))]
+    if external {
+      register_backing_ptr(data, len);
+    }
 
     let mut napi_val = ptr::null_mut();
     check_status!(
@@ -1762,44 +2934,15 @@ impl<'env> Uint8ClampedSlice<'env> {
 
   /// Copy data from a `&[u8]` and create a `Uint8ClampedSlice` from it.
   pub fn copy_from<D: AsRef<[u8]>>(env: &Env, data: D) -> Result<Self> {
-    let data = data.as_ref();
-    let len = data.len();
-    let mut arraybuffer_value = ptr::null_mut();
-    let mut underlying_data = ptr::null_mut();
-
-    check_status!(
-      unsafe {
-        sys::napi_create_arraybuffer(env.0, len, &mut underlying_data, &mut arraybuffer_value)
-      },
-      "Failed to create ArrayBuffer"
-    )?;
-
-    let mut napi_val = ptr::null_mut();
-    check_status!(
-      unsafe {
-        sys::napi_create_typedarray(
-          env.0,
-          TypedArrayType::Uint8Clamped as i32,
-          len,
-          arraybuffer_value,
-          0,
-          &mut napi_val,
-        )
-      },
-      "Create TypedArray failed"
-    )?;
+    Self::from_iter(env, data.as_ref().iter().copied())
+  }
 
-    Ok(Self {
-      inner: if len == 0 {
-        NonNull::dangling()
-      } else {
-        unsafe { NonNull::new_unchecked(underlying_data.cast()) }
-      },
-      length: len,
-      raw_value: napi_val,
-      env: env.0,
-      _marker: PhantomData,
-    })
+  /// Create from the elements of `iter`, see [`Uint8ArraySlice::from_iter`].
+  pub fn from_iter<I: IntoIterator<Item = u8>>(env: &Env, iter: I) -> Result<Self> {
+    match collect_into_arraybuffer(env, iter.into_iter())? {
+      Collected::ArrayBuffer(arraybuffer, len) => Self::from_arraybuffer(&arraybuffer, 0, len),
+      Collected::Vec(data) => Self::from_data(env, data),
+    }
   }
 
   /// Create from `ArrayBuffer`
@@ -1849,6 +2992,28 @@ impl<'env> Uint8ClampedSlice<'env> {
     })
   }
 
+  /// Create a view of the elements from `begin` to `end` (exclusive) on the same `ArrayBuffer`, without copying.
+  ///
+  /// Like `TypedArray.prototype.subarray` in JavaScript, but out of range indexes are errors instead of being clamped.
+  pub fn subarray(&self, begin: usize, end: usize) -> Result<Uint8ClampedSlice<'env>> {
+    let subarray = create_subarray(
+      self.env,
+      self.raw_value,
+      TypedArrayType::Uint8Clamped,
+      self.length,
+      begin,
+      end,
+    )?;
+    unsafe { Self::from_napi_value(self.env, subarray) }
+  }
+
+  /// Copy `data` into the elements starting at `offset`, for example to fill an output array provided by the caller.
+  ///
+  /// Returns an error if `data` doesn't fit in the array, nothing is written in that case.
+  pub fn copy_from_slice_at(&mut self, offset: usize, data: &[u8]) -> Result<()> {
+    copy_from_slice_at(self, offset, data)
+  }
+
   /// Convert a `Uint8ClampedSlice` to a `Uint8ClampedArray`.
   pub fn into_typed_array(self, env: &Env) -> Result<Self> {
     unsafe { Self::from_napi_value(env.0, self.raw_value) }
@@ -1921,3 +3086,184 @@ impl<T: Into<Vec<u64>>> From<T> for BigUint64Array {
     BigUint64Array::new(data.into())
   }
 }
+
+#[derive(Clone, Copy)]
+/// Any JavaScript `TypedArray`, borrowed as the zero copy slice of its element type.
+///
+/// Accept it in a `#[napi]` function to take all kinds of `TypedArray` and dispatch on the element type at runtime.
+/// Like the slices, it can only be used in non-async context and the lifetime is bound to the fn closure.
+pub enum AnyTypedArray<'env> {
+  Int8(Int8ArraySlice<'env>),
+  Uint8(Uint8ArraySlice<'env>),
+  Uint8Clamped(Uint8ClampedSlice<'env>),
+  Int16(Int16ArraySlice<'env>),
+  Uint16(Uint16ArraySlice<'env>),
+  Int32(Int32ArraySlice<'env>),
+  Uint32(Uint32ArraySlice<'env>),
+  Float32(Float32ArraySlice<'env>),
+  Float64(Float64ArraySlice<'env>),
+  #[cfg(feature = "napi6")]
+  BigInt64(BigInt64ArraySlice<'env>),
+  #[cfg(feature = "napi6")]
+  BigUint64(BigUint64ArraySlice<'env>),
+}
+
+macro_rules! any_typed_array_dispatch {
+  ($value:expr, $slice:ident => $body:expr) => {
+    match $value {
+      AnyTypedArray::Int8($slice) => $body,
+      AnyTypedArray::Uint8($slice) => $body,
+      AnyTypedArray::Uint8Clamped($slice) => $body,
+      AnyTypedArray::Int16($slice) => $body,
+      AnyTypedArray::Uint16($slice) => $body,
+      AnyTypedArray::Int32($slice) => $body,
+      AnyTypedArray::Uint32($slice) => $body,
+      AnyTypedArray::Float32($slice) => $body,
+      AnyTypedArray::Float64($slice) => $body,
+      #[cfg(feature = "napi6")]
+      AnyTypedArray::BigInt64($slice) => $body,
+      #[cfg(feature = "napi6")]
+      AnyTypedArray::BigUint64($slice) => $body,
+    }
+  };
+}
+
+impl AnyTypedArray<'_> {
+  /// The element type of the `TypedArray`.
+  pub fn typed_array_type(&self) -> TypedArrayType {
+    match self {
+      Self::Int8(_) => TypedArrayType::Int8,
+      Self::Uint8(_) => TypedArrayType::Uint8,
+      Self::Uint8Clamped(_) => TypedArrayType::Uint8Clamped,
+      Self::Int16(_) => TypedArrayType::Int16,
+      Self::Uint16(_) => TypedArrayType::Uint16,
+      Self::Int32(_) => TypedArrayType::Int32,
+      Self::Uint32(_) => TypedArrayType::Uint32,
+      Self::Float32(_) => TypedArrayType::Float32,
+      Self::Float64(_) => TypedArrayType::Float64,
+      #[cfg(feature = "napi6")]
+      Self::BigInt64(_) => TypedArrayType::BigInt64,
+      #[cfg(feature = "napi6")]
+      Self::BigUint64(_) => TypedArrayType::BigUint64,
+    }
+  }
+
+  /// Number of the elements.
+  pub fn len(&self) -> usize {
+    any_typed_array_dispatch!(self, slice => slice.length)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Number of the bytes.
+  pub fn byte_length(&self) -> usize {
+    any_typed_array_dispatch!(self, slice => mem::size_of_val::<[_]>(slice))
+  }
+
+  /// The underlying memory as bytes, regardless of the element type.
+  pub fn as_bytes(&self) -> &[u8] {
+    let data = any_typed_array_dispatch!(self, slice => slice.as_ptr().cast::<u8>());
+    unsafe { slice::from_raw_parts(data, self.byte_length()) }
+  }
+}
+
+impl_binary_fmt!(AnyTypedArray<'_>, &format!("{:?}Array", this.typed_array_type()), |this| ownership: "JavaScript", length: this.len(), bytes: this.as_bytes());
+
+impl<'env> JsValue<'env> for AnyTypedArray<'env> {
+  fn value(&self) -> Value {
+    any_typed_array_dispatch!(self, slice => slice.value())
+  }
+}
+
+impl<'env> JsObjectValue<'env> for AnyTypedArray<'env> {}
+
+impl TypeName for AnyTypedArray<'_> {
+  fn type_name() -> &'static str {
+    "TypedArray"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for AnyTypedArray<'_> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let mut is_typedarray = false;
+    check_status!(
+      unsafe { sys::napi_is_typedarray(env, napi_val, &mut is_typedarray) },
+      "Failed to validate typed buffer"
+    )?;
+    if !is_typedarray {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Expected a TypedArray value".to_owned(),
+      ));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
+impl FromNapiValue for AnyTypedArray<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { Self::validate(env, napi_val) }?;
+    let mut typed_array_type = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_typedarray_info(
+          env,
+          napi_val,
+          &mut typed_array_type,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Get TypedArray info failed"
+    )?;
+    Ok(match TypedArrayType::from(typed_array_type) {
+      TypedArrayType::Int8 => Self::Int8(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?),
+      TypedArrayType::Uint8 => {
+        Self::Uint8(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Uint8Clamped => {
+        Self::Uint8Clamped(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Int16 => {
+        Self::Int16(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Uint16 => {
+        Self::Uint16(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Int32 => {
+        Self::Int32(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Uint32 => {
+        Self::Uint32(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Float32 => {
+        Self::Float32(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Float64 => {
+        Self::Float64(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      #[cfg(feature = "napi6")]
+      TypedArrayType::BigInt64 => {
+        Self::BigInt64(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      #[cfg(feature = "napi6")]
+      TypedArrayType::BigUint64 => {
+        Self::BigUint64(unsafe { FromNapiValue::from_napi_value(env, napi_val) }?)
+      }
+      TypedArrayType::Unknown => {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Unsupported TypedArray type {}", typed_array_type),
+        ))
+      }
+    })
+  }
+}
@@ -8,6 +8,7 @@
 ///     a.get_u128().1 + b.get_u128().1 // We have opportunity to check if the `u128` has lost precision
 /// }
 /// ```
+use std::hash::{Hash, Hasher};
 use std::ptr;
 
 use crate::{check_status, sys};
@@ -17,6 +18,7 @@ use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
 /// i64 is converted to `Number`
 #[repr(transparent)]
 #[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct i64n(pub i64);
 
 /// <https://nodejs.org/api/n-api.html#napi_create_bigint_words>
@@ -28,6 +30,33 @@ pub struct BigInt {
   pub words: Vec<u64>,
 }
 
+impl BigInt {
+  /// The words without the leading zero words, zero is always positive.
+  fn significant(&self) -> (bool, &[u64]) {
+    let len = self
+      .words
+      .iter()
+      .rposition(|word| *word != 0)
+      .map_or(0, |index| index + 1);
+    (self.sign_bit && len != 0, &self.words[..len])
+  }
+}
+
+/// Compared by value, the zero words at the end of `words` are ignored.
+impl PartialEq for BigInt {
+  fn eq(&self, other: &Self) -> bool {
+    self.significant() == other.significant()
+  }
+}
+
+impl Eq for BigInt {}
+
+impl Hash for BigInt {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.significant().hash(state);
+  }
+}
+
 impl TypeName for BigInt {
   fn type_name() -> &'static str {
     "BigInt"
@@ -0,0 +1,169 @@
+use std::fmt::{self, Write};
+use std::ops::{Bound, RangeBounds};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::sys;
+
+/// Same as the `buffer.INSPECT_MAX_BYTES` of Node.js.
+const PREVIEW_MAX_BYTES: usize = 50;
+
+static EXTERNAL_BUFFER_FALLBACK: AtomicBool = AtomicBool::new(false);
+
+/// Whether the runtime refused an external buffer created from Rust memory, like Electron does,
+/// so the data was copied into a JavaScript allocated buffer instead.
+///
+/// Once it happens, the writes from JavaScript to the buffers created from Rust memory are not visible to Rust.
+pub fn external_buffer_fallback_taken() -> bool {
+  EXTERNAL_BUFFER_FALLBACK.load(Ordering::Relaxed)
+}
+
+/// Check the status of creating an external buffer, and record it if the copy fallback has to be taken.
+#[inline]
+pub(crate) fn external_buffers_disallowed(status: sys::napi_status) -> bool {
+  let disallowed = status == sys::Status::napi_no_external_buffers_allowed;
+  if disallowed {
+    EXTERNAL_BUFFER_FALLBACK.store(true, Ordering::Relaxed);
+    #[cfg(not(feature = "minimal"))]
+    crate::buffer_diagnostics::emit(crate::buffer_diagnostics::BufferEvent::FallbackCopy);
+  }
+  disallowed
+}
+
+/// The classic `hexdump -C` format of the bytes in `range`, 16 bytes per line with the offsets and the ASCII column.
+///
+/// `range` is clamped to the bounds of `bytes`.
+pub(crate) fn hexdump<R: RangeBounds<usize>>(bytes: &[u8], range: R) -> String {
+  let start = match range.start_bound() {
+    Bound::Included(start) => *start,
+    Bound::Excluded(start) => start.saturating_add(1),
+    Bound::Unbounded => 0,
+  }
+  .min(bytes.len());
+  let end = match range.end_bound() {
+    Bound::Included(end) => end.saturating_add(1),
+    Bound::Excluded(end) => *end,
+    Bound::Unbounded => bytes.len(),
+  }
+  .clamp(start, bytes.len());
+  let mut output = String::new();
+  for (line, chunk) in bytes[start..end].chunks(16).enumerate() {
+    let _ = write!(output, "{:08x} ", start + line * 16);
+    for i in 0..16 {
+      if i % 8 == 0 {
+        output.push(' ');
+      }
+      match chunk.get(i) {
+        Some(byte) => {
+          let _ = write!(output, "{:02x} ", byte);
+        }
+        None => output.push_str("   "),
+      }
+    }
+    output.push_str(" |");
+    output.extend(chunk.iter().map(|byte| {
+      if byte.is_ascii_graphic() || *byte == b' ' {
+        *byte as char
+      } else {
+        '.'
+      }
+    }));
+    output.push_str("|\n");
+  }
+  output
+}
+
+pub(crate) fn as_bytes<T>(data: &[T]) -> &[u8] {
+  unsafe { std::slice::from_raw_parts(data.as_ptr().cast(), std::mem::size_of_val(data)) }
+}
+
+/// The bytes and the description of a binary value, formatted by the `Debug` and `Display` impls.
+pub(crate) struct BinaryFmt<'a> {
+  pub(crate) type_name: &'a str,
+  /// Who owns the memory, `JavaScript`, `Rust` or the external owner
+  pub(crate) ownership: &'static str,
+  /// Number of the elements
+  pub(crate) length: usize,
+  pub(crate) bytes: &'a [u8],
+}
+
+impl BinaryFmt<'_> {
+  pub(crate) fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut debug = f.debug_struct(self.type_name);
+    debug
+      .field("length", &self.length)
+      .field("byte_length", &self.bytes.len())
+      .field("ownership", &self.ownership);
+    if self.ownership != "JavaScript" && external_buffer_fallback_taken() {
+      debug.field("external_buffer_fallback", &true);
+    }
+    debug.field("data", &HexPreview(self.bytes)).finish()
+  }
+
+  pub(crate) fn display(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "<{}({})", self.type_name, self.length)?;
+    if !self.bytes.is_empty() {
+      write!(f, " {:?}", HexPreview(self.bytes))?;
+    }
+    f.write_char('>')
+  }
+}
+
+struct HexPreview<'a>(&'a [u8]);
+
+impl fmt::Debug for HexPreview<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, byte) in self.0.iter().take(PREVIEW_MAX_BYTES).enumerate() {
+      if i != 0 {
+        f.write_char(' ')?;
+      }
+      write!(f, "{:02x}", byte)?;
+    }
+    if self.0.len() > PREVIEW_MAX_BYTES {
+      write!(f, " ... {} more bytes", self.0.len() - PREVIEW_MAX_BYTES)?;
+    }
+    Ok(())
+  }
+}
+
+/// Implement `Debug`, `Display` and `hexdump` for a binary value.
+///
+/// `$this` is bound to `&self` in the `ownership`, `length` and `bytes` expressions.
+macro_rules! impl_binary_fmt {
+  ($ty:ty, $type_name:expr, |$this:ident| ownership: $ownership:expr, length: $length:expr, bytes: $bytes:expr $(,)?) => {
+    impl std::fmt::Debug for $ty {
+      fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let $this = self;
+        $crate::bindgen_runtime::BinaryFmt {
+          type_name: $type_name,
+          ownership: $ownership,
+          length: $length,
+          bytes: $bytes,
+        }
+        .debug(f)
+      }
+    }
+
+    impl std::fmt::Display for $ty {
+      fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let $this = self;
+        $crate::bindgen_runtime::BinaryFmt {
+          type_name: $type_name,
+          ownership: $ownership,
+          length: $length,
+          bytes: $bytes,
+        }
+        .display(f)
+      }
+    }
+
+    impl $ty {
+      /// The `hexdump -C` style dump of the bytes in `range`, the offsets are in bytes and `range` is clamped to the bounds.
+      pub fn hexdump<R: std::ops::RangeBounds<usize>>(&self, range: R) -> String {
+        let $this = self;
+        $crate::bindgen_runtime::hexdump($bytes, range)
+      }
+    }
+  };
+}
+
+pub(crate) use impl_binary_fmt;
@@ -0,0 +1,200 @@
+use std::marker::PhantomData;
+use std::ptr;
+
+use super::arraybuffer::get_global_constructor;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+use crate::bindgen_prelude::{ArrayBuffer, Promise, PromiseRaw, Uint8Array, Uint8ArraySlice};
+use crate::{
+  bindgen_prelude::{
+    BufferSlice, FromNapiValue, JsObjectValue, ToNapiValue, TypeName, ValidateNapiValue,
+  },
+  check_status, sys, Env, Error, JsValue, Result, Status, Value, ValueType,
+};
+
+/// The `Blob` of `node:buffer`, which is also the global `Blob`.
+///
+/// It can be accepted and returned by the `#[napi]` functions, and its bytes are read with [`Blob::array_buffer`].
+pub struct Blob<'env> {
+  pub(crate) value: sys::napi_value,
+  pub(crate) env: sys::napi_env,
+  _marker: PhantomData<&'env ()>,
+}
+
+impl<'env> JsValue<'env> for Blob<'env> {
+  fn value(&self) -> Value {
+    Value {
+      env: self.env,
+      value: self.value,
+      value_type: ValueType::Object,
+    }
+  }
+}
+
+impl<'env> JsObjectValue<'env> for Blob<'env> {}
+
+impl TypeName for Blob<'_> {
+  fn type_name() -> &'static str {
+    "Blob"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for Blob<'_> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let constructor = get_global_constructor(env, c"Blob")?;
+    let mut is_instance = false;
+    check_status!(
+      unsafe { sys::napi_instanceof(env, napi_val, constructor, &mut is_instance) },
+      "Check Blob instance failed"
+    )?;
+    if !is_instance {
+      return Err(Error::new(Status::InvalidArg, "Value is not a Blob"));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
+impl FromNapiValue for Blob<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Self {
+      value: napi_val,
+      env,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<'env> Blob<'env> {
+  /// Create a `Blob` of a copy of `data`, like `new Blob([data], { type })`.
+  pub fn new<D: AsRef<[u8]>>(env: &'env Env, data: D, content_type: Option<&str>) -> Result<Self> {
+    let constructor = get_global_constructor(env.0, c"Blob")?;
+    let buffer = BufferSlice::copy_from(env, data)?;
+    let mut parts = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_array_with_length(env.0, 1, &mut parts) },
+      "Create the parts of Blob failed"
+    )?;
+    check_status!(
+      unsafe { sys::napi_set_element(env.0, parts, 0, buffer.raw_value) },
+      "Set the parts of Blob failed"
+    )?;
+    let mut options = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env.0, &mut options) },
+      "Create the options of Blob failed"
+    )?;
+    if let Some(content_type) = content_type {
+      let content_type = unsafe { ToNapiValue::to_napi_value(env.0, content_type)? };
+      check_status!(
+        unsafe { sys::napi_set_named_property(env.0, options, c"type".as_ptr(), content_type) },
+        "Set the type of Blob failed"
+      )?;
+    }
+    let mut blob = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_new_instance(env.0, constructor, 2, [parts, options].as_ptr(), &mut blob)
+      },
+      "Create Blob failed"
+    )?;
+    unsafe { Self::from_napi_value(env.0, blob) }
+  }
+
+  /// The size of the `Blob` in bytes.
+  pub fn size(&self) -> Result<usize> {
+    let size = self.get_named_property_unchecked::<f64>("size")?;
+    Ok(size as usize)
+  }
+
+  /// The MIME type of the `Blob`, it's empty if unknown.
+  pub fn type_(&self) -> Result<String> {
+    self.get_named_property_unchecked::<String>("type")
+  }
+
+  /// A new `Blob` of the bytes from `start` to `end` (exclusive), like `blob.slice(start, end, contentType)`.
+  ///
+  /// The negative offsets count from the end of the `Blob`, and `None` is the start or the end of the `Blob`.
+  pub fn slice(
+    &self,
+    start: Option<i64>,
+    end: Option<i64>,
+    content_type: Option<&str>,
+  ) -> Result<Blob<'env>> {
+    let mut slice = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_named_property(self.env, self.value, c"slice".as_ptr(), &mut slice) },
+      "Get slice of Blob failed"
+    )?;
+    let mut args = [ptr::null_mut(); 3];
+    for (arg, offset) in args.iter_mut().zip([start, end]) {
+      *arg = match offset {
+        Some(offset) => unsafe { ToNapiValue::to_napi_value(self.env, offset)? },
+        None => undefined(self.env)?,
+      };
+    }
+    args[2] = match content_type {
+      Some(content_type) => unsafe { ToNapiValue::to_napi_value(self.env, content_type)? },
+      None => undefined(self.env)?,
+    };
+    let mut blob = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_call_function(self.env, self.value, slice, 3, args.as_ptr(), &mut blob) },
+      "Call slice of Blob failed"
+    )?;
+    unsafe { Self::from_napi_value(self.env, blob) }
+  }
+
+  #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+  /// Read the bytes of the `Blob` with `blob.arrayBuffer()`, the `Promise` resolves to a `Uint8Array` over the `ArrayBuffer`.
+  ///
+  /// The bytes are not copied again after `arrayBuffer()`, and the `Promise` can be awaited in Rust on the tokio runtime.
+  pub fn array_buffer(&self) -> Result<Promise<Uint8Array>> {
+    let mut array_buffer = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_get_named_property(
+          self.env,
+          self.value,
+          c"arrayBuffer".as_ptr(),
+          &mut array_buffer,
+        )
+      },
+      "Get arrayBuffer of Blob failed"
+    )?;
+    let mut promise = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          self.env,
+          self.value,
+          array_buffer,
+          0,
+          ptr::null(),
+          &mut promise,
+        )
+      },
+      "Call arrayBuffer of Blob failed"
+    )?;
+    let bytes = PromiseRaw::<ArrayBuffer<'static>>::new(self.env, promise).then(|ctx| {
+      let len = ctx.value.len();
+      Ok(
+        Uint8ArraySlice::from_arraybuffer(&ctx.value, 0, len)?
+          .value()
+          .value,
+      )
+    })?;
+    unsafe { Promise::from_napi_value(self.env, bytes.inner) }
+  }
+}
+
+fn undefined(env: sys::napi_env) -> Result<sys::napi_value> {
+  let mut undefined = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_undefined(env, &mut undefined) },
+    "Get undefined failed"
+  )?;
+  Ok(undefined)
+}
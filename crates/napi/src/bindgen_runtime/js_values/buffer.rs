@@ -1,61 +1,187 @@
-#[cfg(all(debug_assertions, not(windows)))]
-use std::collections::HashSet;
 use std::ffi::c_void;
+use std::fs::File;
 use std::mem;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Bound, Deref, DerefMut, RangeBounds};
+use std::path::Path;
 use std::ptr::{self, NonNull};
 use std::slice;
-#[cfg(all(debug_assertions, not(windows)))]
-use std::sync::Mutex;
+use std::sync::Arc;
 
+use super::external_memory::adjust_external_memory;
 use crate::{
   bindgen_prelude::*, check_status, env::EMPTY_VEC, sys, JsValue, Result, Value, ValueType,
 };
 
-#[cfg(all(debug_assertions, not(windows)))]
-thread_local! {
-  pub (crate) static BUFFER_DATA: Mutex<HashSet<*mut u8>> = Default::default();
-}
-
-#[cfg(all(debug_assertions, not(windows)))]
-#[inline]
-pub fn register_backing_ptr(ptr: *mut u8) {
-  if ptr.is_null() {
-    return;
-  } // 0-length buffers use NULL
-  BUFFER_DATA.with(|buffer_data| {
-    let mut set = buffer_data.lock().unwrap();
-    if !set.insert(ptr) {
-      panic!(
-        "Share the same data between different buffers is not allowed, \
-                    see: https://github.com/nodejs/node/issues/32463#issuecomment-631974747"
-      );
+#[cfg(not(feature = "minimal"))]
+pub use crate::buffer_diagnostics::{register_backing_ptr, unregister_backing_ptr};
+
+#[cfg(not(feature = "minimal"))]
+use crate::buffer_diagnostics::{emit, BufferEvent};
+
+// The `start..end` of `range`, unchecked
+fn bounds_of<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+  let start = match range.start_bound() {
+    Bound::Included(start) => *start,
+    Bound::Excluded(start) => start.saturating_add(1),
+    Bound::Unbounded => 0,
+  };
+  let end = match range.end_bound() {
+    Bound::Included(end) => end.saturating_add(1),
+    Bound::Excluded(end) => *end,
+    Bound::Unbounded => len,
+  };
+  (start, end)
+}
+
+// Open the file and resolve `range` clamped to its length, into the start and the length to read
+fn open_file_range<R: RangeBounds<u64>>(path: &Path, range: R) -> Result<(File, u64, usize)> {
+  let file = File::open(path).map_err(|err| file_error(path, err))?;
+  let file_len = file.metadata().map_err(|err| file_error(path, err))?.len();
+  let start = match range.start_bound() {
+    Bound::Included(start) => *start,
+    Bound::Excluded(start) => start.saturating_add(1),
+    Bound::Unbounded => 0,
+  };
+  let end = match range.end_bound() {
+    Bound::Included(end) => end.saturating_add(1),
+    Bound::Excluded(end) => *end,
+    Bound::Unbounded => file_len,
+  }
+  .min(file_len);
+  let len = usize::try_from(end.saturating_sub(start)).map_err(|_| {
+    Error::new(
+      Status::InvalidArg,
+      format!("The range of {} is too large", path.display()),
+    )
+  })?;
+  Ok((file, start, len))
+}
+
+// Fill `buf` with the bytes of `file` from `offset`, without moving the cursor of the file where it's supported
+fn read_file_at(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+  #[cfg(unix)]
+  {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+  }
+  #[cfg(windows)]
+  {
+    let mut read = 0;
+    while read < buf.len() {
+      match std::os::windows::fs::FileExt::seek_read(file, &mut buf[read..], offset + read as u64)?
+      {
+        0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+        n => read += n,
+      }
     }
-  });
+    Ok(())
+  }
+  #[cfg(not(any(unix, windows)))]
+  {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = file;
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)
+  }
+}
+
+fn file_error(path: &Path, err: std::io::Error) -> Error {
+  Error::new(
+    Status::GenericFailure,
+    format!("Failed to read {}: {}", path.display(), err),
+  )
 }
 
-#[cfg(all(debug_assertions, not(windows)))]
-#[inline]
-pub fn unregister_backing_ptr(ptr: *mut u8) {
-  if ptr.is_null() {
-    return;
+// `range` of `data` like the `start` and `end` arguments of `Buffer.compare`:
+// the end out of bounds is an error, and the start after the end is empty.
+fn compare_range_of<'a, R: RangeBounds<usize>>(
+  data: &'a [u8],
+  range: &R,
+  name: &str,
+) -> Result<&'a [u8]> {
+  let (start, end) = bounds_of(range, data.len());
+  if end > data.len() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "The {} end {} is out of the bounds of {} bytes",
+        name,
+        end,
+        data.len()
+      ),
+    ));
   }
-  BUFFER_DATA.with(|buffer_data| {
-    let mut set = buffer_data.lock().unwrap();
-    set.remove(&ptr);
-  });
+  Ok(data.get(start..end).unwrap_or_default())
 }
 
-#[cfg(feature = "trace-buffer-drops")]
-macro_rules! trace_drop {
-    ($($arg:tt)*) => {
-        eprintln!("[buffer-drop] {}", format_args!($($arg)*));
-    };
+// `buf.indexOf(value, byteOffset)` of Node.js
+fn index_of(data: &[u8], value: &[u8], byte_offset: i64) -> Option<usize> {
+  let len = data.len() as i64;
+  let offset = if byte_offset < 0 {
+    (len + byte_offset).max(0)
+  } else {
+    byte_offset.min(len)
+  } as usize;
+  let haystack = &data[offset..];
+  match value {
+    [] => Some(offset),
+    [byte] => haystack.iter().position(|b| b == byte).map(|i| i + offset),
+    [first, rest @ ..] => {
+      let last_start = haystack.len().checked_sub(value.len())?;
+      let mut start = 0;
+      while start <= last_start {
+        let found = haystack[start..=last_start]
+          .iter()
+          .position(|b| b == first)?;
+        start += found;
+        if &haystack[start + 1..start + value.len()] == rest {
+          return Some(start + offset);
+        }
+        start += 1;
+      }
+      None
+    }
+  }
 }
 
-#[cfg(not(feature = "trace-buffer-drops"))]
-macro_rules! trace_drop {
-  ($($arg:tt)*) => {};
+macro_rules! impl_node_buffer_ops {
+  ($ty:ty) => {
+    impl $ty {
+      /// Compare the bytes with `target` like `Buffer.compare(buf, target)`:
+      /// byte by byte, and a prefix is less than the longer bytes.
+      pub fn compare<T: AsRef<[u8]>>(&self, target: T) -> std::cmp::Ordering {
+        self.as_ref().cmp(target.as_ref())
+      }
+
+      /// Compare the bytes in `source_range` with the bytes of `target` in `target_range`,
+      /// like `buf.compare(target, targetStart, targetEnd, sourceStart, sourceEnd)`.
+      ///
+      /// A range which ends out of bounds is an error, and a range whose start is at or after its end is empty.
+      pub fn compare_range<T: AsRef<[u8]>, TR: RangeBounds<usize>, SR: RangeBounds<usize>>(
+        &self,
+        target: T,
+        target_range: TR,
+        source_range: SR,
+      ) -> Result<std::cmp::Ordering> {
+        let target = compare_range_of(target.as_ref(), &target_range, "target")?;
+        let source = compare_range_of(self.as_ref(), &source_range, "source")?;
+        Ok(source.cmp(target))
+      }
+
+      /// Whether the bytes start with `prefix`.
+      pub fn starts_with<T: AsRef<[u8]>>(&self, prefix: T) -> bool {
+        self.as_ref().starts_with(prefix.as_ref())
+      }
+
+      /// The index of the first occurrence of `value` at or after `byte_offset`, like `buf.indexOf(value, byteOffset)`.
+      ///
+      /// A negative `byte_offset` counts from the end of the bytes.
+      /// An empty `value` is found at `byte_offset`, clamped to the length.
+      pub fn index_of<T: AsRef<[u8]>>(&self, value: T, byte_offset: i64) -> Option<usize> {
+        index_of(self.as_ref(), value.as_ref(), byte_offset)
+      }
+    }
+  };
 }
 
 /// Zero copy buffer slice shared between Rust and Node.js.
@@ -79,11 +205,7 @@ impl<'env> BufferSlice<'env> {
     let mut vec = data.into();
     let len = vec.len();
 
-    let mut _dummy = 0;
-    check_status!(
-      unsafe { sys::napi_adjust_external_memory(env.0, len as i64, &mut _dummy) },
-      "adjust external memory"
-    )?;
+    adjust_external_memory(env.0, len as i64)?;
 
     if len == 0 {
       check_status!(
@@ -112,7 +234,7 @@ impl<'env> BufferSlice<'env> {
 
     let mut backing_ptr: *mut c_void = src_ptr.cast();
 
-    if status == sys::Status::napi_no_external_buffers_allowed {
+    if external_buffers_disallowed(status) {
       let mut copy_ptr: *mut c_void = ptr::null_mut();
       status = unsafe {
         sys::napi_create_buffer_copy(env.0, len, src_ptr.cast(), &mut copy_ptr, &mut js_value)
@@ -123,8 +245,11 @@ impl<'env> BufferSlice<'env> {
     mem::forget(vec);
     check_status!(status, "Failed to create BufferSlice")?;
 
-    #[cfg(all(debug_assertions, not(windows)))]
-    register_backing_ptr(backing_ptr.cast::<u8>());
+    // the copy of the Electron fallback is owned by JavaScript
+    #[cfg(not(feature = "minimal"))]
+    if backing_ptr == src_ptr.cast() {
+      register_backing_ptr(src_ptr, len);
+    }
 
     Ok(Self {
       inner: unsafe { slice::from_raw_parts_mut(backing_ptr.cast::<u8>(), len) },
@@ -177,7 +302,7 @@ impl<'env> BufferSlice<'env> {
 
     let mut copied_ptr: *mut c_void = ptr::null_mut();
 
-    if status == sys::Status::napi_no_external_buffers_allowed {
+    if external_buffers_disallowed(status) {
       let (hint, finalize) = *Box::from_raw(hint_ptr);
       status = sys::napi_create_buffer_copy(env.0, len, data.cast(), &mut copied_ptr, &mut buf);
       finalize(*env, hint);
@@ -185,14 +310,10 @@ impl<'env> BufferSlice<'env> {
 
     check_status!(status, "Failed to create buffer slice from data")?;
 
-    #[cfg(all(debug_assertions, not(windows)))]
-    {
-      let ptr_to_track: *mut u8 = if status == sys::Status::napi_no_external_buffers_allowed {
-        copied_ptr.cast() // new backing store from buffer-copy
-      } else {
-        data // original external buffer pointer
-      };
-      register_backing_ptr(ptr_to_track);
+    // the copy of the Electron fallback is owned by JavaScript
+    #[cfg(not(feature = "minimal"))]
+    if copied_ptr.is_null() {
+      register_backing_ptr(data, len);
     }
 
     Ok(Self {
@@ -241,12 +362,77 @@ impl<'env> BufferSlice<'env> {
     })
   }
 
+  /// Read the bytes of the file at `path` in `range` into a new JavaScript `Buffer`.
+  ///
+  /// The `Buffer` is allocated first and the file is read directly into it, without an intermediate `Vec<u8>`.
+  /// The range is clamped to the length of the file, like the `start` and `end` of `fs.createReadStream`.
+  /// It blocks the JavaScript thread while reading, use [`Buffer::from_file_async`] for the large files.
+  pub fn from_file<P: AsRef<Path>, R: RangeBounds<u64>>(
+    env: &'env Env,
+    path: P,
+    range: R,
+  ) -> Result<Self> {
+    let path = path.as_ref();
+    let (file, start, len) = open_file_range(path, range)?;
+    let mut js_value = ptr::null_mut();
+    let mut data = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_buffer(env.0, len, &mut data, &mut js_value) },
+      "Failed to create BufferSlice for the file"
+    )?;
+    let inner: &mut [u8] = if len == 0 {
+      &mut []
+    } else {
+      // SAFETY: `data` points to the freshly-allocated Buffer memory
+      unsafe { slice::from_raw_parts_mut(data.cast::<u8>(), len) }
+    };
+    read_file_at(&file, inner, start).map_err(|err| file_error(path, err))?;
+    Ok(Self {
+      inner,
+      raw_value: js_value,
+      env: env.0,
+    })
+  }
+
+  #[cfg(feature = "experimental")]
+  /// Create a `BufferSlice` over `length` bytes of an existing `ArrayBuffer` starting at `byte_offset`, without copying.
+  ///
+  /// Writes through the `BufferSlice` are visible in the `ArrayBuffer` and vice versa.
+  pub fn from_arraybuffer(
+    arraybuffer: &ArrayBuffer<'env>,
+    byte_offset: usize,
+    length: usize,
+  ) -> Result<Self> {
+    let env = arraybuffer.value.env;
+    let mut buffer = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::node_api_create_buffer_from_arraybuffer(
+          env,
+          arraybuffer.value.value,
+          byte_offset,
+          length,
+          &mut buffer,
+        )
+      },
+      "Failed to create BufferSlice from ArrayBuffer"
+    )?;
+    unsafe { Self::from_napi_value(env, buffer) }
+  }
+
   /// Convert a `BufferSlice` to a `Buffer`
   ///
   /// This will perform a `napi_create_reference` internally.
   pub fn into_buffer(self, env: &Env) -> Result<Buffer> {
     unsafe { Buffer::from_napi_value(env.0, self.raw_value) }
   }
+
+  /// Copy `data` into the bytes starting at `offset`, for example to fill an output buffer provided by the caller.
+  ///
+  /// Returns an error if `data` doesn't fit in the buffer, nothing is written in that case.
+  pub fn copy_from_slice_at(&mut self, offset: usize, data: &[u8]) -> Result<()> {
+    super::arraybuffer::copy_from_slice_at(self, offset, data)
+  }
 }
 
 impl<'env> JsValue<'env> for BufferSlice<'env> {
@@ -269,6 +455,32 @@ impl FromNapiValue for BufferSlice<'_> {
       unsafe { sys::napi_get_buffer_info(env, napi_val, &mut buf, &mut len) },
       "Failed to get Buffer pointer and length"
     )?;
+    #[cfg(feature = "slice-borrow-tracking")]
+    if !buf.is_null() {
+      // `Buffer` is a `Uint8Array`, get its `ArrayBuffer` from the TypedArray info
+      let mut array_buffer = ptr::null_mut();
+      let mut byte_offset = 0;
+      check_status!(
+        unsafe {
+          sys::napi_get_typedarray_info(
+            env,
+            napi_val,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut array_buffer,
+            &mut byte_offset,
+          )
+        },
+        "Failed to get the ArrayBuffer of Buffer"
+      )?;
+      track_slice_borrow(
+        env,
+        array_buffer,
+        buf.cast::<u8>().wrapping_sub(byte_offset),
+        "BufferSlice",
+      );
+    }
     // From the docs of `napi_get_buffer_info`:
     // > [out] data: The underlying data buffer of the node::Buffer. If length is 0, this may be
     // > NULL or any other pointer value.
@@ -328,6 +540,15 @@ impl AsRef<[u8]> for BufferSlice<'_> {
   }
 }
 
+impl AsMut<[u8]> for BufferSlice<'_> {
+  fn as_mut(&mut self) -> &mut [u8] {
+    self.inner
+  }
+}
+
+impl_binary_fmt!(BufferSlice<'_>, "BufferSlice", |this| ownership: "JavaScript", length: this.inner.len(), bytes: this.as_ref());
+impl_node_buffer_ops!(BufferSlice<'_>);
+
 impl Deref for BufferSlice<'_> {
   type Target = [u8];
 
@@ -355,20 +576,38 @@ pub struct Buffer {
   pub(crate) capacity: usize,
   raw: Option<(sys::napi_ref, sys::napi_env)>,
   owned_by_rust: bool,
+  // keeps the memory shared with the other Rust owners alive, it's released when the `Buffer` is dropped
+  shared: Option<SharedOwner>,
+}
+
+// The reference counted owner of the memory of a `Buffer` created by `Buffer::from_bytes` or `Buffer::from_shared`
+// The `Bytes` owner is never read, it's only held to be dropped
+#[allow(dead_code)]
+#[derive(Clone)]
+enum SharedOwner {
+  #[cfg(feature = "bytes")]
+  Bytes(bytes::Bytes),
+  Vec(Arc<Vec<u8>>),
 }
 
 impl Drop for Buffer {
   fn drop(&mut self) {
     // Fast-path: Buffer originated from Vec<u8>
     if self.raw.is_none() && self.owned_by_rust {
-      trace_drop!("from-vec len {}", self.len);
+      #[cfg(not(feature = "minimal"))]
+      if self.len != 0 {
+        unregister_backing_ptr(self.inner.as_ptr());
+      }
       unsafe {
         Vec::from_raw_parts(self.inner.as_ptr(), self.len, self.capacity);
       }
       return;
     }
 
-    let (ref_, env) = self.raw.unwrap();
+    // Backed by a `SharedOwner`, which is released with the `Buffer`
+    let Some((ref_, env)) = self.raw else {
+      return;
+    };
     if ref_.is_null() {
       return;
     }
@@ -379,7 +618,11 @@ impl Drop for Buffer {
       unsafe { sys::napi_reference_unref(env, ref_, &mut ref_count) },
       "Failed to unref Buffer reference in drop",
     );
-    trace_drop!("unref → {}  (ptr = {:?})", ref_count, ref_);
+    #[cfg(not(feature = "minimal"))]
+    emit(BufferEvent::Unreferenced {
+      address: self.inner.as_ptr() as usize,
+      ref_count,
+    });
 
     if ref_count != 0 {
       // There are still JS/Rust handles alive; leave reference in place.
@@ -401,20 +644,17 @@ impl Drop for Buffer {
 
       let status =
         sys::node_api_post_finalizer(env.cast(), Some(do_delete), ref_.cast(), ptr::null_mut());
-      trace_drop!(
-        "queued for post-finalizer ({:?})  status={:?}",
-        ref_,
-        status
-      );
-
       if status != sys::Status::napi_ok {
-        #[cfg(feature = "trace-buffer-drops")]
         eprintln!(
           "[buffer-drop] post-finalizer failed for {:?} with status {:?}",
           ref_, status
         );
         std::process::abort();
       }
+      #[cfg(not(feature = "minimal"))]
+      emit(BufferEvent::ReferenceDeletionQueued {
+        address: self.inner.as_ptr() as usize,
+      });
     }
 
     // N-API ≤ 8 (old trampoline)
@@ -459,6 +699,280 @@ impl Drop for Buffer {
 unsafe impl Send for Buffer {}
 unsafe impl Sync for Buffer {}
 
+impl Buffer {
+  fn ownership(&self) -> &'static str {
+    if self.raw.is_some() {
+      return "JavaScript";
+    }
+    match self.shared {
+      #[cfg(feature = "bytes")]
+      Some(SharedOwner::Bytes(_)) => "Bytes",
+      Some(SharedOwner::Vec(_)) => "Arc",
+      None => "Rust",
+    }
+  }
+
+  #[cfg(feature = "bytes")]
+  /// Create a `Buffer` sharing the memory of `bytes::Bytes` without copying.
+  ///
+  /// The `Bytes` handle is dropped when the JavaScript `Buffer` is garbage collected,
+  /// so the memory is released only after the last `Bytes` handle is gone.
+  ///
+  /// `Bytes` is immutable, mutating the `Buffer` from Rust copies the data first.
+  /// JavaScript writes to the `Buffer` are not guarded, they are visible to all the `Bytes` sharing the memory.
+  pub fn from_bytes(bytes: bytes::Bytes) -> Self {
+    if bytes.is_empty() {
+      return Self::default();
+    }
+    Buffer {
+      // SAFETY: the `Bytes` is not empty, so the pointer is not null.
+      inner: unsafe { NonNull::new_unchecked(bytes.as_ptr().cast_mut()) },
+      len: bytes.len(),
+      capacity: bytes.len(),
+      raw: None,
+      owned_by_rust: false,
+      shared: Some(SharedOwner::Bytes(bytes)),
+    }
+  }
+
+  /// Create a `Buffer` sharing the memory of `Arc<Vec<u8>>` without copying.
+  ///
+  /// The JavaScript `Buffer` holds a clone of the `Arc` and drops it when it's garbage collected,
+  /// so the memory can be shared with the other Rust code at the same time, and it's freed after the last `Arc` is dropped.
+  /// Mutating the `Buffer` from Rust copies the data first. `Buffer::from(Arc<Vec<u8>>)` is the safe alternative,
+  /// it copies the bytes unless the `Arc` isn't shared.
+  ///
+  /// # Safety
+  ///
+  /// JavaScript gets a writable `Buffer` over memory the other `Arc`s read as `&Vec<u8>`, possibly from other threads.
+  /// The caller must ensure the JavaScript code never writes to the `Buffer`, or to the views created from it.
+  pub unsafe fn from_shared(data: Arc<Vec<u8>>) -> Self {
+    if data.is_empty() {
+      return Self::default();
+    }
+    Buffer {
+      // SAFETY: the `Vec` is not empty, so the pointer is not null.
+      inner: unsafe { NonNull::new_unchecked(data.as_ptr().cast_mut()) },
+      len: data.len(),
+      capacity: data.len(),
+      raw: None,
+      owned_by_rust: false,
+      shared: Some(SharedOwner::Vec(data)),
+    }
+  }
+
+  /// Take back the `Vec<u8>` the `Buffer` was created from, without copying.
+  ///
+  /// It succeeds if the memory is still owned by Rust, that is the `Buffer` was created from a `Vec<u8>` and not received from JavaScript.
+  /// Otherwise the `Buffer` is returned as the error, `Vec::from` copies the data in that case.
+  pub fn try_into_vec(mut self) -> std::result::Result<Vec<u8>, Buffer> {
+    if self.raw.is_some() || !self.owned_by_rust {
+      return Err(self);
+    }
+    // the memory is handed over to the `Vec`, dropping `self` releases nothing
+    self.owned_by_rust = false;
+    let data = self.inner.as_ptr();
+    #[cfg(not(feature = "minimal"))]
+    if self.len != 0 {
+      unregister_backing_ptr(data);
+    }
+    Ok(unsafe { Vec::from_raw_parts(data, self.len, self.capacity) })
+  }
+
+  /// Turn the `Buffer` into [`SharedBytes`], which can be cloned cheaply and read from any thread.
+  ///
+  /// The `Vec<u8>` of a `Buffer` owned by Rust is reclaimed, and the `Arc<Vec<u8>>` of [`Buffer::from_shared`] is reused
+  /// if the `Buffer` covers all of it. Otherwise the bytes are copied once, and the reference to the JavaScript `Buffer`
+  /// is released, by the custom GC on the JavaScript thread if it's called on another thread.
+  pub fn to_shared(self) -> SharedBytes {
+    if let Some(SharedOwner::Vec(data)) = &self.shared {
+      if ptr::eq(data.as_ptr(), self.inner.as_ptr()) && data.len() == self.len {
+        return SharedBytes { data: data.clone() };
+      }
+    }
+    let data = match self.try_into_vec() {
+      Ok(data) => data,
+      Err(buffer) => buffer.to_vec(),
+    };
+    SharedBytes {
+      data: Arc::new(data),
+    }
+  }
+
+  /// A view of the bytes in `range` on the same memory, without copying.
+  ///
+  /// For a `Buffer` from JavaScript it's the `Buffer` returned by `buf.subarray(start, end)`, which holds its own reference,
+  /// so it stays valid after `self` is dropped. A `Buffer` backed by `bytes::Bytes` or `Arc<Vec<u8>>` shares it.
+  /// The `Buffer`s created from a `Vec<u8>` have no memory to share, they return an error, convert them to JavaScript first.
+  pub fn slice<R: RangeBounds<usize>>(&self, env: &Env, range: R) -> Result<Buffer> {
+    let (start, end) = bounds_of(&range, self.len);
+    if start > end || end > self.len {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Slice {}..{} is out of the bounds of the Buffer of {} bytes",
+          start, end, self.len
+        ),
+      ));
+    }
+    if let Some(shared) = &self.shared {
+      if start == end {
+        return Ok(Buffer::default());
+      }
+      return Ok(Buffer {
+        // SAFETY: `start < end <= len`, the pointer is in the shared memory
+        inner: unsafe { NonNull::new_unchecked(self.inner.as_ptr().add(start)) },
+        len: end - start,
+        capacity: end - start,
+        raw: None,
+        owned_by_rust: false,
+        shared: Some(shared.clone()),
+      });
+    }
+    let Some((reference, buffer_env)) = self.raw.filter(|(reference, _)| !reference.is_null())
+    else {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The Buffer is not backed by JavaScript, it can't be sliced without copying",
+      ));
+    };
+    if buffer_env != env.0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The Buffer can only be sliced in the env where it is created",
+      ));
+    }
+    let env = env.0;
+    let mut buffer = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, reference, &mut buffer) },
+      "Failed to get Buffer value from reference"
+    )?;
+    let mut subarray = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_named_property(env, buffer, c"subarray".as_ptr(), &mut subarray) },
+      "Failed to get Buffer.prototype.subarray"
+    )?;
+    let mut args = [ptr::null_mut(); 2];
+    for (arg, index) in args.iter_mut().zip([start, end]) {
+      check_status!(
+        unsafe { sys::napi_create_double(env, index as f64, arg) },
+        "Failed to create the index of subarray"
+      )?;
+    }
+    let mut view = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_call_function(env, buffer, subarray, 2, args.as_ptr(), &mut view) },
+      "Failed to call Buffer.prototype.subarray"
+    )?;
+    unsafe { Buffer::from_napi_value(env, view) }
+  }
+
+  /// Convert the `Buffer` into a [`Uint8Array`] without copying.
+  ///
+  /// A `Buffer` from JavaScript hands its reference over to the `Uint8Array`,
+  /// so the same JavaScript value is returned when the `Uint8Array` is converted back to JavaScript.
+  /// Otherwise the `Uint8Array` takes over the memory of the `Buffer`.
+  pub fn into_uint8array(mut self) -> Uint8Array {
+    let data = self.inner.as_ptr();
+    let len = self.len;
+    let capacity = self.capacity;
+    let raw = self.raw.take();
+    let owned_by_rust = mem::replace(&mut self.owned_by_rust, false);
+    let shared = self.shared.take();
+    // everything is moved out, dropping `self` releases nothing
+    drop(self);
+
+    if let Some((reference, env)) = raw {
+      return Uint8Array::from_reference(data, len, reference, env);
+    }
+    if let Some(shared) = shared {
+      return unsafe { Uint8Array::with_external_data(data, len, move |_, _| drop(shared)) };
+    }
+    debug_assert!(owned_by_rust);
+    // the memory is handed over to the `Uint8Array`, which doesn't track it
+    #[cfg(not(feature = "minimal"))]
+    if len != 0 {
+      unregister_backing_ptr(data);
+    }
+    Uint8Array::from_vec_with_capacity(unsafe { Vec::from_raw_parts(data, len, capacity) })
+  }
+
+  /// Concatenate `parts` into a new JavaScript `Buffer`, the data is copied into the `Buffer` with a single allocation.
+  ///
+  /// It's the same as `Buffer.concat` in Node.js, without allocating the intermediate `Buffer`s.
+  pub fn concat<'env, D: AsRef<[u8]>>(env: &'env Env, parts: &[D]) -> Result<BufferSlice<'env>> {
+    let len = parts.iter().map(|part| part.as_ref().len()).sum::<usize>();
+    let mut js_value = ptr::null_mut();
+    let mut data = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_buffer(env.0, len, &mut data, &mut js_value) },
+      "Failed to create Buffer for concat"
+    )?;
+    let inner: &mut [u8] = if len == 0 {
+      &mut []
+    } else {
+      unsafe { slice::from_raw_parts_mut(data.cast::<u8>(), len) }
+    };
+    let mut offset = 0;
+    for part in parts {
+      let part = part.as_ref();
+      inner[offset..offset + part.len()].copy_from_slice(part);
+      offset += part.len();
+    }
+    Ok(BufferSlice {
+      inner,
+      raw_value: js_value,
+      env: env.0,
+    })
+  }
+
+  #[cfg(all(feature = "tokio_rt", feature = "napi4", not(feature = "noop")))]
+  /// Read the bytes of the file at `path` in `range` on the blocking thread pool of the tokio runtime.
+  ///
+  /// The bytes are read into a `Vec<u8>` of the exact length, which becomes the memory of the JavaScript `Buffer` without copying.
+  /// The range is clamped to the length of the file, like [`BufferSlice::from_file`].
+  pub async fn from_file_async<P: AsRef<Path>, R: RangeBounds<u64>>(
+    path: P,
+    range: R,
+  ) -> Result<Buffer> {
+    let path = path.as_ref().to_path_buf();
+    let range = (range.start_bound().cloned(), range.end_bound().cloned());
+    crate::tokio_runtime::spawn_blocking(move || {
+      let (file, start, len) = open_file_range(&path, range)?;
+      let mut data = vec![0; len];
+      read_file_at(&file, &mut data, start).map_err(|err| file_error(&path, err))?;
+      Ok(Buffer::from(data))
+    })
+    .await
+    .map_err(|err| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Read file task failed: {}", err),
+      )
+    })?
+  }
+
+  /// The bytes encoded as a string, like `buf.toString(encoding)`, without calling into JavaScript.
+  pub fn to_string_encoded(&self, encoding: Encoding) -> String {
+    encoding.encode(self.as_ref())
+  }
+
+  /// Create a JavaScript `Buffer` from `data` decoded in `encoding`, like `Buffer.from(data, encoding)`.
+  ///
+  /// The string is decoded in Rust and copied into the `Buffer` once.
+  pub fn from_string_encoded<'env>(
+    env: &'env Env,
+    data: &str,
+    encoding: Encoding,
+  ) -> Result<BufferSlice<'env>> {
+    match encoding {
+      Encoding::Utf8 => BufferSlice::copy_from(env, data),
+      _ => BufferSlice::from_data(env, encoding.decode(data)),
+    }
+  }
+}
+
 impl Default for Buffer {
   fn default() -> Self {
     Self::from(Vec::default())
@@ -468,9 +982,12 @@ impl Default for Buffer {
 impl From<Vec<u8>> for Buffer {
   fn from(mut data: Vec<u8>) -> Self {
     let inner_ptr = data.as_mut_ptr();
-    #[cfg(all(debug_assertions, not(windows)))]
-    register_backing_ptr(inner_ptr);
     let len = data.len();
+    // the empty `Vec`s share a dangling pointer
+    #[cfg(not(feature = "minimal"))]
+    if len != 0 {
+      register_backing_ptr(inner_ptr, len);
+    }
     let capacity = data.capacity();
     mem::forget(data);
     Buffer {
@@ -482,13 +999,38 @@ impl From<Vec<u8>> for Buffer {
       capacity,
       raw: None,
       owned_by_rust: true,
+      shared: None,
     }
   }
 }
 
+/// The `Vec<u8>` is taken out of the `Arc` if it's not shared, otherwise the bytes are copied.
+impl From<Arc<Vec<u8>>> for Buffer {
+  fn from(data: Arc<Vec<u8>>) -> Self {
+    Buffer::from(Arc::try_unwrap(data).unwrap_or_else(|data| data.as_ref().clone()))
+  }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for Buffer {
+  fn from(bytes: bytes::Bytes) -> Self {
+    Buffer::from_bytes(bytes)
+  }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::BytesMut> for Buffer {
+  /// The `BytesMut` is frozen into `Bytes` without copying, see [`Buffer::from_bytes`].
+  fn from(bytes: bytes::BytesMut) -> Self {
+    Buffer::from_bytes(bytes.freeze())
+  }
+}
+
 impl From<Buffer> for Vec<u8> {
   fn from(buf: Buffer) -> Self {
-    buf.as_ref().to_vec()
+    buf
+      .try_into_vec()
+      .unwrap_or_else(|buf| buf.as_ref().to_vec())
   }
 }
 
@@ -513,6 +1055,10 @@ impl AsRef<[u8]> for Buffer {
 
 impl AsMut<[u8]> for Buffer {
   fn as_mut(&mut self) -> &mut [u8] {
+    // the shared memory is immutable in Rust, copy the data before writing to it
+    if self.shared.is_some() {
+      *self = Buffer::from(self.as_ref().to_vec());
+    }
     // SAFETY: This is literally undefined behavior. `Buffer::clone` allows you to create shared
     // access to the underlying data, but `as_mut` and `deref_mut` allow unsynchronized mutation of
     // that data (not to speak of the JS side having write access as well, at the same time).
@@ -520,6 +1066,9 @@ impl AsMut<[u8]> for Buffer {
   }
 }
 
+impl_binary_fmt!(Buffer, "Buffer", |this| ownership: this.ownership(), length: this.len, bytes: this.as_ref());
+impl_node_buffer_ops!(Buffer);
+
 impl Deref for Buffer {
   type Target = [u8];
 
@@ -560,6 +1109,11 @@ impl FromNapiValue for Buffer {
       sys::napi_create_reference(env, napi_val, /*refcount*/ 1, &mut reference),
       "Failed to create Buffer reference"
     )?;
+    #[cfg(not(feature = "minimal"))]
+    emit(BufferEvent::Referenced {
+      address: buf as usize,
+      len,
+    });
 
     Ok(Self {
       inner: if len == 0 {
@@ -571,6 +1125,7 @@ impl FromNapiValue for Buffer {
       capacity: len,
       raw: Some((reference, env)),
       owned_by_rust: false,
+      shared: None,
     })
   }
 }
@@ -602,9 +1157,8 @@ impl ToNapiValue for Buffer {
         unsafe { sys::napi_create_buffer(env, len, ptr::null_mut(), &mut ret) }
       } else {
         let value_ptr = val.inner.as_ptr();
+        // The finaliser drops the boxed `Buffer`, which frees the bytes
         let val_box_ptr = Box::into_raw(Box::new(val));
-        // Rust no longer owns the bytes – finaliser will free them
-        (*(val_box_ptr)).owned_by_rust = false;
         let mut status = unsafe {
           sys::napi_create_external_buffer(
             env,
@@ -615,7 +1169,7 @@ impl ToNapiValue for Buffer {
             &mut ret,
           )
         };
-        if status == napi_sys::Status::napi_no_external_buffers_allowed {
+        if external_buffers_disallowed(status) {
           let value = unsafe { Box::from_raw(val_box_ptr) };
           status = unsafe {
             sys::napi_create_buffer_copy(
@@ -652,3 +1206,270 @@ impl ValidateNapiValue for Buffer {
     Ok(ptr::null_mut())
   }
 }
+
+/// The bytes of a [`Buffer`] shared between threads, created by [`Buffer::to_shared`].
+///
+/// Cloning it only increases a reference count. The bytes are immutable, so a `Buffer` created from it,
+/// including the one returned to JavaScript, takes the bytes without copying only if it's the last clone, otherwise they're copied.
+/// As an argument of the `#[napi]` functions it accepts a `Buffer`, which is copied.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SharedBytes {
+  data: Arc<Vec<u8>>,
+}
+
+impl SharedBytes {
+  /// The `Arc<Vec<u8>>` holding the bytes.
+  pub fn into_inner(self) -> Arc<Vec<u8>> {
+    self.data
+  }
+}
+
+impl Deref for SharedBytes {
+  type Target = [u8];
+
+  fn deref(&self) -> &Self::Target {
+    &self.data
+  }
+}
+
+impl AsRef<[u8]> for SharedBytes {
+  fn as_ref(&self) -> &[u8] {
+    &self.data
+  }
+}
+
+impl From<Arc<Vec<u8>>> for SharedBytes {
+  fn from(data: Arc<Vec<u8>>) -> Self {
+    SharedBytes { data }
+  }
+}
+
+impl From<SharedBytes> for Buffer {
+  fn from(shared: SharedBytes) -> Self {
+    // JavaScript can write to the `Buffer`, it never shares the memory read by the other clones
+    Buffer::from(Arc::try_unwrap(shared.data).unwrap_or_else(|data| data.as_ref().clone()))
+  }
+}
+
+impl TypeName for SharedBytes {
+  fn type_name() -> &'static str {
+    "Vec<u8>"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for SharedBytes {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { Buffer::validate(env, napi_val) }
+  }
+}
+
+impl FromNapiValue for SharedBytes {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(unsafe { Buffer::from_napi_value(env, napi_val) }?.to_shared())
+  }
+}
+
+impl ToNapiValue for SharedBytes {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { Buffer::to_napi_value(env, val.into()) }
+  }
+}
+
+/// Assemble bytes in Rust and turn them into a single JavaScript `Buffer`.
+///
+/// The bytes are appended into one growable allocation, which is handed to JavaScript without copying when built.
+#[derive(Debug, Default, Clone)]
+pub struct BufferBuilder {
+  inner: Vec<u8>,
+}
+
+impl BufferBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      inner: Vec::with_capacity(capacity),
+    }
+  }
+
+  /// Append `data` to the end of the builder.
+  pub fn append<D: AsRef<[u8]>>(&mut self, data: D) -> &mut Self {
+    self.inner.extend_from_slice(data.as_ref());
+    self
+  }
+
+  /// Reserve capacity for at least `additional` more bytes.
+  pub fn reserve(&mut self, additional: usize) -> &mut Self {
+    self.inner.reserve(additional);
+    self
+  }
+
+  pub fn len(&self) -> usize {
+    self.inner.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.inner.is_empty()
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.inner.capacity()
+  }
+
+  /// Create the JavaScript `Buffer` from the appended bytes.
+  pub fn build(self, env: &Env) -> Result<BufferSlice<'_>> {
+    BufferSlice::from_data(env, self.inner)
+  }
+}
+
+impl AsRef<[u8]> for BufferBuilder {
+  fn as_ref(&self) -> &[u8] {
+    &self.inner
+  }
+}
+
+impl std::io::Write for BufferBuilder {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.inner.extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+impl From<BufferBuilder> for Buffer {
+  fn from(builder: BufferBuilder) -> Self {
+    Buffer::from(builder.inner)
+  }
+}
+
+/// A [`std::io::Read`], [`std::io::Write`] and [`std::io::Seek`] cursor over the bytes of a [`Buffer`] or a [`BufferSlice`],
+/// so the Rust code taking `impl Read` or `impl Write` can work on the JavaScript memory without copying it into a `Vec<u8>`.
+///
+/// Like `std::io::Cursor<&mut [u8]>`, the length of the underlying buffer is fixed:
+/// the writes stop at the end of it, and seeking past the end is allowed, then reading or writing there does nothing.
+#[derive(Debug, Clone)]
+pub struct BufferCursor<B> {
+  inner: B,
+  position: u64,
+}
+
+impl<B: AsRef<[u8]>> BufferCursor<B> {
+  pub fn new(inner: B) -> Self {
+    Self { inner, position: 0 }
+  }
+
+  pub fn position(&self) -> u64 {
+    self.position
+  }
+
+  pub fn set_position(&mut self, position: u64) {
+    self.position = position;
+  }
+
+  pub fn get_ref(&self) -> &B {
+    &self.inner
+  }
+
+  pub fn get_mut(&mut self) -> &mut B {
+    &mut self.inner
+  }
+
+  pub fn into_inner(self) -> B {
+    self.inner
+  }
+
+  /// The bytes from the current position to the end of the buffer.
+  pub fn remaining_slice(&self) -> &[u8] {
+    let data = self.inner.as_ref();
+    &data[self.offset(data.len())..]
+  }
+
+  // the position clamped to the length of the buffer
+  fn offset(&self, len: usize) -> usize {
+    usize::try_from(self.position).map_or(len, |position| position.min(len))
+  }
+}
+
+impl<B: AsRef<[u8]>> std::io::Read for BufferCursor<B> {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    let remaining = self.remaining_slice();
+    let n = remaining.len().min(buf.len());
+    buf[..n].copy_from_slice(&remaining[..n]);
+    self.position += n as u64;
+    Ok(n)
+  }
+
+  fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+    let remaining = self.remaining_slice();
+    if remaining.len() < buf.len() {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::UnexpectedEof,
+        "failed to fill whole buffer",
+      ));
+    }
+    buf.copy_from_slice(&remaining[..buf.len()]);
+    self.position += buf.len() as u64;
+    Ok(())
+  }
+}
+
+impl<B: AsRef<[u8]>> std::io::BufRead for BufferCursor<B> {
+  fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+    Ok(self.remaining_slice())
+  }
+
+  fn consume(&mut self, amt: usize) {
+    self.position += amt as u64;
+  }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> std::io::Write for BufferCursor<B> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    let offset = self.offset(self.inner.as_ref().len());
+    let data = self.inner.as_mut();
+    let n = (data.len() - offset).min(buf.len());
+    data[offset..offset + n].copy_from_slice(&buf[..n]);
+    self.position += n as u64;
+    Ok(n)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+impl<B: AsRef<[u8]>> std::io::Seek for BufferCursor<B> {
+  fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+    let (base, offset) = match pos {
+      std::io::SeekFrom::Start(position) => {
+        self.position = position;
+        return Ok(position);
+      }
+      std::io::SeekFrom::End(offset) => (self.inner.as_ref().len() as u64, offset),
+      std::io::SeekFrom::Current(offset) => (self.position, offset),
+    };
+    match base.checked_add_signed(offset) {
+      Some(position) => {
+        self.position = position;
+        Ok(position)
+      }
+      None => Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        "invalid seek to a negative or overflowing position",
+      )),
+    }
+  }
+
+  fn stream_position(&mut self) -> std::io::Result<u64> {
+    Ok(self.position)
+  }
+}
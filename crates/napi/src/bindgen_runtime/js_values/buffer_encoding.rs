@@ -0,0 +1,214 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Status};
+
+/// The character encodings of the Node.js `Buffer`, used by [`crate::bindgen_prelude::Buffer::to_string_encoded`]
+/// and [`crate::bindgen_prelude::Buffer::from_string_encoded`].
+///
+/// It's parsed from the encoding names of Node.js, including the aliases like `utf-8`, `binary` and `utf16le`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Encoding {
+  #[default]
+  Utf8,
+  Base64,
+  Base64Url,
+  Hex,
+  Latin1,
+  Ucs2,
+}
+
+impl Encoding {
+  /// The canonical Node.js name of the encoding.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Encoding::Utf8 => "utf8",
+      Encoding::Base64 => "base64",
+      Encoding::Base64Url => "base64url",
+      Encoding::Hex => "hex",
+      Encoding::Latin1 => "latin1",
+      Encoding::Ucs2 => "ucs2",
+    }
+  }
+
+  /// Encode `data` into a string, like `buf.toString(encoding)`.
+  ///
+  /// The invalid UTF-8 and the unpaired UTF-16 surrogates are replaced by `U+FFFD`.
+  pub fn encode(&self, data: &[u8]) -> String {
+    match self {
+      Encoding::Utf8 => String::from_utf8_lossy(data).into_owned(),
+      Encoding::Base64 => encode_base64(data, BASE64_ALPHABET, true),
+      Encoding::Base64Url => encode_base64(data, BASE64URL_ALPHABET, false),
+      Encoding::Hex => encode_hex(data),
+      Encoding::Latin1 => data.iter().map(|b| *b as char).collect(),
+      Encoding::Ucs2 => {
+        let units = data
+          .chunks_exact(2)
+          .map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+        char::decode_utf16(units)
+          .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+          .collect()
+      }
+    }
+  }
+
+  /// Decode `data` into bytes, like `Buffer.from(string, encoding)`.
+  ///
+  /// The decoding is as lenient as Node.js: base64 accepts both alphabets and no padding, skips the other characters,
+  /// and stops at the padding or at a character out of the BMP. Hex stops at the first invalid pair of digits,
+  /// and latin1 keeps the low byte of each UTF-16 code unit.
+  pub fn decode(&self, data: &str) -> Vec<u8> {
+    match self {
+      Encoding::Utf8 => data.as_bytes().to_vec(),
+      Encoding::Base64 | Encoding::Base64Url => decode_base64(data.as_bytes()),
+      Encoding::Hex => decode_hex(data.as_bytes()),
+      Encoding::Latin1 => data.encode_utf16().map(|unit| unit as u8).collect(),
+      Encoding::Ucs2 => data.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+    }
+  }
+}
+
+impl FromStr for Encoding {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_ascii_lowercase().as_str() {
+      "utf8" | "utf-8" => Ok(Encoding::Utf8),
+      "base64" => Ok(Encoding::Base64),
+      "base64url" => Ok(Encoding::Base64Url),
+      "hex" => Ok(Encoding::Hex),
+      "latin1" | "binary" => Ok(Encoding::Latin1),
+      "ucs2" | "ucs-2" | "utf16le" | "utf-16le" => Ok(Encoding::Ucs2),
+      _ => Err(Error::new(
+        Status::InvalidArg,
+        format!("Unknown encoding: {}", s),
+      )),
+    }
+  }
+}
+
+impl fmt::Display for Encoding {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL_ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const INVALID: u8 = 0xff;
+
+// The 6 bits of both base64 alphabets, `INVALID` for the others
+const BASE64_DECODE: [u8; 256] = {
+  let mut table = [INVALID; 256];
+  let mut i = 0;
+  while i < 64 {
+    table[BASE64_ALPHABET[i] as usize] = i as u8;
+    table[BASE64URL_ALPHABET[i] as usize] = i as u8;
+    i += 1;
+  }
+  table
+};
+
+const HEX_DECODE: [u8; 256] = {
+  let mut table = [INVALID; 256];
+  let mut i = 0;
+  while i < 16 {
+    table[HEX_DIGITS[i] as usize] = i as u8;
+    table[HEX_DIGITS[i].to_ascii_uppercase() as usize] = i as u8;
+    i += 1;
+  }
+  table
+};
+
+fn encode_base64(data: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
+  let mut output = Vec::with_capacity(data.len().div_ceil(3) * 4);
+  let chunks = data.chunks_exact(3);
+  let remainder = chunks.remainder();
+  for chunk in chunks {
+    let n = u32::from_be_bytes([0, chunk[0], chunk[1], chunk[2]]);
+    output.extend_from_slice(&[
+      alphabet[(n >> 18) as usize & 63],
+      alphabet[(n >> 12) as usize & 63],
+      alphabet[(n >> 6) as usize & 63],
+      alphabet[n as usize & 63],
+    ]);
+  }
+  match *remainder {
+    [a] => {
+      output.extend_from_slice(&[alphabet[(a >> 2) as usize], alphabet[(a as usize & 3) << 4]]);
+      if pad {
+        output.extend_from_slice(b"==");
+      }
+    }
+    [a, b] => {
+      output.extend_from_slice(&[
+        alphabet[(a >> 2) as usize],
+        alphabet[((a as usize & 3) << 4) | (b >> 4) as usize],
+        alphabet[(b as usize & 15) << 2],
+      ]);
+      if pad {
+        output.push(b'=');
+      }
+    }
+    _ => {}
+  }
+  // SAFETY: the alphabets are ASCII
+  unsafe { String::from_utf8_unchecked(output) }
+}
+
+fn decode_base64(data: &[u8]) -> Vec<u8> {
+  let mut output = Vec::with_capacity(data.len() / 4 * 3 + 2);
+  let mut bits = 0u32;
+  let mut count = 0;
+  for byte in data {
+    // the padding ends the data, and so does a character out of the BMP in Node.js, which starts with 0xF0..=0xF4 in UTF-8
+    if *byte == b'=' || *byte >= 0xf0 {
+      break;
+    }
+    let sextet = BASE64_DECODE[*byte as usize];
+    if sextet == INVALID {
+      continue;
+    }
+    bits = (bits << 6) | sextet as u32;
+    count += 1;
+    if count == 4 {
+      output.extend_from_slice(&bits.to_be_bytes()[1..]);
+      bits = 0;
+      count = 0;
+    }
+  }
+  // the incomplete group of 2 or 3 characters has 1 or 2 bytes, a single character has none
+  match count {
+    2 => output.push((bits >> 4) as u8),
+    3 => output.extend_from_slice(&((bits >> 2) as u16).to_be_bytes()),
+    _ => {}
+  }
+  output
+}
+
+fn encode_hex(data: &[u8]) -> String {
+  let mut output = Vec::with_capacity(data.len() * 2);
+  for byte in data {
+    output.extend_from_slice(&[
+      HEX_DIGITS[(byte >> 4) as usize],
+      HEX_DIGITS[(byte & 15) as usize],
+    ]);
+  }
+  // SAFETY: the hex digits are ASCII
+  unsafe { String::from_utf8_unchecked(output) }
+}
+
+fn decode_hex(data: &[u8]) -> Vec<u8> {
+  let mut output = Vec::with_capacity(data.len() / 2);
+  for pair in data.chunks_exact(2) {
+    let (high, low) = (HEX_DECODE[pair[0] as usize], HEX_DECODE[pair[1] as usize]);
+    if high == INVALID || low == INVALID {
+      break;
+    }
+    output.push((high << 4) | low);
+  }
+  output
+}
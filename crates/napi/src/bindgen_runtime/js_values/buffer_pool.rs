@@ -0,0 +1,570 @@
+use std::ffi::c_void;
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::external_memory::adjust_external_memory;
+#[cfg(not(feature = "minimal"))]
+use crate::bindgen_prelude::{register_backing_ptr, unregister_backing_ptr};
+use crate::{
+  bindgen_runtime::{external_buffers_disallowed, Buffer, BufferSlice, ToNapiValue, TypeName},
+  check_status, sys, Env, Result, ValueType,
+};
+
+#[derive(Clone)]
+/// A pool of fixed-size backing stores for the frequently created small `BufferSlice`s.
+///
+/// The `BufferSlice`s created by the pool are external buffers on the pooled blocks,
+/// the blocks are put back into the pool when the buffers are garbage collected, instead of being freed.
+/// The external memory is only reported to the engine when blocks are allocated or freed,
+/// by the [`ExternalMemoryPolicy`](crate::bindgen_prelude::ExternalMemoryPolicy) of the env.
+///
+/// The blocks are binned by size class, see [`BufferPool::with_size_classes`], a buffer takes a block of the smallest class which fits it.
+/// The data larger than the largest block size is not pooled, it falls back to [`BufferSlice::from_data`].
+pub struct BufferPool {
+  inner: Arc<BufferPoolInner>,
+}
+
+struct BufferPoolInner {
+  // ascending by block size
+  size_classes: Vec<Arc<SizeClass>>,
+  oversized: AtomicU64,
+}
+
+struct SizeClass {
+  block_size: usize,
+  capacity: usize,
+  free: Mutex<Vec<Box<[u8]>>>,
+  // bytes allocated or freed without an env, like by the `PooledBuffer`s on other threads, which are not reported yet
+  unreported_memory: AtomicI64,
+  hits: AtomicU64,
+  misses: AtomicU64,
+  recycled: AtomicU64,
+  discarded: AtomicU64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The counters of a [`BufferPool`], returned by [`BufferPool::stats`].
+pub struct BufferPoolStats {
+  /// Buffers created on a free block of the pool
+  pub hits: u64,
+  /// Buffers for which a new block was allocated
+  pub misses: u64,
+  /// Buffers larger than the largest block size, which are not pooled
+  pub oversized: u64,
+  /// Blocks put back into the pool after their buffers were released
+  pub recycled: u64,
+  /// Blocks freed because the pool was full
+  pub discarded: u64,
+  /// Free blocks in the pool
+  pub available: usize,
+}
+
+impl BufferPool {
+  /// Create a pool of blocks of `block_size` bytes, which keeps at most `capacity` free blocks.
+  pub fn with_capacity(block_size: usize, capacity: usize) -> Self {
+    Self::with_size_classes(&[block_size], capacity)
+  }
+
+  /// Create a pool with a bin of blocks for each size in `block_sizes`, each bin keeps at most `capacity` free blocks.
+  ///
+  /// Like `BufferPool::with_size_classes(&[256, 4096, 64 * 1024], 64)` for the buffers of various sizes,
+  /// so a small buffer doesn't hold a block of the largest size.
+  pub fn with_size_classes(block_sizes: &[usize], capacity: usize) -> Self {
+    let mut block_sizes = block_sizes.to_vec();
+    block_sizes.sort_unstable();
+    block_sizes.dedup();
+    Self {
+      inner: Arc::new(BufferPoolInner {
+        size_classes: block_sizes
+          .into_iter()
+          .map(|block_size| {
+            Arc::new(SizeClass {
+              block_size,
+              capacity,
+              free: Mutex::new(Vec::with_capacity(capacity)),
+              unreported_memory: AtomicI64::new(0),
+              hits: AtomicU64::new(0),
+              misses: AtomicU64::new(0),
+              recycled: AtomicU64::new(0),
+              discarded: AtomicU64::new(0),
+            })
+          })
+          .collect(),
+        oversized: AtomicU64::new(0),
+      }),
+    }
+  }
+
+  /// Size of the largest pooled blocks.
+  pub fn block_size(&self) -> usize {
+    self
+      .inner
+      .size_classes
+      .last()
+      .map_or(0, |size_class| size_class.block_size)
+  }
+
+  /// Sizes of the pooled blocks, ascending.
+  pub fn size_classes(&self) -> impl Iterator<Item = usize> + '_ {
+    self
+      .inner
+      .size_classes
+      .iter()
+      .map(|size_class| size_class.block_size)
+  }
+
+  /// Number of the free blocks in the pool.
+  pub fn available(&self) -> usize {
+    self
+      .inner
+      .size_classes
+      .iter()
+      .map(|size_class| size_class.available())
+      .sum()
+  }
+
+  /// The counters of the pool since it was created.
+  pub fn stats(&self) -> BufferPoolStats {
+    self.inner.size_classes.iter().fold(
+      BufferPoolStats {
+        oversized: self.inner.oversized.load(Ordering::Relaxed),
+        ..Default::default()
+      },
+      |stats, size_class| BufferPoolStats {
+        hits: stats.hits + size_class.hits.load(Ordering::Relaxed),
+        misses: stats.misses + size_class.misses.load(Ordering::Relaxed),
+        recycled: stats.recycled + size_class.recycled.load(Ordering::Relaxed),
+        discarded: stats.discarded + size_class.discarded.load(Ordering::Relaxed),
+        available: stats.available + size_class.available(),
+        ..stats
+      },
+    )
+  }
+
+  /// Free the blocks kept in the pool, the blocks of the live buffers are still put back when they're released.
+  pub fn clear(&self, env: &Env) {
+    for size_class in &self.inner.size_classes {
+      let blocks = size_class
+        .free
+        .lock()
+        .map(|mut free| std::mem::take(&mut *free))
+        .unwrap_or_default();
+      size_class.adjust_external_memory(env.0, -((blocks.len() * size_class.block_size) as i64));
+    }
+  }
+
+  /// Create a `BufferSlice` of `len` zeroed bytes.
+  pub fn alloc<'env>(&self, env: &'env Env, len: usize) -> Result<BufferSlice<'env>> {
+    if len == 0 {
+      return empty_buffer(env);
+    }
+    let Some(size_class) = self.size_class(len) else {
+      return BufferSlice::from_data(env, vec![0; len]);
+    };
+    let mut buffer = self.create(env, size_class, len)?;
+    buffer.fill(0);
+    Ok(buffer)
+  }
+
+  /// Create a `BufferSlice` with a copy of `data`.
+  pub fn from_data<'env>(&self, env: &'env Env, data: &[u8]) -> Result<BufferSlice<'env>> {
+    if data.is_empty() {
+      return empty_buffer(env);
+    }
+    let Some(size_class) = self.size_class(data.len()) else {
+      return BufferSlice::from_data(env, data);
+    };
+    let mut buffer = self.create(env, size_class, data.len())?;
+    buffer.copy_from_slice(data);
+    Ok(buffer)
+  }
+
+  /// Take a [`PooledBuffer`] of `len` zeroed bytes, which doesn't require an `Env` until it's returned to JavaScript.
+  pub fn take(&self, len: usize) -> PooledBuffer {
+    let Some(size_class) = self.size_class(len) else {
+      return PooledBuffer {
+        block: vec![0; len].into_boxed_slice(),
+        len,
+        size_class: None,
+      };
+    };
+    let mut block = size_class.take_block(ptr::null_mut());
+    block[..len].fill(0);
+    PooledBuffer {
+      block,
+      len,
+      size_class: Some(size_class.clone()),
+    }
+  }
+
+  /// Take a [`PooledBuffer`] with a copy of `data`.
+  pub fn copy_from(&self, data: &[u8]) -> PooledBuffer {
+    let Some(size_class) = self.size_class(data.len()) else {
+      return PooledBuffer {
+        block: data.into(),
+        len: data.len(),
+        size_class: None,
+      };
+    };
+    let mut block = size_class.take_block(ptr::null_mut());
+    block[..data.len()].copy_from_slice(data);
+    PooledBuffer {
+      block,
+      len: data.len(),
+      size_class: Some(size_class.clone()),
+    }
+  }
+
+  /// Report the external memory changes of the blocks allocated or freed without an env, and the changes pending
+  /// under the policy of the env, now.
+  pub fn flush_external_memory(&self, env: &Env) -> Result<()> {
+    let bytes = self
+      .inner
+      .size_classes
+      .iter()
+      .map(|size_class| size_class.unreported_memory.swap(0, Ordering::Relaxed))
+      .sum();
+    adjust_external_memory(env.0, bytes)?;
+    env.flush_external_memory()
+  }
+
+  /// Create a [`BufferWriter`] streaming the written bytes to `on_chunk` in the `BufferSlice`s on the largest pooled blocks.
+  pub fn writer<'env, F>(&self, env: &'env Env, on_chunk: F) -> BufferWriter<'env, F>
+  where
+    F: FnMut(BufferSlice<'env>) -> Result<()>,
+  {
+    BufferWriter {
+      env,
+      size_class: self.inner.size_classes.last().cloned(),
+      block: None,
+      len: 0,
+      on_chunk,
+    }
+  }
+
+  // The smallest size class which fits `len` bytes, `None` for the empty and the oversized buffers
+  fn size_class(&self, len: usize) -> Option<&Arc<SizeClass>> {
+    if len == 0 {
+      return None;
+    }
+    let size_class = self
+      .inner
+      .size_classes
+      .iter()
+      .find(|size_class| size_class.block_size >= len);
+    if size_class.is_none() {
+      self.inner.oversized.fetch_add(1, Ordering::Relaxed);
+    }
+    size_class
+  }
+
+  fn create<'env>(
+    &self,
+    env: &'env Env,
+    size_class: &Arc<SizeClass>,
+    len: usize,
+  ) -> Result<BufferSlice<'env>> {
+    let block = size_class.take_block(env.0);
+    let (js_value, data) = unsafe { expose_block(env.0, size_class, block, len)? };
+    Ok(BufferSlice {
+      inner: unsafe { slice::from_raw_parts_mut(data, len) },
+      raw_value: js_value,
+      env: env.0,
+    })
+  }
+}
+
+// The pool registered by `Env::set_buffer_pool`, in the instance data
+#[cfg(feature = "napi6")]
+struct EnvBufferPool(BufferPool);
+
+#[cfg(feature = "napi6")]
+impl Env {
+  /// Register `pool` as the buffer pool of this env, which is shared by the modules through [`Env::buffer_pool`].
+  ///
+  /// It replaces the pool registered before. The pool is unregistered and its free blocks are freed when the env is torn down,
+  /// after the cleanup hooks, which may still return their buffers into the pool.
+  pub fn set_buffer_pool(&self, pool: BufferPool) -> Result<()> {
+    self.set_instance(EnvBufferPool(pool))?;
+    Ok(())
+  }
+
+  /// The buffer pool registered by [`Env::set_buffer_pool`].
+  pub fn buffer_pool(&self) -> Option<BufferPool> {
+    self
+      .with_instance(|pool: &mut EnvBufferPool| pool.0.clone())
+      .ok()
+      .flatten()
+  }
+}
+
+/// A buffer on a block of a [`BufferPool`], created by [`BufferPool::take`] and [`BufferPool::copy_from`].
+///
+/// It's an owned value which can be filled on any thread and returned to JavaScript as a `Buffer`,
+/// the block is put back into the pool when the `Buffer` is garbage collected, or when it's dropped without being returned.
+pub struct PooledBuffer {
+  block: Box<[u8]>,
+  len: usize,
+  // `None` for the empty and the oversized buffers, which are not pooled
+  size_class: Option<Arc<SizeClass>>,
+}
+
+impl PooledBuffer {
+  /// Shorten the buffer to `len` bytes, it has no effect if `len` is not less than the current length.
+  pub fn truncate(&mut self, len: usize) {
+    self.len = self.len.min(len);
+  }
+}
+
+impl Deref for PooledBuffer {
+  type Target = [u8];
+
+  fn deref(&self) -> &Self::Target {
+    &self.block[..self.len]
+  }
+}
+
+impl DerefMut for PooledBuffer {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.block[..self.len]
+  }
+}
+
+impl AsRef<[u8]> for PooledBuffer {
+  fn as_ref(&self) -> &[u8] {
+    self
+  }
+}
+
+impl TypeName for PooledBuffer {
+  fn type_name() -> &'static str {
+    "Buffer"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ToNapiValue for PooledBuffer {
+  unsafe fn to_napi_value(env: sys::napi_env, mut val: Self) -> Result<sys::napi_value> {
+    let block = std::mem::take(&mut val.block);
+    match val.size_class.take() {
+      Some(size_class) => {
+        unsafe { expose_block(env, &size_class, block, val.len) }.map(|(js_value, _)| js_value)
+      }
+      None => {
+        let mut data = Vec::from(block);
+        data.truncate(val.len);
+        unsafe { Buffer::to_napi_value(env, Buffer::from(data)) }
+      }
+    }
+  }
+}
+
+impl Drop for PooledBuffer {
+  fn drop(&mut self) {
+    if let Some(size_class) = self.size_class.take() {
+      size_class.put_block(ptr::null_mut(), std::mem::take(&mut self.block));
+    }
+  }
+}
+
+/// A [`std::io::Write`] streaming the output to JavaScript in `BufferSlice`s, created by [`BufferPool::writer`].
+///
+/// The bytes are written into a block of the pool, which is emitted to the callback once it's full,
+/// so an encoder like gzip or protobuf never accumulates its whole output in one allocation.
+/// [`Write::flush`] emits the partially filled block, call [`BufferWriter::finish`] at the end to emit the rest.
+pub struct BufferWriter<'env, F: FnMut(BufferSlice<'env>) -> Result<()>> {
+  env: &'env Env,
+  // the largest size class of the pool, `None` if the pool has no size class
+  size_class: Option<Arc<SizeClass>>,
+  block: Option<Box<[u8]>>,
+  // bytes written into `block`
+  len: usize,
+  on_chunk: F,
+}
+
+impl<'env, F: FnMut(BufferSlice<'env>) -> Result<()>> BufferWriter<'env, F> {
+  /// Emit the remaining bytes.
+  pub fn finish(mut self) -> Result<()> {
+    self.emit()
+  }
+
+  fn emit(&mut self) -> Result<()> {
+    let (Some(block), Some(size_class)) = (self.block.take(), &self.size_class) else {
+      return Ok(());
+    };
+    let len = std::mem::take(&mut self.len);
+    if len == 0 {
+      size_class.put_block(self.env.0, block);
+      return Ok(());
+    }
+    let (js_value, data) = unsafe { expose_block(self.env.0, size_class, block, len)? };
+    let chunk = BufferSlice {
+      inner: unsafe { slice::from_raw_parts_mut(data, len) },
+      raw_value: js_value,
+      env: self.env.0,
+    };
+    (self.on_chunk)(chunk)
+  }
+}
+
+impl<'env, F: FnMut(BufferSlice<'env>) -> Result<()>> Write for BufferWriter<'env, F> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let Some(size_class) = self
+      .size_class
+      .clone()
+      .filter(|size_class| size_class.block_size > 0)
+    else {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "The block size of the BufferPool is 0",
+      ));
+    };
+    let mut written = 0;
+    while written < buf.len() {
+      let block = self
+        .block
+        .get_or_insert_with(|| size_class.take_block(self.env.0));
+      let n = (block.len() - self.len).min(buf.len() - written);
+      block[self.len..self.len + n].copy_from_slice(&buf[written..written + n]);
+      self.len += n;
+      written += n;
+      if self.len == block.len() {
+        self.emit().map_err(io::Error::other)?;
+      }
+    }
+    Ok(written)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.emit().map_err(io::Error::other)
+  }
+}
+
+impl<'env, F: FnMut(BufferSlice<'env>) -> Result<()>> Drop for BufferWriter<'env, F> {
+  fn drop(&mut self) {
+    // the bytes not emitted by `finish` are discarded
+    if let (Some(block), Some(size_class)) = (self.block.take(), &self.size_class) {
+      size_class.put_block(self.env.0, block);
+    }
+  }
+}
+
+impl SizeClass {
+  fn available(&self) -> usize {
+    self.free.lock().map(|free| free.len()).unwrap_or(0)
+  }
+
+  fn take_block(&self, env: sys::napi_env) -> Box<[u8]> {
+    let block = self.free.lock().ok().and_then(|mut free| free.pop());
+    if let Some(block) = block {
+      self.hits.fetch_add(1, Ordering::Relaxed);
+      return block;
+    }
+    self.misses.fetch_add(1, Ordering::Relaxed);
+    self.adjust_external_memory(env, self.block_size as i64);
+    vec![0; self.block_size].into_boxed_slice()
+  }
+
+  fn put_block(&self, env: sys::napi_env, block: Box<[u8]>) {
+    if let Ok(mut free) = self.free.lock() {
+      if free.len() < self.capacity {
+        free.push(block);
+        self.recycled.fetch_add(1, Ordering::Relaxed);
+        return;
+      }
+    }
+    drop(block);
+    self.discarded.fetch_add(1, Ordering::Relaxed);
+    self.adjust_external_memory(env, -(self.block_size as i64));
+  }
+
+  // Without an env, like for the `PooledBuffer`s taken on other threads, the change is recorded and reported
+  // with the next change on the JavaScript thread
+  fn adjust_external_memory(&self, env: sys::napi_env, bytes: i64) {
+    if env.is_null() {
+      self.unreported_memory.fetch_add(bytes, Ordering::Relaxed);
+      return;
+    }
+    let bytes = bytes + self.unreported_memory.swap(0, Ordering::Relaxed);
+    let _ = adjust_external_memory(env, bytes);
+  }
+}
+
+fn empty_buffer(env: &Env) -> Result<BufferSlice<'_>> {
+  let mut js_value = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_buffer(env.0, 0, ptr::null_mut(), &mut js_value) },
+    "Failed to create zero-length BufferSlice"
+  )?;
+  Ok(BufferSlice {
+    inner: &mut [],
+    raw_value: js_value,
+    env: env.0,
+  })
+}
+
+// Create a `Buffer` of the first `len` bytes of `block`, the block is put back when it's garbage collected.
+// If the runtime disallows external buffers the bytes are copied and the block is put back immediately.
+unsafe fn expose_block(
+  env: sys::napi_env,
+  size_class: &Arc<SizeClass>,
+  block: Box<[u8]>,
+  len: usize,
+) -> Result<(sys::napi_value, *mut u8)> {
+  let mut js_value = ptr::null_mut();
+  let block_ptr = Box::into_raw(block).cast::<u8>();
+  let hint = Arc::into_raw(size_class.clone());
+  let status = unsafe {
+    sys::napi_create_external_buffer(
+      env,
+      len,
+      block_ptr.cast(),
+      Some(return_pooled_block),
+      hint.cast_mut().cast(),
+      &mut js_value,
+    )
+  };
+  if status != sys::Status::napi_ok {
+    let mut data = ptr::null_mut();
+    let copy_status = if external_buffers_disallowed(status) {
+      unsafe { sys::napi_create_buffer_copy(env, len, block_ptr.cast(), &mut data, &mut js_value) }
+    } else {
+      status
+    };
+    // the block isn't registered, it's put back without finalizing it
+    unsafe { put_back_block(env, block_ptr, hint) };
+    check_status!(copy_status, "Failed to create Buffer on the pooled block")?;
+    return Ok((js_value, data.cast()));
+  }
+  #[cfg(not(feature = "minimal"))]
+  register_backing_ptr(block_ptr, len);
+  Ok((js_value, block_ptr))
+}
+
+unsafe extern "C" fn return_pooled_block(
+  env: sys::napi_env,
+  finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  #[cfg(not(feature = "minimal"))]
+  unregister_backing_ptr(finalize_data.cast());
+  unsafe { put_back_block(env, finalize_data.cast(), finalize_hint.cast_const().cast()) };
+}
+
+unsafe fn put_back_block(env: sys::napi_env, block_ptr: *mut u8, size_class: *const SizeClass) {
+  let size_class = unsafe { Arc::from_raw(size_class) };
+  let block = unsafe {
+    Box::from_raw(ptr::slice_from_raw_parts_mut(
+      block_ptr,
+      size_class.block_size,
+    ))
+  };
+  size_class.put_block(env, block);
+}
@@ -0,0 +1,99 @@
+use std::ptr;
+
+use crate::{bindgen_runtime::ToNapiValue, check_status, sys, Env, Result};
+
+/// The async context of an asynchronous operation, like the `AsyncResource` of `node:async_hooks`.
+///
+/// It captures the async context of the JavaScript call which created it, so the JavaScript functions called later by
+/// [`Env::with_callback_scope`] or `Function::make_callback`, from a uv callback or an async completion,
+/// run in that context. For example `AsyncLocalStorage.getStore()` returns the store of the call which started the operation.
+///
+/// It must be dropped on the JavaScript thread.
+pub struct AsyncContext {
+  env: sys::napi_env,
+  context: sys::napi_async_context,
+  resource: sys::napi_ref,
+}
+
+impl AsyncContext {
+  /// `name` is the type of the resource reported to `async_hooks`.
+  pub fn new(env: &Env, name: &str) -> Result<Self> {
+    let mut resource = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env.0, &mut resource) },
+      "Failed to create the resource object of AsyncContext"
+    )?;
+    let resource_name = unsafe { ToNapiValue::to_napi_value(env.0, name) }?;
+    let mut context = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_async_init(env.0, resource, resource_name, &mut context) },
+      "Failed to initialize the async context `{}`",
+      name
+    )?;
+    let mut resource_ref = ptr::null_mut();
+    if let Err(err) = check_status!(
+      unsafe { sys::napi_create_reference(env.0, resource, 1, &mut resource_ref) },
+      "Failed to create the reference of the AsyncContext resource"
+    ) {
+      unsafe { sys::napi_async_destroy(env.0, context) };
+      return Err(err);
+    }
+    Ok(Self {
+      env: env.0,
+      context,
+      resource: resource_ref,
+    })
+  }
+
+  pub(crate) fn raw(&self) -> sys::napi_async_context {
+    self.context
+  }
+
+  fn resource(&self) -> Result<sys::napi_value> {
+    let mut resource = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.env, self.resource, &mut resource) },
+      "Failed to get the resource object of AsyncContext"
+    )?;
+    Ok(resource)
+  }
+}
+
+impl Drop for AsyncContext {
+  fn drop(&mut self) {
+    unsafe {
+      sys::napi_delete_reference(self.env, self.resource);
+      sys::napi_async_destroy(self.env, self.context);
+    }
+  }
+}
+
+impl Env {
+  /// Run `f` in a callback scope of `context`, to call JavaScript outside of a call from JavaScript,
+  /// for example from a uv callback.
+  ///
+  /// The JavaScript functions run in the async context of `context`, and the `process.nextTick` callbacks and the
+  /// microtasks they queue run when the scope is closed, like after a callback of the Node.js APIs.
+  /// The scope is opened in a handle scope, so `f` can create JavaScript values without one.
+  #[cfg(feature = "napi3")]
+  pub fn with_callback_scope<T, F>(&self, context: &AsyncContext, f: F) -> Result<T>
+  where
+    F: for<'scope> FnOnce(&'scope Env) -> Result<T>,
+  {
+    self.with_handle_scope(|env| {
+      let mut scope = ptr::null_mut();
+      check_status!(
+        unsafe {
+          sys::napi_open_callback_scope(env.0, context.resource()?, context.context, &mut scope)
+        },
+        "Failed to open callback scope"
+      )?;
+      let result = f(env);
+      check_status!(
+        unsafe { sys::napi_close_callback_scope(env.0, scope) },
+        "Failed to close callback scope"
+      )?;
+      result
+    })
+  }
+}
@@ -194,6 +194,7 @@ where
 
 impl<'env, T: 'env> FromNapiValue for ClassInstance<'env, T> {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> crate::Result<Self> {
+    check_class_instance::<T>(env, napi_val, type_name::<T>())?;
     let mut value = ptr::null_mut();
     check_status!(
       unsafe { sys::napi_unwrap(env, napi_val, &mut value) },
@@ -279,6 +280,7 @@ pub unsafe fn new_instance<T: 'static + ObjectFinalize>(
     "Failed to wrap native object of class `{}`",
     type_name::<T>(),
   )?;
+  tag_class_instance::<T>(env, result)?;
   Reference::<T>::add_ref(
     env,
     wrapped_value,
@@ -286,3 +288,52 @@ pub unsafe fn new_instance<T: 'static + ObjectFinalize>(
   );
   Ok(result)
 }
+
+/// Tags the instance of the `#[napi]` class `T` after it's wrapped, see [`check_class_instance`].
+///
+/// # Safety
+///
+/// `value` must be a valid object of `env`
+#[doc(hidden)]
+pub unsafe fn tag_class_instance<T: ?Sized>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  unsafe {
+    crate::bindgen_runtime::TypeTag::of_class::<T>().tag_raw(env, value)
+  }
+  #[cfg(not(feature = "napi8"))]
+  {
+    let _ = (env, value);
+    Ok(())
+  }
+}
+
+/// Checks that `value` is an instance of the `#[napi]` class `T` created by this copy of the addon, before its native
+/// pointer is unwrapped.
+///
+/// The objects created by another addon, or by another version of this addon, can wrap a pointer of another type.
+///
+/// # Safety
+///
+/// `value` must be a valid value of `env`
+#[doc(hidden)]
+pub unsafe fn check_class_instance<T: ?Sized>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  class_name: &str,
+) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  {
+    if !unsafe { crate::bindgen_runtime::TypeTag::of_class::<T>().is_tagged_raw(env, value) }? {
+      return Err(crate::Error::new(
+        crate::Status::InvalidArg,
+        format!("The object is not an instance of `{class_name}` created by this addon"),
+      ));
+    }
+  }
+  #[cfg(not(feature = "napi8"))]
+  let _ = (env, value, class_name);
+  Ok(())
+}
@@ -0,0 +1,269 @@
+use std::marker::PhantomData;
+use std::ptr::{self, NonNull};
+use std::slice;
+
+use crate::{
+  bindgen_prelude::{
+    ArrayBuffer, FromNapiValue, JsObjectValue, JsValue, TypeName, ValidateNapiValue,
+  },
+  bindgen_runtime::{impl_binary_fmt, track_slice_borrow},
+  check_status, sys, Error, Result, Status, Value, ValueType,
+};
+
+#[derive(Clone, Copy)]
+/// Zero copy JavaScript `DataView` shared between Rust and Node.js.
+///
+/// It can only be used in non-async context and the lifetime is bound to the fn closure.
+///
+/// Like the `DataView` in JavaScript, the numeric getters and setters read and write at arbitrary byte offsets,
+/// pass `little_endian = true` to use the little-endian byte order, otherwise big-endian is used.
+pub struct DataView<'env> {
+  data: NonNull<u8>,
+  byte_length: usize,
+  byte_offset: usize,
+  array_buffer: sys::napi_value,
+  raw_value: sys::napi_value,
+  env: sys::napi_env,
+  _marker: PhantomData<&'env ()>,
+}
+
+macro_rules! impl_data_view_accessors {
+  ($(($get:ident, $set:ident, $ty:ty)),* $(,)?) => {
+    $(
+      #[doc = concat!("Read a `", stringify!($ty), "` at `byte_offset`.")]
+      pub fn $get(&self, byte_offset: usize, little_endian: bool) -> Result<$ty> {
+        let bytes = self.bytes_at::<{ std::mem::size_of::<$ty>() }>(byte_offset)?;
+        Ok(if little_endian {
+          <$ty>::from_le_bytes(bytes)
+        } else {
+          <$ty>::from_be_bytes(bytes)
+        })
+      }
+
+      #[doc = concat!("Write a `", stringify!($ty), "` at `byte_offset`.")]
+      pub fn $set(&mut self, byte_offset: usize, value: $ty, little_endian: bool) -> Result<()> {
+        let bytes = if little_endian {
+          value.to_le_bytes()
+        } else {
+          value.to_be_bytes()
+        };
+        self.range_mut(byte_offset, bytes.len())?.copy_from_slice(&bytes);
+        Ok(())
+      }
+    )*
+  };
+}
+
+impl<'env> DataView<'env> {
+  /// Create a `DataView` over `length` bytes of `arraybuffer` starting at `byte_offset`.
+  pub fn from_arraybuffer(
+    arraybuffer: &ArrayBuffer<'env>,
+    byte_offset: usize,
+    length: usize,
+  ) -> Result<Self> {
+    if byte_offset
+      .checked_add(length)
+      .map_or(true, |end| end > arraybuffer.len())
+    {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "DataView of {} bytes at offset {} is outside the bounds of the ArrayBuffer of {} bytes",
+          length,
+          byte_offset,
+          arraybuffer.len()
+        ),
+      ));
+    }
+    let env = arraybuffer.value.env;
+    let mut data_view = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_dataview(
+          env,
+          length,
+          arraybuffer.value.value,
+          byte_offset,
+          &mut data_view,
+        )
+      },
+      "Failed to create DataView from ArrayBuffer"
+    )?;
+    unsafe { Self::from_napi_value(env, data_view) }
+  }
+
+  /// Number of bytes of the `DataView`.
+  pub fn byte_length(&self) -> usize {
+    self.byte_length
+  }
+
+  /// Offset of the `DataView` in its `ArrayBuffer`.
+  pub fn byte_offset(&self) -> usize {
+    self.byte_offset
+  }
+
+  /// The `ArrayBuffer` the `DataView` is created on.
+  pub fn array_buffer(&self) -> Result<ArrayBuffer<'env>> {
+    unsafe { ArrayBuffer::from_napi_value(self.env, self.array_buffer) }
+  }
+
+  /// Read an `i8` at `byte_offset`.
+  pub fn get_int8(&self, byte_offset: usize) -> Result<i8> {
+    Ok(self.get_uint8(byte_offset)? as i8)
+  }
+
+  /// Write an `i8` at `byte_offset`.
+  pub fn set_int8(&mut self, byte_offset: usize, value: i8) -> Result<()> {
+    self.set_uint8(byte_offset, value as u8)
+  }
+
+  /// Read a `u8` at `byte_offset`.
+  pub fn get_uint8(&self, byte_offset: usize) -> Result<u8> {
+    Ok(self.range(byte_offset, 1)?[0])
+  }
+
+  /// Write a `u8` at `byte_offset`.
+  pub fn set_uint8(&mut self, byte_offset: usize, value: u8) -> Result<()> {
+    self.range_mut(byte_offset, 1)?[0] = value;
+    Ok(())
+  }
+
+  impl_data_view_accessors!(
+    (get_int16, set_int16, i16),
+    (get_uint16, set_uint16, u16),
+    (get_int32, set_int32, i32),
+    (get_uint32, set_uint32, u32),
+    (get_float32, set_float32, f32),
+    (get_float64, set_float64, f64),
+    (get_big_int64, set_big_int64, i64),
+    (get_big_uint64, set_big_uint64, u64),
+  );
+
+  fn bytes_at<const N: usize>(&self, byte_offset: usize) -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(self.range(byte_offset, N)?);
+    Ok(bytes)
+  }
+
+  fn range(&self, byte_offset: usize, len: usize) -> Result<&[u8]> {
+    self
+      .as_ref()
+      .get(byte_offset..byte_offset.saturating_add(len))
+      .ok_or_else(|| out_of_bounds(byte_offset, len, self.byte_length))
+  }
+
+  fn range_mut(&mut self, byte_offset: usize, len: usize) -> Result<&mut [u8]> {
+    let byte_length = self.byte_length;
+    self
+      .as_mut()
+      .get_mut(byte_offset..byte_offset.saturating_add(len))
+      .ok_or_else(|| out_of_bounds(byte_offset, len, byte_length))
+  }
+}
+
+fn out_of_bounds(byte_offset: usize, len: usize, byte_length: usize) -> Error {
+  Error::new(
+    Status::InvalidArg,
+    format!(
+      "Offset {} with {} bytes is outside the bounds of the DataView of {} bytes",
+      byte_offset, len, byte_length
+    ),
+  )
+}
+
+impl_binary_fmt!(DataView<'_>, "DataView", |this| ownership: "JavaScript", length: this.byte_length, bytes: this.as_ref());
+
+impl AsRef<[u8]> for DataView<'_> {
+  fn as_ref(&self) -> &[u8] {
+    unsafe { slice::from_raw_parts(self.data.as_ptr(), self.byte_length) }
+  }
+}
+
+impl AsMut<[u8]> for DataView<'_> {
+  fn as_mut(&mut self) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(self.data.as_ptr(), self.byte_length) }
+  }
+}
+
+impl<'env> JsValue<'env> for DataView<'env> {
+  fn value(&self) -> Value {
+    Value {
+      env: self.env,
+      value: self.raw_value,
+      value_type: ValueType::Object,
+    }
+  }
+}
+
+impl<'env> JsObjectValue<'env> for DataView<'env> {}
+
+impl TypeName for DataView<'_> {
+  fn type_name() -> &'static str {
+    "DataView"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for DataView<'_> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let mut is_data_view = false;
+    check_status!(
+      unsafe { sys::napi_is_dataview(env, napi_val, &mut is_data_view) },
+      "Failed to check if value is DataView"
+    )?;
+    if !is_data_view {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Expected a DataView value".to_owned(),
+      ));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
+impl FromNapiValue for DataView<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { Self::validate(env, napi_val) }?;
+    let mut byte_length = 0;
+    let mut data = ptr::null_mut();
+    let mut array_buffer = ptr::null_mut();
+    let mut byte_offset = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_dataview_info(
+          env,
+          napi_val,
+          &mut byte_length,
+          &mut data,
+          &mut array_buffer,
+          &mut byte_offset,
+        )
+      },
+      "Get DataView info failed"
+    )?;
+    if !data.is_null() {
+      track_slice_borrow(
+        env,
+        array_buffer,
+        data.cast::<u8>().wrapping_sub(byte_offset),
+        "DataView",
+      );
+    }
+    Ok(Self {
+      data: if byte_length == 0 {
+        NonNull::dangling()
+      } else {
+        unsafe { NonNull::new_unchecked(data.cast()) }
+      },
+      byte_length,
+      byte_offset,
+      array_buffer,
+      raw_value: napi_val,
+      env,
+      _marker: PhantomData,
+    })
+  }
+}
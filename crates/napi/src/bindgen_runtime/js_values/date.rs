@@ -2,7 +2,7 @@ use std::{ptr, str::FromStr};
 
 use chrono::{DateTime, Local, LocalResult, NaiveDateTime, TimeZone};
 
-use crate::{bindgen_prelude::*, check_status, sys, ValueType};
+use crate::{bindgen_prelude::*, check_status, sys, JsDate, ValueType};
 
 impl<Tz: TimeZone> TypeName for DateTime<Tz> {
   fn type_name() -> &'static str {
@@ -16,16 +16,23 @@ impl<Tz: TimeZone> TypeName for DateTime<Tz> {
 
 impl<Tz: TimeZone> ValidateNapiValue for DateTime<Tz> {
   unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
-    let mut is_date = false;
-    check_status!(unsafe { sys::napi_is_date(env, napi_val, &mut is_date) })?;
-    if !is_date {
-      return Err(Error::new(
-        Status::InvalidArg,
-        "Expected a Date object".to_owned(),
-      ));
-    }
+    unsafe { JsDate::validate(env, napi_val) }
+  }
+}
+
+impl TypeName for NaiveDateTime {
+  fn type_name() -> &'static str {
+    "NaiveDateTime"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
 
-    Ok(ptr::null_mut())
+impl ValidateNapiValue for NaiveDateTime {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { JsDate::validate(env, napi_val) }
   }
 }
 
@@ -139,14 +146,10 @@ where
   DateTime<Tz>: From<DateTime<Local>>,
 {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
-    let mut milliseconds_since_epoch_utc = 0.0;
-
-    check_status!(
-      unsafe { sys::napi_get_date_value(env, napi_val, &mut milliseconds_since_epoch_utc) },
-      "Failed to convert napi value into rust type `DateTime`",
-    )?;
+    let milliseconds_since_epoch_utc =
+      unsafe { JsDate::millis_since_epoch(env, napi_val, "DateTime") }?;
 
-    match Local.timestamp_millis_opt(milliseconds_since_epoch_utc as i64) {
+    match Local.timestamp_millis_opt(milliseconds_since_epoch_utc) {
       LocalResult::Single(dt) => Ok(dt.into()),
       _ => Err(Error::new(
         Status::DateExpected,
@@ -3,10 +3,14 @@ use std::{
   ops::{Deref, DerefMut},
 };
 
+#[cfg(feature = "napi8")]
+use super::TypeTag;
 use super::{
   FromNapiMutRef, FromNapiRef, FromNapiValue, ToNapiValue, TypeName, Unknown, ValidateNapiValue,
 };
 use crate::{check_status, sys, Env, Error, Result, Status};
+#[cfg(feature = "napi8")]
+use crate::{JsValue, ValueType};
 
 #[repr(C)]
 pub struct External<T: 'static> {
@@ -100,6 +104,38 @@ impl<T: 'static> External<T> {
     let napi_value = unsafe { ToNapiValue::to_napi_value(env.0, self)? };
     Ok(unsafe { Unknown::from_raw_unchecked(env.0, napi_value) })
   }
+
+  /// Create the `External` of `value` tagged with `tag`, to recover it with [`External::downcast_tagged`].
+  ///
+  /// The `TypeId` of `T` is only unique in the addon which created the `External`,
+  /// the tag makes sure the `External` is created by the code which knows the tag.
+  #[cfg(feature = "napi8")]
+  pub fn new_tagged<'env>(env: &'env Env, value: T, tag: &TypeTag) -> Result<Unknown<'env>> {
+    let external = External::new(value).into_unknown(env)?;
+    tag.tag(&external)?;
+    Ok(external)
+  }
+
+  /// Recover the `External` created by [`External::new_tagged`] with the same `tag`.
+  ///
+  /// It fails if the `External` is created by another addon, or by another version of this addon, instead of
+  /// reading a value of another type.
+  #[cfg(feature = "napi8")]
+  pub fn downcast_tagged<'env>(value: &Unknown<'env>, tag: &TypeTag) -> Result<&'env mut Self> {
+    if value.get_type()? != ValueType::External {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The value is not an `External`".to_owned(),
+      ));
+    }
+    if !tag.is_tagged(value)? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("The `External` is not tagged with {tag:?}"),
+      ));
+    }
+    unsafe { Self::from_napi_mut_ref(value.value().env, value.raw()) }
+  }
 }
 
 impl<T: 'static> FromNapiMutRef for External<T> {
@@ -0,0 +1,334 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+use std::ffi::c_void;
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+use std::ptr;
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+use crate::{check_status, sys, Env, Error, Result, Status};
+
+thread_local! {
+  // The policy and the accounting state by env, removed by a cleanup hook of the env with `napi3`
+  static EXTERNAL_MEMORY: RefCell<HashMap<usize, ExternalMemoryState>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// How the memory of the `ArrayBuffer`s, `Buffer`s and TypedArrays created from Rust data is reported to the engine
+/// by `napi_adjust_external_memory`, set by [`Env::set_external_memory_policy`].
+///
+/// The engine schedules GC by the reported memory, reporting every allocation triggers GC aggressively
+/// in the workloads creating many short-lived buffers.
+pub enum ExternalMemoryPolicy {
+  /// Report every allocation and release immediately
+  #[default]
+  Exact,
+  /// Report the changes once the unreported bytes reach the given size
+  Batched(usize),
+  /// Don't report the memory
+  Disabled,
+}
+
+#[derive(Default)]
+struct ExternalMemoryState {
+  policy: ExternalMemoryPolicy,
+  // changes not reported yet under `Batched`
+  pending: i64,
+  // total reported by the policy, the releases never report more than it
+  reported: i64,
+}
+
+impl ExternalMemoryState {
+  // the bytes to report for the change of `bytes`
+  fn record(&mut self, bytes: i64) -> i64 {
+    let bytes = match self.policy {
+      ExternalMemoryPolicy::Exact => bytes,
+      ExternalMemoryPolicy::Batched(batch) => {
+        self.pending += bytes;
+        if self.pending.unsigned_abs() < batch as u64 {
+          return 0;
+        }
+        std::mem::take(&mut self.pending)
+      }
+      ExternalMemoryPolicy::Disabled => return 0,
+    };
+    self.take_reported(bytes)
+  }
+
+  fn take_pending(&mut self) -> i64 {
+    let pending = std::mem::take(&mut self.pending);
+    self.take_reported(pending)
+  }
+
+  fn take_reported(&mut self, bytes: i64) -> i64 {
+    // the memory allocated while the reporting was disabled is released without being reported
+    let bytes = bytes.max(-self.reported);
+    self.reported += bytes;
+    bytes
+  }
+}
+
+// The engine may run GC and the finalizers adjusting the memory again, so it's called without borrowing the states.
+fn report(env: sys::napi_env, bytes: i64) -> Result<()> {
+  if bytes == 0 {
+    return Ok(());
+  }
+  let mut _adjusted = 0;
+  check_status!(
+    unsafe { sys::napi_adjust_external_memory(env, bytes, &mut _adjusted) },
+    "adjust external memory"
+  )
+}
+
+/// Report `bytes` allocated (or released if negative) outside the JavaScript heap according to the policy of `env`.
+pub(crate) fn adjust_external_memory(env: sys::napi_env, bytes: i64) -> Result<()> {
+  if env.is_null() || bytes == 0 {
+    return Ok(());
+  }
+  let bytes = with_state(env, bytes > 0, |state| state.record(bytes));
+  report(env, bytes.unwrap_or(0))
+}
+
+// Call `f` with the state of `env`, the state is created if `create` is true.
+// A release without a state is never reported, so the finalizers running after the cleanup hook don't create it again.
+fn with_state<R>(
+  env: sys::napi_env,
+  create: bool,
+  f: impl FnOnce(&mut ExternalMemoryState) -> R,
+) -> Option<R> {
+  let (result, created) = EXTERNAL_MEMORY
+    .try_with(|states| {
+      let mut states = states.borrow_mut();
+      let created = !states.contains_key(&(env as usize));
+      if created && !create {
+        return (None, false);
+      }
+      let state = states.entry(env as usize).or_default();
+      (Some(f(state)), created)
+    })
+    .unwrap_or((None, false));
+  #[cfg(feature = "napi3")]
+  if created {
+    let _ = Env::from_raw(env).add_env_cleanup_hook(env as usize, |env| {
+      let _ = EXTERNAL_MEMORY.try_with(|states| states.borrow_mut().remove(&env));
+    });
+  }
+  #[cfg(not(feature = "napi3"))]
+  let _ = created;
+  result
+}
+
+impl Env {
+  /// Set how the memory of the binary values created from Rust data is reported to the engine, see [`ExternalMemoryPolicy`].
+  ///
+  /// The changes pending under the previous policy are reported first.
+  pub fn set_external_memory_policy(&self, policy: ExternalMemoryPolicy) -> Result<()> {
+    let pending = with_state(self.0, true, |state| {
+      state.policy = policy;
+      state.take_pending()
+    });
+    report(self.0, pending.unwrap_or(0))
+  }
+
+  pub fn external_memory_policy(&self) -> ExternalMemoryPolicy {
+    EXTERNAL_MEMORY.with(|states| {
+      states
+        .borrow()
+        .get(&(self.0 as usize))
+        .map(|state| state.policy)
+        .unwrap_or_default()
+    })
+  }
+
+  /// Report the changes pending under [`ExternalMemoryPolicy::Batched`] now.
+  pub fn flush_external_memory(&self) -> Result<()> {
+    let pending = EXTERNAL_MEMORY.with(|states| {
+      states
+        .borrow_mut()
+        .get_mut(&(self.0 as usize))
+        .map_or(0, ExternalMemoryState::take_pending)
+    });
+    report(self.0, pending)
+  }
+}
+
+/// Reports `bytes` of memory allocated outside the JavaScript heap when it's created, and reports them released when it's dropped.
+///
+/// Keep it next to the allocation, e.g. in the struct wrapped by a class, so the engine schedules GC with the memory
+/// retained by the JavaScript objects. It can be dropped on any thread, the release is reported on the JavaScript thread
+/// of the env it's created in, through a threadsafe function of the env. Without `napi6`, the releases on the other threads
+/// are not reported.
+///
+/// Unlike the binary values, it's reported exactly, regardless of the [`ExternalMemoryPolicy`].
+#[must_use = "the memory is reported released when the ExternalMemoryGuard is dropped"]
+pub struct ExternalMemoryGuard {
+  env: sys::napi_env,
+  bytes: i64,
+  thread: ThreadId,
+  #[cfg(all(feature = "napi6", not(feature = "noop")))]
+  releaser: Arc<MemoryReleaser>,
+}
+
+// The memory is only adjusted on the thread it's created on
+unsafe impl Send for ExternalMemoryGuard {}
+unsafe impl Sync for ExternalMemoryGuard {}
+
+impl ExternalMemoryGuard {
+  pub fn new(env: &Env, bytes: usize) -> Result<Self> {
+    #[cfg(all(feature = "napi6", not(feature = "noop")))]
+    let releaser = env.with_instance_or_init(
+      || MemoryReleaser::create(env).map(EnvMemoryReleaser),
+      |releaser| releaser.0.clone(),
+    )?;
+    report(env.0, bytes as i64)?;
+    Ok(Self {
+      env: env.0,
+      bytes: bytes as i64,
+      thread: thread::current().id(),
+      #[cfg(all(feature = "napi6", not(feature = "noop")))]
+      releaser,
+    })
+  }
+
+  /// The bytes currently reported by this guard.
+  pub fn bytes(&self) -> usize {
+    self.bytes as usize
+  }
+
+  /// Report the allocation grown or shrunk to `bytes`, it must be called on the thread the guard is created on.
+  pub fn resize(&mut self, bytes: usize) -> Result<()> {
+    if thread::current().id() != self.thread {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "ExternalMemoryGuard can only be resized on the thread it's created on".to_owned(),
+      ));
+    }
+    report(self.env, bytes as i64 - self.bytes)?;
+    self.bytes = bytes as i64;
+    Ok(())
+  }
+}
+
+impl Drop for ExternalMemoryGuard {
+  fn drop(&mut self) {
+    if self.bytes == 0 {
+      return;
+    }
+    if thread::current().id() == self.thread {
+      let _ = report(self.env, -self.bytes);
+      return;
+    }
+    #[cfg(all(feature = "napi6", not(feature = "noop")))]
+    self.releaser.release(self.bytes);
+  }
+}
+
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+// The releaser of the env, in the instance data
+struct EnvMemoryReleaser(Arc<MemoryReleaser>);
+
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+/// The threadsafe function of an env reporting the memory released by the `ExternalMemoryGuard`s dropped on the other threads.
+struct MemoryReleaser {
+  // `None` once the threadsafe function is finalized with the env
+  tsfn: Mutex<Option<sys::napi_threadsafe_function>>,
+}
+
+// The threadsafe function is only called while it's not finalized, under the lock
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+unsafe impl Send for MemoryReleaser {}
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+unsafe impl Sync for MemoryReleaser {}
+
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+impl MemoryReleaser {
+  fn create(env: &Env) -> Result<Arc<Self>> {
+    let releaser = Arc::new(Self {
+      tsfn: Mutex::new(None),
+    });
+    let mut resource_name = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_string_utf8(
+          env.0,
+          c"ExternalMemoryRelease".as_ptr(),
+          21,
+          &mut resource_name,
+        )
+      },
+      "Create the resource name of the external memory releaser failed"
+    )?;
+    let finalize_data = Arc::into_raw(releaser.clone());
+    let mut tsfn = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env.0,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          resource_name,
+          0,
+          1,
+          finalize_data.cast_mut().cast(),
+          Some(finalize_memory_releaser),
+          ptr::null_mut(),
+          Some(release_external_memory),
+          &mut tsfn,
+        )
+      },
+      "Create the external memory releaser failed"
+    )
+    .inspect_err(|_| drop(unsafe { Arc::from_raw(finalize_data) }))?;
+    // it doesn't keep the event loop alive
+    check_status!(
+      unsafe { sys::napi_unref_threadsafe_function(env.0, tsfn) },
+      "Unref the external memory releaser failed"
+    )?;
+    if let Ok(mut slot) = releaser.tsfn.lock() {
+      *slot = Some(tsfn);
+    }
+    Ok(releaser)
+  }
+
+  fn release(&self, bytes: i64) {
+    let Ok(tsfn) = self.tsfn.lock() else {
+      return;
+    };
+    if let Some(tsfn) = *tsfn {
+      unsafe {
+        sys::napi_call_threadsafe_function(
+          tsfn,
+          bytes as usize as *mut c_void,
+          sys::ThreadsafeFunctionCallMode::nonblocking,
+        )
+      };
+    }
+  }
+}
+
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+unsafe extern "C" fn finalize_memory_releaser(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  let releaser = unsafe { Arc::from_raw(finalize_data.cast_const().cast::<MemoryReleaser>()) };
+  if let Ok(mut tsfn) = releaser.tsfn.lock() {
+    *tsfn = None;
+  };
+}
+
+#[cfg(all(feature = "napi6", not(feature = "noop")))]
+unsafe extern "C" fn release_external_memory(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  _context: *mut c_void,
+  data: *mut c_void,
+) {
+  // the env is null when the env is torn down
+  if !env.is_null() {
+    let _ = report(env, -(data as usize as i64));
+  }
+}
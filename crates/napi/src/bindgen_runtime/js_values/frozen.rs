@@ -0,0 +1,252 @@
+use std::ffi::{c_void, CString};
+use std::ptr;
+
+use crate::{
+  bindgen_runtime::{
+    BigInt64Array, BigUint64Array, Float32Array, Float64Array, FromNapiValue, Int16Array,
+    Int32Array, Int8Array, RefCache, ToNapiValue, TypeName, Uint16Array, Uint32Array, Uint8Array,
+    Uint8ClampedArray,
+  },
+  check_status, sys, Env, JsError, Result, ValueType,
+};
+
+// The methods which would write or expose a writable view of the memory
+const WRITERS: [&str; 6] = ["copyWithin", "fill", "reverse", "set", "sort", "subarray"];
+
+// The traps of the `Proxy` handler which reject the writes
+const REJECTED_TRAPS: [&str; 4] = ["set", "defineProperty", "deleteProperty", "setPrototypeOf"];
+
+#[derive(PartialEq, Eq, Hash)]
+// The `Proxy` handler wrapping the TypedArrays is cached per env
+struct FrozenHandler;
+
+/// A TypedArray which is read-only in JavaScript, created by `freeze` of the TypedArrays like [`Uint8Array::freeze`].
+///
+/// The TypedArray is wrapped in a `Proxy`, so the elements can't be written and the methods writing the elements throw,
+/// the `buffer` and `subarray` which would expose a writable view of the memory throw as well.
+/// As a `Proxy` isn't a TypedArray for Node-API, it can't be passed back to Rust as a TypedArray.
+pub struct Frozen<T>(T);
+
+impl<T> Frozen<T> {
+  /// The TypedArray before it's frozen.
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T: TypeName> TypeName for Frozen<T> {
+  fn type_name() -> &'static str {
+    T::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: ToNapiValue> ToNapiValue for Frozen<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let typed_array = unsafe { T::to_napi_value(env, val.0)? };
+    let handler = RefCache::get_or_create(&Env::from_raw(env), FrozenHandler, || {
+      create_frozen_handler(env)
+    })?;
+    let mut global = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_global(env, &mut global) },
+      "Failed to get global object"
+    )?;
+    let mut proxy = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_named_property(env, global, c"Proxy".as_ptr(), &mut proxy) },
+      "Failed to get the Proxy constructor"
+    )?;
+    let mut frozen = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_new_instance(env, proxy, 2, [typed_array, handler].as_ptr(), &mut frozen)
+      },
+      "Failed to freeze the TypedArray"
+    )?;
+    Ok(frozen)
+  }
+}
+
+fn create_frozen_handler(env: sys::napi_env) -> Result<sys::napi_value> {
+  let mut handler = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_object(env, &mut handler) },
+    "Failed to create the Proxy handler"
+  )?;
+  let traps = REJECTED_TRAPS
+    .iter()
+    .map(|trap| (*trap, reject_write as TrapCallback))
+    .chain([("get", frozen_get as TrapCallback)]);
+  for (trap, callback) in traps {
+    let mut function = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_function(
+          env,
+          trap.as_ptr().cast(),
+          trap.len() as isize,
+          Some(callback),
+          ptr::null_mut(),
+          &mut function,
+        )
+      },
+      "Failed to create the `{}` trap",
+      trap
+    )?;
+    let trap = CString::new(trap)?;
+    check_status!(
+      unsafe { sys::napi_set_named_property(env, handler, trap.as_ptr(), function) },
+      "Failed to set the `{:?}` trap",
+      trap
+    )?;
+  }
+  Ok(handler)
+}
+
+type TrapCallback = unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value;
+
+unsafe extern "C" fn reject_write(
+  env: sys::napi_env,
+  _info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut result = ptr::null_mut();
+  unsafe { sys::napi_get_boolean(env, false, &mut result) };
+  result
+}
+
+// `get(target, key)`, the methods are bound to the TypedArray, except the `WRITERS` which throw
+unsafe extern "C" fn frozen_get(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  match unsafe { frozen_get_property(env, info) } {
+    Ok(value) => value,
+    Err(err) => {
+      unsafe { JsError::from(err).throw_into(env) };
+      ptr::null_mut()
+    }
+  }
+}
+
+unsafe fn frozen_get_property(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> Result<sys::napi_value> {
+  let mut argc = 2;
+  let mut args = [ptr::null_mut(); 2];
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        info,
+        &mut argc,
+        args.as_mut_ptr(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    },
+    "Failed to get the arguments of the `get` trap"
+  )?;
+  let [target, key] = args;
+  let mut key_type = 0;
+  check_status!(unsafe { sys::napi_typeof(env, key, &mut key_type) })?;
+  let name = if key_type == sys::ValueType::napi_string {
+    Some(unsafe { String::from_napi_value(env, key)? })
+  } else {
+    None
+  };
+  match name.as_deref() {
+    Some("buffer") => {
+      return throw_type_error(
+        env,
+        "The ArrayBuffer of a frozen TypedArray is not accessible",
+      )
+    }
+    Some(name) => {
+      if let Some(index) = WRITERS.iter().position(|writer| *writer == name) {
+        let mut writer = ptr::null_mut();
+        check_status!(
+          unsafe {
+            sys::napi_create_function(
+              env,
+              name.as_ptr().cast(),
+              name.len() as isize,
+              Some(reject_call),
+              index as *mut c_void,
+              &mut writer,
+            )
+          },
+          "Failed to create the `{}` method of the frozen TypedArray",
+          name
+        )?;
+        return Ok(writer);
+      }
+    }
+    None => {}
+  }
+  let mut value = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_property(env, target, key, &mut value) })?;
+  let mut value_type = 0;
+  check_status!(unsafe { sys::napi_typeof(env, value, &mut value_type) })?;
+  if value_type != sys::ValueType::napi_function || name.as_deref() == Some("constructor") {
+    return Ok(value);
+  }
+  let mut bind = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_named_property(env, value, c"bind".as_ptr(), &mut bind) })?;
+  let mut bound = ptr::null_mut();
+  check_status!(unsafe { sys::napi_call_function(env, value, bind, 1, &target, &mut bound) })?;
+  Ok(bound)
+}
+
+unsafe extern "C" fn reject_call(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut index = ptr::null_mut();
+  unsafe {
+    sys::napi_get_cb_info(
+      env,
+      info,
+      ptr::null_mut(),
+      ptr::null_mut(),
+      ptr::null_mut(),
+      &mut index,
+    )
+  };
+  let writer = WRITERS[index as usize];
+  throw_type_error(env, &format!("Cannot call {writer} on a frozen TypedArray"))
+    .unwrap_or(ptr::null_mut())
+}
+
+fn throw_type_error(env: sys::napi_env, message: &str) -> Result<sys::napi_value> {
+  let message = CString::new(message)?;
+  check_status!(unsafe { sys::napi_throw_type_error(env, ptr::null(), message.as_ptr()) })?;
+  Ok(ptr::null_mut())
+}
+
+macro_rules! impl_freeze {
+  ($name:ident) => {
+    impl $name {
+      /// Convert to a TypedArray which is read-only in JavaScript, see [`Frozen`].
+      pub fn freeze(self) -> Frozen<Self> {
+        Frozen(self)
+      }
+    }
+  };
+}
+
+impl_freeze!(Int8Array);
+impl_freeze!(Uint8Array);
+impl_freeze!(Uint8ClampedArray);
+impl_freeze!(Int16Array);
+impl_freeze!(Uint16Array);
+impl_freeze!(Int32Array);
+impl_freeze!(Uint32Array);
+impl_freeze!(Float32Array);
+impl_freeze!(Float64Array);
+impl_freeze!(BigInt64Array);
+impl_freeze!(BigUint64Array);
@@ -1,9 +1,22 @@
 use std::ptr;
+#[cfg(all(feature = "napi4", not(target_family = "wasm")))]
+use std::time::Duration;
+#[cfg(feature = "napi4")]
+use std::{rc::Rc, sync::Arc};
 
-use super::{Either, FromNapiValue, ToNapiValue, TypeName, Unknown, ValidateNapiValue};
+use super::{
+  AsyncContext, Either, FromNapiValue, ToNapiValue, TypeName, Unknown, ValidateNapiValue,
+};
 
+#[cfg(all(feature = "napi4", not(target_family = "wasm")))]
+use crate::threadsafe_function::BatchedThreadsafeFunction;
+#[cfg(feature = "napi4")]
+use crate::threadsafe_function::{
+  HighWaterMarkCallback, Keepalive, ThreadsafeCallContext, ThreadsafeFunction,
+  ThreadsafeFunctionStats,
+};
 #[cfg(feature = "napi4")]
-use crate::threadsafe_function::{ThreadsafeCallContext, ThreadsafeFunction};
+use crate::ErrorSink;
 #[cfg(feature = "compat-mode")]
 #[allow(deprecated)]
 pub use crate::JsFunction;
@@ -14,6 +27,15 @@ use crate::{
 
 pub trait JsValuesTupleIntoVec {
   fn into_vec(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>>;
+
+  /// The arguments of a `ThreadsafeFunction` call, a tuple is spread like `FnArgs`,
+  /// so `ThreadsafeFunction<(A, B)>` calls `(a: A, b: B) => R` as declared in the generated types
+  fn into_threadsafe_args(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>>
+  where
+    Self: Sized,
+  {
+    self.into_vec(env)
+  }
 }
 
 impl<T> JsValuesTupleIntoVec for T
@@ -31,6 +53,15 @@ where
       }])
     }
   }
+
+  #[allow(clippy::not_unsafe_ptr_arg_deref)]
+  fn into_threadsafe_args(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>> {
+    if std::mem::size_of::<T>() == 0 {
+      Ok(vec![])
+    } else {
+      unsafe { <T as ToNapiValue>::to_napi_args(env, self) }
+    }
+  }
 }
 pub trait TupleFromSliceValues {
   #[allow(clippy::missing_safety_doc)]
@@ -213,12 +244,96 @@ impl<Args: JsValuesTupleIntoVec, Return> Function<'_, Args, Return> {
     ThreadsafeFunctionBuilder {
       env: self.env,
       value: self.value,
+      keepalive: Keepalive::Always,
+      error_sink: None,
+      high_water_mark: None,
+      track_latency: false,
       _args: std::marker::PhantomData,
       _return: std::marker::PhantomData,
     }
   }
 }
 
+#[cfg(feature = "napi5")]
+impl<'scope, Args: 'static + JsValuesTupleIntoVec, Return: 'static> Function<'scope, Args, Return> {
+  /// Wrap the JavaScript function with Rust hooks.
+  ///
+  /// Returns a new JavaScript function with the same name. When it is called, `before` runs first and
+  /// may inspect or replace the arguments and `this`, then the original function is called, then
+  /// `after` receives the value returned by `before` along with the return value of the original function,
+  /// and its result is returned to the caller.
+  ///
+  /// Returning an `Err` from either hook throws it to the caller; an exception thrown by the
+  /// original function skips `after` and is re-thrown as is.
+  ///
+  /// ```no_run
+  /// use std::time::Instant;
+  ///
+  /// use napi::bindgen_prelude::*;
+  /// use napi_derive::napi;
+  ///
+  /// #[napi]
+  /// pub fn measure<'env>(func: Function<'env, (), ()>) -> Result<Function<'env, (), ()>> {
+  ///   func.wrap_with(
+  ///     |_ctx| Ok(Instant::now()),
+  ///     |_ctx, started, ret| {
+  ///       eprintln!("took {:?}", started.elapsed());
+  ///       Ok(ret)
+  ///     },
+  ///   )
+  /// }
+  /// ```
+  pub fn wrap_with<State, Before, After>(
+    &self,
+    before: Before,
+    after: After,
+  ) -> Result<Function<'scope, Args, Return>>
+  where
+    Before: 'static + Fn(&mut FunctionHookContext) -> Result<State>,
+    After:
+      'static + for<'a> Fn(&FunctionHookContext<'a>, State, Unknown<'a>) -> Result<Unknown<'a>>,
+  {
+    let original = self.create_ref()?;
+    let name = self.name().unwrap_or_default();
+    let env = Env::from_raw(self.env);
+    let wrapped =
+      env.create_function_from_closure::<Args, sys::napi_value, _>(&name, move |ctx| {
+        let raw_env = ctx.env.raw();
+        let original = original.borrow_back(ctx.env)?;
+        let mut hook_ctx = FunctionHookContext {
+          env: ctx.env,
+          this: ctx.this,
+          args: ctx.args.to_vec(),
+        };
+        let state = before(&mut hook_ctx)?;
+        let mut raw_return = ptr::null_mut();
+        check_pending_exception!(
+          raw_env,
+          unsafe {
+            sys::napi_call_function(
+              raw_env,
+              hook_ctx.this,
+              original.value,
+              hook_ctx.args.len(),
+              hook_ctx.args.as_ptr(),
+              &mut raw_return,
+            )
+          },
+          "Call wrapped Function failed"
+        )?;
+        let ret = unsafe { Unknown::from_raw_unchecked(raw_env, raw_return) };
+        after(&hook_ctx, state, ret).map(|ret| ret.raw())
+      })?;
+    Ok(Function {
+      env: self.env,
+      value: wrapped.value,
+      _args: std::marker::PhantomData,
+      _return: std::marker::PhantomData,
+      _scope: std::marker::PhantomData,
+    })
+  }
+}
+
 impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> Function<'_, Args, Return> {
   /// Call the JavaScript function.
   /// `this` in the JavaScript function will be `undefined`.
@@ -270,6 +385,39 @@ impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> Function<'_, Args, Retur
     unsafe { Return::from_napi_value(self.env, raw_return) }
   }
 
+  /// Call the JavaScript function with `napi_make_callback`, in the async context of `context`.
+  ///
+  /// Unlike [`Function::apply`], the `process.nextTick` callbacks and the microtasks queued by the function run before it
+  /// returns if there is no JavaScript on the stack, so it can call JavaScript from a uv callback or an async completion.
+  ///
+  /// `this` must be an object, pass the global object for a plain function.
+  pub fn make_callback<Context: ToNapiValue>(
+    &self,
+    context: &AsyncContext,
+    this: Context,
+    args: Args,
+  ) -> Result<Return> {
+    let raw_this = unsafe { Context::to_napi_value(self.env, this) }?;
+    let args_ptr = args.into_vec(self.env)?;
+    let mut raw_return = ptr::null_mut();
+    check_pending_exception!(
+      self.env,
+      unsafe {
+        sys::napi_make_callback(
+          self.env,
+          context.raw(),
+          raw_this,
+          self.value,
+          args_ptr.len(),
+          args_ptr.as_ptr(),
+          &mut raw_return,
+        )
+      },
+      "Make callback failed"
+    )?;
+    unsafe { Return::from_napi_value(self.env, raw_return) }
+  }
+
   /// Call `Function.bind`
   pub fn bind<T: ToNapiValue>(&self, this: T) -> Result<Function<'_, Args, Return>> {
     let raw_this = unsafe { T::to_napi_value(self.env, this) }?;
@@ -317,6 +465,10 @@ pub struct ThreadsafeFunctionBuilder<
 > {
   pub(crate) env: sys::napi_env,
   pub(crate) value: sys::napi_value,
+  keepalive: Keepalive,
+  error_sink: Option<Rc<dyn ErrorSink>>,
+  high_water_mark: Option<(usize, HighWaterMarkCallback)>,
+  track_latency: bool,
   _args: std::marker::PhantomData<(T, &'env Args, ErrorStatus)>,
   _return: std::marker::PhantomData<Return>,
 }
@@ -349,6 +501,10 @@ impl<
     ThreadsafeFunctionBuilder {
       env: self.env,
       value: self.value,
+      keepalive: self.keepalive,
+      error_sink: self.error_sink,
+      high_water_mark: self.high_water_mark,
+      track_latency: self.track_latency,
       _args: std::marker::PhantomData,
       _return: std::marker::PhantomData,
     }
@@ -369,6 +525,10 @@ impl<
     ThreadsafeFunctionBuilder {
       env: self.env,
       value: self.value,
+      keepalive: self.keepalive,
+      error_sink: self.error_sink,
+      high_water_mark: self.high_water_mark,
+      track_latency: self.track_latency,
       _args: std::marker::PhantomData,
       _return: std::marker::PhantomData,
     }
@@ -389,11 +549,46 @@ impl<
     ThreadsafeFunctionBuilder {
       env: self.env,
       value: self.value,
+      keepalive: self.keepalive,
+      error_sink: self.error_sink,
+      high_water_mark: self.high_water_mark,
+      track_latency: self.track_latency,
       _args: std::marker::PhantomData,
       _return: std::marker::PhantomData,
     }
   }
 
+  /// See [`Keepalive`], the ThreadsafeFunction keeps the event loop alive until it's dropped by default.
+  pub fn with_keepalive(mut self, keepalive: Keepalive) -> Self {
+    self.keepalive = keepalive;
+    self
+  }
+
+  /// Deliver the errors of the callbacks to `sink` instead of the sink of the env, see [`ErrorSink`].
+  pub fn with_error_sink<S: ErrorSink>(mut self, sink: S) -> Self {
+    self.error_sink = Some(Rc::new(sink));
+    self
+  }
+
+  /// Call `callback` when the number of the queued calls reaches `mark`, on the thread which makes the call.
+  ///
+  /// It's called again once the queue goes below `mark` and reaches it again. See [`ThreadsafeFunctionStats`].
+  pub fn with_high_water_mark<F>(mut self, mark: usize, callback: F) -> Self
+  where
+    F: 'static + Fn(ThreadsafeFunctionStats) + Send + Sync,
+  {
+    self.high_water_mark = Some((mark, Arc::new(callback)));
+    self
+  }
+
+  /// Measure how long the calls wait in the queue, for [`ThreadsafeFunctionStats::average_latency`].
+  ///
+  /// It takes a lock on every call, so it's off by default.
+  pub fn with_latency_tracking(mut self) -> Self {
+    self.track_latency = true;
+    self
+  }
+
   pub fn build_callback<CallJsBackArgs, Callback>(
     &self,
     call_js_back: Callback,
@@ -406,11 +601,20 @@ impl<
     ErrorStatus: AsRef<str>,
     ErrorStatus: From<Status>,
   {
-    ThreadsafeFunction::<T, Return, Args, ErrorStatus, CalleeHandled, Weak, MaxQueueSize>::create(
+    let tsfn = ThreadsafeFunction::<T, Return, Args, ErrorStatus, CalleeHandled, Weak, MaxQueueSize>::create_with_error_sink(
       self.env,
       self.value,
       call_js_back,
-    )
+      self.error_sink.clone(),
+    )?;
+    tsfn.set_keepalive(self.env, self.keepalive)?;
+    if let Some((mark, callback)) = &self.high_water_mark {
+      tsfn.set_high_water_mark(*mark, callback.clone());
+    }
+    if self.track_latency {
+      tsfn.track_latency();
+    }
+    Ok(tsfn)
   }
 }
 
@@ -427,7 +631,47 @@ impl<
   pub fn build(
     &self,
   ) -> Result<ThreadsafeFunction<T, Return, T, ErrorStatus, CalleeHandled, Weak, MaxQueueSize>> {
-    unsafe { ThreadsafeFunction::from_napi_value(self.env, self.value) }
+    let tsfn =
+      ThreadsafeFunction::<T, Return, T, ErrorStatus, CalleeHandled, Weak, MaxQueueSize>::create_with_error_sink(
+        self.env,
+        self.value,
+        |ctx| Ok(ctx.value),
+        self.error_sink.clone(),
+      )?;
+    tsfn.set_keepalive(self.env, self.keepalive)?;
+    if let Some((mark, callback)) = &self.high_water_mark {
+      tsfn.set_high_water_mark(*mark, callback.clone());
+    }
+    if self.track_latency {
+      tsfn.track_latency();
+    }
+    Ok(tsfn)
+  }
+}
+
+#[cfg(all(feature = "napi4", not(target_family = "wasm")))]
+impl<
+    T: 'static + ToNapiValue,
+    Return: FromNapiValue,
+    ErrorStatus: AsRef<str> + From<Status>,
+    const Weak: bool,
+  > ThreadsafeFunctionBuilder<'_, Vec<T>, Vec<T>, Return, ErrorStatus, false, Weak, 0>
+{
+  /// Build a [`BatchedThreadsafeFunction`], which calls the JavaScript function with arrays of the values,
+  /// at most `max_batch_size` values per call and at most one call per `flush_interval` unless a batch is full.
+  pub fn build_batched(
+    &self,
+    max_batch_size: usize,
+    flush_interval: Duration,
+  ) -> Result<BatchedThreadsafeFunction<T>> {
+    BatchedThreadsafeFunction::create(
+      self.env,
+      self.value,
+      max_batch_size,
+      flush_interval,
+      Weak,
+      self.error_sink.clone(),
+    )
   }
 }
 
@@ -574,6 +818,79 @@ impl FunctionCallContext<'_> {
   }
 }
 
+#[cfg(feature = "napi5")]
+/// The call context passed to the hooks of [`Function::wrap_with`].
+///
+/// Unlike [`FunctionCallContext`], the arguments and `this` can be replaced before the original function is called.
+pub struct FunctionHookContext<'scope> {
+  pub(crate) args: Vec<sys::napi_value>,
+  pub(crate) this: sys::napi_value,
+  pub env: &'scope Env,
+}
+
+#[cfg(feature = "napi5")]
+impl FunctionHookContext<'_> {
+  /// Get the number of arguments that will be passed to the original function.
+  pub fn length(&self) -> usize {
+    self.args.len()
+  }
+
+  pub fn get<ArgType: FromNapiValue>(&self, index: usize) -> Result<ArgType> {
+    if index >= self.length() {
+      Err(crate::Error::new(
+        crate::Status::GenericFailure,
+        "Arguments index out of range".to_owned(),
+      ))
+    } else {
+      unsafe { ArgType::from_napi_value(self.env.0, self.args[index]) }
+    }
+  }
+
+  /// Replace the argument at `index`.
+  /// If `index` is past the end of the arguments, the gap is filled with `undefined`.
+  pub fn set<ArgType: ToNapiValue>(&mut self, index: usize, value: ArgType) -> Result<()> {
+    let value = unsafe { ArgType::to_napi_value(self.env.0, value) }?;
+    if index >= self.args.len() {
+      let undefined = unsafe { <()>::to_napi_value(self.env.0, ()) }?;
+      self.args.resize(index + 1, undefined);
+    }
+    self.args[index] = value;
+    Ok(())
+  }
+
+  /// Append an argument.
+  pub fn push<ArgType: ToNapiValue>(&mut self, value: ArgType) -> Result<()> {
+    let value = unsafe { ArgType::to_napi_value(self.env.0, value) }?;
+    self.args.push(value);
+    Ok(())
+  }
+
+  /// Remove all the arguments after `len`.
+  pub fn truncate(&mut self, len: usize) {
+    self.args.truncate(len);
+  }
+
+  /// Get the arguments Vec.
+  pub fn arguments<T: FromNapiValue>(&self) -> Result<Vec<T>> {
+    self
+      .args
+      .iter()
+      .map(|arg| unsafe { <T as FromNapiValue>::from_napi_value(self.env.0, *arg) })
+      .collect::<Result<Vec<T>>>()
+  }
+
+  /// Get the `this` that will be used to call the original function.
+  pub fn this<This: FromNapiValue>(&self) -> Result<This> {
+    unsafe { This::from_napi_value(self.env.0, self.this) }
+  }
+
+  /// Replace the `this` that will be used to call the original function.
+  pub fn set_this<This: ToNapiValue>(&mut self, this: This) -> Result<()> {
+    self.this = unsafe { This::to_napi_value(self.env.0, this) }?;
+    Ok(())
+  }
+}
+
 #[cfg(feature = "compat-mode")]
 macro_rules! impl_call_apply {
   ($fn_call_name:ident, $fn_apply_name:ident, $($ident:ident),*) => {
@@ -0,0 +1,190 @@
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::{
+  bindgen_runtime::{external_buffers_disallowed, ArrayBuffer, FromNapiValue},
+  check_status, sys, Env, Error, Result, Status,
+};
+
+// The memory given up by the `LoanedBuffer`, freed by the finalizer of the lent `ArrayBuffer`.
+type AbandonedData = Rc<RefCell<Option<Vec<u8>>>>;
+
+/// A Rust allocation lent to JavaScript as an `ArrayBuffer`, and recalled later without copying.
+///
+/// [`LoanedBuffer::lend`] creates an `ArrayBuffer` on the memory, [`LoanedBuffer::recall`] detaches it,
+/// so the JavaScript views of it become empty and the memory is owned by Rust again.
+/// The same memory can be lent and recalled repeatedly, for example to pass the frames between a Rust producer
+/// and a JavaScript consumer without allocating for each frame.
+///
+/// The `ArrayBuffer` is kept alive while it's lent. If it's detached by JavaScript meanwhile,
+/// the memory may be owned by another `ArrayBuffer`, so it can't be recalled. `recall` returns an error
+/// and the `LoanedBuffer` is left empty, the memory is freed when the lent `ArrayBuffer` is finalized.
+///
+/// Dropping a lent `LoanedBuffer` recalls it, so it must be dropped on the JavaScript thread.
+pub struct LoanedBuffer {
+  data: Vec<u8>,
+  loan: Option<Loan>,
+}
+
+struct Loan {
+  env: sys::napi_env,
+  array_buffer_ref: sys::napi_ref,
+  abandoned: AbandonedData,
+}
+
+impl LoanedBuffer {
+  pub fn new(data: Vec<u8>) -> Self {
+    Self { data, loan: None }
+  }
+
+  /// Whether the memory is lent to JavaScript.
+  pub fn is_lent(&self) -> bool {
+    self.loan.is_some()
+  }
+
+  /// The memory, returns an error while it's lent to JavaScript.
+  pub fn data(&self) -> Result<&[u8]> {
+    self.check_not_lent()?;
+    Ok(&self.data)
+  }
+
+  /// The memory, returns an error while it's lent to JavaScript.
+  pub fn data_mut(&mut self) -> Result<&mut Vec<u8>> {
+    self.check_not_lent()?;
+    Ok(&mut self.data)
+  }
+
+  /// Recall the memory and take it out of the `LoanedBuffer`.
+  pub fn into_inner(mut self) -> Result<Vec<u8>> {
+    self.recall()?;
+    Ok(mem::take(&mut self.data))
+  }
+
+  /// Lend the memory to JavaScript as an `ArrayBuffer` until [`LoanedBuffer::recall`].
+  pub fn lend<'env>(&mut self, env: &'env Env) -> Result<ArrayBuffer<'env>> {
+    self.check_not_lent()?;
+    let mut array_buffer = ptr::null_mut();
+    if self.data.is_empty() {
+      check_status!(
+        unsafe { sys::napi_create_arraybuffer(env.0, 0, ptr::null_mut(), &mut array_buffer) },
+        "Create empty ArrayBuffer for LoanedBuffer failed"
+      )?;
+      return unsafe { ArrayBuffer::from_napi_value(env.0, array_buffer) };
+    }
+    let abandoned = AbandonedData::default();
+    let hint = Box::into_raw(Box::new(abandoned.clone()));
+    let status = unsafe {
+      sys::napi_create_external_arraybuffer(
+        env.0,
+        self.data.as_mut_ptr().cast(),
+        self.data.len(),
+        Some(finalize_loaned_buffer),
+        hint.cast(),
+        &mut array_buffer,
+      )
+    };
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Box::from_raw(hint) });
+      if external_buffers_disallowed(status) {
+        return Err(Error::new(
+          Status::GenericFailure,
+          "LoanedBuffer can't be lent, external ArrayBuffers are not allowed in this runtime",
+        ));
+      }
+      check_status!(status, "Create ArrayBuffer for LoanedBuffer failed")?;
+    }
+    let mut array_buffer_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env.0, array_buffer, 1, &mut array_buffer_ref) },
+      "Create reference for LoanedBuffer failed"
+    )?;
+    self.loan = Some(Loan {
+      env: env.0,
+      array_buffer_ref,
+      abandoned,
+    });
+    unsafe { ArrayBuffer::from_napi_value(env.0, array_buffer) }
+  }
+
+  /// Detach the lent `ArrayBuffer` and take the memory back, it's a no-op if the memory is not lent.
+  pub fn recall(&mut self) -> Result<()> {
+    let Some(loan) = self.loan.take() else {
+      return Ok(());
+    };
+    let result = unsafe { loan.detach() };
+    unsafe {
+      sys::napi_delete_reference(loan.env, loan.array_buffer_ref);
+    }
+    match result {
+      Ok(true) => Ok(()),
+      Ok(false) => {
+        // the memory may be owned by the `ArrayBuffer` it was transferred to
+        *loan.abandoned.borrow_mut() = Some(mem::take(&mut self.data));
+        Err(Error::new(
+          Status::InvalidArg,
+          "The ArrayBuffer of LoanedBuffer was detached by JavaScript, the memory can't be recalled",
+        ))
+      }
+      Err(err) => {
+        *loan.abandoned.borrow_mut() = Some(mem::take(&mut self.data));
+        Err(err)
+      }
+    }
+  }
+
+  fn check_not_lent(&self) -> Result<()> {
+    if self.is_lent() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The memory of LoanedBuffer is lent to JavaScript, recall it first",
+      ));
+    }
+    Ok(())
+  }
+}
+
+impl Loan {
+  /// Returns `false` if the `ArrayBuffer` was detached by JavaScript before.
+  unsafe fn detach(&self) -> Result<bool> {
+    let mut array_buffer = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.env, self.array_buffer_ref, &mut array_buffer) },
+      "Get ArrayBuffer from LoanedBuffer reference failed"
+    )?;
+    let mut is_detached = false;
+    check_status!(unsafe {
+      sys::napi_is_detached_arraybuffer(self.env, array_buffer, &mut is_detached)
+    })?;
+    if is_detached {
+      return Ok(false);
+    }
+    check_status!(
+      unsafe { sys::napi_detach_arraybuffer(self.env, array_buffer) },
+      "Detach the ArrayBuffer of LoanedBuffer failed"
+    )?;
+    Ok(true)
+  }
+}
+
+impl From<Vec<u8>> for LoanedBuffer {
+  fn from(data: Vec<u8>) -> Self {
+    Self::new(data)
+  }
+}
+
+impl Drop for LoanedBuffer {
+  fn drop(&mut self) {
+    let _ = self.recall();
+  }
+}
+
+unsafe extern "C" fn finalize_loaned_buffer(
+  _env: sys::napi_env,
+  _finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_hint.cast::<AbandonedData>()) });
+}
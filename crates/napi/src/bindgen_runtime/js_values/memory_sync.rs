@@ -0,0 +1,136 @@
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "napi6")]
+use crate::bindgen_runtime::{BigInt64ArraySlice, BigUint64ArraySlice};
+use crate::{
+  bindgen_runtime::{
+    ArrayBuffer, BufferSlice, Float32ArraySlice, Float64ArraySlice, Int16ArraySlice,
+    Int32ArraySlice, Int8ArraySlice, Uint16ArraySlice, Uint32ArraySlice, Uint8ArraySlice,
+    Uint8ClampedSlice,
+  },
+  sys, JsError, JsValue, Result,
+};
+
+#[cfg(target_family = "wasm")]
+extern "C" {
+  pub(crate) fn emnapi_sync_memory(
+    env: sys::napi_env,
+    js_to_wasm: bool,
+    arraybuffer_or_view: sys::napi_value,
+    byte_offset: usize,
+    length: usize,
+  ) -> sys::napi_status;
+}
+
+/// The binary values whose memory is copied into the wasm memory by emnapi,
+/// the changes made in Rust are lost unless they are synced back to JavaScript.
+///
+/// Use [`SyncMemory::synced`] for mutable access which syncs automatically,
+/// it compiles to plain mutable access on the other targets.
+pub trait SyncMemory<'env>: JsValue<'env> + DerefMut<Target = [Self::Item]> {
+  type Item;
+
+  /// Copy the data from the wasm memory back to JavaScript, it's a no-op on the other targets.
+  fn sync_memory(&self) -> Result<()> {
+    let value = self.value();
+    sync_to_js(
+      value.env,
+      value.value,
+      std::mem::size_of_val::<[Self::Item]>(self),
+    )
+  }
+
+  /// Mutable access to the data, which is synced back to JavaScript when the guard is dropped if it's modified.
+  ///
+  /// A sync failure in `drop` is thrown into JavaScript, use [`SyncedMemory::finish`] to handle it in Rust.
+  fn synced(&mut self) -> SyncedMemory<'_, 'env, Self> {
+    SyncedMemory {
+      value: self,
+      modified: false,
+      _env: std::marker::PhantomData,
+    }
+  }
+}
+
+#[cfg(target_family = "wasm")]
+fn sync_to_js(env: sys::napi_env, value: sys::napi_value, byte_length: usize) -> Result<()> {
+  crate::check_status!(
+    unsafe { emnapi_sync_memory(env, false, value, 0, byte_length) },
+    "Failed to sync memory"
+  )
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[inline(always)]
+fn sync_to_js(_env: sys::napi_env, _value: sys::napi_value, _byte_length: usize) -> Result<()> {
+  Ok(())
+}
+
+/// The guard returned by [`SyncMemory::synced`].
+pub struct SyncedMemory<'a, 'env, T: SyncMemory<'env>> {
+  value: &'a mut T,
+  modified: bool,
+  _env: std::marker::PhantomData<&'env ()>,
+}
+
+impl<'env, T: SyncMemory<'env>> SyncedMemory<'_, 'env, T> {
+  /// Sync the modified data back to JavaScript and return the error if it fails.
+  pub fn finish(mut self) -> Result<()> {
+    self.sync()
+  }
+
+  fn sync(&mut self) -> Result<()> {
+    if !self.modified {
+      return Ok(());
+    }
+    self.modified = false;
+    self.value.sync_memory()
+  }
+}
+
+impl<'env, T: SyncMemory<'env>> Deref for SyncedMemory<'_, 'env, T> {
+  type Target = [T::Item];
+
+  fn deref(&self) -> &Self::Target {
+    self.value
+  }
+}
+
+impl<'env, T: SyncMemory<'env>> DerefMut for SyncedMemory<'_, 'env, T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.modified = true;
+    self.value
+  }
+}
+
+impl<'env, T: SyncMemory<'env>> Drop for SyncedMemory<'_, 'env, T> {
+  fn drop(&mut self) {
+    if let Err(err) = self.sync() {
+      unsafe { JsError::from(err).throw_into(self.value.value().env) };
+    }
+  }
+}
+
+macro_rules! impl_sync_memory {
+  ($ty:ident, $item:ty) => {
+    impl<'env> SyncMemory<'env> for $ty<'env> {
+      type Item = $item;
+    }
+  };
+}
+
+impl_sync_memory!(ArrayBuffer, u8);
+impl_sync_memory!(BufferSlice, u8);
+impl_sync_memory!(Uint8ClampedSlice, u8);
+impl_sync_memory!(Int8ArraySlice, i8);
+impl_sync_memory!(Uint8ArraySlice, u8);
+impl_sync_memory!(Int16ArraySlice, i16);
+impl_sync_memory!(Uint16ArraySlice, u16);
+impl_sync_memory!(Int32ArraySlice, i32);
+impl_sync_memory!(Uint32ArraySlice, u32);
+impl_sync_memory!(Float32ArraySlice, f32);
+impl_sync_memory!(Float64ArraySlice, f64);
+#[cfg(feature = "napi6")]
+impl_sync_memory!(BigInt64ArraySlice, i64);
+#[cfg(feature = "napi6")]
+impl_sync_memory!(BigUint64ArraySlice, u64);
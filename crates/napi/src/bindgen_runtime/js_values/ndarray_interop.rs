@@ -0,0 +1,163 @@
+use ndarray::{Array, ArrayView, ArrayViewMut, Dimension, IntoDimension, ShapeError};
+
+#[cfg(feature = "napi6")]
+use crate::bindgen_runtime::{
+  BigInt64Array, BigInt64ArraySlice, BigUint64Array, BigUint64ArraySlice,
+};
+use crate::{
+  bindgen_runtime::{
+    Float32Array, Float32ArraySlice, Float64Array, Float64ArraySlice, Int16Array, Int16ArraySlice,
+    Int32Array, Int32ArraySlice, Int8Array, Int8ArraySlice, Object, ToNapiValue, TypeName,
+    Uint16Array, Uint16ArraySlice, Uint32Array, Uint32ArraySlice, Uint8Array, Uint8ArraySlice,
+  },
+  sys, Env, Error, Result, Status, ValueType,
+};
+
+fn shape_error(type_name: &str, len: usize, err: ShapeError) -> Error {
+  Error::new(
+    Status::InvalidArg,
+    format!(
+      "Can't view the {} of {} elements in the shape: {}",
+      type_name, len, err
+    ),
+  )
+}
+
+macro_rules! impl_ndarray {
+  ($name:ident, $slice_type:ident, $rust_type:ident) => {
+    impl $name {
+      /// View the elements as a 2D array of `shape` in the row-major order.
+      pub fn view_as_array2(
+        &self,
+        shape: (usize, usize),
+      ) -> Result<ndarray::ArrayView2<'_, $rust_type>> {
+        self.view_as_array(shape)
+      }
+
+      /// View the elements as a mutable 2D array of `shape` in the row-major order.
+      ///
+      /// # Safety
+      ///
+      /// The JavaScript side may modify the underlying buffer without synchronization, see [`Self::as_mut`].
+      pub unsafe fn view_as_array2_mut(
+        &mut self,
+        shape: (usize, usize),
+      ) -> Result<ndarray::ArrayViewMut2<'_, $rust_type>> {
+        unsafe { self.view_as_array_mut(shape) }
+      }
+
+      /// View the elements as an array of `shape` in the row-major order, the number of the elements must match the shape.
+      pub fn view_as_array<Sh: IntoDimension>(
+        &self,
+        shape: Sh,
+      ) -> Result<ArrayView<'_, $rust_type, Sh::Dim>> {
+        ArrayView::from_shape(shape, &self[..])
+          .map_err(|err| shape_error(stringify!($name), self.len(), err))
+      }
+
+      /// View the elements as a mutable array of `shape` in the row-major order, the number of the elements must match the shape.
+      ///
+      /// # Safety
+      ///
+      /// The JavaScript side may modify the underlying buffer without synchronization, see [`Self::as_mut`].
+      pub unsafe fn view_as_array_mut<Sh: IntoDimension>(
+        &mut self,
+        shape: Sh,
+      ) -> Result<ArrayViewMut<'_, $rust_type, Sh::Dim>> {
+        let len = self.len();
+        ArrayViewMut::from_shape(shape, unsafe { self.as_mut() })
+          .map_err(|err| shape_error(stringify!($name), len, err))
+      }
+    }
+
+    impl $slice_type<'_> {
+      /// View the elements as a 2D array of `shape` in the row-major order.
+      pub fn view_as_array2(
+        &self,
+        shape: (usize, usize),
+      ) -> Result<ndarray::ArrayView2<'_, $rust_type>> {
+        self.view_as_array(shape)
+      }
+
+      /// View the elements as a mutable 2D array of `shape` in the row-major order.
+      pub fn view_as_array2_mut(
+        &mut self,
+        shape: (usize, usize),
+      ) -> Result<ndarray::ArrayViewMut2<'_, $rust_type>> {
+        self.view_as_array_mut(shape)
+      }
+
+      /// View the elements as an array of `shape` in the row-major order, the number of the elements must match the shape.
+      pub fn view_as_array<Sh: IntoDimension>(
+        &self,
+        shape: Sh,
+      ) -> Result<ArrayView<'_, $rust_type, Sh::Dim>> {
+        ArrayView::from_shape(shape, &self[..])
+          .map_err(|err| shape_error(stringify!($slice_type), self.len(), err))
+      }
+
+      /// View the elements as a mutable array of `shape` in the row-major order, the number of the elements must match the shape.
+      pub fn view_as_array_mut<Sh: IntoDimension>(
+        &mut self,
+        shape: Sh,
+      ) -> Result<ArrayViewMut<'_, $rust_type, Sh::Dim>> {
+        let len = self.len();
+        ArrayViewMut::from_shape(shape, &mut self[..])
+          .map_err(|err| shape_error(stringify!($slice_type), len, err))
+      }
+    }
+
+    impl<D: Dimension> TypeName for Array<$rust_type, D> {
+      fn type_name() -> &'static str {
+        "ndarray::Array"
+      }
+
+      fn value_type() -> ValueType {
+        ValueType::Object
+      }
+    }
+
+    /// Converted to `{ data, shape }`, `data` is a TypedArray of the elements in the row-major order.
+    impl<D: Dimension> ToNapiValue for Array<$rust_type, D> {
+      unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        // numbers rather than the BigInts of `usize`
+        let shape = val
+          .shape()
+          .iter()
+          .map(|&dim| dim as f64)
+          .collect::<Vec<_>>();
+        let data = if val.is_standard_layout() {
+          let len = val.len();
+          let (data, offset) = val.into_raw_vec_and_offset();
+          let offset = offset.unwrap_or(0);
+          if offset == 0 && data.len() == len {
+            data
+          } else {
+            // the array is a part of its allocation
+            data[offset..offset + len].to_vec()
+          }
+        } else {
+          val.iter().copied().collect()
+        };
+        let env = Env::from_raw(env);
+        let mut obj = Object::new(&env)?;
+        obj.set("data", $name::new(data))?;
+        obj.set("shape", shape)?;
+        unsafe { Object::to_napi_value(env.raw(), obj) }
+      }
+    }
+  };
+}
+
+impl_ndarray!(Int8Array, Int8ArraySlice, i8);
+impl_ndarray!(Uint8Array, Uint8ArraySlice, u8);
+impl_ndarray!(Int16Array, Int16ArraySlice, i16);
+impl_ndarray!(Uint16Array, Uint16ArraySlice, u16);
+impl_ndarray!(Int32Array, Int32ArraySlice, i32);
+impl_ndarray!(Uint32Array, Uint32ArraySlice, u32);
+impl_ndarray!(Float32Array, Float32ArraySlice, f32);
+impl_ndarray!(Float64Array, Float64ArraySlice, f64);
+#[cfg(feature = "napi6")]
+impl_ndarray!(BigInt64Array, BigInt64ArraySlice, i64);
+#[cfg(feature = "napi6")]
+impl_ndarray!(BigUint64Array, BigUint64ArraySlice, u64);
@@ -65,10 +65,11 @@ pub trait JsObjectValue<'env>: JsValue<'env> {
   where
     T: ToNapiValue,
   {
-    let key = CString::new(name)?;
+    let mut key = ptr::null_mut();
     let env = self.value().env;
+    check_status!(unsafe { create_property_key(env, name, &mut key) })?;
     check_status!(unsafe {
-      sys::napi_set_named_property(env, self.raw(), key.as_ptr(), T::to_napi_value(env, value)?)
+      sys::napi_set_property(env, self.raw(), key, T::to_napi_value(env, value)?)
     })
   }
 
@@ -97,13 +98,12 @@ pub trait JsObjectValue<'env>: JsValue<'env> {
   where
     T: FromNapiValue + ValidateNapiValue,
   {
-    let key = CString::new(name)?;
+    let mut key = ptr::null_mut();
     let mut raw_value = ptr::null_mut();
     let env = self.value().env;
+    check_status!(unsafe { create_property_key(env, name, &mut key) })?;
     check_status!(
-      unsafe {
-        sys::napi_get_named_property(env, self.value().value, key.as_ptr(), &mut raw_value)
-      },
+      unsafe { sys::napi_get_property(env, self.value().value, key, &mut raw_value) },
       "get_named_property error"
     )?;
     unsafe { <T as ValidateNapiValue>::validate(env, raw_value) }.map_err(|mut err| {
@@ -113,17 +113,56 @@ pub trait JsObjectValue<'env>: JsValue<'env> {
     unsafe { <T as FromNapiValue>::from_napi_value(env, raw_value) }
   }
 
+  /// Set the property keyed by the well-known `symbol`, like `obj[Symbol.toStringTag] = value` in JavaScript.
+  ///
+  /// Use [`JsObjectValue::set_property`] with a [`crate::JsSymbol`] for the other symbols.
+  fn set_symbol_property<T>(&mut self, symbol: WellKnownSymbol, value: T) -> Result<()>
+  where
+    T: ToNapiValue,
+  {
+    let env = self.value().env;
+    let key = symbol.get(env)?;
+    check_status!(
+      unsafe { sys::napi_set_property(env, self.raw(), key, T::to_napi_value(env, value)?) },
+      "Failed to set property Symbol.{}",
+      symbol.name()
+    )
+  }
+
+  /// Get the property keyed by the well-known `symbol`, like `obj[Symbol.iterator]` in JavaScript.
+  fn get_symbol_property<T>(&self, symbol: WellKnownSymbol) -> Result<T>
+  where
+    T: FromNapiValue + ValidateNapiValue,
+  {
+    let env = self.value().env;
+    let key = symbol.get(env)?;
+    let mut raw_value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_property(env, self.value().value, key, &mut raw_value) },
+      "Failed to get property Symbol.{}",
+      symbol.name()
+    )?;
+    unsafe { <T as ValidateNapiValue>::validate(env, raw_value) }.map_err(|mut err| {
+      err.reason = format!(
+        "Object property 'Symbol.{}' type mismatch. {}",
+        symbol.name(),
+        err.reason
+      );
+      err
+    })?;
+    unsafe { <T as FromNapiValue>::from_napi_value(env, raw_value) }
+  }
+
   fn get_named_property_unchecked<T>(&self, name: &str) -> Result<T>
   where
     T: FromNapiValue,
   {
-    let key = CString::new(name)?;
+    let mut key = ptr::null_mut();
     let mut raw_value = ptr::null_mut();
     let env = self.value().env;
+    check_status!(unsafe { create_property_key(env, name, &mut key) })?;
     check_status!(
-      unsafe {
-        sys::napi_get_named_property(env, self.value().value, key.as_ptr(), &mut raw_value)
-      },
+      unsafe { sys::napi_get_property(env, self.value().value, key, &mut raw_value) },
       "get_named_property_unchecked error"
     )?;
     unsafe { <T as FromNapiValue>::from_napi_value(env, raw_value) }
@@ -156,9 +195,7 @@ pub trait JsObjectValue<'env>: JsValue<'env> {
     let mut result = false;
     let mut js_key = ptr::null_mut();
     let env = self.value().env;
-    check_status!(unsafe {
-      sys::napi_create_string_utf8(env, name.as_ptr().cast(), name.len() as isize, &mut js_key)
-    })?;
+    check_status!(unsafe { create_property_key(env, name, &mut js_key) })?;
     check_status!(unsafe {
       sys::napi_delete_property(env, self.value().value, js_key, &mut result)
     })?;
@@ -169,9 +206,7 @@ pub trait JsObjectValue<'env>: JsValue<'env> {
     let mut result = false;
     let mut js_key = ptr::null_mut();
     let env = self.value().env;
-    check_status!(unsafe {
-      sys::napi_create_string_utf8(env, key.as_ptr().cast(), key.len() as isize, &mut js_key)
-    })?;
+    check_status!(unsafe { create_property_key(env, key, &mut js_key) })?;
     check_status!(unsafe {
       sys::napi_has_own_property(env, self.value().value, js_key, &mut result)
     })?;
@@ -194,9 +229,7 @@ pub trait JsObjectValue<'env>: JsValue<'env> {
     let mut js_key = ptr::null_mut();
     let mut result = false;
     let env = self.value().env;
-    check_status!(unsafe {
-      sys::napi_create_string_utf8(env, name.as_ptr().cast(), name.len() as isize, &mut js_key)
-    })?;
+    check_status!(unsafe { create_property_key(env, name, &mut js_key) })?;
     check_status!(unsafe { sys::napi_has_property(env, self.value().value, js_key, &mut result) })?;
     Ok(result)
   }
@@ -573,12 +606,7 @@ impl Object<'_> {
     unsafe {
       let mut property_key = std::ptr::null_mut();
       check_status!(
-        sys::napi_create_string_utf8(
-          self.0.env,
-          field.as_ptr().cast(),
-          field.len() as isize,
-          &mut property_key,
-        ),
+        create_property_key(self.0.env, field, &mut property_key),
         "Failed to create property key with `{field}`"
       )?;
 
@@ -599,21 +627,42 @@ impl Object<'_> {
     }
   }
 
-  pub fn set<K: AsRef<str>, V: ToNapiValue>(&mut self, field: K, val: V) -> Result<()> {
-    unsafe { self.set_inner(field.as_ref(), V::to_napi_value(self.0.env, val)?) }
+  /// Get the property of an interned `key`, like [`Object::get`] without creating the key again.
+  pub fn get_key<V: FromNapiValue>(&self, key: &PropertyKey) -> Result<Option<V>> {
+    let mut ret = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_property(self.0.env, self.0.value, key.raw(), &mut ret) },
+      "Failed to get property with the interned key"
+    )?;
+    if type_of!(self.0.env, ret)? == ValueType::Undefined {
+      return Ok(None);
+    }
+    unsafe { V::from_napi_value(self.0.env, ret) }.map(Some)
   }
 
-  unsafe fn set_inner(&mut self, field: &str, napi_val: sys::napi_value) -> Result<()> {
-    let mut property_key = std::ptr::null_mut();
+  /// Set the property of an interned `key`, like [`Object::set`] without creating the key again.
+  pub fn set_key<V: ToNapiValue>(&mut self, key: &PropertyKey, val: V) -> Result<()> {
     check_status!(
       unsafe {
-        sys::napi_create_string_utf8(
+        sys::napi_set_property(
           self.0.env,
-          field.as_ptr().cast(),
-          field.len() as isize,
-          &mut property_key,
+          self.0.value,
+          key.raw(),
+          V::to_napi_value(self.0.env, val)?,
         )
       },
+      "Failed to set property with the interned key"
+    )
+  }
+
+  pub fn set<K: AsRef<str>, V: ToNapiValue>(&mut self, field: K, val: V) -> Result<()> {
+    unsafe { self.set_inner(field.as_ref(), V::to_napi_value(self.0.env, val)?) }
+  }
+
+  unsafe fn set_inner(&mut self, field: &str, napi_val: sys::napi_value) -> Result<()> {
+    let mut property_key = std::ptr::null_mut();
+    check_status!(
+      unsafe { create_property_key(self.0.env, field, &mut property_key) },
       "Failed to create property key with `{field}`"
     )?;
 
@@ -798,3 +847,22 @@ pub(crate) unsafe extern "C" fn finalize_closures(
     drop(unsafe { Box::from_raw(closure) });
   }
 }
+
+/// Create a string used as a property key, with Node-API 10 the key is internalized by the engine,
+/// which makes repeated lookups of the same key cheaper.
+pub(crate) unsafe fn create_property_key(
+  env: sys::napi_env,
+  key: &str,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  #[cfg(feature = "napi10")]
+  {
+    unsafe {
+      sys::node_api_create_property_key_utf8(env, key.as_ptr().cast(), key.len() as isize, result)
+    }
+  }
+  #[cfg(not(feature = "napi10"))]
+  {
+    unsafe { sys::napi_create_string_utf8(env, key.as_ptr().cast(), key.len() as isize, result) }
+  }
+}
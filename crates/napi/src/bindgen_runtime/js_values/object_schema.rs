@@ -0,0 +1,180 @@
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::ptr;
+
+use crate::{
+  bindgen_runtime::{FromNapiValue, Object},
+  check_status, sys, Env, Error, JsValue, Result, Status,
+};
+
+thread_local! {
+  // a nested conversion takes an empty vector instead of the borrowed one
+  static FIELD_VALUES: Cell<Vec<sys::napi_value>> = const { Cell::new(Vec::new()) };
+  static FIELD_DESCRIPTORS: Cell<Vec<sys::napi_property_descriptor>> = const { Cell::new(Vec::new()) };
+}
+
+#[doc(hidden)]
+pub struct SchemaField {
+  pub key: &'static CStr,
+  pub name: &'static str,
+}
+
+/// The field list and the conversions of a `#[napi(object)]` struct, generated by the macro.
+///
+/// It's implemented for the `#[napi(object)]` structs which can be converted in both directions,
+/// use it through [`Schema`].
+pub trait ObjectSchema: Sized {
+  #[doc(hidden)]
+  const FIELDS: &'static [SchemaField];
+
+  #[doc(hidden)]
+  /// `values` are the values of the `FIELDS` in order, `undefined` if the property is missing.
+  unsafe fn read_fields(env: sys::napi_env, values: &[sys::napi_value]) -> Result<Self>;
+
+  #[doc(hidden)]
+  /// Fill `values` with the values of the `FIELDS` in order, the skipped fields are left null.
+  unsafe fn write_fields(
+    env: sys::napi_env,
+    val: Self,
+    values: &mut [sys::napi_value],
+  ) -> Result<()>;
+}
+
+/// A conversion plan of a `#[napi(object)]` struct, for converting objects of the same shape repeatedly,
+/// like the messages of an RPC protocol.
+///
+/// The field list, the property keys and the field conversions are resolved when the plan is compiled,
+/// [`Schema::read`] and [`Schema::write`] only look up and define the properties,
+/// and all the properties of a written object are defined by a single `napi_define_properties` call.
+///
+/// ```rust
+/// static MESSAGE_SCHEMA: LazyLock<Schema<Message>> = LazyLock::new(Schema::compile);
+///
+/// let message = MESSAGE_SCHEMA.read(&obj)?;
+/// let obj = MESSAGE_SCHEMA.write(env, message)?;
+/// ```
+pub struct Schema<T: ObjectSchema> {
+  fields: &'static [SchemaField],
+  _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ObjectSchema> Clone for Schema<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ObjectSchema> Copy for Schema<T> {}
+
+impl<T: ObjectSchema> Default for Schema<T> {
+  fn default() -> Self {
+    Self::compile()
+  }
+}
+
+impl<T: ObjectSchema> Schema<T> {
+  pub fn compile() -> Self {
+    Self {
+      fields: T::FIELDS,
+      _marker: PhantomData,
+    }
+  }
+
+  /// The JavaScript names of the fields.
+  pub fn field_names(&self) -> impl Iterator<Item = &'static str> {
+    self.fields.iter().map(|field| field.name)
+  }
+
+  /// Convert `obj` into `T`.
+  pub fn read<'env, O: JsValue<'env>>(&self, obj: &O) -> Result<T> {
+    let obj = obj.value();
+    let mut values = FIELD_VALUES.take();
+    values.clear();
+    let result = self.fields.iter().try_for_each(|field| {
+      let mut value = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_named_property(obj.env, obj.value, field.key.as_ptr(), &mut value) },
+        "Failed to get property `{}`",
+        field.name
+      )?;
+      values.push(value);
+      Ok(())
+    });
+    let result = result.and_then(|_| unsafe { T::read_fields(obj.env, &values) });
+    FIELD_VALUES.set(values);
+    result
+  }
+
+  /// Convert `val` into a JavaScript object.
+  pub fn write<'env>(&self, env: &'env Env, val: T) -> Result<Object<'env>> {
+    let mut values = FIELD_VALUES.take();
+    values.clear();
+    values.resize(self.fields.len(), ptr::null_mut());
+    let mut descriptors = FIELD_DESCRIPTORS.take();
+    descriptors.clear();
+    let result = unsafe { T::write_fields(env.0, val, &mut values) }.and_then(|_| {
+      descriptors.extend(
+        self
+          .fields
+          .iter()
+          .zip(values.iter())
+          .filter(|(_, value)| !value.is_null())
+          .map(|(field, value)| sys::napi_property_descriptor {
+            utf8name: field.key.as_ptr(),
+            name: ptr::null_mut(),
+            method: None,
+            getter: None,
+            setter: None,
+            value: *value,
+            attributes: sys::PropertyAttributes::writable
+              | sys::PropertyAttributes::enumerable
+              | sys::PropertyAttributes::configurable,
+            data: ptr::null_mut(),
+          }),
+      );
+      let mut obj = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_object(env.0, &mut obj) },
+        "Failed to create object"
+      )?;
+      if !descriptors.is_empty() {
+        check_status!(
+          unsafe {
+            sys::napi_define_properties(env.0, obj, descriptors.len(), descriptors.as_ptr())
+          },
+          "Failed to define object properties"
+        )?;
+      }
+      unsafe { Object::from_napi_value(env.0, obj) }
+    });
+    FIELD_VALUES.set(values);
+    FIELD_DESCRIPTORS.set(descriptors);
+    result
+  }
+}
+
+/// Convert a field read by [`Schema::read`], a missing required field is an error.
+#[doc(hidden)]
+pub unsafe fn read_schema_field<V: FromNapiValue>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  required: bool,
+  type_name: &str,
+  field: &str,
+) -> Result<V> {
+  if required {
+    let mut value_type = 0;
+    check_status!(unsafe { sys::napi_typeof(env, value, &mut value_type) })?;
+    if value_type == sys::ValueType::napi_undefined {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Missing field `{}`", field),
+      ));
+    }
+  }
+  unsafe { V::from_napi_value(env, value) }.map_err(|mut err| {
+    err.reason = format!("{} on {}.{}", err.reason, type_name, field);
+    err
+  })
+}
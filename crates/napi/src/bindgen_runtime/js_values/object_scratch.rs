@@ -0,0 +1,97 @@
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::ptr;
+
+use crate::{bindgen_runtime::ToNapiValue, check_status, sys, Result};
+
+/// Scratch vector of property descriptors used by `#[napi(object, cache_keys)]`.
+///
+/// Every object type has its own thread-local `ObjectScratch`, so converting the same type repeatedly
+/// reuses the allocation and defines all the properties with a single `napi_define_properties` call.
+#[doc(hidden)]
+pub struct ObjectScratch(Cell<Vec<sys::napi_property_descriptor>>);
+
+impl Default for ObjectScratch {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ObjectScratch {
+  pub const fn new() -> Self {
+    Self(Cell::new(Vec::new()))
+  }
+
+  /// Create an object with the properties pushed by `f`.
+  pub fn create_object<F>(&self, env: sys::napi_env, f: F) -> Result<sys::napi_value>
+  where
+    F: FnOnce(&mut ObjectProperties) -> Result<()>,
+  {
+    // a nested conversion of the same type takes an empty vector instead of the borrowed one
+    let mut properties = ObjectProperties {
+      env,
+      descriptors: self.0.take(),
+    };
+    let result = f(&mut properties).and_then(|_| properties.define());
+    properties.descriptors.clear();
+    self.0.set(properties.descriptors);
+    result
+  }
+}
+
+#[doc(hidden)]
+pub struct ObjectProperties {
+  env: sys::napi_env,
+  descriptors: Vec<sys::napi_property_descriptor>,
+}
+
+impl ObjectProperties {
+  /// `key` is interned by the engine once the object is created.
+  pub fn push<V: ToNapiValue>(&mut self, key: &'static CStr, value: V) -> Result<()> {
+    let value = unsafe { V::to_napi_value(self.env, value)? };
+    self.descriptors.push(sys::napi_property_descriptor {
+      utf8name: key.as_ptr(),
+      name: ptr::null_mut(),
+      method: None,
+      getter: None,
+      setter: None,
+      value,
+      attributes: sys::PropertyAttributes::writable
+        | sys::PropertyAttributes::enumerable
+        | sys::PropertyAttributes::configurable,
+      data: ptr::null_mut(),
+    });
+    Ok(())
+  }
+
+  fn define(&self) -> Result<sys::napi_value> {
+    let mut obj = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(self.env, &mut obj) },
+      "Failed to create object"
+    )?;
+    if !self.descriptors.is_empty() {
+      check_status!(
+        unsafe {
+          sys::napi_define_properties(
+            self.env,
+            obj,
+            self.descriptors.len(),
+            self.descriptors.as_ptr(),
+          )
+        },
+        "Failed to define object properties"
+      )?;
+    }
+    Ok(obj)
+  }
+}
+
+/// Build the property key of a `#[napi(object, cache_keys)]` field at compile time.
+#[doc(hidden)]
+pub const fn object_property_key(bytes_with_nul: &'static [u8]) -> &'static CStr {
+  match CStr::from_bytes_with_nul(bytes_with_nul) {
+    Ok(key) => key,
+    Err(_) => panic!("Object property key can't contain nul bytes"),
+  }
+}
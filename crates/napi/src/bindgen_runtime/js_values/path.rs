@@ -0,0 +1,201 @@
+//! `OsString` and `PathBuf` are converted from and to the JavaScript strings without going through `String`.
+//!
+//! The JavaScript strings are UTF-16 which may contain unpaired surrogates, just like the Windows paths, so they are
+//! converted losslessly on Windows. On Unix, the unpaired surrogates are encoded in WTF-8, and the WTF-8 bytes are
+//! decoded back into them, so any JavaScript string round-trips. The Unix paths which are not WTF-8 can't be
+//! represented in a JavaScript string, their invalid bytes are replaced by `U+FFFD` like `fs.readdir` of Node.js does,
+//! convert them into a `Buffer` to keep the bytes.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use crate::{bindgen_prelude::*, check_status, check_status_and_type, sys};
+
+impl TypeName for OsString {
+  fn type_name() -> &'static str {
+    "OsString"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ValidateNapiValue for OsString {}
+
+impl FromNapiValue for OsString {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut len = 0;
+
+    check_status_and_type!(
+      unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
+      env,
+      napi_val,
+      "Failed to convert JavaScript value `{}` into rust type `OsString`"
+    )?;
+
+    // end char len in C
+    len += 1;
+    let mut units = vec![0; len];
+    let mut written_char_count = 0;
+
+    check_status_and_type!(
+      unsafe {
+        sys::napi_get_value_string_utf16(
+          env,
+          napi_val,
+          units.as_mut_ptr(),
+          len,
+          &mut written_char_count,
+        )
+      },
+      env,
+      napi_val,
+      "Failed to convert JavaScript value `{}` into rust type `OsString`"
+    )?;
+
+    units.truncate(written_char_count);
+
+    Ok(utf16_to_os_string(&units))
+  }
+}
+
+impl ToNapiValue for &OsStr {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    // most of the paths are UTF-8, which skips the transcoding
+    if let Some(s) = val.to_str() {
+      return unsafe { ToNapiValue::to_napi_value(env, s) };
+    }
+
+    let units = os_str_to_utf16(val);
+    let mut ptr = ptr::null_mut();
+
+    check_status!(
+      unsafe { sys::napi_create_string_utf16(env, units.as_ptr(), units.len() as isize, &mut ptr) },
+      "Failed to convert rust `OsStr` into napi `string`"
+    )?;
+
+    Ok(ptr)
+  }
+}
+
+impl ToNapiValue for &OsString {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_os_str()) }
+  }
+}
+
+impl ToNapiValue for OsString {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_os_str()) }
+  }
+}
+
+impl TypeName for PathBuf {
+  fn type_name() -> &'static str {
+    "PathBuf"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ValidateNapiValue for PathBuf {}
+
+impl FromNapiValue for PathBuf {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(PathBuf::from(unsafe {
+      OsString::from_napi_value(env, napi_val)?
+    }))
+  }
+}
+
+impl ToNapiValue for &Path {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_os_str()) }
+  }
+}
+
+impl ToNapiValue for &PathBuf {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_os_str()) }
+  }
+}
+
+impl ToNapiValue for PathBuf {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_os_str()) }
+  }
+}
+
+#[cfg(windows)]
+fn utf16_to_os_string(units: &[u16]) -> OsString {
+  std::os::windows::ffi::OsStringExt::from_wide(units)
+}
+
+#[cfg(windows)]
+fn os_str_to_utf16(s: &OsStr) -> Vec<u16> {
+  std::os::windows::ffi::OsStrExt::encode_wide(s).collect()
+}
+
+#[cfg(unix)]
+fn utf16_to_os_string(units: &[u16]) -> OsString {
+  let mut bytes = Vec::with_capacity(units.len());
+  for c in char::decode_utf16(units.iter().copied()) {
+    match c {
+      Ok(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+      // WTF-8 encodes the unpaired surrogate like a 3 bytes UTF-8 character
+      Err(err) => {
+        let unit = err.unpaired_surrogate();
+        bytes.extend_from_slice(&[
+          0xe0 | (unit >> 12) as u8,
+          0x80 | ((unit >> 6) & 0x3f) as u8,
+          0x80 | (unit & 0x3f) as u8,
+        ]);
+      }
+    }
+  }
+  std::os::unix::ffi::OsStringExt::from_vec(bytes)
+}
+
+#[cfg(unix)]
+fn os_str_to_utf16(s: &OsStr) -> Vec<u16> {
+  let mut bytes = std::os::unix::ffi::OsStrExt::as_bytes(s);
+  let mut units = Vec::with_capacity(bytes.len());
+  loop {
+    match std::str::from_utf8(bytes) {
+      Ok(valid) => {
+        units.extend(valid.encode_utf16());
+        return units;
+      }
+      Err(err) => {
+        let (valid, invalid) = bytes.split_at(err.valid_up_to());
+        // SAFETY: validated by `from_utf8`
+        units.extend(unsafe { std::str::from_utf8_unchecked(valid) }.encode_utf16());
+        match *invalid {
+          // the surrogates encoded by WTF-8, `0xED 0xA0..=0xBF` starts U+D800..=U+DFFF
+          [0xed, second @ 0xa0..=0xbf, third @ 0x80..=0xbf, ..] => {
+            units.push(0xd000 | ((second as u16 & 0x3f) << 6) | (third as u16 & 0x3f));
+            bytes = &invalid[3..];
+          }
+          _ => {
+            units.push(char::REPLACEMENT_CHARACTER as u16);
+            bytes = &invalid[err.error_len().unwrap_or(invalid.len())..];
+          }
+        }
+      }
+    }
+  }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn utf16_to_os_string(units: &[u16]) -> OsString {
+  String::from_utf16_lossy(units).into()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_str_to_utf16(s: &OsStr) -> Vec<u16> {
+  s.to_string_lossy().encode_utf16().collect()
+}
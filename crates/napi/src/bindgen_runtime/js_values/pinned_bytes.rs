@@ -0,0 +1,217 @@
+use std::ptr;
+use std::slice;
+
+use crate::{
+  bindgen_runtime::{FromNapiValue, TypeName, TypedArrayType, ValidateNapiValue},
+  check_status, sys, Env, Error, Result, Status, ValueType,
+};
+
+/// The bytes of an incoming `Buffer`, `TypedArray` or `DataView`, which can be carried into async tasks.
+///
+/// `PinnedBytes` holds a reference to the underlying `ArrayBuffer` rather than the view,
+/// so the backing store stays alive until the `PinnedBytes` is dropped, no matter what happens to the view in JavaScript.
+/// The bytes are not copied.
+///
+/// Node-API can't mark an `ArrayBuffer` as non-detachable, if JavaScript transfers the `ArrayBuffer` while it's pinned,
+/// [`PinnedBytes::resolve`] returns an error.
+/// Call it on the JavaScript thread before reading the bytes you received earlier, for example after an `await` in an `async fn`
+/// which runs on the JavaScript thread.
+/// Off the JavaScript thread the bytes can only be read by the `unsafe` [`PinnedBytes::as_slice`].
+pub struct PinnedBytes {
+  data: *const u8,
+  len: usize,
+  byte_offset: usize,
+  array_buffer_ref: sys::napi_ref,
+  env: sys::napi_env,
+}
+
+/// SAFETY: the backing store is kept alive by the reference, and the reference is only released on the JavaScript thread.
+unsafe impl Send for PinnedBytes {}
+unsafe impl Sync for PinnedBytes {}
+
+impl PinnedBytes {
+  /// Offset of the bytes in the underlying `ArrayBuffer`.
+  pub fn byte_offset(&self) -> usize {
+    self.byte_offset
+  }
+
+  /// Look up the `ArrayBuffer` again and return the current bytes.
+  ///
+  /// Returns an error if the `ArrayBuffer` has been detached or shrunk since the `PinnedBytes` was created.
+  pub fn resolve(&self, env: &Env) -> Result<&[u8]> {
+    let mut array_buffer = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env.0, self.array_buffer_ref, &mut array_buffer) },
+      "Get ArrayBuffer from PinnedBytes reference failed"
+    )?;
+    let mut data = ptr::null_mut();
+    let mut byte_length = 0;
+    check_status!(
+      unsafe { sys::napi_get_arraybuffer_info(env.0, array_buffer, &mut data, &mut byte_length) },
+      "Get ArrayBuffer info of PinnedBytes failed"
+    )?;
+    if (data.is_null() && self.len != 0) || self.byte_offset + self.len > byte_length {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The ArrayBuffer of PinnedBytes has been detached",
+      ));
+    }
+    if self.len == 0 {
+      return Ok(&[]);
+    }
+    Ok(unsafe { slice::from_raw_parts(data.cast::<u8>().add(self.byte_offset), self.len) })
+  }
+
+  /// The bytes as they were when the `PinnedBytes` was created, without looking up the `ArrayBuffer` again.
+  ///
+  /// It can be called from any thread, use [`PinnedBytes::resolve`] instead on the JavaScript thread.
+  ///
+  /// # Safety
+  ///
+  /// The `ArrayBuffer` must not be detached, transferred or shrunk by JavaScript while the returned slice is alive,
+  /// and JavaScript must not write to the bytes meanwhile.
+  pub unsafe fn as_slice(&self) -> &[u8] {
+    if self.len == 0 {
+      return &[];
+    }
+    unsafe { slice::from_raw_parts(self.data, self.len) }
+  }
+}
+
+impl Drop for PinnedBytes {
+  fn drop(&mut self) {
+    #[cfg(all(feature = "napi4", not(feature = "noop")))]
+    {
+      use std::sync::atomic::Ordering;
+
+      use crate::bindgen_runtime::{
+        CUSTOM_GC_TSFN, CUSTOM_GC_TSFN_DESTROYED, THREADS_CAN_ACCESS_ENV,
+      };
+
+      if !THREADS_CAN_ACCESS_ENV.with(|cell| cell.get()) {
+        // release the reference on the JavaScript thread
+        if !CUSTOM_GC_TSFN_DESTROYED.load(Ordering::SeqCst) {
+          unsafe {
+            sys::napi_call_threadsafe_function(
+              CUSTOM_GC_TSFN.load(Ordering::SeqCst),
+              self.array_buffer_ref.cast(),
+              1,
+            )
+          };
+        }
+        return;
+      }
+    }
+    unsafe {
+      sys::napi_reference_unref(self.env, self.array_buffer_ref, ptr::null_mut());
+      sys::napi_delete_reference(self.env, self.array_buffer_ref);
+    }
+  }
+}
+
+impl TypeName for PinnedBytes {
+  fn type_name() -> &'static str {
+    "PinnedBytes"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for PinnedBytes {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let mut is_typed_array = false;
+    check_status!(
+      unsafe { sys::napi_is_typedarray(env, napi_val, &mut is_typed_array) },
+      "Failed to check if value is typed array"
+    )?;
+    let mut is_data_view = false;
+    check_status!(
+      unsafe { sys::napi_is_dataview(env, napi_val, &mut is_data_view) },
+      "Failed to check if value is DataView"
+    )?;
+    if !is_typed_array && !is_data_view {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Expected a Buffer, TypedArray or DataView value".to_owned(),
+      ));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
+impl FromNapiValue for PinnedBytes {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut is_typed_array = false;
+    check_status!(
+      unsafe { sys::napi_is_typedarray(env, napi_val, &mut is_typed_array) },
+      "Failed to check if value is typed array"
+    )?;
+    let mut data = ptr::null_mut();
+    let mut array_buffer = ptr::null_mut();
+    let mut byte_offset = 0;
+    let len = if is_typed_array {
+      let mut typed_array_type = 0;
+      let mut length = 0;
+      check_status!(
+        unsafe {
+          sys::napi_get_typedarray_info(
+            env,
+            napi_val,
+            &mut typed_array_type,
+            &mut length,
+            &mut data,
+            &mut array_buffer,
+            &mut byte_offset,
+          )
+        },
+        "Get TypedArray info failed"
+      )?;
+      length * element_size(TypedArrayType::from(typed_array_type))?
+    } else {
+      let mut byte_length = 0;
+      check_status!(
+        unsafe {
+          sys::napi_get_dataview_info(
+            env,
+            napi_val,
+            &mut byte_length,
+            &mut data,
+            &mut array_buffer,
+            &mut byte_offset,
+          )
+        },
+        "Expected a Buffer, TypedArray or DataView value"
+      )?;
+      byte_length
+    };
+    let mut array_buffer_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, array_buffer, 1, &mut array_buffer_ref) },
+      "Failed to create ArrayBuffer reference for PinnedBytes"
+    )?;
+    Ok(Self {
+      data: data.cast(),
+      len,
+      byte_offset,
+      array_buffer_ref,
+      env,
+    })
+  }
+}
+
+fn element_size(typed_array_type: TypedArrayType) -> Result<usize> {
+  match typed_array_type {
+    TypedArrayType::Int8 | TypedArrayType::Uint8 | TypedArrayType::Uint8Clamped => Ok(1),
+    TypedArrayType::Int16 | TypedArrayType::Uint16 => Ok(2),
+    TypedArrayType::Int32 | TypedArrayType::Uint32 | TypedArrayType::Float32 => Ok(4),
+    TypedArrayType::Float64 => Ok(8),
+    #[cfg(feature = "napi6")]
+    TypedArrayType::BigInt64 | TypedArrayType::BigUint64 => Ok(8),
+    TypedArrayType::Unknown => Err(Error::new(
+      Status::InvalidArg,
+      "Unknown TypedArray type for PinnedBytes",
+    )),
+  }
+}
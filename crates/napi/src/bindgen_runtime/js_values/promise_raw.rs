@@ -6,7 +6,7 @@ use std::ptr;
 use crate::bindgen_runtime::Promise;
 use crate::{
   bindgen_prelude::{
-    FromNapiValue, JsObjectValue, Result, ToNapiValue, TypeName, ValidateNapiValue,
+    FromNapiValue, JsObjectValue, Result, ToNapiValue, TypeName, Unknown, ValidateNapiValue,
   },
   check_status, sys, Env, Error, JsValue, Value, ValueType,
 };
@@ -197,8 +197,103 @@ impl<'env, T: FromNapiValue> PromiseRaw<'env, T> {
     })
   }
 
+  /// Promise.then method with both the fulfilled and the rejected callbacks
+  ///
+  /// Only one of them is called, with the resolved value converted to `T` or the rejected reason converted to `E`.
+  pub fn then_or_catch<E, U, OnFulfilled, OnRejected>(
+    &self,
+    on_fulfilled: OnFulfilled,
+    on_rejected: OnRejected,
+  ) -> Result<PromiseRaw<'env, U>>
+  where
+    E: FromNapiValue,
+    U: ToNapiValue,
+    OnFulfilled: 'static + FnOnce(CallbackContext<T>) -> Result<U>,
+    OnRejected: 'static + FnOnce(CallbackContext<E>) -> Result<U>,
+  {
+    let mut then_fn = ptr::null_mut();
+    const THEN: &[u8; 5] = b"then\0";
+    check_status!(unsafe {
+      sys::napi_get_named_property(self.env, self.inner, THEN.as_ptr().cast(), &mut then_fn)
+    })?;
+    // shared by both callbacks and freed after the Promise is GCed
+    let callbacks = Box::into_raw(Box::new(ThenOrCatchCallbacks {
+      on_fulfilled: Some(on_fulfilled),
+      on_rejected: Some(on_rejected),
+    }));
+    let mut fulfilled_callback = ptr::null_mut();
+    let mut rejected_callback = ptr::null_mut();
+    let status = unsafe {
+      sys::napi_create_function(
+        self.env,
+        THEN.as_ptr().cast(),
+        4,
+        Some(raw_promise_then_or_catch_fulfilled::<T, U, OnFulfilled, OnRejected>),
+        callbacks.cast(),
+        &mut fulfilled_callback,
+      )
+    };
+    let status = if status == sys::Status::napi_ok {
+      unsafe {
+        sys::napi_create_function(
+          self.env,
+          THEN.as_ptr().cast(),
+          4,
+          Some(raw_promise_then_or_catch_rejected::<E, U, OnFulfilled, OnRejected>),
+          callbacks.cast(),
+          &mut rejected_callback,
+        )
+      }
+    } else {
+      status
+    };
+    let mut new_promise = ptr::null_mut();
+    let status = if status == sys::Status::napi_ok {
+      unsafe {
+        sys::napi_call_function(
+          self.env,
+          self.inner,
+          then_fn,
+          2,
+          [fulfilled_callback, rejected_callback].as_ptr(),
+          &mut new_promise,
+        )
+      }
+    } else {
+      status
+    };
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Box::from_raw(callbacks) });
+      return Err(Error::new(
+        status.into(),
+        "Call the PromiseRaw::then with fulfilled and rejected callbacks failed",
+      ));
+    }
+
+    // use `napi_wrap` to trigger the finalizer after the Promise is GCed
+    check_status!(
+      unsafe {
+        sys::napi_wrap(
+          self.env,
+          new_promise,
+          callbacks.cast(),
+          Some(then_or_catch_callbacks_finalizer::<OnFulfilled, OnRejected>),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Wrap finalizer for PromiseRaw failed"
+    )?;
+
+    Ok(PromiseRaw::<U> {
+      env: self.env,
+      inner: new_promise,
+      _phantom: &PhantomData,
+    })
+  }
+
   /// Promise.finally method
-  pub fn finally<U, Callback>(&mut self, cb: Callback) -> Result<PromiseRaw<'env, T>>
+  pub fn finally<U, Callback>(&self, cb: Callback) -> Result<PromiseRaw<'env, T>>
   where
     U: ToNapiValue,
     Callback: 'static + FnOnce(Env) -> Result<U>,
@@ -210,7 +305,8 @@ impl<'env, T: FromNapiValue> PromiseRaw<'env, T> {
       sys::napi_get_named_property(self.env, self.inner, FINALLY.as_ptr().cast(), &mut then_fn)
     })?;
     let mut then_callback = ptr::null_mut();
-    let rust_cb = Box::into_raw(Box::new(cb));
+    // freed after the Promise is GCed, the callback is taken out of it once called
+    let rust_cb = Box::into_raw(Box::new(Some(cb)));
     check_status!(
       unsafe {
         sys::napi_create_function(
@@ -239,6 +335,20 @@ impl<'env, T: FromNapiValue> PromiseRaw<'env, T> {
       "Call then callback on PromiseRaw failed"
     )?;
 
+    check_status!(
+      unsafe {
+        sys::napi_wrap(
+          self.env,
+          new_promise,
+          rust_cb.cast(),
+          Some(promise_finally_finalizer::<Callback>),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Wrap finalizer for PromiseRaw failed"
+    )?;
+
     Ok(Self {
       env: self.env,
       inner: new_promise,
@@ -246,6 +356,78 @@ impl<'env, T: FromNapiValue> PromiseRaw<'env, T> {
     })
   }
 
+  /// Like `then`, but the resolved value is converted to `V`, for a Promise whose value type isn't known when it's
+  /// created, like the `PromiseRaw<Unknown>` returned by a JavaScript function.
+  pub fn then_typed<V, U, Callback>(&self, cb: Callback) -> Result<PromiseRaw<'env, U>>
+  where
+    V: 'env + FromNapiValue,
+    U: ToNapiValue,
+    Callback: 'static + FnOnce(CallbackContext<V>) -> Result<U>,
+  {
+    PromiseRaw::<V>::new(self.env, self.inner).then(cb)
+  }
+
+  /// Like `catch`, but the rejection reason is converted to an `Error`, with the reason coerced to a string.
+  ///
+  /// The `Error` keeps a reference to the reason, so returning it rejects the new Promise with the original value.
+  pub fn catch_typed<U, Callback>(&self, cb: Callback) -> Result<PromiseRaw<'env, U>>
+  where
+    U: ToNapiValue,
+    Callback: 'static + FnOnce(CallbackContext<Error>) -> Result<U>,
+  {
+    self.catch(move |ctx: CallbackContext<Unknown<'static>>| {
+      cb(CallbackContext {
+        env: ctx.env,
+        value: Error::from(ctx.value),
+      })
+    })
+  }
+
+  /// `Promise.all`, the returned Promise resolves with the values of `promises` in their order,
+  /// or rejects with the reason of the first of them which rejects.
+  pub fn all(env: &'env Env, promises: Vec<PromiseRaw<'_, T>>) -> Result<PromiseRaw<'env, Vec<T>>> {
+    let mut array = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_array_with_length(env.0, promises.len(), &mut array) },
+      "Create array for PromiseRaw::all failed"
+    )?;
+    for (index, promise) in promises.iter().enumerate() {
+      check_status!(
+        unsafe { sys::napi_set_element(env.0, array, index as u32, promise.inner) },
+        "Set element for PromiseRaw::all failed"
+      )?;
+    }
+    let mut global = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_global(env.0, &mut global) })?;
+    let mut promise_ctor = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_get_named_property(env.0, global, c"Promise".as_ptr(), &mut promise_ctor)
+      },
+      "Get the global Promise failed"
+    )?;
+    let mut all_fn = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_named_property(env.0, promise_ctor, c"all".as_ptr(), &mut all_fn) },
+      "Get Promise.all failed"
+    )?;
+    let mut new_promise = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env.0,
+          promise_ctor,
+          all_fn,
+          1,
+          [array].as_ptr(),
+          &mut new_promise,
+        )
+      },
+      "Call Promise.all failed"
+    )?;
+    Ok(PromiseRaw::new(env.0, new_promise))
+  }
+
   #[cfg(all(feature = "napi4", feature = "tokio_rt"))]
   /// Convert `PromiseRaw<T>` to `Promise<T>`
   ///
@@ -449,11 +631,96 @@ where
     },
     "Get callback info from finally callback failed"
   )?;
-  let cb: Box<Cb> = unsafe { Box::from_raw(rust_cb.cast()) };
+  let Some(cb) = unsafe { &mut *rust_cb.cast::<Option<Cb>>() }.take() else {
+    return unsafe { <()>::to_napi_value(env, ()) };
+  };
 
   unsafe { U::to_napi_value(env, cb(Env(env))?) }
 }
 
+struct ThenOrCatchCallbacks<OnFulfilled, OnRejected> {
+  on_fulfilled: Option<OnFulfilled>,
+  on_rejected: Option<OnRejected>,
+}
+
+unsafe extern "C" fn raw_promise_then_or_catch_fulfilled<T, U, OnFulfilled, OnRejected>(
+  env: sys::napi_env,
+  cbinfo: sys::napi_callback_info,
+) -> sys::napi_value
+where
+  T: FromNapiValue,
+  U: ToNapiValue,
+  OnFulfilled: FnOnce(CallbackContext<T>) -> Result<U>,
+{
+  handle_then_or_catch_callback::<T, U, _, OnFulfilled, OnRejected>(env, cbinfo, |callbacks| {
+    callbacks.on_fulfilled.take()
+  })
+  .unwrap_or_else(|err| throw_error(env, err, "Error in Promise.then"))
+}
+
+unsafe extern "C" fn raw_promise_then_or_catch_rejected<E, U, OnFulfilled, OnRejected>(
+  env: sys::napi_env,
+  cbinfo: sys::napi_callback_info,
+) -> sys::napi_value
+where
+  E: FromNapiValue,
+  U: ToNapiValue,
+  OnRejected: FnOnce(CallbackContext<E>) -> Result<U>,
+{
+  handle_then_or_catch_callback::<E, U, _, OnFulfilled, OnRejected>(env, cbinfo, |callbacks| {
+    callbacks.on_rejected.take()
+  })
+  .unwrap_or_else(|err| throw_error(env, err, "Error in Promise.then"))
+}
+
+#[inline(always)]
+fn handle_then_or_catch_callback<V, U, Cb, OnFulfilled, OnRejected>(
+  env: sys::napi_env,
+  cbinfo: sys::napi_callback_info,
+  take: impl FnOnce(&mut ThenOrCatchCallbacks<OnFulfilled, OnRejected>) -> Option<Cb>,
+) -> Result<sys::napi_value>
+where
+  V: FromNapiValue,
+  U: ToNapiValue,
+  Cb: FnOnce(CallbackContext<V>) -> Result<U>,
+{
+  let mut callback_values = [ptr::null_mut()];
+  let mut callbacks = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        cbinfo,
+        &mut 1,
+        callback_values.as_mut_ptr(),
+        ptr::null_mut(),
+        &mut callbacks,
+      )
+    },
+    "Get callback info from then callback failed"
+  )?;
+  let callbacks =
+    unsafe { &mut *callbacks.cast::<ThenOrCatchCallbacks<OnFulfilled, OnRejected>>() };
+  // only one of the callbacks is called, drop the other one now
+  let cb = take(callbacks);
+  callbacks.on_fulfilled = None;
+  callbacks.on_rejected = None;
+  let Some(cb) = cb else {
+    return unsafe { <()>::to_napi_value(env, ()) };
+  };
+  let value: V = unsafe { FromNapiValue::from_napi_value(env, callback_values[0]) }?;
+
+  unsafe {
+    U::to_napi_value(
+      env,
+      cb(CallbackContext {
+        env: Env(env),
+        value,
+      })?,
+    )
+  }
+}
+
 pub struct CallbackContext<T> {
   pub env: Env,
   pub value: T,
@@ -510,6 +777,24 @@ extern "C" fn promise_callback_finalizer<T, U, Cb>(
   Cb: FnOnce(CallbackContext<T>) -> Result<U>,
 {
   if !unsafe { *Box::from_raw(finalize_data.cast()) } {
-    drop(unsafe { Box::from_raw(finalize_hint.cast::<Cb>()) });
+    drop(unsafe { Box::from_raw(finalize_hint.cast::<(Cb, *mut bool)>()) });
   }
 }
+
+extern "C" fn promise_finally_finalizer<Cb>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data.cast::<Option<Cb>>()) });
+}
+
+extern "C" fn then_or_catch_callbacks_finalizer<OnFulfilled, OnRejected>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe {
+    Box::from_raw(finalize_data.cast::<ThenOrCatchCallbacks<OnFulfilled, OnRejected>>())
+  });
+}
@@ -0,0 +1,177 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::ptr;
+
+use super::object::create_property_key;
+use crate::{
+  bindgen_prelude::{FromNapiValue, TypeName, ValidateNapiValue},
+  check_status, sys, Env, JsValue, Result, Value, ValueType,
+};
+
+thread_local! {
+  // The keys interned by `Env::intern_key`, by env
+  static INTERNED_KEYS: RefCell<HashMap<usize, InternedKeys>> = RefCell::new(HashMap::new());
+}
+
+struct InternedKeys {
+  // The strings can't be referenced before Node-API 10, so they are held by a referenced array,
+  // at the index recorded in `indices`
+  keys: sys::napi_ref,
+  indices: HashMap<Box<str>, u32>,
+}
+
+#[derive(Clone, Copy)]
+/// A property key interned by [`Env::intern_key`], which is created once per env and reused by the later lookups.
+///
+/// It's a JavaScript string, so it can also be passed to [`crate::bindgen_prelude::JsObjectValue::get_property`]
+/// and [`crate::bindgen_prelude::JsObjectValue::set_property`].
+pub struct PropertyKey<'env> {
+  env: sys::napi_env,
+  value: sys::napi_value,
+  _marker: PhantomData<&'env ()>,
+}
+
+impl<'env> JsValue<'env> for PropertyKey<'env> {
+  fn value(&self) -> Value {
+    Value {
+      env: self.env,
+      value: self.value,
+      value_type: ValueType::String,
+    }
+  }
+}
+
+impl TypeName for PropertyKey<'_> {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ValidateNapiValue for PropertyKey<'_> {}
+
+impl FromNapiValue for PropertyKey<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(PropertyKey {
+      env,
+      value: napi_val,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl Env {
+  /// The property key of `key`, cached for the lifetime of the env.
+  ///
+  /// The key is created with `node_api_create_property_key_utf8` with Node-API 10, which is internalized by the engine.
+  /// The following calls with the same `key` return the cached key without allocating,
+  /// so it should be used for the fixed set of keys accessed repeatedly, like the fields of the structs,
+  /// rather than the keys from the user input, which are never evicted.
+  pub fn intern_key(&self, key: &str) -> Result<PropertyKey<'_>> {
+    let cached = INTERNED_KEYS.with(|keys| {
+      keys
+        .borrow()
+        .get(&(self.0 as usize))
+        .map(|interned| (interned.keys, interned.indices.get(key).copied()))
+    });
+    let value = match cached {
+      Some((keys, Some(index))) => {
+        let keys = self.interned_keys_array(keys)?;
+        let mut value = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_get_element(self.0, keys, index, &mut value) },
+          "Get the interned property key `{}` failed",
+          key
+        )?;
+        value
+      }
+      Some((keys, None)) => {
+        let value = self.create_key(key)?;
+        let keys_array = self.interned_keys_array(keys)?;
+        self.push_interned_key(keys_array, key, value)?;
+        value
+      }
+      None => {
+        let value = self.create_key(key)?;
+        let mut keys_array = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_create_array(self.0, &mut keys_array) },
+          "Create the interned property keys failed"
+        )?;
+        let mut keys = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_create_reference(self.0, keys_array, 1, &mut keys) },
+          "Create reference for the interned property keys failed"
+        )?;
+        INTERNED_KEYS.with(|interned| {
+          interned.borrow_mut().insert(
+            self.0 as usize,
+            InternedKeys {
+              keys,
+              indices: HashMap::new(),
+            },
+          )
+        });
+        #[cfg(feature = "napi3")]
+        self.add_env_cleanup_hook(self.0 as usize, |env| {
+          INTERNED_KEYS.with(|interned| interned.borrow_mut().remove(&env));
+        })?;
+        self.push_interned_key(keys_array, key, value)?;
+        value
+      }
+    };
+    Ok(PropertyKey {
+      env: self.0,
+      value,
+      _marker: PhantomData,
+    })
+  }
+
+  fn create_key(&self, key: &str) -> Result<sys::napi_value> {
+    let mut value = ptr::null_mut();
+    check_status!(
+      unsafe { create_property_key(self.0, key, &mut value) },
+      "Create the property key `{}` failed",
+      key
+    )?;
+    Ok(value)
+  }
+
+  fn interned_keys_array(&self, keys: sys::napi_ref) -> Result<sys::napi_value> {
+    let mut keys_array = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.0, keys, &mut keys_array) },
+      "Get the interned property keys failed"
+    )?;
+    Ok(keys_array)
+  }
+
+  fn push_interned_key(
+    &self,
+    keys_array: sys::napi_value,
+    key: &str,
+    value: sys::napi_value,
+  ) -> Result<()> {
+    let index = INTERNED_KEYS.with(|interned| {
+      interned
+        .borrow()
+        .get(&(self.0 as usize))
+        .map_or(0, |interned| interned.indices.len() as u32)
+    });
+    check_status!(
+      unsafe { sys::napi_set_element(self.0, keys_array, index, value) },
+      "Intern the property key `{}` failed",
+      key
+    )?;
+    INTERNED_KEYS.with(|interned| {
+      if let Some(interned) = interned.borrow_mut().get_mut(&(self.0 as usize)) {
+        interned.indices.insert(key.into(), index);
+      }
+    });
+    Ok(())
+  }
+}
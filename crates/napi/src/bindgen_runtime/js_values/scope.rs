@@ -1,6 +1,9 @@
 use std::ptr;
 
-use crate::{bindgen_runtime::FromNapiValue, check_status, sys, Env, JsValue, Result};
+use crate::{
+  bindgen_runtime::{FromNapiValue, Unknown, ValidateNapiValue},
+  check_status, sys, Env, JsValue, Result,
+};
 
 pub struct HandleScope {
   pub(crate) scope: sys::napi_handle_scope,
@@ -103,6 +106,71 @@ impl Drop for EscapableHandleScope<'_> {
   }
 }
 
+impl Env {
+  /// Run `scope_fn` in a new handle scope, the values created in it are released once it returns,
+  /// so the loops creating many temporary values don't retain them until the native call returns.
+  ///
+  /// The values created from the `Env` passed to `scope_fn` borrow it, so they can't be returned out of the scope.
+  /// Convert them into Rust values, or use [`Env::with_escapable_handle_scope`] to return a JavaScript value.
+  ///
+  /// ```no_run
+  /// for i in 0..arr.len() {
+  ///   total += env.with_handle_scope(|env| {
+  ///     let value: JsString = arr.get_element(i)?;
+  ///     Ok(value.utf8_len()?)
+  ///   })?;
+  /// }
+  /// ```
+  pub fn with_handle_scope<T, F>(&self, scope_fn: F) -> Result<T>
+  where
+    F: for<'scope> FnOnce(&'scope Env) -> Result<T>,
+  {
+    let mut scope = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_open_handle_scope(self.0, &mut scope) },
+      "Failed to open handle scope"
+    )?;
+    let scope_env = Env(self.0);
+    let ret = scope_fn(&scope_env);
+    check_status!(
+      unsafe { sys::napi_close_handle_scope(self.0, scope) },
+      "Failed to close handle scope"
+    )?;
+    ret
+  }
+
+  /// Run `scope_fn` in a new escapable handle scope, and escape the value it returns into the outer scope as `V`.
+  ///
+  /// The other values created in the scope are released once it returns, like [`Env::with_handle_scope`].
+  pub fn with_escapable_handle_scope<'env, V, F>(&'env self, scope_fn: F) -> Result<V>
+  where
+    V: JsValue<'env> + FromNapiValue + ValidateNapiValue,
+    F: for<'scope> FnOnce(&'scope Env) -> Result<Unknown<'scope>>,
+  {
+    let mut scope = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_open_escapable_handle_scope(self.0, &mut scope) },
+      "Failed to open handle scope"
+    )?;
+    let scope_env = Env(self.0);
+    let escaped = scope_fn(&scope_env).and_then(|value| {
+      let mut escaped = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_escape_handle(self.0, scope, value.raw(), &mut escaped) },
+        "Failed to escape handle"
+      )?;
+      Ok(escaped)
+    });
+    check_status!(
+      unsafe { sys::napi_close_escapable_handle_scope(self.0, scope) },
+      "Failed to close handle scope"
+    )?;
+    let escaped = escaped?;
+    unsafe { V::validate(self.0, escaped) }?;
+    unsafe { V::from_napi_value(self.0, escaped) }
+  }
+}
+
 pub trait JsValuesTuple {
   fn env(&self) -> sys::napi_env;
 }
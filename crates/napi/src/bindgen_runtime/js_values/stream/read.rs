@@ -499,6 +499,7 @@ impl<T: FromNapiValue + 'static> futures_core::Stream for Reader<T> {
               reason: "".to_string(),
               maybe_raw: error_ref,
               maybe_env: cx.env.0,
+              maybe_value: None,
             });
             Ok(())
           })?
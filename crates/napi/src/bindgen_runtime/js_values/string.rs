@@ -1,9 +1,12 @@
+use std::borrow::Cow;
+use std::cell::{OnceCell, RefCell};
 use std::ffi::c_char;
 use std::fmt::Display;
+use std::marker::PhantomData;
 use std::ops::Deref;
 use std::ptr;
 
-use crate::{bindgen_prelude::*, check_status, check_status_and_type, sys};
+use crate::{bindgen_prelude::*, check_status, check_status_and_type, sys, type_of};
 
 impl TypeName for String {
   fn type_name() -> &'static str {
@@ -101,9 +104,194 @@ impl ToNapiValue for &str {
   }
 }
 
-#[derive(Debug)]
+impl TypeName for Cow<'_, str> {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ValidateNapiValue for Cow<'_, str> {}
+
+impl FromNapiValue for Cow<'_, str> {
+  /// Node-API copies the characters out of the engine, so the string received from JavaScript is always `Owned`,
+  /// use [`JsStringRef`] to read it without allocating.
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Cow::Owned(unsafe {
+      String::from_napi_value(env, napi_val)?
+    }))
+  }
+}
+
+impl ToNapiValue for Cow<'_, str> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_ref()) }
+  }
+}
+
+impl ToNapiValue for &Cow<'_, str> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_ref()) }
+  }
+}
+
+// The pooled buffers of `JsStringRef` larger than it are freed rather than reused
+const MAX_POOLED_STRING_CAPACITY: usize = 64 * 1024;
+const MAX_POOLED_STRING_BUFFERS: usize = 16;
+
+thread_local! {
+  static STRING_BUFFERS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A JavaScript string read as `&str` for the duration of the call, without allocating.
+///
+/// The UTF-8 characters are copied into a buffer reused by the `JsStringRef`s on the same thread,
+/// so reading the string arguments of a hot function doesn't allocate once the buffers are warmed up.
+/// Convert it into a `String` with [`JsStringRef::into_owned`] to keep it.
+pub struct JsStringRef<'env> {
+  buffer: Vec<u8>,
+  _marker: PhantomData<&'env ()>,
+}
+
+impl JsStringRef<'_> {
+  pub fn as_str(&self) -> &str {
+    // SAFETY: written by `napi_get_value_string_utf8`
+    unsafe { std::str::from_utf8_unchecked(&self.buffer) }
+  }
+
+  pub fn into_owned(self) -> String {
+    self.as_str().to_owned()
+  }
+}
+
+impl Deref for JsStringRef<'_> {
+  type Target = str;
+
+  fn deref(&self) -> &Self::Target {
+    self.as_str()
+  }
+}
+
+impl AsRef<str> for JsStringRef<'_> {
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
+
+impl Display for JsStringRef<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl std::fmt::Debug for JsStringRef<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Debug::fmt(self.as_str(), f)
+  }
+}
+
+impl Drop for JsStringRef<'_> {
+  fn drop(&mut self) {
+    if self.buffer.capacity() > MAX_POOLED_STRING_CAPACITY {
+      return;
+    }
+    let mut buffer = std::mem::take(&mut self.buffer);
+    buffer.clear();
+    let _ = STRING_BUFFERS.try_with(|buffers| {
+      let mut buffers = buffers.borrow_mut();
+      if buffers.len() < MAX_POOLED_STRING_BUFFERS {
+        buffers.push(buffer);
+      }
+    });
+  }
+}
+
+impl TypeName for JsStringRef<'_> {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ValidateNapiValue for JsStringRef<'_> {}
+
+impl FromNapiValue for JsStringRef<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut len = 0;
+
+    check_status_and_type!(
+      unsafe { sys::napi_get_value_string_utf8(env, napi_val, ptr::null_mut(), 0, &mut len) },
+      env,
+      napi_val,
+      "Failed to convert JavaScript value `{}` into rust type `JsStringRef`"
+    )?;
+
+    let mut buffer = STRING_BUFFERS
+      .with(|buffers| buffers.borrow_mut().pop())
+      .unwrap_or_default();
+    // end char len in C
+    buffer.reserve(len + 1);
+    let mut written_char_count = 0;
+
+    check_status_and_type!(
+      unsafe {
+        sys::napi_get_value_string_utf8(
+          env,
+          napi_val,
+          buffer.as_mut_ptr().cast(),
+          len + 1,
+          &mut written_char_count,
+        )
+      },
+      env,
+      napi_val,
+      "Failed to convert napi `{}` into rust type `JsStringRef`"
+    )?;
+
+    // SAFETY: the `written_char_count` bytes are initialized
+    unsafe { buffer.set_len(written_char_count) };
+
+    Ok(JsStringRef {
+      buffer,
+      _marker: PhantomData,
+    })
+  }
+}
+
+/// A JavaScript string of UTF-16 code units, converted with `napi_create_string_utf16` and `napi_get_value_string_utf16`.
+///
+/// The code units are kept as they are, including the unpaired surrogates, so it's lossless unlike `String`,
+/// and it can be passed to the wide string APIs of Windows without transcoding through UTF-8.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Utf16String(Vec<u16>);
 
+impl Utf16String {
+  /// Wrap the UTF-16 code units in `units` without copying.
+  pub fn from_units(units: Vec<u16>) -> Self {
+    Utf16String(units)
+  }
+
+  pub fn as_slice(&self) -> &[u16] {
+    &self.0
+  }
+
+  pub fn into_units(self) -> Vec<u16> {
+    self.0
+  }
+
+  #[cfg(windows)]
+  /// Convert into an `OsString` without transcoding, the unpaired surrogates are kept.
+  pub fn to_os_string(&self) -> std::ffi::OsString {
+    std::os::windows::ffi::OsStringExt::from_wide(&self.0)
+  }
+}
+
 impl ValidateNapiValue for Utf16String {}
 
 impl From<String> for Utf16String {
@@ -112,6 +300,25 @@ impl From<String> for Utf16String {
   }
 }
 
+impl From<&str> for Utf16String {
+  fn from(s: &str) -> Self {
+    Utf16String(s.encode_utf16().collect())
+  }
+}
+
+impl From<Vec<u16>> for Utf16String {
+  fn from(units: Vec<u16>) -> Self {
+    Utf16String(units)
+  }
+}
+
+#[cfg(windows)]
+impl From<&std::ffi::OsStr> for Utf16String {
+  fn from(s: &std::ffi::OsStr) -> Self {
+    Utf16String(std::os::windows::ffi::OsStrExt::encode_wide(s).collect())
+  }
+}
+
 impl Display for Utf16String {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     write!(f, "{}", String::from_utf16_lossy(self))
@@ -138,39 +345,49 @@ impl TypeName for Utf16String {
 
 impl FromNapiValue for Utf16String {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
-    let mut len = 0;
+    Ok(Utf16String(unsafe { get_utf16_units(env, napi_val)? }))
+  }
+}
 
-    check_status!(
-      unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
-      "Failed to convert napi `utf16 string` into rust type `String`",
-    )?;
+unsafe fn get_utf16_units(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Vec<u16>> {
+  let mut len = 0;
 
-    // end char len in C
-    len += 1;
-    let mut ret = vec![0; len];
-    let mut written_char_count = 0;
-
-    check_status!(
-      unsafe {
-        sys::napi_get_value_string_utf16(
-          env,
-          napi_val,
-          ret.as_mut_ptr(),
-          len,
-          &mut written_char_count,
-        )
-      },
-      "Failed to convert napi `utf16 string` into rust type `String`",
-    )?;
+  check_status!(
+    unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
+    "Failed to convert napi `utf16 string` into rust type `String`",
+  )?;
 
-    ret.truncate(written_char_count);
+  // end char len in C
+  len += 1;
+  let mut ret = vec![0; len];
+  let mut written_char_count = 0;
 
-    Ok(Utf16String(ret))
-  }
+  check_status!(
+    unsafe {
+      sys::napi_get_value_string_utf16(
+        env,
+        napi_val,
+        ret.as_mut_ptr(),
+        len,
+        &mut written_char_count,
+      )
+    },
+    "Failed to convert napi `utf16 string` into rust type `String`",
+  )?;
+
+  ret.truncate(written_char_count);
+
+  Ok(ret)
 }
 
 impl ToNapiValue for Utf16String {
   unsafe fn to_napi_value(env: sys::napi_env, val: Utf16String) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, &val) }
+  }
+}
+
+impl ToNapiValue for &Utf16String {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
     let mut ptr = ptr::null_mut();
 
     check_status!(
@@ -184,25 +401,182 @@ impl ToNapiValue for Utf16String {
   }
 }
 
-#[derive(Debug)]
+/// A JavaScript string received as UTF-16, whose code units are only copied out of the engine when they are read.
+///
+/// Returning it to JavaScript, or only reading its length, doesn't copy the string.
+/// The code units are copied once by the first [`Utf16Slice::as_slice`], which is what the wide string APIs take,
+/// for example `widestring::U16Str::from_slice` or the `PCWSTR` of the Windows APIs.
+pub struct Utf16Slice<'env> {
+  env: sys::napi_env,
+  value: sys::napi_value,
+  units: OnceCell<Vec<u16>>,
+  _marker: PhantomData<&'env ()>,
+}
+
+impl Utf16Slice<'_> {
+  /// The number of UTF-16 code units, which is the `length` of the string in JavaScript.
+  pub fn len(&self) -> Result<usize> {
+    if let Some(units) = self.units.get() {
+      return Ok(units.len());
+    }
+    let mut len = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_value_string_utf16(self.env, self.value, ptr::null_mut(), 0, &mut len)
+      },
+      "Failed to get the length of `utf16 string`"
+    )?;
+    Ok(len)
+  }
+
+  pub fn is_empty(&self) -> Result<bool> {
+    Ok(self.len()? == 0)
+  }
+
+  /// The UTF-16 code units, copied from the engine on the first call.
+  pub fn as_slice(&self) -> Result<&[u16]> {
+    if let Some(units) = self.units.get() {
+      return Ok(units);
+    }
+    let units = unsafe { get_utf16_units(self.env, self.value)? };
+    Ok(self.units.get_or_init(|| units))
+  }
+
+  pub fn to_utf16_string(&self) -> Result<Utf16String> {
+    Ok(Utf16String(self.as_slice()?.to_vec()))
+  }
+
+  #[cfg(windows)]
+  /// Convert into an `OsString` without transcoding, the unpaired surrogates are kept.
+  pub fn to_os_string(&self) -> Result<std::ffi::OsString> {
+    Ok(std::os::windows::ffi::OsStringExt::from_wide(
+      self.as_slice()?,
+    ))
+  }
+}
+
+impl TypeName for Utf16Slice<'_> {
+  fn type_name() -> &'static str {
+    "String(utf16)"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ValidateNapiValue for Utf16Slice<'_> {}
+
+impl FromNapiValue for Utf16Slice<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value_type = type_of!(env, napi_val)?;
+    if value_type != ValueType::String {
+      return Err(Error::new(
+        Status::StringExpected,
+        format!("Expect value to be String, but received {}", value_type),
+      ));
+    }
+    Ok(Utf16Slice {
+      env,
+      value: napi_val,
+      units: OnceCell::new(),
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl ToNapiValue for Utf16Slice<'_> {
+  unsafe fn to_napi_value(_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    Ok(val.value)
+  }
+}
+
+impl ToNapiValue for &Utf16Slice<'_> {
+  unsafe fn to_napi_value(_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    Ok(val.value)
+  }
+}
+
+/// A JavaScript string of latin1 characters, converted with `napi_create_string_latin1` and `napi_get_value_string_latin1`.
+///
+/// Each byte is one character, so it skips the UTF-8 validation and transcoding of `String` for the byte oriented text,
+/// like the headers of the network protocols. The characters received from JavaScript keep the low byte of their
+/// UTF-16 code units, like the `latin1` encoding of Node.js.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Latin1String(Vec<u8>);
 
+impl Latin1String {
+  /// Wrap the latin1 characters in `bytes` without copying.
+  pub fn from_bytes(bytes: Vec<u8>) -> Self {
+    Latin1String(bytes)
+  }
+
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.0
+  }
+
+  pub fn into_bytes(self) -> Vec<u8> {
+    self.0
+  }
+
+  /// Borrow the characters as a [`Latin1Str`].
+  pub fn as_latin1_str(&self) -> Latin1Str<'_> {
+    Latin1Str(&self.0)
+  }
+}
+
 impl ValidateNapiValue for Latin1String {}
 
 impl From<String> for Latin1String {
   fn from(s: String) -> Self {
-    Latin1String(s.into_bytes())
+    if s.is_ascii() {
+      return Latin1String(s.into_bytes());
+    }
+    Latin1String::from(s.as_str())
+  }
+}
+
+impl From<&str> for Latin1String {
+  fn from(s: &str) -> Self {
+    if s.is_ascii() {
+      return Latin1String(s.as_bytes().to_vec());
+    }
+    Latin1String(s.encode_utf16().map(|unit| unit as u8).collect())
+  }
+}
+
+impl From<Vec<u8>> for Latin1String {
+  fn from(bytes: Vec<u8>) -> Self {
+    Latin1String(bytes)
+  }
+}
+
+impl From<Latin1String> for String {
+  fn from(s: Latin1String) -> Self {
+    if s.0.is_ascii() {
+      // SAFETY: ASCII is valid UTF-8
+      return unsafe { String::from_utf8_unchecked(s.0) };
+    }
+    latin1_to_string(&s.0)
   }
 }
 
 #[cfg(feature = "latin1")]
+fn latin1_to_string(latin1: &[u8]) -> String {
+  let mut dst_slice = vec![0; latin1.len() * 2];
+  let written = encoding_rs::mem::convert_latin1_to_utf8(latin1, dst_slice.as_mut_slice());
+  dst_slice.truncate(written);
+  unsafe { String::from_utf8_unchecked(dst_slice) }
+}
+
+#[cfg(not(feature = "latin1"))]
+fn latin1_to_string(latin1: &[u8]) -> String {
+  latin1.iter().map(|&b| b as char).collect()
+}
+
 impl Display for Latin1String {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    let mut dst_slice = vec![0; self.0.len() * 2];
-    let written =
-      encoding_rs::mem::convert_latin1_to_utf8(self.0.as_slice(), dst_slice.as_mut_slice());
-    dst_slice.truncate(written);
-    write!(f, "{}", unsafe { String::from_utf8_unchecked(dst_slice) })
+    Display::fmt(&self.as_latin1_str(), f)
   }
 }
 
@@ -271,6 +645,77 @@ impl ToNapiValue for Latin1String {
   }
 }
 
+impl ToNapiValue for &Latin1String {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_latin1_str()) }
+  }
+}
+
+/// Borrowed latin1 characters, like the ASCII text in a `&[u8]`, which is created as a JavaScript string
+/// with `napi_create_string_latin1` without the UTF-8 validation or an allocation in Rust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Latin1Str<'a>(&'a [u8]);
+
+impl<'a> Latin1Str<'a> {
+  /// Each byte of `bytes` is one latin1 character.
+  pub fn new(bytes: &'a [u8]) -> Self {
+    Latin1Str(bytes)
+  }
+
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.0
+  }
+}
+
+impl<'a> From<&'a [u8]> for Latin1Str<'a> {
+  fn from(bytes: &'a [u8]) -> Self {
+    Latin1Str(bytes)
+  }
+}
+
+impl Deref for Latin1Str<'_> {
+  type Target = [u8];
+
+  fn deref(&self) -> &Self::Target {
+    self.0
+  }
+}
+
+impl Display for Latin1Str<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.0.is_ascii() {
+      // SAFETY: ASCII is valid UTF-8
+      return f.write_str(unsafe { std::str::from_utf8_unchecked(self.0) });
+    }
+    f.write_str(&latin1_to_string(self.0))
+  }
+}
+
+impl TypeName for Latin1Str<'_> {
+  fn type_name() -> &'static str {
+    "String(latin1)"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for Latin1Str<'_> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut ptr = ptr::null_mut();
+
+    check_status!(
+      unsafe {
+        sys::napi_create_string_latin1(env, val.0.as_ptr().cast(), val.0.len() as isize, &mut ptr)
+      },
+      "Failed to convert rust `&[u8]` into napi `latin1 string`"
+    )?;
+
+    Ok(ptr)
+  }
+}
+
 pub const NAPI_AUTO_LENGTH: isize = -1;
 
 #[derive(Debug)]
@@ -1,6 +1,6 @@
 use std::ptr;
 
-use crate::{check_status, sys};
+use crate::{check_status, sys, Env, Error, JsSymbol, Result, Status, ValueType};
 
 use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
 
@@ -102,3 +102,109 @@ impl FromNapiValue for Symbol {
     })
   }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The well-known symbols, which are the properties of the global `Symbol`.
+pub enum WellKnownSymbol {
+  AsyncDispose,
+  AsyncIterator,
+  Dispose,
+  HasInstance,
+  IsConcatSpreadable,
+  Iterator,
+  Match,
+  MatchAll,
+  Replace,
+  Search,
+  Species,
+  Split,
+  ToPrimitive,
+  ToStringTag,
+  Unscopables,
+}
+
+impl WellKnownSymbol {
+  /// The property name on the global `Symbol`, e.g. `asyncIterator`.
+  pub fn name(self) -> &'static str {
+    match self {
+      WellKnownSymbol::AsyncDispose => "asyncDispose",
+      WellKnownSymbol::AsyncIterator => "asyncIterator",
+      WellKnownSymbol::Dispose => "dispose",
+      WellKnownSymbol::HasInstance => "hasInstance",
+      WellKnownSymbol::IsConcatSpreadable => "isConcatSpreadable",
+      WellKnownSymbol::Iterator => "iterator",
+      WellKnownSymbol::Match => "match",
+      WellKnownSymbol::MatchAll => "matchAll",
+      WellKnownSymbol::Replace => "replace",
+      WellKnownSymbol::Search => "search",
+      WellKnownSymbol::Species => "species",
+      WellKnownSymbol::Split => "split",
+      WellKnownSymbol::ToPrimitive => "toPrimitive",
+      WellKnownSymbol::ToStringTag => "toStringTag",
+      WellKnownSymbol::Unscopables => "unscopables",
+    }
+  }
+
+  pub(crate) fn get(self, env: sys::napi_env) -> Result<sys::napi_value> {
+    let mut global = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_global(env, &mut global) },
+      "Get global object failed"
+    )?;
+    let mut symbol_object = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_get_named_property(env, global, c"Symbol".as_ptr().cast(), &mut symbol_object)
+      },
+      "Get global Symbol failed"
+    )?;
+    let mut symbol = ptr::null_mut();
+    let mut key = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        self.name().as_ptr().cast(),
+        self.name().len() as isize,
+        &mut key,
+      )
+    })?;
+    check_status!(
+      unsafe { sys::napi_get_property(env, symbol_object, key, &mut symbol) },
+      "Get Symbol.{} failed",
+      self.name()
+    )?;
+    // `Symbol.dispose` and `Symbol.asyncDispose` are missing on the older runtimes
+    let mut value_type = 0;
+    check_status!(unsafe { sys::napi_typeof(env, symbol, &mut value_type) })?;
+    if ValueType::from(value_type) != ValueType::Symbol {
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!("Symbol.{} is not supported by the runtime", self.name()),
+      ));
+    }
+    Ok(symbol)
+  }
+}
+
+impl TypeName for WellKnownSymbol {
+  fn type_name() -> &'static str {
+    "Symbol"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Symbol
+  }
+}
+
+impl ToNapiValue for WellKnownSymbol {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    val.get(env)
+  }
+}
+
+impl Symbol {
+  /// The well-known `symbol`, like `Symbol.asyncIterator` in JavaScript.
+  pub fn well_known(env: &Env, symbol: WellKnownSymbol) -> Result<JsSymbol<'_>> {
+    unsafe { JsSymbol::from_napi_value(env.0, symbol.get(env.0)?) }
+  }
+}
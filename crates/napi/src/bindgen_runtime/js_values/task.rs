@@ -1,14 +1,19 @@
 use std::ffi::c_void;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::ptr;
 use std::rc::Rc;
-use std::sync::atomic::{AtomicPtr, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
 
+#[cfg(feature = "napi5")]
+use crate::Interrupt;
 use crate::Value;
 use crate::{
   async_work,
   bindgen_prelude::{FromNapiValue, JsObjectValue, ToNapiValue, TypeName, Unknown},
-  check_status, sys, Env, Error, JsError, Task, ValueType,
+  check_status, sys, Env, Error, ErrorSink, JsError, Status, Task, ValueType,
 };
 
 use super::Object;
@@ -16,6 +21,9 @@ use super::Object;
 pub struct AsyncTask<T: Task> {
   inner: T,
   abort_signal: Option<AbortSignal>,
+  #[cfg(feature = "napi5")]
+  interrupt: Option<Interrupt>,
+  error_sink: Option<Rc<dyn ErrorSink>>,
 }
 
 impl<T: Task> TypeName for T {
@@ -33,6 +41,9 @@ impl<T: Task> AsyncTask<T> {
     Self {
       inner: task,
       abort_signal: None,
+      #[cfg(feature = "napi5")]
+      interrupt: None,
+      error_sink: None,
     }
   }
 
@@ -40,6 +51,9 @@ impl<T: Task> AsyncTask<T> {
     Self {
       inner: task,
       abort_signal: Some(signal),
+      #[cfg(feature = "napi5")]
+      interrupt: None,
+      error_sink: None,
     }
   }
 
@@ -47,18 +61,60 @@ impl<T: Task> AsyncTask<T> {
     Self {
       inner: task,
       abort_signal: signal,
+      #[cfg(feature = "napi5")]
+      interrupt: None,
+      error_sink: None,
     }
   }
+
+  /// Expose an `interrupt()` method on the returned `Promise`, which sets the `interrupt`.
+  ///
+  /// The `task` should hold a clone of the `interrupt` and call `Interrupt::check` in `compute`.
+  /// If the task is not started yet, it's cancelled and the `Promise` is rejected with `AbortError` immediately.
+  #[cfg(feature = "napi5")]
+  pub fn with_interrupt(mut self, interrupt: Interrupt) -> Self {
+    self.interrupt = Some(interrupt);
+    self
+  }
+
+  /// Deliver the error of `Task::finally`, or the failure to settle the `Promise`,
+  /// to `sink` instead of the sink of the env, see [`ErrorSink`].
+  pub fn with_error_sink<S: ErrorSink>(mut self, sink: S) -> Self {
+    self.error_sink = Some(Rc::new(sink));
+    self
+  }
 }
 
 /// <https://developer.mozilla.org/zh-CN/docs/Web/API/AbortController>
+///
+/// Passed to an `AsyncTask`, it cancels the task if it's not started yet. Passed to a `#[napi] async fn`,
+/// the future of the function is dropped once it's aborted, and the returned `Promise` rejects with `AbortError`:
+///
+/// ```
+/// #[napi]
+/// pub async fn fetch(url: String, signal: AbortSignal) -> Result<String> {
+///   download(url).await
+/// }
+/// ```
+///
+/// The [`CancellationToken`] of the signal can be moved to other threads and tasks, like the `compute` of a `Task`
+/// which is started already.
 pub struct AbortSignal {
-  raw_work: Rc<AtomicPtr<sys::napi_async_work__>>,
-  status: Rc<AtomicU8>,
+  raw_work: Arc<AtomicPtr<sys::napi_async_work__>>,
+  status: Arc<AtomicU8>,
+  token: CancellationToken,
 }
 
-unsafe impl Send for AbortSignal {}
-unsafe impl Sync for AbortSignal {}
+impl AbortSignal {
+  /// The token which is cancelled when the signal is aborted.
+  pub fn token(&self) -> CancellationToken {
+    self.token.clone()
+  }
+
+  pub fn aborted(&self) -> bool {
+    self.token.is_cancelled()
+  }
+}
 
 #[repr(transparent)]
 struct AbortSignalStack(Vec<AbortSignal>);
@@ -73,12 +129,17 @@ impl FromNapiValue for AbortSignal {
       },
       PhantomData,
     );
-    let async_work_inner: Rc<AtomicPtr<sys::napi_async_work__>> =
-      Rc::new(AtomicPtr::new(ptr::null_mut()));
-    let task_status = Rc::new(AtomicU8::new(0));
+    let async_work_inner: Arc<AtomicPtr<sys::napi_async_work__>> =
+      Arc::new(AtomicPtr::new(ptr::null_mut()));
+    let task_status = Arc::new(AtomicU8::new(0));
+    let token = CancellationToken::new();
+    if signal.get_named_property_unchecked::<Option<bool>>("aborted")? == Some(true) {
+      token.cancel();
+    }
     let abort_signal = AbortSignal {
       raw_work: async_work_inner.clone(),
       status: task_status.clone(),
+      token: token.clone(),
     };
     let js_env = Env::from_raw(env);
 
@@ -117,6 +178,7 @@ impl FromNapiValue for AbortSignal {
     Ok(AbortSignal {
       raw_work: async_work_inner,
       status: task_status,
+      token,
     })
   }
 }
@@ -158,6 +220,9 @@ fn on_abort_impl(
       "Unwrap async_task from AbortSignal failed"
     )?;
     let abort_controller_stack = Box::leak(Box::from_raw(async_task as *mut AbortSignalStack));
+    for abort_controller in abort_controller_stack.0.iter() {
+      abort_controller.token.cancel();
+    }
     for abort_controller in abort_controller_stack.0.iter() {
       // Task Completed, return now
       if abort_controller.status.load(Ordering::Relaxed) == 1 {
@@ -184,16 +249,40 @@ fn on_abort_impl(
 
 impl<T: Task> ToNapiValue for AsyncTask<T> {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> crate::Result<sys::napi_value> {
-    if let Some(abort_signal) = val.abort_signal {
-      let async_promise = async_work::run(env, val.inner, Some(abort_signal.status.clone()))?;
+    let async_promise = if let Some(abort_signal) = val.abort_signal {
+      let async_promise = async_work::run(
+        env,
+        val.inner,
+        Some(abort_signal.status.clone()),
+        val.error_sink,
+      )?;
       abort_signal
         .raw_work
         .store(async_promise.napi_async_work, Ordering::Relaxed);
-      Ok(async_promise.promise_object().inner)
+      async_promise
     } else {
-      let async_promise = async_work::run(env, val.inner, None)?;
-      Ok(async_promise.promise_object().inner)
+      async_work::run(env, val.inner, None, val.error_sink)?
+    };
+    let promise = async_promise.promise_object().inner;
+    #[cfg(feature = "napi5")]
+    if let Some(interrupt) = val.interrupt {
+      let raw_work = async_promise.napi_async_work;
+      let status = async_promise.status;
+      let js_env = Env::from_raw(env);
+      let interrupt_fn =
+        js_env.create_function_from_closure::<(), (), _>("interrupt", move |_| {
+          interrupt.interrupt();
+          // same as `on_abort`, the async work can only be cancelled before it's started
+          if status.load(Ordering::Relaxed) == 0
+            && unsafe { sys::napi_cancel_async_work(env, raw_work) } == sys::Status::napi_ok
+          {
+            status.store(2, Ordering::Relaxed);
+          }
+          Ok(())
+        })?;
+      Object::from_raw(env, promise).set_named_property("interrupt", interrupt_fn)?;
     }
+    Ok(promise)
   }
 }
 
@@ -204,3 +293,95 @@ unsafe extern "C" fn async_task_abort_controller_finalize(
 ) {
   drop(unsafe { Box::from_raw(finalize_data as *mut AbortSignalStack) });
 }
+
+/// Cancelled when the [`AbortSignal`] it's taken from is aborted, or by [`CancellationToken::cancel`].
+///
+/// It's cheap to clone, and can be moved to other threads and tasks.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+#[derive(Default)]
+struct CancellationState {
+  cancelled: AtomicBool,
+  wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancellationToken {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn cancel(&self) {
+    if self.0.cancelled.swap(true, Ordering::AcqRel) {
+      return;
+    }
+    let wakers = std::mem::take(&mut *self.lock_wakers());
+    wakers.into_iter().for_each(Waker::wake);
+  }
+
+  pub fn is_cancelled(&self) -> bool {
+    self.0.cancelled.load(Ordering::Acquire)
+  }
+
+  /// Return an `AbortError` if it's cancelled, use it with `?` at the check points of a computation.
+  pub fn check(&self) -> crate::Result<()> {
+    if self.is_cancelled() {
+      return Err(abort_error());
+    }
+    Ok(())
+  }
+
+  /// Resolves once it's cancelled.
+  pub async fn cancelled(&self) {
+    std::future::poll_fn(|cx| self.poll_cancelled(cx.waker())).await
+  }
+
+  /// Run `fut` until it's cancelled, then `fut` is dropped and an `AbortError` is returned.
+  pub async fn run_until_cancelled<T, E, F>(self, fut: F) -> crate::Result<T>
+  where
+    E: Into<Error>,
+    F: Future<Output = Result<T, E>>,
+  {
+    let mut fut = std::pin::pin!(fut);
+    std::future::poll_fn(|cx| {
+      if self.is_cancelled() {
+        return Poll::Ready(Err(abort_error()));
+      }
+      if let Poll::Ready(output) = fut.as_mut().poll(cx) {
+        return Poll::Ready(output.map_err(Into::into));
+      }
+      self.poll_cancelled(cx.waker()).map(|_| Err(abort_error()))
+    })
+    .await
+  }
+
+  fn poll_cancelled(&self, waker: &Waker) -> Poll<()> {
+    if self.is_cancelled() {
+      return Poll::Ready(());
+    }
+    {
+      let mut wakers = self.lock_wakers();
+      if !wakers.iter().any(|w| w.will_wake(waker)) {
+        wakers.push(waker.clone());
+      }
+    }
+    // checked again, it may be cancelled before the waker is registered
+    if self.is_cancelled() {
+      Poll::Ready(())
+    } else {
+      Poll::Pending
+    }
+  }
+
+  fn lock_wakers(&self) -> std::sync::MutexGuard<'_, Vec<Waker>> {
+    self
+      .0
+      .wakers
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+fn abort_error() -> Error {
+  Error::new(Status::Cancelled, "AbortError".to_owned())
+}
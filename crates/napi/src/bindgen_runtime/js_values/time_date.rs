@@ -0,0 +1,43 @@
+use time::OffsetDateTime;
+
+use crate::{bindgen_prelude::*, check_status, sys, JsDate, ValueType};
+
+impl TypeName for OffsetDateTime {
+  fn type_name() -> &'static str {
+    "OffsetDateTime"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for OffsetDateTime {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { JsDate::validate(env, napi_val) }
+  }
+}
+
+impl ToNapiValue for OffsetDateTime {
+  unsafe fn to_napi_value(env: sys::napi_env, val: OffsetDateTime) -> Result<sys::napi_value> {
+    let mut ptr = std::ptr::null_mut();
+    let millis_since_epoch_utc = (val.unix_timestamp_nanos() / 1_000_000) as f64;
+
+    check_status!(
+      unsafe { sys::napi_create_date(env, millis_since_epoch_utc, &mut ptr) },
+      "Failed to convert rust type `OffsetDateTime` into napi value",
+    )?;
+
+    Ok(ptr)
+  }
+}
+
+/// The `Date` is converted into an `OffsetDateTime` in UTC, JavaScript doesn't keep the offset of a `Date`.
+impl FromNapiValue for OffsetDateTime {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let milliseconds_since_epoch_utc =
+      unsafe { JsDate::millis_since_epoch(env, napi_val, "OffsetDateTime") }?;
+    OffsetDateTime::from_unix_timestamp_nanos(milliseconds_since_epoch_utc as i128 * 1_000_000)
+      .map_err(|err| Error::new(Status::DateExpected, format!("Found invalid date: {err}")))
+  }
+}
@@ -0,0 +1,76 @@
+use std::any::type_name;
+
+use crate::{check_status, sys, JsValue, Result};
+
+/// A 128-bit tag attached to an object or an external with `napi_type_tag_object`, to check that it's created
+/// by the code that tagged it before its native pointer is recovered.
+///
+/// An object created by another addon, or by another version of this addon, can carry a native pointer of an
+/// unrelated layout, the tag tells them apart. Use a random value, for example the bytes of a UUID:
+///
+/// ```
+/// static DATABASE_TAG: TypeTag = TypeTag::new(0x1edf75a38336451d, 0xa5ed9ce2e4c00c38);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeTag {
+  pub lower: u64,
+  pub upper: u64,
+}
+
+// its address is different in each copy of the addon which is loaded
+static ADDON_MARKER: u8 = 0;
+
+impl TypeTag {
+  pub const fn new(lower: u64, upper: u64) -> Self {
+    Self { lower, upper }
+  }
+
+  /// The tag of the `#[napi]` class `T` in this copy of the addon.
+  pub(crate) fn of_class<T: ?Sized>() -> Self {
+    // FNV-1a
+    let lower = type_name::<T>()
+      .bytes()
+      .fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+      });
+    Self::new(lower, &ADDON_MARKER as *const u8 as u64)
+  }
+
+  /// Tags the object or the external, it can only be tagged once.
+  pub fn tag<'env, V: JsValue<'env>>(&self, value: &V) -> Result<()> {
+    unsafe { self.tag_raw(value.value().env, value.raw()) }
+  }
+
+  /// Whether the object or the external is tagged with this tag.
+  pub fn is_tagged<'env, V: JsValue<'env>>(&self, value: &V) -> Result<bool> {
+    unsafe { self.is_tagged_raw(value.value().env, value.raw()) }
+  }
+
+  pub(crate) unsafe fn tag_raw(&self, env: sys::napi_env, value: sys::napi_value) -> Result<()> {
+    check_status!(
+      unsafe { sys::napi_type_tag_object(env, value, &self.to_raw()) },
+      "Failed to tag the object with {:?}",
+      self
+    )
+  }
+
+  pub(crate) unsafe fn is_tagged_raw(
+    &self,
+    env: sys::napi_env,
+    value: sys::napi_value,
+  ) -> Result<bool> {
+    let mut result = false;
+    check_status!(
+      unsafe { sys::napi_check_object_type_tag(env, value, &self.to_raw(), &mut result) },
+      "Failed to check the type tag of the object"
+    )?;
+    Ok(result)
+  }
+
+  fn to_raw(self) -> sys::napi_type_tag {
+    sys::napi_type_tag {
+      lower: self.lower,
+      upper: self.upper,
+    }
+  }
+}
@@ -0,0 +1,189 @@
+use std::ops::Deref;
+use std::ptr;
+
+use crate::{
+  bindgen_runtime::{create_subarray, FromNapiValue, TypedArrayType, Unknown},
+  check_status, sys, Error, Result, Status,
+};
+
+/// The chunks of a TypedArray, created by `iter_chunks` of the TypedArrays like [`Uint8Array::iter_chunks`](crate::bindgen_prelude::Uint8Array::iter_chunks).
+///
+/// Every chunk has its own handle scope, which is closed when the chunk is dropped,
+/// so the JavaScript values created while processing a chunk don't pile up for a large payload.
+/// A chunk borrows the `TypedArrayChunks`, call [`TypedArrayChunks::next_chunk`] in a loop to process the chunks one by one:
+///
+/// ```rust
+/// let mut chunks = input.iter_chunks(env, 64 * 1024)?;
+/// while let Some(chunk) = chunks.next_chunk() {
+///   let chunk = chunk?;
+///   on_chunk.call(chunk.to_js()?)?;
+/// }
+/// ```
+pub struct TypedArrayChunks<'a, T> {
+  env: sys::napi_env,
+  data: &'a [T],
+  chunk_len: usize,
+  offset: usize,
+  typed_array_type: TypedArrayType,
+  // the JavaScript TypedArray to create the views on, `None` if the data is owned by Rust
+  typed_array: Option<sys::napi_value>,
+}
+
+impl<'a, T> TypedArrayChunks<'a, T> {
+  pub(crate) fn new(
+    env: sys::napi_env,
+    data: &'a [T],
+    chunk_len: usize,
+    typed_array_type: TypedArrayType,
+    typed_array: Option<sys::napi_value>,
+  ) -> Result<Self> {
+    if chunk_len == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The chunk length must be greater than 0",
+      ));
+    }
+    Ok(Self {
+      env,
+      data,
+      chunk_len,
+      offset: 0,
+      typed_array_type,
+      typed_array,
+    })
+  }
+
+  /// Number of the remaining chunks.
+  pub fn remaining(&self) -> usize {
+    (self.data.len() - self.offset).div_ceil(self.chunk_len)
+  }
+
+  /// Open a handle scope and return the next chunk, the scope is closed when the chunk is dropped.
+  pub fn next_chunk(&mut self) -> Option<Result<TypedArrayChunk<'_, T>>> {
+    if self.offset >= self.data.len() {
+      return None;
+    }
+    let mut scope = ptr::null_mut();
+    if let Err(err) = check_status!(
+      unsafe { sys::napi_open_handle_scope(self.env, &mut scope) },
+      "Failed to open handle scope"
+    ) {
+      return Some(Err(err));
+    }
+    let begin = self.offset;
+    let end = self.data.len().min(begin + self.chunk_len);
+    self.offset = end;
+    Some(Ok(TypedArrayChunk {
+      env: self.env,
+      data: &self.data[begin..end],
+      offset: begin,
+      typed_array_type: self.typed_array_type,
+      typed_array: self.typed_array,
+      total_len: self.data.len(),
+      scope,
+    }))
+  }
+
+  /// Call `f` with every chunk, stops at the first error.
+  pub fn try_for_each<F>(mut self, mut f: F) -> Result<()>
+  where
+    F: FnMut(TypedArrayChunk<'_, T>) -> Result<()>,
+  {
+    while let Some(chunk) = self.next_chunk() {
+      f(chunk?)?;
+    }
+    Ok(())
+  }
+}
+
+/// A chunk of a TypedArray, see [`TypedArrayChunks`].
+pub struct TypedArrayChunk<'c, T> {
+  env: sys::napi_env,
+  data: &'c [T],
+  offset: usize,
+  typed_array_type: TypedArrayType,
+  typed_array: Option<sys::napi_value>,
+  total_len: usize,
+  scope: sys::napi_handle_scope,
+}
+
+impl<T> TypedArrayChunk<'_, T> {
+  /// Index of the first element of the chunk in the TypedArray.
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /// The chunk as a JavaScript TypedArray of the same type, released when the chunk is dropped.
+  ///
+  /// It's a view on the same `ArrayBuffer` if the JavaScript value of the TypedArray is available,
+  /// otherwise the elements are copied into a new TypedArray.
+  pub fn to_js(&self) -> Result<Unknown<'_>> {
+    let value = match self.typed_array {
+      Some(typed_array) => create_subarray(
+        self.env,
+        typed_array,
+        self.typed_array_type,
+        self.total_len,
+        self.offset,
+        self.offset + self.data.len(),
+      )?,
+      None => {
+        let byte_length = std::mem::size_of_val(self.data);
+        let mut data = ptr::null_mut();
+        let mut arraybuffer = ptr::null_mut();
+        check_status!(
+          unsafe {
+            sys::napi_create_arraybuffer(self.env, byte_length, &mut data, &mut arraybuffer)
+          },
+          "Failed to create ArrayBuffer for the chunk"
+        )?;
+        if byte_length > 0 {
+          unsafe {
+            ptr::copy_nonoverlapping(self.data.as_ptr().cast::<u8>(), data.cast(), byte_length)
+          };
+        }
+        let mut typed_array = ptr::null_mut();
+        check_status!(
+          unsafe {
+            sys::napi_create_typedarray(
+              self.env,
+              self.typed_array_type.into(),
+              self.data.len(),
+              arraybuffer,
+              0,
+              &mut typed_array,
+            )
+          },
+          "Failed to create TypedArray for the chunk"
+        )?;
+        typed_array
+      }
+    };
+    unsafe { Unknown::from_napi_value(self.env, value) }
+  }
+}
+
+impl<T> Deref for TypedArrayChunk<'_, T> {
+  type Target = [T];
+
+  fn deref(&self) -> &Self::Target {
+    self.data
+  }
+}
+
+impl<T> AsRef<[T]> for TypedArrayChunk<'_, T> {
+  fn as_ref(&self) -> &[T] {
+    self.data
+  }
+}
+
+impl<T> Drop for TypedArrayChunk<'_, T> {
+  fn drop(&mut self) {
+    let status = unsafe { sys::napi_close_handle_scope(self.env, self.scope) };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Failed to close the handle scope of the TypedArray chunk, status code: {}",
+      crate::Status::from(status)
+    );
+  }
+}
@@ -122,6 +122,7 @@ impl<T: 'static> FromNapiValue for Reference<T> {
     env: crate::sys::napi_env,
     napi_val: crate::sys::napi_value,
   ) -> Result<Self> {
+    unsafe { super::check_class_instance::<T>(env, napi_val, std::any::type_name::<T>()) }?;
     let mut value = ptr::null_mut();
     check_status!(
       unsafe { crate::sys::napi_unwrap(env, napi_val, &mut value) },
@@ -329,3 +330,388 @@ impl<T: 'static, S: 'static> DerefMut for SharedReference<T, S> {
     unsafe { Box::leak(Box::from_raw(self.raw)) }
   }
 }
+
+#[cfg(feature = "napi4")]
+pub use send_guard::*;
+
+#[cfg(feature = "napi4")]
+mod send_guard {
+  use std::cell::{Cell, RefCell};
+  use std::ffi::c_void;
+  use std::marker::PhantomData;
+  use std::mem::{self, ManuallyDrop};
+  use std::ptr;
+  use std::sync::atomic::{AtomicU64, Ordering};
+  use std::sync::{Arc, Mutex};
+
+  use super::Reference;
+  use crate::{check_status, sys, Env, Error, Result, Status};
+
+  static RELEASED_IN_PLACE: AtomicU64 = AtomicU64::new(0);
+  static QUEUED: AtomicU64 = AtomicU64::new(0);
+  static RELEASED_FROM_QUEUE: AtomicU64 = AtomicU64::new(0);
+  static LEAKED: AtomicU64 = AtomicU64::new(0);
+
+  thread_local! {
+    static RELEASE_QUEUE: RefCell<Option<Arc<ReleaseQueue>>> = const { RefCell::new(None) };
+  }
+
+  #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+  /// Counters of the [`ReferenceSendGuard`]s released in this process.
+  pub struct ReferenceReleaseMetrics {
+    /// Guards dropped on the JavaScript thread, the reference is released immediately.
+    pub released_in_place: u64,
+    /// Guards dropped on other threads, the reference is queued for the JavaScript thread.
+    pub queued: u64,
+    /// Queued references released on the JavaScript thread.
+    pub released_from_queue: u64,
+    /// References which are never released because their environment is torn down first.
+    pub leaked: u64,
+  }
+
+  /// Get the [`ReferenceReleaseMetrics`], `queued - released_from_queue - leaked` is the number of pending releases.
+  pub fn reference_release_metrics() -> ReferenceReleaseMetrics {
+    ReferenceReleaseMetrics {
+      released_in_place: RELEASED_IN_PLACE.load(Ordering::Relaxed),
+      queued: QUEUED.load(Ordering::Relaxed),
+      released_from_queue: RELEASED_FROM_QUEUE.load(Ordering::Relaxed),
+      leaked: LEAKED.load(Ordering::Relaxed),
+    }
+  }
+
+  struct PendingRelease {
+    napi_ref: sys::napi_ref,
+    // `None` for the raw references created by `RawReferenceSendGuard`
+    finalize_callbacks: Option<Arc<Cell<*mut dyn FnOnce()>>>,
+  }
+
+  /// SAFETY: the reference and the finalize callbacks are only touched on the JavaScript thread.
+  unsafe impl Send for PendingRelease {}
+
+  impl PendingRelease {
+    /// Must be called on the JavaScript thread of `env`.
+    fn release(self, env: sys::napi_env) {
+      match self.finalize_callbacks {
+        // the referenced object has been finalized, see `Drop for Reference`
+        Some(finalize_callbacks) => {
+          if Arc::strong_count(&finalize_callbacks) > 1 {
+            let status = unsafe { sys::napi_reference_unref(env, self.napi_ref, ptr::null_mut()) };
+            debug_assert!(
+              status == sys::Status::napi_ok,
+              "Reference unref failed, status code: {}",
+              Status::from(status)
+            );
+          }
+        }
+        None => {
+          let status = unsafe { sys::napi_delete_reference(env, self.napi_ref) };
+          debug_assert!(
+            status == sys::Status::napi_ok,
+            "Delete reference failed, status code: {}",
+            Status::from(status)
+          );
+        }
+      }
+    }
+  }
+
+  #[derive(Default)]
+  struct ReleaseQueueState {
+    pending: Vec<PendingRelease>,
+    closed: bool,
+  }
+
+  /// A dedicated `ThreadsafeFunction` per environment, which releases the references dropped on other threads.
+  struct ReleaseQueue {
+    tsfn: sys::napi_threadsafe_function,
+    state: Mutex<ReleaseQueueState>,
+  }
+
+  /// SAFETY: `tsfn` is only called while `state` is locked and the queue is not closed.
+  unsafe impl Send for ReleaseQueue {}
+  unsafe impl Sync for ReleaseQueue {}
+
+  impl ReleaseQueue {
+    fn current(env: sys::napi_env) -> Result<Arc<Self>> {
+      if let Some(queue) = RELEASE_QUEUE.with(|cell| cell.borrow().clone()) {
+        return Ok(queue);
+      }
+      let mut func = ptr::null_mut();
+      check_status!(
+        unsafe {
+          sys::napi_create_function(
+            env,
+            c"reference_release".as_ptr(),
+            17,
+            Some(noop),
+            ptr::null_mut(),
+            &mut func,
+          )
+        },
+        "Create reference release function failed"
+      )?;
+      let mut name = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_string_utf8(env, c"ReferenceRelease".as_ptr(), 16, &mut name) },
+        "Create reference release resource name failed"
+      )?;
+      let queue = Arc::new_cyclic(|weak: &std::sync::Weak<Self>| {
+        let mut tsfn = ptr::null_mut();
+        let status = unsafe {
+          sys::napi_create_threadsafe_function(
+            env,
+            func,
+            ptr::null_mut(),
+            name,
+            0,
+            1,
+            ptr::null_mut(),
+            Some(finalize_release_queue),
+            weak.clone().into_raw().cast_mut().cast(),
+            Some(drain_release_queue),
+            &mut tsfn,
+          )
+        };
+        if status == sys::Status::napi_ok {
+          crate::cleanup_env::on_threadsafe_function_created(env);
+        }
+        Self {
+          tsfn,
+          state: Mutex::new(ReleaseQueueState {
+            pending: Vec::new(),
+            closed: status != sys::Status::napi_ok,
+          }),
+        }
+      });
+      if queue.tsfn.is_null() {
+        return Err(Error::new(
+          Status::GenericFailure,
+          "Create reference release ThreadsafeFunction failed",
+        ));
+      }
+      // the release queue should not keep the event loop alive
+      check_status!(
+        unsafe { sys::napi_unref_threadsafe_function(env, queue.tsfn) },
+        "Unref reference release ThreadsafeFunction failed"
+      )?;
+      RELEASE_QUEUE.with(|cell| cell.replace(Some(queue.clone())));
+      Ok(queue)
+    }
+
+    fn is_current_thread(self: &Arc<Self>) -> bool {
+      RELEASE_QUEUE.with(|cell| {
+        cell
+          .borrow()
+          .as_ref()
+          .is_some_and(|queue| Arc::ptr_eq(queue, self))
+      })
+    }
+
+    /// Release in place on the JavaScript thread, otherwise queue the release.
+    fn release_or_push(self: &Arc<Self>, env: sys::napi_env, release: PendingRelease) {
+      if self.is_current_thread() {
+        RELEASED_IN_PLACE.fetch_add(1, Ordering::Relaxed);
+        release.release(env);
+      } else {
+        self.push(release);
+      }
+    }
+
+    fn push(&self, release: PendingRelease) {
+      let mut state = self
+        .state
+        .lock()
+        .expect("Lock reference release queue failed");
+      if state.closed {
+        LEAKED.fetch_add(1, Ordering::Relaxed);
+        return;
+      }
+      QUEUED.fetch_add(1, Ordering::Relaxed);
+      state.pending.push(release);
+      // the queue is drained in one call, only schedule it for the first pending release
+      if state.pending.len() == 1 {
+        unsafe {
+          sys::napi_call_threadsafe_function(
+            self.tsfn,
+            ptr::null_mut(),
+            sys::ThreadsafeFunctionCallMode::nonblocking,
+          )
+        };
+      }
+    }
+  }
+
+  unsafe extern "C" fn noop(
+    _env: sys::napi_env,
+    _info: sys::napi_callback_info,
+  ) -> sys::napi_value {
+    ptr::null_mut()
+  }
+
+  unsafe extern "C" fn drain_release_queue(
+    env: sys::napi_env,
+    _js_callback: sys::napi_value,
+    context: *mut c_void,
+    _data: *mut c_void,
+  ) {
+    // the environment is being torn down, `finalize_release_queue` counts the pending releases as leaked
+    if env.is_null() {
+      return;
+    }
+    // the `Weak` is owned by the ThreadsafeFunction and released in `finalize_release_queue`
+    let queue =
+      ManuallyDrop::new(unsafe { std::sync::Weak::from_raw(context.cast::<ReleaseQueue>()) });
+    let Some(queue) = queue.upgrade() else {
+      return;
+    };
+    let pending = mem::take(
+      &mut queue
+        .state
+        .lock()
+        .expect("Lock reference release queue failed")
+        .pending,
+    );
+    RELEASED_FROM_QUEUE.fetch_add(pending.len() as u64, Ordering::Relaxed);
+    for release in pending {
+      release.release(env);
+    }
+  }
+
+  unsafe extern "C" fn finalize_release_queue(
+    _env: sys::napi_env,
+    _finalize_data: *mut c_void,
+    finalize_hint: *mut c_void,
+  ) {
+    let queue = unsafe { std::sync::Weak::from_raw(finalize_hint.cast::<ReleaseQueue>()) };
+    if let Some(queue) = queue.upgrade() {
+      let mut state = queue
+        .state
+        .lock()
+        .expect("Lock reference release queue failed");
+      state.closed = true;
+      LEAKED.fetch_add(state.pending.len() as u64, Ordering::Relaxed);
+      state.pending.clear();
+    }
+    let _ = RELEASE_QUEUE.try_with(|cell| cell.take());
+  }
+
+  /// A [`Reference`] which can be sent to and dropped on any thread.
+  ///
+  /// If it's dropped on the JavaScript thread, the reference is released immediately like `Reference`,
+  /// otherwise the release is queued to a `ThreadsafeFunction` dedicated to the environment and done on the JavaScript thread.
+  /// Use [`reference_release_metrics`] to observe the releases.
+  ///
+  /// Call [`ReferenceSendGuard::into_reference`] on the JavaScript thread to use the referenced value again.
+  pub struct ReferenceSendGuard<T: 'static> {
+    raw: *mut T,
+    env: sys::napi_env,
+    release: ManuallyDrop<PendingRelease>,
+    queue: Arc<ReleaseQueue>,
+    _marker: PhantomData<T>,
+  }
+
+  /// SAFETY: the referenced value is only accessible after converting back to `Reference` on the JavaScript thread.
+  unsafe impl<T: Send> Send for ReferenceSendGuard<T> {}
+  unsafe impl<T: Send> Sync for ReferenceSendGuard<T> {}
+
+  impl<T: 'static> Reference<T> {
+    /// Convert the `Reference` into a [`ReferenceSendGuard`], which can be dropped on any thread.
+    pub fn into_send_guard(self, env: &Env) -> Result<ReferenceSendGuard<T>> {
+      let queue = ReleaseQueue::current(env.0)?;
+      let reference = ManuallyDrop::new(self);
+      Ok(ReferenceSendGuard {
+        raw: reference.raw,
+        env: env.0,
+        release: ManuallyDrop::new(PendingRelease {
+          napi_ref: reference.napi_ref,
+          finalize_callbacks: Some(unsafe { ptr::read(&reference.finalize_callbacks) }),
+        }),
+        queue,
+        _marker: PhantomData,
+      })
+    }
+  }
+
+  impl<T: 'static> ReferenceSendGuard<T> {
+    /// Convert back to the `Reference`, it must be called on the JavaScript thread where the guard is created.
+    pub fn into_reference(self, env: &Env) -> Result<Reference<T>> {
+      if env.0 != self.env || !self.queue.is_current_thread() {
+        return Err(Error::new(
+          Status::InvalidArg,
+          "ReferenceSendGuard can only be converted back on the JavaScript thread where it is created",
+        ));
+      }
+      let mut guard = ManuallyDrop::new(self);
+      let release = unsafe { ManuallyDrop::take(&mut guard.release) };
+      drop(unsafe { ptr::read(&guard.queue) });
+      Ok(Reference {
+        raw: guard.raw,
+        napi_ref: release.napi_ref,
+        env: guard.env.cast(),
+        finalize_callbacks: release
+          .finalize_callbacks
+          .expect("ReferenceSendGuard always holds the finalize callbacks"),
+      })
+    }
+  }
+
+  impl<T: 'static> Drop for ReferenceSendGuard<T> {
+    fn drop(&mut self) {
+      let release = unsafe { ManuallyDrop::take(&mut self.release) };
+      self.queue.release_or_push(self.env, release);
+    }
+  }
+
+  /// A `napi_ref` to any JavaScript value which can be sent to and dropped on any thread, like [`ReferenceSendGuard`].
+  pub(crate) struct RawReferenceSendGuard {
+    env: sys::napi_env,
+    release: ManuallyDrop<PendingRelease>,
+    queue: Arc<ReleaseQueue>,
+  }
+
+  /// SAFETY: the reference is only touched on the JavaScript thread.
+  unsafe impl Send for RawReferenceSendGuard {}
+  unsafe impl Sync for RawReferenceSendGuard {}
+
+  impl RawReferenceSendGuard {
+    pub(crate) fn new(env: sys::napi_env, value: sys::napi_value) -> Result<Self> {
+      let queue = ReleaseQueue::current(env)?;
+      let mut napi_ref = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_reference(env, value, 1, &mut napi_ref) },
+        "Create reference failed"
+      )?;
+      Ok(Self {
+        env,
+        release: ManuallyDrop::new(PendingRelease {
+          napi_ref,
+          finalize_callbacks: None,
+        }),
+        queue,
+      })
+    }
+
+    /// Get the referenced value, it must be called on the JavaScript thread where the guard is created.
+    pub(crate) fn get(&self, env: sys::napi_env) -> Result<sys::napi_value> {
+      if env != self.env || !self.queue.is_current_thread() {
+        return Err(Error::new(
+          Status::InvalidArg,
+          "The referenced value can only be accessed on the JavaScript thread where it is created",
+        ));
+      }
+      let mut value = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env, self.release.napi_ref, &mut value) },
+        "Get referenced value failed"
+      )?;
+      Ok(value)
+    }
+  }
+
+  impl Drop for RawReferenceSendGuard {
+    fn drop(&mut self) {
+      let release = unsafe { ManuallyDrop::take(&mut self.release) };
+      self.queue.release_or_push(self.env, release);
+    }
+  }
+}
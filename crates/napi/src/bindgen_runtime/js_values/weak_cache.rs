@@ -0,0 +1,185 @@
+use std::{ffi::c_void, marker::PhantomData, ptr};
+
+use crate::{
+  bindgen_runtime::{
+    External, FnArgs, FromNapiValue, Function, JsObjectValue, JsValuesTupleIntoVec, Object,
+    TypeTag, Unknown,
+  },
+  check_status, sys, Env, Error, JsValue, Result, Status, ValueType,
+};
+
+/// A cache which associates Rust values with JavaScript objects by their identity.
+///
+/// The entries are stored in a JavaScript `WeakMap`, with the values wrapped in `External` tagged with the type
+/// of the cache, so an entry is released together with its key once the key object is garbage collected,
+/// and an `External` put into the `WeakMap` by other code is never read as `V`.
+/// It's useful for memoizing results computed from objects passed in by the caller.
+///
+/// The `WeakMap` is held by a strong reference, which is deleted when the cache is dropped,
+/// or when the env is torn down if the cache outlives it.
+pub struct JsWeakCache<V: 'static> {
+  // boxed, the env cleanup hook refers to it by address
+  weak_map: Box<WeakMapRef>,
+  _value: PhantomData<V>,
+}
+
+struct WeakMapRef {
+  env: sys::napi_env,
+  // null once the env cleanup hook deleted it
+  raw: sys::napi_ref,
+}
+
+impl<V: 'static> JsWeakCache<V> {
+  pub fn new(env: &Env) -> Result<Self> {
+    let global = env.get_global()?;
+    let weak_map_class =
+      global.get_named_property_unchecked::<Function<'_, (), Object>>("WeakMap")?;
+    let weak_map = unsafe { weak_map_class.new_instance(())?.cast::<Object>()? };
+    let mut raw = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env.0, weak_map.raw(), 1, &mut raw) },
+      "Create reference of the WeakMap failed"
+    )?;
+    let mut weak_map = Box::new(WeakMapRef { env: env.0, raw });
+    if let Err(err) = check_status!(
+      unsafe {
+        sys::napi_add_env_cleanup_hook(
+          env.0,
+          Some(delete_weak_map_ref),
+          (&mut *weak_map as *mut WeakMapRef).cast(),
+        )
+      },
+      "Add the cleanup hook of JsWeakCache failed"
+    ) {
+      unsafe { sys::napi_delete_reference(env.0, raw) };
+      return Err(err);
+    }
+    Ok(Self {
+      weak_map,
+      _value: PhantomData,
+    })
+  }
+
+  /// Get the value associated with `key`.
+  pub fn get<'a, 'env, K: JsObjectValue<'env>>(
+    &'a self,
+    env: &'a Env,
+    key: &K,
+  ) -> Result<Option<&'a V>> {
+    Ok(self.get_external(env, key)?.map(|external| &**external))
+  }
+
+  /// Get the mutable value associated with `key`.
+  pub fn get_mut<'a, 'env, K: JsObjectValue<'env>>(
+    &'a mut self,
+    env: &'a Env,
+    key: &K,
+  ) -> Result<Option<&'a mut V>> {
+    Ok(self.get_external(env, key)?.map(|external| &mut **external))
+  }
+
+  /// Associate `value` with `key`, the previous value is released by the GC.
+  pub fn insert<'env, K: JsObjectValue<'env>>(
+    &mut self,
+    env: &Env,
+    key: &K,
+    value: V,
+  ) -> Result<()> {
+    let external = External::new_tagged(env, value, &Self::tag())?;
+    self.call_method::<_, Unknown>(env, "set", FnArgs::from((key.to_unknown(), external)))?;
+    Ok(())
+  }
+
+  /// Get the value associated with `key`, or insert the value returned by `f` if there is none.
+  pub fn get_or_insert_with<'a, 'env, K: JsObjectValue<'env>, F: FnOnce() -> Result<V>>(
+    &'a mut self,
+    env: &'a Env,
+    key: &K,
+    f: F,
+  ) -> Result<&'a mut V> {
+    if !self.contains(env, key)? {
+      self.insert(env, key, f()?)?;
+    }
+    self.get_mut(env, key)?.ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        "The value inserted into JsWeakCache is missing",
+      )
+    })
+  }
+
+  /// Whether there is a value associated with `key`.
+  pub fn contains<'env, K: JsObjectValue<'env>>(&self, env: &Env, key: &K) -> Result<bool> {
+    self.call_method(env, "has", key.to_unknown())
+  }
+
+  /// Remove the value associated with `key`.
+  ///
+  /// Returns `false` if there is no value associated with `key`.
+  pub fn remove<'env, K: JsObjectValue<'env>>(&mut self, env: &Env, key: &K) -> Result<bool> {
+    self.call_method(env, "delete", key.to_unknown())
+  }
+
+  fn tag() -> TypeTag {
+    TypeTag::of_class::<Self>()
+  }
+
+  fn get_external<'a, 'env, K: JsObjectValue<'env>>(
+    &self,
+    env: &'a Env,
+    key: &K,
+  ) -> Result<Option<&'a mut External<V>>> {
+    let value: Unknown<'a> = self.call_method(env, "get", key.to_unknown())?;
+    if value.get_type()? == ValueType::Undefined {
+      return Ok(None);
+    }
+    // the `External` is kept alive by the `WeakMap` as long as the key is alive
+    External::downcast_tagged(&value, &Self::tag()).map(Some)
+  }
+
+  fn call_method<Args: JsValuesTupleIntoVec, Return: FromNapiValue>(
+    &self,
+    env: &Env,
+    name: &str,
+    args: Args,
+  ) -> Result<Return> {
+    if self.weak_map.raw.is_null() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The env of JsWeakCache is torn down",
+      ));
+    }
+    let mut weak_map = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env.0, self.weak_map.raw, &mut weak_map) },
+      "Get the WeakMap of JsWeakCache failed"
+    )?;
+    let weak_map = Object::from_raw(env.0, weak_map);
+    let method = weak_map.get_named_property_unchecked::<Function<'_, Args, Return>>(name)?;
+    method.apply(weak_map, args)
+  }
+}
+
+impl<V: 'static> Drop for JsWeakCache<V> {
+  fn drop(&mut self) {
+    let weak_map = &mut *self.weak_map;
+    if weak_map.raw.is_null() {
+      return;
+    }
+    unsafe {
+      sys::napi_remove_env_cleanup_hook(
+        weak_map.env,
+        Some(delete_weak_map_ref),
+        (weak_map as *mut WeakMapRef).cast(),
+      );
+      sys::napi_delete_reference(weak_map.env, weak_map.raw);
+    }
+    weak_map.raw = ptr::null_mut();
+  }
+}
+
+unsafe extern "C" fn delete_weak_map_ref(data: *mut c_void) {
+  let weak_map = unsafe { &mut *data.cast::<WeakMapRef>() };
+  unsafe { sys::napi_delete_reference(weak_map.env, weak_map.raw) };
+  weak_map.raw = ptr::null_mut();
+}
@@ -0,0 +1,198 @@
+//! Event loop watchdog, enabled by the `loop-watchdog` feature.
+//!
+//! Every synchronous `#[napi]` export and every `ThreadsafeFunction` callback runs on the JavaScript thread,
+//! and the event loop can't make any progress until it returns.
+//! The watchdog measures how long each of these native calls takes, and reports the calls that exceed the budget,
+//! which helps to find accidental long blocks in the addon.
+//!
+//! By default each slow call is reported as one line on stderr, which is the intended output of the watchdog:
+//!
+//! ```text
+//! [napi-rs] `readConfig` blocked the event loop for 84.213456ms, budget is 50ms
+//! ```
+//!
+//! Set a callback by [`set_loop_watchdog_callback`] to route the reports elsewhere, for example into the logger
+//! of the application, stderr isn't written while a callback is set.
+//!
+//! Without the `loop-watchdog` feature, all the functions in this module are no-op.
+
+#[cfg(feature = "loop-watchdog")]
+use std::cell::Cell;
+#[cfg(feature = "loop-watchdog")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "loop-watchdog")]
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+#[cfg(feature = "loop-watchdog")]
+use std::time::Instant;
+
+#[cfg(feature = "loop-watchdog")]
+type LoopWatchdogCallback = Arc<dyn Fn(&LoopWatchdogReport) + Send + Sync>;
+
+/// The default budget of a native call, 50ms.
+pub const DEFAULT_LOOP_WATCHDOG_BUDGET: Duration = Duration::from_millis(50);
+
+#[cfg(feature = "loop-watchdog")]
+static LOOP_WATCHDOG_BUDGET_NANOS: AtomicU64 =
+  AtomicU64::new(DEFAULT_LOOP_WATCHDOG_BUDGET.as_nanos() as u64);
+#[cfg(feature = "loop-watchdog")]
+static LOOP_WATCHDOG_CALLBACK: RwLock<Option<LoopWatchdogCallback>> = RwLock::new(None);
+
+#[cfg(feature = "loop-watchdog")]
+thread_local! {
+  // Nested native calls, for example an export calling a JavaScript function which calls into another export,
+  // are included in the outermost call, only the outermost call is measured.
+  static LOOP_WATCHDOG_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Where the native call that blocked the event loop came from.
+pub enum LoopWatchdogSource {
+  /// A synchronous `#[napi]` export, methods are named as `Class.method`.
+  Export(&'static str),
+  /// The Rust callback and the JavaScript function of a `ThreadsafeFunction`.
+  ThreadsafeFunction,
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A native call that exceeded the watchdog budget.
+pub struct LoopWatchdogReport {
+  pub source: LoopWatchdogSource,
+  /// How long the event loop was blocked by the call.
+  pub elapsed: Duration,
+  /// The budget when the call finished.
+  pub budget: Duration,
+}
+
+/// Set the budget of a single native call.
+///
+/// Calls that take longer than `budget` are reported to the callback set by [`set_loop_watchdog_callback`],
+/// or reported to stderr if there is no callback.
+pub fn set_loop_watchdog_budget(budget: Duration) {
+  #[cfg(feature = "loop-watchdog")]
+  LOOP_WATCHDOG_BUDGET_NANOS.store(
+    budget.as_nanos().min(u64::MAX as u128) as u64,
+    Ordering::Relaxed,
+  );
+  #[cfg(not(feature = "loop-watchdog"))]
+  let _ = budget;
+}
+
+/// Get the budget of a single native call.
+pub fn get_loop_watchdog_budget() -> Duration {
+  #[cfg(feature = "loop-watchdog")]
+  {
+    Duration::from_nanos(LOOP_WATCHDOG_BUDGET_NANOS.load(Ordering::Relaxed))
+  }
+  #[cfg(not(feature = "loop-watchdog"))]
+  {
+    DEFAULT_LOOP_WATCHDOG_BUDGET
+  }
+}
+
+/// Report the slow calls to a callback instead of stderr.
+///
+/// The callback is called on the JavaScript thread right after the slow native call returns,
+/// so it should be cheap and must not call into JavaScript.
+/// It's called without holding any lock, so it may replace or clear the callback itself.
+pub fn set_loop_watchdog_callback<F>(callback: F)
+where
+  F: Fn(&LoopWatchdogReport) + Send + Sync + 'static,
+{
+  #[cfg(feature = "loop-watchdog")]
+  {
+    *LOOP_WATCHDOG_CALLBACK
+      .write()
+      .expect("Write LOOP_WATCHDOG_CALLBACK failed") = Some(Arc::new(callback));
+  }
+  #[cfg(not(feature = "loop-watchdog"))]
+  let _ = callback;
+}
+
+/// Remove the callback set by [`set_loop_watchdog_callback`], slow calls are reported to stderr again.
+pub fn clear_loop_watchdog_callback() {
+  #[cfg(feature = "loop-watchdog")]
+  {
+    *LOOP_WATCHDOG_CALLBACK
+      .write()
+      .expect("Write LOOP_WATCHDOG_CALLBACK failed") = None;
+  }
+}
+
+#[doc(hidden)]
+/// Measure a native call until dropped.
+pub struct LoopWatchdogScope {
+  #[cfg(feature = "loop-watchdog")]
+  inner: Option<(LoopWatchdogSource, Instant)>,
+}
+
+impl LoopWatchdogScope {
+  #[inline]
+  pub fn enter(name: &'static str) -> Self {
+    Self::enter_with(LoopWatchdogSource::Export(name))
+  }
+
+  #[inline]
+  pub(crate) fn enter_threadsafe_function() -> Self {
+    Self::enter_with(LoopWatchdogSource::ThreadsafeFunction)
+  }
+
+  #[inline]
+  fn enter_with(source: LoopWatchdogSource) -> Self {
+    #[cfg(feature = "loop-watchdog")]
+    {
+      let depth = LOOP_WATCHDOG_DEPTH.with(|depth| {
+        let current = depth.get();
+        depth.set(current + 1);
+        current
+      });
+      Self {
+        inner: (depth == 0).then(|| (source, Instant::now())),
+      }
+    }
+    #[cfg(not(feature = "loop-watchdog"))]
+    {
+      let _ = source;
+      Self {}
+    }
+  }
+}
+
+#[cfg(feature = "loop-watchdog")]
+impl Drop for LoopWatchdogScope {
+  fn drop(&mut self) {
+    LOOP_WATCHDOG_DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    let Some((source, start)) = self.inner.take() else {
+      return;
+    };
+    let elapsed = start.elapsed();
+    let budget = get_loop_watchdog_budget();
+    if elapsed <= budget {
+      return;
+    }
+    let report = LoopWatchdogReport {
+      source,
+      elapsed,
+      budget,
+    };
+    // Clone the callback out of the lock, so the callback is able to replace itself
+    let callback = LOOP_WATCHDOG_CALLBACK
+      .read()
+      .expect("Read LOOP_WATCHDOG_CALLBACK failed")
+      .clone();
+    match callback {
+      Some(callback) => callback(&report),
+      // the default report, see the module docs
+      None => {
+        let source = match report.source {
+          LoopWatchdogSource::Export(name) => format!("`{name}`"),
+          LoopWatchdogSource::ThreadsafeFunction => "ThreadsafeFunction callback".to_owned(),
+        };
+        eprintln!(
+          "[napi-rs] {} blocked the event loop for {:?}, budget is {:?}",
+          source, report.elapsed, report.budget
+        );
+      }
+    }
+  }
+}
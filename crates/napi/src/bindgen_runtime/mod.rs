@@ -4,9 +4,19 @@ use std::rc::Rc;
 pub use callback_info::*;
 pub use ctor::ctor;
 pub use env::*;
+#[cfg(feature = "napi6")]
+pub use intl::*;
 pub use iterator::Generator;
 pub use js_values::*;
+pub use loop_watchdog::*;
 pub use module_register::*;
+#[cfg(feature = "napi4")]
+pub use named_thread::*;
+#[cfg(feature = "napi6")]
+pub(crate) use ref_cache::RefCache;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+pub use single_flight::*;
+pub use slice_borrows::*;
 
 use super::sys;
 use crate::{JsError, Result, Status};
@@ -16,9 +26,21 @@ pub mod async_iterator;
 mod callback_info;
 mod env;
 mod error;
+#[cfg(feature = "napi6")]
+mod intl;
 pub mod iterator;
 mod js_values;
+mod loop_watchdog;
 mod module_register;
+#[cfg(feature = "napi4")]
+mod named_thread;
+#[cfg(feature = "napi6")]
+mod ref_cache;
+#[cfg(feature = "napi6")]
+mod scheduling;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+mod single_flight;
+mod slice_borrows;
 
 pub trait ObjectFinalize: Sized {
   #[allow(unused)]
@@ -47,7 +69,11 @@ pub(crate) unsafe extern "C" fn raw_finalize_unchecked<T: ObjectFinalize>(
   {
     let finalize_callbacks_rc = unsafe { Rc::from_raw(finalize_callbacks_ptr) };
 
-    #[cfg(all(debug_assertions, not(target_family = "wasm")))]
+    #[cfg(all(
+      debug_assertions,
+      not(target_family = "wasm"),
+      not(feature = "minimal")
+    ))]
     {
       let rc_strong_count = Rc::strong_count(&finalize_callbacks_rc);
       // If `Rc` strong count is 2, it means the finalize of referenced `Object` is called before the `fn drop` of the `Reference`
@@ -71,22 +97,22 @@ pub(crate) unsafe extern "C" fn raw_finalize_unchecked<T: ObjectFinalize>(
   }
 }
 
+#[doc(hidden)]
+/// Emit the `DeprecationWarning` of the `#[napi(deprecated = "..", deprecation_warning)]` exports.
+pub fn emit_deprecation_warning(env: sys::napi_env, message: &str) {
+  let _ = Env::from_raw(env).emit_warning(message, "DeprecationWarning");
+}
+
 /// # Safety
 ///
 /// called when node buffer is ready for gc
 #[doc(hidden)]
 pub unsafe extern "C" fn drop_buffer(
   _env: sys::napi_env,
-  #[allow(unused)] finalize_data: *mut c_void,
+  _finalize_data: *mut c_void,
   finalize_hint: *mut c_void,
 ) {
-  #[cfg(all(debug_assertions, not(windows)))]
-  {
-    js_values::BUFFER_DATA.with(|buffer_data| {
-      let mut buffer = buffer_data.lock().expect("Unlock Buffer data failed");
-      buffer.remove(&(finalize_data as *mut u8));
-    });
-  }
+  // dropping the `Buffer` forgets its backing pointer
   unsafe {
     drop(Box::from_raw(finalize_hint as *mut Buffer));
   }
@@ -97,14 +123,15 @@ pub unsafe extern "C" fn drop_buffer(
 /// called when node buffer slice is ready for gc
 #[doc(hidden)]
 pub unsafe extern "C" fn drop_buffer_slice(
-  _env: sys::napi_env,
+  env: sys::napi_env,
   finalize_data: *mut c_void,
   finalize_hint: *mut c_void,
 ) {
-  let len = *unsafe { Box::from_raw(finalize_hint.cast()) };
-  #[cfg(all(debug_assertions, not(windows)))]
+  let (len, cap) = *unsafe { Box::from_raw(finalize_hint.cast::<(usize, usize)>()) };
+  #[cfg(not(feature = "minimal"))]
   unregister_backing_ptr(finalize_data as *mut u8);
   unsafe {
-    drop(Vec::from_raw_parts(finalize_data, len, len));
+    drop(Vec::from_raw_parts(finalize_data.cast::<u8>(), len, cap));
   }
+  let _ = adjust_external_memory(env, -(len as i64));
 }
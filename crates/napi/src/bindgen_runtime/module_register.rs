@@ -108,9 +108,15 @@ static MODULE_REGISTER_HOOK_CALLBACK: LazyLock<RwLock<Option<ExportRegisterHookC
 #[cfg(not(feature = "noop"))]
 static MODULE_CLASS_PROPERTIES: LazyLock<ModuleClassProperty> = LazyLock::new(Default::default);
 #[cfg(not(feature = "noop"))]
+static MODULE_EXPORT_SIGNATURES: LazyLock<RwLock<ExportSignatures>> =
+  LazyLock::new(Default::default);
+#[cfg(not(feature = "noop"))]
 static MODULE_COUNT: AtomicUsize = AtomicUsize::new(0);
 #[cfg(not(feature = "noop"))]
 static FIRST_MODULE_REGISTERED: AtomicBool = AtomicBool::new(false);
+#[cfg(all(feature = "tokio_rt", not(feature = "noop")))]
+// The envs using the tokio runtime, which is shut down with the last of them
+static ASYNC_RUNTIME_ENVS: AtomicUsize = AtomicUsize::new(0);
 thread_local! {
   static REGISTERED_CLASSES: LazyCell<RegisteredClasses> = LazyCell::new(Default::default);
   static FN_REGISTER_MAP: LazyCell<FnRegisterMap> = LazyCell::new(Default::default);
@@ -126,6 +132,69 @@ thread_local! {
   pub(crate) static THREADS_CAN_ACCESS_ENV: Cell<bool> = const { Cell::new(false) };
 }
 
+thread_local! {
+  // The macro registered exports and the `exports` object of the current env
+  static REGISTERED_EXPORTS: RefCell<Vec<ExportInfo>> = const { RefCell::new(Vec::new()) };
+  static MODULE_EXPORTS_REF: Cell<sys::napi_ref> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kind of an export registered by the `#[napi]` macro.
+pub enum ExportKind {
+  /// `#[napi] fn`
+  Function,
+  /// `#[napi] struct` and `#[napi] class`
+  Class,
+  /// Exports that are created as Objects, like `#[napi] enum`
+  Object,
+  /// Other values, like `#[napi] const`
+  Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An export registered by the `#[napi]` macro, see [`registered_exports`].
+pub struct ExportInfo {
+  /// The `js_mod` namespace of this export, `None` if it was exported on the top level.
+  pub js_mod: Option<&'static str>,
+  /// The JavaScript name of this export.
+  pub name: &'static str,
+  pub kind: ExportKind,
+  /// The TypeScript signature of a `#[napi] fn` without its name, like `(a: number, b: number): number`.
+  ///
+  /// It's `None` for the other kinds and when `napi-derive` was built without the `type-def` feature.
+  pub signature: Option<&'static str>,
+}
+
+/// List the exports registered by the `#[napi]` macro while the module was initialized in the current env.
+///
+/// The list is empty before `napi_register_module_v1` has run.
+/// Exports that are added, removed or replaced later via `Env::set_module_export` or
+/// `Env::remove_module_export` are not reflected in it.
+pub fn registered_exports() -> Vec<ExportInfo> {
+  REGISTERED_EXPORTS.with(|exports| exports.borrow().clone())
+}
+
+pub(crate) fn get_module_exports_ref() -> Option<sys::napi_ref> {
+  let exports_ref = MODULE_EXPORTS_REF.with(|cell| cell.get());
+  if exports_ref.is_null() {
+    None
+  } else {
+    Some(exports_ref)
+  }
+}
+
+#[cfg(not(feature = "noop"))]
+fn strip_nul(name: &'static str) -> &'static str {
+  name.strip_suffix('\0').unwrap_or(name)
+}
+
+#[cfg(not(feature = "noop"))]
+type ExportSignatures = HashMap<
+  /* js_mod and export name */ (Option<&'static str>, &'static str),
+  /* TypeScript signature */ &'static str,
+  FxBuildHasher,
+>;
+
 type RegisteredClasses = PersistedPerInstanceHashMap<
   /* export name */ String,
   /* constructor */ sys::napi_ref,
@@ -181,6 +250,28 @@ pub fn register_module_export(
 ) {
 }
 
+#[cfg(not(feature = "noop"))]
+#[doc(hidden)]
+pub fn register_module_export_signature(
+  js_mod: Option<&'static str>,
+  name: &'static str,
+  signature: &'static str,
+) {
+  MODULE_EXPORT_SIGNATURES
+    .write()
+    .expect("Register module export signature failed")
+    .insert((js_mod, name), signature);
+}
+
+#[cfg(feature = "noop")]
+#[doc(hidden)]
+pub fn register_module_export_signature(
+  _js_mod: Option<&'static str>,
+  _name: &'static str,
+  _signature: &'static str,
+) {
+}
+
 #[cfg(not(feature = "noop"))]
 #[doc(hidden)]
 pub fn register_module_export_hook(cb: ExportRegisterHookCallback) {
@@ -320,7 +411,18 @@ pub unsafe extern "C" fn napi_register_module_v1(
     wait_first_thread_registered();
   }
 
+  // registered before the other cleanup hooks of the env, so the `Default` and `Last` phases run after them
+  #[cfg(feature = "napi3")]
+  if let Err(e) = crate::cleanup_env::register_phased_hooks(&crate::Env::from_raw(env)) {
+    JsError::from(e).throw_into(env);
+  }
+
   let mut exports_objects: HashSet<String> = HashSet::default();
+  let mut registered_exports = Vec::new();
+  #[cfg(feature = "strict-register")]
+  let mut expected_exports = Vec::new();
+  #[cfg(feature = "strict-register")]
+  let mut register_errors = Vec::new();
 
   {
     let mut register_callback = MODULE_REGISTER_CALLBACK
@@ -378,6 +480,8 @@ pub unsafe extern "C" fn napi_register_module_v1(
           }
         }
         for (name, callback) in items {
+          #[cfg(feature = "strict-register")]
+          expected_exports.push((*js_mod, *name));
           unsafe {
             let js_name = CStr::from_bytes_with_nul_unchecked(name.as_bytes());
             if let Err(e) = callback(env).and_then(|v| {
@@ -390,8 +494,31 @@ pub unsafe extern "C" fn napi_register_module_v1(
                 sys::napi_set_named_property(env, exported_object, js_name.as_ptr(), v),
                 "Failed to register export `{}`",
                 name,
-              )
+              )?;
+              let kind = match crate::type_of!(env, v)? {
+                crate::ValueType::Function => ExportKind::Function,
+                crate::ValueType::Object => ExportKind::Object,
+                _ => ExportKind::Value,
+              };
+              let signature = match kind {
+                ExportKind::Function => MODULE_EXPORT_SIGNATURES
+                  .read()
+                  .expect("Read module export signatures failed")
+                  .get(&(*js_mod, *name))
+                  .copied(),
+                _ => None,
+              };
+              registered_exports.push(ExportInfo {
+                js_mod: js_mod.map(strip_nul),
+                name: strip_nul(name),
+                kind,
+                signature,
+              });
+              Ok(())
             }) {
+              #[cfg(feature = "strict-register")]
+              register_errors.push(format!("`{}`: {}", strip_nul(name), e.reason));
+              #[cfg(not(feature = "strict-register"))]
               JsError::from(e).throw_into(env)
             }
           }
@@ -404,6 +531,8 @@ pub unsafe extern "C" fn napi_register_module_v1(
   MODULE_CLASS_PROPERTIES.borrow(|inner| {
     inner.iter().for_each(|(_, js_mods)| {
       for (js_mod, (js_name, props)) in js_mods {
+        #[cfg(feature = "strict-register")]
+        expected_exports.push((*js_mod, *js_name));
         let mut exports_js_mod = ptr::null_mut();
         unsafe {
           if let Some(js_mod_str) = js_mod {
@@ -467,6 +596,12 @@ pub unsafe extern "C" fn napi_register_module_v1(
           sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref);
 
           registered_classes.insert(js_name.to_string(), ctor_ref);
+          registered_exports.push(ExportInfo {
+            js_mod: js_mod.map(strip_nul),
+            name: strip_nul(js_name),
+            kind: ExportKind::Class,
+            signature: None,
+          });
 
           check_status_or_throw!(
             env,
@@ -493,6 +628,23 @@ pub unsafe extern "C" fn napi_register_module_v1(
       *map = registered_classes;
     })
   });
+  REGISTERED_EXPORTS.with(|cell| {
+    *cell.borrow_mut() = registered_exports;
+  });
+
+  #[cfg(feature = "strict-register")]
+  unsafe {
+    verify_registered_exports(env, exports, &expected_exports, register_errors)
+  };
+
+  // a strong reference, the exports object is kept alive until the env is torn down and `thread_cleanup` deletes it
+  let mut exports_ref = ptr::null_mut();
+  check_status_or_throw!(
+    env,
+    unsafe { sys::napi_create_reference(env, exports, 1, &mut exports_ref) },
+    "Failed to create reference for the module exports"
+  );
+  MODULE_EXPORTS_REF.with(|cell| cell.set(exports_ref));
 
   let module_register_hook_callback = MODULE_REGISTER_HOOK_CALLBACK
     .read()
@@ -543,12 +695,106 @@ pub unsafe extern "C" fn napi_register_module_v1(
     #[cfg(feature = "tokio_rt")]
     {
       crate::tokio_runtime::start_async_runtime();
+      ASYNC_RUNTIME_ENVS.fetch_add(1, Ordering::SeqCst);
+      // the hooks of the other phases may still spawn onto the runtime
+      if let Err(e) =
+        crate::Env::from_raw(env).add_phased_cleanup_hook(crate::CleanupPhase::Last, (), |_| {
+          if ASYNC_RUNTIME_ENVS.fetch_sub(1, Ordering::SeqCst) == 1 {
+            crate::tokio_runtime::shutdown_async_runtime();
+          }
+        })
+      {
+        JsError::from(e).throw_into(env);
+      }
     }
   }
   FIRST_MODULE_REGISTERED.store(true, Ordering::SeqCst);
   exports
 }
 
+#[cfg(all(feature = "strict-register", not(feature = "noop")))]
+/// Check that every `#[napi]` export is registered exactly once and is present on the `exports` object,
+/// throw an aggregated error listing all the problems otherwise.
+unsafe fn verify_registered_exports(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+  expected_exports: &[(Option<&'static str>, &'static str)],
+  mut problems: Vec<String>,
+) {
+  let mut is_exception_pending = false;
+  unsafe { sys::napi_is_exception_pending(env, &mut is_exception_pending) };
+  if is_exception_pending {
+    let mut exception = ptr::null_mut();
+    unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+    let mut message = ptr::null_mut();
+    unsafe { sys::napi_coerce_to_string(env, exception, &mut message) };
+    let message =
+      unsafe { <String as crate::bindgen_prelude::FromNapiValue>::from_napi_value(env, message) }
+        .unwrap_or_else(|_| "unknown exception".to_owned());
+    problems.push(message);
+  }
+
+  let mut export_counts: HashMap<(Option<&'static str>, &'static str), usize> = HashMap::default();
+  for (js_mod, name) in expected_exports {
+    *export_counts.entry((*js_mod, *name)).or_default() += 1;
+    if let Some(js_mod) = js_mod {
+      // the namespace object is a top level export too
+      export_counts.entry((None, *js_mod)).or_insert(0);
+    }
+  }
+  for ((js_mod, name), count) in &export_counts {
+    let export_name = match js_mod {
+      Some(js_mod) => format!("{}.{}", strip_nul(js_mod), strip_nul(name)),
+      None => strip_nul(name).to_owned(),
+    };
+    let is_namespace = js_mod.is_none()
+      && expected_exports
+        .iter()
+        .any(|(m, _)| m.map(strip_nul) == Some(strip_nul(name)));
+    if *count > 1 || (*count == 1 && is_namespace) {
+      problems.push(format!(
+        "`{}` is exported more than once, the later exports overwrite the former",
+        export_name
+      ));
+      continue;
+    }
+    let mut target = exports;
+    if let Some(js_mod) = js_mod {
+      let js_mod = unsafe { CStr::from_bytes_with_nul_unchecked(js_mod.as_bytes()) };
+      unsafe { sys::napi_get_named_property(env, exports, js_mod.as_ptr(), &mut target) };
+    }
+    let mut has_export = false;
+    if !target.is_null() {
+      let name = unsafe { CStr::from_bytes_with_nul_unchecked(name.as_bytes()) };
+      unsafe { sys::napi_has_named_property(env, target, name.as_ptr(), &mut has_export) };
+    }
+    if !has_export {
+      problems.push(format!(
+        "`{}` is missing from the module exports",
+        export_name
+      ));
+    }
+  }
+
+  if problems.is_empty() {
+    return;
+  }
+  problems.sort();
+  problems.dedup();
+  let error = crate::Error::new(
+    crate::Status::GenericFailure,
+    format!(
+      "Failed to register the native module exports:\n{}",
+      problems
+        .iter()
+        .map(|problem| format!("  - {}", problem))
+        .collect::<Vec<_>>()
+        .join("\n")
+    ),
+  );
+  unsafe { JsError::from(error).throw_into(env) };
+}
+
 #[cfg(not(feature = "noop"))]
 pub(crate) unsafe extern "C" fn noop(
   env: sys::napi_env,
@@ -625,15 +871,16 @@ fn create_custom_gc(env: sys::napi_env) {
 
 #[cfg(not(feature = "noop"))]
 unsafe extern "C" fn thread_cleanup(
-  _: sys::napi_env,
+  env: sys::napi_env,
   #[allow(unused_variables)] id: *mut std::ffi::c_void,
   _data: *mut std::ffi::c_void,
 ) {
+  let exports_ref = MODULE_EXPORTS_REF.with(|cell| cell.replace(ptr::null_mut()));
+  if !exports_ref.is_null() {
+    unsafe { sys::napi_delete_reference(env, exports_ref) };
+  }
+  REGISTERED_EXPORTS.with(|cell| cell.borrow_mut().clear());
   if MODULE_COUNT.fetch_sub(1, Ordering::Relaxed) == 1 {
-    #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-    {
-      crate::tokio_runtime::shutdown_async_runtime();
-    }
     crate::bindgen_runtime::REFERENCE_MAP.with(|cell| cell.borrow_mut(|m| m.clear()));
     #[allow(clippy::needless_return)]
     return;
@@ -0,0 +1,106 @@
+//! Named background threads for the `#[napi(thread = "name")]` exports.
+//!
+//! Each name owns one long-lived thread, created lazily by the first call. The calls are executed one by one
+//! in the order they are made, so the native resources which are not thread safe, like a single database handle,
+//! can be used from the same thread without locking.
+#![cfg_attr(feature = "noop", allow(unused))]
+
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{LazyLock, Mutex};
+
+use crate::{bindgen_prelude::Unknown, sys, Env, Error, Result, Status};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static NAMED_THREADS: LazyLock<Mutex<HashMap<&'static str, Sender<Job>>>> =
+  LazyLock::new(Default::default);
+
+fn send_to_thread(name: &'static str, job: Job) -> Result<()> {
+  let mut threads = NAMED_THREADS
+    .lock()
+    .map_err(|_| Error::new(Status::GenericFailure, "Lock NAMED_THREADS failed"))?;
+  if let Some(sender) = threads.get(name) {
+    return sender.send(job).map_err(|_| thread_gone(name));
+  }
+  let (sender, receiver) = mpsc::channel::<Job>();
+  std::thread::Builder::new()
+    .name(format!("napi-{}", name))
+    .spawn(move || {
+      for job in receiver {
+        job();
+      }
+    })
+    .map_err(|err| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to spawn the `{}` thread: {}", name, err),
+      )
+    })?;
+  sender.send(job).map_err(|_| thread_gone(name))?;
+  threads.insert(name, sender);
+  Ok(())
+}
+
+fn thread_gone(name: &str) -> Error {
+  Error::new(
+    Status::GenericFailure,
+    format!("The `{}` thread is not running", name),
+  )
+}
+
+#[cfg(feature = "noop")]
+#[allow(unused)]
+pub fn execute_on_thread<
+  Data: 'static + Send,
+  E: Into<Error>,
+  Work: 'static + Send + FnOnce() -> std::result::Result<Data, E>,
+  Resolver: 'static + Send + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+>(
+  env: sys::napi_env,
+  name: &'static str,
+  work: Work,
+  resolver: Resolver,
+) -> Result<sys::napi_value> {
+  Ok(std::ptr::null_mut())
+}
+
+#[cfg(not(feature = "noop"))]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+/// Run `work` on the thread named `name` and return a `Promise` resolved by `resolver` on the JavaScript thread.
+pub fn execute_on_thread<
+  Data: 'static + Send,
+  E: Into<Error>,
+  Work: 'static + Send + FnOnce() -> std::result::Result<Data, E>,
+  Resolver: 'static + Send + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+>(
+  env: sys::napi_env,
+  name: &'static str,
+  work: Work,
+  resolver: Resolver,
+) -> Result<sys::napi_value> {
+  let env = Env::from_raw(env);
+  let (deferred, promise) = crate::JsDeferred::new(&env)?;
+  let deferred_for_error = deferred.clone();
+  let job = Box::new(move || match catch_unwind(AssertUnwindSafe(work)) {
+    Ok(Ok(v)) => deferred.resolve(move |env| {
+      resolver(env.raw(), v).map(|v| unsafe { Unknown::from_raw_unchecked(env.raw(), v) })
+    }),
+    Ok(Err(err)) => deferred.reject(err.into()),
+    Err(reason) => {
+      let message = if let Some(s) = reason.downcast_ref::<&str>() {
+        s.to_string()
+      } else if let Some(s) = reason.downcast_ref::<String>() {
+        s.clone()
+      } else {
+        format!("Panic in the `{}` thread", name)
+      };
+      deferred.reject(Error::new(Status::GenericFailure, message))
+    }
+  });
+  if let Err(err) = send_to_thread(name, job) {
+    deferred_for_error.reject(err);
+  }
+  Ok(promise.0.value)
+}
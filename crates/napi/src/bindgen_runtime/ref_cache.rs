@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ptr;
+
+use crate::{check_status, sys, Env, Result};
+
+/// The JavaScript values cached by `K` in the instance data of an env, each subsystem has its own type of `K`.
+///
+/// The references are deleted by an env cleanup hook, or when the instance data is dropped if the hook didn't run.
+pub(crate) struct RefCache<K> {
+  env: sys::napi_env,
+  refs: HashMap<K, sys::napi_ref>,
+}
+
+impl<K: Eq + Hash + 'static> RefCache<K> {
+  /// The value cached by `key`, it's `create`d and cached if it's not cached in the env yet.
+  pub(crate) fn get_or_create(
+    env: &Env,
+    key: K,
+    create: impl FnOnce() -> Result<sys::napi_value>,
+  ) -> Result<sys::napi_value> {
+    let cached = env
+      .with_instance(|cache: &mut Self| cache.refs.get(&key).copied())?
+      .flatten();
+    let mut value = ptr::null_mut();
+    if let Some(reference) = cached {
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env.0, reference, &mut value) },
+        "Get the cached value failed"
+      )?;
+      return Ok(value);
+    }
+    // `create` may use the other caches, so nothing is borrowed while it's running
+    value = create()?;
+    let mut reference = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env.0, value, 1, &mut reference) },
+      "Create reference for the cached value failed"
+    )?;
+    let previous =
+      env.with_instance_or_init(|| Self::new(env), |cache| cache.refs.insert(key, reference))?;
+    if let Some(previous) = previous {
+      unsafe { sys::napi_delete_reference(env.0, previous) };
+    }
+    Ok(value)
+  }
+
+  fn new(env: &Env) -> Result<Self> {
+    env.add_env_cleanup_hook(env.0 as usize, |env| {
+      let env = Env::from_raw(env as sys::napi_env);
+      let _ = env.remove_instance::<Self>();
+    })?;
+    Ok(Self {
+      env: env.0,
+      refs: HashMap::new(),
+    })
+  }
+}
+
+impl<K> Drop for RefCache<K> {
+  fn drop(&mut self) {
+    for (_, reference) in self.refs.drain() {
+      unsafe { sys::napi_delete_reference(self.env, reference) };
+    }
+  }
+}
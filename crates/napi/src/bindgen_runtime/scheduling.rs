@@ -0,0 +1,63 @@
+use std::cell::Cell;
+
+use crate::{Env, Error, Result, Status, ValueType};
+
+use super::{FromNapiValue, Function, JsObjectValue, RefCache, Unknown};
+
+#[derive(PartialEq, Eq, Hash)]
+// `setImmediate` of the global object is cached per env
+struct SetImmediate;
+
+impl Env {
+  /// Run `callback` in a microtask, once the current JavaScript call and the microtasks queued before it are done.
+  ///
+  /// An error returned by `callback` is thrown from the microtask, and reported as an uncaught exception.
+  pub fn queue_microtask<F>(&self, callback: F) -> Result<()>
+  where
+    F: 'static + FnOnce(Env) -> Result<()>,
+  {
+    let task = self.scheduled_task(callback)?;
+    self.global_this()?.queue_microtask(task)
+  }
+
+  /// Run `callback` by `setImmediate`, in the check phase of the event loop, after the pending I/O callbacks.
+  ///
+  /// An error returned by `callback` is thrown from the immediate, and reported as an uncaught exception.
+  pub fn set_immediate<F>(&self, callback: F) -> Result<()>
+  where
+    F: 'static + FnOnce(Env) -> Result<()>,
+  {
+    let set_immediate = RefCache::get_or_create(self, SetImmediate, || {
+      let set_immediate: Unknown = self
+        .get_global()?
+        .get_named_property_unchecked("setImmediate")?;
+      if set_immediate.get_type()? != ValueType::Function {
+        return Err(Error::new(
+          Status::GenericFailure,
+          "`setImmediate` is not available in the runtime",
+        ));
+      }
+      Ok(set_immediate.0.value)
+    })?;
+    let set_immediate: Function<Function<(), Unknown>, Unknown> =
+      unsafe { Function::from_napi_value(self.0, set_immediate) }?;
+    set_immediate.call(self.scheduled_task(callback)?)?;
+    Ok(())
+  }
+
+  fn scheduled_task<F>(&self, callback: F) -> Result<Function<'_, (), Unknown<'_>>>
+  where
+    F: 'static + FnOnce(Env) -> Result<()>,
+  {
+    // the function is called once by the scheduler
+    let callback = Cell::new(Some(callback));
+    let task =
+      self.create_function_from_closure::<(), (), _>("scheduledTask", move |ctx| match callback
+        .take()
+      {
+        Some(callback) => callback(*ctx.env),
+        None => Ok(()),
+      })?;
+    unsafe { Function::from_napi_value(self.0, task.value) }
+  }
+}
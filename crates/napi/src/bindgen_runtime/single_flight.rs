@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+use crate::{Error, Result, Status};
+
+type Flight<V> = Arc<OnceCell<std::result::Result<V, (Status, String)>>>;
+
+/// Deduplicate concurrent calls of an expensive async operation.
+///
+/// Concurrent calls of [`SingleFlight::run`] with the same key await one underlying future and share its result.
+/// The result is only shared until the future resolves, the next call with the same key runs the operation again.
+///
+/// ```no_run
+/// use std::sync::LazyLock;
+///
+/// use napi::bindgen_prelude::*;
+///
+/// static LOOKUP: LazyLock<SingleFlight<String, String>> = LazyLock::new(SingleFlight::new);
+///
+/// async fn lookup(key: String) -> Result<String> {
+///   LOOKUP
+///     .run(key.clone(), || async move { Ok(format!("value of {key}")) })
+///     .await
+/// }
+/// ```
+pub struct SingleFlight<K, V> {
+  in_flight: Mutex<HashMap<K, Flight<V>>>,
+}
+
+impl<K, V> Default for SingleFlight<K, V> {
+  fn default() -> Self {
+    Self {
+      in_flight: Mutex::new(HashMap::new()),
+    }
+  }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> SingleFlight<K, V> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Run the future returned by `f`, or wait for the one already running with the same `key`.
+  ///
+  /// If the caller which is running the future is cancelled, one of the waiting callers runs its own `f` instead.
+  pub async fn run<F, Fut>(&self, key: K, f: F) -> Result<V>
+  where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<V>>,
+  {
+    let flight = self
+      .in_flight
+      .lock()
+      .expect("Lock SingleFlight failed")
+      .entry(key.clone())
+      .or_default()
+      .clone();
+    let result = flight
+      .get_or_init(|| async move { f().await.map_err(|err| (err.status, err.reason.clone())) })
+      .await
+      .clone();
+    {
+      let mut in_flight = self.in_flight.lock().expect("Lock SingleFlight failed");
+      if in_flight
+        .get(&key)
+        .is_some_and(|current| Arc::ptr_eq(current, &flight))
+      {
+        in_flight.remove(&key);
+      }
+    }
+    result.map_err(|(status, reason)| Error::new(status, reason))
+  }
+
+  /// Number of keys which have a running future.
+  pub fn in_flight(&self) -> usize {
+    self
+      .in_flight
+      .lock()
+      .expect("Lock SingleFlight failed")
+      .len()
+  }
+}
@@ -0,0 +1,193 @@
+//! Slice borrow tracking, enabled by the `slice-borrow-tracking` feature.
+//!
+//! The `*Slice` types, `BufferSlice` and `DataView` converted from JavaScript borrow the memory of an `ArrayBuffer`
+//! without copying until the native call returns. Detaching the `ArrayBuffer` meanwhile leaves them dangling.
+//! The tracking records these borrows for every synchronous `#[napi]` export, then
+//!
+//! - `ArrayBuffer::detach` returns an error while the `ArrayBuffer` is borrowed by a Rust slice.
+//! - The borrowed `ArrayBuffer`s detached by JavaScript during the call, for example transferred in a callback,
+//!   are reported to the callback set by [`set_slice_borrow_callback`] when the call returns.
+//!
+//! Without the `slice-borrow-tracking` feature, all the functions in this module are no-op.
+
+#[cfg(feature = "slice-borrow-tracking")]
+use std::cell::RefCell;
+#[cfg(feature = "slice-borrow-tracking")]
+use std::sync::RwLock;
+
+#[cfg(feature = "slice-borrow-tracking")]
+use crate::sys;
+
+#[cfg(feature = "slice-borrow-tracking")]
+type SliceBorrowCallback = Box<dyn Fn(&SliceBorrowReport) + Send + Sync>;
+
+#[cfg(feature = "slice-borrow-tracking")]
+static SLICE_BORROW_CALLBACK: RwLock<Option<SliceBorrowCallback>> = RwLock::new(None);
+
+#[cfg(feature = "slice-borrow-tracking")]
+struct SliceBorrow {
+  env: sys::napi_env,
+  array_buffer: sys::napi_value,
+  // start of the `ArrayBuffer` memory, identifies the `ArrayBuffer`
+  base: *const u8,
+  slice_type: &'static str,
+}
+
+#[cfg(feature = "slice-borrow-tracking")]
+struct SliceBorrowFrame {
+  export: &'static str,
+  borrows: Vec<SliceBorrow>,
+}
+
+#[cfg(feature = "slice-borrow-tracking")]
+thread_local! {
+  // One frame for each nested native call, the `napi_value`s are valid until the frame is popped.
+  static SLICE_BORROW_FRAMES: RefCell<Vec<SliceBorrowFrame>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A Rust slice whose `ArrayBuffer` was detached by JavaScript while the slice was borrowed.
+pub struct SliceBorrowReport {
+  /// The `#[napi]` export which borrowed the slice, methods are named as `Class.method`.
+  pub export: &'static str,
+  /// The Rust type of the slice, like `Uint8ArraySlice`.
+  pub slice_type: &'static str,
+}
+
+/// Set the callback receiving the slices whose `ArrayBuffer` was detached by JavaScript, they are not reported
+/// if there is no callback.
+///
+/// The callback is called on the JavaScript thread right after the native call returns,
+/// so it should be cheap and must not call into JavaScript.
+pub fn set_slice_borrow_callback<F>(callback: F)
+where
+  F: Fn(&SliceBorrowReport) + Send + Sync + 'static,
+{
+  #[cfg(feature = "slice-borrow-tracking")]
+  {
+    *SLICE_BORROW_CALLBACK
+      .write()
+      .expect("Write SLICE_BORROW_CALLBACK failed") = Some(Box::new(callback));
+  }
+  #[cfg(not(feature = "slice-borrow-tracking"))]
+  let _ = callback;
+}
+
+/// Remove the callback set by [`set_slice_borrow_callback`], detached slices are not reported anymore.
+pub fn clear_slice_borrow_callback() {
+  #[cfg(feature = "slice-borrow-tracking")]
+  {
+    *SLICE_BORROW_CALLBACK
+      .write()
+      .expect("Write SLICE_BORROW_CALLBACK failed") = None;
+  }
+}
+
+/// Record a slice borrowing the `ArrayBuffer` which memory starts at `base`, in the current native call.
+#[inline]
+#[allow(unused_variables)]
+pub(crate) fn track_slice_borrow(
+  env: crate::sys::napi_env,
+  array_buffer: crate::sys::napi_value,
+  base: *const u8,
+  slice_type: &'static str,
+) {
+  #[cfg(feature = "slice-borrow-tracking")]
+  {
+    if base.is_null() {
+      return;
+    }
+    SLICE_BORROW_FRAMES.with(|frames| {
+      // slices converted outside of the `#[napi]` exports are not tracked
+      if let Some(frame) = frames.borrow_mut().last_mut() {
+        frame.borrows.push(SliceBorrow {
+          env,
+          array_buffer,
+          base,
+          slice_type,
+        });
+      }
+    });
+  }
+}
+
+/// Number of the live Rust slices borrowing the `ArrayBuffer` which memory starts at `base`.
+#[cfg(feature = "napi7")]
+#[inline]
+#[allow(unused_variables)]
+pub(crate) fn slice_borrow_count(base: *const u8) -> usize {
+  #[cfg(feature = "slice-borrow-tracking")]
+  {
+    if base.is_null() {
+      return 0;
+    }
+    SLICE_BORROW_FRAMES.with(|frames| {
+      frames
+        .borrow()
+        .iter()
+        .flat_map(|frame| frame.borrows.iter())
+        .filter(|borrow| borrow.base == base)
+        .count()
+    })
+  }
+  #[cfg(not(feature = "slice-borrow-tracking"))]
+  {
+    0
+  }
+}
+
+#[doc(hidden)]
+/// Track the slices borrowed in a native call until dropped.
+pub struct SliceBorrowScope {
+  #[cfg(feature = "slice-borrow-tracking")]
+  _private: (),
+}
+
+impl SliceBorrowScope {
+  #[inline]
+  pub fn enter(name: &'static str) -> Self {
+    #[cfg(feature = "slice-borrow-tracking")]
+    {
+      SLICE_BORROW_FRAMES.with(|frames| {
+        frames.borrow_mut().push(SliceBorrowFrame {
+          export: name,
+          borrows: Vec::new(),
+        })
+      });
+      Self { _private: () }
+    }
+    #[cfg(not(feature = "slice-borrow-tracking"))]
+    {
+      let _ = name;
+      Self {}
+    }
+  }
+}
+
+#[cfg(feature = "slice-borrow-tracking")]
+impl Drop for SliceBorrowScope {
+  fn drop(&mut self) {
+    let Some(frame) = SLICE_BORROW_FRAMES.with(|frames| frames.borrow_mut().pop()) else {
+      return;
+    };
+    let callback = SLICE_BORROW_CALLBACK
+      .read()
+      .expect("Read SLICE_BORROW_CALLBACK failed");
+    let Some(callback) = callback.as_ref() else {
+      return;
+    };
+    for borrow in frame.borrows {
+      let mut is_detached = false;
+      let status = unsafe {
+        sys::napi_is_detached_arraybuffer(borrow.env, borrow.array_buffer, &mut is_detached)
+      };
+      if status != sys::Status::napi_ok || !is_detached {
+        continue;
+      }
+      callback(&SliceBorrowReport {
+        export: frame.export,
+        slice_type: borrow.slice_type,
+      });
+    }
+  }
+}
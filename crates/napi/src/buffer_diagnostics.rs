@@ -0,0 +1,223 @@
+//! Diagnostics of the external buffers, whose memory is owned by Rust and released by the JavaScript finalizers.
+//!
+//! While it's enabled, every external `Buffer`, `ArrayBuffer` and `TypedArray` created by napi-rs is recorded
+//! until it's finalized, so the leaked buffers can be listed with [`live_buffers`] or [`report`].
+//! Registering the same memory twice panics, since Node.js doesn't allow the external buffers to alias,
+//! see: <https://github.com/nodejs/node/issues/32463#issuecomment-631974747>.
+//!
+//! It's enabled by default in the debug builds. The `NAPI_RS_BUFFER_DIAGNOSTICS` environment variable overrides the default:
+//! `0` disables it, `backtrace` enables it with the creation backtraces, and any other value enables it.
+//! It can also be switched at runtime with [`set_enabled`] and [`set_capture_backtraces`].
+//!
+//! With the `trace-buffer-drops` feature, the lifecycle of the buffers is also reported to the hook installed by
+//! [`set_buffer_event_hook`] as [`BufferEvent`]s, which can feed the metrics of the application.
+
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::thread;
+
+const ENV_VAR: &str = "NAPI_RS_BUFFER_DIAGNOSTICS";
+
+static ENABLED: LazyLock<AtomicBool> = LazyLock::new(|| {
+  AtomicBool::new(match std::env::var(ENV_VAR) {
+    Ok(value) => value != "0",
+    Err(_) => cfg!(debug_assertions),
+  })
+});
+
+static CAPTURE_BACKTRACES: LazyLock<AtomicBool> =
+  LazyLock::new(|| AtomicBool::new(std::env::var(ENV_VAR).is_ok_and(|value| value == "backtrace")));
+
+static LIVE_BUFFERS: LazyLock<Mutex<HashMap<usize, ExternalBuffer>>> =
+  LazyLock::new(Default::default);
+
+fn live() -> MutexGuard<'static, HashMap<usize, ExternalBuffer>> {
+  // the map is consistent after each operation, recover it from a panicked thread
+  LIVE_BUFFERS
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+static BUFFER_EVENT_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// An event in the lifecycle of the buffers, reported to the hook of [`set_buffer_event_hook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BufferEvent {
+  /// An external buffer is created over `len` bytes of memory owned by Rust.
+  Created { address: usize, len: usize },
+  /// The memory owned by Rust of an external buffer, or of a `Buffer` which is never passed to JavaScript, is released.
+  Finalized { address: usize },
+  /// A `Buffer` received from JavaScript is referenced by Rust.
+  Referenced { address: usize, len: usize },
+  /// A referenced `Buffer` is dropped in Rust, and `ref_count` references are left.
+  Unreferenced { address: usize, ref_count: u32 },
+  /// The last reference is deleted off the JavaScript thread, so the deletion is queued to the post finalizer.
+  ReferenceDeletionQueued { address: usize },
+  /// The runtime refused an external buffer, so the data is copied into a buffer allocated by JavaScript.
+  FallbackCopy,
+}
+
+/// Report the [`BufferEvent`]s to `hook`, replacing the previous one.
+///
+/// The `hook` is called synchronously where the event happens, which may be a finalizer or another thread,
+/// so it should only record the event, like increasing a counter.
+/// The events are only reported with the `trace-buffer-drops` feature, which is enabled by default.
+pub fn set_buffer_event_hook(hook: fn(BufferEvent)) {
+  BUFFER_EVENT_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Stop reporting the [`BufferEvent`]s.
+pub fn remove_buffer_event_hook() {
+  BUFFER_EVENT_HOOK.store(ptr::null_mut(), Ordering::Release);
+}
+
+#[cfg(feature = "trace-buffer-drops")]
+#[inline]
+pub(crate) fn emit(event: BufferEvent) {
+  let hook = BUFFER_EVENT_HOOK.load(Ordering::Acquire);
+  if !hook.is_null() {
+    // SAFETY: only the `fn(BufferEvent)` of `set_buffer_event_hook` are stored
+    let hook = unsafe { std::mem::transmute::<*mut (), fn(BufferEvent)>(hook) };
+    hook(event);
+  }
+}
+
+#[cfg(not(feature = "trace-buffer-drops"))]
+#[inline]
+pub(crate) fn emit(_event: BufferEvent) {}
+
+/// A live external buffer.
+#[derive(Debug, Clone)]
+pub struct ExternalBuffer {
+  /// The address of the memory.
+  pub address: usize,
+  /// The size in bytes.
+  pub len: usize,
+  /// The name, or the id if it's unnamed, of the thread which created the buffer.
+  pub thread: String,
+  /// Where the buffer was created, if the backtraces are captured.
+  pub backtrace: Option<Arc<Backtrace>>,
+}
+
+impl fmt::Display for ExternalBuffer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{:#x} ({} bytes) created on thread {}",
+      self.address, self.len, self.thread
+    )?;
+    if let Some(backtrace) = &self.backtrace {
+      write!(f, "\n{}", backtrace)?;
+    }
+    Ok(())
+  }
+}
+
+/// Whether the external buffers are recorded.
+pub fn is_enabled() -> bool {
+  ENABLED.load(Ordering::Relaxed)
+}
+
+/// Start or stop recording the external buffers.
+///
+/// Disabling forgets the recorded buffers, and the buffers created before enabling are not listed.
+pub fn set_enabled(enabled: bool) {
+  ENABLED.store(enabled, Ordering::Relaxed);
+  if !enabled {
+    live().clear();
+  }
+}
+
+/// Whether a backtrace is captured for each recorded buffer.
+pub fn is_capturing_backtraces() -> bool {
+  CAPTURE_BACKTRACES.load(Ordering::Relaxed)
+}
+
+/// Capture a backtrace for each recorded buffer, which is slow but shows where the leaked buffers come from.
+pub fn set_capture_backtraces(capture: bool) {
+  CAPTURE_BACKTRACES.store(capture, Ordering::Relaxed);
+}
+
+/// The external buffers which are not finalized yet, sorted by address.
+pub fn live_buffers() -> Vec<ExternalBuffer> {
+  let mut buffers = live().values().cloned().collect::<Vec<_>>();
+  buffers.sort_by_key(|buffer| buffer.address);
+  buffers
+}
+
+/// A human readable summary of the live external buffers, one per line, followed by their backtraces if captured.
+pub fn report() -> String {
+  let buffers = live_buffers();
+  let total = buffers.iter().map(|buffer| buffer.len).sum::<usize>();
+  let mut report = format!(
+    "{} live external buffers, {} bytes in total\n",
+    buffers.len(),
+    total
+  );
+  for buffer in buffers {
+    let _ = writeln!(report, "{}", buffer);
+  }
+  report
+}
+
+/// Record the memory of a new external buffer.
+///
+/// # Panics
+///
+/// If the memory is already used by another live external buffer.
+#[inline]
+pub fn register_backing_ptr(ptr: *mut u8, len: usize) {
+  // 0-length buffers use NULL
+  if ptr.is_null() {
+    return;
+  }
+  emit(BufferEvent::Created {
+    address: ptr as usize,
+    len,
+  });
+  if !is_enabled() {
+    return;
+  }
+  let current = thread::current();
+  let buffer = ExternalBuffer {
+    address: ptr as usize,
+    len,
+    thread: match current.name() {
+      Some(name) => name.to_owned(),
+      None => format!("{:?}", current.id()),
+    },
+    backtrace: is_capturing_backtraces().then(|| Arc::new(Backtrace::force_capture())),
+  };
+  let mut live = live();
+  if let Some(existing) = live.get(&buffer.address) {
+    let existing = existing.to_string();
+    drop(live);
+    panic!(
+      "Share the same data between different buffers is not allowed, \
+                    see: https://github.com/nodejs/node/issues/32463#issuecomment-631974747\n\
+                    The data is used by the buffer at {}",
+      existing
+    );
+  }
+  live.insert(buffer.address, buffer);
+}
+
+/// Forget the memory of a finalized external buffer.
+#[inline]
+pub fn unregister_backing_ptr(ptr: *mut u8) {
+  if ptr.is_null() {
+    return;
+  }
+  emit(BufferEvent::Finalized {
+    address: ptr as usize,
+  });
+  if !is_enabled() {
+    return;
+  }
+  live().remove(&(ptr as usize));
+}
@@ -0,0 +1,350 @@
+//! A byte queue from a Rust producer to a JavaScript consumer, with backpressure.
+//!
+//! [`ByteSender::send`] returns a future which waits while `high_water_mark` bytes are queued and not received yet,
+//! so a fast producer is suspended until JavaScript catches up.
+//! The [`ByteReceiver`] is converted into a JavaScript async iterator of `Buffer`s,
+//! or feeds the `Buffer`s to a JavaScript callback with [`ByteReceiver::for_each`].
+//!
+//! ```
+//! #[napi(ts_return_type = "AsyncIterableIterator<Buffer>")]
+//! pub fn watch_log() -> ByteReceiver {
+//!   let (sender, receiver) = byte_channel::channel(64 * 1024);
+//!   napi::bindgen_prelude::spawn(async move {
+//!     while let Some(line) = next_line().await {
+//!       if sender.send(line).await.is_err() {
+//!         // the iteration is stopped in JavaScript
+//!         break;
+//!       }
+//!     }
+//!   });
+//!   receiver
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+use crate::{
+  bindgen_prelude::{
+    AsyncBlock, AsyncBlockBuilder, Buffer, FromNapiValue, Function, JsObjectValue, Object,
+    ToNapiValue, TypeName, Unknown, ValidateNapiValue,
+  },
+  sys,
+  threadsafe_function::ThreadsafeFunctionCallMode,
+  Env, Error, JsValue, Result, Status, ValueType,
+};
+
+/// Create a `ByteChannel` which suspends the senders once `high_water_mark` bytes are queued.
+///
+/// A chunk is always accepted when the queue is empty, even if it's larger than `high_water_mark`.
+pub fn channel(high_water_mark: usize) -> (ByteSender, ByteReceiver) {
+  let shared = Arc::new(Shared {
+    state: Mutex::new(State {
+      queue: VecDeque::new(),
+      buffered: 0,
+      senders: 1,
+      error: None,
+      receiver_closed: false,
+      producers: Vec::new(),
+      consumers: Vec::new(),
+    }),
+    high_water_mark,
+  });
+  (
+    ByteSender {
+      shared: shared.clone(),
+    },
+    ByteReceiver { shared },
+  )
+}
+
+struct Shared {
+  state: Mutex<State>,
+  high_water_mark: usize,
+}
+
+impl Shared {
+  fn lock(&self) -> MutexGuard<'_, State> {
+    // the state is consistent after each operation, recover it from a panicked thread
+    self
+      .state
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+struct State {
+  queue: VecDeque<Vec<u8>>,
+  buffered: usize,
+  senders: usize,
+  // set by `ByteSender::abort`
+  error: Option<String>,
+  receiver_closed: bool,
+  producers: Vec<Waker>,
+  consumers: Vec<Waker>,
+}
+
+fn wake_all(wakers: &mut Vec<Waker>) {
+  for waker in wakers.drain(..) {
+    waker.wake();
+  }
+}
+
+/// The sending half of a `ByteChannel`, it can be cloned to send from multiple producers.
+///
+/// The channel ends after all the `ByteSender`s are dropped.
+pub struct ByteSender {
+  shared: Arc<Shared>,
+}
+
+impl ByteSender {
+  /// Queue `chunk`, waiting while the queue is at the high water mark.
+  ///
+  /// It's an error if the receiver is closed, for example the JavaScript iteration is stopped with `break`.
+  pub fn send<D: Into<Vec<u8>>>(&self, chunk: D) -> SendChunk<'_> {
+    SendChunk {
+      sender: self,
+      chunk: Some(chunk.into()),
+    }
+  }
+
+  /// Fail the channel with `reason`, the queued chunks are discarded and the receiver gets the error.
+  pub fn abort<R: Into<String>>(&self, reason: R) {
+    let mut state = self.shared.lock();
+    state.error.get_or_insert(reason.into());
+    state.queue.clear();
+    state.buffered = 0;
+    wake_all(&mut state.consumers);
+  }
+
+  /// Whether the receiver is closed, the chunks sent after it are rejected.
+  pub fn is_closed(&self) -> bool {
+    self.shared.lock().receiver_closed
+  }
+}
+
+impl Clone for ByteSender {
+  fn clone(&self) -> Self {
+    self.shared.lock().senders += 1;
+    Self {
+      shared: self.shared.clone(),
+    }
+  }
+}
+
+impl Drop for ByteSender {
+  fn drop(&mut self) {
+    let mut state = self.shared.lock();
+    state.senders -= 1;
+    if state.senders == 0 {
+      wake_all(&mut state.consumers);
+    }
+  }
+}
+
+/// The future returned by [`ByteSender::send`].
+pub struct SendChunk<'a> {
+  sender: &'a ByteSender,
+  chunk: Option<Vec<u8>>,
+}
+
+impl Future for SendChunk<'_> {
+  type Output = Result<()>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let shared = self.sender.shared.clone();
+    let mut state = shared.lock();
+    if state.receiver_closed {
+      return Poll::Ready(Err(Error::new(
+        Status::Closing,
+        "The receiver of the ByteChannel is closed",
+      )));
+    }
+    if state.buffered > 0 && state.buffered >= shared.high_water_mark {
+      state.producers.push(cx.waker().clone());
+      return Poll::Pending;
+    }
+    let Some(chunk) = self.chunk.take() else {
+      return Poll::Ready(Ok(()));
+    };
+    state.buffered += chunk.len();
+    state.queue.push_back(chunk);
+    wake_all(&mut state.consumers);
+    Poll::Ready(Ok(()))
+  }
+}
+
+/// The receiving half of a `ByteChannel`.
+///
+/// Returned to JavaScript, it's an async iterator of `Buffer`s, which can be consumed by `for await`.
+/// Stopping the iteration or garbage collecting the iterator closes the receiver.
+pub struct ByteReceiver {
+  shared: Arc<Shared>,
+}
+
+impl ByteReceiver {
+  /// Receive the next chunk, `None` after all the senders are dropped.
+  pub fn recv(&self) -> RecvChunk<'_> {
+    RecvChunk { receiver: self }
+  }
+
+  /// Close the receiver, the queued chunks are discarded and the senders get an error.
+  pub fn close(&self) {
+    let mut state = self.shared.lock();
+    state.receiver_closed = true;
+    state.queue.clear();
+    state.buffered = 0;
+    wake_all(&mut state.producers);
+  }
+
+  /// Call `callback` with each chunk in JavaScript, the next chunk is received after the `callback` returns.
+  ///
+  /// The returned `Promise` resolves after the channel ends, and rejects if it's aborted or the `callback` throws,
+  /// the receiver is closed in that case.
+  pub fn for_each<Return: FromNapiValue + 'static>(
+    self,
+    env: &Env,
+    callback: Function<'_, Buffer, Return>,
+  ) -> Result<AsyncBlock<()>> {
+    let callback = callback.build_threadsafe_function::<Buffer>().build()?;
+    AsyncBlockBuilder::new(async move {
+      while let Some(chunk) = self.recv().await? {
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        let status = callback.call_with_return_value(
+          Buffer::from(chunk),
+          ThreadsafeFunctionCallMode::NonBlocking,
+          move |result, _| {
+            // the `Error` from JavaScript can only be dropped on the JavaScript thread
+            let _ = sender.send(result.map(drop).map_err(|err| err.reason.clone()));
+            Ok(())
+          },
+        );
+        if status != Status::Ok {
+          self.close();
+          return Err(Error::new(
+            status,
+            "Failed to call the callback of ByteChannel",
+          ));
+        }
+        match receiver.await {
+          Ok(Ok(())) => {}
+          Ok(Err(reason)) => {
+            self.close();
+            return Err(Error::new(Status::GenericFailure, reason));
+          }
+          Err(_) => {
+            self.close();
+            return Err(Error::new(
+              Status::GenericFailure,
+              "The callback of ByteChannel was not called",
+            ));
+          }
+        }
+      }
+      Ok(())
+    })
+    .build(env)
+  }
+}
+
+impl Drop for ByteReceiver {
+  fn drop(&mut self) {
+    self.close();
+  }
+}
+
+/// The future returned by [`ByteReceiver::recv`].
+pub struct RecvChunk<'a> {
+  receiver: &'a ByteReceiver,
+}
+
+impl Future for RecvChunk<'_> {
+  type Output = Result<Option<Vec<u8>>>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let mut state = self.receiver.shared.lock();
+    if let Some(chunk) = state.queue.pop_front() {
+      state.buffered -= chunk.len();
+      wake_all(&mut state.producers);
+      return Poll::Ready(Ok(Some(chunk)));
+    }
+    if let Some(reason) = &state.error {
+      return Poll::Ready(Err(Error::new(Status::GenericFailure, reason.clone())));
+    }
+    if state.senders == 0 || state.receiver_closed {
+      return Poll::Ready(Ok(None));
+    }
+    state.consumers.push(cx.waker().clone());
+    Poll::Pending
+  }
+}
+
+impl TypeName for ByteReceiver {
+  fn type_name() -> &'static str {
+    "AsyncIterableIterator<Buffer>"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for ByteReceiver {}
+
+impl ToNapiValue for ByteReceiver {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let env = Env::from_raw(env);
+    // the functions share the receiver, it's closed after all of them are garbage collected
+    let receiver = Arc::new(val);
+    let mut iterator = Object::new(&env)?;
+
+    let next_receiver = receiver.clone();
+    let next = env.create_function_from_closure::<(), sys::napi_value, _>("next", move |ctx| {
+      let receiver = next_receiver.clone();
+      let promise = ctx
+        .env
+        .spawn_future_with_callback(async move { receiver.recv().await }, iterator_result)?;
+      Ok(promise.inner)
+    })?;
+    let return_receiver = receiver;
+    let return_ =
+      env.create_function_from_closure::<(), sys::napi_value, _>("return", move |ctx| {
+        return_receiver.close();
+        let promise = ctx
+          .env
+          .spawn_future_with_callback(async { Ok(None) }, iterator_result)?;
+        Ok(promise.inner)
+      })?;
+    let async_iterator = env
+      .create_function_from_closure::<(), sys::napi_value, _>("asyncIterator", |ctx| {
+        Ok(ctx.this::<Unknown>()?.raw())
+      })?;
+    iterator.set_named_property("next", next)?;
+    iterator.set_named_property("return", return_)?;
+    let symbol = env
+      .get_global()?
+      .get_named_property_unchecked::<Object>("Symbol")?
+      .get_named_property_unchecked::<Unknown>("asyncIterator")?;
+    iterator.set_property(symbol, async_iterator)?;
+    Ok(iterator.raw())
+  }
+}
+
+// The `{ value, done }` of the async iterator
+fn iterator_result(env: Env, chunk: Option<Vec<u8>>) -> Result<Object<'static>> {
+  let mut result = Object::new(&env)?;
+  match chunk {
+    Some(chunk) => {
+      result.set("value", Buffer::from(chunk))?;
+      result.set("done", false)?;
+    }
+    None => {
+      result.set("value", ())?;
+      result.set("done", true)?;
+    }
+  }
+  Ok(result)
+}
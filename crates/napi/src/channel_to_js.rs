@@ -0,0 +1,194 @@
+//! Deliver the items of a tokio `mpsc` channel to a JavaScript callback.
+//!
+//! The drain loop runs on the async runtime, and the `ThreadsafeFunction` calling the callback is released once
+//! the channel ends, so the process can exit after the last sender is dropped:
+//!
+//! ```
+//! #[napi(ts_return_type = "Promise<void>")]
+//! pub fn watch(callback: Function<String, ()>) -> Result<AsyncBlock<()>> {
+//!   let (sender, receiver) = tokio::sync::mpsc::channel(16);
+//!   napi::bindgen_prelude::spawn(async move {
+//!     while let Some(path) = next_change().await {
+//!       if sender.send(path).await.is_err() {
+//!         // the callback threw
+//!         break;
+//!       }
+//!     }
+//!   });
+//!   channel_to_js(receiver, callback, ChannelToJsOptions::default())
+//! }
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{mpsc::Receiver, Semaphore};
+
+use crate::{
+  bindgen_prelude::{AsyncBlock, AsyncBlockBuilder},
+  bindgen_runtime::{FromNapiValue, Function, JsValuesTupleIntoVec},
+  threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+  Env, Error, Result, Status,
+};
+
+/// The options of [`channel_to_js`] and [`channel_to_js_batched`].
+#[derive(Clone, Copy, Debug)]
+pub struct ChannelToJsOptions {
+  /// The calls which are queued or running at the same time, 1 by default.
+  ///
+  /// No more items are received from the channel while the limit is reached, so a bounded channel suspends
+  /// its senders until JavaScript catches up.
+  pub max_in_flight: usize,
+  /// The maximum number of the items passed to each call of `channel_to_js_batched`, 64 by default.
+  pub max_batch_size: usize,
+}
+
+impl Default for ChannelToJsOptions {
+  fn default() -> Self {
+    Self {
+      max_in_flight: 1,
+      max_batch_size: 64,
+    }
+  }
+}
+
+/// Call `callback` with each item of `receiver`, in the order they are sent.
+///
+/// The returned `Promise` resolves after all the senders are dropped and the callbacks of the items return.
+/// If `callback` throws, the receiver is closed, so the senders get an error, and the `Promise` rejects.
+pub fn channel_to_js<T, Return>(
+  mut receiver: Receiver<T>,
+  callback: Function<'_, T, Return>,
+  options: ChannelToJsOptions,
+) -> Result<AsyncBlock<()>>
+where
+  T: 'static + Send + JsValuesTupleIntoVec,
+  Return: 'static + FromNapiValue,
+{
+  let env = Env::from_raw(callback.env);
+  let drain = Drain::new(callback.build_threadsafe_function::<T>().build()?, options);
+  AsyncBlockBuilder::new(async move {
+    while let Some(item) = receiver.recv().await {
+      if let Err(err) = drain.deliver(item).await {
+        receiver.close();
+        return Err(err);
+      }
+    }
+    drain.finish().await
+  })
+  .build(&env)
+}
+
+/// Like [`channel_to_js`], but `callback` is called with arrays of the items which are queued in the channel,
+/// at most `max_batch_size` items per call.
+pub fn channel_to_js_batched<T, Return>(
+  mut receiver: Receiver<T>,
+  callback: Function<'_, Vec<T>, Return>,
+  options: ChannelToJsOptions,
+) -> Result<AsyncBlock<()>>
+where
+  T: 'static + Send,
+  Vec<T>: JsValuesTupleIntoVec,
+  Return: 'static + FromNapiValue,
+{
+  let env = Env::from_raw(callback.env);
+  let max_batch_size = options.max_batch_size.max(1);
+  let drain = Drain::new(
+    callback.build_threadsafe_function::<Vec<T>>().build()?,
+    options,
+  );
+  AsyncBlockBuilder::new(async move {
+    while let Some(item) = receiver.recv().await {
+      let mut batch = vec![item];
+      while batch.len() < max_batch_size {
+        match receiver.try_recv() {
+          Ok(item) => batch.push(item),
+          Err(_) => break,
+        }
+      }
+      if let Err(err) = drain.deliver(batch).await {
+        receiver.close();
+        return Err(err);
+      }
+    }
+    drain.finish().await
+  })
+  .build(&env)
+}
+
+struct Drain<Args: 'static + JsValuesTupleIntoVec, Return: 'static + FromNapiValue> {
+  callback: ThreadsafeFunction<Args, Return, Args, Status, false>,
+  in_flight: Arc<Semaphore>,
+  max_in_flight: u32,
+  // the message of the first error thrown by the callback
+  failure: Arc<Mutex<Option<String>>>,
+}
+
+impl<Args: 'static + JsValuesTupleIntoVec, Return: 'static + FromNapiValue> Drain<Args, Return> {
+  fn new(
+    callback: ThreadsafeFunction<Args, Return, Args, Status, false>,
+    options: ChannelToJsOptions,
+  ) -> Self {
+    let max_in_flight = options.max_in_flight.clamp(1, u32::MAX as usize) as u32;
+    Self {
+      callback,
+      in_flight: Arc::new(Semaphore::new(max_in_flight as usize)),
+      max_in_flight,
+      failure: Arc::new(Mutex::new(None)),
+    }
+  }
+
+  /// Wait for a free slot, and queue the call of the callback
+  async fn deliver(&self, args: Args) -> Result<()> {
+    let permit = self
+      .in_flight
+      .clone()
+      .acquire_owned()
+      .await
+      .map_err(|_| Error::new(Status::Closing, "The channel_to_js is closed"))?;
+    self.check()?;
+    let failure = self.failure.clone();
+    let status = self.callback.call_with_return_value(
+      args,
+      ThreadsafeFunctionCallMode::NonBlocking,
+      move |result, _| {
+        if let Err(err) = result {
+          // the `Error` from JavaScript can only be dropped on the JavaScript thread
+          failure
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get_or_insert_with(|| err.reason.clone());
+        }
+        drop(permit);
+        Ok(())
+      },
+    );
+    if status != Status::Ok {
+      return Err(Error::new(
+        status,
+        "Failed to call the callback of channel_to_js",
+      ));
+    }
+    Ok(())
+  }
+
+  /// Wait for the queued calls to return
+  async fn finish(self) -> Result<()> {
+    let _permits = self
+      .in_flight
+      .acquire_many(self.max_in_flight)
+      .await
+      .map_err(|_| Error::new(Status::Closing, "The channel_to_js is closed"))?;
+    self.check()
+  }
+
+  fn check(&self) -> Result<()> {
+    match &*self
+      .failure
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+    {
+      Some(reason) => Err(Error::new(Status::GenericFailure, reason.clone())),
+      None => Ok(()),
+    }
+  }
+}
@@ -1,3 +1,8 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::{Env, Error, Result, Status};
+
 pub(crate) struct CleanupEnvHookData<T: 'static> {
   pub(crate) data: T,
   pub(crate) hook: Box<dyn FnOnce(T)>,
@@ -7,3 +12,195 @@ pub(crate) struct CleanupEnvHookData<T: 'static> {
 /// And used by `Env::remove_env_cleanup_hook`
 #[derive(Clone, Copy)]
 pub struct CleanupEnvHook<T: 'static>(pub(crate) *mut CleanupEnvHookData<T>);
+
+/// The phase of the env teardown in which a hook of `Env::add_phased_cleanup_hook` runs.
+///
+/// The phases run in order, and the hooks of a phase run in the reverse order of their registration,
+/// like the hooks of `Env::add_env_cleanup_hook`. napi-rs shuts its tokio runtime down in the `Last` phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CleanupPhase {
+  /// Before the threadsafe functions created by napi-rs in the env are torn down, so the hook can still call them.
+  BeforeTsfnTeardown,
+  #[default]
+  Default,
+  /// After the hooks of the other phases, for the shared resources the other hooks may still use.
+  Last,
+}
+
+/// Created by `Env::add_phased_cleanup_hook`
+/// And used by `Env::remove_phased_cleanup_hook`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhasedCleanupHook {
+  env: usize,
+  id: u64,
+}
+
+struct PhasedHooks {
+  // the hook running the `BeforeTsfnTeardown` hooks, which is moved after the threadsafe functions created later
+  before_tsfn_teardown: Option<CleanupEnvHook<usize>>,
+  next_id: u64,
+  hooks: Vec<PhasedHook>,
+}
+
+struct PhasedHook {
+  phase: CleanupPhase,
+  id: u64,
+  hook: Box<dyn FnOnce()>,
+}
+
+thread_local! {
+  static PHASED_HOOKS: RefCell<HashMap<usize, PhasedHooks>> = RefCell::new(HashMap::new());
+}
+
+impl Env {
+  /// Registers `cleanup_fn` to be run with `cleanup_data` in `phase` of the teardown of the current Node.js environment.
+  ///
+  /// The hooks of the `Default` and `Last` phases run from a single hook of `Env::add_env_cleanup_hook` registered
+  /// when the module is loaded, so they run after the hooks registered by `Env::add_env_cleanup_hook`.
+  /// The hooks of the `BeforeTsfnTeardown` phase run from another hook, which is moved whenever napi-rs creates a
+  /// threadsafe function in the env, so only the envs with such hooks pay for it.
+  pub fn add_phased_cleanup_hook<T, F>(
+    &self,
+    phase: CleanupPhase,
+    cleanup_data: T,
+    cleanup_fn: F,
+  ) -> Result<PhasedCleanupHook>
+  where
+    T: 'static,
+    F: 'static + FnOnce(T),
+  {
+    let env = self.0 as usize;
+    if !PHASED_HOOKS.with(|hooks| hooks.borrow().contains_key(&env)) {
+      register_phased_hooks(self)?;
+    }
+    let needs_before_tsfn_teardown = phase == CleanupPhase::BeforeTsfnTeardown
+      && PHASED_HOOKS.with(|hooks| {
+        hooks
+          .borrow()
+          .get(&env)
+          .is_some_and(|phased| phased.before_tsfn_teardown.is_none())
+      });
+    if needs_before_tsfn_teardown {
+      let hook = self.add_env_cleanup_hook(env, run_before_tsfn_teardown_hooks)?;
+      set_before_tsfn_teardown(env, hook);
+    }
+    let id = PHASED_HOOKS.with(|hooks| {
+      let mut hooks = hooks.borrow_mut();
+      let phased = hooks.get_mut(&env).expect("PhasedHooks is just registered");
+      let id = phased.next_id;
+      phased.next_id += 1;
+      phased.hooks.push(PhasedHook {
+        phase,
+        id,
+        hook: Box::new(move || cleanup_fn(cleanup_data)),
+      });
+      id
+    });
+    Ok(PhasedCleanupHook { env, id })
+  }
+
+  /// Removes the hook registered by `Env::add_phased_cleanup_hook` before it runs.
+  pub fn remove_phased_cleanup_hook(&self, hook: PhasedCleanupHook) -> Result<()> {
+    let removed = PHASED_HOOKS.with(|hooks| {
+      let mut hooks = hooks.borrow_mut();
+      let phased = hooks.get_mut(&hook.env)?;
+      let index = phased
+        .hooks
+        .iter()
+        .position(|phased_hook| phased_hook.id == hook.id)?;
+      Some(phased.hooks.remove(index))
+    });
+    match removed {
+      Some(removed) => {
+        drop(removed);
+        Ok(())
+      }
+      None => Err(Error::new(
+        Status::InvalidArg,
+        "The phased cleanup hook is already run or removed".to_owned(),
+      )),
+    }
+  }
+}
+
+/// Registers the hook running the phased hooks of `env`, once when the module is loaded.
+pub(crate) fn register_phased_hooks(env: &Env) -> Result<()> {
+  let key = env.0 as usize;
+  if PHASED_HOOKS.with(|hooks| hooks.borrow().contains_key(&key)) {
+    return Ok(());
+  }
+  env.add_env_cleanup_hook(key, run_phased_hooks)?;
+  PHASED_HOOKS.with(|hooks| {
+    hooks.borrow_mut().insert(
+      key,
+      PhasedHooks {
+        before_tsfn_teardown: None,
+        next_id: 0,
+        hooks: Vec::new(),
+      },
+    )
+  });
+  Ok(())
+}
+
+fn set_before_tsfn_teardown(env: usize, hook: CleanupEnvHook<usize>) {
+  PHASED_HOOKS.with(|hooks| {
+    if let Some(phased) = hooks.borrow_mut().get_mut(&env) {
+      phased.before_tsfn_teardown = Some(hook);
+    }
+  });
+}
+
+/// Moves the hook which runs the `BeforeTsfnTeardown` hooks of `env` after the threadsafe function which is just created,
+/// the env cleanup hooks run in the reverse order of their registration.
+#[cfg(feature = "napi4")]
+pub(crate) fn on_threadsafe_function_created(env: crate::sys::napi_env) {
+  let Some(hook) = PHASED_HOOKS.with(|hooks| {
+    hooks
+      .borrow()
+      .get(&(env as usize))
+      .and_then(|phased| phased.before_tsfn_teardown)
+  }) else {
+    return;
+  };
+  let env = Env::from_raw(env);
+  if env.remove_env_cleanup_hook(hook).is_err() {
+    return;
+  }
+  // the hook data is leaked by `add_env_cleanup_hook`, and only freed when it runs
+  drop(unsafe { Box::from_raw(hook.0) });
+  if let Ok(hook) = env.add_env_cleanup_hook(env.0 as usize, run_before_tsfn_teardown_hooks) {
+    set_before_tsfn_teardown(env.0 as usize, hook);
+  }
+}
+
+fn run_before_tsfn_teardown_hooks(env: usize) {
+  let hooks = PHASED_HOOKS.with(|hooks| {
+    let mut hooks = hooks.borrow_mut();
+    let Some(phased) = hooks.get_mut(&env) else {
+      return Vec::new();
+    };
+    phased.before_tsfn_teardown = None;
+    let (before_tsfn_teardown, others) = std::mem::take(&mut phased.hooks)
+      .into_iter()
+      .partition(|phased_hook| phased_hook.phase == CleanupPhase::BeforeTsfnTeardown);
+    phased.hooks = others;
+    before_tsfn_teardown
+  });
+  run_in_order(hooks);
+}
+
+fn run_phased_hooks(env: usize) {
+  let Some(phased) = PHASED_HOOKS.with(|hooks| hooks.borrow_mut().remove(&env)) else {
+    return;
+  };
+  run_in_order(phased.hooks);
+}
+
+fn run_in_order(mut hooks: Vec<PhasedHook>) {
+  // the later registered hooks of a phase run first
+  hooks.sort_by(|a, b| a.phase.cmp(&b.phase).then(b.id.cmp(&a.id)));
+  for phased_hook in hooks {
+    (phased_hook.hook)();
+  }
+}
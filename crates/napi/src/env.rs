@@ -1,7 +1,9 @@
 #![allow(deprecated)]
 
 #[cfg(any(feature = "compat-mode", feature = "napi6"))]
-use std::any::{type_name, TypeId};
+use std::any::{type_name, Any, TypeId};
+#[cfg(feature = "napi6")]
+use std::cell::{Cell, RefCell};
 use std::convert::TryInto;
 use std::ffi::CString;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
@@ -10,17 +12,19 @@ use std::future::Future;
 use std::mem;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
+#[cfg(feature = "napi6")]
+use std::ptr::NonNull;
 
 #[cfg(feature = "serde-json")]
 use serde::de::DeserializeOwned;
 #[cfg(feature = "serde-json")]
 use serde::Serialize;
 
-#[cfg(all(debug_assertions, not(windows)))]
+#[cfg(not(feature = "minimal"))]
 use crate::bindgen_prelude::unregister_backing_ptr;
 
-#[cfg(feature = "napi8")]
-use crate::async_cleanup_hook::AsyncCleanupHook;
+#[cfg(feature = "compat-mode")]
+use crate::bindgen_runtime::external_buffers_disallowed;
 #[cfg(all(feature = "napi6", feature = "compat-mode"))]
 use crate::bindgen_runtime::u128_with_sign_to_napi_value;
 #[cfg(feature = "napi6")]
@@ -47,13 +51,25 @@ use crate::{
   js_values::*,
   sys,
   task::Task,
-  Error, ExtendedErrorInfo, NodeVersion, Result, Status, ValueType,
+  Error, ExtendedErrorInfo, Feature, NodeVersion, Result, Status, ValueType,
 };
 
 pub type Callback = unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value;
 
 pub(crate) static EMPTY_VEC: Vec<u8> = vec![];
 
+#[derive(Debug, Clone, Copy, Default)]
+/// The options of [`Env::run_script_with_options`].
+///
+/// `napi_run_script` doesn't accept a script origin, so the filename is attached with a `//# sourceURL=` comment,
+/// and the lines are offset by prepending the blank lines, which V8 reports in the stack traces the same way.
+pub struct ScriptOptions<'a> {
+  /// The filename reported in the stack traces of the script, it can't contain the whitespaces.
+  pub filename: Option<&'a str>,
+  /// The number of lines before the first line of the script, for a script embedded in another file.
+  pub line_offset: u32,
+}
+
 #[derive(Clone, Copy)]
 /// `Env` is used to represent a context that the underlying N-API implementation can use to persist VM-specific state.
 ///
@@ -228,6 +244,89 @@ impl Env {
     unsafe { JsString::from_napi_value(self.0, raw_value) }
   }
 
+  #[cfg(feature = "napi10")]
+  /// Create a latin1 string backed by `chars` without copying, if the engine supports external strings.
+  pub fn create_external_string_latin1(&self, chars: &'static [u8]) -> Result<JsString<'_>> {
+    let mut raw_value = ptr::null_mut();
+    let mut copied = false;
+    check_status!(unsafe {
+      sys::node_api_create_external_string_latin1(
+        self.0,
+        chars.as_ptr().cast(),
+        chars.len() as isize,
+        None,
+        ptr::null_mut(),
+        &mut raw_value,
+        &mut copied,
+      )
+    })?;
+    unsafe { JsString::from_napi_value(self.0, raw_value) }
+  }
+
+  #[cfg(feature = "napi10")]
+  /// Create a UTF-16 string backed by `chars` without copying, if the engine supports external strings.
+  pub fn create_external_string_utf16(&self, chars: &'static [u16]) -> Result<JsString<'_>> {
+    let mut raw_value = ptr::null_mut();
+    let mut copied = false;
+    check_status!(unsafe {
+      sys::node_api_create_external_string_utf16(
+        self.0,
+        chars.as_ptr(),
+        chars.len() as isize,
+        None,
+        ptr::null_mut(),
+        &mut raw_value,
+        &mut copied,
+      )
+    })?;
+    unsafe { JsString::from_napi_value(self.0, raw_value) }
+  }
+
+  #[cfg(feature = "napi10")]
+  /// Create an internalized string, which is faster to use as a property key than the one created by `create_string`.
+  pub fn create_property_key(&self, key: &str) -> Result<JsString<'_>> {
+    let mut raw_value = ptr::null_mut();
+    check_status!(unsafe {
+      sys::node_api_create_property_key_utf8(
+        self.0,
+        key.as_ptr().cast(),
+        key.len() as isize,
+        &mut raw_value,
+      )
+    })?;
+    unsafe { JsString::from_napi_value(self.0, raw_value) }
+  }
+
+  #[cfg(feature = "napi10")]
+  /// The UTF-16 version of `create_property_key`.
+  pub fn create_property_key_utf16(&self, key: &[u16]) -> Result<JsString<'_>> {
+    let mut raw_value = ptr::null_mut();
+    check_status!(unsafe {
+      sys::node_api_create_property_key_utf16(
+        self.0,
+        key.as_ptr(),
+        key.len() as isize,
+        &mut raw_value,
+      )
+    })?;
+    unsafe { JsString::from_napi_value(self.0, raw_value) }
+  }
+
+  #[cfg(feature = "napi10")]
+  /// The latin1 version of `create_property_key`.
+  pub fn create_property_key_latin1(&self, key: &[u8]) -> Result<JsString<'_>> {
+    let mut raw_value = ptr::null_mut();
+    check_status!(unsafe {
+      sys::node_api_create_property_key_latin1(
+        self.0,
+        key.as_ptr().cast(),
+        key.len() as isize,
+        &mut raw_value,
+      )
+    })?;
+    unsafe { JsString::from_napi_value(self.0, raw_value) }
+  }
+
   pub fn create_symbol(&self, description: Option<&str>) -> Result<JsSymbol> {
     let mut result = ptr::null_mut();
     check_status!(unsafe {
@@ -320,7 +419,7 @@ impl Env {
           &mut raw_value,
         );
         // electron doesn't support external buffers
-        if status == sys::Status::napi_no_external_buffers_allowed {
+        if external_buffers_disallowed(status) {
           drop(Box::from_raw(hint_ptr));
           let mut dest_data_ptr = ptr::null_mut();
           let status = sys::napi_create_buffer_copy(
@@ -391,7 +490,7 @@ impl Env {
         hint_ptr.cast(),
         &mut raw_value,
       );
-      if status == sys::Status::napi_no_external_buffers_allowed {
+      if external_buffers_disallowed(status) {
         let (hint, finalize) = *Box::from_raw(hint_ptr);
         let mut result_data = ptr::null_mut();
         let status = sys::napi_create_buffer_copy(
@@ -506,7 +605,7 @@ impl Env {
           hint_ptr.cast(),
           &mut raw_value,
         );
-        if status == sys::Status::napi_no_external_buffers_allowed {
+        if external_buffers_disallowed(status) {
           drop(Box::from_raw(hint_ptr));
           let mut underlying_data = ptr::null_mut();
           let status =
@@ -583,7 +682,7 @@ impl Env {
         hint_ptr.cast(),
         &mut raw_value,
       );
-      if status == sys::Status::napi_no_external_buffers_allowed {
+      if external_buffers_disallowed(status) {
         let (hint, finalize) = *Box::from_raw(hint_ptr);
         let mut underlying_data = ptr::null_mut();
         let status =
@@ -1016,7 +1115,7 @@ impl Env {
 
   /// Run [Task](./trait.Task.html) in libuv thread pool, return [AsyncWorkPromise](./struct.AsyncWorkPromise.html)
   pub fn spawn<T: 'static + Task>(&self, task: T) -> Result<AsyncWorkPromise<T::JsValue>> {
-    async_work::run(self.0, task, None)
+    async_work::run(self.0, task, None, None)
   }
 
   pub fn run_in_scope<T, F>(&self, executor: F) -> Result<T>
@@ -1044,6 +1143,48 @@ impl Env {
     unsafe { V::from_napi_value(self.0, raw_value) }
   }
 
+  /// Like [`Env::run_script`], with the filename and the line offset of the script in [`ScriptOptions`],
+  /// so the errors thrown by the script point to where it's embedded.
+  ///
+  /// ```
+  /// let value = env.run_script_with_options(
+  ///   include_str!("helpers.js"),
+  ///   ScriptOptions {
+  ///     filename: Some("helpers.js"),
+  ///     ..Default::default()
+  ///   },
+  /// )?;
+  /// ```
+  pub fn run_script_with_options<S: AsRef<str>>(
+    &self,
+    script: S,
+    options: ScriptOptions,
+  ) -> Result<Unknown<'_>> {
+    self.run_script_as(script, options)
+  }
+
+  /// [`Env::run_script_with_options`] with the result converted into `V`.
+  pub fn run_script_as<V: FromNapiValue, S: AsRef<str>>(
+    &self,
+    script: S,
+    options: ScriptOptions,
+  ) -> Result<V> {
+    let mut source = "\n".repeat(options.line_offset as usize);
+    source.push_str(script.as_ref());
+    if let Some(filename) = options.filename {
+      if filename.is_empty() || filename.contains(char::is_whitespace) {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Invalid script filename `{filename}`, it can't be empty or contain whitespaces"),
+        ));
+      }
+      // on its own line, so it's not swallowed by a trailing line comment of the script
+      source.push_str("\n//# sourceURL=");
+      source.push_str(filename);
+    }
+    self.run_script(source)
+  }
+
   /// `process.versions.napi`
   pub fn get_napi_version(&self) -> Result<u32> {
     let global = self.get_global()?;
@@ -1224,21 +1365,13 @@ impl Env {
     Hint: 'static,
     F: FnOnce(FinalizeContext<T, Hint>),
   {
-    check_status!(unsafe {
-      sys::napi_set_instance_data(
-        self.0,
-        Box::into_raw(Box::new((TaggedObject::new(native), finalize_cb))).cast(),
-        Some(
-          set_instance_finalize_callback::<T, Hint, F>
-            as unsafe extern "C" fn(
-              env: sys::napi_env,
-              finalize_data: *mut c_void,
-              finalize_hint: *mut c_void,
-            ),
-        ),
-        Box::into_raw(Box::new(hint)).cast(),
-      )
-    })
+    let instances = self.instances_or_init()?;
+    instances.data.set(Some(InstanceData {
+      data: Box::into_raw(Box::new((TaggedObject::new(native), finalize_cb))).cast(),
+      hint: Box::into_raw(Box::new(hint)).cast(),
+      finalize: set_instance_finalize_callback::<T, Hint, F>,
+    }));
+    Ok(())
   }
 
   /// This API retrieves data that was previously associated with the currently running Agent via `Env::set_instance_data()`.
@@ -1249,16 +1382,12 @@ impl Env {
   where
     T: 'static,
   {
-    let mut unknown_tagged_object: *mut c_void = ptr::null_mut();
+    let Some(data) = self.instances()?.and_then(|instances| instances.data.get()) else {
+      return Ok(None);
+    };
+    let unknown_tagged_object = data.data;
     unsafe {
-      check_status!(sys::napi_get_instance_data(
-        self.0,
-        &mut unknown_tagged_object
-      ))?;
       let type_id = unknown_tagged_object as *const TypeId;
-      if unknown_tagged_object.is_null() {
-        return Ok(None);
-      }
       if *type_id == TypeId::of::<T>() {
         let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
         (*tagged_object).object.as_mut().map(Some).ok_or_else(|| {
@@ -1279,56 +1408,94 @@ impl Env {
     }
   }
 
-  /// Registers hook, which is a function of type `FnOnce(Arg)`, as a function to be run with the `arg` parameter once the current Node.js environment exits.
+  /// Associates `value` with the currently running Agent by its type, and returns the value of the same type set before.
   ///
-  /// Unlike [`add_env_cleanup_hook`](https://docs.rs/napi/latest/napi/struct.Env.html#method.add_env_cleanup_hook), the hook is allowed to be asynchronous.
+  /// Unlike `Env::set_instance_data()`, a value of each type can be set, so the subsystems of an addon can keep their env-local state without colliding.
+  /// The values are dropped in the reverse order of their insertion when the env is torn down.
   ///
-  /// Otherwise, behavior generally matches that of [`add_env_cleanup_hook`](https://docs.rs/napi/latest/napi/struct.Env.html#method.add_env_cleanup_hook).
-  #[cfg(feature = "napi8")]
-  pub fn add_removable_async_cleanup_hook<Arg, F>(
+  /// It fails if the value of type `T` is borrowed by `Env::with_instance()`.
+  #[cfg(feature = "napi6")]
+  pub fn set_instance<T: 'static>(&self, value: T) -> Result<Option<T>> {
+    self.instances_or_init()?.insert(value)
+  }
+
+  /// Calls `f` with the value of type `T` set by `Env::set_instance()`, returns `None` if it's not set.
+  ///
+  /// The value can't be replaced or removed while `f` is running, `Env::set_instance()`, `Env::remove_instance()`
+  /// and the nested `Env::with_instance()` of the same type fail instead.
+  #[cfg(feature = "napi6")]
+  pub fn with_instance<T: 'static, R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<Option<R>> {
+    let Some(instances) = self.instances()? else {
+      return Ok(None);
+    };
+    let Some(value) = instances.borrow::<T>()? else {
+      return Ok(None);
+    };
+    let _borrow = InstanceBorrow {
+      instances,
+      type_id: TypeId::of::<T>(),
+    };
+    // the value is not dropped or moved until `_borrow` is released
+    Ok(Some(f(unsafe { &mut *value })))
+  }
+
+  /// Calls `f` with the value of type `T`, set to `init()` first if it's not set.
+  #[cfg(feature = "napi6")]
+  pub(crate) fn with_instance_or_init<T: 'static, R>(
     &self,
-    arg: Arg,
-    cleanup_fn: F,
-  ) -> Result<AsyncCleanupHook>
-  where
-    F: FnOnce(Arg),
-    Arg: 'static,
-  {
-    let mut handle = ptr::null_mut();
-    check_status!(unsafe {
-      sys::napi_add_async_cleanup_hook(
-        self.0,
-        Some(
-          async_finalize::<Arg, F>
-            as unsafe extern "C" fn(handle: sys::napi_async_cleanup_hook_handle, data: *mut c_void),
-        ),
-        Box::leak(Box::new((arg, cleanup_fn))) as *mut (Arg, F) as *mut c_void,
-        &mut handle,
-      )
-    })?;
-    Ok(AsyncCleanupHook(handle))
+    init: impl FnOnce() -> Result<T>,
+    f: impl FnOnce(&mut T) -> R,
+  ) -> Result<R> {
+    let instances = self.instances_or_init()?;
+    if !instances.contains::<T>() {
+      // `init` may access the other instances, so nothing is borrowed while it's running
+      let value = init()?;
+      if !instances.contains::<T>() {
+        instances.insert(value)?;
+      }
+    }
+    Ok(
+      self
+        .with_instance(f)?
+        .expect("the instance is just inserted"),
+    )
   }
 
-  /// This API is very similar to [`add_removable_async_cleanup_hook`](https://docs.rs/napi/latest/napi/struct.Env.html#method.add_removable_async_cleanup_hook)
+  /// Removes the value of type `T` set by `Env::set_instance()` and returns it.
   ///
-  /// Use this one if you don't want remove the cleanup hook anymore.
-  #[cfg(feature = "napi8")]
-  pub fn add_async_cleanup_hook<Arg, F>(&self, arg: Arg, cleanup_fn: F) -> Result<()>
-  where
-    F: FnOnce(Arg),
-    Arg: 'static,
-  {
+  /// It fails if the value is borrowed by `Env::with_instance()`.
+  #[cfg(feature = "napi6")]
+  pub fn remove_instance<T: 'static>(&self) -> Result<Option<T>> {
+    match self.instances()? {
+      Some(instances) => instances.remove(),
+      None => Ok(None),
+    }
+  }
+
+  #[cfg(feature = "napi6")]
+  fn instances(&self) -> Result<Option<&InstanceMap>> {
+    let mut instances = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_instance_data(self.0, &mut instances) })?;
+    // the instance data slot is only set by `Env::instances_or_init()`, and freed when the env is torn down
+    Ok(unsafe { instances.cast::<InstanceMap>().as_ref() })
+  }
+
+  #[cfg(feature = "napi6")]
+  fn instances_or_init(&self) -> Result<&InstanceMap> {
+    if let Some(instances) = self.instances()? {
+      return Ok(instances);
+    }
+    let instances = Box::into_raw(Box::<InstanceMap>::default());
     check_status!(unsafe {
-      sys::napi_add_async_cleanup_hook(
+      sys::napi_set_instance_data(
         self.0,
-        Some(
-          async_finalize::<Arg, F>
-            as unsafe extern "C" fn(handle: sys::napi_async_cleanup_hook_handle, data: *mut c_void),
-        ),
-        Box::leak(Box::new((arg, cleanup_fn))) as *mut (Arg, F) as *mut c_void,
+        instances.cast(),
+        Some(finalize_instance_map),
         ptr::null_mut(),
       )
     })
+    .inspect_err(|_| drop(unsafe { Box::from_raw(instances) }))?;
+    Ok(unsafe { &*instances })
   }
 
   #[cfg(feature = "napi9")]
@@ -1374,6 +1541,20 @@ impl Env {
     Ok(module_filename.to_string_lossy().into_owned())
   }
 
+  #[cfg(feature = "napi9")]
+  /// The path of the `.node` file of the addon, from the `file://` URL of `Env::get_module_file_name()`.
+  pub fn module_file_name(&self) -> Result<std::path::PathBuf> {
+    file_url_to_path(&self.get_module_file_name()?)
+  }
+
+  #[cfg(feature = "napi9")]
+  /// The directory of the `.node` file of the addon, to locate the resources shipped next to it.
+  pub fn module_dir(&self) -> Result<std::path::PathBuf> {
+    let mut path = self.module_file_name()?;
+    path.pop();
+    Ok(path)
+  }
+
   /// ### Serialize `Rust Struct` into `JavaScript Value`
   ///
   /// ```
@@ -1444,6 +1625,26 @@ impl Env {
     Ok(result)
   }
 
+  /// The highest Node-API version supported by the runtime, which may be lower than the `napiN` feature compiled with.
+  pub fn napi_version(&self) -> Result<u32> {
+    let mut version = 0;
+    check_status!(
+      unsafe { sys::napi_get_version(self.0, &mut version) },
+      "Get the Node-API version failed"
+    )?;
+    Ok(version)
+  }
+
+  /// The version of Node.js running the addon, from `napi_get_node_version`.
+  pub fn node_version(&self) -> Result<NodeVersion> {
+    self.get_node_version()
+  }
+
+  /// Whether the runtime supports `feature`, to fall back on the older runtimes instead of failing to call the newer APIs.
+  pub fn runtime_supports(&self, feature: Feature) -> Result<bool> {
+    Ok(self.napi_version()? >= feature.napi_version())
+  }
+
   pub fn get_node_version(&self) -> Result<NodeVersion> {
     let mut result = ptr::null();
     check_status!(unsafe { sys::napi_get_node_version(self.0, &mut result) })?;
@@ -1466,7 +1667,7 @@ unsafe extern "C" fn drop_buffer(
   finalize_data: *mut c_void,
   hint: *mut c_void,
 ) {
-  #[cfg(all(debug_assertions, not(windows)))]
+  #[cfg(not(feature = "minimal"))]
   unregister_backing_ptr(finalize_data as *mut u8);
 
   let length_ptr = hint as *mut (usize, usize);
@@ -1516,6 +1717,197 @@ unsafe extern "C" fn set_instance_finalize_callback<T, Hint, F>(
   });
 }
 
+#[cfg(feature = "napi6")]
+unsafe extern "C" fn finalize_instance_map(
+  raw_env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  let instances = finalize_data.cast::<InstanceMap>();
+  // the values may access the other instances while they are dropped, so the map is kept until they are all gone
+  unsafe { (*instances).clear(raw_env) };
+  drop(unsafe { Box::from_raw(instances) });
+}
+
+#[cfg(feature = "napi6")]
+#[derive(Default)]
+/// The values set by `Env::set_instance()` by their types, and the data set by `Env::set_instance_data()`.
+///
+/// It's stored in the instance data slot of the env, so the both APIs can be used in the same addon.
+struct InstanceMap {
+  entries: RefCell<Vec<InstanceEntry>>,
+  data: Cell<Option<InstanceData>>,
+}
+
+#[cfg(feature = "napi6")]
+struct InstanceEntry {
+  type_id: TypeId,
+  // not a `Box`, the pointer stays valid while the entries are moved around
+  value: NonNull<dyn Any>,
+  borrowed: bool,
+}
+
+#[cfg(feature = "napi6")]
+#[derive(Clone, Copy)]
+struct InstanceData {
+  data: *mut c_void,
+  hint: *mut c_void,
+  finalize: unsafe extern "C" fn(sys::napi_env, *mut c_void, *mut c_void),
+}
+
+#[cfg(feature = "napi6")]
+impl InstanceMap {
+  fn contains<T: 'static>(&self) -> bool {
+    self
+      .entries
+      .borrow()
+      .iter()
+      .any(|entry| entry.type_id == TypeId::of::<T>())
+  }
+
+  fn insert<T: 'static>(&self, value: T) -> Result<Option<T>> {
+    let previous = self.remove::<T>()?;
+    let value: Box<dyn Any> = Box::new(value);
+    self.entries.borrow_mut().push(InstanceEntry {
+      type_id: TypeId::of::<T>(),
+      value: unsafe { NonNull::new_unchecked(Box::into_raw(value)) },
+      borrowed: false,
+    });
+    Ok(previous)
+  }
+
+  fn borrow<T: 'static>(&self) -> Result<Option<*mut T>> {
+    let mut entries = self.entries.borrow_mut();
+    let Some(entry) = entries
+      .iter_mut()
+      .find(|entry| entry.type_id == TypeId::of::<T>())
+    else {
+      return Ok(None);
+    };
+    if entry.borrowed {
+      return Err(already_borrowed::<T>());
+    }
+    entry.borrowed = true;
+    Ok(Some(entry.value.as_ptr().cast::<T>()))
+  }
+
+  fn remove<T: 'static>(&self) -> Result<Option<T>> {
+    let entry = {
+      let mut entries = self.entries.borrow_mut();
+      let Some(index) = entries
+        .iter()
+        .position(|entry| entry.type_id == TypeId::of::<T>())
+      else {
+        return Ok(None);
+      };
+      if entries[index].borrowed {
+        return Err(already_borrowed::<T>());
+      }
+      entries.remove(index)
+    };
+    let value = unsafe { Box::from_raw(entry.value.as_ptr()) };
+    Ok(value.downcast().ok().map(|value| *value))
+  }
+
+  fn clear(&self, raw_env: sys::napi_env) {
+    if let Some(data) = self.data.take() {
+      unsafe { (data.finalize)(raw_env, data.data, data.hint) };
+    }
+    // the later values may depend on the earlier ones
+    loop {
+      let Some(entry) = self.entries.borrow_mut().pop() else {
+        break;
+      };
+      drop(unsafe { Box::from_raw(entry.value.as_ptr()) });
+    }
+  }
+}
+
+#[cfg(feature = "napi6")]
+fn already_borrowed<T>() -> Error {
+  Error::new(
+    Status::GenericFailure,
+    format!(
+      "The instance of `{}` is borrowed by `Env::with_instance()`",
+      type_name::<T>()
+    ),
+  )
+}
+
+#[cfg(feature = "napi6")]
+/// Releases the value borrowed by `Env::with_instance()`, even if the callback panics.
+struct InstanceBorrow<'a> {
+  instances: &'a InstanceMap,
+  type_id: TypeId,
+}
+
+#[cfg(feature = "napi6")]
+impl Drop for InstanceBorrow<'_> {
+  fn drop(&mut self) {
+    if let Some(entry) = self
+      .instances
+      .entries
+      .borrow_mut()
+      .iter_mut()
+      .find(|entry| entry.type_id == self.type_id)
+    {
+      entry.borrowed = false;
+    }
+  }
+}
+
+#[cfg(feature = "napi9")]
+fn file_url_to_path(url: &str) -> Result<std::path::PathBuf> {
+  let Some(path) = url.strip_prefix("file://") else {
+    return Err(Error::new(
+      Status::GenericFailure,
+      format!("The module file name `{url}` is not a file URL"),
+    ));
+  };
+  // `file:///C:/addon.node` on Windows, `file://server/share/addon.node` for the UNC paths
+  let path = if cfg!(windows) {
+    match path.strip_prefix('/') {
+      Some(local) => local.to_owned(),
+      None => format!("//{path}"),
+    }
+  } else {
+    path.to_owned()
+  };
+  let mut bytes = Vec::with_capacity(path.len());
+  let mut rest = path.as_bytes();
+  while let Some((&byte, tail)) = rest.split_first() {
+    let decoded = (byte == b'%')
+      .then(|| tail.get(..2))
+      .flatten()
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    match decoded {
+      Some(decoded) => {
+        bytes.push(decoded);
+        rest = &tail[2..];
+      }
+      None => {
+        bytes.push(byte);
+        rest = tail;
+      }
+    }
+  }
+  #[cfg(unix)]
+  {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(std::ffi::OsString::from_vec(bytes).into())
+  }
+  #[cfg(not(unix))]
+  {
+    String::from_utf8(bytes).map(Into::into).map_err(|err| {
+      Error::new(
+        Status::GenericFailure,
+        format!("The module file name `{url}` is not valid UTF-8: {err}"),
+      )
+    })
+  }
+}
+
 #[cfg(feature = "napi3")]
 unsafe extern "C" fn cleanup_env<T: 'static>(hook_data: *mut c_void) {
   let cleanup_env_hook = unsafe { Box::from_raw(hook_data as *mut CleanupEnvHookData<T>) };
@@ -1524,34 +1916,19 @@ unsafe extern "C" fn cleanup_env<T: 'static>(hook_data: *mut c_void) {
 
 pub(crate) unsafe extern "C" fn raw_finalize_with_custom_callback<Hint, Finalize>(
   env: sys::napi_env,
-  _finalize_data: *mut c_void,
+  finalize_data: *mut c_void,
   finalize_hint: *mut c_void,
 ) where
   Finalize: FnOnce(Env, Hint),
 {
+  #[cfg(not(feature = "minimal"))]
+  unregister_backing_ptr(finalize_data.cast());
+  #[cfg(feature = "minimal")]
+  let _ = finalize_data;
   let (hint, callback) = unsafe { *Box::from_raw(finalize_hint as *mut (Hint, Finalize)) };
   callback(Env::from_raw(env), hint);
 }
 
-#[cfg(feature = "napi8")]
-unsafe extern "C" fn async_finalize<Arg, F>(
-  handle: sys::napi_async_cleanup_hook_handle,
-  data: *mut c_void,
-) where
-  Arg: 'static,
-  F: FnOnce(Arg),
-{
-  let (arg, callback) = unsafe { *Box::from_raw(data as *mut (Arg, F)) };
-  callback(arg);
-  if !handle.is_null() {
-    let status = unsafe { sys::napi_remove_async_cleanup_hook(handle) };
-    assert!(
-      status == sys::Status::napi_ok,
-      "Remove async cleanup hook failed after async cleanup callback"
-    );
-  }
-}
-
 #[cfg(feature = "napi5")]
 pub(crate) unsafe extern "C" fn trampoline<
   Return: ToNapiValue,
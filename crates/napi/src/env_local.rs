@@ -0,0 +1,120 @@
+//! A replacement of the `static` globals of an addon, for the addons loaded in multiple `worker_threads`.
+//!
+//! Each worker has its own env, and a `static OnceCell` initialized by one of them is shared with the others,
+//! including the `napi_value`s and references which are only valid in the env that created them.
+//!
+//! ```
+//! static CONFIG: EnvLocal<Config> = EnvLocal::new(|env| Config::load(env));
+//!
+//! #[napi]
+//! fn get_config_name(env: &Env) -> Result<String> {
+//!   Ok(CONFIG.get(env)?.name.clone())
+//! }
+//! ```
+
+use std::any::Any;
+use std::rc::Rc;
+
+use crate::{Env, Result};
+
+/// A value initialized once per env, and dropped when the env is torn down.
+///
+/// The values are stored by `Env::set_instance()`, so they don't collide with the instance data of the addon.
+///
+/// [`EnvLocal::get`] returns an `Rc` handle, which keeps the value alive after [`EnvLocal::set`] or [`EnvLocal::take`]
+/// replaced it; use a `Cell` or `RefCell` to mutate it in place.
+pub struct EnvLocal<T: 'static> {
+  init: fn(&Env) -> Result<T>,
+}
+
+impl<T: 'static> EnvLocal<T> {
+  /// `init` is called the first time the value is accessed in each env.
+  pub const fn new(init: fn(&Env) -> Result<T>) -> Self {
+    Self { init }
+  }
+
+  /// The value of the env, initialized if it's the first access in the env.
+  ///
+  /// If `init` fails, the error is returned and `init` is called again on the next access.
+  pub fn get(&'static self, env: &Env) -> Result<Rc<T>> {
+    if let Some(value) = self.try_get(env)? {
+      return Ok(value);
+    }
+    // `init` may access the other `EnvLocal`s, so the values are not borrowed while it's running
+    let value = Rc::new((self.init)(env)?);
+    self.with_values(env, |values| {
+      if let Some(value) = values.get(self.key()) {
+        return value;
+      }
+      values.entries.push((self.key(), value.clone()));
+      value
+    })
+  }
+
+  /// The value of the env, if it's initialized.
+  pub fn try_get(&'static self, env: &Env) -> Result<Option<Rc<T>>> {
+    Ok(
+      env
+        .with_instance(|values: &mut EnvLocalValues| values.get(self.key()))?
+        .flatten(),
+    )
+  }
+
+  /// Replaces the value of the env, and returns the previous one.
+  pub fn set(&'static self, env: &Env, value: T) -> Result<Option<Rc<T>>> {
+    self.with_values(env, |values| {
+      let previous = values.remove(self.key());
+      values.entries.push((self.key(), Rc::new(value)));
+      previous
+    })
+  }
+
+  /// Removes the value of the env, it's initialized again on the next access.
+  ///
+  /// The handles returned by [`EnvLocal::get`] before keep the removed value alive.
+  pub fn take(&'static self, env: &Env) -> Result<Option<Rc<T>>> {
+    Ok(
+      env
+        .with_instance(|values: &mut EnvLocalValues| values.remove(self.key()))?
+        .flatten(),
+    )
+  }
+
+  // the statics are told apart by their addresses
+  fn key(&'static self) -> usize {
+    self as *const Self as usize
+  }
+
+  fn with_values<R>(&self, env: &Env, f: impl FnOnce(&mut EnvLocalValues) -> R) -> Result<R> {
+    env.with_instance_or_init(|| Ok(EnvLocalValues::default()), f)
+  }
+}
+
+#[derive(Default)]
+struct EnvLocalValues {
+  entries: Vec<(usize, Rc<dyn Any>)>,
+}
+
+impl EnvLocalValues {
+  fn get<T: 'static>(&self, key: usize) -> Option<Rc<T>> {
+    self
+      .entries
+      .iter()
+      .find(|(k, _)| *k == key)
+      .and_then(|(_, value)| value.clone().downcast().ok())
+  }
+
+  fn remove<T: 'static>(&mut self, key: usize) -> Option<Rc<T>> {
+    let index = self.entries.iter().position(|(k, _)| *k == key)?;
+    self.entries.remove(index).1.downcast().ok()
+  }
+}
+
+impl Drop for EnvLocalValues {
+  fn drop(&mut self) {
+    // the later values may depend on the earlier ones
+    while let Some(entry) = self.entries.pop() {
+      drop(entry);
+    }
+  }
+}
@@ -25,8 +25,12 @@ pub struct Error<S: AsRef<str> = Status> {
   // Convert raw `JsError` into Error
   pub(crate) maybe_raw: sys::napi_ref,
   pub(crate) maybe_env: sys::napi_env,
+  // Structured JavaScript value attached by `Error::with_value`
+  pub(crate) maybe_value: Option<ErrorValue>,
 }
 
+pub(crate) type ErrorValue = Box<dyn FnOnce(sys::napi_env) -> Result<sys::napi_value> + Send>;
+
 impl<S: AsRef<str>> Drop for Error<S> {
   fn drop(&mut self) {
     // @TODO: deal with Error created with reference and leave it to drop in `async fn`
@@ -140,6 +144,7 @@ impl From<Unknown<'_>> for Error {
         reason: error_message,
         maybe_raw: result,
         maybe_env,
+        maybe_value: None,
       };
     }
 
@@ -148,6 +153,7 @@ impl From<Unknown<'_>> for Error {
       reason: "".to_string(),
       maybe_raw: result,
       maybe_env,
+      maybe_value: None,
     }
   }
 }
@@ -176,6 +182,7 @@ impl<S: AsRef<str>> Error<S> {
       reason: reason.to_string(),
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      maybe_value: None,
     }
   }
 
@@ -185,12 +192,29 @@ impl<S: AsRef<str>> Error<S> {
       reason: "".to_owned(),
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      maybe_value: None,
     }
   }
+
+  /// Attach a structured value, for example a `#[napi(object)]` struct, to this Error.
+  ///
+  /// The value is converted on the JavaScript thread and thrown, or used as the rejection reason of the `Promise`
+  /// returned by an `async fn`, instead of a JavaScript `Error` created from `status` and `reason`.
+  /// So the `catch` blocks in JavaScript can branch on its fields rather than parsing the message.
+  pub fn with_value<V: ToNapiValue + Send + 'static>(mut self, value: V) -> Self {
+    self.maybe_value = Some(Box::new(move |env| unsafe { V::to_napi_value(env, value) }));
+    self
+  }
 }
 
 impl<S: AsRef<str> + Clone> Error<S> {
   pub fn try_clone(&self) -> Result<Self> {
+    if self.maybe_value.is_some() {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "Can't clone an Error carrying a structured value".to_owned(),
+      ));
+    }
     if !self.maybe_raw.is_null() {
       check_status!(
         unsafe { sys::napi_reference_ref(self.maybe_env, self.maybe_raw, &mut 0) },
@@ -202,6 +226,7 @@ impl<S: AsRef<str> + Clone> Error<S> {
       reason: self.reason.to_string(),
       maybe_raw: self.maybe_raw,
       maybe_env: self.maybe_env,
+      maybe_value: None,
     })
   }
 }
@@ -213,6 +238,7 @@ impl Error {
       reason: reason.into(),
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      maybe_value: None,
     }
   }
 }
@@ -224,6 +250,7 @@ impl From<std::ffi::NulError> for Error {
       reason: format!("{}", error),
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      maybe_value: None,
     }
   }
 }
@@ -235,6 +262,7 @@ impl From<std::io::Error> for Error {
       reason: format!("{}", error),
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      maybe_value: None,
     }
   }
 }
@@ -319,6 +347,12 @@ macro_rules! impl_object_methods {
       ///
       /// This function is safety if env is not null ptr.
       pub unsafe fn into_value(mut self, env: sys::napi_env) -> sys::napi_value {
+        if let Some(value) = self.0.maybe_value.take() {
+          // fallback to the `Error` created from `status` and `reason` if the conversion failed
+          if let Ok(value) = value(env) {
+            return value;
+          }
+        }
         if !self.0.maybe_raw.is_null() {
           let mut err = ptr::null_mut();
           let get_err_status =
@@ -477,6 +511,7 @@ macro_rules! check_status {
     let c = $code;
     match c {
       $crate::sys::Status::napi_ok => Ok(()),
+      _ if $crate::__private::MINIMAL => Err($crate::Error::from_status($crate::Status::from(c))),
       _ => Err($crate::Error::new($crate::Status::from(c), format!($($msg)*))),
     }
   }};
@@ -485,6 +520,7 @@ macro_rules! check_status {
     let c = $code;
     match c {
       $crate::sys::Status::napi_ok => Ok(()),
+      _ if $crate::__private::MINIMAL => Err($crate::Error::from_status($crate::Status::from(c))),
       _ => Err($crate::Error::new($crate::Status::from(c), format!($msg, $crate::type_of!($env, $val)?))),
     }
   }};
@@ -497,6 +533,10 @@ macro_rules! check_status_and_type {
     let c = $code;
     match c {
       $crate::sys::Status::napi_ok => Ok(()),
+      _ if $crate::__private::MINIMAL => Err($crate::Error::new(
+        $crate::Status::from(c),
+        "Failed to convert the JavaScript value into the Rust type".to_owned(),
+      )),
       _ => {
         use $crate::js_values::JsValue;
         let value_type = $crate::type_of!($env, $val)?;
@@ -0,0 +1,157 @@
+//! Where the errors raised in the background contexts are delivered, like a failed `ThreadsafeFunction` callback or
+//! the completion of an `AsyncTask` which can't settle its `Promise`, since there is no JavaScript caller to throw to.
+//!
+//! By default they are thrown as `uncaughtException`, which crashes the process unless a listener is registered.
+//! A library can route them to a handler registered by its users instead:
+//!
+//! ```
+//! #[napi]
+//! fn on_error(env: &Env, handler: Function<Unknown, ()>) -> Result<()> {
+//!   env.set_error_sink(ErrorHandler::new(&handler)?)
+//! }
+//! ```
+//!
+//! The sink of the env can be overridden per `ThreadsafeFunction` with `ThreadsafeFunctionBuilder::with_error_sink`,
+//! and per `AsyncTask` with `AsyncTask::with_error_sink`.
+
+use std::ptr;
+#[cfg(feature = "napi6")]
+use std::rc::Rc;
+
+use crate::bindgen_runtime::{Function, JsValuesTupleIntoVec};
+use crate::{check_status, sys, Env, Error, JsError, Result};
+
+/// Receives the errors which have no JavaScript caller to be thrown to.
+pub trait ErrorSink: 'static {
+  fn report(&self, env: &Env, err: Error);
+}
+
+impl<F: Fn(&Env, Error) + 'static> ErrorSink for F {
+  fn report(&self, env: &Env, err: Error) {
+    self(env, err)
+  }
+}
+
+/// The default sink, it triggers an `uncaughtException`.
+pub struct UncaughtException;
+
+impl ErrorSink for UncaughtException {
+  fn report(&self, env: &Env, err: Error) {
+    uncaught_exception(env.0, unsafe { JsError::from(err).into_value(env.0) });
+  }
+}
+
+fn uncaught_exception(env: sys::napi_env, error: sys::napi_value) {
+  #[cfg(feature = "napi3")]
+  unsafe {
+    sys::napi_fatal_exception(env, error)
+  };
+  // thrown from the callback, without a JavaScript caller to catch it
+  #[cfg(not(feature = "napi3"))]
+  unsafe {
+    sys::napi_throw(env, error)
+  };
+}
+
+/// Calls a JavaScript function with the error.
+///
+/// If the function throws, the exception is reported as an `uncaughtException`.
+pub struct ErrorHandler {
+  env: sys::napi_env,
+  handler: sys::napi_ref,
+}
+
+impl ErrorHandler {
+  pub fn new<Args: JsValuesTupleIntoVec, Return>(handler: &Function<Args, Return>) -> Result<Self> {
+    let mut handler_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(handler.env, handler.value, 1, &mut handler_ref) },
+      "Failed to create the reference of the error handler"
+    )?;
+    Ok(Self {
+      env: handler.env,
+      handler: handler_ref,
+    })
+  }
+
+  fn call(&self, env: &Env, err: Error) -> Result<()> {
+    let mut handler = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env.0, self.handler, &mut handler) },
+      "Failed to get the error handler"
+    )?;
+    let mut recv = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_undefined(env.0, &mut recv) })?;
+    let error = unsafe { JsError::from(err).into_value(env.0) };
+    check_status!(
+      unsafe { sys::napi_call_function(env.0, recv, handler, 1, &error, ptr::null_mut()) },
+      "Failed to call the error handler"
+    )
+  }
+}
+
+impl ErrorSink for ErrorHandler {
+  fn report(&self, env: &Env, err: Error) {
+    if let Err(err) = self.call(env, err) {
+      let mut pending = false;
+      unsafe { sys::napi_is_exception_pending(env.0, &mut pending) };
+      if pending {
+        // thrown by the handler
+        let mut exception = ptr::null_mut();
+        unsafe { sys::napi_get_and_clear_last_exception(env.0, &mut exception) };
+        uncaught_exception(env.0, exception);
+      } else {
+        UncaughtException.report(env, err);
+      }
+    }
+  }
+}
+
+impl Drop for ErrorHandler {
+  fn drop(&mut self) {
+    unsafe { sys::napi_delete_reference(self.env, self.handler) };
+  }
+}
+
+#[cfg(feature = "napi6")]
+// The sink of the env, in the instance data
+struct EnvErrorSink(Rc<dyn ErrorSink>);
+
+impl Env {
+  /// Set the sink of the errors raised in the background contexts of the env, see [`ErrorSink`].
+  #[cfg(feature = "napi6")]
+  pub fn set_error_sink<S: ErrorSink>(&self, sink: S) -> Result<()> {
+    if self.set_instance(EnvErrorSink(Rc::new(sink)))?.is_none() {
+      // the sink may hold references, it's dropped while the env is still alive
+      self.add_env_cleanup_hook(self.0 as usize, |env| {
+        let env = Env::from_raw(env as sys::napi_env);
+        let _ = env.remove_instance::<EnvErrorSink>();
+      })?;
+    }
+    Ok(())
+  }
+
+  /// Deliver `err` to the sink of the env, or throw it as an `uncaughtException` if there is none.
+  pub fn report_error(&self, err: Error) {
+    report_error(self, None, err)
+  }
+}
+
+/// Deliver `err` to `sink`, or to the sink of the env if it's `None`
+pub(crate) fn report_error(env: &Env, sink: Option<&dyn ErrorSink>, err: Error) {
+  if let Some(sink) = sink {
+    return sink.report(env, err);
+  }
+  // cloned so the sink can replace itself
+  #[cfg(feature = "napi6")]
+  let env_sink = env
+    .with_instance(|sink: &mut EnvErrorSink| sink.0.clone())
+    .ok()
+    .flatten();
+  #[cfg(not(feature = "napi6"))]
+  let env_sink: Option<std::rc::Rc<dyn ErrorSink>> = None;
+  match env_sink {
+    Some(sink) => sink.report(env, err),
+    None => UncaughtException.report(env, err),
+  }
+}
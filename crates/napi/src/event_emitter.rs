@@ -0,0 +1,453 @@
+//! An `EventEmitter` like the one of `node:events`, to emit events from Rust to the JavaScript listeners.
+//!
+//! The [`EventEmitter`] lives on the JavaScript thread, usually in a `#[napi]` class, which exposes its
+//! `on`, `once` and `off` methods. [`EventSender`]s emit from the other threads, the events are queued and
+//! delivered to the listeners on the JavaScript thread in the order they are emitted:
+//!
+//! ```
+//! #[napi]
+//! pub struct Watcher {
+//!   events: EventEmitter,
+//! }
+//!
+//! #[napi]
+//! impl Watcher {
+//!   #[napi(constructor)]
+//!   pub fn new(env: &Env) -> Result<Self> {
+//!     let events = EventEmitter::new(env)?;
+//!     let sender = events.sender();
+//!     std::thread::spawn(move || {
+//!       for path in changed_paths() {
+//!         sender.emit("change", path);
+//!       }
+//!     });
+//!     Ok(Self { events })
+//!   }
+//!
+//!   #[napi(ts_args_type = "event: string, listener: (payload: any) => void")]
+//!   pub fn on(&self, event: String, listener: Function<Unknown, ()>) -> Result<()> {
+//!     self.events.on(&event, &listener)
+//!   }
+//! }
+//! ```
+//!
+//! The event loop is kept alive while an `EventSender` exists, so the events of a background thread are delivered
+//! before the process exits. The emitter is closed when it's dropped, for example when the class instance is
+//! garbage collected, the events emitted after it are rejected and the listeners are released.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::{
+  atomic::{AtomicUsize, Ordering},
+  Arc, RwLock,
+};
+
+use crate::bindgen_runtime::{Function, JsValuesTupleIntoVec, ToNapiValue, Unknown};
+use crate::error_sink::report_error;
+use crate::{check_status, sys, Env, Error, Result, Status};
+
+/// Emits the events to the JavaScript listeners, it must be used and dropped on the JavaScript thread.
+pub struct EventEmitter {
+  env: sys::napi_env,
+  listeners: Rc<RefCell<Listeners>>,
+  shared: Arc<SenderShared>,
+}
+
+impl EventEmitter {
+  pub fn new(env: &Env) -> Result<Self> {
+    let listeners = Rc::new(RefCell::new(Listeners {
+      env: env.0,
+      events: HashMap::new(),
+    }));
+    let shared = Arc::new(SenderShared {
+      raw: RwLock::new(None),
+      senders: AtomicUsize::new(0),
+    });
+    let context = Box::into_raw(Box::new(EmitterContext {
+      listeners: listeners.clone(),
+      shared: shared.clone(),
+    }));
+    let async_resource_name =
+      unsafe { ToNapiValue::to_napi_value(env.0, "napi_rs_event_emitter") }?;
+    let mut raw = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env.0,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          async_resource_name,
+          0,
+          1,
+          context.cast(),
+          Some(finalize_emitter_context),
+          context.cast(),
+          Some(call_emitter_js),
+          &mut raw,
+        )
+      },
+      "Create threadsafe function in EventEmitter::new failed"
+    )
+    .inspect_err(|_| drop(unsafe { Box::from_raw(context) }))?;
+    // it's referenced while an `EventSender` exists
+    check_status!(
+      unsafe { sys::napi_unref_threadsafe_function(env.0, raw) },
+      "Unref threadsafe function in EventEmitter::new failed"
+    )?;
+    crate::cleanup_env::on_threadsafe_function_created(env.0);
+    *shared.write() = Some(RawThreadsafeFunction(raw));
+    Ok(Self {
+      env: env.0,
+      listeners,
+      shared,
+    })
+  }
+
+  /// Add `listener` to the end of the listeners of `event`.
+  pub fn on<Args: JsValuesTupleIntoVec, Return>(
+    &self,
+    event: &str,
+    listener: &Function<Args, Return>,
+  ) -> Result<()> {
+    self.add_listener(event, listener.value, false)
+  }
+
+  /// Add `listener` to the end of the listeners of `event`, it's removed before it's called the first time.
+  pub fn once<Args: JsValuesTupleIntoVec, Return>(
+    &self,
+    event: &str,
+    listener: &Function<Args, Return>,
+  ) -> Result<()> {
+    self.add_listener(event, listener.value, true)
+  }
+
+  /// Remove the most recently added `listener` of `event`, it does nothing if it's not a listener of `event`.
+  pub fn off<Args: JsValuesTupleIntoVec, Return>(
+    &self,
+    event: &str,
+    listener: &Function<Args, Return>,
+  ) -> Result<()> {
+    let removed = {
+      let mut listeners = self.listeners.borrow_mut();
+      let Some(event_listeners) = listeners.events.get_mut(event) else {
+        return Ok(());
+      };
+      let mut index = None;
+      for (i, registered) in event_listeners.iter().enumerate().rev() {
+        let mut value = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_get_reference_value(self.env, registered.listener, &mut value) },
+          "Failed to get the listener of `{}`",
+          event
+        )?;
+        let mut equals = false;
+        check_status!(
+          unsafe { sys::napi_strict_equals(self.env, value, listener.value, &mut equals) },
+          "Failed to compare the listener of `{}`",
+          event
+        )?;
+        if equals {
+          index = Some(i);
+          break;
+        }
+      }
+      let removed = index.map(|index| event_listeners.remove(index));
+      if event_listeners.is_empty() {
+        listeners.events.remove(event);
+      }
+      removed
+    };
+    if let Some(removed) = removed {
+      unsafe { sys::napi_delete_reference(self.env, removed.listener) };
+    }
+    Ok(())
+  }
+
+  /// The number of the listeners of `event`.
+  pub fn listener_count(&self, event: &str) -> usize {
+    self
+      .listeners
+      .borrow()
+      .events
+      .get(event)
+      .map_or(0, Vec::len)
+  }
+
+  /// Call the listeners of `event` with `payload` synchronously, it returns `false` if `event` has no listeners.
+  ///
+  /// The errors thrown by the listeners are delivered to the `ErrorSink` of the env, after all of them are called.
+  pub fn emit<P: ToNapiValue>(&self, event: &str, payload: P) -> Result<bool> {
+    let payload = unsafe { P::to_napi_value(self.env, payload) }?;
+    dispatch(self.env, &self.listeners, event, payload)
+  }
+
+  /// A sender to emit the events from the other threads, the event loop is kept alive until all of them are dropped.
+  pub fn sender(&self) -> EventSender {
+    if self.shared.senders.fetch_add(1, Ordering::AcqRel) == 0 {
+      if let Some(RawThreadsafeFunction(raw)) = *self.shared.read() {
+        unsafe { sys::napi_ref_threadsafe_function(self.env, raw) };
+      }
+    }
+    EventSender {
+      shared: self.shared.clone(),
+    }
+  }
+
+  fn add_listener(&self, event: &str, listener: sys::napi_value, once: bool) -> Result<()> {
+    let mut listener_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(self.env, listener, 1, &mut listener_ref) },
+      "Failed to create the reference of the listener of `{}`",
+      event
+    )?;
+    self
+      .listeners
+      .borrow_mut()
+      .events
+      .entry(event.to_owned())
+      .or_default()
+      .push(Listener {
+        listener: listener_ref,
+        once,
+      });
+    Ok(())
+  }
+}
+
+impl Drop for EventEmitter {
+  fn drop(&mut self) {
+    let mut raw = self.shared.write();
+    // it's already finalized if the env is torn down
+    if let Some(RawThreadsafeFunction(raw)) = raw.take() {
+      unsafe {
+        sys::napi_release_threadsafe_function(raw, sys::ThreadsafeFunctionReleaseMode::release)
+      };
+    }
+  }
+}
+
+/// Emits the events of an [`EventEmitter`] from any thread, it can be cloned and sent to the other threads.
+pub struct EventSender {
+  shared: Arc<SenderShared>,
+}
+
+impl EventSender {
+  /// Queue `event`, the listeners are called with `payload` on the JavaScript thread.
+  ///
+  /// It returns `Status::Closing` after the `EventEmitter` is dropped.
+  pub fn emit<E, P>(&self, event: E, payload: P) -> Status
+  where
+    E: Into<String>,
+    P: 'static + ToNapiValue + Send,
+  {
+    let raw = self.shared.read();
+    let Some(RawThreadsafeFunction(raw)) = *raw else {
+      return Status::Closing;
+    };
+    let data = Box::into_raw(Box::new(EmitData {
+      event: event.into(),
+      payload: Box::new(move |env| unsafe { P::to_napi_value(env, payload) }),
+    }));
+    let status: Status = unsafe {
+      sys::napi_call_threadsafe_function(
+        raw,
+        data.cast(),
+        sys::ThreadsafeFunctionCallMode::nonblocking,
+      )
+    }
+    .into();
+    if status != Status::Ok {
+      drop(unsafe { Box::from_raw(data) });
+    }
+    status
+  }
+}
+
+impl Clone for EventSender {
+  fn clone(&self) -> Self {
+    self.shared.senders.fetch_add(1, Ordering::AcqRel);
+    Self {
+      shared: self.shared.clone(),
+    }
+  }
+}
+
+impl Drop for EventSender {
+  fn drop(&mut self) {
+    if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+      // the function can only be unreferenced on the JavaScript thread, it's woken up with an empty item
+      if let Some(RawThreadsafeFunction(raw)) = *self.shared.read() {
+        unsafe {
+          sys::napi_call_threadsafe_function(
+            raw,
+            ptr::null_mut(),
+            sys::ThreadsafeFunctionCallMode::nonblocking,
+          )
+        };
+      }
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+struct RawThreadsafeFunction(sys::napi_threadsafe_function);
+
+// the threadsafe function can be called from any thread until it's released
+unsafe impl Send for RawThreadsafeFunction {}
+unsafe impl Sync for RawThreadsafeFunction {}
+
+struct SenderShared {
+  // `None` once the emitter is dropped or the threadsafe function is finalized
+  raw: RwLock<Option<RawThreadsafeFunction>>,
+  senders: AtomicUsize,
+}
+
+impl SenderShared {
+  fn read(&self) -> std::sync::RwLockReadGuard<'_, Option<RawThreadsafeFunction>> {
+    self
+      .raw
+      .read()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+
+  fn write(&self) -> std::sync::RwLockWriteGuard<'_, Option<RawThreadsafeFunction>> {
+    self
+      .raw
+      .write()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+struct Listeners {
+  env: sys::napi_env,
+  events: HashMap<String, Vec<Listener>>,
+}
+
+impl Drop for Listeners {
+  fn drop(&mut self) {
+    for listener in self.events.values().flatten() {
+      unsafe { sys::napi_delete_reference(self.env, listener.listener) };
+    }
+  }
+}
+
+struct Listener {
+  listener: sys::napi_ref,
+  once: bool,
+}
+
+struct EmitData {
+  event: String,
+  payload: Box<dyn FnOnce(sys::napi_env) -> Result<sys::napi_value> + Send>,
+}
+
+// Owned by the JavaScript thread
+struct EmitterContext {
+  listeners: Rc<RefCell<Listeners>>,
+  shared: Arc<SenderShared>,
+}
+
+fn dispatch(
+  env: sys::napi_env,
+  listeners: &RefCell<Listeners>,
+  event: &str,
+  payload: sys::napi_value,
+) -> Result<bool> {
+  // the listeners can add or remove the listeners while they are called
+  let (callees, removed) = {
+    let mut listeners = listeners.borrow_mut();
+    let Some(event_listeners) = listeners.events.get_mut(event) else {
+      return Ok(false);
+    };
+    let mut callees = Vec::with_capacity(event_listeners.len());
+    for registered in event_listeners.iter() {
+      let mut value = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env, registered.listener, &mut value) },
+        "Failed to get the listener of `{}`",
+        event
+      )?;
+      callees.push(value);
+    }
+    let (removed, kept) = std::mem::take(event_listeners)
+      .into_iter()
+      .partition::<Vec<_>, _>(|registered| registered.once);
+    if kept.is_empty() {
+      listeners.events.remove(event);
+    } else {
+      *event_listeners = kept;
+    }
+    (callees, removed)
+  };
+  for registered in removed {
+    unsafe { sys::napi_delete_reference(env, registered.listener) };
+  }
+
+  let mut recv = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_undefined(env, &mut recv) })?;
+  let mut errors = Vec::new();
+  for callee in callees {
+    let status =
+      unsafe { sys::napi_call_function(env, recv, callee, 1, &payload, ptr::null_mut()) };
+    if status == sys::Status::napi_pending_exception {
+      let mut exception = ptr::null_mut();
+      unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+      errors.push(Error::from(unsafe {
+        Unknown::from_raw_unchecked(env, exception)
+      }));
+    } else if status != sys::Status::napi_ok {
+      errors.push(Error::new(
+        Status::from(status),
+        format!("Failed to call the listener of `{event}`"),
+      ));
+    }
+  }
+  let env = Env::from_raw(env);
+  for err in errors {
+    report_error(&env, None, err);
+  }
+  Ok(true)
+}
+
+unsafe extern "C" fn finalize_emitter_context(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  let context = unsafe { Box::<EmitterContext>::from_raw(finalize_data.cast()) };
+  context.shared.write().take();
+}
+
+unsafe extern "C" fn call_emitter_js(
+  raw_env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  context: *mut c_void,
+  data: *mut c_void,
+) {
+  let data = (!data.is_null()).then(|| unsafe { Box::<EmitData>::from_raw(data.cast()) });
+  // env can be null when shutting down, the context is already dropped by `finalize_emitter_context` then
+  if raw_env.is_null() {
+    return;
+  }
+
+  let context: &EmitterContext = unsafe { &*context.cast() };
+  let Some(data) = data else {
+    // the last `EventSender` is dropped, unless a new one is created since
+    if context.shared.senders.load(Ordering::Acquire) == 0 {
+      if let Some(RawThreadsafeFunction(raw)) = *context.shared.read() {
+        unsafe { sys::napi_unref_threadsafe_function(raw_env, raw) };
+      }
+    }
+    return;
+  };
+  let _watchdog = crate::bindgen_runtime::LoopWatchdogScope::enter_threadsafe_function();
+  let EmitData { event, payload } = *data;
+  let result =
+    payload(raw_env).and_then(|payload| dispatch(raw_env, &context.listeners, &event, payload));
+  if let Err(err) = result {
+    report_error(&Env::from_raw(raw_env), None, err);
+  }
+}
@@ -0,0 +1,389 @@
+//! The runtime which drives the futures of `#[napi] async fn`.
+//!
+//! With the `tokio_rt` feature the futures run on the tokio runtime of napi-rs. Without it they run on
+//! [`SingleThreadExecutor`], so the async functions work in builds which can't link tokio. An embedder can integrate
+//! its own runtime by implementing [`Executor`] and installing it before the first async call:
+//!
+//! ```ignore
+//! use napi::executor::{BoxFuture, Executor};
+//!
+//! struct AsyncStdExecutor;
+//!
+//! impl Executor for AsyncStdExecutor {
+//!   fn spawn(&self, future: BoxFuture) {
+//!     async_std::task::spawn(future);
+//!   }
+//!
+//!   fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+//!     async_std::task::spawn_blocking(task);
+//!   }
+//! }
+//!
+//! #[napi_derive::module_init]
+//! fn init() {
+//!   napi::executor::set_executor(AsyncStdExecutor).unwrap();
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+#[cfg(not(feature = "noop"))]
+use std::marker::PhantomData;
+
+use crate::{sys, Error, Result};
+#[cfg(not(feature = "noop"))]
+use crate::{Env, JsDeferred, Status, Unknown};
+
+/// A future which can be spawned on an [`Executor`].
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Runs the futures of the async functions, and the blocking tasks.
+pub trait Executor: Send + Sync + 'static {
+  /// Run `future` to completion in the background.
+  fn spawn(&self, future: BoxFuture);
+
+  /// Run `task` on a thread where blocking is allowed.
+  fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>);
+}
+
+static CUSTOM_EXECUTOR: OnceLock<Box<dyn Executor>> = OnceLock::new();
+
+/// Install the executor of the process, it must be called before the first async call.
+///
+/// Returns an error if an executor is already installed.
+pub fn set_executor<E: Executor>(executor: E) -> Result<()> {
+  CUSTOM_EXECUTOR.set(Box::new(executor)).map_err(|_| {
+    Error::from_reason("The executor of napi-rs is already set, it can only be set once")
+  })
+}
+
+/// The executor installed by [`set_executor`], or the default one.
+pub fn executor() -> &'static dyn Executor {
+  if let Some(executor) = custom_executor() {
+    return executor;
+  }
+  #[cfg(feature = "tokio_rt")]
+  {
+    &TokioExecutor
+  }
+  #[cfg(not(feature = "tokio_rt"))]
+  {
+    static FALLBACK: SingleThreadExecutor = SingleThreadExecutor::new();
+    &FALLBACK
+  }
+}
+
+pub(crate) fn custom_executor() -> Option<&'static dyn Executor> {
+  CUSTOM_EXECUTOR.get().map(|executor| &**executor)
+}
+
+/// Spawns the futures on the tokio runtime of napi-rs.
+#[cfg(feature = "tokio_rt")]
+pub struct TokioExecutor;
+
+#[cfg(feature = "tokio_rt")]
+#[cfg_attr(feature = "noop", allow(unused_variables))]
+impl Executor for TokioExecutor {
+  fn spawn(&self, future: BoxFuture) {
+    #[cfg(not(feature = "noop"))]
+    crate::tokio_runtime::spawn(future);
+  }
+
+  fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+    #[cfg(not(feature = "noop"))]
+    crate::tokio_runtime::spawn_blocking(task);
+  }
+}
+
+/// Polls all the futures on one background thread, started by the first spawn.
+///
+/// The blocking tasks run on a pool of threads, so they don't stall the futures. The pool starts a thread when a task
+/// is spawned and all its threads are busy, up to [`SingleThreadExecutor::DEFAULT_MAX_BLOCKING_THREADS`] threads
+/// or the number set by [`SingleThreadExecutor::with_max_blocking_threads`], the other tasks wait in a queue.
+/// The threads exit after 10 seconds without a task.
+pub struct SingleThreadExecutor {
+  queue: OnceLock<Sender<Arc<Job>>>,
+  blocking: OnceLock<Arc<BlockingPool>>,
+  max_blocking_threads: usize,
+}
+
+impl SingleThreadExecutor {
+  pub const DEFAULT_MAX_BLOCKING_THREADS: usize = 16;
+
+  pub const fn new() -> Self {
+    Self::with_max_blocking_threads(Self::DEFAULT_MAX_BLOCKING_THREADS)
+  }
+
+  /// Run the blocking tasks on at most `max` threads, at least one thread is used.
+  pub const fn with_max_blocking_threads(max: usize) -> Self {
+    Self {
+      queue: OnceLock::new(),
+      blocking: OnceLock::new(),
+      max_blocking_threads: if max == 0 { 1 } else { max },
+    }
+  }
+
+  fn queue(&self) -> &Sender<Arc<Job>> {
+    self.queue.get_or_init(|| {
+      let (sender, receiver) = mpsc::channel();
+      std::thread::Builder::new()
+        .name("napi-rs-executor".to_owned())
+        .spawn(move || run_jobs(receiver))
+        .expect("Failed to start the thread of SingleThreadExecutor");
+      sender
+    })
+  }
+}
+
+impl Default for SingleThreadExecutor {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Executor for SingleThreadExecutor {
+  fn spawn(&self, future: BoxFuture) {
+    let queue = self.queue();
+    let job = Arc::new(Job {
+      future: Mutex::new(Some(future)),
+      scheduled: AtomicBool::new(true),
+      queue: queue.clone(),
+    });
+    // the thread never exits, the send can't fail
+    let _ = queue.send(job);
+  }
+
+  fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+    let pool = self.blocking.get_or_init(|| {
+      Arc::new(BlockingPool {
+        state: Mutex::new(BlockingState {
+          tasks: VecDeque::new(),
+          threads: 0,
+          idle: 0,
+        }),
+        available: Condvar::new(),
+        max_threads: self.max_blocking_threads,
+      })
+    });
+    BlockingPool::spawn(pool, task);
+  }
+}
+
+// how long an idle blocking thread waits for a task before it exits
+const BLOCKING_THREAD_KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+struct BlockingPool {
+  state: Mutex<BlockingState>,
+  // notified when a task is queued
+  available: Condvar,
+  max_threads: usize,
+}
+
+struct BlockingState {
+  tasks: VecDeque<Box<dyn FnOnce() + Send>>,
+  threads: usize,
+  // the threads waiting for a task
+  idle: usize,
+}
+
+impl BlockingPool {
+  fn lock(&self) -> std::sync::MutexGuard<'_, BlockingState> {
+    self
+      .state
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+
+  fn spawn(pool: &Arc<Self>, task: Box<dyn FnOnce() + Send>) {
+    let mut state = pool.lock();
+    state.tasks.push_back(task);
+    if state.idle == 0 && state.threads < pool.max_threads {
+      state.threads += 1;
+      drop(state);
+      let pool = pool.clone();
+      std::thread::Builder::new()
+        .name("napi-rs-blocking".to_owned())
+        .spawn(move || pool.run())
+        .expect("Failed to start the blocking thread of SingleThreadExecutor");
+    } else {
+      drop(state);
+      pool.available.notify_one();
+    }
+  }
+
+  fn run(&self) {
+    let mut state = self.lock();
+    loop {
+      match state.tasks.pop_front() {
+        Some(task) => {
+          drop(state);
+          // a panic only ends the task, the thread keeps serving the queue
+          let _ = panic::catch_unwind(AssertUnwindSafe(task));
+          state = self.lock();
+        }
+        None => {
+          state.idle += 1;
+          let (next, timeout) = self
+            .available
+            .wait_timeout(state, BLOCKING_THREAD_KEEP_ALIVE)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+          state = next;
+          state.idle -= 1;
+          if timeout.timed_out() && state.tasks.is_empty() {
+            state.threads -= 1;
+            return;
+          }
+        }
+      }
+    }
+  }
+}
+
+struct Job {
+  // taken while it's polled, and dropped once it's ready
+  future: Mutex<Option<BoxFuture>>,
+  // whether the job is in the queue, so waking it again doesn't queue it twice
+  scheduled: AtomicBool,
+  queue: Sender<Arc<Job>>,
+}
+
+impl Wake for Job {
+  fn wake(self: Arc<Self>) {
+    if !self.scheduled.swap(true, Ordering::AcqRel) {
+      let _ = self.queue.send(self.clone());
+    }
+  }
+}
+
+fn run_jobs(receiver: Receiver<Arc<Job>>) {
+  while let Ok(job) = receiver.recv() {
+    // cleared before polling, so a wake during the poll queues the job again
+    job.scheduled.store(false, Ordering::Release);
+    let Some(mut future) = job
+      .future
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+      .take()
+    else {
+      // woken after it's done
+      continue;
+    };
+    let waker = Waker::from(job.clone());
+    let mut cx = Context::from_waker(&waker);
+    // a panic drops the future, the other jobs keep running
+    if let Ok(Poll::Pending) =
+      panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx)))
+    {
+      *job
+        .future
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(future);
+    }
+  }
+}
+
+#[cfg(not(feature = "noop"))]
+pub(crate) struct SendableResolver<
+  Data: 'static + Send,
+  R: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+> {
+  inner: R,
+  _data: PhantomData<Data>,
+}
+
+#[cfg(not(feature = "noop"))]
+// the `SendableResolver` will be only called in the `threadsafe_function_call_js` callback
+// which means it will be always called in the Node.js JavaScript thread
+// so the inner function is not required to be `Send`
+// but the `Send` bound is required by the `Executor::spawn` function
+unsafe impl<Data: 'static + Send, R: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>>
+  Send for SendableResolver<Data, R>
+{
+}
+
+#[cfg(not(feature = "noop"))]
+impl<Data: 'static + Send, R: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>>
+  SendableResolver<Data, R>
+{
+  pub(crate) fn new(inner: R) -> Self {
+    Self {
+      inner,
+      _data: PhantomData,
+    }
+  }
+
+  pub(crate) fn resolve(self, env: sys::napi_env, data: Data) -> Result<sys::napi_value> {
+    (self.inner)(env, data)
+  }
+}
+
+#[cfg(feature = "noop")]
+#[allow(unused)]
+pub fn execute_future<
+  Data: 'static + Send,
+  Fut: 'static + Send + Future<Output = std::result::Result<Data, impl Into<Error>>>,
+  Resolver: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+>(
+  env: sys::napi_env,
+  fut: Fut,
+  resolver: Resolver,
+) -> Result<sys::napi_value> {
+  Ok(std::ptr::null_mut())
+}
+
+#[cfg(not(feature = "noop"))]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+/// Spawn `fut` on the [`executor`], and return a `Promise` settled with its output.
+///
+/// A panic in `fut` rejects the `Promise`.
+pub fn execute_future<
+  Data: 'static + Send,
+  Fut: 'static + Send + Future<Output = std::result::Result<Data, impl Into<Error>>>,
+  Resolver: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+>(
+  env: sys::napi_env,
+  fut: Fut,
+  resolver: Resolver,
+) -> Result<sys::napi_value> {
+  let env = Env::from_raw(env);
+  let (deferred, promise) = JsDeferred::new(&env)?;
+  let sendable_resolver = SendableResolver::new(resolver);
+
+  let mut fut = Box::pin(fut);
+  executor().spawn(Box::pin(async move {
+    let output = std::future::poll_fn(|cx| {
+      match panic::catch_unwind(AssertUnwindSafe(|| fut.as_mut().poll(cx))) {
+        Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+        Ok(Poll::Pending) => Poll::Pending,
+        Err(reason) => Poll::Ready(Err(reason)),
+      }
+    })
+    .await;
+    match output {
+      Ok(Ok(v)) => deferred.resolve(move |env| {
+        sendable_resolver
+          .resolve(env.raw(), v)
+          .map(|v| unsafe { Unknown::from_raw_unchecked(env.raw(), v) })
+      }),
+      Ok(Err(e)) => deferred.reject(e.into()),
+      Err(reason) => {
+        let reason = reason
+          .downcast_ref::<&str>()
+          .map(|s| s.to_string())
+          .or_else(|| reason.downcast_ref::<String>().cloned())
+          .unwrap_or_else(|| "Panic in async function".to_owned());
+        deferred.reject(Error::new(Status::GenericFailure, reason))
+      }
+    }
+  }));
+
+  Ok(promise.0.value)
+}
@@ -67,6 +67,29 @@ impl JsDate<'_> {
     )
   }
 
+  /// The milliseconds since the epoch of the `Date` converted into `type_name`, it fails for an invalid date,
+  /// whose value is `NaN`.
+  #[cfg(any(feature = "chrono_date", feature = "time_date"))]
+  pub(crate) unsafe fn millis_since_epoch(
+    env: sys::napi_env,
+    napi_val: sys::napi_value,
+    type_name: &str,
+  ) -> Result<i64> {
+    let mut milliseconds_since_epoch_utc = 0.0;
+    check_status!(
+      unsafe { sys::napi_get_date_value(env, napi_val, &mut milliseconds_since_epoch_utc) },
+      "Failed to convert napi value into rust type `{}`",
+      type_name,
+    )?;
+    if milliseconds_since_epoch_utc.is_nan() {
+      return Err(Error::new(
+        Status::DateExpected,
+        "Found invalid date".to_owned(),
+      ));
+    }
+    Ok(milliseconds_since_epoch_utc as i64)
+  }
+
   pub fn value_of(&self) -> Result<f64> {
     let mut timestamp: f64 = 0.0;
     check_status!(unsafe { sys::napi_get_date_value(self.0.env, self.0.value, &mut timestamp) })?;
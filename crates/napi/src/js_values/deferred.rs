@@ -43,6 +43,14 @@ impl DeferredTrace {
   }
 
   fn into_rejected(self, raw_env: sys::napi_env, mut err: Error) -> Result<sys::napi_value> {
+    if err.maybe_value.is_some() {
+      // structured values are rejected as is, without the trace
+      check_status!(
+        unsafe { sys::napi_delete_reference(raw_env, self.0) },
+        "Failed to delete reference in DeferredTrace"
+      )?;
+      return Ok(unsafe { crate::JsError::from(err).into_value(raw_env) });
+    }
     let env = Env::from_raw(raw_env);
     let mut raw = ptr::null_mut();
     check_status!(
@@ -227,6 +235,7 @@ fn js_deferred_new_raw(
     },
     "Create threadsafe function in JsDeferred failed"
   )?;
+  crate::cleanup_env::on_threadsafe_function_created(env.0);
 
   let promise = Object::from_raw(env.0, raw_promise);
 
@@ -1,5 +1,9 @@
+use std::cell::OnceCell;
+use std::ops::Deref;
+
 use super::*;
-use crate::bindgen_runtime::{FnArgs, FromNapiValue, Function, Unknown};
+use crate::bindgen_runtime::{FnArgs, FromNapiValue, Function, Object, Unknown};
+use crate::{Error, Status};
 
 pub struct JsGlobal<'env>(
   pub(crate) Value,
@@ -83,6 +87,12 @@ impl JSON<'_> {
     let func: Function<V, std::string::String> = self.get_named_property_unchecked("stringify")?;
     func.call(value)
   }
+
+  /// `JSON.parse(text)`, converted into `T`.
+  pub fn parse<T: FromNapiValue>(&self, text: &str) -> Result<T> {
+    let func: Function<&str, T> = self.get_named_property_unchecked("parse")?;
+    func.call(text)
+  }
 }
 
 type SupportType<'a> = Function<'a, FnArgs<(Function<'a, (), Unknown<'a>>, f64)>, JsTimeout<'a>>;
@@ -120,3 +130,186 @@ impl<'env> JsGlobal<'env> {
     func.call(timer)
   }
 }
+
+/// The global object returned by [`crate::Env::global_this`], with the typed accessors of the common globals.
+///
+/// The globals are looked up once and cached in it, so keep it while accessing them repeatedly in a call.
+/// It dereferences to [`JsGlobal`] for the other properties.
+pub struct Global<'env> {
+  global: JsGlobal<'env>,
+  process: OnceCell<sys::napi_value>,
+  console: OnceCell<sys::napi_value>,
+  json: OnceCell<sys::napi_value>,
+  queue_microtask: OnceCell<sys::napi_value>,
+  structured_clone: OnceCell<sys::napi_value>,
+}
+
+impl<'env> Global<'env> {
+  pub(crate) fn new(global: JsGlobal<'env>) -> Self {
+    Self {
+      global,
+      process: OnceCell::new(),
+      console: OnceCell::new(),
+      json: OnceCell::new(),
+      queue_microtask: OnceCell::new(),
+      structured_clone: OnceCell::new(),
+    }
+  }
+
+  pub fn process(&self) -> Result<Process<'env>> {
+    let process = self.cached(&self.process, "process")?;
+    unsafe { Process::from_napi_value(self.global.0.env, process) }
+  }
+
+  pub fn console(&self) -> Result<Console<'env>> {
+    let console = self.cached(&self.console, "console")?;
+    unsafe { Console::from_napi_value(self.global.0.env, console) }
+  }
+
+  pub fn json(&self) -> Result<JSON<'env>> {
+    let json = self.cached(&self.json, "JSON")?;
+    unsafe { JSON::from_napi_value(self.global.0.env, json) }
+  }
+
+  /// `queueMicrotask(callback)`
+  pub fn queue_microtask(&self, callback: Function<(), Unknown>) -> Result<()> {
+    let queue_microtask = self.cached(&self.queue_microtask, "queueMicrotask")?;
+    let func: Function<Function<(), Unknown>, ()> =
+      unsafe { Function::from_napi_value(self.global.0.env, queue_microtask)? };
+    func.call(callback)
+  }
+
+  /// `structuredClone(value)`, converted into `T`. It's available since Node.js 17.
+  pub fn structured_clone<V: ToNapiValue, T: FromNapiValue>(&self, value: V) -> Result<T> {
+    let structured_clone = self.cached(&self.structured_clone, "structuredClone")?;
+    let func: Function<V, T> =
+      unsafe { Function::from_napi_value(self.global.0.env, structured_clone)? };
+    func.call(value)
+  }
+
+  fn cached(&self, cell: &OnceCell<sys::napi_value>, name: &str) -> Result<sys::napi_value> {
+    if let Some(value) = cell.get() {
+      return Ok(*value);
+    }
+    let value: Unknown = self.global.get_named_property_unchecked(name)?;
+    if value.get_type()? == ValueType::Undefined {
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!("`{name}` is not available in the runtime"),
+      ));
+    }
+    Ok(*cell.get_or_init(|| value.raw()))
+  }
+}
+
+impl<'env> Deref for Global<'env> {
+  type Target = JsGlobal<'env>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.global
+  }
+}
+
+/// The `process` object of Node.js.
+pub struct Process<'env>(
+  pub(crate) Value,
+  pub(crate) std::marker::PhantomData<&'env ()>,
+);
+
+impl<'env> JsValue<'env> for Process<'env> {
+  fn value(&self) -> Value {
+    self.0
+  }
+}
+
+impl<'env> JsObjectValue<'env> for Process<'env> {}
+
+impl FromNapiValue for Process<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Process(
+      Value {
+        env,
+        value: napi_val,
+        value_type: ValueType::Object,
+      },
+      std::marker::PhantomData,
+    ))
+  }
+}
+
+impl Process<'_> {
+  /// `process.platform`
+  pub fn platform(&self) -> Result<std::string::String> {
+    self.get_named_property("platform")
+  }
+
+  /// `process.env[name]`
+  pub fn env_var(&self, name: &str) -> Result<Option<std::string::String>> {
+    let env: Object = self.get_named_property_unchecked("env")?;
+    env.get_named_property(name)
+  }
+
+  /// `process.cwd()`
+  pub fn cwd(&self) -> Result<std::string::String> {
+    let func: Function<(), std::string::String> = self.get_named_property_unchecked("cwd")?;
+    func.call(())
+  }
+
+  /// `process.emitWarning(message, warning_type)`
+  pub fn emit_warning(&self, message: &str, warning_type: &str) -> Result<()> {
+    let func: Function<FnArgs<(&str, &str)>, Unknown> =
+      self.get_named_property_unchecked("emitWarning")?;
+    func.call((message, warning_type).into())?;
+    Ok(())
+  }
+}
+
+/// The global `console` object.
+pub struct Console<'env>(
+  pub(crate) Value,
+  pub(crate) std::marker::PhantomData<&'env ()>,
+);
+
+impl<'env> JsValue<'env> for Console<'env> {
+  fn value(&self) -> Value {
+    self.0
+  }
+}
+
+impl<'env> JsObjectValue<'env> for Console<'env> {}
+
+impl FromNapiValue for Console<'_> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Console(
+      Value {
+        env,
+        value: napi_val,
+        value_type: ValueType::Object,
+      },
+      std::marker::PhantomData,
+    ))
+  }
+}
+
+impl Console<'_> {
+  /// `console.log(message)`
+  pub fn log(&self, message: &str) -> Result<()> {
+    self.print("log", message)
+  }
+
+  /// `console.warn(message)`
+  pub fn warn(&self, message: &str) -> Result<()> {
+    self.print("warn", message)
+  }
+
+  /// `console.error(message)`
+  pub fn error(&self, message: &str) -> Result<()> {
+    self.print("error", message)
+  }
+
+  fn print(&self, method: &str, message: &str) -> Result<()> {
+    let func: Function<&str, Unknown> = self.get_named_property_unchecked(method)?;
+    func.call(message)?;
+    Ok(())
+  }
+}
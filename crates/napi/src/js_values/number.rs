@@ -1,4 +1,5 @@
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
 
 use crate::{
   bindgen_runtime::{FromNapiValue, TypeName, ValidateNapiValue},
@@ -68,6 +69,33 @@ impl JsNumber<'_> {
   }
 }
 
+/// Compared by value with the `SameValueZero` algorithm, like the keys of JavaScript `Map`,
+/// so `NaN` equals `NaN` and `-0` equals `+0`.
+impl PartialEq for JsNumber<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    same_value_zero_bits(self.get_double().unwrap_or(f64::NAN))
+      == same_value_zero_bits(other.get_double().unwrap_or(f64::NAN))
+  }
+}
+
+impl Eq for JsNumber<'_> {}
+
+impl Hash for JsNumber<'_> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    same_value_zero_bits(self.get_double().unwrap_or(f64::NAN)).hash(state);
+  }
+}
+
+fn same_value_zero_bits(value: f64) -> u64 {
+  if value.is_nan() {
+    f64::NAN.to_bits()
+  } else if value == 0.0 {
+    0
+  } else {
+    value.to_bits()
+  }
+}
+
 impl TryFrom<JsNumber<'_>> for u32 {
   type Error = Error;
 
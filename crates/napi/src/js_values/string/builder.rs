@@ -0,0 +1,220 @@
+use std::fmt;
+use std::ptr;
+
+use crate::{
+  bindgen_runtime::{FromNapiValue, ToNapiValue, TypeName},
+  check_status, sys, Env, JsString, Result, ValueType,
+};
+
+/// Build a large JavaScript string from many segments, which is created by a single `napi_create_string_*` call.
+///
+/// The segments are kept in UTF-8 until a UTF-16 segment is pushed, then all of them are kept in UTF-16,
+/// so the unpaired surrogates of the UTF-16 segments and the JavaScript strings are preserved.
+/// It implements [`fmt::Write`], so `write!` can format into it, and it can be returned from the `#[napi]` functions.
+///
+/// ```
+/// let mut csv = JsStringBuilder::new();
+/// for row in rows {
+///   writeln!(csv, "{},{}", row.name, row.value)?;
+/// }
+/// csv.build(env)
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JsStringBuilder {
+  chars: Chars,
+}
+
+#[derive(Debug, Clone)]
+enum Chars {
+  Utf8(String),
+  Utf16(Vec<u16>),
+}
+
+impl Default for Chars {
+  fn default() -> Self {
+    Chars::Utf8(String::new())
+  }
+}
+
+impl JsStringBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Reserve `capacity` bytes for the UTF-8 segments.
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      chars: Chars::Utf8(String::with_capacity(capacity)),
+    }
+  }
+
+  pub fn push_str(&mut self, s: &str) -> &mut Self {
+    match &mut self.chars {
+      Chars::Utf8(chars) => chars.push_str(s),
+      Chars::Utf16(chars) => chars.extend(s.encode_utf16()),
+    }
+    self
+  }
+
+  pub fn push_char(&mut self, c: char) -> &mut Self {
+    match &mut self.chars {
+      Chars::Utf8(chars) => chars.push(c),
+      Chars::Utf16(chars) => chars.extend_from_slice(c.encode_utf16(&mut [0; 2])),
+    }
+    self
+  }
+
+  /// Push the UTF-16 code units in `chars`, which may contain unpaired surrogates.
+  pub fn push_utf16(&mut self, chars: &[u16]) -> &mut Self {
+    self.utf16().extend_from_slice(chars);
+    self
+  }
+
+  /// Push the characters of a JavaScript string, without converting it into a Rust `String` first.
+  pub fn push_js_string(&mut self, s: &JsString) -> Result<&mut Self> {
+    let len = s.utf16_len()?;
+    let chars = self.utf16();
+    let start = chars.len();
+    // end char len in C
+    chars.reserve(len + 1);
+    let mut written_char_count = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_value_string_utf16(
+          s.0.env,
+          s.0.value,
+          chars.as_mut_ptr().add(start),
+          len + 1,
+          &mut written_char_count,
+        )
+      },
+      "Failed to push the JavaScript string into JsStringBuilder"
+    )?;
+    // SAFETY: the `written_char_count` code units after `start` are initialized
+    unsafe { chars.set_len(start + written_char_count) };
+    Ok(self)
+  }
+
+  /// The length in bytes of the UTF-8 segments, or in code units after a UTF-16 segment is pushed.
+  pub fn len(&self) -> usize {
+    match &self.chars {
+      Chars::Utf8(chars) => chars.len(),
+      Chars::Utf16(chars) => chars.len(),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn clear(&mut self) {
+    match &mut self.chars {
+      Chars::Utf8(chars) => chars.clear(),
+      Chars::Utf16(chars) => chars.clear(),
+    }
+  }
+
+  /// Create the JavaScript string of all the segments.
+  pub fn build<'env>(&self, env: &'env Env) -> Result<JsString<'env>> {
+    unsafe { JsString::from_napi_value(env.0, self.create(env.0)?) }
+  }
+
+  fn create(&self, env: sys::napi_env) -> Result<sys::napi_value> {
+    let mut raw_value = ptr::null_mut();
+    match &self.chars {
+      Chars::Utf8(chars) => check_status!(
+        unsafe {
+          sys::napi_create_string_utf8(
+            env,
+            chars.as_ptr().cast(),
+            chars.len() as isize,
+            &mut raw_value,
+          )
+        },
+        "Failed to create the string of JsStringBuilder"
+      )?,
+      Chars::Utf16(chars) => check_status!(
+        unsafe {
+          sys::napi_create_string_utf16(env, chars.as_ptr(), chars.len() as isize, &mut raw_value)
+        },
+        "Failed to create the string of JsStringBuilder"
+      )?,
+    }
+    Ok(raw_value)
+  }
+
+  fn utf16(&mut self) -> &mut Vec<u16> {
+    if let Chars::Utf8(chars) = &self.chars {
+      let mut utf16 = Vec::with_capacity(chars.len());
+      utf16.extend(chars.encode_utf16());
+      self.chars = Chars::Utf16(utf16);
+    }
+    match &mut self.chars {
+      Chars::Utf16(chars) => chars,
+      Chars::Utf8(_) => unreachable!(),
+    }
+  }
+}
+
+impl fmt::Write for JsStringBuilder {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    self.push_str(s);
+    Ok(())
+  }
+
+  fn write_char(&mut self, c: char) -> fmt::Result {
+    self.push_char(c);
+    Ok(())
+  }
+}
+
+impl<S: AsRef<str>> Extend<S> for JsStringBuilder {
+  fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+    for s in iter {
+      self.push_str(s.as_ref());
+    }
+  }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for JsStringBuilder {
+  fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+    let mut builder = Self::new();
+    builder.extend(iter);
+    builder
+  }
+}
+
+impl TypeName for JsStringBuilder {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for JsStringBuilder {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    val.create(env)
+  }
+}
+
+impl ToNapiValue for &JsStringBuilder {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    val.create(env)
+  }
+}
+
+impl<'env> JsString<'env> {
+  /// Concatenate `self` and `others` into a new string, with a single `napi_create_string_utf16` call
+  /// rather than calling `String.prototype.concat` or converting them into Rust `String`s.
+  pub fn concat(&self, others: &[JsString]) -> Result<JsString<'env>> {
+    let mut builder = JsStringBuilder::new();
+    builder.push_js_string(self)?;
+    for other in others {
+      builder.push_js_string(other)?;
+    }
+    unsafe { JsString::from_napi_value(self.0.env, builder.create(self.0.env)?) }
+  }
+}
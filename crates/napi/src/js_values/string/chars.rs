@@ -0,0 +1,157 @@
+use std::ops::{Bound, RangeBounds};
+use std::ptr;
+
+use crate::{bindgen_runtime::FromNapiValue, check_status, sys, JsString, Result};
+
+impl<'env> JsString<'env> {
+  /// The characters and their UTF-16 offsets, which are the indices of the string in JavaScript.
+  ///
+  /// The unpaired surrogates are yielded as `U+FFFD`. The UTF-16 code units are copied once,
+  /// without transcoding the string into UTF-8.
+  pub fn char_indices(&self) -> Result<CharIndices> {
+    Ok(CharIndices {
+      units: self.utf16_prefix(None)?,
+      offset: 0,
+    })
+  }
+
+  /// The Unicode code points, like iterating the string with `for...of` in JavaScript.
+  ///
+  /// The unpaired surrogates are yielded as they are, so it's lossless unlike [`JsString::char_indices`].
+  pub fn code_points(&self) -> Result<CodePoints> {
+    Ok(CodePoints {
+      units: self.utf16_prefix(None)?,
+      offset: 0,
+    })
+  }
+
+  /// A new string of the characters in `range`, counted by code points rather than UTF-16 code units,
+  /// so a surrogate pair is never split.
+  ///
+  /// The range is clamped to the number of characters like `String.prototype.substring`.
+  /// Only the code units up to the end of the range are copied out of the engine.
+  pub fn substring_by_chars<R: RangeBounds<usize>>(&self, range: R) -> Result<JsString<'env>> {
+    let start = match range.start_bound() {
+      Bound::Included(start) => *start,
+      Bound::Excluded(start) => start.saturating_add(1),
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(end) => Some(end.saturating_add(1)),
+      Bound::Excluded(end) => Some(*end),
+      Bound::Unbounded => None,
+    };
+    // each character takes at most 2 code units
+    let units = self.utf16_prefix(end.map(|end| end.saturating_mul(2)))?;
+    let mut code_points = CodePoints { units, offset: 0 };
+    let mut start_offset = None;
+    let mut chars = 0;
+    while end.map_or(true, |end| chars < end) {
+      if chars == start {
+        start_offset = Some(code_points.offset);
+      }
+      if code_points.next().is_none() {
+        break;
+      }
+      chars += 1;
+    }
+    let end_offset = code_points.offset;
+    let substring =
+      &code_points.units[start_offset.unwrap_or(end_offset).min(end_offset)..end_offset];
+    let mut raw_value = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_string_utf16(
+          self.0.env,
+          substring.as_ptr(),
+          substring.len() as isize,
+          &mut raw_value,
+        )
+      },
+      "Failed to create the substring"
+    )?;
+    unsafe { JsString::from_napi_value(self.0.env, raw_value) }
+  }
+
+  // The first `max_len` code units, or all of them
+  fn utf16_prefix(&self, max_len: Option<usize>) -> Result<Vec<u16>> {
+    let len = self.utf16_len()?;
+    let len = max_len.map_or(len, |max_len| max_len.min(len));
+    // end char len in C
+    let mut units = Vec::with_capacity(len + 1);
+    let mut written_char_count = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_value_string_utf16(
+          self.0.env,
+          self.0.value,
+          units.as_mut_ptr(),
+          len + 1,
+          &mut written_char_count,
+        )
+      },
+      "Failed to get the UTF-16 code units of the string"
+    )?;
+    // SAFETY: the `written_char_count` code units are initialized
+    unsafe { units.set_len(written_char_count) };
+    Ok(units)
+  }
+}
+
+/// The iterator of [`JsString::char_indices`].
+pub struct CharIndices {
+  units: Vec<u16>,
+  offset: usize,
+}
+
+impl Iterator for CharIndices {
+  type Item = (usize, char);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let offset = self.offset;
+    let (code_point, len) = decode_code_point(&self.units[offset..])?;
+    self.offset += len;
+    Some((
+      offset,
+      char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER),
+    ))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = self.units.len() - self.offset;
+    (remaining.div_ceil(2), Some(remaining))
+  }
+}
+
+/// The iterator of [`JsString::code_points`].
+pub struct CodePoints {
+  units: Vec<u16>,
+  offset: usize,
+}
+
+impl Iterator for CodePoints {
+  type Item = u32;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (code_point, len) = decode_code_point(&self.units[self.offset..])?;
+    self.offset += len;
+    Some(code_point)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = self.units.len() - self.offset;
+    (remaining.div_ceil(2), Some(remaining))
+  }
+}
+
+// The first code point of `units` and its length in code units
+fn decode_code_point(units: &[u16]) -> Option<(u32, usize)> {
+  match *units {
+    [] => None,
+    [high @ 0xd800..=0xdbff, low @ 0xdc00..=0xdfff, ..] => Some((
+      0x10000 + (((high as u32) - 0xd800) << 10) + ((low as u32) - 0xdc00),
+      2,
+    )),
+    [unit, ..] => Some((unit as u32, 1)),
+  }
+}
@@ -1,3 +1,4 @@
+use std::hash::{Hash, Hasher};
 use std::mem::ManuallyDrop;
 
 use crate::JsString;
@@ -39,6 +40,20 @@ impl<'env> JsStringLatin1<'env> {
   }
 }
 
+impl PartialEq for JsStringLatin1<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl Eq for JsStringLatin1<'_> {}
+
+impl Hash for JsStringLatin1<'_> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_slice().hash(state);
+  }
+}
+
 impl From<JsStringLatin1<'_>> for Vec<u8> {
   fn from(value: JsStringLatin1) -> Self {
     value.take()
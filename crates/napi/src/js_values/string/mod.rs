@@ -1,18 +1,26 @@
+#[cfg(feature = "napi10")]
+use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::mem;
 use std::ptr;
 
+#[cfg(feature = "napi10")]
+use crate::Env;
 use crate::{
   bindgen_runtime::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue},
   check_status, sys, Result, Value, ValueType,
 };
 
+pub use builder::JsStringBuilder;
+pub use chars::{CharIndices, CodePoints};
 pub use latin1::JsStringLatin1;
 pub use utf16::JsStringUtf16;
 pub use utf8::JsStringUtf8;
 
 use super::JsValue;
 
+mod builder;
+mod chars;
 mod latin1;
 mod utf16;
 mod utf8;
@@ -164,4 +172,91 @@ impl<'env> JsString<'env> {
       }),
     })
   }
+
+  #[cfg(feature = "napi10")]
+  /// Create a string of the UTF-16 code units in `chars` without copying them into the JavaScript heap.
+  ///
+  /// The `Vec` is dropped by the finalizer after the string is garbage collected.
+  /// The engine may copy the string instead, like for the short strings, then the `Vec` is dropped immediately.
+  pub fn from_external_utf16(env: &'env Env, chars: Vec<u16>) -> Result<Self> {
+    create_external_string(
+      env,
+      chars,
+      |env, chars, len, finalize, hint, result, copied| unsafe {
+        sys::node_api_create_external_string_utf16(env, chars, len, finalize, hint, result, copied)
+      },
+    )
+  }
+
+  #[cfg(feature = "napi10")]
+  /// Create a string of the latin1 characters in `chars` without copying them into the JavaScript heap.
+  ///
+  /// The `Vec` is dropped by the finalizer after the string is garbage collected.
+  /// The engine may copy the string instead, like for the short strings, then the `Vec` is dropped immediately.
+  pub fn from_external_latin1(env: &'env Env, chars: Vec<u8>) -> Result<Self> {
+    create_external_string(
+      env,
+      chars,
+      |env, chars, len, finalize, hint, result, copied| unsafe {
+        sys::node_api_create_external_string_latin1(
+          env,
+          chars.cast(),
+          len,
+          finalize,
+          hint,
+          result,
+          copied,
+        )
+      },
+    )
+  }
+}
+
+#[cfg(feature = "napi10")]
+type CreateExternalString<T> = fn(
+  sys::napi_env,
+  *const T,
+  isize,
+  sys::node_api_basic_finalize,
+  *mut c_void,
+  *mut sys::napi_value,
+  *mut bool,
+) -> sys::napi_status;
+
+#[cfg(feature = "napi10")]
+fn create_external_string<'env, T>(
+  env: &'env Env,
+  chars: Vec<T>,
+  create: CreateExternalString<T>,
+) -> Result<JsString<'env>> {
+  let mut chars = mem::ManuallyDrop::new(chars);
+  let data = chars.as_mut_ptr();
+  let hint = Box::into_raw(Box::new((chars.len(), chars.capacity())));
+  let mut raw_value = ptr::null_mut();
+  // the finalizer is already called if the string is copied
+  let mut copied = false;
+  let status = create(
+    env.0,
+    data,
+    chars.len() as isize,
+    Some(drop_external_string::<T>),
+    hint.cast(),
+    &mut raw_value,
+    &mut copied,
+  );
+  if status != sys::Status::napi_ok {
+    unsafe { drop_external_string::<T>(ptr::null_mut(), data.cast(), hint.cast()) };
+  }
+  check_status!(status, "Failed to create external string")?;
+  unsafe { JsString::from_napi_value(env.0, raw_value) }
+}
+
+#[cfg(feature = "napi10")]
+unsafe extern "C" fn drop_external_string<T>(
+  _env: sys::node_api_basic_env,
+  finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  let (len, cap) = *unsafe { Box::from_raw(finalize_hint.cast::<(usize, usize)>()) };
+  drop(unsafe { Vec::from_raw_parts(finalize_data.cast::<T>(), len, cap) });
 }
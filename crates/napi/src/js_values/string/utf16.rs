@@ -1,4 +1,5 @@
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
 use crate::{Error, JsString, Result, Status};
@@ -34,6 +35,20 @@ impl<'env> JsStringUtf16<'env> {
   }
 }
 
+impl PartialEq for JsStringUtf16<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.buf == other.buf
+  }
+}
+
+impl Eq for JsStringUtf16<'_> {}
+
+impl Hash for JsStringUtf16<'_> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.buf.hash(state);
+  }
+}
+
 impl TryFrom<JsStringUtf16<'_>> for String {
   type Error = Error;
 
@@ -1,4 +1,5 @@
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
 use std::str;
 
 use crate::{Error, JsString, Result};
@@ -38,6 +39,20 @@ impl<'env> JsStringUtf8<'env> {
   }
 }
 
+impl PartialEq for JsStringUtf8<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.buf == other.buf
+  }
+}
+
+impl Eq for JsStringUtf8<'_> {}
+
+impl Hash for JsStringUtf8<'_> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.buf.hash(state);
+  }
+}
+
 impl TryFrom<JsStringUtf8<'_>> for String {
   type Error = Error;
 
@@ -2,7 +2,7 @@ use std::ptr;
 
 use crate::{
   bindgen_runtime::{FromNapiValue, TypeName, ValidateNapiValue},
-  sys, type_of, JsValue, Result, Value, ValueType,
+  check_status, sys, type_of, JsValue, Result, Value, ValueType,
 };
 
 #[derive(Clone, Copy)]
@@ -50,11 +50,45 @@ impl FromNapiValue for Unknown<'_> {
   }
 }
 
-impl Unknown<'_> {
+impl<'env> Unknown<'env> {
   pub fn get_type(&self) -> Result<ValueType> {
     type_of!(self.0.env, self.0.value)
   }
 
+  /// Compare with `other` like the `===` operator in JavaScript.
+  pub fn strict_equals<V: JsValue<'env>>(&self, other: V) -> Result<bool> {
+    let mut result = false;
+    check_status!(unsafe {
+      sys::napi_strict_equals(self.0.env, self.0.value, other.raw(), &mut result)
+    })?;
+    Ok(result)
+  }
+
+  /// Compare with `other` like `Object.is` in JavaScript.
+  ///
+  /// It's the same as `strict_equals` except that `NaN` equals `NaN`, and `-0` doesn't equal `+0`.
+  pub fn same_value<V: JsValue<'env>>(&self, other: V) -> Result<bool> {
+    let other = other.value();
+    if self.get_type()? == ValueType::Number
+      && type_of!(other.env, other.value)? == ValueType::Number
+    {
+      let mut a = 0_f64;
+      let mut b = 0_f64;
+      check_status!(unsafe { sys::napi_get_value_double(self.0.env, self.0.value, &mut a) })?;
+      check_status!(unsafe { sys::napi_get_value_double(other.env, other.value, &mut b) })?;
+      return Ok(if a.is_nan() {
+        b.is_nan()
+      } else {
+        a.to_bits() == b.to_bits()
+      });
+    }
+    let mut result = false;
+    check_status!(unsafe {
+      sys::napi_strict_equals(self.0.env, self.0.value, other.value, &mut result)
+    })?;
+    Ok(result)
+  }
+
   /// # Safety
   ///
   /// This function should be called after `JsUnknown::get_type`
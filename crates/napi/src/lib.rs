@@ -17,6 +17,7 @@
 //! ### tokio_rt
 //! With `tokio_rt` feature, `napi-rs` provides a ***tokio runtime*** in an additional thread.
 //! And you can easily run tokio `future` in it and return `promise`.
+//! Without it, `#[napi] async fn` runs on a fallback thread, see the [`executor`] module.
 //!
 //! ```
 //! use futures::prelude::*;
@@ -64,6 +65,29 @@
 //! }
 //! ```
 //!
+//! ### minimal
+//!
+//! For the size sensitive deployments. It compiles out the `buffer_diagnostics` registry and the
+//! `trace-buffer-drops` events, and the errors of the failed N-API calls only carry the status instead of the formatted messages.
+//!
+//! ### strict-register
+//!
+//! Verify the `#[napi]` exports while the module is loaded. The exports which failed to register,
+//! are missing from the `exports` object or share a name with another export are collected,
+//! and one error listing all of them is thrown, so `require` fails instead of leaving the exports `undefined`.
+//!
+//! ### ndarray
+//!
+//! View the TypedArrays as multi-dimensional [ndarray](https://docs.rs/ndarray) arrays without copying,
+//! and return `ndarray::Array` to JavaScript as `{ data, shape }`.
+//!
+//! ```
+//! #[napi]
+//! fn transpose(matrix: Float64Array, rows: u32, cols: u32) -> Result<Array2<f64>> {
+//!     Ok(matrix.view_as_array2((rows as usize, cols as usize))?.t().to_owned())
+//! }
+//! ```
+//!
 
 #[cfg(all(target_family = "wasm", not(feature = "noop"), feature = "napi3"))]
 #[link(wasm_import_module = "napi")]
@@ -78,25 +102,46 @@ extern "C" {
 #[cfg(feature = "napi8")]
 mod async_cleanup_hook;
 #[cfg(feature = "napi8")]
-pub use async_cleanup_hook::AsyncCleanupHook;
+pub use async_cleanup_hook::{AsyncCleanupHook, CleanupHandle};
 mod async_work;
 mod bindgen_runtime;
+#[cfg(not(feature = "minimal"))]
+pub mod buffer_diagnostics;
+#[cfg(all(feature = "tokio_rt", feature = "napi5"))]
+pub mod byte_channel;
 #[cfg(feature = "compat-mode")]
 mod call_context;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+mod channel_to_js;
 #[cfg(feature = "napi3")]
 mod cleanup_env;
 mod env;
+#[cfg(feature = "napi6")]
+mod env_local;
 mod error;
+mod error_sink;
+#[cfg(feature = "napi4")]
+pub mod event_emitter;
+#[cfg(feature = "napi4")]
+pub mod executor;
 mod js_values;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+pub mod runtime;
 mod status;
 mod task;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 mod tokio_runtime;
 mod value_type;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+pub use channel_to_js::{channel_to_js, channel_to_js_batched, ChannelToJsOptions};
 #[cfg(feature = "napi3")]
-pub use cleanup_env::CleanupEnvHook;
+pub use cleanup_env::{CleanupEnvHook, CleanupPhase, PhasedCleanupHook};
+#[cfg(all(feature = "tokio_rt", feature = "napi5"))]
+pub mod streams;
 #[cfg(feature = "napi4")]
 pub mod threadsafe_function;
+#[cfg(all(feature = "napi3", not(target_family = "wasm")))]
+pub mod uv;
 
 mod version;
 
@@ -108,12 +153,15 @@ pub use call_context::CallContext;
 
 pub use bindgen_runtime::iterator;
 pub use env::*;
+#[cfg(feature = "napi6")]
+pub use env_local::EnvLocal;
 pub use error::*;
+pub use error_sink::{ErrorHandler, ErrorSink, UncaughtException};
 pub use js_values::*;
 pub use status::Status;
-pub use task::Task;
+pub use task::{Interrupt, Task};
 pub use value_type::*;
-pub use version::NodeVersion;
+pub use version::{Feature, NodeVersion};
 #[cfg(feature = "serde-json")]
 #[macro_use]
 extern crate serde;
@@ -154,11 +202,16 @@ macro_rules! assert_type_of {
 pub mod bindgen_prelude {
   #[cfg(all(feature = "compat-mode", not(feature = "noop")))]
   pub use crate::bindgen_runtime::register_module_exports;
+  // the async functions are driven by the tokio runtime, or by the fallback executor without tokio
+  #[cfg(all(feature = "napi4", not(feature = "tokio_rt")))]
+  pub use crate::executor::execute_future;
+  #[cfg(feature = "tokio_rt")]
+  pub use crate::tokio_runtime::execute_tokio_future as execute_future;
   #[cfg(feature = "tokio_rt")]
   pub use crate::tokio_runtime::*;
   pub use crate::{
     assert_type_of, bindgen_runtime::*, check_pending_exception, check_status,
-    check_status_or_throw, error, error::*, sys, type_of, JsError, JsValue, Property,
+    check_status_or_throw, error, error::*, sys, type_of, Interrupt, JsError, JsValue, Property,
     PropertyAttributes, Result, Status, Task, ValueType,
   };
 
@@ -181,6 +234,9 @@ pub mod __private {
 
   use crate::sys;
 
+  /// Whether the `minimal` feature is enabled, the exported macros check it to skip formatting the error messages.
+  pub const MINIMAL: bool = cfg!(feature = "minimal");
+
   pub unsafe fn log_js_value<V: AsRef<[sys::napi_value]>>(
     // `info`, `log`, `warning` or `error`
     method: &str,
@@ -0,0 +1,100 @@
+//! Configure the tokio runtime which napi-rs creates on the first async call.
+//!
+//! The runtime uses tokio's defaults, one worker thread per core, which adds up when the addon is loaded in every
+//! Electron renderer. The builder can be adjusted before the first use:
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! #[napi_derive::module_init]
+//! fn init() {
+//!   napi::runtime::configure(|builder| {
+//!     builder.worker_threads(2).thread_name("my-addon");
+//!   })
+//!   .unwrap();
+//!   napi::runtime::shutdown_timeout(Duration::from_secs(1));
+//! }
+//! ```
+//!
+//! A runtime passed to `create_custom_tokio_runtime` is used as is.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{Error, Result, Status};
+
+type Configure = Arc<dyn Fn(&mut Builder) + Send + Sync>;
+
+struct RuntimeConfig {
+  configure: Option<Configure>,
+  shutdown_timeout: Option<Duration>,
+  // the runtime is created, `configure` has no effect anymore
+  started: bool,
+}
+
+static CONFIG: Mutex<RuntimeConfig> = Mutex::new(RuntimeConfig {
+  configure: None,
+  shutdown_timeout: None,
+  started: false,
+});
+
+fn config() -> MutexGuard<'static, RuntimeConfig> {
+  CONFIG
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Adjust the builder of the tokio runtime, `enable_all` is already applied to it.
+///
+/// It's called again if the runtime is recreated, like when an Electron window reloads.
+/// It's called without holding any lock, so it may call [`shutdown_timeout`].
+/// Returns an error if the runtime is already started.
+pub fn configure<F>(configure: F) -> Result<()>
+where
+  F: Fn(&mut Builder) + Send + Sync + 'static,
+{
+  let mut config = config();
+  if config.started {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "The tokio runtime of napi-rs is already started, `configure` must be called before the first use",
+    ));
+  }
+  config.configure = Some(Arc::new(configure));
+  Ok(())
+}
+
+/// Wait up to `timeout` for the spawned tasks when the runtime is shut down with the env.
+///
+/// By default the runtime is shut down in the background without waiting.
+pub fn shutdown_timeout(timeout: Duration) {
+  config().shutdown_timeout = Some(timeout);
+}
+
+pub(crate) fn mark_started() {
+  config().started = true;
+}
+
+pub(crate) fn build(builder: &mut Builder) -> Runtime {
+  // cloned out of the lock, so the closure is able to call back into this module
+  let configure = {
+    let mut config = config();
+    config.started = true;
+    config.configure.clone()
+  };
+  builder.enable_all();
+  if let Some(configure) = configure {
+    configure(builder);
+  }
+  builder.build().expect("Create tokio runtime failed")
+}
+
+pub(crate) fn shutdown(rt: Runtime) {
+  let timeout = config().shutdown_timeout;
+  match timeout {
+    Some(timeout) => rt.shutdown_timeout(timeout),
+    None => rt.shutdown_background(),
+  }
+}
@@ -0,0 +1,464 @@
+//! Adapters between the Node.js streams and the tokio I/O traits.
+//!
+//! [`NodeReadable`] implements [`AsyncRead`] on a `stream.Readable` and [`NodeWritable`] implements [`AsyncWrite`] on a `stream.Writable`,
+//! so the Rust async code can consume and produce Node.js streams directly, for example with `tokio::io::copy`.
+//! They're created on the JavaScript thread and can be moved to the tokio runtime,
+//! the stream is driven by its event listeners and by the threadsafe functions calling its methods.
+//!
+//! ```rust
+//! #[napi]
+//! pub fn pipe_through_rust(env: &Env, input: Object, output: Object) -> Result<AsyncBlock<u64>> {
+//!   let mut reader = NodeReadable::new(&input)?;
+//!   let mut writer = NodeWritable::new(&output)?;
+//!   AsyncBlockBuilder::new(async move {
+//!     let copied = tokio::io::copy(&mut reader, &mut writer).await?;
+//!     writer.shutdown().await?;
+//!     Ok(copied)
+//!   })
+//!   .build(env)
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::ffi::CStr;
+use std::io;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{
+  bindgen_prelude::{
+    Buffer, BufferSlice, Either, FromNapiValue, Function, FunctionCallContext, JsObjectValue,
+    Object, Unknown,
+  },
+  check_status, sys,
+  threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+  Env, JsValue, Result, Status,
+};
+
+/// The default number of bytes buffered by [`NodeReadable`] before the stream is paused,
+/// and submitted by [`NodeWritable`] before the writes wait for JavaScript, the same as the Node.js default for the byte streams.
+pub const DEFAULT_HIGH_WATER_MARK: usize = 16 * 1024;
+
+type StreamMethod = ThreadsafeFunction<(), Unknown<'static>, (), Status, false, true>;
+
+#[derive(Default)]
+struct ReadState {
+  chunks: VecDeque<Vec<u8>>,
+  // bytes of the first chunk which are read already
+  offset: usize,
+  buffered: usize,
+  paused: bool,
+  ended: bool,
+  error: Option<String>,
+  // the `NodeReadable` is dropped, the data is discarded
+  closed: bool,
+  waker: Option<Waker>,
+}
+
+/// A `stream.Readable` read by [`AsyncRead`].
+///
+/// The chunks emitted by the `data` events are queued, the stream is paused once `high_water_mark` bytes are queued,
+/// and resumed when the queue is drained by the reads. A chunk of string is read as its UTF-8 bytes.
+/// The bytes queued when the stream errors are read before the error.
+/// After the `NodeReadable` is dropped, the stream keeps flowing and the data is discarded.
+pub struct NodeReadable {
+  state: Arc<Mutex<ReadState>>,
+  resume: StreamMethod,
+  high_water_mark: usize,
+}
+
+impl NodeReadable {
+  pub fn new(stream: &Object) -> Result<Self> {
+    Self::with_high_water_mark(stream, DEFAULT_HIGH_WATER_MARK)
+  }
+
+  /// Create a `NodeReadable` which buffers at most about `high_water_mark` bytes before pausing the stream.
+  pub fn with_high_water_mark(stream: &Object, high_water_mark: usize) -> Result<Self> {
+    let env = Env::from(stream.value().env);
+    let state = Arc::new(Mutex::new(ReadState::default()));
+    let resume = stream_method(stream, "resume")?;
+
+    let data_state = state.clone();
+    let on_data = env.create_function_from_closure::<(), (), _>("onData", move |ctx| {
+      let chunk = match ctx.first_arg::<Either<BufferSlice, String>>()? {
+        Either::A(buffer) => buffer.to_vec(),
+        Either::B(string) => string.into_bytes(),
+      };
+      let mut state = lock(&data_state)?;
+      if state.closed {
+        return Ok(());
+      }
+      state.buffered += chunk.len();
+      state.chunks.push_back(chunk);
+      wake(&mut state.waker);
+      if state.buffered >= high_water_mark && !state.paused {
+        state.paused = true;
+        drop(state);
+        call_method(&ctx, "pause")?;
+      }
+      Ok(())
+    })?;
+    let end_state = state.clone();
+    let on_end = env.create_function_from_closure::<(), (), _>("onEnd", move |_| {
+      let mut state = lock(&end_state)?;
+      state.ended = true;
+      wake(&mut state.waker);
+      Ok(())
+    })?;
+    let error_state = state.clone();
+    let on_error = env.create_function_from_closure::<(), (), _>("onError", move |ctx| {
+      let message = error_message(&ctx)?;
+      let mut state = lock(&error_state)?;
+      state.error.get_or_insert(message);
+      wake(&mut state.waker);
+      Ok(())
+    })?;
+    let close_state = state.clone();
+    let on_close = env.create_function_from_closure::<(), (), _>("onClose", move |_| {
+      let mut state = lock(&close_state)?;
+      if !state.ended && state.error.is_none() {
+        state.error = Some("The stream was closed before the end".to_owned());
+      }
+      wake(&mut state.waker);
+      Ok(())
+    })?;
+    listen(stream, c"end", on_end)?;
+    listen(stream, c"error", on_error)?;
+    listen(stream, c"close", on_close)?;
+    // the `data` listener switches the stream into the flowing mode
+    listen(stream, c"data", on_data)?;
+
+    Ok(Self {
+      state,
+      resume,
+      high_water_mark,
+    })
+  }
+
+  fn resume(&self) -> io::Result<()> {
+    match self
+      .resume
+      .call((), ThreadsafeFunctionCallMode::NonBlocking)
+    {
+      Status::Ok => Ok(()),
+      status => Err(io::Error::other(format!(
+        "Failed to resume the stream: {}",
+        status
+      ))),
+    }
+  }
+}
+
+impl AsyncRead for NodeReadable {
+  fn poll_read(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<io::Result<()>> {
+    let mut state = lock(&self.state)?;
+    if state.chunks.is_empty() {
+      if let Some(error) = &state.error {
+        return Poll::Ready(Err(io::Error::other(error.clone())));
+      }
+      if state.ended {
+        return Poll::Ready(Ok(()));
+      }
+      state.waker = Some(cx.waker().clone());
+      if state.paused {
+        state.paused = false;
+        drop(state);
+        self.resume()?;
+      }
+      return Poll::Pending;
+    }
+    while buf.remaining() > 0 {
+      let offset = state.offset;
+      let Some(chunk) = state.chunks.front() else {
+        break;
+      };
+      let n = (chunk.len() - offset).min(buf.remaining());
+      buf.put_slice(&chunk[offset..offset + n]);
+      if offset + n == chunk.len() {
+        state.chunks.pop_front();
+        state.offset = 0;
+      } else {
+        state.offset += n;
+      }
+      state.buffered -= n;
+    }
+    if state.paused && state.buffered < self.high_water_mark / 2 {
+      state.paused = false;
+      drop(state);
+      self.resume()?;
+    }
+    Poll::Ready(Ok(()))
+  }
+}
+
+impl Drop for NodeReadable {
+  fn drop(&mut self) {
+    let Ok(mut state) = self.state.lock() else {
+      return;
+    };
+    state.closed = true;
+    state.chunks.clear();
+    state.buffered = 0;
+    if std::mem::take(&mut state.paused) {
+      drop(state);
+      let _ = self.resume();
+    }
+  }
+}
+
+#[derive(Default)]
+struct WriteState {
+  // bytes submitted to `write` and not written by JavaScript yet
+  in_flight: usize,
+  // `write` returned `false`, waiting for the `drain` event
+  needs_drain: bool,
+  ending: bool,
+  finished: bool,
+  error: Option<String>,
+  waker: Option<Waker>,
+}
+
+/// A `stream.Writable` written by [`AsyncWrite`].
+///
+/// The writes are passed to `writable.write` on the JavaScript thread, they wait once `high_water_mark` bytes are in flight,
+/// or until the `drain` event if `write` returned `false`.
+/// [`AsyncWrite::poll_flush`] waits until all the writes are passed to the stream,
+/// and [`AsyncWrite::poll_shutdown`] calls `writable.end()` and waits for the `finish` event.
+pub struct NodeWritable {
+  state: Arc<Mutex<WriteState>>,
+  write: ThreadsafeFunction<Buffer, bool, Buffer, Status, false, true>,
+  end: StreamMethod,
+  high_water_mark: usize,
+}
+
+impl NodeWritable {
+  pub fn new(stream: &Object) -> Result<Self> {
+    Self::with_high_water_mark(stream, DEFAULT_HIGH_WATER_MARK)
+  }
+
+  /// Create a `NodeWritable` which has at most about `high_water_mark` bytes in flight.
+  pub fn with_high_water_mark(stream: &Object, high_water_mark: usize) -> Result<Self> {
+    let env = Env::from(stream.value().env);
+    let state = Arc::new(Mutex::new(WriteState::default()));
+    let write = stream
+      .get_named_property_unchecked::<Function<Buffer, bool>>("write")?
+      .bind(*stream)?
+      .build_threadsafe_function()
+      .weak::<true>()
+      .build()?;
+    let end = stream_method(stream, "end")?;
+
+    let drain_state = state.clone();
+    let on_drain = env.create_function_from_closure::<(), (), _>("onDrain", move |_| {
+      let mut state = lock(&drain_state)?;
+      state.needs_drain = false;
+      wake(&mut state.waker);
+      Ok(())
+    })?;
+    let finish_state = state.clone();
+    let on_finish = env.create_function_from_closure::<(), (), _>("onFinish", move |_| {
+      let mut state = lock(&finish_state)?;
+      state.finished = true;
+      wake(&mut state.waker);
+      Ok(())
+    })?;
+    let error_state = state.clone();
+    let on_error = env.create_function_from_closure::<(), (), _>("onError", move |ctx| {
+      let message = error_message(&ctx)?;
+      let mut state = lock(&error_state)?;
+      state.error.get_or_insert(message);
+      wake(&mut state.waker);
+      Ok(())
+    })?;
+    let close_state = state.clone();
+    let on_close = env.create_function_from_closure::<(), (), _>("onClose", move |_| {
+      let mut state = lock(&close_state)?;
+      if !state.finished && state.error.is_none() {
+        state.error = Some("The stream was closed before it finished".to_owned());
+      }
+      wake(&mut state.waker);
+      Ok(())
+    })?;
+    listen(stream, c"drain", on_drain)?;
+    listen(stream, c"finish", on_finish)?;
+    listen(stream, c"error", on_error)?;
+    listen(stream, c"close", on_close)?;
+
+    Ok(Self {
+      state,
+      write,
+      end,
+      high_water_mark,
+    })
+  }
+}
+
+impl AsyncWrite for NodeWritable {
+  fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+    let mut state = lock(&self.state)?;
+    if let Some(error) = &state.error {
+      return Poll::Ready(Err(io::Error::other(error.clone())));
+    }
+    if state.ending {
+      return Poll::Ready(Err(io::Error::new(
+        io::ErrorKind::BrokenPipe,
+        "Write after the stream is shut down",
+      )));
+    }
+    if buf.is_empty() {
+      return Poll::Ready(Ok(0));
+    }
+    if state.needs_drain || state.in_flight >= self.high_water_mark {
+      state.waker = Some(cx.waker().clone());
+      return Poll::Pending;
+    }
+    let len = buf.len();
+    state.in_flight += len;
+    drop(state);
+    let callback_state = self.state.clone();
+    let status = self.write.call_with_return_value(
+      Buffer::from(buf.to_vec()),
+      ThreadsafeFunctionCallMode::NonBlocking,
+      move |written, _| {
+        let mut state = lock(&callback_state)?;
+        state.in_flight -= len;
+        match written {
+          Ok(true) => {}
+          Ok(false) => state.needs_drain = true,
+          Err(err) => {
+            state.error.get_or_insert(err.reason.clone());
+          }
+        }
+        wake(&mut state.waker);
+        Ok(())
+      },
+    );
+    if status != Status::Ok {
+      return Poll::Ready(Err(io::Error::other(format!(
+        "Failed to write to the stream: {}",
+        status
+      ))));
+    }
+    Poll::Ready(Ok(len))
+  }
+
+  fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    let mut state = lock(&self.state)?;
+    if let Some(error) = &state.error {
+      return Poll::Ready(Err(io::Error::other(error.clone())));
+    }
+    if state.in_flight > 0 || state.needs_drain {
+      state.waker = Some(cx.waker().clone());
+      return Poll::Pending;
+    }
+    Poll::Ready(Ok(()))
+  }
+
+  fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    let mut state = lock(&self.state)?;
+    if state.finished {
+      return Poll::Ready(Ok(()));
+    }
+    if let Some(error) = &state.error {
+      return Poll::Ready(Err(io::Error::other(error.clone())));
+    }
+    state.waker = Some(cx.waker().clone());
+    if !state.ending {
+      state.ending = true;
+      drop(state);
+      // queued after the pending writes, so `end` is called after them
+      let status = self.end.call((), ThreadsafeFunctionCallMode::NonBlocking);
+      if status != Status::Ok {
+        return Poll::Ready(Err(io::Error::other(format!(
+          "Failed to end the stream: {}",
+          status
+        ))));
+      }
+    }
+    Poll::Pending
+  }
+}
+
+fn stream_method(stream: &Object, name: &str) -> Result<StreamMethod> {
+  stream
+    .get_named_property_unchecked::<Function<(), Unknown<'static>>>(name)?
+    .bind(*stream)?
+    .build_threadsafe_function()
+    .weak::<true>()
+    .build()
+}
+
+fn listen<Args, Return>(
+  stream: &Object,
+  event: &CStr,
+  listener: Function<Args, Return>,
+) -> Result<()>
+where
+  Args: crate::bindgen_prelude::JsValuesTupleIntoVec,
+{
+  let env = stream.value().env;
+  let mut on = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, stream.value().value, c"on".as_ptr(), &mut on) },
+    "Get on of the stream failed"
+  )?;
+  let mut event_name = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        event.as_ptr(),
+        event.count_bytes() as isize,
+        &mut event_name,
+      )
+    },
+    "Create the event name failed"
+  )?;
+  check_status!(
+    unsafe {
+      sys::napi_call_function(
+        env,
+        stream.value().value,
+        on,
+        2,
+        [event_name, listener.value().value].as_ptr(),
+        ptr::null_mut(),
+      )
+    },
+    "Listen to {:?} of the stream failed",
+    event
+  )
+}
+
+// Call the method `name` of `this` of the listener, which is the stream
+fn call_method(ctx: &FunctionCallContext, name: &str) -> Result<()> {
+  let stream = ctx.this::<Object>()?;
+  let method = stream.get_named_property_unchecked::<Function<(), Unknown>>(name)?;
+  method.apply(stream, ())?;
+  Ok(())
+}
+
+fn error_message(ctx: &FunctionCallContext) -> Result<String> {
+  let error = ctx.first_arg::<Unknown>()?;
+  unsafe { String::from_napi_value(ctx.env.raw(), error.coerce_to_string()?.raw()) }
+}
+
+fn lock<T>(state: &Mutex<T>) -> io::Result<MutexGuard<'_, T>> {
+  state
+    .lock()
+    .map_err(|_| io::Error::other("Poisoned lock of the stream state"))
+}
+
+fn wake(waker: &mut Option<Waker>) {
+  if let Some(waker) = waker.take() {
+    waker.wake();
+  }
+}
@@ -1,6 +1,9 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 use crate::{
   bindgen_runtime::{ToNapiValue, TypeName},
-  Env, Error, Result,
+  Env, Error, Result, Status,
 };
 
 pub trait Task: Send + Sized {
@@ -25,3 +28,33 @@ pub trait Task: Send + Sized {
     Ok(())
   }
 }
+
+#[derive(Clone, Default)]
+/// A cooperative interruption flag for the long computations.
+///
+/// Pass a clone of it into the `Task` or the spawned closures and call [`Interrupt::check`] at the check points,
+/// then `AsyncTask::with_interrupt` exposes an `interrupt()` method on the returned `Promise` to set it from JavaScript.
+pub struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Request the computation to stop at its next check point.
+  pub fn interrupt(&self) {
+    self.0.store(true, Ordering::Release);
+  }
+
+  pub fn is_interrupted(&self) -> bool {
+    self.0.load(Ordering::Acquire)
+  }
+
+  /// Return a `Cancelled` error if the computation is interrupted, use it with `?` at the check points.
+  pub fn check(&self) -> Result<()> {
+    if self.is_interrupted() {
+      return Err(Error::new(Status::Cancelled, "Interrupted".to_owned()));
+    }
+    Ok(())
+  }
+}
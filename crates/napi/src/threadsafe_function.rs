@@ -1,23 +1,38 @@
 #![allow(clippy::single_component_path_imports)]
 
+use std::collections::VecDeque;
 #[cfg(feature = "tokio_rt")]
 use std::convert::identity;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::ops::Deref;
 use std::os::raw::c_void;
 use std::ptr::{self, null_mut};
+use std::rc::Rc;
 use std::sync::{
   self,
-  atomic::{AtomicBool, AtomicPtr, Ordering},
-  Arc, RwLock, RwLockWriteGuard,
+  atomic::{AtomicBool, AtomicPtr, AtomicU64, AtomicUsize, Ordering},
+  Arc, Condvar, Mutex, Once, OnceLock,
 };
+use std::task::{Poll, Waker};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
 
 use crate::bindgen_runtime::{
   FromNapiValue, JsValuesTupleIntoVec, TypeName, Unknown, ValidateNapiValue,
 };
+use crate::error_sink::report_error;
 use crate::{
-  check_status, get_error_message_and_stack_trace, sys, Env, Error, JsError, Result, Status,
+  check_status, get_error_message_and_stack_trace, sys, Env, Error, ErrorSink, JsError, Result,
+  Status,
 };
 
+#[cfg(not(target_family = "wasm"))]
+mod batched;
+
+#[cfg(not(target_family = "wasm"))]
+pub use batched::BatchedThreadsafeFunction;
+
 #[deprecated(since = "2.17.0", note = "Please use `ThreadsafeFunction` instead")]
 pub type ThreadSafeCallContext<T> = ThreadsafeCallContext<T>;
 
@@ -32,9 +47,39 @@ pub struct ThreadsafeCallContext<T: 'static> {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ThreadsafeFunctionCallMode {
   NonBlocking,
+  /// Wait for a free slot while the queue is full.
+  ///
+  /// It must not be used on the JavaScript thread with a `max_queue_size`, which would never free the slot,
+  /// in debug builds such a call fails with `Status::WouldDeadlock`.
   Blocking,
 }
 
+/// The priority of a call of a ThreadsafeFunction, see `ThreadsafeFunctionHandle::call_with_priority`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ThreadsafeFunctionCallPriority {
+  /// Delivered in the order of the queue.
+  #[default]
+  Normal,
+  /// Delivered ahead of the normal calls waiting in the queue, like an error notification or a shutdown signal
+  /// which must not wait for the bulk data queued before it.
+  Urgent,
+}
+
+/// Whether a ThreadsafeFunction keeps the event loop alive, set by `ThreadsafeFunctionBuilder::with_keepalive`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Keepalive {
+  /// Keep the event loop alive until the ThreadsafeFunction is dropped, unless it's built with `weak::<true>()`.
+  #[default]
+  Always,
+  /// Keep the event loop alive only while calls are queued and not delivered yet, so an idle ThreadsafeFunction,
+  /// like a background telemetry callback, doesn't hold the process open.
+  ///
+  /// The event loop can only be kept alive from the JavaScript thread: a call made from another thread while the
+  /// function is idle is delivered if the event loop is still running, then the function is referenced until
+  /// the calls queued meanwhile are delivered.
+  WhileActive,
+}
+
 impl From<ThreadsafeFunctionCallMode> for sys::napi_threadsafe_function_call_mode {
   fn from(value: ThreadsafeFunctionCallMode) -> Self {
     match value {
@@ -44,49 +89,48 @@ impl From<ThreadsafeFunctionCallMode> for sys::napi_threadsafe_function_call_mod
   }
 }
 
-struct ThreadsafeFunctionHandle {
+struct ThreadsafeFunctionInner {
   raw: AtomicPtr<sys::napi_threadsafe_function__>,
-  aborted: RwLock<bool>,
+  aborted: AbortState,
   referred: AtomicBool,
+  // 0 if the queue is unbounded
+  max_queue_size: usize,
+  queue_slots: Arc<QueueSlots>,
+  keepalive: Arc<KeepaliveState>,
 }
 
-impl ThreadsafeFunctionHandle {
-  /// create a Arc to hold the `ThreadsafeFunctionHandle`
-  fn new(raw: sys::napi_threadsafe_function) -> Arc<Self> {
+impl ThreadsafeFunctionInner {
+  /// create a Arc to hold the `ThreadsafeFunctionInner`
+  fn new(raw: sys::napi_threadsafe_function, max_queue_size: usize) -> Arc<Self> {
     Arc::new(Self {
       raw: AtomicPtr::new(raw),
-      aborted: RwLock::new(false),
+      aborted: AbortState::default(),
       referred: AtomicBool::new(true),
+      max_queue_size,
+      queue_slots: Arc::default(),
+      keepalive: Arc::default(),
     })
   }
 
-  /// Lock `aborted` with read access, call `f` with the value of `aborted`, then unlock it
+  /// Enter the call path, call `f` with the value of `aborted`, then leave it
   fn with_read_aborted<RT, F>(&self, f: F) -> RT
   where
     F: FnOnce(bool) -> RT,
   {
-    let aborted_guard = self
-      .aborted
-      .read()
-      .expect("Threadsafe Function aborted lock failed");
-    f(*aborted_guard)
+    self.aborted.read(f)
   }
 
-  /// Lock `aborted` with write access, call `f` with the `RwLockWriteGuard`, then unlock it
+  /// Wait for the calls in progress to leave, then call `f` with exclusive access to `aborted`
   fn with_write_aborted<RT, F>(&self, f: F) -> RT
   where
-    F: FnOnce(RwLockWriteGuard<bool>) -> RT,
+    F: FnOnce(&mut bool) -> RT,
   {
-    let aborted_guard = self
-      .aborted
-      .write()
-      .expect("Threadsafe Function aborted lock failed");
-    f(aborted_guard)
+    self.aborted.write(f)
   }
 
   #[allow(clippy::arc_with_non_send_sync)]
-  fn null() -> Arc<Self> {
-    Self::new(null_mut())
+  fn null(max_queue_size: usize) -> Arc<Self> {
+    Self::new(null_mut(), max_queue_size)
   }
 
   fn get_raw(&self) -> sys::napi_threadsafe_function {
@@ -96,9 +140,654 @@ impl ThreadsafeFunctionHandle {
   fn set_raw(&self, raw: sys::napi_threadsafe_function) {
     self.raw.store(raw, Ordering::SeqCst)
   }
+
+  /// A blocking call waits for the JavaScript thread to take an item off a full queue, so on the JavaScript thread
+  /// itself it would hang forever. It's checked in debug builds, the bounded queue is the only one which can be full.
+  fn would_deadlock(&self, mode: ThreadsafeFunctionCallMode) -> bool {
+    if mode != ThreadsafeFunctionCallMode::Blocking || self.max_queue_size == 0 {
+      return false;
+    }
+    #[cfg(all(debug_assertions, not(feature = "noop")))]
+    if crate::bindgen_runtime::THREADS_CAN_ACCESS_ENV.with(|cell| cell.get()) {
+      self.queue_slots.reject();
+      return true;
+    }
+    false
+  }
+
+  /// Deliver `call` ahead of the items waiting in the queue, only a wake up of the JavaScript thread is queued
+  fn call_urgent(&self, call: PendingCall, mode: ThreadsafeFunctionCallMode) -> sys::napi_status {
+    let data = call.data;
+    if !self.queue_slots.push_urgent(call) {
+      self.queue_slots.reject();
+      return sys::Status::napi_closing;
+    }
+    let status = unsafe { self.call_raw(ptr::null_mut(), mode) };
+    if status != sys::Status::napi_ok && !self.queue_slots.remove_urgent(data) {
+      // delivered in the wake up of another item
+      return sys::Status::napi_ok;
+    }
+    status
+  }
+
+  /// `data` must be a boxed item of the type expected by the `call_js_cb` of the function
+  unsafe fn call_raw(
+    &self,
+    data: *mut c_void,
+    mode: ThreadsafeFunctionCallMode,
+  ) -> sys::napi_status {
+    let status = unsafe { self.enqueue_raw(data, mode) };
+    if status != sys::Status::napi_ok {
+      self.queue_slots.reject();
+    }
+    status
+  }
+
+  /// Like `call_raw`, but a rejected item is not counted as a dropped call, so it can be retried
+  unsafe fn enqueue_raw(
+    &self,
+    data: *mut c_void,
+    mode: ThreadsafeFunctionCallMode,
+  ) -> sys::napi_status {
+    if !self.queue_slots.enqueue() {
+      return sys::Status::napi_closing;
+    }
+    self.keepalive.queued();
+    let status = unsafe { sys::napi_call_threadsafe_function(self.get_raw(), data, mode.into()) };
+    if status != sys::Status::napi_ok {
+      self.queue_slots.cancel();
+      self.keepalive.delivered();
+      self.keepalive.update();
+    }
+    status
+  }
+
+  /// Enqueue the item in the non-blocking mode, and wait for a free slot while the queue is full
+  async fn call_bounded(&self, mut pending: PendingCall) -> Result<()> {
+    std::future::poll_fn(|cx| {
+      self.with_read_aborted(|aborted| {
+        if aborted {
+          return Poll::Ready(Err(crate::Error::from_status(Status::Closing)));
+        }
+        loop {
+          let generation = self.queue_slots.generation();
+          let status =
+            unsafe { self.enqueue_raw(pending.data, ThreadsafeFunctionCallMode::NonBlocking) };
+          match status {
+            sys::Status::napi_ok => {
+              pending.enqueued();
+              return Poll::Ready(Ok(()));
+            }
+            sys::Status::napi_queue_full => {
+              if self.queue_slots.wait(generation, cx.waker()) {
+                return Poll::Pending;
+              }
+            }
+            status => {
+              self.queue_slots.reject();
+              return Poll::Ready(check_status!(
+                status,
+                "Threadsafe function call_async_bounded failed"
+              ));
+            }
+          }
+        }
+      })
+    })
+    .await
+  }
+}
+
+const ABORTED: usize = 1 << (usize::BITS - 1);
+const WRITING: usize = 1 << (usize::BITS - 2);
+const CALLERS: usize = !(ABORTED | WRITING);
+
+/// The `aborted` flag, read by every call without taking a lock.
+///
+/// The calls in progress are counted in the low bits, so `abort` and the finalizer, which are rare, can wait for
+/// them to leave before the threadsafe function is released, while the calls never wait for each other.
+/// The lock is only taken while a writer is in progress, by the writer and by the calls waiting for it.
+#[derive(Default)]
+struct AbortState {
+  state: AtomicUsize,
+  lock: Mutex<()>,
+  // notified when the last caller leaves while a writer waits, and when the writer is done
+  changed: Condvar,
 }
 
-impl Drop for ThreadsafeFunctionHandle {
+impl AbortState {
+  fn read<RT>(&self, f: impl FnOnce(bool) -> RT) -> RT {
+    loop {
+      let state = self.state.fetch_add(1, Ordering::SeqCst);
+      if state & WRITING == 0 {
+        let result = f(state & ABORTED != 0);
+        self.leave();
+        return result;
+      }
+      // back off until the writer is done
+      self.leave();
+      let guard = lock(&self.lock);
+      drop(
+        self
+          .changed
+          .wait_while(guard, |_| self.state.load(Ordering::SeqCst) & WRITING != 0)
+          .expect("Threadsafe Function abort state lock failed"),
+      );
+    }
+  }
+
+  fn leave(&self) {
+    let state = self.state.fetch_sub(1, Ordering::SeqCst);
+    if state & WRITING != 0 && state & CALLERS == 1 {
+      // the writer checks the callers with the lock held, so it's either not waiting yet or woken here
+      let _guard = lock(&self.lock);
+      self.changed.notify_all();
+    }
+  }
+
+  fn write<RT>(&self, f: impl FnOnce(&mut bool) -> RT) -> RT {
+    let guard = lock(&self.lock);
+    // wait for the other writer, then wait for the calls in progress to leave
+    let guard = self
+      .changed
+      .wait_while(guard, |_| {
+        self.state.fetch_or(WRITING, Ordering::SeqCst) & WRITING != 0
+      })
+      .and_then(|guard| {
+        self
+          .changed
+          .wait_while(guard, |_| self.state.load(Ordering::SeqCst) & CALLERS != 0)
+      })
+      .expect("Threadsafe Function abort state lock failed");
+    drop(guard);
+    let mut aborted = self.state.load(Ordering::SeqCst) & ABORTED != 0;
+    let result = f(&mut aborted);
+    if aborted {
+      self.state.fetch_or(ABORTED, Ordering::SeqCst);
+    }
+    let _guard = lock(&self.lock);
+    self.state.fetch_and(!WRITING, Ordering::SeqCst);
+    self.changed.notify_all();
+    result
+  }
+}
+
+/// Tracks the queued items, to wake the `call_async_bounded` calls waiting for a free slot of the queue,
+/// and the `close_with_drain` waiting for the queue to be drained, and to report the `ThreadsafeFunctionStats`
+///
+/// The counters are atomics, so the calls don't take a lock, the locks are only taken to wait for a free slot,
+/// for the urgent calls, and to measure the latency if it's enabled.
+#[derive(Default)]
+struct QueueSlots {
+  // increased each time an item is taken off the queue
+  generation: AtomicU64,
+  // the items which are queued and not taken off the queue yet
+  pending: AtomicUsize,
+  delivered: AtomicUsize,
+  dropped: AtomicUsize,
+  // set by `close_with_drain`, the new calls are rejected
+  draining: AtomicBool,
+  calls: AtomicU64,
+  // the calls which are not accepted by the queue
+  rejected: AtomicU64,
+  // the length of `waiters`, so taking an item off the queue doesn't lock it if nobody waits
+  waiter_count: AtomicUsize,
+  waiters: Mutex<Vec<Waker>>,
+  // the calls of `ThreadsafeFunctionCallPriority::Urgent`, delivered ahead of the queue
+  urgent_count: AtomicUsize,
+  urgent: Mutex<VecDeque<PendingCall>>,
+  high_water_mark: OnceLock<HighWaterMark>,
+  // set by `ThreadsafeFunctionBuilder::with_latency_tracking`
+  latency: OnceLock<Mutex<QueueLatency>>,
+}
+
+pub(crate) type HighWaterMarkCallback = Arc<dyn Fn(ThreadsafeFunctionStats) + Send + Sync>;
+
+struct HighWaterMark {
+  mark: usize,
+  callback: HighWaterMarkCallback,
+}
+
+#[derive(Default)]
+struct QueueLatency {
+  // when the pending items are queued, in the order of the queue
+  enqueued_at: VecDeque<Instant>,
+  total: Duration,
+  delivered: u32,
+}
+
+impl QueueSlots {
+  fn generation(&self) -> u64 {
+    self.generation.load(Ordering::SeqCst)
+  }
+
+  /// Register `waker` if no slot is freed since `generation`, otherwise the call should be retried right away
+  fn wait(&self, generation: u64, waker: &Waker) -> bool {
+    self.register(waker);
+    // checked after the waker is registered, so a slot freed in between either wakes it or is seen here
+    self.generation.load(Ordering::SeqCst) == generation
+  }
+
+  /// Count an item which is about to be queued, it returns `false` if the function is draining
+  fn enqueue(&self) -> bool {
+    let pending = self.pending.fetch_add(1, Ordering::SeqCst) + 1;
+    if self.draining.load(Ordering::SeqCst) {
+      decrement(&self.pending);
+      // the `close_with_drain` may wait for this item
+      self.wake();
+      return false;
+    }
+    self.calls.fetch_add(1, Ordering::Relaxed);
+    if let Some(latency) = self.latency.get() {
+      lock(latency).enqueued_at.push_back(Instant::now());
+    }
+    if let Some(high_water_mark) = self
+      .high_water_mark
+      .get()
+      .filter(|high_water_mark| pending == high_water_mark.mark)
+    {
+      (high_water_mark.callback)(self.stats());
+    }
+    true
+  }
+
+  /// The item counted by `enqueue` is rejected by the queue
+  fn cancel(&self) {
+    decrement(&self.pending);
+    self.calls.fetch_sub(1, Ordering::Relaxed);
+    if let Some(latency) = self.latency.get() {
+      lock(latency).enqueued_at.pop_back();
+    }
+  }
+
+  /// Count a call which is not accepted by the queue
+  fn reject(&self) {
+    self.calls.fetch_add(1, Ordering::Relaxed);
+    self.rejected.fetch_add(1, Ordering::Relaxed);
+  }
+
+  /// Called when an item is taken off the queue to be delivered
+  fn release(&self) {
+    decrement(&self.pending);
+    self.delivered.fetch_add(1, Ordering::Relaxed);
+    if let Some(latency) = self.latency.get() {
+      let mut latency = lock(latency);
+      if let Some(enqueued_at) = latency.enqueued_at.pop_front() {
+        latency.total += enqueued_at.elapsed();
+        latency.delivered += 1;
+      }
+    }
+    self.wake();
+  }
+
+  /// Called when the function is finalized, the items left in the queue are discarded
+  fn finalize(&self) {
+    self.draining.store(true, Ordering::SeqCst);
+    self
+      .dropped
+      .fetch_add(self.pending.swap(0, Ordering::SeqCst), Ordering::Relaxed);
+    if let Some(latency) = self.latency.get() {
+      lock(latency).enqueued_at.clear();
+    }
+    let urgent = {
+      let mut urgent = lock(&self.urgent);
+      self.urgent_count.store(0, Ordering::Release);
+      std::mem::take(&mut *urgent)
+    };
+    // dropped without the lock, the items may hold anything
+    drop(urgent);
+    self.wake();
+  }
+
+  /// Add an urgent call, it returns `false` if the function is draining
+  fn push_urgent(&self, call: PendingCall) -> bool {
+    let mut urgent = lock(&self.urgent);
+    // checked with the lock held, `finalize` takes the urgent calls after it starts draining
+    if self.draining.load(Ordering::SeqCst) {
+      return false;
+    }
+    urgent.push_back(call);
+    self.urgent_count.fetch_add(1, Ordering::Release);
+    true
+  }
+
+  /// Take back the urgent call of `data` whose wake up is rejected by the queue, it returns `false` if the call is
+  /// delivered already
+  fn remove_urgent(&self, data: *mut c_void) -> bool {
+    let call = {
+      let mut urgent = lock(&self.urgent);
+      let index = urgent.iter().position(|call| call.data == data);
+      let call = index.and_then(|index| urgent.remove(index));
+      if call.is_some() {
+        self.urgent_count.fetch_sub(1, Ordering::Release);
+      }
+      call
+    };
+    call.is_some()
+  }
+
+  fn take_urgent(&self) -> Option<PendingCall> {
+    if self.urgent_count.load(Ordering::Acquire) == 0 {
+      return None;
+    }
+    let mut urgent = lock(&self.urgent);
+    let call = urgent.pop_front();
+    if call.is_some() {
+      self.urgent_count.fetch_sub(1, Ordering::Release);
+    }
+    call
+  }
+
+  fn stats(&self) -> ThreadsafeFunctionStats {
+    ThreadsafeFunctionStats {
+      queue_depth: self.pending.load(Ordering::Relaxed),
+      total_calls: self.calls.load(Ordering::Relaxed),
+      dropped_calls: self.rejected.load(Ordering::Relaxed)
+        + self.dropped.load(Ordering::Relaxed) as u64,
+      average_latency: self
+        .latency
+        .get()
+        .map(|latency| {
+          let latency = lock(latency);
+          if latency.delivered == 0 {
+            Duration::ZERO
+          } else {
+            latency.total / latency.delivered
+          }
+        })
+        .unwrap_or_default(),
+    }
+  }
+
+  fn wake(&self) {
+    self.generation.fetch_add(1, Ordering::SeqCst);
+    if self.waiter_count.load(Ordering::SeqCst) == 0 {
+      return;
+    }
+    let waiters = {
+      let mut waiters = lock(&self.waiters);
+      self.waiter_count.store(0, Ordering::SeqCst);
+      std::mem::take(&mut *waiters)
+    };
+    waiters.into_iter().for_each(Waker::wake);
+  }
+
+  /// Reject the new calls, and return the counters to compute the `DrainReport` from
+  fn start_draining(&self) -> (usize, usize) {
+    self.draining.store(true, Ordering::SeqCst);
+    let counters = (
+      self.delivered.load(Ordering::SeqCst),
+      self.dropped.load(Ordering::SeqCst),
+    );
+    // the `call_async_bounded` calls waiting for a free slot fail with `Closing`
+    self.wake();
+    counters
+  }
+
+  /// Whether the queue is drained, otherwise `waker` is woken when an item is taken off the queue
+  fn poll_drained(&self, waker: &Waker) -> bool {
+    if self.pending.load(Ordering::SeqCst) == 0 {
+      return true;
+    }
+    self.register(waker);
+    self.pending.load(Ordering::SeqCst) == 0
+  }
+
+  fn report(&self, (delivered, dropped): (usize, usize), timed_out: bool) -> DrainReport {
+    let pending = self.pending.load(Ordering::SeqCst);
+    DrainReport {
+      delivered: self.delivered.load(Ordering::SeqCst) - delivered,
+      dropped: self.dropped.load(Ordering::SeqCst) - dropped + if timed_out { pending } else { 0 },
+      timed_out,
+    }
+  }
+
+  fn register(&self, waker: &Waker) {
+    let mut waiters = lock(&self.waiters);
+    if !waiters.iter().any(|w| w.will_wake(waker)) {
+      waiters.push(waker.clone());
+    }
+    self.waiter_count.store(waiters.len(), Ordering::SeqCst);
+  }
+}
+
+/// Decrease `counter` unless it's zero
+fn decrement(counter: &AtomicUsize) {
+  let _ = counter.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+    count.checked_sub(1)
+  });
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> sync::MutexGuard<'_, T> {
+  mutex.lock().expect("Threadsafe Function lock failed")
+}
+
+/// The counters of a [`ThreadsafeFunction`], to monitor the backpressure of the JavaScript thread.
+///
+/// They are shared by the clones of the function.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ThreadsafeFunctionStats {
+  /// The calls which are queued and not delivered to the JavaScript function yet.
+  pub queue_depth: usize,
+  /// All the calls, including the dropped calls.
+  pub total_calls: u64,
+  /// The calls which are rejected, because the queue is full or the function is closing,
+  /// or discarded when the function is aborted or the env is torn down.
+  pub dropped_calls: u64,
+  /// The average time the delivered calls wait in the queue.
+  ///
+  /// It's only measured if the function is built with `with_latency_tracking`, it's zero otherwise.
+  pub average_latency: Duration,
+}
+
+/// The result of [`ThreadsafeFunction::close_with_drain`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DrainReport {
+  /// The queued calls which are delivered to the JavaScript function while draining.
+  pub delivered: usize,
+  /// The queued calls which are discarded, because the timeout is reached or the env is torn down.
+  pub dropped: usize,
+  /// Whether the timeout is reached before the queue is drained.
+  pub timed_out: bool,
+}
+
+// The deadlines of the pending `close_with_drain` calls, all served by a single thread which is spawned on the first
+// call and parked while there is no deadline
+static DEADLINES: Mutex<Vec<(u64, Instant, Arc<QueueSlots>)>> = Mutex::new(Vec::new());
+static DEADLINES_CHANGED: Condvar = Condvar::new();
+static DEADLINE_THREAD: Once = Once::new();
+static NEXT_DEADLINE_ID: AtomicU64 = AtomicU64::new(0);
+
+// Wakes the `close_with_drain` at the deadline, the deadline is removed once it's dropped
+struct DeadlineTimer {
+  id: u64,
+}
+
+impl DeadlineTimer {
+  fn start(deadline: Instant, queue_slots: Arc<QueueSlots>) -> Self {
+    DEADLINE_THREAD.call_once(|| {
+      thread::Builder::new()
+        .name("napi-rs-tsfn-deadline".to_owned())
+        .spawn(run_deadline_timers)
+        .expect("Spawn the Threadsafe Function deadline thread failed");
+    });
+    let id = NEXT_DEADLINE_ID.fetch_add(1, Ordering::Relaxed);
+    lock(&DEADLINES).push((id, deadline, queue_slots));
+    DEADLINES_CHANGED.notify_one();
+    Self { id }
+  }
+}
+
+impl Drop for DeadlineTimer {
+  fn drop(&mut self) {
+    // the thread isn't notified, it wakes up at the removed deadline at most once
+    lock(&DEADLINES).retain(|(id, _, _)| *id != self.id);
+  }
+}
+
+fn run_deadline_timers() {
+  let mut deadlines = lock(&DEADLINES);
+  loop {
+    let now = Instant::now();
+    let mut expired = Vec::new();
+    deadlines.retain(|(_, deadline, queue_slots)| {
+      if *deadline > now {
+        return true;
+      }
+      expired.push(queue_slots.clone());
+      false
+    });
+    if !expired.is_empty() {
+      // woken without the lock, the wakers may start another `close_with_drain` right away
+      drop(deadlines);
+      expired.iter().for_each(|queue_slots| queue_slots.wake());
+      deadlines = lock(&DEADLINES);
+      continue;
+    }
+    deadlines = match deadlines.iter().map(|(_, deadline, _)| *deadline).min() {
+      Some(deadline) => {
+        DEADLINES_CHANGED
+          .wait_timeout(deadlines, deadline - now)
+          .expect("Threadsafe Function lock failed")
+          .0
+      }
+      None => DEADLINES_CHANGED
+        .wait(deadlines)
+        .expect("Threadsafe Function lock failed"),
+    };
+  }
+}
+
+// The item of a `call_async_bounded` or of an urgent call, it's owned by the caller until the queue accepts it
+struct PendingCall {
+  data: *mut c_void,
+  drop_data: unsafe fn(*mut c_void),
+}
+
+// the items are sent to the JavaScript thread, like the items of `ThreadsafeFunction::call`
+unsafe impl Send for PendingCall {}
+
+impl PendingCall {
+  fn new<D>(data: D) -> Self {
+    Self {
+      data: Box::into_raw(Box::new(data)).cast(),
+      drop_data: drop_pending_data::<D>,
+    }
+  }
+
+  fn enqueued(&mut self) {
+    self.data = ptr::null_mut();
+  }
+
+  fn into_raw(mut self) -> *mut c_void {
+    std::mem::replace(&mut self.data, ptr::null_mut())
+  }
+}
+
+impl Drop for PendingCall {
+  fn drop(&mut self) {
+    if !self.data.is_null() {
+      unsafe { (self.drop_data)(self.data) };
+    }
+  }
+}
+
+unsafe fn drop_pending_data<D>(data: *mut c_void) {
+  drop(unsafe { Box::<D>::from_raw(data.cast()) });
+}
+
+/// References the function while calls are queued, for `Keepalive::WhileActive`
+#[derive(Default)]
+struct KeepaliveState {
+  while_active: AtomicBool,
+  // the calls which are queued and not delivered yet
+  pending: AtomicUsize,
+  js_thread: OnceLock<KeepaliveTarget>,
+  // only changed on the JavaScript thread
+  referred: AtomicBool,
+}
+
+struct KeepaliveTarget {
+  thread: ThreadId,
+  env: usize,
+  raw: usize,
+}
+
+impl KeepaliveState {
+  fn enable(&self, env: sys::napi_env, raw: sys::napi_threadsafe_function) -> Result<()> {
+    if self.while_active.load(Ordering::Acquire) {
+      return Ok(());
+    }
+    let _ = self.js_thread.set(KeepaliveTarget {
+      thread: thread::current().id(),
+      env: env as usize,
+      raw: raw as usize,
+    });
+    check_status!(
+      unsafe { sys::napi_unref_threadsafe_function(env, raw) },
+      "Unref threadsafe function failed in WhileActive keepalive mode"
+    )?;
+    self.referred.store(false, Ordering::Relaxed);
+    self.while_active.store(true, Ordering::Release);
+    Ok(())
+  }
+
+  fn queued(&self) {
+    if self.while_active.load(Ordering::Acquire) && self.pending.fetch_add(1, Ordering::AcqRel) == 0
+    {
+      self.update();
+    }
+  }
+
+  fn delivered(&self) {
+    if self.while_active.load(Ordering::Acquire) {
+      let _ = self
+        .pending
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+          pending.checked_sub(1)
+        });
+    }
+  }
+
+  /// Reference the function if calls are queued, unreference it otherwise, it does nothing outside of the JavaScript thread
+  fn update(&self) {
+    if !self.while_active.load(Ordering::Acquire) {
+      return;
+    }
+    let Some(target) = self
+      .js_thread
+      .get()
+      .filter(|target| target.thread == thread::current().id())
+    else {
+      return;
+    };
+    let active = self.pending.load(Ordering::Acquire) > 0;
+    if active == self.referred.load(Ordering::Relaxed) {
+      return;
+    }
+    let (env, raw) = (
+      target.env as sys::napi_env,
+      target.raw as sys::napi_threadsafe_function,
+    );
+    let status = if active {
+      unsafe { sys::napi_ref_threadsafe_function(env, raw) }
+    } else {
+      unsafe { sys::napi_unref_threadsafe_function(env, raw) }
+    };
+    if status == sys::Status::napi_ok {
+      self.referred.store(active, Ordering::Relaxed);
+    }
+  }
+}
+
+struct ThreadsafeFunctionContext<R> {
+  callback: R,
+  queue_slots: Arc<QueueSlots>,
+  keepalive: Arc<KeepaliveState>,
+  error_sink: Option<Rc<dyn ErrorSink>>,
+}
+
+impl Drop for ThreadsafeFunctionInner {
   fn drop(&mut self) {
     self.with_read_aborted(|aborted| {
       if !aborted {
@@ -137,7 +826,6 @@ struct ThreadsafeFunctionCallJsBackData<T, Return = Unknown<'static>> {
 ///
 /// ```rust
 /// use std::thread;
-/// use std::sync::Arc;
 ///
 /// use napi::{
 ///     threadsafe_function::{
@@ -147,7 +835,8 @@ struct ThreadsafeFunctionCallJsBackData<T, Return = Unknown<'static>> {
 /// use napi_derive::napi;
 ///
 /// #[napi]
-/// pub fn call_threadsafe_function(callback: Arc<ThreadsafeFunction<(u32, bool, String), ()>>) {
+/// pub fn call_threadsafe_function(tsfn: ThreadsafeFunction<(u32, bool, String), ()>) {
+///   // the clones share the function, it's released once all of them are dropped
 ///   let tsfn_cloned = tsfn.clone();
 ///
 ///   thread::spawn(move || {
@@ -162,6 +851,11 @@ struct ThreadsafeFunctionCallJsBackData<T, Return = Unknown<'static>> {
 ///   });
 /// }
 /// ```
+///
+/// As an argument of a `#[napi]` function, it's created from the JavaScript function passed in.
+/// The elements of a tuple `T` are passed as separate arguments, the function above is typed as
+/// `(err: Error | null, arg0: number, arg1: boolean, arg2: string) => void`.
+/// With `Weak` set to `true`, the function doesn't keep the event loop alive.
 pub struct ThreadsafeFunction<
   T: 'static,
   Return: 'static + FromNapiValue = Unknown<'static>,
@@ -171,8 +865,8 @@ pub struct ThreadsafeFunction<
   const Weak: bool = false,
   const MaxQueueSize: usize = 0,
 > {
-  handle: Arc<ThreadsafeFunctionHandle>,
-  _phantom: PhantomData<(T, CallJsBackArgs, Return, ErrorStatus)>,
+  handle: ThreadsafeFunctionHandle<T, Return, ErrorStatus, { CalleeHandled }>,
+  _phantom: PhantomData<CallJsBackArgs>,
 }
 
 unsafe impl<
@@ -303,6 +997,30 @@ impl<
       { Weak },
       { MaxQueueSize },
     >,
+  > {
+    Self::create_with_error_sink(env, func, callback, None)
+  }
+
+  /// The errors of `callback`, and of the callbacks of `call_with_return_value`, are delivered to `error_sink`,
+  /// or to the sink of the env if it's `None`
+  pub(crate) fn create_with_error_sink<
+    NewArgs: 'static + JsValuesTupleIntoVec,
+    R: 'static + FnMut(ThreadsafeCallContext<T>) -> Result<NewArgs>,
+  >(
+    env: sys::napi_env,
+    func: sys::napi_value,
+    callback: R,
+    error_sink: Option<Rc<dyn ErrorSink>>,
+  ) -> Result<
+    ThreadsafeFunction<
+      T,
+      Return,
+      NewArgs,
+      ErrorStatus,
+      { CalleeHandled },
+      { Weak },
+      { MaxQueueSize },
+    >,
   > {
     let mut async_resource_name = ptr::null_mut();
     static THREAD_SAFE_FUNCTION_ASYNC_RESOURCE_NAME: &str = "napi_rs_threadsafe_function";
@@ -342,8 +1060,13 @@ impl<
     }
 
     let mut raw_tsfn = ptr::null_mut();
-    let callback_ptr = Box::into_raw(Box::new(callback));
-    let handle = ThreadsafeFunctionHandle::null();
+    let inner = ThreadsafeFunctionInner::null(MaxQueueSize);
+    let context_ptr = Box::into_raw(Box::new(ThreadsafeFunctionContext {
+      callback,
+      queue_slots: inner.queue_slots.clone(),
+      keepalive: inner.keepalive.clone(),
+      error_sink,
+    }));
     check_status!(
       unsafe {
         sys::napi_create_threadsafe_function(
@@ -353,16 +1076,17 @@ impl<
           async_resource_name,
           MaxQueueSize,
           1,
-          Arc::downgrade(&handle).into_raw().cast_mut().cast(), // pass handler to thread_finalize_cb
+          Arc::downgrade(&inner).into_raw().cast_mut().cast(), // pass handler to thread_finalize_cb
           Some(thread_finalize_cb::<T, NewArgs, R>),
-          callback_ptr.cast(),
+          context_ptr.cast(),
           Some(call_js_cb::<T, Return, NewArgs, ErrorStatus, R, CalleeHandled>),
           &mut raw_tsfn,
         )
       },
       "Create threadsafe function in ThreadsafeFunction::create failed"
     )?;
-    handle.set_raw(raw_tsfn);
+    inner.set_raw(raw_tsfn);
+    crate::cleanup_env::on_threadsafe_function_created(env);
 
     // Weak ThreadsafeFunction will not prevent the event loop from exiting
     if Weak {
@@ -373,11 +1097,19 @@ impl<
     }
 
     Ok(ThreadsafeFunction {
-      handle,
+      handle: ThreadsafeFunctionHandle {
+        inner,
+        _phantom: PhantomData,
+      },
       _phantom: PhantomData,
     })
   }
 
+  /// A cheap handle to call this function from other threads and tasks, see [`ThreadsafeFunctionHandle`].
+  pub fn handle(&self) -> ThreadsafeFunctionHandle<T, Return, ErrorStatus, { CalleeHandled }> {
+    self.handle.clone()
+  }
+
   #[deprecated(
     since = "2.17.0",
     note = "Please use `ThreadsafeFunction::clone` instead of manually increasing the reference count"
@@ -387,10 +1119,12 @@ impl<
   ///
   /// "ref" is a keyword so that we use "refer" here.
   pub fn refer(&mut self, env: &Env) -> Result<()> {
-    self.handle.with_read_aborted(|aborted| {
-      if !aborted && !self.handle.referred.load(Ordering::Relaxed) {
-        check_status!(unsafe { sys::napi_ref_threadsafe_function(env.0, self.handle.get_raw()) })?;
-        self.handle.referred.store(true, Ordering::Relaxed);
+    self.handle.inner.with_read_aborted(|aborted| {
+      if !aborted && !self.handle.inner.referred.load(Ordering::Relaxed) {
+        check_status!(unsafe {
+          sys::napi_ref_threadsafe_function(env.0, self.handle.inner.get_raw())
+        })?;
+        self.handle.inner.referred.store(true, Ordering::Relaxed);
       }
       Ok(())
     })
@@ -403,43 +1137,102 @@ impl<
   /// See [napi_unref_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_unref_threadsafe_function)
   /// for more information.
   pub fn unref(&mut self, env: &Env) -> Result<()> {
-    self.handle.with_read_aborted(|aborted| {
-      if !aborted && self.handle.referred.load(Ordering::Relaxed) {
+    self.handle.inner.with_read_aborted(|aborted| {
+      if !aborted && self.handle.inner.referred.load(Ordering::Relaxed) {
         check_status!(unsafe {
-          sys::napi_unref_threadsafe_function(env.0, self.handle.get_raw())
+          sys::napi_unref_threadsafe_function(env.0, self.handle.inner.get_raw())
         })?;
-        self.handle.referred.store(false, Ordering::Relaxed);
+        self.handle.inner.referred.store(false, Ordering::Relaxed);
       }
       Ok(())
     })
   }
 
-  pub fn aborted(&self) -> bool {
-    self.handle.with_read_aborted(|aborted| aborted)
-  }
-
   #[deprecated(
     since = "2.17.0",
     note = "Drop all references to the ThreadsafeFunction will automatically release it"
   )]
   pub fn abort(self) -> Result<()> {
-    self.handle.with_write_aborted(|mut aborted_guard| {
-      if !*aborted_guard {
+    self.handle.inner.with_write_aborted(|aborted| {
+      if !*aborted {
         check_status!(unsafe {
           sys::napi_release_threadsafe_function(
-            self.handle.get_raw(),
+            self.handle.inner.get_raw(),
             sys::ThreadsafeFunctionReleaseMode::abort,
           )
         })?;
-        *aborted_guard = true;
+        *aborted = true;
       }
       Ok(())
     })
   }
 
-  /// Get the raw `ThreadSafeFunction` pointer
-  pub fn raw(&self) -> sys::napi_threadsafe_function {
-    self.handle.get_raw()
+  /// Stop accepting new calls, and wait for the queued calls to be delivered to the JavaScript function,
+  /// for example before exiting on `SIGTERM`.
+  ///
+  /// The new calls made by this function or its clones fail with `Status::Closing`. If the queue isn't drained
+  /// within `timeout`, the function is aborted, and the remaining calls are discarded and reported as dropped.
+  ///
+  /// The JavaScript thread must keep running while the future is awaited, so it must not be blocked on.
+  /// The draining starts when it's called, the calls delivered before the future is polled are counted too.
+  pub fn close_with_drain(self, timeout: Duration) -> impl Future<Output = DrainReport> {
+    let deadline = Instant::now() + timeout;
+    let queue_slots = self.handle.inner.queue_slots.clone();
+    let counters = queue_slots.start_draining();
+    async move {
+      let mut deadline_timer: Option<DeadlineTimer> = None;
+      let timed_out = std::future::poll_fn(|cx| {
+        if queue_slots.poll_drained(cx.waker()) {
+          return Poll::Ready(false);
+        }
+        if Instant::now() >= deadline {
+          return Poll::Ready(true);
+        }
+        deadline_timer.get_or_insert_with(|| DeadlineTimer::start(deadline, queue_slots.clone()));
+        Poll::Pending
+      })
+      .await;
+      drop(deadline_timer);
+      let report = queue_slots.report(counters, timed_out);
+      if timed_out {
+        self.handle.inner.with_write_aborted(|aborted| {
+          if !*aborted {
+            unsafe {
+              sys::napi_release_threadsafe_function(
+                self.handle.inner.get_raw(),
+                sys::ThreadsafeFunctionReleaseMode::abort,
+              )
+            };
+            *aborted = true;
+          }
+        });
+      }
+      report
+    }
+  }
+
+  pub(crate) fn set_high_water_mark(&self, mark: usize, callback: HighWaterMarkCallback) {
+    let _ = self
+      .handle
+      .inner
+      .queue_slots
+      .high_water_mark
+      .set(HighWaterMark { mark, callback });
+  }
+
+  pub(crate) fn track_latency(&self) {
+    let _ = self.handle.inner.queue_slots.latency.set(Mutex::default());
+  }
+
+  pub(crate) fn set_keepalive(&self, env: sys::napi_env, keepalive: Keepalive) -> Result<()> {
+    match keepalive {
+      Keepalive::Always => Ok(()),
+      Keepalive::WhileActive => self
+        .handle
+        .inner
+        .keepalive
+        .enable(env, self.handle.inner.get_raw()),
+    }
   }
 }
 
@@ -448,21 +1241,156 @@ impl<
     Return: FromNapiValue,
     CallJsBackArgs: 'static + JsValuesTupleIntoVec,
     ErrorStatus: AsRef<str> + From<Status>,
+    const CalleeHandled: bool,
     const Weak: bool,
     const MaxQueueSize: usize,
-  > ThreadsafeFunction<T, Return, CallJsBackArgs, ErrorStatus, true, { Weak }, { MaxQueueSize }>
+  > Clone
+  for ThreadsafeFunction<
+    T,
+    Return,
+    CallJsBackArgs,
+    ErrorStatus,
+    { CalleeHandled },
+    { Weak },
+    { MaxQueueSize },
+  >
+{
+  fn clone(&self) -> Self {
+    Self {
+      handle: self.handle.clone(),
+      _phantom: PhantomData,
+    }
+  }
+}
+
+impl<
+    T: 'static,
+    Return: FromNapiValue,
+    CallJsBackArgs: 'static + JsValuesTupleIntoVec,
+    ErrorStatus: AsRef<str> + From<Status>,
+    const CalleeHandled: bool,
+    const Weak: bool,
+    const MaxQueueSize: usize,
+  > Deref
+  for ThreadsafeFunction<
+    T,
+    Return,
+    CallJsBackArgs,
+    ErrorStatus,
+    { CalleeHandled },
+    { Weak },
+    { MaxQueueSize },
+  >
+{
+  type Target = ThreadsafeFunctionHandle<T, Return, ErrorStatus, { CalleeHandled }>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.handle
+  }
+}
+
+/// The calling half of a [`ThreadsafeFunction`], which is `Send`, `Sync` and cheap to clone.
+///
+/// All the clones of a `ThreadsafeFunction` and of its handles share one reference counted state, so cloning is a
+/// single atomic increment, calling only takes a lock for the urgent calls, with the latency tracking, or while the
+/// function is being aborted, and the threadsafe function is released once, when the last of them is dropped. Unlike the `ThreadsafeFunction`, its type doesn't carry the parameters which only matter
+/// when it's created, so it's easy to name in the fields of a struct or to move into a tokio task:
+///
+/// ```rust
+/// #[napi]
+/// pub fn serve(on_request: ThreadsafeFunction<String, ()>) {
+///   let on_request = on_request.handle();
+///   napi::bindgen_prelude::spawn(async move {
+///     while let Some(request) = next_request().await {
+///       let on_request = on_request.clone();
+///       tokio::spawn(async move {
+///         on_request.call(Ok(request), ThreadsafeFunctionCallMode::NonBlocking);
+///       });
+///     }
+///   });
+/// }
+/// ```
+pub struct ThreadsafeFunctionHandle<
+  T: 'static,
+  Return: 'static + FromNapiValue = Unknown<'static>,
+  ErrorStatus: AsRef<str> + From<Status> = Status,
+  const CalleeHandled: bool = true,
+> {
+  inner: Arc<ThreadsafeFunctionInner>,
+  _phantom: PhantomData<(T, Return, ErrorStatus)>,
+}
+
+unsafe impl<
+    T: 'static,
+    Return: FromNapiValue,
+    ErrorStatus: AsRef<str> + From<Status>,
+    const CalleeHandled: bool,
+  > Send for ThreadsafeFunctionHandle<T, Return, ErrorStatus, { CalleeHandled }>
+{
+}
+
+unsafe impl<
+    T: 'static,
+    Return: FromNapiValue,
+    ErrorStatus: AsRef<str> + From<Status>,
+    const CalleeHandled: bool,
+  > Sync for ThreadsafeFunctionHandle<T, Return, ErrorStatus, { CalleeHandled }>
+{
+}
+
+impl<
+    T: 'static,
+    Return: FromNapiValue,
+    ErrorStatus: AsRef<str> + From<Status>,
+    const CalleeHandled: bool,
+  > Clone for ThreadsafeFunctionHandle<T, Return, ErrorStatus, { CalleeHandled }>
+{
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      _phantom: PhantomData,
+    }
+  }
+}
+
+impl<
+    T: 'static,
+    Return: FromNapiValue,
+    ErrorStatus: AsRef<str> + From<Status>,
+    const CalleeHandled: bool,
+  > ThreadsafeFunctionHandle<T, Return, ErrorStatus, { CalleeHandled }>
+{
+  pub fn aborted(&self) -> bool {
+    self.inner.with_read_aborted(|aborted| aborted)
+  }
+
+  /// Get the raw `ThreadSafeFunction` pointer
+  pub fn raw(&self) -> sys::napi_threadsafe_function {
+    self.inner.get_raw()
+  }
+
+  /// The counters of the calls made by this function and its clones.
+  pub fn stats(&self) -> ThreadsafeFunctionStats {
+    self.inner.queue_slots.stats()
+  }
+}
+
+impl<T: 'static, Return: FromNapiValue, ErrorStatus: AsRef<str> + From<Status>>
+  ThreadsafeFunctionHandle<T, Return, ErrorStatus, true>
 {
   /// See [napi_call_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_call_threadsafe_function)
   /// for more information.
   pub fn call(&self, value: Result<T, ErrorStatus>, mode: ThreadsafeFunctionCallMode) -> Status {
-    self.handle.with_read_aborted(|aborted| {
+    self.inner.with_read_aborted(|aborted| {
       if aborted {
         return Status::Closing;
       }
+      if self.inner.would_deadlock(mode) {
+        return Status::WouldDeadlock;
+      }
 
       unsafe {
-        sys::napi_call_threadsafe_function(
-          self.handle.get_raw(),
+        self.inner.call_raw(
           Box::into_raw(Box::new(value.map(|data| {
             ThreadsafeFunctionCallJsBackData {
               data,
@@ -471,13 +1399,48 @@ impl<
             }
           })))
           .cast(),
-          mode.into(),
+          mode,
         )
       }
       .into()
     })
   }
 
+  /// Like `call`, but an `Urgent` call is delivered ahead of the normal calls waiting in the queue.
+  ///
+  /// It still takes a slot of the queue, so with a `max_queue_size` it waits for, or is rejected by, a full queue
+  /// like the normal calls. The urgent calls are delivered in the order they are made.
+  pub fn call_with_priority(
+    &self,
+    value: Result<T, ErrorStatus>,
+    mode: ThreadsafeFunctionCallMode,
+    priority: ThreadsafeFunctionCallPriority,
+  ) -> Status {
+    if priority == ThreadsafeFunctionCallPriority::Normal {
+      return self.call(value, mode);
+    }
+    self.inner.with_read_aborted(|aborted| {
+      if aborted {
+        return Status::Closing;
+      }
+      if self.inner.would_deadlock(mode) {
+        return Status::WouldDeadlock;
+      }
+
+      self
+        .inner
+        .call_urgent(
+          PendingCall::new(value.map(|data| ThreadsafeFunctionCallJsBackData {
+            data,
+            call_variant: ThreadsafeFunctionCallVariant::Direct,
+            callback: Box::new(|_d: Result<Return>, _| Ok(())),
+          })),
+          mode,
+        )
+        .into()
+    })
+  }
+
   /// Call the ThreadsafeFunction, and handle the return value with a callback
   pub fn call_with_return_value<F: 'static + FnOnce(Result<Return>, Env) -> Result<()>>(
     &self,
@@ -485,14 +1448,16 @@ impl<
     mode: ThreadsafeFunctionCallMode,
     cb: F,
   ) -> Status {
-    self.handle.with_read_aborted(|aborted| {
+    self.inner.with_read_aborted(|aborted| {
       if aborted {
         return Status::Closing;
       }
+      if self.inner.would_deadlock(mode) {
+        return Status::WouldDeadlock;
+      }
 
       unsafe {
-        sys::napi_call_threadsafe_function(
-          self.handle.get_raw(),
+        self.inner.call_raw(
           Box::into_raw(Box::new(value.map(|data| {
             ThreadsafeFunctionCallJsBackData {
               data,
@@ -501,27 +1466,43 @@ impl<
             }
           })))
           .cast(),
-          mode.into(),
+          mode,
         )
       }
       .into()
     })
   }
 
+  /// Call the ThreadsafeFunction without blocking the thread, the returned future resolves once the value is enqueued.
+  ///
+  /// If the queue is full, which only happens with a `max_queue_size` set on the builder, it waits for the JavaScript
+  /// thread to take an item off the queue, so a fast producer is slowed down to the pace of JavaScript.
+  pub async fn call_async_bounded(&self, value: Result<T, ErrorStatus>) -> Result<()> {
+    self
+      .inner
+      .call_bounded(PendingCall::new(value.map(|data| {
+        ThreadsafeFunctionCallJsBackData {
+          data,
+          call_variant: ThreadsafeFunctionCallVariant::Direct,
+          callback: Box::new(|_d: Result<Return>, _| Ok(())),
+        }
+      })))
+      .await
+  }
+
   #[cfg(feature = "tokio_rt")]
   /// Call the ThreadsafeFunction, and handle the return value with in `async` way
   pub async fn call_async(&self, value: Result<T, ErrorStatus>) -> Result<Return> {
     let (sender, receiver) = tokio::sync::oneshot::channel::<Result<Return>>();
 
-    self.handle.with_read_aborted(|aborted| {
+    self.inner.with_read_aborted(|aborted| {
       if aborted {
         return Err(crate::Error::from_status(Status::Closing));
       }
 
       check_status!(
         unsafe {
-          sys::napi_call_threadsafe_function(
-            self.handle.get_raw(),
+          self.inner.call_raw(
             Box::into_raw(Box::new(value.map(|data| {
               ThreadsafeFunctionCallJsBackData {
                 data,
@@ -536,7 +1517,7 @@ impl<
               }
             })))
             .cast(),
-            ThreadsafeFunctionCallMode::NonBlocking.into(),
+            ThreadsafeFunctionCallMode::NonBlocking,
           )
         },
         "Threadsafe function call_async failed"
@@ -554,39 +1535,70 @@ impl<
   }
 }
 
-impl<
-    T: 'static,
-    Return: FromNapiValue,
-    CallJsBackArgs: 'static + JsValuesTupleIntoVec,
-    ErrorStatus: AsRef<str> + From<Status>,
-    const Weak: bool,
-    const MaxQueueSize: usize,
-  > ThreadsafeFunction<T, Return, CallJsBackArgs, ErrorStatus, false, { Weak }, { MaxQueueSize }>
+impl<T: 'static, Return: FromNapiValue, ErrorStatus: AsRef<str> + From<Status>>
+  ThreadsafeFunctionHandle<T, Return, ErrorStatus, false>
 {
   /// See [napi_call_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_call_threadsafe_function)
   /// for more information.
   pub fn call(&self, value: T, mode: ThreadsafeFunctionCallMode) -> Status {
-    self.handle.with_read_aborted(|aborted| {
+    self.inner.with_read_aborted(|aborted| {
       if aborted {
         return Status::Closing;
       }
+      if self.inner.would_deadlock(mode) {
+        return Status::WouldDeadlock;
+      }
 
       unsafe {
-        sys::napi_call_threadsafe_function(
-          self.handle.get_raw(),
+        self.inner.call_raw(
           Box::into_raw(Box::new(ThreadsafeFunctionCallJsBackData {
             data: value,
             call_variant: ThreadsafeFunctionCallVariant::Direct,
             callback: Box::new(|_d: Result<Return>, _: Env| Ok(())),
           }))
           .cast(),
-          mode.into(),
+          mode,
         )
       }
       .into()
     })
   }
 
+  /// Like `call`, but an `Urgent` call is delivered ahead of the normal calls waiting in the queue.
+  ///
+  /// It still takes a slot of the queue, so with a `max_queue_size` it waits for, or is rejected by, a full queue
+  /// like the normal calls. The urgent calls are delivered in the order they are made.
+  pub fn call_with_priority(
+    &self,
+    value: T,
+    mode: ThreadsafeFunctionCallMode,
+    priority: ThreadsafeFunctionCallPriority,
+  ) -> Status {
+    if priority == ThreadsafeFunctionCallPriority::Normal {
+      return self.call(value, mode);
+    }
+    self.inner.with_read_aborted(|aborted| {
+      if aborted {
+        return Status::Closing;
+      }
+      if self.inner.would_deadlock(mode) {
+        return Status::WouldDeadlock;
+      }
+
+      self
+        .inner
+        .call_urgent(
+          PendingCall::new(ThreadsafeFunctionCallJsBackData {
+            data: value,
+            call_variant: ThreadsafeFunctionCallVariant::Direct,
+            callback: Box::new(|_d: Result<Return>, _: Env| Ok(())),
+          }),
+          mode,
+        )
+        .into()
+    })
+  }
+
   /// Call the ThreadsafeFunction, and handle the return value with a callback
   pub fn call_with_return_value<F: 'static + FnOnce(Result<Return>, Env) -> Result<()>>(
     &self,
@@ -594,40 +1606,56 @@ impl<
     mode: ThreadsafeFunctionCallMode,
     cb: F,
   ) -> Status {
-    self.handle.with_read_aborted(|aborted| {
+    self.inner.with_read_aborted(|aborted| {
       if aborted {
         return Status::Closing;
       }
+      if self.inner.would_deadlock(mode) {
+        return Status::WouldDeadlock;
+      }
 
       unsafe {
-        sys::napi_call_threadsafe_function(
-          self.handle.get_raw(),
+        self.inner.call_raw(
           Box::into_raw(Box::new(ThreadsafeFunctionCallJsBackData {
             data: value,
             call_variant: ThreadsafeFunctionCallVariant::WithCallback,
             callback: Box::new(cb),
           }))
           .cast(),
-          mode.into(),
+          mode,
         )
       }
       .into()
     })
   }
 
+  /// Call the ThreadsafeFunction without blocking the thread, the returned future resolves once the value is enqueued.
+  ///
+  /// If the queue is full, which only happens with a `max_queue_size` set on the builder, it waits for the JavaScript
+  /// thread to take an item off the queue, so a fast producer is slowed down to the pace of JavaScript.
+  pub async fn call_async_bounded(&self, value: T) -> Result<()> {
+    self
+      .inner
+      .call_bounded(PendingCall::new(ThreadsafeFunctionCallJsBackData {
+        data: value,
+        call_variant: ThreadsafeFunctionCallVariant::Direct,
+        callback: Box::new(|_d: Result<Return>, _: Env| Ok(())),
+      }))
+      .await
+  }
+
   #[cfg(feature = "tokio_rt")]
   /// Call the ThreadsafeFunction, and handle the return value with in `async` way
   pub async fn call_async(&self, value: T) -> Result<Return> {
     let (sender, receiver) = tokio::sync::oneshot::channel::<Return>();
 
-    self.handle.with_read_aborted(|aborted| {
+    self.inner.with_read_aborted(|aborted| {
       if aborted {
         return Err(crate::Error::from_status(Status::Closing));
       }
 
       check_status!(unsafe {
-        sys::napi_call_threadsafe_function(
-          self.handle.get_raw(),
+        self.inner.call_raw(
           Box::into_raw(Box::new(ThreadsafeFunctionCallJsBackData {
             data: value,
             call_variant: ThreadsafeFunctionCallVariant::WithCallback,
@@ -642,7 +1670,7 @@ impl<
             }),
           }))
           .cast(),
-          ThreadsafeFunctionCallMode::NonBlocking.into(),
+          ThreadsafeFunctionCallMode::NonBlocking,
         )
       })
     })?;
@@ -660,19 +1688,21 @@ unsafe extern "C" fn thread_finalize_cb<T: 'static, V: 'static + JsValuesTupleIn
 ) where
   R: 'static + FnMut(ThreadsafeCallContext<T>) -> Result<V>,
 {
-  let handle_option: Option<Arc<ThreadsafeFunctionHandle>> =
+  let handle_option: Option<Arc<ThreadsafeFunctionInner>> =
     unsafe { sync::Weak::from_raw(finalize_data.cast()).upgrade() };
 
   if let Some(handle) = handle_option {
-    handle.with_write_aborted(|mut aborted_guard| {
-      if !*aborted_guard {
-        *aborted_guard = true;
+    handle.with_write_aborted(|aborted| {
+      if !*aborted {
+        *aborted = true;
       }
     });
   }
 
   // cleanup
-  drop(unsafe { Box::<R>::from_raw(finalize_hint.cast()) });
+  let context = unsafe { Box::<ThreadsafeFunctionContext<R>>::from_raw(finalize_hint.cast()) };
+  // the items left in the queue are discarded, the bounded calls waiting for a slot fail with `Closing`
+  context.queue_slots.finalize();
 }
 
 unsafe extern "C" fn call_js_cb<
@@ -690,12 +1720,57 @@ unsafe extern "C" fn call_js_cb<
 ) where
   R: 'static + FnMut(ThreadsafeCallContext<T>) -> Result<V>,
 {
-  // env and/or callback can be null when shutting down
+  // env and/or callback can be null when shutting down, the context is already dropped by `thread_finalize_cb` then
   if raw_env.is_null() || js_callback.is_null() {
     return;
   }
 
-  let callback: &mut R = unsafe { Box::leak(Box::from_raw(context.cast())) };
+  let context: &mut ThreadsafeFunctionContext<R> =
+    unsafe { Box::leak(Box::from_raw(context.cast())) };
+  // the item is taken off the queue
+  context.queue_slots.release();
+  context.keepalive.delivered();
+
+  let _watchdog = crate::bindgen_runtime::LoopWatchdogScope::enter_threadsafe_function();
+  // the urgent calls jump ahead of the item, which is null if it only wakes up the JavaScript thread for them
+  while let Some(urgent) = context.queue_slots.take_urgent() {
+    let status = unsafe {
+      deliver::<T, Return, V, ErrorStatus, R, CalleeHandled>(
+        raw_env,
+        js_callback,
+        context,
+        urgent.into_raw(),
+      )
+    };
+    handle_call_js_cb_status(status, raw_env);
+  }
+  if !data.is_null() {
+    let status = unsafe {
+      deliver::<T, Return, V, ErrorStatus, R, CalleeHandled>(raw_env, js_callback, context, data)
+    };
+    handle_call_js_cb_status(status, raw_env);
+  }
+  context.keepalive.update();
+}
+
+/// Call the JavaScript function with an item of the queue
+unsafe fn deliver<
+  T: 'static,
+  Return: FromNapiValue,
+  V: 'static + JsValuesTupleIntoVec,
+  ErrorStatus: AsRef<str> + From<Status>,
+  R,
+  const CalleeHandled: bool,
+>(
+  raw_env: sys::napi_env,
+  js_callback: sys::napi_value,
+  context: &mut ThreadsafeFunctionContext<R>,
+  data: *mut c_void,
+) -> sys::napi_status
+where
+  R: 'static + FnMut(ThreadsafeCallContext<T>) -> Result<V>,
+{
+  let callback = &mut context.callback;
   let val = unsafe {
     if CalleeHandled {
       *Box::<Result<ThreadsafeFunctionCallJsBackData<T, Return>, ErrorStatus>>::from_raw(
@@ -714,14 +1789,20 @@ unsafe extern "C" fn call_js_cb<
       env: Env::from_raw(raw_env),
       value: v.data,
     })
-    .and_then(|ret| Ok((ret.into_vec(raw_env)?, v.call_variant, v.callback)))
+    .and_then(|ret| {
+      Ok((
+        ret.into_threadsafe_args(raw_env)?,
+        v.call_variant,
+        v.callback,
+      ))
+    })
     .map_err(|err| Error::new(err.status.into(), err.reason.clone()))
   });
 
   // Follow async callback conventions: https://nodejs.org/en/knowledge/errors/what-are-the-error-conventions/
   // Check if the Result is okay, if so, pass a null as the first (error) argument automatically.
   // If the Result is an error, pass that as the first argument.
-  let status = match ret {
+  match ret {
     Ok((values, call_variant, callback)) => {
       let args: Vec<sys::napi_value> = if CalleeHandled {
         let mut js_null = ptr::null_mut();
@@ -753,6 +1834,7 @@ unsafe extern "C" fn call_js_cb<
             Err(Error {
               maybe_raw: error_reference,
               maybe_env: raw_env,
+              maybe_value: None,
               status: Status::from(raw_status),
               reason,
             })
@@ -761,14 +1843,22 @@ unsafe extern "C" fn call_js_cb<
           unsafe { Return::from_napi_value(raw_env, return_value) }
         };
         if let Err(err) = callback(callback_arg, Env::from_raw(raw_env)) {
-          unsafe { sys::napi_fatal_exception(raw_env, JsError::from(err).into_value(raw_env)) };
+          report_error(&Env::from_raw(raw_env), context.error_sink.as_deref(), err);
         }
       }
       status
     }
-    Err(e) if !CalleeHandled => unsafe {
-      sys::napi_fatal_exception(raw_env, JsError::from(e).into_value(raw_env))
-    },
+    Err(e) if !CalleeHandled => {
+      // the status of `ErrorStatus` is kept in the `code` of the JavaScript error
+      let error = unsafe { JsError::from(e).into_value(raw_env) };
+      let error = Error::from(unsafe { Unknown::from_raw_unchecked(raw_env, error) });
+      report_error(
+        &Env::from_raw(raw_env),
+        context.error_sink.as_deref(),
+        error,
+      );
+      sys::Status::napi_ok
+    }
     Err(e) => unsafe {
       sys::napi_call_function(
         raw_env,
@@ -779,8 +1869,7 @@ unsafe extern "C" fn call_js_cb<
         ptr::null_mut(),
       )
     },
-  };
-  handle_call_js_cb_status(status, raw_env)
+  }
 }
 
 fn handle_call_js_cb_status(status: sys::napi_status, raw_env: sys::napi_env) {
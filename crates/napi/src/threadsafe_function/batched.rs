@@ -0,0 +1,286 @@
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::handle_call_js_cb_status;
+use crate::bindgen_runtime::ToNapiValue;
+use crate::error_sink::report_error;
+use crate::uv::UvTimer;
+use crate::{check_status, sys, Env, Error, ErrorSink, Result, Status};
+
+/// A threadsafe function which delivers the values to the JavaScript callback in batches, as a single array.
+///
+/// The values queued before the JavaScript thread wakes up are coalesced into one call, and at most one call is made
+/// per `flush_interval` unless `max_batch_size` values are waiting, so high frequency events like file changes or
+/// metrics don't wake up the event loop for each value.
+///
+/// It's created by `ThreadsafeFunctionBuilder::build_batched`:
+///
+/// ```rust
+/// #[napi]
+/// pub fn watch(callback: Function<Vec<String>, ()>) -> Result<()> {
+///   let tsfn = callback
+///     .build_threadsafe_function()
+///     .build_batched(64, Duration::from_millis(10))?;
+///   std::thread::spawn(move || {
+///     for path in changed_paths() {
+///       tsfn.call(path);
+///     }
+///   });
+///   Ok(())
+/// }
+/// ```
+///
+/// The values still waiting when it's dropped are delivered right away, without waiting for the interval.
+pub struct BatchedThreadsafeFunction<T: 'static> {
+  raw: sys::napi_threadsafe_function,
+  shared: Arc<BatchShared<T>>,
+}
+
+unsafe impl<T: Send> Send for BatchedThreadsafeFunction<T> {}
+unsafe impl<T: Send> Sync for BatchedThreadsafeFunction<T> {}
+
+impl<T: 'static + ToNapiValue> BatchedThreadsafeFunction<T> {
+  pub(crate) fn create(
+    env: sys::napi_env,
+    func: sys::napi_value,
+    max_batch_size: usize,
+    flush_interval: Duration,
+    weak: bool,
+    error_sink: Option<Rc<dyn ErrorSink>>,
+  ) -> Result<Self> {
+    if max_batch_size == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The max batch size of BatchedThreadsafeFunction must not be 0",
+      ));
+    }
+    let async_resource_name =
+      unsafe { ToNapiValue::to_napi_value(env, "napi_rs_batched_threadsafe_function") }?;
+    let shared = Arc::new(BatchShared {
+      state: Mutex::new(BatchState {
+        items: Vec::new(),
+        scheduled: false,
+        closing: false,
+        finalized: false,
+      }),
+      max_batch_size,
+      flush_interval,
+    });
+    let context = Box::into_raw(Box::new(BatchContext {
+      raw: ptr::null_mut(),
+      shared: shared.clone(),
+      last_flush: None,
+      timer: None,
+      error_sink,
+    }));
+    let mut raw = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env,
+          func,
+          ptr::null_mut(),
+          async_resource_name,
+          0,
+          1,
+          context.cast(),
+          Some(finalize_batch_context::<T>),
+          context.cast(),
+          Some(call_batch_js::<T>),
+          &mut raw,
+        )
+      },
+      "Create threadsafe function in BatchedThreadsafeFunction::create failed"
+    )
+    .inspect_err(|_| drop(unsafe { Box::from_raw(context) }))?;
+    unsafe { (*context).raw = raw };
+    crate::cleanup_env::on_threadsafe_function_created(env);
+
+    // Weak BatchedThreadsafeFunction will not prevent the event loop from exiting
+    if weak {
+      check_status!(
+        unsafe { sys::napi_unref_threadsafe_function(env, raw) },
+        "Unref threadsafe function failed in Weak mode"
+      )?;
+    }
+
+    Ok(Self { raw, shared })
+  }
+
+  /// Queue `value` for the next batch, it never blocks the thread.
+  pub fn call(&self, value: T) -> Status {
+    let mut state = self.shared.lock();
+    if state.closing || state.finalized {
+      return Status::Closing;
+    }
+    state.items.push(value);
+    // the pending batch is flushed without waiting for the interval once it's full
+    if state.scheduled && state.items.len() != self.shared.max_batch_size {
+      return Status::Ok;
+    }
+    state.scheduled = true;
+    signal(self.raw)
+  }
+
+  /// The number of values waiting for the next batch.
+  pub fn pending(&self) -> usize {
+    self.shared.lock().items.len()
+  }
+}
+
+impl<T: 'static> Drop for BatchedThreadsafeFunction<T> {
+  fn drop(&mut self) {
+    {
+      let mut state = self.shared.lock();
+      if state.finalized {
+        return;
+      }
+      state.closing = true;
+      if !state.items.is_empty() {
+        signal(self.raw);
+      }
+    }
+    unsafe {
+      sys::napi_release_threadsafe_function(self.raw, sys::ThreadsafeFunctionReleaseMode::release)
+    };
+  }
+}
+
+struct BatchShared<T> {
+  state: Mutex<BatchState<T>>,
+  max_batch_size: usize,
+  flush_interval: Duration,
+}
+
+impl<T> BatchShared<T> {
+  fn lock(&self) -> MutexGuard<'_, BatchState<T>> {
+    self
+      .state
+      .lock()
+      .expect("BatchedThreadsafeFunction state lock failed")
+  }
+}
+
+struct BatchState<T> {
+  items: Vec<T>,
+  // a wake up of the JavaScript thread is queued, or the flush timer is started
+  scheduled: bool,
+  // the function is released, the remaining values are delivered without waiting for the interval
+  closing: bool,
+  finalized: bool,
+}
+
+// Owned by the JavaScript thread
+struct BatchContext<T> {
+  raw: sys::napi_threadsafe_function,
+  shared: Arc<BatchShared<T>>,
+  last_flush: Option<Instant>,
+  timer: Option<UvTimer>,
+  error_sink: Option<Rc<dyn ErrorSink>>,
+}
+
+impl<T> BatchContext<T> {
+  fn start_timer(&mut self, env: &Env, timeout: Duration) -> Result<()> {
+    if self.timer.is_none() {
+      let raw = self.raw;
+      let timer = UvTimer::new(env, move || {
+        signal(raw);
+      })?;
+      // the threadsafe function keeps the event loop alive if it's not weak
+      timer.unref();
+      self.timer = Some(timer);
+    }
+    match &self.timer {
+      Some(timer) => timer.start(timeout, Duration::ZERO),
+      None => Ok(()),
+    }
+  }
+}
+
+fn signal(raw: sys::napi_threadsafe_function) -> Status {
+  unsafe {
+    sys::napi_call_threadsafe_function(
+      raw,
+      ptr::null_mut(),
+      sys::ThreadsafeFunctionCallMode::nonblocking,
+    )
+  }
+  .into()
+}
+
+unsafe extern "C" fn finalize_batch_context<T>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  let context = unsafe { Box::<BatchContext<T>>::from_raw(finalize_data.cast()) };
+  context.shared.lock().finalized = true;
+}
+
+unsafe extern "C" fn call_batch_js<T: ToNapiValue>(
+  raw_env: sys::napi_env,
+  js_callback: sys::napi_value,
+  context: *mut c_void,
+  _data: *mut c_void,
+) {
+  // env and/or callback can be null when shutting down
+  if raw_env.is_null() || js_callback.is_null() {
+    return;
+  }
+
+  let _watchdog = crate::bindgen_runtime::LoopWatchdogScope::enter_threadsafe_function();
+  let context: &mut BatchContext<T> = unsafe { &mut *context.cast() };
+  let env = Env::from_raw(raw_env);
+  let shared = context.shared.clone();
+  loop {
+    let (batch, closing) = {
+      let mut state = shared.lock();
+      if state.items.is_empty() {
+        state.scheduled = false;
+        return;
+      }
+      let since_last_flush = context.last_flush.map(|last_flush| last_flush.elapsed());
+      let due = state.closing
+        || state.items.len() >= shared.max_batch_size
+        || since_last_flush.map_or(true, |elapsed| elapsed >= shared.flush_interval);
+      if !due {
+        drop(state);
+        let timeout = shared.flush_interval - since_last_flush.unwrap_or_default();
+        if let Err(err) = context.start_timer(&env, timeout) {
+          report_error(&env, context.error_sink.as_deref(), err);
+        }
+        return;
+      }
+      let len = state.items.len().min(shared.max_batch_size);
+      (state.items.drain(..len).collect::<Vec<T>>(), state.closing)
+    };
+    context.last_flush = Some(Instant::now());
+
+    let status = match unsafe { Vec::<T>::to_napi_value(raw_env, batch) } {
+      Ok(batch) => {
+        let mut recv = ptr::null_mut();
+        unsafe { sys::napi_get_undefined(raw_env, &mut recv) };
+        unsafe { sys::napi_call_function(raw_env, recv, js_callback, 1, &batch, ptr::null_mut()) }
+      }
+      Err(err) => {
+        report_error(&env, context.error_sink.as_deref(), err);
+        sys::Status::napi_ok
+      }
+    };
+    handle_call_js_cb_status(status, raw_env);
+
+    // the rest is delivered in the next tick, or right away once the function is released
+    if !closing {
+      let mut state = shared.lock();
+      if state.items.is_empty() {
+        state.scheduled = false;
+      } else {
+        signal(context.raw);
+      }
+      return;
+    }
+  }
+}
@@ -2,6 +2,9 @@
 use std::sync::{LazyLock, OnceLock, RwLock};
 use std::{future::Future, marker::PhantomData};
 
+#[cfg(not(feature = "noop"))]
+use crate::executor::SendableResolver;
+
 use tokio::runtime::Runtime;
 
 use crate::{bindgen_runtime::ToNapiValue, sys, Env, Error, Result};
@@ -14,6 +17,7 @@ fn create_runtime() -> Runtime {
     .get()
     .and_then(|rt| rt.write().ok().and_then(|mut rt| rt.take()))
   {
+    crate::runtime::mark_started();
     return user_defined_rt;
   }
   #[cfg(any(
@@ -21,17 +25,11 @@ fn create_runtime() -> Runtime {
     not(target_family = "wasm")
   ))]
   {
-    tokio::runtime::Builder::new_multi_thread()
-      .enable_all()
-      .build()
-      .expect("Create tokio runtime failed")
+    crate::runtime::build(&mut tokio::runtime::Builder::new_multi_thread())
   }
   #[cfg(all(target_family = "wasm", not(tokio_unstable)))]
   {
-    tokio::runtime::Builder::new_current_thread()
-      .enable_all()
-      .build()
-      .expect("Create tokio runtime failed")
+    crate::runtime::build(&mut tokio::runtime::Builder::new_current_thread())
   }
 }
 
@@ -83,7 +81,7 @@ pub fn start_async_runtime() {
 #[cfg(not(feature = "noop"))]
 pub fn shutdown_async_runtime() {
   if let Some(rt) = RT.write().ok().and_then(|mut rt| rt.take()) {
-    rt.shutdown_background();
+    crate::runtime::shutdown(rt);
   }
 }
 
@@ -159,41 +157,6 @@ pub fn within_runtime_if_available<F: FnOnce() -> T, T>(f: F) -> T {
   f()
 }
 
-#[cfg(not(feature = "noop"))]
-struct SendableResolver<
-  Data: 'static + Send,
-  R: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
-> {
-  inner: R,
-  _data: PhantomData<Data>,
-}
-
-#[cfg(not(feature = "noop"))]
-// the `SendableResolver` will be only called in the `threadsafe_function_call_js` callback
-// which means it will be always called in the Node.js JavaScript thread
-// so the inner function is not required to be `Send`
-// but the `Send` bound is required by the `execute_tokio_future` function
-unsafe impl<Data: 'static + Send, R: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>>
-  Send for SendableResolver<Data, R>
-{
-}
-
-#[cfg(not(feature = "noop"))]
-impl<Data: 'static + Send, R: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>>
-  SendableResolver<Data, R>
-{
-  fn new(inner: R) -> Self {
-    Self {
-      inner,
-      _data: PhantomData,
-    }
-  }
-
-  fn resolve(self, env: sys::napi_env, data: Data) -> Result<sys::napi_value> {
-    (self.inner)(env, data)
-  }
-}
-
 #[cfg(feature = "noop")]
 #[allow(unused)]
 pub fn execute_tokio_future<
@@ -219,6 +182,10 @@ pub fn execute_tokio_future<
   fut: Fut,
   resolver: Resolver,
 ) -> Result<sys::napi_value> {
+  // an embedder's runtime replaces the tokio runtime
+  if crate::executor::custom_executor().is_some() {
+    return crate::executor::execute_future(env, fut, resolver);
+  }
   let env = Env::from_raw(env);
   let (deferred, promise) = JsDeferred::new(&env)?;
   #[cfg(any(
@@ -0,0 +1,307 @@
+//! The libuv handles on the event loop of the env, to run native work on the JavaScript thread without
+//! spawning a thread and calling back through a [`crate::threadsafe_function::ThreadsafeFunction`].
+//!
+//! The callbacks are called by the event loop, outside of any JavaScript call. The handles are closed when they are
+//! dropped, or when the env is torn down. After the env is torn down, starting or stopping a handle returns an
+//! error with `Status::Closing`, and the other methods do nothing.
+//!
+//! ```
+//! let mut ticks = 0;
+//! let timer = UvTimer::new(&env, move || {
+//!   ticks += 1;
+//! })?;
+//! timer.start(Duration::from_millis(100), Duration::from_millis(100))?;
+//! // don't keep the process alive for the timer
+//! timer.unref();
+//! ```
+
+use std::alloc::{self, Layout};
+use std::cell::Cell;
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_int;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::{check_status, sys, Env, Error, Result, Status};
+
+/// A `uv_timer_t`, which calls the callback after a timeout, and then repeatedly if it has a repeat interval.
+pub struct UvTimer(Handle);
+
+impl UvTimer {
+  pub fn new<F: FnMut() + 'static>(env: &Env, callback: F) -> Result<Self> {
+    Handle::new(
+      env,
+      sys::uv_handle_type::UV_TIMER,
+      callback,
+      |uv_loop, raw| unsafe { sys::uv_timer_init(uv_loop, raw.cast()) },
+    )
+    .map(Self)
+  }
+
+  /// Call the callback after `timeout`, and then every `repeat` if it's not zero.
+  ///
+  /// The timer is restarted if it's already started.
+  pub fn start(&self, timeout: Duration, repeat: Duration) -> Result<()> {
+    let raw = self.0.raw()?;
+    check_uv(
+      unsafe {
+        sys::uv_timer_start(
+          raw.cast(),
+          Some(timer_cb),
+          timeout.as_millis() as u64,
+          repeat.as_millis() as u64,
+        )
+      },
+      "uv_timer_start",
+    )
+  }
+
+  pub fn stop(&self) -> Result<()> {
+    let raw = self.0.raw()?;
+    check_uv(unsafe { sys::uv_timer_stop(raw.cast()) }, "uv_timer_stop")
+  }
+
+  /// Keep the event loop alive while the timer is active, which is the default.
+  pub fn refer(&self) {
+    self.0.refer()
+  }
+
+  /// Don't keep the event loop alive for the timer, like `timeout.unref()` in Node.js.
+  pub fn unref(&self) {
+    self.0.unref()
+  }
+
+  pub fn has_ref(&self) -> bool {
+    self.0.has_ref()
+  }
+}
+
+/// A `uv_idle_t`, which calls the callback once per loop iteration while it's started.
+///
+/// The event loop doesn't block for I/O while an idle handle is active, use [`UvCheck`] to run after the I/O instead.
+pub struct UvIdle(Handle);
+
+impl UvIdle {
+  pub fn new<F: FnMut() + 'static>(env: &Env, callback: F) -> Result<Self> {
+    Handle::new(
+      env,
+      sys::uv_handle_type::UV_IDLE,
+      callback,
+      |uv_loop, raw| unsafe { sys::uv_idle_init(uv_loop, raw.cast()) },
+    )
+    .map(Self)
+  }
+
+  pub fn start(&self) -> Result<()> {
+    let raw = self.0.raw()?;
+    check_uv(
+      unsafe { sys::uv_idle_start(raw.cast(), Some(idle_cb)) },
+      "uv_idle_start",
+    )
+  }
+
+  pub fn stop(&self) -> Result<()> {
+    let raw = self.0.raw()?;
+    check_uv(unsafe { sys::uv_idle_stop(raw.cast()) }, "uv_idle_stop")
+  }
+
+  /// Keep the event loop alive while the handle is active, which is the default.
+  pub fn refer(&self) {
+    self.0.refer()
+  }
+
+  /// Don't keep the event loop alive for the handle.
+  pub fn unref(&self) {
+    self.0.unref()
+  }
+
+  pub fn has_ref(&self) -> bool {
+    self.0.has_ref()
+  }
+}
+
+/// A `uv_check_t`, which calls the callback once per loop iteration after polling for I/O, like `setImmediate`.
+pub struct UvCheck(Handle);
+
+impl UvCheck {
+  pub fn new<F: FnMut() + 'static>(env: &Env, callback: F) -> Result<Self> {
+    Handle::new(
+      env,
+      sys::uv_handle_type::UV_CHECK,
+      callback,
+      |uv_loop, raw| unsafe { sys::uv_check_init(uv_loop, raw.cast()) },
+    )
+    .map(Self)
+  }
+
+  pub fn start(&self) -> Result<()> {
+    let raw = self.0.raw()?;
+    check_uv(
+      unsafe { sys::uv_check_start(raw.cast(), Some(check_cb)) },
+      "uv_check_start",
+    )
+  }
+
+  pub fn stop(&self) -> Result<()> {
+    let raw = self.0.raw()?;
+    check_uv(unsafe { sys::uv_check_stop(raw.cast()) }, "uv_check_stop")
+  }
+
+  /// Keep the event loop alive while the handle is active, which is the default.
+  ///
+  /// A check handle alone doesn't keep the event loop alive, the loop still exits once there is nothing else to do.
+  pub fn refer(&self) {
+    self.0.refer()
+  }
+
+  /// Don't keep the event loop alive for the handle.
+  pub fn unref(&self) {
+    self.0.unref()
+  }
+
+  pub fn has_ref(&self) -> bool {
+    self.0.has_ref()
+  }
+}
+
+// The handle and the callback are freed by the close callback of libuv, which is called in a later loop iteration,
+// so the callback can drop its own handle.
+struct Handle {
+  raw: *mut sys::uv_handle_s,
+  env: sys::napi_env,
+  // set once the handle is closed by the env teardown, the memory may be freed then
+  closed: Rc<Cell<bool>>,
+}
+
+struct HandleData {
+  callback: Box<dyn FnMut()>,
+  layout: Layout,
+  closed: Rc<Cell<bool>>,
+}
+
+impl Handle {
+  fn new<F: FnMut() + 'static>(
+    env: &Env,
+    handle_type: sys::uv_handle_type,
+    callback: F,
+    init: impl FnOnce(*mut sys::uv_loop_s, *mut sys::uv_handle_s) -> c_int,
+  ) -> Result<Self> {
+    let uv_loop = env.get_uv_event_loop()?;
+    // the handle types are opaque, their sizes are provided by libuv
+    let layout = Layout::from_size_align(
+      unsafe { sys::uv_handle_size(handle_type) },
+      std::mem::align_of::<u128>(),
+    )
+    .map_err(|err| Error::new(Status::GenericFailure, err.to_string()))?;
+    let raw = unsafe { alloc::alloc_zeroed(layout) }.cast::<sys::uv_handle_s>();
+    if raw.is_null() {
+      alloc::handle_alloc_error(layout);
+    }
+    if let Err(err) = check_uv(init(uv_loop, raw), "Init uv handle") {
+      unsafe { alloc::dealloc(raw.cast(), layout) };
+      return Err(err);
+    }
+    let closed = Rc::new(Cell::new(false));
+    let data = Box::new(HandleData {
+      callback: Box::new(callback),
+      layout,
+      closed: closed.clone(),
+    });
+    unsafe { sys::uv_handle_set_data(raw, Box::into_raw(data).cast()) };
+    if let Err(err) = check_status!(
+      unsafe { sys::napi_add_env_cleanup_hook(env.0, Some(close_on_teardown), raw.cast()) },
+      "Add the cleanup hook of the uv handle failed"
+    ) {
+      unsafe { close(raw) };
+      return Err(err);
+    }
+    Ok(Self {
+      raw,
+      env: env.0,
+      closed,
+    })
+  }
+
+  fn raw(&self) -> Result<*mut sys::uv_handle_s> {
+    if self.closed.get() {
+      return Err(Error::new(
+        Status::Closing,
+        "The uv handle is closed by the env teardown",
+      ));
+    }
+    Ok(self.raw)
+  }
+
+  fn refer(&self) {
+    if let Ok(raw) = self.raw() {
+      unsafe { sys::uv_ref(raw) }
+    }
+  }
+
+  fn unref(&self) {
+    if let Ok(raw) = self.raw() {
+      unsafe { sys::uv_unref(raw) }
+    }
+  }
+
+  fn has_ref(&self) -> bool {
+    self
+      .raw()
+      .is_ok_and(|raw| unsafe { sys::uv_has_ref(raw) != 0 })
+  }
+}
+
+impl Drop for Handle {
+  fn drop(&mut self) {
+    if self.closed.get() {
+      return;
+    }
+    unsafe {
+      sys::napi_remove_env_cleanup_hook(self.env, Some(close_on_teardown), self.raw.cast());
+      close(self.raw);
+    }
+  }
+}
+
+unsafe fn close(raw: *mut sys::uv_handle_s) {
+  let data = unsafe { sys::uv_handle_get_data(raw) }.cast::<HandleData>();
+  unsafe { (*data).closed.set(true) };
+  unsafe { sys::uv_close(raw, Some(free_handle)) };
+}
+
+unsafe extern "C" fn close_on_teardown(raw: *mut c_void) {
+  unsafe { close(raw.cast()) };
+}
+
+unsafe extern "C" fn free_handle(raw: *mut sys::uv_handle_s) {
+  let data = unsafe { Box::from_raw(sys::uv_handle_get_data(raw).cast::<HandleData>()) };
+  unsafe { alloc::dealloc(raw.cast(), data.layout) };
+}
+
+unsafe fn call(raw: *mut sys::uv_handle_s) {
+  let data = unsafe { sys::uv_handle_get_data(raw) }.cast::<HandleData>();
+  unsafe { ((*data).callback)() };
+}
+
+unsafe extern "C" fn timer_cb(raw: *mut sys::uv_timer_s) {
+  unsafe { call(raw.cast()) };
+}
+
+unsafe extern "C" fn idle_cb(raw: *mut sys::uv_idle_s) {
+  unsafe { call(raw.cast()) };
+}
+
+unsafe extern "C" fn check_cb(raw: *mut sys::uv_check_s) {
+  unsafe { call(raw.cast()) };
+}
+
+fn check_uv(code: c_int, operation: &str) -> Result<()> {
+  if code >= 0 {
+    return Ok(());
+  }
+  let message = unsafe { CStr::from_ptr(sys::uv_strerror(code)) };
+  Err(Error::new(
+    Status::GenericFailure,
+    format!("{operation} failed: {}", message.to_string_lossy()),
+  ))
+}
@@ -1,5 +1,6 @@
 use crate::{sys, Error, Status};
 use std::ffi::CStr;
+use std::fmt;
 
 #[derive(Debug, Clone, Copy)]
 pub struct NodeVersion {
@@ -9,6 +10,19 @@ pub struct NodeVersion {
   pub release: &'static str,
 }
 
+impl NodeVersion {
+  /// Whether the version is `major.minor.patch` or later.
+  pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+    (self.major, self.minor, self.patch) >= (major, minor, patch)
+  }
+}
+
+impl fmt::Display for NodeVersion {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+  }
+}
+
 impl TryFrom<sys::napi_node_version> for NodeVersion {
   type Error = Error;
 
@@ -25,3 +39,38 @@ impl TryFrom<sys::napi_node_version> for NodeVersion {
     })
   }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+/// The Node-API features which the runtime may not support, checked by [`crate::Env::runtime_supports`].
+///
+/// The addon compiled with a newer `napiN` feature still loads on the older runtimes, but the newer APIs fail there,
+/// so check them before calling to fall back gracefully.
+pub enum Feature {
+  /// `ThreadsafeFunction`
+  ThreadsafeFunction,
+  /// `BigInt`, `napi_get_all_property_names`, and the instance data
+  BigInt,
+  /// `ArrayBuffer::detach`
+  DetachArrayBuffer,
+  /// The async cleanup hooks, the type tags and `Object::freeze`/`seal`
+  AsyncCleanupHook,
+  /// `Env::symbol_for`, `Env::throw_syntax_error` and `Env::get_module_file_name`
+  SymbolFor,
+  /// The external strings and `Env::create_property_key_utf16`
+  ExternalString,
+}
+
+impl Feature {
+  /// The Node-API version introducing the feature.
+  pub fn napi_version(self) -> u32 {
+    match self {
+      Feature::ThreadsafeFunction => 4,
+      Feature::BigInt => 6,
+      Feature::DetachArrayBuffer => 7,
+      Feature::AsyncCleanupHook => 8,
+      Feature::SymbolFor => 9,
+      Feature::ExternalString => 10,
+    }
+  }
+}
@@ -0,0 +1,171 @@
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use napi::executor::{executor, set_executor, BoxFuture, Executor, SingleThreadExecutor};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pending until `wake` is called from another thread.
+#[derive(Clone, Default)]
+struct Signal(Arc<Mutex<(bool, Option<Waker>)>>);
+
+impl Signal {
+  fn wake(&self) {
+    let mut state = self.0.lock().unwrap();
+    state.0 = true;
+    if let Some(waker) = state.1.take() {
+      waker.wake();
+    }
+  }
+}
+
+impl Future for Signal {
+  type Output = ();
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    let mut state = self.0.lock().unwrap();
+    if state.0 {
+      return Poll::Ready(());
+    }
+    state.1 = Some(cx.waker().clone());
+    Poll::Pending
+  }
+}
+
+#[test]
+fn single_thread_executor_runs_futures() {
+  let executor = SingleThreadExecutor::new();
+  let (sender, receiver) = mpsc::channel();
+  let signal = Signal::default();
+  let pending = signal.clone();
+  let first = sender.clone();
+  executor.spawn(Box::pin(async move {
+    pending.await;
+    first.send("woken").unwrap();
+  }));
+  executor.spawn(Box::pin(async move {
+    sender.send("ready").unwrap();
+  }));
+  assert_eq!(receiver.recv_timeout(TIMEOUT), Ok("ready"));
+  signal.wake();
+  assert_eq!(receiver.recv_timeout(TIMEOUT), Ok("woken"));
+}
+
+#[test]
+fn single_thread_executor_survives_panics() {
+  let executor = SingleThreadExecutor::new();
+  let (sender, receiver) = mpsc::channel();
+  executor.spawn(Box::pin(async {
+    panic!("panic in the future");
+  }));
+  executor.spawn(Box::pin(async move {
+    sender.send(()).unwrap();
+  }));
+  assert_eq!(receiver.recv_timeout(TIMEOUT), Ok(()));
+}
+
+#[test]
+fn single_thread_executor_runs_blocking_tasks() {
+  let executor = SingleThreadExecutor::new();
+  let (sender, receiver) = mpsc::channel();
+  executor.spawn_blocking(Box::new(move || {
+    sender
+      .send(std::thread::current().name().map(ToOwned::to_owned))
+      .unwrap();
+  }));
+  assert_eq!(
+    receiver.recv_timeout(TIMEOUT),
+    Ok(Some("napi-rs-blocking".to_owned()))
+  );
+}
+
+#[test]
+fn single_thread_executor_bounds_the_blocking_threads() {
+  let executor = SingleThreadExecutor::with_max_blocking_threads(2);
+  let (sender, receiver) = mpsc::channel();
+  for _ in 0..6 {
+    let sender = sender.clone();
+    executor.spawn_blocking(Box::new(move || {
+      std::thread::sleep(Duration::from_millis(20));
+      sender.send(std::thread::current().id()).unwrap();
+    }));
+  }
+  let mut threads = (0..6)
+    .map(|_| receiver.recv_timeout(TIMEOUT).unwrap())
+    .collect::<Vec<_>>();
+  threads.sort_by_key(|id| format!("{id:?}"));
+  threads.dedup();
+  assert!(threads.len() <= 2);
+}
+
+#[test]
+fn single_thread_executor_queues_a_woken_job_once() {
+  let executor = SingleThreadExecutor::new();
+  let (sender, receiver) = mpsc::channel();
+  let polls = Arc::new(AtomicUsize::new(0));
+  let counted = polls.clone();
+  executor.spawn(Box::pin(std::future::poll_fn(move |cx| {
+    if counted.fetch_add(1, Ordering::SeqCst) == 0 {
+      // woken twice while it's polled, it's polled again only once
+      cx.waker().wake_by_ref();
+      cx.waker().wake_by_ref();
+    } else {
+      sender.send(()).unwrap();
+    }
+    Poll::<()>::Pending
+  })));
+  assert_eq!(receiver.recv_timeout(TIMEOUT), Ok(()));
+  // the jobs run in order, so the extra poll would have happened before this one
+  let (synced, sync) = mpsc::channel();
+  executor.spawn(Box::pin(async move {
+    synced.send(()).unwrap();
+  }));
+  assert_eq!(sync.recv_timeout(TIMEOUT), Ok(()));
+  assert_eq!(polls.load(Ordering::SeqCst), 2);
+}
+
+struct CountingExecutor {
+  spawned: Arc<AtomicUsize>,
+  inner: SingleThreadExecutor,
+}
+
+impl Executor for CountingExecutor {
+  fn spawn(&self, future: BoxFuture) {
+    self.spawned.fetch_add(1, Ordering::SeqCst);
+    self.inner.spawn(future);
+  }
+
+  fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+    self.inner.spawn_blocking(task);
+  }
+}
+
+#[test]
+fn set_custom_executor() {
+  let spawned = Arc::new(AtomicUsize::new(0));
+  // `Error` releases its JavaScript reference on drop, which needs the Node-API symbols,
+  // so the results are never dropped in the test binary
+  let set = ManuallyDrop::new(set_executor(CountingExecutor {
+    spawned: spawned.clone(),
+    inner: SingleThreadExecutor::new(),
+  }));
+  assert!(set.is_ok());
+  let (sender, receiver) = mpsc::channel();
+  executor().spawn(Box::pin(async move {
+    sender.send(()).unwrap();
+  }));
+  assert_eq!(receiver.recv_timeout(TIMEOUT), Ok(()));
+  assert_eq!(spawned.load(Ordering::SeqCst), 1);
+
+  let set_again = ManuallyDrop::new(set_executor(SingleThreadExecutor::new()));
+  assert_eq!(
+    set_again.as_ref().err().map(|err| err.reason.as_str()),
+    Some("The executor of napi-rs is already set, it can only be set once")
+  );
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "tokio_rt", not(feature = "noop")))]
+
+use std::mem::ManuallyDrop;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use napi::bindgen_prelude::{block_on, shutdown_async_runtime, spawn, start_async_runtime};
+
+#[test]
+fn configure_before_first_use() {
+  // `Error` releases its JavaScript reference on drop, which needs the Node-API symbols,
+  // so the results are never dropped in the test binary
+  static CONFIGURED_AGAIN: AtomicBool = AtomicBool::new(false);
+  let configured = ManuallyDrop::new(napi::runtime::configure(|builder| {
+    builder.worker_threads(1).thread_name("napi-rs-configured");
+    // calling back into the module from the closure must not deadlock
+    napi::runtime::shutdown_timeout(Duration::from_secs(1));
+    let configured_again = ManuallyDrop::new(napi::runtime::configure(|_| {}));
+    CONFIGURED_AGAIN.store(configured_again.is_ok(), Ordering::Relaxed);
+  }));
+  assert!(configured.is_ok());
+  napi::runtime::shutdown_timeout(Duration::from_secs(1));
+
+  start_async_runtime();
+  let spawned = block_on(spawn(async {
+    assert_eq!(std::thread::current().name(), Some("napi-rs-configured"));
+  }));
+  assert!(spawned.is_ok());
+  assert!(!CONFIGURED_AGAIN.load(Ordering::Relaxed));
+
+  let configured_late = ManuallyDrop::new(napi::runtime::configure(|_| {}));
+  assert_eq!(
+    configured_late.as_ref().err().map(|err| err.reason.as_str()),
+    Some(
+      "The tokio runtime of napi-rs is already started, `configure` must be called before the first use"
+    )
+  );
+  shutdown_async_runtime();
+}
@@ -487,13 +487,39 @@ mod napi1 {
 #[cfg(feature = "napi2")]
 mod napi2 {
   use super::super::types::*;
-  use std::os::raw::c_int;
+  use std::os::raw::{c_char, c_int, c_void};
 
   generate!(
     extern "C" {
       fn napi_get_uv_event_loop(env: napi_env, loop_: *mut *mut uv_loop_s) -> napi_status;
 
       fn uv_run(loop_: *mut uv_loop_s, mode: uv_run_mode) -> c_int;
+
+      fn uv_strerror(err: c_int) -> *const c_char;
+      fn uv_handle_size(type_: uv_handle_type) -> usize;
+      fn uv_handle_get_data(handle: *const uv_handle_s) -> *mut c_void;
+      fn uv_handle_set_data(handle: *mut uv_handle_s, data: *mut c_void);
+      fn uv_close(handle: *mut uv_handle_s, close_cb: uv_close_cb);
+      fn uv_ref(handle: *mut uv_handle_s);
+      fn uv_unref(handle: *mut uv_handle_s);
+      fn uv_has_ref(handle: *const uv_handle_s) -> c_int;
+
+      fn uv_timer_init(loop_: *mut uv_loop_s, handle: *mut uv_timer_s) -> c_int;
+      fn uv_timer_start(
+        handle: *mut uv_timer_s,
+        cb: uv_timer_cb,
+        timeout: u64,
+        repeat: u64,
+      ) -> c_int;
+      fn uv_timer_stop(handle: *mut uv_timer_s) -> c_int;
+
+      fn uv_idle_init(loop_: *mut uv_loop_s, handle: *mut uv_idle_s) -> c_int;
+      fn uv_idle_start(handle: *mut uv_idle_s, cb: uv_idle_cb) -> c_int;
+      fn uv_idle_stop(handle: *mut uv_idle_s) -> c_int;
+
+      fn uv_check_init(loop_: *mut uv_loop_s, handle: *mut uv_check_s) -> c_int;
+      fn uv_check_start(handle: *mut uv_check_s, cb: uv_check_cb) -> c_int;
+      fn uv_check_stop(handle: *mut uv_check_s) -> c_int;
     }
   );
 }
@@ -694,6 +720,19 @@ mod napi8 {
       fn napi_object_freeze(env: napi_env, object: napi_value) -> napi_status;
 
       fn napi_object_seal(env: napi_env, object: napi_value) -> napi_status;
+
+      fn napi_type_tag_object(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const napi_type_tag,
+      ) -> napi_status;
+
+      fn napi_check_object_type_tag(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const napi_type_tag,
+        result: *mut bool,
+      ) -> napi_status;
     }
   );
 }
@@ -62,6 +62,38 @@ pub enum uv_run_mode {
   UV_RUN_ONCE = 1,
   UV_RUN_NOWAIT = 2,
 }
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct uv_handle_s {
+  _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct uv_timer_s {
+  _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct uv_idle_s {
+  _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct uv_check_s {
+  _unused: [u8; 0],
+}
+/// The handle types allocated by `uv_handle_size`, the others are omitted
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum uv_handle_type {
+  UV_CHECK = 2,
+  UV_IDLE = 6,
+  UV_TIMER = 13,
+}
+pub type uv_close_cb = Option<unsafe extern "C" fn(handle: *mut uv_handle_s)>;
+pub type uv_timer_cb = Option<unsafe extern "C" fn(handle: *mut uv_timer_s)>;
+pub type uv_idle_cb = Option<unsafe extern "C" fn(handle: *mut uv_idle_s)>;
+pub type uv_check_cb = Option<unsafe extern "C" fn(handle: *mut uv_check_s)>;
 pub type napi_deferred = *mut napi_deferred__;
 
 pub type napi_property_attributes = i32;
@@ -215,6 +247,14 @@ pub type napi_async_cleanup_hook_handle = *mut napi_async_cleanup_hook_handle__;
 pub type napi_async_cleanup_hook =
   Option<unsafe extern "C" fn(handle: napi_async_cleanup_hook_handle, data: *mut c_void)>;
 
+#[cfg(feature = "napi8")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct napi_type_tag {
+  pub lower: u64,
+  pub upper: u64,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct napi_callback_scope__ {
@@ -47,3 +47,22 @@ test.serial('should be able to execute future paralleled', async (t) => {
     t.deepEqual(readFileSync(filepath), fileContent)
   }
 })
+
+test.serial('should run futures on the configured tokio runtime', async (t) => {
+  if (napiVersion < 4) {
+    t.is(bindings.getTokioThreadName, undefined)
+    return
+  }
+  t.is(await bindings.getTokioThreadName(), 'compat-mode-tokio')
+})
+
+test.serial('should not configure the started tokio runtime', (t) => {
+  if (napiVersion < 4) {
+    t.is(bindings.configureStartedRuntime, undefined)
+    return
+  }
+  t.is(
+    bindings.configureStartedRuntime(),
+    'The tokio runtime of napi-rs is already started, `configure` must be called before the first use',
+  )
+})
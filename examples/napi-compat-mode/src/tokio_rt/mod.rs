@@ -1,11 +1,15 @@
 use napi::{JsObject, Result};
 
 mod read_file;
+mod runtime;
 
 use read_file::*;
+use runtime::*;
 
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("testExecuteTokioReadfile", test_execute_tokio_readfile)?;
   exports.create_named_method("testTokioError", error_from_tokio_future)?;
+  exports.create_named_method("getTokioThreadName", get_tokio_thread_name)?;
+  exports.create_named_method("configureStartedRuntime", configure_started_runtime)?;
   Ok(())
 }
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use napi::{CallContext, JsObject, JsString, JsValue, Result};
+
+// runs when the addon is loaded, before the tokio runtime is created by the first env
+#[module_init]
+fn configure_tokio_runtime() {
+  napi::runtime::configure(|builder| {
+    builder.worker_threads(2).thread_name("compat-mode-tokio");
+  })
+  .expect("Configure the tokio runtime failed");
+  napi::runtime::shutdown_timeout(Duration::from_secs(1));
+}
+
+#[js_function]
+pub fn get_tokio_thread_name(ctx: CallContext) -> Result<JsObject> {
+  ctx.env.execute_tokio_future(
+    async move { Ok(std::thread::current().name().unwrap_or_default().to_owned()) },
+    |&mut env, name| env.create_string(&name).map(|v| v.raw()),
+  )
+}
+
+#[js_function]
+pub fn configure_started_runtime(ctx: CallContext) -> Result<JsString> {
+  match napi::runtime::configure(|builder| {
+    builder.worker_threads(1);
+  }) {
+    Ok(()) => ctx.env.create_string(""),
+    Err(e) => ctx.env.create_string(&e.reason),
+  }
+}
@@ -0,0 +1,11 @@
+const { tsfnKeepaliveWhileActive } = require('../index.cjs')
+
+const received = []
+
+tsfnKeepaliveWhileActive((value) => {
+  received.push(value)
+}, 3)
+
+process.on('exit', () => {
+  console.log(JSON.stringify(received))
+})
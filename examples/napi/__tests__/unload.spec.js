@@ -1,5 +1,6 @@
 // use the commonjs syntax to prevent compiler from transpiling the module syntax
 
+import { spawnSync } from 'node:child_process'
 import { createRequire } from 'node:module'
 import * as path from 'node:path'
 
@@ -55,3 +56,29 @@ test('load module multi times', (t) => {
   )
   t.is(add2(1, 2), 3)
 })
+
+test('strict register fails to load the module with all the problems', (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const { status, stderr } = spawnSync(
+    process.execPath,
+    ['-e', `require(${JSON.stringify(path.join(__dirname, `../${binaryName}`))})`],
+    {
+      encoding: 'utf8',
+      env: { ...process.env, NAPI_RS_FAIL_STRICT_REGISTER: '1' },
+    },
+  )
+  t.not(status, 0)
+  t.true(
+    stderr.includes(
+      [
+        'Failed to register the native module exports:',
+        '  - `STRICT_REGISTER_PROBE` is missing from the module exports',
+        '  - `STRICT_REGISTER_PROBE`: StrictRegisterProbe is asked to fail',
+      ].join('\n'),
+    ),
+    stderr,
+  )
+})
@@ -1,10 +1,11 @@
+import { AsyncLocalStorage } from 'node:async_hooks'
 import { Buffer } from 'node:buffer'
 import { exec } from 'node:child_process'
-import { join } from 'node:path'
+import { dirname, join, sep } from 'node:path'
 import { fileURLToPath } from 'node:url'
 import { createReadStream } from 'node:fs'
 import { readFile as nodeReadFile } from 'node:fs/promises'
-import { Readable } from 'node:stream'
+import { Readable, Writable } from 'node:stream'
 
 import { Subject, take } from 'rxjs'
 import Sinon, { spy } from 'sinon'
@@ -95,6 +96,7 @@ import {
   setSymbolInObj,
   createSymbol,
   createSymbolFor,
+  createObjectWithPropertyKeys,
   threadsafeFunctionFatalMode,
   createExternal,
   getExternal,
@@ -187,8 +189,11 @@ import {
   returnFromSharedCrate,
   chronoNativeDateTime,
   chronoNativeDateTimeReturn,
+  timeOffsetDateTimeToMillis,
+  timeOffsetDateTimeAdd1Minute,
   throwAsyncError,
   getModuleFileName,
+  getModuleDir,
   throwSyntaxError,
   type AliasedStruct,
   returnObjectOnlyToJs,
@@ -202,6 +207,7 @@ import {
   uInit8ArrayFromString,
   callThenOnPromise,
   callCatchOnPromise,
+  callThenOrCatchOnPromise,
   callFinallyOnPromise,
   StructuredKind,
   validateStructuredEnum,
@@ -209,6 +215,8 @@ import {
   getBufferSlice,
   createExternalBufferSlice,
   createBufferSliceFromCopiedData,
+  buffersFromBytes,
+  arrayBufferFromBytes,
   Reader,
   withinAsyncRuntimeIfAvailable,
   errorMessageContainsNullByte,
@@ -238,6 +246,188 @@ import {
   shorterScope,
   shorterEscapableScope,
   tsfnThrowFromJsCallbackContainsTsfn,
+  wrapWithHooks,
+  getRegisteredExportKind,
+  getRegisteredExportSignature,
+  setModuleExport,
+  removeModuleExport,
+  sumU32Multiversion,
+  blockEventLoop,
+  startLoopWatchdog,
+  startLoopWatchdogOnce,
+  stopLoopWatchdog,
+  ObjectMemo,
+  concatBuffers,
+  buildBuffer,
+  encodeVarints,
+  sumPinnedBytes,
+  resolvePinnedBytesAfter,
+  singleFlightLookup,
+  singleFlightLookupCount,
+  createCachedKeysObjects,
+  createDataView,
+  writeDataView,
+  getReferenceReleaseStats,
+  lookupWithStructuredError,
+  throwStructuredError,
+  type LookupFailure,
+  sumSharedArrayBuffer,
+  createSharedMemory,
+  incrementSharedCounter,
+  fillSharedMemory,
+  countUntilInterrupted,
+  createResizableArrayBuffer,
+  resizeArrayBuffer,
+  shrinkAndReadOldHandle,
+  startSliceBorrowReports,
+  stopSliceBorrowReports,
+  detachWhileBorrowed,
+  callWithBorrowedSlice,
+  summarizeTypedArray,
+  strictEquals,
+  sameValue,
+  countDistinctStrings,
+  countDistinctNumbers,
+  countDistinctBigints,
+  kvSet,
+  kvGet,
+  kvThreadName,
+  uint16Subarray,
+  incrementArraybuffer,
+  writeAroundCallback,
+  FrameProducer,
+  pooledBuffer,
+  pooledBuffersAvailable,
+  alignedFloat32Array,
+  float32ArrayAlignment,
+  sumV1,
+  sumV2,
+  writeUint16At,
+  writeBufferAt,
+  debugBuffer,
+  displayFloat32Array,
+  hexdumpUint8Array,
+  formatCurrency,
+  sortByLocale,
+  echoRpcMessages,
+  rpcMessageFields,
+  sumChunks,
+  reverseTransferred,
+  transposeMatrix,
+  scaleMatrixRows,
+  bufferIntoUint8Array,
+  rustBufferIntoUint8ArrayTwice,
+  uint8ArrayIntoBuffer,
+  convertRustBuffers,
+  squareInPlace,
+  getFrozenUint8Array,
+  reportedExternalMemory,
+  MemoryPolicy,
+  createSequenceArraybuffer,
+  copyIntoArraybuffer,
+  streamRepeated,
+  linspace,
+  squaresBelow,
+  evenNumbers,
+  copyFloat64Array,
+  pooledFill,
+  envBufferPoolStats,
+  reclaimRustBuffer,
+  jsBufferIntoVec,
+  splitBuffer,
+  sliceBytesBuffer,
+  sliceRustBuffer,
+  readNodeStream,
+  uppercaseNodeStream,
+  parseRecords,
+  patchBuffer,
+  compareBuffers,
+  indexOfInBuffer,
+  encodeBuffer,
+  decodeToBuffer,
+  createTextBlob,
+  describeBlob,
+  sliceBlob,
+  readBlobReversed,
+  readFileRange,
+  readFileFrom,
+  sharedTableBuffer,
+  copiedTableBuffer,
+  sharedTableOwners,
+  frozenBytesMutBuffer,
+  byteChunksSent,
+  forEachByteChunk,
+  produceByteChunks,
+  produceFailingByteChunks,
+  bufferDiagnosticsEnabled,
+  bufferDiagnosticsReport,
+  liveExternalBufferSizes,
+  bufferEventCounts,
+  countBufferEvents,
+  sharedBytesAfterWrite,
+  sharedBytesRoundTrip,
+  sumInThreads,
+  repeatExternalLatin1,
+  repeatExternalUtf16,
+  latin1FromString,
+  latin1HeaderLine,
+  statusText,
+  joinPath,
+  pathFileName,
+  movePoint,
+  sumPointCoordinates,
+  countWords,
+  normalizeNewlines,
+  concatStrings,
+  numberedLines,
+  nonAsciiCharOffsets,
+  sliceChars,
+  stringCodePoints,
+  countUnpairedSurrogates,
+  limitUtf16Len,
+  runScriptWithFilename,
+  runScriptAsU32,
+  recordInstanceCall,
+  replaceBorrowedInstance,
+  getLastInstanceCaller,
+  externalMemoryGuardChanges,
+  countUvTimerTicks,
+  uvIdleRefStates,
+  getRuntimeInfo,
+  createTaggedDisposable,
+  getToStringTag,
+  getAsyncIteratorSymbol,
+  parseAndCloneJson,
+  queueNativeMicrotask,
+  getProcessPlatform,
+  countCharsInScopes,
+  joinInEscapableScope,
+  createTaggedExternal,
+  getTaggedExternal,
+  callFromUvTimer,
+  scheduleNativeTasks,
+  tsfnBoundedProducer,
+  tsfnBatchedProducer,
+  tsfnCloseWithDrain,
+  tsfnReportErrorsToHandler,
+  asyncTaskFailingCleanup,
+  asyncTaskFailingCleanupInEnv,
+  setBackgroundErrorHandler,
+  tsfnQueueStats,
+  channelNumbersToJs,
+  channelBatchesToJs,
+  acceptThreadsafeFunctionTuple,
+  Ticker,
+  tsfnHandleInTasks,
+  tsfnBlockingCallOnJsThread,
+  tsfnUrgentCall,
+  sleepWithSignal,
+  sleepWithOptionalSignal,
+  droppedSleepCount,
+  countUntilAborted,
+  callThenTypedOnPromise,
+  callCatchTypedOnPromise,
+  sumAllPromises,
 } from '../index.cjs'
 // import other stuff in `#[napi(module_exports)]`
 import nativeAddon from '../index.cjs'
@@ -268,6 +458,11 @@ test('number', (t) => {
   )
 })
 
+test('multiversion fn', (t) => {
+  t.is(sumU32Multiversion([1, 2, 3, 4]), 10)
+  t.is(sumU32Multiversion([]), 0)
+})
+
 test('string', (t) => {
   t.true(contains('hello', 'ell'))
   t.false(contains('John', 'jn'))
@@ -285,6 +480,75 @@ test('string', (t) => {
   t.is(returnCString(), 'Hello from C string!')
 })
 
+
+test('convert latin1 strings without UTF-8 transcoding', (t) => {
+  t.is(statusText(404), 'Not Found')
+  t.is(statusText(599), 'Unknown')
+  t.is(latin1HeaderLine('Content-Type', 'text/plain; charset=é'), 'Content-Type: text/plain; charset=é')
+  t.deepEqual(latin1FromString('café'), ['café', 'café'])
+  // the characters out of latin1 keep their low byte, like `Buffer.from('€', 'latin1')`
+  t.deepEqual(latin1FromString('€'), ['¬', '¬'])
+})
+
+
+test('convert paths without losing the unpaired surrogates', (t) => {
+  t.is(joinPath('dir', 'café'), join('dir', 'café'))
+  t.is(pathFileName(join('dir', '😀.txt')), '😀.txt')
+  t.is(pathFileName(join('dir', '\uD800.txt')), '\uD800.txt')
+  t.is(pathFileName(sep), null)
+})
+
+
+test('borrow JavaScript strings as &str and Cow<str>', (t) => {
+  t.is(countWords(' hello  wörld 😀 '), 3)
+  t.is(countWords(''), 0)
+  t.is(countWords(`${'a'.repeat(100_000)} b`), 2)
+  t.is(normalizeNewlines('a\r\nb\r\n'), 'a\nb\n')
+  t.is(normalizeNewlines('abc'), 'abc')
+  // @ts-expect-error
+  t.throws(() => countWords(1), {
+    message: 'Failed to convert JavaScript value `Number 1 ` into rust type `JsStringRef`',
+  })
+})
+
+
+test('build large strings with JsStringBuilder', (t) => {
+  t.is(numberedLines('row ', 3), 'row 0\nrow 1\nrow 2\n')
+  const lines = numberedLines('x', 100_000).split('\n')
+  t.is(lines.length, 100_001)
+  t.is(lines[99_999], 'x99999')
+  t.is(concatStrings('a', ['b\uD800', '😀', '']), 'ab\uD800😀')
+  t.is(concatStrings('', []), '')
+})
+
+
+test('slice and iterate JsString by characters', (t) => {
+  const text = 'a😀b\uD800cé'
+  t.is(sliceChars(text, 1, 2), '😀')
+  t.is(sliceChars(text, 1), '😀b\uD800cé')
+  t.is(sliceChars(text, 3, 4), '\uD800')
+  t.is(sliceChars(text, 0, 100), text)
+  t.is(sliceChars(text, 10), '')
+  t.deepEqual(
+    stringCodePoints(text),
+    [...text].map((c) => c.codePointAt(0)),
+  )
+  t.deepEqual(nonAsciiCharOffsets(text), [1, 4, 6])
+})
+
+
+test('read UTF-16 strings lazily with Utf16Slice', (t) => {
+  t.is(limitUtf16Len('héllo', 10), 'héllo')
+  // the code units are kept as they are, even if a surrogate pair is split
+  t.is(limitUtf16Len('a😀b', 2), 'a\uD83D')
+  t.is(countUnpairedSurrogates('a\uD800b\uDC00😀'), 2)
+  // @ts-expect-error
+  t.throws(() => countUnpairedSurrogates(1), {
+    code: 'StringExpected',
+    message: 'Expect value to be String, but received Number',
+  })
+})
+
 test('array', (t) => {
   t.deepEqual(getNums(), [1, 1, 2, 3, 5, 8])
   t.deepEqual(getWords(), ['foo', 'bar'])
@@ -409,6 +673,19 @@ test('function call', async (t) => {
   )
 })
 
+test('wrap function with hooks', (t) => {
+  const wrapped = wrapWithHooks((a, b) => a + b)
+  t.is(wrapped(1, 2), 5)
+  t.is(wrapped(10, 5), 35)
+  t.throws(
+    () =>
+      wrapWithHooks(() => {
+        throw new Error('original throws')
+      })(1, 2),
+    { message: 'original throws' },
+  )
+})
+
 test('class', (t) => {
   const dog = new Animal(Kind.Dog, '旺财')
 
@@ -532,6 +809,14 @@ test('class in object field', (t) => {
   t.is(receiveObjectWithClassField(obj), obj.bird)
 })
 
+test('reject the instances of the other classes', (t) => {
+  const err = t.throws(() =>
+    // @ts-expect-error
+    receiveObjectWithClassField({ bird: new Animal(Kind.Dog, '旺财') }),
+  )
+  t.regex(err!.message, /is not an instance of `.*Bird` created by this addon/)
+})
+
 test('custom finalize class', (t) => {
   t.notThrows(() => new CustomFinalize(200, 200))
 })
@@ -542,6 +827,20 @@ test('should be able to create object reference and shared reference', (t) => {
   t.is(new JsRemote(repo).name(), 'origin')
 })
 
+test('reference send guard dropped on other thread', async (t) => {
+  const before = getReferenceReleaseStats()
+  const repo = new JsRepo('.')
+  repo.dropOnOtherThread()
+  t.is(getReferenceReleaseStats().queued, before.queued + 1)
+  t.is(repo.dirThroughSendGuard(), '.')
+  t.is(getReferenceReleaseStats().releasedInPlace, before.releasedInPlace + 1)
+  await new Promise((resolve) => setTimeout(resolve, 50))
+  t.is(
+    getReferenceReleaseStats().releasedFromQueue,
+    before.releasedFromQueue + 1,
+  )
+})
+
 test('should be able to into_reference', (t) => {
   const rules = ['body: { color: red }', 'div: { color: blue }']
   const sheet = new CssStyleSheet('test.css', rules)
@@ -609,11 +908,34 @@ test('promise', async (t) => {
   t.is(res, '1')
   const cat = await callCatchOnPromise(Promise.reject('cat'))
   t.is(cat, 'cat')
+  t.is(await callThenOrCatchOnPromise(Promise.resolve(1)), 'fulfilled: 1')
+  t.is(await callThenOrCatchOnPromise(Promise.reject('cat')), 'rejected: cat')
   const spy = Sinon.spy()
   await callFinallyOnPromise(Promise.resolve(1), spy)
   t.true(spy.calledOnce)
 })
 
+test('typed promise combinators', async (t) => {
+  t.is(await callThenTypedOnPromise(Promise.resolve(21)), 42)
+  t.is(
+    await callCatchTypedOnPromise(Promise.reject(new Error('cat'))),
+    'caught Error: cat',
+  )
+  t.is(
+    await sumAllPromises([
+      Promise.resolve(1),
+      Promise.resolve(2),
+      Promise.resolve(3),
+    ]),
+    6,
+  )
+  t.is(await sumAllPromises([]), 0)
+  await t.throwsAsync(
+    sumAllPromises([Promise.resolve(1), Promise.reject(new Error('dog'))]),
+    { message: 'dog' },
+  )
+})
+
 test('object', (t) => {
   t.deepEqual(listObjKeys({ name: 'John Doe', age: 20 }), ['name', 'age'])
   t.deepEqual(createObj(), { test: 1 })
@@ -683,6 +1005,63 @@ test('get str from object', (t) => {
   t.notThrows(() => getStrFromObject())
 })
 
+test('object with cached keys', (t) => {
+  const objects = createCachedKeysObjects(3)
+  t.deepEqual(objects, [
+    {
+      id: 0,
+      label: 'item 0',
+      note: 'even',
+      children: [{ id: 100, label: 'child', children: [] }],
+    },
+    {
+      id: 1,
+      label: 'item 1',
+      children: [{ id: 101, label: 'child', children: [] }],
+    },
+    {
+      id: 2,
+      label: 'item 2',
+      note: 'even',
+      children: [{ id: 102, label: 'child', children: [] }],
+    },
+  ])
+  t.deepEqual(Object.keys(objects[0]), ['id', 'label', 'note', 'children'])
+  t.false('note' in objects[1])
+  objects[0].id = 42
+  t.is(objects[0].id, 42)
+  t.deepEqual(createCachedKeysObjects(0), [])
+})
+
+test('compiled object schema', (t) => {
+  t.deepEqual(rpcMessageFields(), ['id', 'method', 'params', 'traceId'])
+  t.deepEqual(
+    echoRpcMessages([
+      { id: 1, method: 'ping', params: ['a'] },
+      { id: 2, method: 'get', params: [], traceId: 'trace' },
+    ]),
+    [
+      { id: 1, method: 'PING', params: ['a'] },
+      { id: 2, method: 'GET', params: [], traceId: 'trace' },
+    ],
+  )
+  t.throws(() => echoRpcMessages([{ id: 1, params: [] }]), {
+    message: 'Missing field `method`',
+  })
+  t.throws(() => echoRpcMessages([{ id: 'x', method: 'ping', params: [] }]), {
+    message: /on RpcMessage\.id$/,
+  })
+})
+
+
+test('access properties with interned keys', (t) => {
+  t.is(sumPointCoordinates([{ x: 1, y: 2 }, { x: 3 }, { y: 4 }]), 10)
+  const point = { x: 1 }
+  movePoint(point, 2)
+  movePoint(point, 2)
+  t.deepEqual(point, { x: 5 })
+})
+
 test('create object from Property', (t) => {
   const obj = createObjWithProperty()
   t.true(obj.value instanceof ArrayBuffer)
@@ -724,6 +1103,23 @@ test('create symbol', (t) => {
   t.is(createSymbol().toString(), 'Symbol(a symbol)')
 })
 
+
+test('well-known symbols and symbol-keyed properties', (t) => {
+  const obj = createTaggedDisposable('NapiThing') as {
+    disposed: boolean
+    [Symbol.dispose]?: () => void
+  }
+  t.is(Object.prototype.toString.call(obj), '[object NapiThing]')
+  t.is(getToStringTag(obj), 'NapiThing')
+  t.is(getToStringTag({}), null)
+  t.is(getAsyncIteratorSymbol(), Symbol.asyncIterator)
+  if (typeof Symbol.dispose === 'symbol') {
+    t.false(obj.disposed)
+    obj[Symbol.dispose]!()
+    t.true(obj.disposed)
+  }
+})
+
 test('Option', (t) => {
   t.is(mapOption(null), null)
   t.is(mapOption(3), 4)
@@ -762,11 +1158,45 @@ test('custom status code in Error', (t) => {
   })
 })
 
+test('structured error values', async (t) => {
+  t.is(await lookupWithStructuredError('found'), 42)
+  const busy = await t.throwsAsync(() => lookupWithStructuredError('busy'), {
+    any: true,
+  })
+  t.deepEqual(busy, { code: 'BUSY', key: 'busy', retryable: true })
+  try {
+    await lookupWithStructuredError('missing')
+    t.fail('should reject')
+  } catch (err) {
+    t.false(err instanceof Error)
+    t.is((err as LookupFailure).code, 'NOT_FOUND')
+  }
+  const thrown = t.throws(() => throwStructuredError('sync'), { any: true })
+  t.deepEqual(thrown, { code: 'NOT_FOUND', key: 'sync', retryable: false })
+})
+
 test('function ts type override', (t) => {
   // @ts-expect-error
   t.deepEqual(tsRename({ foo: 1, bar: 2, baz: 2 }), ['foo', 'bar', 'baz'])
 })
 
+
+test('deprecated function emits warning once', async (t) => {
+  const warnings: string[] = []
+  const onWarning = (warning: Error) => {
+    if (warning.name === 'DeprecationWarning') {
+      warnings.push(warning.message)
+    }
+  }
+  process.on('warning', onWarning)
+  t.is(sumV1(1, 2), 3)
+  t.is(sumV1(3, 4), 7)
+  t.is(sumV2(1, 2), 3)
+  await new Promise((resolve) => setTimeout(resolve, 10))
+  process.off('warning', onWarning)
+  t.deepEqual(warnings, ['sumV1 is deprecated: use `sumV2` instead'])
+})
+
 test('function individual ts arg type override', (t) => {
   t.is(
     overrideIndividualArgOnFunction('someStr', () => 'anotherStr', 42),
@@ -894,6 +1324,27 @@ test('get bigint json value', (t) => {
   })
 })
 
+test('value equality', (t) => {
+  t.true(strictEquals('a', 'a'))
+  t.false(strictEquals({}, {}))
+  t.false(strictEquals(NaN, NaN))
+  t.true(strictEquals(0, -0))
+  t.true(sameValue(NaN, NaN))
+  t.false(sameValue(0, -0))
+  t.true(sameValue(1, 1))
+  t.false(sameValue(1, '1'))
+  t.is(countDistinctStrings(['a', 'b', 'a', '😀', '😀']), 3)
+  t.is(countDistinctNumbers([1, 1, NaN, NaN, 0, -0, 2.5]), 4)
+})
+
+
+test('create external strings from Rust memory', (t) => {
+  const text = repeatExternalUtf16('héllo 👋', 1000)
+  t.is(text, 'héllo 👋'.repeat(1000))
+  t.is(repeatExternalLatin1(0xe9, 5), 'ééééé')
+  t.is(repeatExternalLatin1(0x41, 0), '')
+})
+
 test('buffer', (t) => {
   let buf = getBuffer()
   t.is(buf.toString('utf-8'), 'Hello world')
@@ -902,6 +1353,12 @@ test('buffer', (t) => {
   t.is(getBufferSlice().toString('utf-8'), 'Hello world')
   t.is(createExternalBufferSlice().toString('utf-8'), 'Hello world')
   t.is(createBufferSliceFromCopiedData().toString('utf-8'), 'Hello world')
+  t.deepEqual(
+    buffersFromBytes().map((b) => b.toString('utf-8')),
+    ['Hello', 'world'],
+  )
+  t.is(Buffer.from(arrayBufferFromBytes()).toString('utf-8'), 'Hello world')
+  t.is(Buffer.from(arrayBufferFromBytes()).toString('utf-8'), 'Hello world')
 
   const a = getEmptyBuffer()
   const b = getEmptyBuffer()
@@ -911,6 +1368,277 @@ test('buffer', (t) => {
   t.true(Array.isArray(asyncBufferToArray(Buffer.from([1, 2, 3]).buffer)))
 })
 
+
+test('buffer pool', (t) => {
+  const buffers = Array.from({ length: 8 }, (_, i) => pooledBuffer(`pooled ${i}`))
+  t.deepEqual(
+    buffers.map((b) => b.toString('utf8')),
+    Array.from({ length: 8 }, (_, i) => `pooled ${i}`),
+  )
+  t.is(
+    pooledBuffer('longer than the pooled block size').toString('utf8'),
+    'longer than the pooled block size',
+  )
+  t.is(pooledBuffer('').length, 0)
+  t.true(pooledBuffersAvailable() <= 64)
+})
+
+test('buffer pool size classes and stats', (t) => {
+  const { sizeClasses, hits, misses, oversized } = envBufferPoolStats()
+  t.deepEqual(sizeClasses, [16, 64, 256])
+
+  const buffers = [pooledFill(7, 10), pooledFill(1, 40), pooledFill(2, 300)]
+  t.deepEqual(
+    buffers.map((b) => [b.length, b[0], b[b.length - 1]]),
+    [
+      [10, 7, 7],
+      [40, 1, 1],
+      [300, 2, 2],
+    ],
+  )
+  t.is(pooledFill(3, 0).length, 0)
+
+  const stats = envBufferPoolStats()
+  t.is(stats.hits + stats.misses, hits + misses + 2)
+  t.is(stats.oversized, oversized + 1)
+  t.true(stats.available <= 24)
+})
+
+test('concat and build buffer', (t) => {
+  t.deepEqual(
+    concatBuffers([Buffer.from('Hello'), Buffer.from(' '), Buffer.from('world')]),
+    Buffer.from('Hello world'),
+  )
+  t.is(concatBuffers([]).length, 0)
+  t.is(buildBuffer(['a', 'bc']).toString('utf-8'), 'a\nbc\n')
+  t.is(buildBuffer([]).length, 0)
+  t.deepEqual(encodeVarints([1, 300, 0]), new Uint8Array([1, 0xac, 0x02, 0]))
+  t.is(encodeVarints([]).length, 0)
+})
+
+test('stream output in pooled buffers', (t) => {
+  const chunks: Buffer[] = []
+  streamRepeated('abcde', 7, (chunk) => {
+    chunks.push(Buffer.from(chunk))
+  })
+  t.deepEqual(
+    chunks.map((chunk) => chunk.length),
+    [16, 16, 3],
+  )
+  t.is(Buffer.concat(chunks).toString('utf8'), 'abcde'.repeat(7))
+
+  let called = false
+  streamRepeated('', 3, () => {
+    called = true
+  })
+  t.false(called)
+
+  t.throws(
+    () =>
+      streamRepeated('ab', 20, () => {
+        throw new Error('stop')
+      }),
+    { message: /stop/ },
+  )
+})
+
+test('data view', (t) => {
+  const arrayBuffer = new ArrayBuffer(32)
+  const view = createDataView(arrayBuffer, 4, 22)
+  t.true(view instanceof DataView)
+  t.is(view.buffer, arrayBuffer)
+  t.is(view.byteOffset, 4)
+  t.is(view.byteLength, 22)
+  t.is(writeDataView(view), 17.5)
+  t.is(view.getUint16(0), 0x1234)
+  t.is(view.getInt32(2, true), -2)
+  t.is(view.getFloat64(6, true), 1.5)
+  t.is(view.getBigUint64(14), 2n ** 64n - 1n)
+  t.throws(() => writeDataView(new DataView(arrayBuffer, 0, 8)), {
+    message: 'Offset 6 with 8 bytes is outside the bounds of the DataView of 8 bytes',
+  })
+  t.throws(() => createDataView(arrayBuffer, 30, 4))
+  // @ts-expect-error
+  t.throws(() => writeDataView(new Uint8Array(4)), {
+    message: 'Expected a DataView value',
+  })
+})
+
+test('resizable array buffer', (t) => {
+  const buf = createResizableArrayBuffer(4, 16)
+  t.is(buf.byteLength, 4)
+  t.is((buf as any).maxByteLength, 16)
+  t.deepEqual(resizeArrayBuffer(buf, 12), {
+    byteLength: 12,
+    maxByteLength: 16,
+  })
+  t.is(buf.byteLength, 12)
+  t.is(resizeArrayBuffer(buf, 2).byteLength, 2)
+  t.throws(() => resizeArrayBuffer(buf, 17), {
+    message: 'ArrayBuffer can't be resized to 17 bytes, the max byte length is 16',
+  })
+  t.throws(() => resizeArrayBuffer(new ArrayBuffer(4), 2), {
+    message: 'ArrayBuffer is not resizable',
+  })
+  t.throws(() => createResizableArrayBuffer(8, 4))
+})
+
+test('resizable array buffer handles see the shrunk length', (t) => {
+  const buf = createResizableArrayBuffer(8, 16)
+  new Uint8Array(buf).set([1, 2, 3, 4, 5, 6, 7, 8])
+  t.deepEqual(shrinkAndReadOldHandle(buf, buf, 3), [1, 2, 3])
+  t.is(buf.byteLength, 3)
+  t.deepEqual(shrinkAndReadOldHandle(buf, buf, 0), [])
+})
+
+test('shared array buffer', async (t) => {
+  const sab = new SharedArrayBuffer(4)
+  new Uint8Array(sab).set([1, 2, 3, 4])
+  t.is(sumSharedArrayBuffer(sab), 10)
+  t.throws(() => sumSharedArrayBuffer(new ArrayBuffer(4) as any), {
+    message: 'Expected a SharedArrayBuffer value',
+  })
+  const memory = createSharedMemory(8)
+  t.true(memory instanceof SharedArrayBuffer)
+  incrementSharedCounter(memory, 4)
+  t.is(Atomics.load(new Int32Array(memory), 0), 4)
+  t.throws(() => incrementSharedCounter(new SharedArrayBuffer(3), 1))
+  await fillSharedMemory(memory, 7)
+  t.deepEqual(Array.from(new Uint8Array(memory)), [7, 7, 7, 7, 7, 7, 7, 7])
+})
+
+test('any typed array', (t) => {
+  t.deepEqual(summarizeTypedArray(new Int8Array([-1, 2, 3])), {
+    kind: 'Int8',
+    length: 3,
+    byteLength: 3,
+    sum: 4,
+  })
+  t.deepEqual(summarizeTypedArray(new Float64Array([0.5, 1.5])), {
+    kind: 'Float64',
+    length: 2,
+    byteLength: 16,
+    sum: 2,
+  })
+  t.deepEqual(summarizeTypedArray(new BigUint64Array([1n, 2n]).subarray(1)), {
+    kind: 'BigUint64',
+    length: 1,
+    byteLength: 8,
+    sum: 2,
+  })
+  t.is(
+    summarizeTypedArray(new Uint8ClampedArray([255, 255])).kind,
+    'Uint8Clamped',
+  )
+  t.throws(() => summarizeTypedArray(new ArrayBuffer(4) as any), {
+    code: 'InvalidArg',
+  })
+})
+
+test('typed array subarray', (t) => {
+  const input = new Uint16Array([1, 2, 3, 4, 5, 6]).subarray(1)
+  const subarray = uint16Subarray(input, 1, 3)
+  t.deepEqual(Array.from(subarray), [6, 8])
+  t.is(subarray.buffer, input.buffer)
+  t.is(subarray.byteOffset, 4)
+  t.deepEqual(Array.from(input), [2, 6, 8, 5, 6])
+  t.throws(() => uint16Subarray(input, 3, 9), {
+    code: 'InvalidArg',
+    message: 'Subarray 3..9 is out of the bounds of the TypedArray of 5 elements',
+  })
+})
+
+
+test('copy into typed array at offset', (t) => {
+  const output = new Uint16Array(4)
+  writeUint16At(output, 1, [7, 8])
+  t.deepEqual(Array.from(output), [0, 7, 8, 0])
+  t.throws(() => writeUint16At(output, 3, [1, 2]), {
+    message: '2 elements at offset 3 are out of the bounds of the array of 4 elements',
+  })
+  t.deepEqual(Array.from(output), [0, 7, 8, 0])
+  const buffer = Buffer.alloc(8, '-')
+  writeBufferAt(buffer, 2, 'napi')
+  t.is(buffer.toString('utf8'), '--napi--')
+  t.throws(() => writeBufferAt(buffer, 6, 'napi'))
+})
+
+test('iterate typed array in chunks', (t) => {
+  const input = new Uint8Array([1, 2, 3, 4, 5, 6, 7])
+  for (const copy of [false, true]) {
+    const chunks: [number, number[], boolean][] = []
+    t.deepEqual(
+      sumChunks(input, 3, copy, (chunk, offset) => {
+        t.true(chunk instanceof Uint8Array)
+        chunks.push([offset, Array.from(chunk as Uint8Array), (chunk as Uint8Array).buffer === input.buffer])
+      }),
+      [6, 15, 7],
+    )
+    t.deepEqual(chunks, [
+      [0, [1, 2, 3], !copy],
+      [3, [4, 5, 6], !copy],
+      [6, [7], !copy],
+    ])
+  }
+  t.deepEqual(sumChunks(new Uint8Array(0), 3, false, () => {}), [])
+  t.throws(() => sumChunks(input, 0, false, () => {}), {
+    message: 'The chunk length must be greater than 0',
+  })
+})
+
+test('debug and hexdump binary values', (t) => {
+  t.is(
+    debugBuffer(Buffer.from('napi')),
+    'Buffer { length: 4, byte_length: 4, ownership: "JavaScript", data: 6e 61 70 69 }',
+  )
+  t.true(debugBuffer(Buffer.alloc(60)).endsWith('00 ... 10 more bytes }'))
+  t.is(
+    displayFloat32Array(new Float32Array([1, 2])),
+    '<Float32Array(2) 00 00 80 3f 00 00 00 40>',
+  )
+  const bytes = new Uint8Array(Buffer.from('napi-rs hexdump helper test!'))
+  t.is(
+    hexdumpUint8Array(bytes, 2, 100),
+    '00000002  70 69 2d 72 73 20 68 65  78 64 75 6d 70 20 68 65  |pi-rs hexdump he|\n' +
+      '00000012  6c 70 65 72 20 74 65 73  74 21                    |lper test!|\n',
+  )
+  t.is(hexdumpUint8Array(bytes, 40, 50), '')
+})
+
+test('Intl number format and collator', (t) => {
+  t.is(
+    formatCurrency(1234.5, 'USD', 'en-US'),
+    new Intl.NumberFormat('en-US', { style: 'currency', currency: 'USD' }).format(1234.5),
+  )
+  // the cached instance is reused
+  t.is(formatCurrency(42, 'USD', 'en-US'), '$42.00')
+  const values = ['z', 'ä', 'a', 'item10', 'item2']
+  t.deepEqual(
+    sortByLocale(values, 'de'),
+    [...values].sort(new Intl.Collator('de', { numeric: true }).compare),
+  )
+  t.throws(() => formatCurrency(1, 'USD', 'not a locale!'), { instanceOf: RangeError })
+})
+
+test('pinned bytes', async (t) => {
+  const buf = Buffer.from([1, 2, 3, 4, 5])
+  t.is(await sumPinnedBytes(buf.subarray(1, 4)), 9)
+  t.is(await sumPinnedBytes(new Uint16Array([256, 1])), 2)
+  t.is(await sumPinnedBytes(new DataView(new Uint8Array([7, 8]).buffer)), 15)
+  const bytes = new Uint8Array([1, 2, 3])
+  t.is(
+    resolvePinnedBytesAfter(bytes, () => {}),
+    3,
+  )
+  t.throws(
+    () =>
+      resolvePinnedBytesAfter(bytes, () => {
+        structuredClone(bytes.buffer, { transfer: [bytes.buffer] })
+      }),
+    { message: 'The ArrayBuffer of PinnedBytes has been detached' },
+  )
+})
+
 test('Return BufferSlice with lifetime', (t) => {
   const reader = new Reader()
   const reader2 = new Reader()
@@ -990,6 +1718,14 @@ test('mutate TypedArray', (t) => {
   t.deepEqual(input, new Float32Array([2.0, 4.0, 6.0, 8.0, 10.0]))
 })
 
+
+test('aligned typed array', (t) => {
+  const aligned = alignedFloat32Array([1, 2, 3, 4])
+  t.deepEqual(Array.from(aligned), [1, 2, 3, 4])
+  t.true(float32ArrayAlignment(aligned) >= 64)
+  t.is(alignedFloat32Array([]).length, 0)
+})
+
 test('deref uint8 array', (t) => {
   t.is(
     derefUint8Array(new Uint8Array([1, 2]), new Uint8ClampedArray([3, 4])),
@@ -1011,6 +1747,31 @@ test('async', async (t) => {
   await t.throwsAsync(() => readFileAsync('some_nonexist_path.file'))
 })
 
+test('single flight async calls', async (t) => {
+  const before = singleFlightLookupCount()
+  const results = await Promise.all([
+    singleFlightLookup('a'),
+    singleFlightLookup('a'),
+    singleFlightLookup('bb'),
+    singleFlightLookup('a'),
+  ])
+  t.is(singleFlightLookupCount() - before, 2)
+  t.is(results[0], results[1])
+  t.is(results[0], results[3])
+  t.not(results[0], results[2])
+  // the result is only shared until it resolves
+  await singleFlightLookup('a')
+  t.is(singleFlightLookupCount() - before, 3)
+  const errors = await Promise.allSettled([
+    singleFlightLookup(''),
+    singleFlightLookup(''),
+  ])
+  for (const error of errors) {
+    t.is(error.status, 'rejected')
+    t.is((error as PromiseRejectedResult).reason.message, 'Empty key')
+  }
+})
+
 test('within async runtime', (t) => {
   t.notThrows(() => withinAsyncRuntimeIfAvailable())
 })
@@ -1062,6 +1823,359 @@ test('create arraybuffer with native', (t) => {
   t.deepEqual(ret, buf)
 })
 
+
+test('mutate arraybuffer with detach checks', (t) => {
+  const buf = new Uint8Array([1, 2, 255]).buffer
+  incrementArraybuffer(buf)
+  t.deepEqual(Array.from(new Uint8Array(buf)), [2, 3, 0])
+
+  const kept = new ArrayBuffer(4)
+  t.is(
+    writeAroundCallback(kept, () => {}),
+    4,
+  )
+  t.deepEqual(Array.from(new Uint8Array(kept)), [1, 2, 0, 0])
+
+  const transferred = new ArrayBuffer(4)
+  const view = new Uint8Array(transferred)
+  let moved: ArrayBuffer | undefined
+  t.throws(
+    () =>
+      writeAroundCallback(transferred, () => {
+        moved = structuredClone(transferred, { transfer: [transferred] })
+      }),
+    { message: 'ArrayBuffer is detached' },
+  )
+  t.is(view.length, 0)
+  t.deepEqual(Array.from(new Uint8Array(moved!)), [1, 0, 0, 0])
+})
+
+test('transfer arraybuffer to Rust and back', (t) => {
+  const input = new Uint8Array([1, 2, 3])
+  const output = reverseTransferred(input.buffer)
+  t.deepEqual(Array.from(new Uint8Array(output)), [3, 2, 1])
+  t.is(input.buffer.byteLength, 0)
+  t.is(input.length, 0)
+  t.throws(() => reverseTransferred(input.buffer), {
+    message: 'ArrayBuffer is detached',
+  })
+})
+
+
+test('fill uninitialized arraybuffer', (t) => {
+  t.deepEqual(
+    new Uint8Array(createSequenceArraybuffer(5)),
+    new Uint8Array([0, 1, 2, 3, 4]),
+  )
+  t.is(createSequenceArraybuffer(0).byteLength, 0)
+  t.deepEqual(
+    new Uint8Array(copyIntoArraybuffer(new Uint8Array([7, 8, 9]))),
+    new Uint8Array([7, 8, 9]),
+  )
+})
+
+test('create typed arrays from iterators', (t) => {
+  t.deepEqual(linspace(0, 1, 5), new Float64Array([0, 0.25, 0.5, 0.75, 1]))
+  t.deepEqual(linspace(0, 1, 0), new Float64Array([]))
+  t.deepEqual(squaresBelow(50), new Uint32Array([0, 1, 4, 9, 16, 25, 36, 49]))
+  t.deepEqual(squaresBelow(0), new Uint32Array([]))
+  t.deepEqual(
+    evenNumbers(new Uint32Array([1, 2, 3, 4, 6])),
+    new Uint32Array([2, 4, 6]),
+  )
+  t.deepEqual(
+    copyFloat64Array(new Float64Array([1.5, -2, 3])),
+    new Float64Array([1.5, -2, 3]),
+  )
+})
+
+
+test('ndarray views and conversion', (t) => {
+  const input = new Float64Array([1, 2, 3, 4, 5, 6])
+  const transposed = transposeMatrix(input, 2, 3)
+  t.deepEqual(transposed.shape, [3, 2])
+  t.deepEqual(transposed.data, new Float64Array([1, 4, 2, 5, 3, 6]))
+  t.throws(() => transposeMatrix(input, 4, 2), {
+    message: /Can't view the Float64ArraySlice of 6 elements in the shape/,
+  })
+  scaleMatrixRows(input, 2, 3)
+  t.deepEqual(input, new Float64Array([1, 2, 3, 8, 10, 12]))
+})
+
+
+test('convert between Buffer and Uint8Array without copying', (t) => {
+  const buffer = Buffer.from('hello')
+  t.is(bufferIntoUint8Array(buffer), buffer)
+  const array = new Uint8Array([1, 2, 3])
+  t.is(uint8ArrayIntoBuffer(array), array)
+  const [fromBuffer, fromArray] = convertRustBuffers()
+  t.false(Buffer.isBuffer(fromBuffer))
+  t.deepEqual(fromBuffer, new Uint8Array(Buffer.from('buffer')))
+  t.true(Buffer.isBuffer(fromArray))
+  t.deepEqual(fromArray, Buffer.from('array'))
+  t.deepEqual(rustBufferIntoUint8ArrayTwice(), new Uint8Array(16).fill(2))
+})
+
+test('take back the Vec of a Buffer', (t) => {
+  t.true(reclaimRustBuffer())
+  t.is(jsBufferIntoVec(Buffer.from([1, 2, 3])), 6)
+})
+
+test('slice Buffer without copying', (t) => {
+  const input = Buffer.from('abcdefghij')
+  const parts = splitBuffer(input, 4)
+  t.deepEqual(
+    parts.map((part) => part.toString()),
+    ['abcd', 'efgh', 'ij'],
+  )
+  t.true(parts[0] instanceof Buffer)
+  parts[1][0] = 'X'.charCodeAt(0)
+  t.is(input.toString(), 'abcdXfghij')
+  t.deepEqual(splitBuffer(Buffer.alloc(0), 3), [])
+
+  t.is(sliceBytesBuffer(6, 11).toString(), 'world')
+  t.throws(() => sliceBytesBuffer(6, 12), {
+    message: 'Slice 6..12 is out of the bounds of the Buffer of 11 bytes',
+  })
+  t.throws(() => sliceRustBuffer(), {
+    message: "The Buffer is not backed by JavaScript, it can't be sliced without copying",
+  })
+})
+
+test('read, write and seek a Buffer with BufferCursor', (t) => {
+  const record = (text: string) => {
+    const data = Buffer.from(text)
+    const header = Buffer.alloc(2)
+    header.writeUInt16LE(data.length)
+    return Buffer.concat([header, data])
+  }
+  t.deepEqual(
+    parseRecords(Buffer.concat([record('foo'), record(''), record('hello world')])),
+    ['foo', '', 'hello world'],
+  )
+  t.throws(() => parseRecords(Buffer.concat([record('foo'), Buffer.from([5, 0, 1])])), {
+    message: 'failed to fill whole buffer',
+  })
+  const buffer = Buffer.from('hello world')
+  t.is(patchBuffer(buffer, -5, 'WORLDS'), 5)
+  t.is(buffer.toString(), 'hello WORLD')
+  t.is(patchBuffer(buffer, 3, 'x'), 0)
+  t.is(buffer.toString(), 'hello WORLD')
+  t.throws(() => patchBuffer(buffer, -20, 'x'), {
+    message: 'invalid seek to a negative or overflowing position',
+  })
+})
+
+test('compare and search Buffer like Node.js', (t) => {
+  const haystack = Buffer.from('abcabcabd')
+  for (const [value, offset] of [
+    ['abd', 0],
+    ['abc', 1],
+    ['c', -4],
+    ['', 4],
+    ['', 20],
+    ['abx', 0],
+    ['b', -100],
+  ] as const) {
+    t.is(indexOfInBuffer(haystack, value, offset), haystack.indexOf(value, offset))
+    t.is(
+      indexOfInBuffer(haystack, Buffer.from(value), offset),
+      haystack.indexOf(Buffer.from(value), offset),
+    )
+  }
+  const pairs = [
+    ['abc', 'abd'],
+    ['abc', 'ab'],
+    ['', ''],
+    ['b', 'abc'],
+  ]
+  for (const [source, target] of pairs) {
+    const [a, b] = [Buffer.from(source), Buffer.from(target)]
+    t.is(compareBuffers(a, b), Buffer.compare(a, b))
+    t.is(compareBuffers(a, b, 1, 2), a.compare(b, 1, undefined, 2))
+  }
+})
+
+test('encode and decode Buffer in the Node.js encodings', (t) => {
+  const data = Buffer.from([0, 1, 127, 128, 200, 250, 251, 255, 65, 66])
+  for (const encoding of ['utf8', 'base64', 'base64url', 'hex', 'latin1', 'ucs2'] as const) {
+    t.is(encodeBuffer(data, encoding), data.toString(encoding))
+    const text = data.toString(encoding)
+    t.deepEqual(decodeToBuffer(text, encoding), Buffer.from(text, encoding))
+  }
+  t.is(encodeBuffer(Buffer.from('hello'), 'binary'), 'hello')
+  t.deepEqual(decodeToBuffer('aGVs bG8', 'base64'), Buffer.from('hello'))
+  t.deepEqual(decodeToBuffer('abcdzz12', 'hex'), Buffer.from([0xab, 0xcd]))
+  t.throws(() => encodeBuffer(data, 'utf32'), { message: 'Unknown encoding: utf32' })
+})
+
+test('accept and return Blob', async (t) => {
+  const blob = createTextBlob('hello world')
+  t.true(blob instanceof Blob)
+  t.is(blob.type, 'text/plain')
+  t.is(await blob.text(), 'hello world')
+  t.is(describeBlob(blob), '11 bytes of text/plain')
+  const tail = sliceBlob(blob, -5)
+  t.is(await tail.text(), 'world')
+  t.is(describeBlob(tail), '5 bytes of ')
+  t.is(await sliceBlob(blob, 1, 3).text(), 'el')
+  t.is((await readBlobReversed(blob)).toString(), 'dlrow olleh')
+  t.is((await readBlobReversed(new Blob(['abc', new Uint8Array([100])]))).toString(), 'dcba')
+  t.is((await readBlobReversed(new Blob([]))).length, 0)
+})
+
+test('read file into Buffer', async (t) => {
+  const selfPath = fileURLToPath(import.meta.url)
+  const content = await nodeReadFile(selfPath)
+  t.deepEqual(readFileRange(selfPath), content)
+  t.deepEqual(readFileRange(selfPath, 10, 20), content.subarray(10, 20))
+  t.deepEqual(readFileRange(selfPath, 10, content.length + 100), content.subarray(10))
+  t.is(readFileRange(selfPath, content.length + 1).length, 0)
+  t.deepEqual(await readFileFrom(selfPath, 100), content.subarray(100))
+  t.throws(() => readFileRange(join(selfPath, 'not-exist')), {
+    message: /^Failed to read/,
+  })
+  await t.throwsAsync(() => readFileFrom(join(selfPath, 'not-exist'), 0), {
+    message: /^Failed to read/,
+  })
+})
+
+test('share Arc<Vec<u8>> and BytesMut memory with Buffer', (t) => {
+  const owners = sharedTableOwners()
+  const table = sharedTableBuffer(0)
+  const tail = sharedTableBuffer(7)
+  t.is(table.toString(), 'shared table')
+  t.is(tail.toString(), 'table')
+  t.is(sharedTableOwners(), owners + 2)
+  t.is(copiedTableBuffer().toString(), 'shared table')
+  t.is(sharedTableOwners(), owners + 2)
+  t.is(frozenBytesMutBuffer().toString(), 'frozen BytesMut')
+})
+
+
+test('list the live external buffers with buffer diagnostics', (t) => {
+  // enabled by default in the debug builds
+  t.true(bufferDiagnosticsEnabled())
+  const buffer = createExternalBufferSlice()
+  t.true(liveExternalBufferSizes().includes(buffer.length))
+  t.regex(bufferDiagnosticsReport(), /^\d+ live external buffers, \d+ bytes in total\n/)
+  t.regex(bufferDiagnosticsReport(), new RegExp(`0x[0-9a-f]+ \\(${buffer.length} bytes\\)`))
+})
+
+
+test('report the buffer events to the hook', (t) => {
+  countBufferEvents(true)
+  const before = bufferEventCounts()
+  const buffer = getBuffer()
+  t.is(jsBufferIntoVec(Buffer.from('abc')), 294)
+  const after = bufferEventCounts()
+  countBufferEvents(false)
+  t.true(after.created > before.created)
+  t.true(after.referenced > before.referenced)
+  t.true(after.unreferenced > before.unreferenced)
+  t.is(buffer.toString(), 'Hello world')
+})
+
+
+test('share Buffer bytes with threads through SharedBytes', (t) => {
+  t.is(sumInThreads(Buffer.alloc(1000, 3), 4), 3000)
+  t.is(sumInThreads(Buffer.alloc(0), 3), 0)
+  const [input, owned] = sharedBytesRoundTrip(Buffer.from('shared'))
+  t.is(input.toString(), 'shared')
+  t.is(owned.toString(), 'shared')
+  const kept = sharedBytesAfterWrite(Buffer.from('shared'), (buffer) => {
+    buffer.write('SHARED')
+  })
+  t.is(kept.toString(), 'shared')
+})
+
+
+test('sync memory after mutable access', (t) => {
+  const input = new Float64Array([1, 2, 3])
+  squareInPlace(input)
+  t.deepEqual(input, new Float64Array([1, 4, 9]))
+})
+
+
+test('frozen typed array', (t) => {
+  const frozen = getFrozenUint8Array()
+  t.deepEqual([...frozen], [1, 2, 3])
+  t.is(frozen.length, 3)
+  t.true(frozen instanceof Uint8Array)
+  t.throws(
+    () => {
+      // @ts-expect-error
+      frozen[0] = 9
+    },
+    { instanceOf: TypeError },
+  )
+  t.throws(() => frozen.fill(0), {
+    message: 'Cannot call fill on a frozen TypedArray',
+  })
+  t.throws(() => frozen.buffer, {
+    message: 'The ArrayBuffer of a frozen TypedArray is not accessible',
+  })
+  t.deepEqual(frozen.slice(1), new Uint8Array([2, 3]))
+  t.is(frozen[0], 1)
+})
+
+
+test('external memory policy', (t) => {
+  const size = 1024 * 1024
+  const exact = reportedExternalMemory(size, MemoryPolicy.Exact)
+  const disabled = reportedExternalMemory(size, MemoryPolicy.Disabled)
+  t.is(exact - disabled, size)
+  t.is(reportedExternalMemory(size, MemoryPolicy.Batched), disabled)
+})
+
+
+test('report external memory with ExternalMemoryGuard', async (t) => {
+  const size = 1024 * 1024
+  t.deepEqual(externalMemoryGuardChanges(size, false), [size, size * 2, 0])
+  t.deepEqual(externalMemoryGuardChanges(size, true), [size, size * 2])
+  // the release on the other thread is reported on the JavaScript thread
+  await new Promise((resolve) => setTimeout(resolve, 10))
+})
+
+
+test('run native work on the uv handles of the event loop', async (t) => {
+  const [ticks, checks] = await countUvTimerTicks(3)
+  t.is(ticks, 3)
+  t.true(checks > 0)
+  t.deepEqual(uvIdleRefStates(), [true, false])
+})
+
+test('call JavaScript from a uv callback in the async context of the call', async (t) => {
+  const storage = new AsyncLocalStorage<number>()
+  for (const useMakeCallback of [false, true]) {
+    const stores = await new Promise<(number | undefined)[]>((resolve) => {
+      storage.run(42, () => {
+        callFromUvTimer(() => {
+          const store = storage.getStore()
+          process.nextTick(() => resolve([store, storage.getStore()]))
+        }, useMakeCallback)
+      })
+    })
+    t.deepEqual(stores, [42, 42])
+  }
+})
+
+
+test('lend and recall a buffer', (t) => {
+  const producer = new FrameProducer(4)
+  t.false(producer.isLent)
+  const frame = new Uint8Array(producer.nextFrame())
+  t.true(producer.isLent)
+  t.deepEqual(Array.from(frame), [1, 1, 1, 1])
+  frame[0] = 10
+  t.is(producer.recall(), 13)
+  t.false(producer.isLent)
+  t.is(frame.length, 0)
+  const next = new Uint8Array(producer.nextFrame())
+  t.deepEqual(Array.from(next), [2, 2, 2, 2])
+  t.is(producer.recall(), 8)
+})
+
 test('Uint8Array from String', async (t) => {
   t.is(
     Buffer.from(await uInit8ArrayFromString()).toString('utf8'),
@@ -1148,6 +2262,26 @@ test('external', (t) => {
   t.is(e?.message, '<u32> on `External` is not the type of wrapped object')
 })
 
+test('type-tagged external', (t) => {
+  const ext = createTaggedExternal(42)
+  t.is(getTaggedExternal(ext), 42)
+  const e = t.throws(() => getTaggedExternal(createExternal(42)))
+  t.regex(e!.message, /is not tagged with/)
+  t.throws(() => getTaggedExternal({}))
+})
+
+test('weak cache keyed by object identity', (t) => {
+  const memo = new ObjectMemo()
+  const a = {}
+  const b = {}
+  t.is(memo.compute(a), 1)
+  t.is(memo.compute(a), 1)
+  t.is(memo.compute(b), 2)
+  t.true(memo.forget(a))
+  t.false(memo.forget(a))
+  t.is(memo.compute(a), 3)
+})
+
 test('optional external', (t) => {
   const FX = 42
   const extEmpty = createOptionalExternal()
@@ -1169,6 +2303,69 @@ test('should be able to run script', async (t) => {
   t.is(await runScript(`Promise.resolve(1)`), 1)
 })
 
+
+test('run script with the filename and line offset', (t) => {
+  t.is(runScriptAsU32(`40 + 2`), 42)
+  t.throws(() => runScriptAsU32(`'42'`))
+  const err = t.throws(() =>
+    runScriptWithFilename(
+      `1 +\n(() => { throw new Error('boom') })() // trailing comment`,
+      'helpers.js',
+      10,
+    ),
+  )
+  t.regex(err?.stack ?? '', /at helpers\.js:12:/)
+  t.throws(() => runScriptWithFilename(`1`, 'has space.js', 0), {
+    code: 'InvalidArg',
+  })
+})
+
+
+test('keep env-local state of multiple types', (t) => {
+  t.is(getLastInstanceCaller(), null)
+  t.is(recordInstanceCall('first'), 1)
+  t.is(recordInstanceCall('second'), 2)
+  t.is(getLastInstanceCaller(), 'second')
+  t.throws(() => replaceBorrowedInstance(), {
+    message: /is borrowed by `Env::with_instance\(\)`/,
+  })
+  t.is(recordInstanceCall('third'), 3)
+})
+
+
+test('detect the runtime versions and features', (t) => {
+  const info = getRuntimeInfo()
+  t.is(info.napiVersion, Number(process.versions.napi))
+  t.is(info.nodeVersion, process.versions.node)
+  t.is(info.supportsExternalStrings, info.napiVersion >= 10)
+})
+
+
+test('access the typed globals', async (t) => {
+  t.deepEqual(parseAndCloneJson('{"a":[1,2],"b":{"c":null}}'), {
+    a: [1, 2],
+    b: { c: null },
+  })
+  t.throws(() => parseAndCloneJson('{'), { instanceOf: SyntaxError })
+  t.is(getProcessPlatform(), process.platform)
+  const order: string[] = []
+  const done = new Promise((resolve) => setTimeout(resolve, 0))
+  queueNativeMicrotask(() => order.push('microtask'))
+  order.push('sync')
+  await done
+  t.deepEqual(order, ['sync', 'microtask'])
+})
+
+test('schedule the native microtasks and immediates', async (t) => {
+  const order: string[] = []
+  scheduleNativeTasks((name) => {
+    order.push(name)
+  })
+  order.push('sync')
+  await new Promise((resolve) => setImmediate(resolve))
+  t.deepEqual(order, ['sync', 'microtask', 'immediate'])
+})
+
 test('should be able to return object from shared crate', (t) => {
   t.deepEqual(returnFromSharedCrate(), {
     value: 42,
@@ -1220,6 +2417,60 @@ test('abort signal should be able to reuse with different tasks', async (t) => {
   })
 })
 
+AbortSignalTest('drop the future of an async fn once the signal is aborted', async (t) => {
+  const ctrl = new AbortController()
+  const promise = sleepWithSignal(1000, ctrl.signal)
+  setTimeout(() => ctrl.abort(), 20)
+  await t.throwsAsync(promise, { code: 'Cancelled', message: 'AbortError' })
+  t.is(droppedSleepCount(), 1)
+  t.is(await sleepWithSignal(10, new AbortController().signal), 10)
+
+  const aborted = new AbortController()
+  aborted.abort()
+  await t.throwsAsync(sleepWithSignal(10, aborted.signal), {
+    message: 'AbortError',
+  })
+})
+
+AbortSignalTest('cancel the async function with an optional AbortSignal', async (t) => {
+  t.is(await sleepWithOptionalSignal(10), 10)
+  t.is(await sleepWithOptionalSignal(10, new AbortController().signal), 10)
+  const ctrl = new AbortController()
+  const promise = sleepWithOptionalSignal(1000, ctrl.signal)
+  setTimeout(() => ctrl.abort(), 20)
+  await t.throwsAsync(promise, { code: 'Cancelled', message: 'AbortError' })
+})
+
+AbortSignalTest('stop a running async task by the token of the signal', async (t) => {
+  const ctrl = new AbortController()
+  const promise = countUntilAborted(100, ctrl.signal)
+  setTimeout(() => ctrl.abort(), 50)
+  await t.throwsAsync(promise, { code: 'Cancelled', message: 'AbortError' })
+  t.is(await countUntilAborted(3, new AbortController().signal), 3)
+})
+
+test('interrupt running async task', async (t) => {
+  const promise = countUntilInterrupted()
+  await new Promise((resolve) => setTimeout(resolve, 50))
+  promise.interrupt()
+  await t.throwsAsync(promise, { code: 'Cancelled', message: 'Interrupted' })
+  // interrupt a settled task is a no-op
+  t.notThrows(() => promise.interrupt())
+})
+
+test('run exports on a named thread', async (t) => {
+  const previous = await Promise.all(
+    Array.from({ length: 10 }, (_, i) => kvSet('key', `${i}`)),
+  )
+  t.deepEqual(previous, [null, '0', '1', '2', '3', '4', '5', '6', '7', '8'])
+  t.is(await kvGet('key'), '9')
+  t.is(await kvThreadName(), 'napi-kv')
+  await t.throwsAsync(() => kvGet('missing'), {
+    code: 'InvalidArg',
+    message: 'Key missing not found',
+  })
+})
+
 const BigIntTest = typeof BigInt !== 'undefined' ? test : test.skip
 
 BigIntTest('BigInt add', (t) => {
@@ -1255,6 +2506,10 @@ BigIntTest('from i128 i64', (t) => {
   t.is(bigintFromI128(), BigInt('-100'))
 })
 
+BigIntTest('BigInt hash by value', (t) => {
+  t.is(countDistinctBigints([0n, -0n, 1n, 1n, -1n, 2n ** 64n, 2n ** 64n]), 4)
+})
+
 Napi4Test('call ThreadsafeFunction', (t) => {
   if (process.env.WASI_TEST) {
     t.pass()
@@ -1330,6 +2585,24 @@ Napi4Test('throw error from thread safe function fatal mode', (t) => {
   })
 })
 
+Napi4Test('exit once the calls of a WhileActive ThreadsafeFunction are delivered', (t) => {
+  const p = exec('node ./tsfn-keepalive.cjs', {
+    cwd: __dirname,
+    timeout: 10000,
+  })
+  let stdout = ''
+  p.stdout?.on('data', (data) => {
+    stdout += data
+  })
+  return new Promise<void>((resolve) => {
+    p.on('exit', (code) => {
+      t.is(code, 0)
+      t.true(stdout.includes('[0,1,2]'))
+      resolve()
+    })
+  })
+})
+
 Napi4Test('await Promise in rust', async (t) => {
   const fx = 20
   const result = await asyncPlus100(
@@ -1369,6 +2642,162 @@ Napi4Test('async call ThreadsafeFunction', async (t) => {
   )
 })
 
+Napi4Test('wait for the free slots of a bounded ThreadsafeFunction', async (t) => {
+  const count = 100
+  const received: number[] = []
+  let resolveReceived: () => void
+  const allReceived = new Promise<void>((resolve) => {
+    resolveReceived = resolve
+  })
+  await tsfnBoundedProducer((value) => {
+    received.push(value)
+    if (received.length === count) {
+      resolveReceived()
+    }
+  }, count)
+  await allReceived
+  t.deepEqual(received, Array.from({ length: count }, (_, i) => i))
+})
+
+Napi4Test('deliver the values of a batched ThreadsafeFunction in arrays', async (t) => {
+  const count = 1000
+  const batches: number[][] = []
+  await new Promise<void>((resolve) => {
+    let received = 0
+    tsfnBatchedProducer(
+      (batch) => {
+        batches.push(batch)
+        received += batch.length
+        if (received === count) {
+          resolve()
+        }
+      },
+      count,
+      64,
+    )
+  })
+  t.true(batches.length < count)
+  t.true(batches.every((batch) => batch.length > 0 && batch.length <= 64))
+  t.deepEqual(batches.flat(), Array.from({ length: count }, (_, i) => i))
+})
+
+Napi4Test('drain the queued calls when closing a ThreadsafeFunction', async (t) => {
+  const received: number[] = []
+  const drained = await tsfnCloseWithDrain(
+    (value) => {
+      received.push(value)
+    },
+    5,
+    1000,
+  )
+  t.deepEqual(drained, { delivered: 5, dropped: 0, timedOut: false })
+  t.deepEqual(received, [0, 1, 2, 3, 4])
+
+  const slow: number[] = []
+  const report = await tsfnCloseWithDrain(
+    (value) => {
+      const end = Date.now() + 30
+      while (Date.now() < end) {}
+      slow.push(value)
+    },
+    5,
+    10,
+  )
+  t.true(report.timedOut)
+  t.is(report.delivered + report.dropped, 5)
+  t.is(slow.length, report.delivered)
+})
+
+Napi4Test('report the errors of a ThreadsafeFunction to the error handler', async (t) => {
+  const received: number[] = []
+  const errors = await new Promise<string[]>((resolve) => {
+    const errors: string[] = []
+    tsfnReportErrorsToHandler(
+      (value) => {
+        received.push(value)
+      },
+      (err) => {
+        errors.push(err.message)
+        if (errors.length === 3) {
+          resolve(errors)
+        }
+      },
+      6,
+    )
+  })
+  t.deepEqual(errors, ['odd value 1', 'odd value 3', 'odd value 5'])
+  t.deepEqual(received, [0, 2, 4])
+})
+
+Napi4Test('report the queue stats of a ThreadsafeFunction', async (t) => {
+  const received: number[] = []
+  const [highWaterMark, queued, delivered] = await tsfnQueueStats((value) => {
+    received.push(value)
+  }, 6)
+  t.deepEqual(highWaterMark, {
+    queueDepth: 3,
+    totalCalls: 3,
+    droppedCalls: 0,
+    averageLatencyMs: 0,
+  })
+  t.is(queued.queueDepth, 4)
+  t.is(queued.totalCalls, 6)
+  t.is(queued.droppedCalls, 2)
+  t.is(delivered.queueDepth, 0)
+  t.is(delivered.totalCalls, 6)
+  t.is(delivered.droppedCalls, 2)
+  t.true(delivered.averageLatencyMs > 0)
+  t.deepEqual(received, [0, 1, 2, 3])
+})
+
+Napi4Test('emit the events of a Rust EventEmitter to the JavaScript listeners', async (t) => {
+  const ticker = new Ticker()
+  const ticks: number[] = []
+  const first: number[] = []
+  const onTick = (value: number) => {
+    ticks.push(value)
+  }
+  ticker.on('tick', onTick)
+  ticker.once('tick', (value: number) => {
+    first.push(value)
+  })
+  t.is(ticker.listenerCount('tick'), 2)
+  const done = await new Promise<number>((resolve) => {
+    ticker.on('done', resolve)
+    ticker.start(5)
+  })
+  t.is(done, 5)
+  t.deepEqual(ticks, [0, 1, 2, 3, 4])
+  t.deepEqual(first, [0])
+  t.is(ticker.listenerCount('tick'), 1)
+  ticker.off('tick', onTick)
+  t.is(ticker.listenerCount('tick'), 0)
+
+  const received: string[] = []
+  ticker.on('message', (payload: string) => {
+    received.push(payload)
+  })
+  t.true(ticker.emitNow('message', 'hello'))
+  t.false(ticker.emitNow('unknown', 'hello'))
+  t.deepEqual(received, ['hello'])
+})
+
+test('report the error of an AsyncTask to the error handler', async (t) => {
+  const errors: Error[] = []
+  await asyncTaskFailingCleanup((err) => {
+    errors.push(err)
+  })
+  t.is(errors.length, 1)
+  t.is(errors[0].message, 'Failed to clean up the task')
+
+  const envErrors: string[] = []
+  setBackgroundErrorHandler((err) => {
+    envErrors.push(err.message)
+  })
+  await asyncTaskFailingCleanupInEnv()
+  t.deepEqual(envErrors, ['Failed to clean up the task'])
+})
+
 test('Throw from ThreadsafeFunction JavaScript callback', async (t) => {
   const errMsg = 'ThrowFromJavaScriptRawCallback'
   await t.throwsAsync(
@@ -1444,6 +2873,43 @@ Napi4Test('accept ThreadsafeFunction tuple args', async (t) => {
   })
 })
 
+Napi4Test('accept ThreadsafeFunction with a tuple of arguments', async (t) => {
+  await new Promise<void>((resolve) => {
+    acceptThreadsafeFunctionTuple((num, str) => {
+      t.is(num, 1)
+      t.is(str, 'NAPI-RS')
+      resolve()
+    })
+  })
+})
+
+Napi4Test('call ThreadsafeFunction from tokio tasks through its handle', async (t) => {
+  const sum = await tsfnHandleInTasks((err, value) => {
+    t.is(err, null)
+    return value * 2
+  }, 10)
+  t.is(sum, 90)
+})
+
+Napi4Test('fail the blocking call of a bounded ThreadsafeFunction on the JavaScript thread', (t) => {
+  const status = tsfnBlockingCallOnJsThread(() => {})
+  // it's only detected in debug builds
+  t.true(status === 'WouldDeadlock' || status === 'Ok')
+})
+
+Napi4Test('deliver the urgent calls of a ThreadsafeFunction ahead of the queue', async (t) => {
+  const received = await new Promise<string[]>((resolve) => {
+    const received: string[] = []
+    tsfnUrgentCall((value) => {
+      received.push(value)
+      if (received.length === 4) {
+        resolve(received)
+      }
+    }, 3)
+  })
+  t.deepEqual(received, ['shutdown', 'bulk 0', 'bulk 1', 'bulk 2'])
+})
+
 Napi4Test('threadsafe function return Promise and await in Rust', async (t) => {
   const value = await tsfnReturnPromise((err, value) => {
     if (err) {
@@ -1570,6 +3036,17 @@ Napi5Test('Date to chrono::NativeDateTime test', (t) => {
   t.is(chronoNativeDateTime(fixture), fixture.valueOf())
 })
 
+Napi5Test('Date to time::OffsetDateTime test', (t) => {
+  const fixture = new Date('2022-02-09T19:31:55.396Z')
+  t.is(timeOffsetDateTimeToMillis(fixture), fixture.getTime())
+  t.deepEqual(
+    timeOffsetDateTimeAdd1Minute(fixture),
+    new Date(fixture.getTime() + 60 * 1000),
+  )
+  const err = t.throws(() => timeOffsetDateTimeToMillis(new Date(NaN)))
+  t.is(err?.message, 'Found invalid date')
+})
+
 Napi5Test('Date from chrono::NativeDateTime test', (t) => {
   const fixture = chronoNativeDateTimeReturn()
   t.true(fixture instanceof Date)
@@ -1594,6 +3071,24 @@ Napi9Test('get module file name', (t) => {
   )
 })
 
+Napi9Test('get module dir', (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  t.is(getModuleDir(), dirname(fileURLToPath(getModuleFileName())))
+})
+
+const Napi10Test = Number(process.versions.napi) >= 10 ? test : test.skip
+
+Napi10Test('create property keys and external strings', (t) => {
+  t.deepEqual(createObjectWithPropertyKeys(), {
+    utf8: 'latin1 value',
+    utf16: 'value',
+    café: 'latin1 key',
+  })
+})
+
 test('throw syntax error', (t) => {
   const message = `Syntax Error: Unexpected token '}'`
   const code = 'InvalidCharacterError'
@@ -1667,6 +3162,88 @@ test('create readable stream from channel', async (t) => {
   t.is(Buffer.concat(chunksFromClass).toString('utf-8'), 'hello'.repeat(100))
 })
 
+test('adapt Node.js streams to AsyncRead and AsyncWrite', async (t) => {
+  const text = 'hello world '.repeat(100)
+  const read = await readNodeStream(Readable.from([text, Buffer.from('!')]))
+  t.is(read.toString('utf-8'), `${text}!`)
+  const chunks: Buffer[] = []
+  const output = new Writable({
+    highWaterMark: 2,
+    write(chunk, _encoding, callback) {
+      chunks.push(chunk)
+      setTimeout(callback, 1)
+    },
+  })
+  t.is(await uppercaseNodeStream(Readable.from(['abc', 'defgh', 'ij']), output), 10)
+  t.is(Buffer.concat(chunks).toString('utf-8'), 'ABCDEFGHIJ')
+  t.true(output.writableFinished)
+  const failing = new Readable({
+    read() {
+      this.destroy(new Error('boom'))
+    },
+  })
+  await t.throwsAsync(() => readNodeStream(failing), { message: /boom/ })
+})
+
+
+test('stream Rust chunks to JavaScript with ByteChannel backpressure', async (t) => {
+  const received: number[] = []
+  for await (const chunk of produceByteChunks(10, 8)) {
+    received.push(chunk[0])
+    await new Promise((resolve) => setTimeout(resolve, 5))
+    // 8 bytes of 4 bytes chunks are queued at most, and one more is sent after this one is received
+    t.true(byteChunksSent() - received.length <= 3)
+  }
+  t.deepEqual(received, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9])
+  await t.throwsAsync(
+    async () => {
+      for await (const _ of produceFailingByteChunks()) {
+      }
+    },
+    { message: 'producer failed' },
+  )
+  const called: number[] = []
+  await forEachByteChunk(5, (chunk) => {
+    called.push(chunk[0])
+  })
+  t.deepEqual(called, [0, 1, 2, 3, 4])
+  await t.throwsAsync(
+    () =>
+      forEachByteChunk(5, (chunk) => {
+        if (chunk[0] === 2) {
+          throw new Error('stop')
+        }
+      }),
+    { message: /stop/ },
+  )
+})
+
+test('deliver the items of a tokio channel to a JavaScript callback', async (t) => {
+  const received: number[] = []
+  await channelNumbersToJs(10, (value) => {
+    received.push(value)
+  })
+  t.deepEqual(received, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9])
+  const batches: number[][] = []
+  await channelBatchesToJs(10, (batch) => {
+    batches.push(batch)
+  })
+  t.deepEqual(batches, [
+    [0, 1, 2, 3],
+    [4, 5, 6, 7],
+    [8, 9],
+  ])
+  await t.throwsAsync(
+    () =>
+      channelNumbersToJs(100, (value) => {
+        if (value === 2) {
+          throw new Error('stop')
+        }
+      }),
+    { message: /stop/ },
+  )
+})
+
 test('spawnThreadInThread should be fine', async (t) => {
   await new Promise((resolve, reject) => {
     spawnThreadInThread((err, num) => {
@@ -1713,6 +3290,57 @@ test('module exports', (t) => {
   t.is(nativeAddon.NAPI_RS_SYMBOL, Symbol.for('NAPI_RS_SYMBOL'))
 })
 
+test('loop watchdog', (t) => {
+  startLoopWatchdog(10)
+  blockEventLoop(1)
+  blockEventLoop(30)
+  t.deepEqual(stopLoopWatchdog(), ['blockEventLoop'])
+})
+
+test('loop watchdog callback can clear itself', (t) => {
+  startLoopWatchdogOnce(10)
+  blockEventLoop(30)
+  t.deepEqual(stopLoopWatchdog(), ['blockEventLoop'])
+})
+
+test('registered exports and export manipulation', (t) => {
+  t.is(getRegisteredExportKind(null, 'add'), 'Function')
+  t.is(getRegisteredExportKind(null, 'Animal'), 'Class')
+  t.is(getRegisteredExportKind(null, 'Kind'), 'Object')
+  t.is(getRegisteredExportKind(null, 'DEFAULT_COST'), 'Value')
+  t.is(getRegisteredExportKind('xxh3', 'xxh3_64'), 'Function')
+  t.is(getRegisteredExportKind(null, 'notExist'), null)
+  t.is(getRegisteredExportSignature(null, 'add'), '(a: number, b: number): number')
+  t.is(getRegisteredExportSignature(null, 'Animal'), null)
+  const addon = nativeAddon as Record<string, any>
+  setModuleExport(null, 'replacedExport', 42)
+  t.is(addon.replacedExport, 42)
+  setModuleExport('newJsMod', 'answer', 42)
+  t.is(addon.newJsMod.answer, 42)
+  t.true(removeModuleExport(null, 'replacedExport'))
+  t.is(addon.replacedExport, undefined)
+  t.false(removeModuleExport(null, 'replacedExport'))
+})
+
+test('slice borrow tracking', (t) => {
+  const buf = new ArrayBuffer(8)
+  t.throws(() => detachWhileBorrowed(buf, new Uint8Array(buf, 2)), {
+    message: "ArrayBuffer can't be detached while 1 Rust slices borrow it",
+  })
+  t.is(buf.byteLength, 8)
+  t.is(detachWhileBorrowed(buf, new Uint8Array(4)), 4)
+  t.is(buf.byteLength, 0)
+  startSliceBorrowReports()
+  const transferred = new ArrayBuffer(8)
+  callWithBorrowedSlice(new Uint8Array(transferred), () => {
+    structuredClone(transferred, { transfer: [transferred] })
+  })
+  callWithBorrowedSlice(new Uint8Array(8), () => {})
+  t.deepEqual(stopSliceBorrowReports(), [
+    'callWithBorrowedSlice: Uint8ArraySlice',
+  ])
+})
+
 test('shorter scope', (t) => {
   const result = shorterScope(['hello', { foo: 'bar' }, 'world', true])
   t.deepEqual(result, [5, 1, 5, 0])
@@ -1733,3 +3361,8 @@ test('escapable handle scope', (t) => {
     shorterEscapableScope(makeIterFunction())
   })
 })
+
+test('bound the handle lifetimes with Env::with_handle_scope', (t) => {
+  t.is(countCharsInScopes(['ab', '😀c', '']), 4)
+  t.is(joinInEscapableScope(['a', 'b', 'c']), 'abc')
+})
@@ -4,7 +4,16 @@ import { Worker } from 'node:worker_threads'
 
 import test from 'ava'
 
-import { Animal, Kind, DEFAULT_COST, shutdownRuntime } from '../index.cjs'
+import {
+  Animal,
+  Kind,
+  DEFAULT_COST,
+  shutdownRuntime,
+  countEnvLocalCalls,
+  resetEnvLocalCalls,
+  takePhasedCleanupOrder,
+  takeAsyncCleanupEvents,
+} from '../index.cjs'
 
 const __dirname = join(fileURLToPath(import.meta.url), '..')
 
@@ -114,3 +123,64 @@ test('should be able to new Class in worker thread concurrently', async (t) => {
     }),
   )
 })
+
+test('EnvLocal values are initialized per worker thread', async (t) => {
+  resetEnvLocalCalls()
+  t.is(countEnvLocalCalls(), 1)
+  await Promise.all(
+    Array.from({ length: concurrency }).map(() => {
+      const w = new Worker(join(__dirname, 'worker.js'), {
+        env: process.env,
+      })
+      return new Promise<void>((resolve, reject) => {
+        w.postMessage({ type: 'env_local' })
+        w.on('message', (msg) => {
+          t.is(msg, 2)
+          resolve()
+        })
+        w.on('error', (err) => {
+          reject(err)
+        })
+      }).then(() => w.terminate())
+    }),
+  )
+  t.is(countEnvLocalCalls(), 2)
+  t.is(resetEnvLocalCalls(), 2)
+})
+
+test('run the phased cleanup hooks in order when the worker exits', async (t) => {
+  takePhasedCleanupOrder()
+  const w = new Worker(join(__dirname, 'worker.js'), {
+    env: process.env,
+  })
+  await new Promise<void>((resolve, reject) => {
+    w.postMessage({ type: 'phased_cleanup' })
+    w.on('message', () => {
+      w.terminate()
+    })
+    w.on('exit', () => resolve())
+    w.on('error', reject)
+  })
+  t.deepEqual(takePhasedCleanupOrder(), [
+    'before-tsfn',
+    'default-2',
+    'default-1',
+    'last',
+  ])
+})
+
+test('wait for the async cleanup hook to be completed when the worker exits', async (t) => {
+  takeAsyncCleanupEvents()
+  const w = new Worker(join(__dirname, 'worker.js'), {
+    env: process.env,
+  })
+  await new Promise<void>((resolve, reject) => {
+    w.postMessage({ type: 'async_cleanup' })
+    w.on('message', () => {
+      w.terminate()
+    })
+    w.on('exit', () => resolve())
+    w.on('error', reject)
+  })
+  t.deepEqual(takeAsyncCleanupEvents(), ['started', 'completed'])
+})
@@ -45,6 +45,18 @@ parentPort.on('message', ({ type }) => {
       }
       parentPort.postMessage(ellie.name)
       break
+    case 'phased_cleanup':
+      native.registerPhasedCleanupHooks()
+      parentPort.postMessage('done')
+      break
+    case 'async_cleanup':
+      native.registerAsyncCleanupHooks()
+      parentPort.postMessage('done')
+      break
+    case 'env_local':
+      native.countEnvLocalCalls()
+      parentPort.postMessage(native.countEnvLocalCalls())
+      break
     default:
       throw new TypeError(`Unknown message type: ${type}`)
   }
@@ -375,12 +375,94 @@ if (!nativeBinding) {
 }
 
 module.exports = nativeBinding
+module.exports.acceptThreadsafeFunctionTuple = nativeBinding.acceptThreadsafeFunctionTuple
+module.exports.alignedFloat32Array = nativeBinding.alignedFloat32Array
 module.exports.Animal = nativeBinding.Animal
 module.exports.AnimalWithDefaultConstructor = nativeBinding.AnimalWithDefaultConstructor
 module.exports.AnotherClassForEither = nativeBinding.AnotherClassForEither
 module.exports.AnotherCssStyleSheet = nativeBinding.AnotherCssStyleSheet
 module.exports.AnotherCSSStyleSheet = nativeBinding.AnotherCSSStyleSheet
+module.exports.arrayBufferFromBytes = nativeBinding.arrayBufferFromBytes
 module.exports.Asset = nativeBinding.Asset
+module.exports.asyncTaskFailingCleanup = nativeBinding.asyncTaskFailingCleanup
+module.exports.asyncTaskFailingCleanupInEnv = nativeBinding.asyncTaskFailingCleanupInEnv
+module.exports.blockEventLoop = nativeBinding.blockEventLoop
+module.exports.bufferDiagnosticsEnabled = nativeBinding.bufferDiagnosticsEnabled
+module.exports.bufferDiagnosticsReport = nativeBinding.bufferDiagnosticsReport
+module.exports.bufferEventCounts = nativeBinding.bufferEventCounts
+module.exports.bufferIntoUint8Array = nativeBinding.bufferIntoUint8Array
+module.exports.buffersFromBytes = nativeBinding.buffersFromBytes
+module.exports.buildBuffer = nativeBinding.buildBuffer
+module.exports.byteChunksSent = nativeBinding.byteChunksSent
+module.exports.callCatchTypedOnPromise = nativeBinding.callCatchTypedOnPromise
+module.exports.callFromUvTimer = nativeBinding.callFromUvTimer
+module.exports.callThenOrCatchOnPromise = nativeBinding.callThenOrCatchOnPromise
+module.exports.callThenTypedOnPromise = nativeBinding.callThenTypedOnPromise
+module.exports.callWithBorrowedSlice = nativeBinding.callWithBorrowedSlice
+module.exports.channelBatchesToJs = nativeBinding.channelBatchesToJs
+module.exports.channelNumbersToJs = nativeBinding.channelNumbersToJs
+module.exports.compareBuffers = nativeBinding.compareBuffers
+module.exports.concatBuffers = nativeBinding.concatBuffers
+module.exports.concatStrings = nativeBinding.concatStrings
+module.exports.convertRustBuffers = nativeBinding.convertRustBuffers
+module.exports.copiedTableBuffer = nativeBinding.copiedTableBuffer
+module.exports.copyFloat64Array = nativeBinding.copyFloat64Array
+module.exports.copyIntoArraybuffer = nativeBinding.copyIntoArraybuffer
+module.exports.countBufferEvents = nativeBinding.countBufferEvents
+module.exports.countCharsInScopes = nativeBinding.countCharsInScopes
+module.exports.countDistinctBigints = nativeBinding.countDistinctBigints
+module.exports.countDistinctNumbers = nativeBinding.countDistinctNumbers
+module.exports.countDistinctStrings = nativeBinding.countDistinctStrings
+module.exports.countEnvLocalCalls = nativeBinding.countEnvLocalCalls
+module.exports.countUnpairedSurrogates = nativeBinding.countUnpairedSurrogates
+module.exports.countUntilAborted = nativeBinding.countUntilAborted
+module.exports.countUntilInterrupted = nativeBinding.countUntilInterrupted
+module.exports.countUvTimerTicks = nativeBinding.countUvTimerTicks
+module.exports.countWords = nativeBinding.countWords
+module.exports.createCachedKeysObjects = nativeBinding.createCachedKeysObjects
+module.exports.createDataView = nativeBinding.createDataView
+module.exports.createObjectWithPropertyKeys = nativeBinding.createObjectWithPropertyKeys
+module.exports.createResizableArrayBuffer = nativeBinding.createResizableArrayBuffer
+module.exports.createSequenceArraybuffer = nativeBinding.createSequenceArraybuffer
+module.exports.createSharedMemory = nativeBinding.createSharedMemory
+module.exports.createTaggedDisposable = nativeBinding.createTaggedDisposable
+module.exports.createTaggedExternal = nativeBinding.createTaggedExternal
+module.exports.createTextBlob = nativeBinding.createTextBlob
+module.exports.debugBuffer = nativeBinding.debugBuffer
+module.exports.decodeToBuffer = nativeBinding.decodeToBuffer
+module.exports.describeBlob = nativeBinding.describeBlob
+module.exports.detachWhileBorrowed = nativeBinding.detachWhileBorrowed
+module.exports.displayFloat32Array = nativeBinding.displayFloat32Array
+module.exports.droppedSleepCount = nativeBinding.droppedSleepCount
+module.exports.echoRpcMessages = nativeBinding.echoRpcMessages
+module.exports.encodeBuffer = nativeBinding.encodeBuffer
+module.exports.encodeVarints = nativeBinding.encodeVarints
+module.exports.envBufferPoolStats = nativeBinding.envBufferPoolStats
+module.exports.evenNumbers = nativeBinding.evenNumbers
+module.exports.externalMemoryGuardChanges = nativeBinding.externalMemoryGuardChanges
+module.exports.fillSharedMemory = nativeBinding.fillSharedMemory
+module.exports.float32ArrayAlignment = nativeBinding.float32ArrayAlignment
+module.exports.forEachByteChunk = nativeBinding.forEachByteChunk
+module.exports.formatCurrency = nativeBinding.formatCurrency
+module.exports.FrameProducer = nativeBinding.FrameProducer
+module.exports.frozenBytesMutBuffer = nativeBinding.frozenBytesMutBuffer
+module.exports.getAsyncIteratorSymbol = nativeBinding.getAsyncIteratorSymbol
+module.exports.getFrozenUint8Array = nativeBinding.getFrozenUint8Array
+module.exports.getLastInstanceCaller = nativeBinding.getLastInstanceCaller
+module.exports.getModuleDir = nativeBinding.getModuleDir
+module.exports.getProcessPlatform = nativeBinding.getProcessPlatform
+module.exports.getReferenceReleaseStats = nativeBinding.getReferenceReleaseStats
+module.exports.getRegisteredExportKind = nativeBinding.getRegisteredExportKind
+module.exports.getRegisteredExportSignature = nativeBinding.getRegisteredExportSignature
+module.exports.getRuntimeInfo = nativeBinding.getRuntimeInfo
+module.exports.getTaggedExternal = nativeBinding.getTaggedExternal
+module.exports.getToStringTag = nativeBinding.getToStringTag
+module.exports.hexdumpUint8Array = nativeBinding.hexdumpUint8Array
+module.exports.incrementArraybuffer = nativeBinding.incrementArraybuffer
+module.exports.incrementSharedCounter = nativeBinding.incrementSharedCounter
+module.exports.indexOfInBuffer = nativeBinding.indexOfInBuffer
+module.exports.joinInEscapableScope = nativeBinding.joinInEscapableScope
+module.exports.joinPath = nativeBinding.joinPath
 module.exports.JsAsset = nativeBinding.JsAsset
 module.exports.Assets = nativeBinding.Assets
 module.exports.JsAssets = nativeBinding.JsAssets
@@ -407,17 +489,124 @@ module.exports.Fib = nativeBinding.Fib
 module.exports.Fib2 = nativeBinding.Fib2
 module.exports.Fib3 = nativeBinding.Fib3
 module.exports.GetterSetterWithClosures = nativeBinding.GetterSetterWithClosures
+module.exports.jsBufferIntoVec = nativeBinding.jsBufferIntoVec
 module.exports.JsClassForEither = nativeBinding.JsClassForEither
 module.exports.JsRemote = nativeBinding.JsRemote
 module.exports.JsRepo = nativeBinding.JsRepo
+module.exports.kvGet = nativeBinding.kvGet
+module.exports.kvSet = nativeBinding.kvSet
+module.exports.kvThreadName = nativeBinding.kvThreadName
+module.exports.latin1FromString = nativeBinding.latin1FromString
+module.exports.latin1HeaderLine = nativeBinding.latin1HeaderLine
+module.exports.limitUtf16Len = nativeBinding.limitUtf16Len
+module.exports.linspace = nativeBinding.linspace
+module.exports.liveExternalBufferSizes = nativeBinding.liveExternalBufferSizes
+module.exports.lookupWithStructuredError = nativeBinding.lookupWithStructuredError
+module.exports.movePoint = nativeBinding.movePoint
 module.exports.NinjaTurtle = nativeBinding.NinjaTurtle
+module.exports.nonAsciiCharOffsets = nativeBinding.nonAsciiCharOffsets
+module.exports.normalizeNewlines = nativeBinding.normalizeNewlines
 module.exports.NotUseNullableClass = nativeBinding.NotUseNullableClass
 module.exports.NotWritableClass = nativeBinding.NotWritableClass
+module.exports.numberedLines = nativeBinding.numberedLines
+module.exports.ObjectMemo = nativeBinding.ObjectMemo
 module.exports.Optional = nativeBinding.Optional
 module.exports.PackageJsonReader = nativeBinding.PackageJsonReader
+module.exports.parseAndCloneJson = nativeBinding.parseAndCloneJson
+module.exports.parseRecords = nativeBinding.parseRecords
+module.exports.patchBuffer = nativeBinding.patchBuffer
+module.exports.pathFileName = nativeBinding.pathFileName
+module.exports.pooledBuffer = nativeBinding.pooledBuffer
+module.exports.pooledBuffersAvailable = nativeBinding.pooledBuffersAvailable
+module.exports.pooledFill = nativeBinding.pooledFill
+module.exports.produceByteChunks = nativeBinding.produceByteChunks
+module.exports.produceFailingByteChunks = nativeBinding.produceFailingByteChunks
+module.exports.queueNativeMicrotask = nativeBinding.queueNativeMicrotask
+module.exports.readBlobReversed = nativeBinding.readBlobReversed
 module.exports.Reader = nativeBinding.Reader
+module.exports.readFileFrom = nativeBinding.readFileFrom
+module.exports.readFileRange = nativeBinding.readFileRange
+module.exports.readNodeStream = nativeBinding.readNodeStream
+module.exports.reclaimRustBuffer = nativeBinding.reclaimRustBuffer
+module.exports.recordInstanceCall = nativeBinding.recordInstanceCall
+module.exports.registerAsyncCleanupHooks = nativeBinding.registerAsyncCleanupHooks
+module.exports.registerPhasedCleanupHooks = nativeBinding.registerPhasedCleanupHooks
+module.exports.removeModuleExport = nativeBinding.removeModuleExport
+module.exports.repeatExternalLatin1 = nativeBinding.repeatExternalLatin1
+module.exports.repeatExternalUtf16 = nativeBinding.repeatExternalUtf16
+module.exports.replaceBorrowedInstance = nativeBinding.replaceBorrowedInstance
+module.exports.reportedExternalMemory = nativeBinding.reportedExternalMemory
+module.exports.resetEnvLocalCalls = nativeBinding.resetEnvLocalCalls
+module.exports.resizeArrayBuffer = nativeBinding.resizeArrayBuffer
+module.exports.resolvePinnedBytesAfter = nativeBinding.resolvePinnedBytesAfter
+module.exports.reverseTransferred = nativeBinding.reverseTransferred
+module.exports.rpcMessageFields = nativeBinding.rpcMessageFields
+module.exports.runScriptAsU32 = nativeBinding.runScriptAsU32
+module.exports.runScriptWithFilename = nativeBinding.runScriptWithFilename
+module.exports.rustBufferIntoUint8ArrayTwice = nativeBinding.rustBufferIntoUint8ArrayTwice
+module.exports.sameValue = nativeBinding.sameValue
+module.exports.scaleMatrixRows = nativeBinding.scaleMatrixRows
+module.exports.scheduleNativeTasks = nativeBinding.scheduleNativeTasks
 module.exports.Selector = nativeBinding.Selector
+module.exports.setBackgroundErrorHandler = nativeBinding.setBackgroundErrorHandler
+module.exports.setModuleExport = nativeBinding.setModuleExport
+module.exports.sharedBytesAfterWrite = nativeBinding.sharedBytesAfterWrite
+module.exports.sharedBytesRoundTrip = nativeBinding.sharedBytesRoundTrip
+module.exports.sharedTableBuffer = nativeBinding.sharedTableBuffer
+module.exports.sharedTableOwners = nativeBinding.sharedTableOwners
+module.exports.shrinkAndReadOldHandle = nativeBinding.shrinkAndReadOldHandle
+module.exports.singleFlightLookup = nativeBinding.singleFlightLookup
+module.exports.singleFlightLookupCount = nativeBinding.singleFlightLookupCount
+module.exports.sleepWithOptionalSignal = nativeBinding.sleepWithOptionalSignal
+module.exports.sleepWithSignal = nativeBinding.sleepWithSignal
+module.exports.sliceBlob = nativeBinding.sliceBlob
+module.exports.sliceBytesBuffer = nativeBinding.sliceBytesBuffer
+module.exports.sliceChars = nativeBinding.sliceChars
+module.exports.sliceRustBuffer = nativeBinding.sliceRustBuffer
+module.exports.sortByLocale = nativeBinding.sortByLocale
+module.exports.splitBuffer = nativeBinding.splitBuffer
+module.exports.squareInPlace = nativeBinding.squareInPlace
+module.exports.squaresBelow = nativeBinding.squaresBelow
+module.exports.startLoopWatchdog = nativeBinding.startLoopWatchdog
+module.exports.startLoopWatchdogOnce = nativeBinding.startLoopWatchdogOnce
+module.exports.startSliceBorrowReports = nativeBinding.startSliceBorrowReports
+module.exports.statusText = nativeBinding.statusText
+module.exports.stopLoopWatchdog = nativeBinding.stopLoopWatchdog
+module.exports.stopSliceBorrowReports = nativeBinding.stopSliceBorrowReports
+module.exports.streamRepeated = nativeBinding.streamRepeated
+module.exports.strictEquals = nativeBinding.strictEquals
+module.exports.stringCodePoints = nativeBinding.stringCodePoints
+module.exports.sumAllPromises = nativeBinding.sumAllPromises
+module.exports.sumChunks = nativeBinding.sumChunks
+module.exports.sumInThreads = nativeBinding.sumInThreads
+module.exports.summarizeTypedArray = nativeBinding.summarizeTypedArray
+module.exports.sumPinnedBytes = nativeBinding.sumPinnedBytes
+module.exports.sumPointCoordinates = nativeBinding.sumPointCoordinates
+module.exports.sumSharedArrayBuffer = nativeBinding.sumSharedArrayBuffer
+module.exports.sumU32Multiversion = nativeBinding.sumU32Multiversion
+module.exports.sumV1 = nativeBinding.sumV1
+module.exports.sumV2 = nativeBinding.sumV2
+module.exports.takeAsyncCleanupEvents = nativeBinding.takeAsyncCleanupEvents
+module.exports.takePhasedCleanupOrder = nativeBinding.takePhasedCleanupOrder
+module.exports.throwStructuredError = nativeBinding.throwStructuredError
+module.exports.Ticker = nativeBinding.Ticker
+module.exports.timeOffsetDateTimeAdd1Minute = nativeBinding.timeOffsetDateTimeAdd1Minute
+module.exports.timeOffsetDateTimeToMillis = nativeBinding.timeOffsetDateTimeToMillis
+module.exports.transposeMatrix = nativeBinding.transposeMatrix
+module.exports.tsfnBatchedProducer = nativeBinding.tsfnBatchedProducer
+module.exports.tsfnBlockingCallOnJsThread = nativeBinding.tsfnBlockingCallOnJsThread
+module.exports.tsfnBoundedProducer = nativeBinding.tsfnBoundedProducer
+module.exports.tsfnCloseWithDrain = nativeBinding.tsfnCloseWithDrain
+module.exports.tsfnHandleInTasks = nativeBinding.tsfnHandleInTasks
+module.exports.tsfnKeepaliveWhileActive = nativeBinding.tsfnKeepaliveWhileActive
+module.exports.tsfnQueueStats = nativeBinding.tsfnQueueStats
+module.exports.tsfnReportErrorsToHandler = nativeBinding.tsfnReportErrorsToHandler
+module.exports.tsfnUrgentCall = nativeBinding.tsfnUrgentCall
+module.exports.uint16Subarray = nativeBinding.uint16Subarray
+module.exports.uint8ArrayIntoBuffer = nativeBinding.uint8ArrayIntoBuffer
+module.exports.uppercaseNodeStream = nativeBinding.uppercaseNodeStream
 module.exports.UseNullableClass = nativeBinding.UseNullableClass
+module.exports.uvIdleRefStates = nativeBinding.uvIdleRefStates
 module.exports.Width = nativeBinding.Width
 module.exports.acceptArraybuffer = nativeBinding.acceptArraybuffer
 module.exports.acceptSlice = nativeBinding.acceptSlice
@@ -563,6 +752,7 @@ module.exports.Kind = nativeBinding.Kind
 module.exports.KindInValidate = nativeBinding.KindInValidate
 module.exports.listObjKeys = nativeBinding.listObjKeys
 module.exports.mapOption = nativeBinding.mapOption
+module.exports.MemoryPolicy = nativeBinding.MemoryPolicy
 module.exports.mergeTupleArray = nativeBinding.mergeTupleArray
 module.exports.mutateExternal = nativeBinding.mutateExternal
 module.exports.mutateOptionalExternal = nativeBinding.mutateOptionalExternal
@@ -670,6 +860,11 @@ module.exports.validateUndefined = nativeBinding.validateUndefined
 module.exports.withAbortController = nativeBinding.withAbortController
 module.exports.withinAsyncRuntimeIfAvailable = nativeBinding.withinAsyncRuntimeIfAvailable
 module.exports.withoutAbortController = nativeBinding.withoutAbortController
+module.exports.wrapWithHooks = nativeBinding.wrapWithHooks
+module.exports.writeAroundCallback = nativeBinding.writeAroundCallback
+module.exports.writeBufferAt = nativeBinding.writeBufferAt
+module.exports.writeDataView = nativeBinding.writeDataView
+module.exports.writeUint16At = nativeBinding.writeUint16At
 module.exports.xxh64Alias = nativeBinding.xxh64Alias
 module.exports.xxh2 = nativeBinding.xxh2
 module.exports.xxh3 = nativeBinding.xxh3
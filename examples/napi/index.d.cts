@@ -180,6 +180,16 @@ export declare class Fib3 {
   [Symbol.iterator](): Iterator<number, void, number>
 }
 
+/** Lend the same memory to JavaScript for each frame. */
+export declare class FrameProducer {
+  constructor(len: number)
+  /** Recall the previous frame and lend the next one. */
+  nextFrame(): ArrayBuffer
+  /** Recall the current frame and return the sum of the bytes written by JavaScript. */
+  recall(): number
+  get isLent(): boolean
+}
+
 export declare class GetterSetterWithClosures {
   constructor()
 }
@@ -196,6 +206,8 @@ export declare class JsRemote {
 export declare class JsRepo {
   constructor(dir: string)
   remote(): JsRemote
+  dropOnOtherThread(): void
+  dirThroughSendGuard(): string
 }
 
 export declare class NinjaTurtle {
@@ -222,6 +234,12 @@ export declare class NotWritableClass {
   setName(name: string): void
 }
 
+export declare class ObjectMemo {
+  constructor()
+  compute(key: object): number
+  forget(key: object): boolean
+}
+
 export declare class Optional {
   static optionEnd(required: string, optional?: string | undefined | null): string
   static optionStart(optional: string | undefined | null, required: string): string
@@ -248,6 +266,17 @@ export declare class Selector {
   constructor(orderBy: Array<string>, select: Array<string>, struct: string, where?: string)
 }
 
+export declare class Ticker {
+  constructor()
+  on(event: string, listener: (payload: any) => void): void
+  once(event: string, listener: (payload: any) => void): void
+  off(event: string, listener: (payload: any) => void): void
+  listenerCount(event: string): number
+  /** Emit `tick` with `0..count` from another thread, then `done` with the count */
+  start(count: number): void
+  emitNow(event: string, payload: string): boolean
+}
+
 export declare class UseNullableClass {
   requiredNumberField: number
   requiredStringField: string
@@ -275,6 +304,8 @@ export declare function acceptThreadsafeFunction(func: ((err: Error | null, arg:
 
 export declare function acceptThreadsafeFunctionFatal(func: ((arg: number) => void)): void
 
+export declare function acceptThreadsafeFunctionTuple(func: ((arg0: number, arg1: string) => void)): void
+
 export declare function acceptThreadsafeFunctionTupleArgs(func: ((err: Error | null, arg0: number, arg1: boolean, arg2: string) => any)): void
 
 export declare function acceptUint8ClampedSlice(input: Uint8ClampedArray): bigint
@@ -298,14 +329,23 @@ export interface AllOptionalObject {
   age?: number
 }
 
+export declare function alignedFloat32Array(data: Array<number>): Float32Array
+
 export declare function appendBuffer(buf: Buffer): Buffer
 
 export declare function apply0(ctx: Animal, callback: () => void): void
 
 export declare function apply1(ctx: Animal, callback: (arg: string) => void, name: string): void
 
+export declare function arrayBufferFromBytes(): ArrayBuffer
+
 export declare function arrayBufferPassThrough(buf: Uint8Array): Promise<Uint8Array>
 
+export interface ArrayBufferResizeInfo {
+  byteLength: number
+  maxByteLength: number
+}
+
 export declare function asyncBufferToArray(buf: ArrayBuffer): Array<number>
 
 export declare function asyncMultiTwo(arg: number): Promise<number>
@@ -314,6 +354,11 @@ export declare function asyncPlus100(p: Promise<number>): Promise<number>
 
 export declare function asyncReduceBuffer(buf: Buffer): Promise<number>
 
+export declare function asyncTaskFailingCleanup(onError: (arg: Error) => void): Promise<void>
+
+/** The error of `finally` is delivered to the handler of the env */
+export declare function asyncTaskFailingCleanupInEnv(): Promise<void>
+
 export declare function asyncTaskOptionalReturn(): Promise<number | null>
 
 export declare function asyncTaskReadFile(path: string): Promise<Buffer>
@@ -332,14 +377,35 @@ export declare function bigintFromI64(): bigint
 
 export declare function bigintGetU64AsString(bi: bigint): string
 
+export declare function blockEventLoop(ms: number): void
+
 export declare function btreeSetToJs(): Set<string>
 
 export declare function btreeSetToRust(set: Set<string>): void
 
+export declare function bufferDiagnosticsEnabled(): boolean
+
+export declare function bufferDiagnosticsReport(): string
+
+export declare function bufferEventCounts(): BufferEventCounts
+
+export interface BufferEventCounts {
+  created: number
+  finalized: number
+  referenced: number
+  unreferenced: number
+}
+
+export declare function bufferIntoUint8Array(input: Buffer): Uint8Array
+
 export declare function bufferPassThrough(buf: Buffer): Promise<Buffer>
 
+export declare function buffersFromBytes(): Array<Buffer>
+
 export declare function bufferWithAsyncBlock(buf: Buffer): Promise<number>
 
+export declare function buildBuffer(parts: Array<string>): Buffer
+
 export declare function buildThreadsafeFunctionFromFunction(callback: (arg0: number, arg1: number) => number): void
 
 export declare function buildThreadsafeFunctionFromFunctionCalleeHandle(callback: () => void): void
@@ -348,6 +414,15 @@ export interface C {
   baz: number
 }
 
+export interface CachedKeysObject {
+  id: number
+  label: string
+  note?: string
+  children: Array<CachedKeysObject>
+}
+
+export declare function byteChunksSent(): number
+
 export declare function call0(callback: () => number): number
 
 export declare function call1(callback: (arg: number) => number, arg: number): number
@@ -362,8 +437,13 @@ export declare function callbackReturnPromiseAndSpawn(jsFunc: (arg0: string) =>
 
 export declare function callCatchOnPromise(input: Promise<number>): Promise<string>
 
+export declare function callCatchTypedOnPromise(input: Promise<number>): Promise<string>
+
 export declare function callFinallyOnPromise(input: Promise<number>, onFinally: () => void): Promise<number>
 
+/** Call `callback` from a uv timer, in the async context of this call. */
+export declare function callFromUvTimer(callback: () => void, useMakeCallback: boolean): void
+
 export declare function callFunction(cb: () => number): number
 
 export declare function callFunctionWithArg(cb: (arg0: number, arg1: number) => number, arg0: number, arg1: number): number
@@ -376,10 +456,20 @@ export declare function callRuleHandler(rule: Rule, arg: number): number
 
 export declare function callThenOnPromise(input: Promise<number>): Promise<string>
 
+export declare function callThenOrCatchOnPromise(input: Promise<number>): Promise<string>
+
+export declare function callThenTypedOnPromise(input: Promise<unknown>): Promise<number>
+
 export declare function callThreadsafeFunction(tsfn: ((err: Error | null, arg: number) => unknown)): void
 
+export declare function callWithBorrowedSlice(view: Uint8Array, callback: () => void): void
+
 export declare function captureErrorInCallback(cb1: () => void, cb2: (arg0: Error) => void): void
 
+export declare function channelBatchesToJs(count: number, callback: (arg: Array<number>) => void): Promise<undefined>
+
+export declare function channelNumbersToJs(count: number, callback: (arg: number) => void): Promise<undefined>
+
 export declare function chronoDateAdd1Minute(input: Date): Date
 
 export declare function chronoDateFixtureReturn1(): Date
@@ -402,16 +492,53 @@ export declare function chronoUtcDateReturn(): Date | null
 
 export declare function chronoUtcDateToMillis(input: Date): number
 
+export declare function compareBuffers(source: Buffer, target: Buffer, targetStart?: number | undefined | null, sourceStart?: number | undefined | null): number
+
+export declare function concatBuffers(parts: Array<Buffer>): Buffer
+
 export declare function concatLatin1(s: string): string
 
 export declare function concatStr(s: string): string
 
+export declare function concatStrings(first: string, rest: Array<string>): string
+
 export declare function concatUtf16(s: string): string
 
 export declare function contains(source: string, target: string): boolean
 
+export declare function convertRustBuffers(): Array<Buffer | Uint8Array>
+
 export declare function convertU32Array(input: Uint32Array): Array<number>
 
+export declare function copiedTableBuffer(): Buffer
+
+export declare function copyFloat64Array(input: Float64Array): Float64Array
+
+export declare function copyIntoArraybuffer(input: Uint8Array): ArrayBuffer
+
+export declare function countBufferEvents(enabled: boolean): void
+
+export declare function countCharsInScopes(arr: unknown[]): number
+
+export declare function countDistinctBigints(values: Array<bigint>): number
+
+export declare function countDistinctNumbers(values: Array<number>): number
+
+export declare function countDistinctStrings(values: Array<string>): number
+
+export declare function countEnvLocalCalls(): number
+
+export declare function countUnpairedSurrogates(text: string): number
+
+export declare function countUntilAborted(limit: number, signal: AbortSignal): Promise<number>
+
+export declare function countUntilInterrupted(): Promise<number> & { interrupt(): void }
+
+/** Tick a uv timer `ticks` times, resolved with the number of the ticks and the check callbacks called meanwhile. */
+export declare function countUvTimerTicks(ticks: number): Promise<[number, number]>
+
+export declare function countWords(text: string): number
+
 export declare function createArraybuffer(): ArrayBuffer
 
 export declare function createBigInt(): bigint
@@ -420,6 +547,10 @@ export declare function createBigIntI64(): bigint
 
 export declare function createBufferSliceFromCopiedData(): Buffer
 
+export declare function createCachedKeysObjects(count: number): Array<CachedKeysObject>
+
+export declare function createDataView(arrayBuffer: ArrayBuffer, byteOffset: number, length: number): DataView
+
 export declare function createExternal(size: number): ExternalObject<number>
 
 export declare function createExternalBufferSlice(): Buffer
@@ -432,6 +563,8 @@ export declare function createObj(): object
 
 export declare function createObjectWithClassField(): ObjectFieldClassInstance
 
+export declare function createObjectWithPropertyKeys(): object
+
 export declare function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
 
 export declare function createOptionalExternal(size?: number | undefined | null): ExternalObject<number> | null
@@ -442,10 +575,23 @@ export declare function createReadableStreamFromClass(readableStreamClass: typeo
 
 export declare function createReferenceOnFunction(cb: () => void): Promise<void>
 
+export declare function createResizableArrayBuffer(byteLength: number, maxByteLength: number): ArrayBuffer
+
+export declare function createSequenceArraybuffer(len: number): ArrayBuffer
+
+export declare function createSharedMemory(byteLength: number): SharedArrayBuffer
+
 export declare function createSymbol(): symbol
 
 export declare function createSymbolFor(desc: string): symbol
 
+/** Create an object tagged by `Symbol.toStringTag`, which is disposed by `Symbol.dispose` if the runtime supports it. */
+export declare function createTaggedDisposable(tag: string): object
+
+export declare function createTaggedExternal(size: number): unknown
+
+export declare function createTextBlob(text: string): Blob
+
 /** You could break the step and for an new continuous value. */
 export declare const enum CustomNumEnum {
   One = 1,
@@ -490,8 +636,23 @@ export interface DefaultUseNullableStruct {
   optionalStringField?: string
 }
 
+export declare function debugBuffer(buf: Buffer): string
+
+export declare function decodeToBuffer(input: string, encoding: string): Buffer
+
 export declare function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
 
+export declare function describeBlob(blob: Blob): string
+
+export declare function detachWhileBorrowed(buf: ArrayBuffer, view: Uint8Array): number
+
+export declare function displayFloat32Array(input: Float32Array): string
+
+export declare function droppedSleepCount(): number
+
+/** Read the messages with a compiled `Schema`, and write them back with the method upper cased. */
+export declare function echoRpcMessages(messages: Array<object>): Array<object>
+
 export declare function either3(input: string | number | boolean): number
 
 export declare function either4(input: string | number | boolean | Obj): number
@@ -514,28 +675,53 @@ export declare const enum Empty {
 
 }
 
+export declare function encodeBuffer(input: Buffer, encoding: string): string
+
+/** Encode `values` as LEB128 varints. */
+export declare function encodeVarints(values: Array<number>): Uint8Array
+
 export declare function enumToI32(e: CustomNumEnum): number
 
+export declare function envBufferPoolStats(): PoolStats
+
 export declare function errorMessageContainsNullByte(msg: string): void
 
 export declare function esmResolve(next: () => Promise<undefined>): Promise<undefined>
 
+export declare function evenNumbers(input: Uint32Array): Uint32Array
+
 export declare function extendsJavascriptError(errorClass: any): void
 
+/** Hold `size` bytes with an `ExternalMemoryGuard` and return the external memory reported while it's held, resized and released. */
+export declare function externalMemoryGuardChanges(size: number, releaseOnThread: boolean): Array<number>
+
 export declare function f32ArrayToArray(input: Float32Array): Array<number>
 
 export declare function f64ArrayToArray(input: Float64Array): Array<number>
 
 export declare function fibonacci(n: number): number
 
+export declare function fillSharedMemory(memory: SharedArrayBuffer, value: number): Promise<void>
+
+/** The largest power of two alignment of the data, up to 4096. */
+export declare function float32ArrayAlignment(input: Float32Array): number
+
 export declare function fnReceivedAliased(s: AliasedStruct, e: ALIAS): void
 
 export interface FunctionData {
   handle: () => number
 }
 
+export declare function forEachByteChunk(count: number, callback: (arg: Buffer) => void): Promise<undefined>
+
+export declare function formatCurrency(value: number, currency: string, locale: string): string
+
+export declare function frozenBytesMutBuffer(): Buffer
+
 export declare function generateFunctionAndCallIt(): FunctionData
 
+export declare function getAsyncIteratorSymbol(): symbol
+
 export declare function getBigintJsonValue(value: bigint): void
 
 export declare function getBtreeMapping(): Record<string, number>
@@ -554,16 +740,22 @@ export declare function getEmptyTypedArray(): Uint8Array
 
 export declare function getExternal(external: ExternalObject<number>): number
 
+export declare function getFrozenUint8Array(): Readonly<Uint8Array>
+
 export declare function getGlobal(): typeof global
 
 export declare function getIndexMapping(): Record<string, number>
 
 export declare function getIndexMappingWithHasher(): Record<string, number>
 
+export declare function getLastInstanceCaller(): string | null
+
 export declare function getMapping(): Record<string, number>
 
 export declare function getMappingWithHasher(): Record<string, number>
 
+export declare function getModuleDir(): string
+
 export declare function getModuleFileName(): string
 
 export declare function getMyVec(): MyVec
@@ -583,16 +775,32 @@ export declare function getOptionalExternal(external?: ExternalObject<number> |
 
 export declare function getPackageJsonName(packageJson: PackageJson): string
 
+export declare function getProcessPlatform(): string
+
+export declare function getReferenceReleaseStats(): ReferenceReleaseStats
+
+export declare function getRegisteredExportKind(jsMod: string | undefined | null, name: string): string | null
+
+export declare function getRegisteredExportSignature(jsMod: string | undefined | null, name: string): string | null
+
+export declare function getRuntimeInfo(): RuntimeInfo
+
 export declare function getStrFromObject(): void
 
+export declare function getTaggedExternal(external: unknown): number
+
 export declare function getterFromObj(): number
 
+export declare function getToStringTag(obj: object): string | null
+
 export declare function getTuple(val: [number, string, number]): number
 
 export declare function getUndefined(): void
 
 export declare function getWords(): Array<string>
 
+export declare function hexdumpUint8Array(input: Uint8Array, start: number, end: number): string
+
 export declare function i16ArrayToArray(input: Int16Array): Array<number>
 
 export declare function i32ArrayToArray(input: Int32Array): Array<number>
@@ -601,8 +809,20 @@ export declare function i64ArrayToArray(input: BigInt64Array): Array<number>
 
 export declare function i8ArrayToArray(input: Int8Array): Array<number>
 
+export declare function incrementArraybuffer(buf: ArrayBuffer): void
+
+export declare function incrementSharedCounter(memory: SharedArrayBuffer, threads: number): void
+
 export declare function indexmapPassthrough(fixture: Record<string, number>): Record<string, number>
 
+export declare function indexOfInBuffer(input: Buffer, value: string | Buffer, byteOffset?: number | undefined | null): number
+
+export declare function joinInEscapableScope(parts: Array<string>): string
+
+export declare function joinPath(base: string, name: string): string
+
+export declare function jsBufferIntoVec(input: Buffer): number | string
+
 export declare function jsErrorCallback(value: unknown): Array<Error>
 
 /** default enum values are continuos i32s start from 0 */
@@ -625,6 +845,21 @@ export declare const enum KindInValidate {
   Duck = 2
 }
 
+export declare function kvGet(key: string): Promise<string>
+
+export declare function kvSet(key: string, value: string): Promise<string | null>
+
+export declare function kvThreadName(): Promise<string | null>
+
+export declare function latin1FromString(value: string): [string, string]
+
+export declare function latin1HeaderLine(name: string, value: string): string
+
+/** Return `text` as it is if it's shorter than `max_len`, without copying it out of the engine. */
+export declare function limitUtf16Len(text: string, maxLen: number): string
+
+export declare function linspace(start: number, end: number, count: number): Float64Array
+
 export declare function listObjKeys(obj: object): Array<string>
 
 export interface LocalDates {
@@ -632,14 +867,32 @@ export interface LocalDates {
   end?: Date
 }
 
+export interface LookupFailure {
+  code: string
+  key: string
+  retryable: boolean
+}
+
+export declare function liveExternalBufferSizes(): Array<number>
+
+export declare function lookupWithStructuredError(key: string): Promise<number>
+
 export declare function mapOption(val?: number | undefined | null): number | null
 
+export declare const enum MemoryPolicy {
+  Exact = 'exact',
+  Batched = 'batched',
+  Disabled = 'disabled'
+}
+
 export declare function mergeTupleArray(t1: TupleToArray, t2: TupleToArray): TupleToArray
 
 export interface Meta {
   merge: boolean
 }
 
+export declare function movePoint(point: object, dx: number): void
+
 export declare function mutateExternal(external: ExternalObject<number>, newVal: number): void
 
 export declare function mutateOptionalExternal(external: ExternalObject<number> | undefined | null, newVal: number): void
@@ -670,6 +923,14 @@ export interface ObjectFieldClassInstance {
   bird: Bird
 }
 
+/** The JavaScript index of each character which isn't ASCII. */
+export declare function nonAsciiCharOffsets(text: string): Array<number>
+
+/** Replace the `\r\n` with `\n`, the text without `\r` is returned without copying in Rust. */
+export declare function normalizeNewlines(text: string): string
+
+export declare function numberedLines(prefix: string, count: number): string
+
 export declare function objectGetNamedPropertyShouldPerformTypecheck(obj: { foo: number; bar: string; }): void
 
 export interface ObjectOnlyFromJs {
@@ -709,6 +970,11 @@ export declare function panic(): void
 
 export declare function panicInAsync(): Promise<void>
 
+/** Parse `json` by `JSON.parse` and return its `structuredClone`. */
+export declare function parseAndCloneJson(json: string): unknown
+
+export declare function parseRecords(input: Buffer): Array<string>
+
 export declare function passSetToJs(): Set<string>
 
 export declare function passSetToRust(set: Set<string>): void
@@ -721,15 +987,49 @@ export interface Pet {
   eitherTsfn: string | ((err: Error | null, arg: number) => number)
 }
 
+export declare function patchBuffer(input: Buffer, offsetFromEnd: number, patch: string): number
+
+export declare function pathFileName(path: string): string | null
+
 export declare function plusOne(this: Width): number
 
+export declare function pooledBuffer(text: string): Buffer
+
+export declare function pooledBuffersAvailable(): number
+
+export declare function pooledFill(byte: number, len: number): Buffer
+
+export interface PoolStats {
+  sizeClasses: Array<number>
+  hits: number
+  misses: number
+  oversized: number
+  recycled: number
+  discarded: number
+  available: number
+}
+
+export declare function produceByteChunks(count: number, highWaterMark: number): AsyncIterableIterator<Buffer>
+
+export declare function produceFailingByteChunks(): AsyncIterableIterator<Buffer>
+
 export declare function promiseInEither(input: number | Promise<number>): Promise<boolean>
 
+export declare function queueNativeMicrotask(callback: () => unknown): void
+
+export declare function readBlobReversed(blob: Blob): Promise<Buffer>
+
 /** napi = { version = 2, features = ["serde-json"] } */
 export declare function readFile(callback: (arg0: Error | undefined, arg1?: string | undefined | null) => void): void
 
 export declare function readFileAsync(path: string): Promise<Buffer>
 
+export declare function readFileFrom(path: string, start: number): Promise<Buffer>
+
+export declare function readFileRange(path: string, start?: number | undefined | null, end?: number | undefined | null): Buffer
+
+export declare function readNodeStream(input: import('node:stream').Readable): Promise<Buffer>
+
 export declare function readPackageJson(): PackageJson
 
 export declare function receiveAllOptionalObject(obj?: AllOptionalObject | undefined | null): void
@@ -750,8 +1050,39 @@ export declare function receiveStrictObject(strictObject: StrictObject): void
 
 export declare function receiveString(s: string): string
 
+export declare function reclaimRustBuffer(): boolean
+
+export declare function recordInstanceCall(caller: string): number
+
 export declare function referenceAsCallback(callback: (arg0: number, arg1: number) => number, arg0: number, arg1: number): number
 
+export interface ReferenceReleaseStats {
+  releasedInPlace: number
+  queued: number
+  releasedFromQueue: number
+}
+
+export declare function registerAsyncCleanupHooks(): void
+
+export declare function registerPhasedCleanupHooks(): void
+
+export declare function removeModuleExport(jsMod: string | undefined | null, name: string): boolean
+
+export declare function repeatExternalLatin1(byte: number, times: number): string
+
+export declare function repeatExternalUtf16(text: string, times: number): string
+
+export declare function replaceBorrowedInstance(): void
+
+/** Create a `BufferSlice` of `size` bytes under `policy`, and return the change of the external memory reported to the engine. */
+export declare function reportedExternalMemory(size: number, policy: MemoryPolicy): number
+
+export declare function resetEnvLocalCalls(): number | null
+
+export declare function resizeArrayBuffer(buf: ArrayBuffer, newByteLength: number): ArrayBufferResizeInfo
+
+export declare function resolvePinnedBytesAfter(bytes: ArrayBufferView, callback: () => void): number
+
 export declare function returnCString(): string
 
 export declare function returnEither(input: number): string | number
@@ -770,8 +1101,20 @@ export declare function returnUndefinedIfInvalid(input: boolean): boolean
 
 export declare function returnUndefinedIfInvalidPromise(input: Promise<boolean>): Promise<boolean>
 
+/** Take the bytes of `buf` and return them reversed in a new `ArrayBuffer`, `buf` is detached. */
+export declare function reverseTransferred(buf: ArrayBuffer): ArrayBuffer
+
 export declare function roundtripStr(s: string): string
 
+export interface RpcMessage {
+  id: number
+  method: string
+  params: Array<string>
+  traceId?: string
+}
+
+export declare function rpcMessageFields(): Array<string>
+
 export interface Rule {
   name: string
   handler: RuleHandler<number, number>
@@ -782,18 +1125,87 @@ export type RuleHandler<Args, Ret> =
 
 export declare function runScript(script: string): unknown
 
+export declare function runScriptAsU32(script: string): number
+
+export declare function runScriptWithFilename(script: string, filename: string, lineOffset: number): unknown
+
+export interface RuntimeInfo {
+  napiVersion: number
+  nodeVersion: string
+  supportsExternalStrings: boolean
+}
+
+export declare function rustBufferIntoUint8ArrayTwice(): Uint8Array
+
+export declare function sameValue(a: unknown, b: unknown): boolean
+
+export declare function scaleMatrixRows(input: Float64Array, rows: number, cols: number): void
+
+export declare function scheduleNativeTasks(callback: (arg: string) => void): void
+
+export declare function setBackgroundErrorHandler(onError: (arg: Error) => void): void
+
+export declare function setModuleExport(jsMod: string | undefined | null, name: string, value: unknown): void
+
 export declare function setNullByteProperty(obj: object): void
 
 export declare function setSymbolInObj(symbol: symbol): object
 
+/** Return `input` to JavaScript while a clone is kept, and read the clone after `write` mutates the returned `Buffer`. */
+export declare function sharedBytesAfterWrite(input: Buffer, write: (arg: Buffer) => void): Buffer
+
+export declare function sharedBytesRoundTrip(input: Buffer): [Buffer, Buffer]
+
+export declare function sharedTableBuffer(start: number): Buffer
+
+export declare function sharedTableOwners(): number
+
 export declare function shorterEscapableScope(createString: () => string | null): string
 
 export declare function shorterScope(arr: unknown[]): Array<number>
 
+/**
+ * Shrink `buf` to `new_byte_length` bytes, then read the bytes through `old`, a handle of the same `ArrayBuffer`
+ * taken before the resize.
+ */
+export declare function shrinkAndReadOldHandle(buf: ArrayBuffer, old: ArrayBuffer, newByteLength: number): Array<number>
+
 export declare function shutdownRuntime(): void
 
+export declare function singleFlightLookup(key: string): Promise<number>
+
+export declare function singleFlightLookupCount(): number
+
+export declare function sleepWithOptionalSignal(ms: number, signal?: AbortSignal | undefined | null): Promise<number>
+
+export declare function sleepWithSignal(ms: number, signal: AbortSignal): Promise<number>
+
+export declare function sliceBlob(blob: Blob, start?: number | undefined | null, end?: number | undefined | null): Blob
+
+export declare function sliceBytesBuffer(start: number, end: number): Buffer
+
+export declare function sliceChars(text: string, start: number, end?: number | undefined | null): string
+
+export declare function sliceRustBuffer(): Buffer
+
+export declare function sortByLocale(values: Array<string>, locale: string): Array<string>
+
 export declare function spawnThreadInThread(tsfn: ((err: Error | null, arg: number) => number)): void
 
+export declare function splitBuffer(input: Buffer, size: number): Array<Buffer>
+
+/** Square the elements in place, synced back to JavaScript in the wasm builds. */
+export declare function squareInPlace(input: Float64Array): void
+
+export declare function squaresBelow(limit: number): Uint32Array
+
+export declare function startLoopWatchdog(budgetMs: number): void
+
+/** Like `startLoopWatchdog`, but the callback clears itself after the first report. */
+export declare function startLoopWatchdogOnce(budgetMs: number): void
+
+export declare function startSliceBorrowReports(): void
+
 export declare const enum Status {
   Pristine = 'Pristine',
   Loading = 'Loading',
@@ -810,6 +1222,19 @@ export interface StrictObject {
   name: string
 }
 
+export declare function statusText(code: number): string
+
+export declare function stopLoopWatchdog(): Array<string>
+
+export declare function stopSliceBorrowReports(): Array<string>
+
+/** Write `text` `times` times and stream the output to `on_chunk` in pooled buffers. */
+export declare function streamRepeated(text: string, times: number, onChunk: (arg: Buffer) => void): void
+
+export declare function strictEquals(a: unknown, b: unknown): boolean
+
+export declare function stringCodePoints(text: string): Array<number>
+
 export declare const enum StringEnum {
   VariantOne = 'variantone',
   VariantTwo = 'varianttwo',
@@ -822,14 +1247,53 @@ export type StructuredKind =
   | { type2: 'Birthday', name: string, age: number }
   | { type2: 'Tuple', field0: number, field1: number }
 
+export declare function sumAllPromises(promises: Array<Promise<number>>): Promise<number>
+
 export declare function sumBtreeMapping(nums: Record<string, number>): number
 
+/**
+ * Sum the bytes of every chunk, and pass the chunks to `on_chunk` with their offsets.
+ *
+ * With `copy`, the chunks are taken from a copy of `input` owned by Rust.
+ */
+export declare function sumChunks(input: Uint8Array, chunkLen: number, copy: boolean, onChunk: (arg0: unknown, arg1: number) => void): Array<number>
+
 export declare function sumIndexMapping(nums: Record<string, number>): number
 
+export declare function sumInThreads(input: Buffer, threads: number): number
+
 export declare function sumMapping(nums: Record<string, number>): number
 
+export declare function summarizeTypedArray(input: Int8Array | Uint8Array | Uint8ClampedArray | Int16Array | Uint16Array | Int32Array | Uint32Array | Float32Array | Float64Array | BigInt64Array | BigUint64Array): TypedArraySummary
+
 export declare function sumNums(nums: Array<number>): number
 
+export declare function sumPinnedBytes(bytes: ArrayBufferView): Promise<number>
+
+/** Sum the `x` and `y` of the points, the keys are interned once per env. */
+export declare function sumPointCoordinates(points: Array<object>): number
+
+export declare function sumSharedArrayBuffer(buf: SharedArrayBuffer): number
+
+export declare function sumU32Multiversion(input: Array<number>): number
+
+/**
+ * Sum of two numbers.
+ * @since 1.0
+ * @deprecated use `sumV2` instead
+ */
+export declare function sumV1(a: number, b: number): number
+
+/**
+ * Sum of two numbers.
+ * @since 2.0
+ */
+export declare function sumV2(a: number, b: number): number
+
+export declare function takeAsyncCleanupEvents(): Array<string>
+
+export declare function takePhasedCleanupOrder(): Array<string>
+
 export declare function testSerdeBigNumberPrecision(number: string): any
 
 export declare function testSerdeBufferBytes(obj: object): bigint
@@ -850,16 +1314,44 @@ export declare function throwAsyncError(): Promise<void>
 
 export declare function throwError(): void
 
+export declare function throwStructuredError(key: string): void
+
 export declare function throwSyntaxError(error: string, code?: string | undefined | null): void
 
+export declare function timeOffsetDateTimeAdd1Minute(input: Date): Date
+
+export declare function timeOffsetDateTimeToMillis(input: Date): number
+
 export declare function toJsObj(): object
 
+export declare function transposeMatrix(input: Float64Array, rows: number, cols: number): { data: ArrayBufferView; shape: Array<number> }
+
 export declare function tsfnAsyncCall(func: (arg0: number, arg1: number, arg2: number) => string): Promise<void>
 
+export declare function tsfnBatchedProducer(callback: (arg: Array<number>) => void, count: number, maxBatchSize: number): void
+
+export declare function tsfnBlockingCallOnJsThread(callback: (arg: number) => void): string
+
+export declare function tsfnBoundedProducer(func: (arg: number) => void, count: number): Promise<void>
+
 export declare function tsfnCallWithCallback(tsfn: ((err: Error | null, ) => string)): void
 
+export declare function tsfnCloseWithDrain(callback: (arg: number) => void, count: number, timeoutMs: number): Promise<TsfnDrainReport>
+
+export declare function tsfnHandleInTasks(func: ((err: Error | null, arg: number) => number), tasks: number): Promise<number>
+
 export declare function tsfnInEither(pet: Pet): void
 
+export declare function tsfnKeepaliveWhileActive(callback: (arg: number) => void, count: number): void
+
+/**
+ * The queue holds 4 calls, and the high-water mark is 3.
+ * Resolves with the stats at the high-water mark, after the calls are queued, and after they are delivered.
+ */
+export declare function tsfnQueueStats(callback: (arg: number) => void, count: number): Promise<Array<TsfnQueueStats>>
+
+export declare function tsfnReportErrorsToHandler(callback: (arg: number) => void, onError: (arg: Error) => void, count: number): void
+
 export declare function tsfnReturnPromise(func: ((err: Error | null, arg: number) => Promise<number>)): Promise<number>
 
 export declare function tsfnReturnPromiseTimeout(func: ((err: Error | null, arg: number) => Promise<number>)): Promise<number>
@@ -868,8 +1360,23 @@ export declare function tsfnThrowFromJs(tsfn: ((err: Error | null, arg: number)
 
 export declare function tsfnThrowFromJsCallbackContainsTsfn(tsfn: ((err: Error | null, arg: number) => Promise<number>)): Promise<void>
 
+export declare function tsfnUrgentCall(callback: ((arg: string) => void), count: number): void
+
 export declare function tsRename(a: { foo: number }): string[]
 
+export interface TsfnDrainReport {
+  delivered: number
+  dropped: number
+  timedOut: boolean
+}
+
+export interface TsfnQueueStats {
+  queueDepth: number
+  totalCalls: number
+  droppedCalls: number
+  averageLatencyMs: number
+}
+
 export interface TsTypeChanged {
   typeOverride: object
   typeOverrideOptional?: object
@@ -883,6 +1390,13 @@ export interface TupleObject {
 export type TupleToArray =
   [field0: string, field1: number, field2?: Meta]
 
+export interface TypedArraySummary {
+  kind: string
+  length: number
+  byteLength: number
+  sum: number
+}
+
 export declare function u16ArrayToArray(input: Uint16Array): Array<number>
 
 export declare function u32ArrayToArray(input: Uint32Array): Array<number>
@@ -905,6 +1419,15 @@ export interface UtcDates {
   end?: Date
 }
 
+export declare function uint16Subarray(input: Uint16Array, begin: number, end: number): Uint16Array
+
+export declare function uint8ArrayIntoBuffer(input: Uint8Array): Buffer
+
+export declare function uppercaseNodeStream(input: import('node:stream').Readable, output: import('node:stream').Writable): Promise<number>
+
+/** Create a uv idle handle, and return whether it refs the event loop before and after `unref`. */
+export declare function uvIdleRefStates(): Array<boolean>
+
 export declare function validateArray(arr: Array<number>): number
 
 export declare function validateBigint(input: bigint): bigint
@@ -960,6 +1483,16 @@ export declare function withinAsyncRuntimeIfAvailable(): void
 
 export declare function withoutAbortController(a: number, b: number): Promise<number>
 
+export declare function wrapWithHooks(cb: (arg0: number, arg1: number) => number): (arg0: number, arg1: number) => number
+
+export declare function writeAroundCallback(buf: ArrayBuffer, callback: () => void): number
+
+export declare function writeBufferAt(output: Buffer, offset: number, text: string): void
+
+export declare function writeDataView(view: DataView): number
+
+export declare function writeUint16At(output: Uint16Array, offset: number, values: Array<number>): void
+
 export declare function xxh64Alias(input: Buffer): bigint
 
 export declare namespace xxh2 {
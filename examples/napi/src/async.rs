@@ -43,3 +43,59 @@ pub fn within_async_runtime_if_available() {
     println!("within_runtime_if_available");
   });
 }
+
+static SINGLE_FLIGHT_LOOKUP: std::sync::LazyLock<SingleFlight<String, u32>> =
+  std::sync::LazyLock::new(SingleFlight::new);
+static SINGLE_FLIGHT_LOOKUP_COUNT: std::sync::atomic::AtomicU32 =
+  std::sync::atomic::AtomicU32::new(0);
+
+#[napi]
+async fn single_flight_lookup(key: String) -> Result<u32> {
+  SINGLE_FLIGHT_LOOKUP
+    .run(key.clone(), || async move {
+      tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+      if key.is_empty() {
+        return Err(Error::new(Status::InvalidArg, "Empty key"));
+      }
+      Ok(
+        SINGLE_FLIGHT_LOOKUP_COUNT.fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+          + key.len() as u32 * 100,
+      )
+    })
+    .await
+}
+
+#[napi]
+fn single_flight_lookup_count() -> u32 {
+  SINGLE_FLIGHT_LOOKUP_COUNT.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+static DROPPED_SLEEP_COUNT: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+// counts the sleeps whose future is dropped before they are done
+struct SleepGuard;
+
+impl Drop for SleepGuard {
+  fn drop(&mut self) {
+    DROPPED_SLEEP_COUNT.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+  }
+}
+
+#[napi]
+async fn sleep_with_signal(ms: u32, _signal: AbortSignal) -> Result<u32> {
+  let guard = SleepGuard;
+  tokio::time::sleep(std::time::Duration::from_millis(ms.into())).await;
+  std::mem::forget(guard);
+  Ok(ms)
+}
+
+#[napi]
+fn dropped_sleep_count() -> u32 {
+  DROPPED_SLEEP_COUNT.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+#[napi]
+async fn sleep_with_optional_signal(ms: u32, _signal: Option<AbortSignal>) -> Result<u32> {
+  tokio::time::sleep(std::time::Duration::from_millis(ms.into())).await;
+  Ok(ms)
+}
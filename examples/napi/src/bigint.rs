@@ -32,3 +32,11 @@ pub fn bigint_from_i64() -> BigInt {
 pub fn bigint_from_i128() -> BigInt {
   BigInt::from(-100i128)
 }
+
+#[napi]
+pub fn count_distinct_bigints(values: Vec<BigInt>) -> u32 {
+  values
+    .into_iter()
+    .collect::<std::collections::HashSet<_>>()
+    .len() as u32
+}
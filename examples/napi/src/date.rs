@@ -90,3 +90,13 @@ pub fn chrono_date_fixture_return2() -> chrono::DateTime<FixedOffset> {
     .single()
     .unwrap()
 }
+
+#[napi]
+pub fn time_offset_date_time_to_millis(input: time::OffsetDateTime) -> i64 {
+  (input.unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+#[napi]
+pub fn time_offset_date_time_add_1_minute(input: time::OffsetDateTime) -> time::OffsetDateTime {
+  input + time::Duration::minutes(1)
+}
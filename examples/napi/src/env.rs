@@ -1,16 +1,271 @@
-use napi::bindgen_prelude::*;
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use napi::{bindgen_prelude::*, CleanupPhase, EnvLocal, ErrorHandler, Feature, ScriptOptions};
 
 #[napi]
 pub fn run_script(env: &Env, script: String) -> Result<Unknown> {
   env.run_script(script)
 }
 
+#[napi]
+pub fn run_script_with_filename(
+  env: &Env,
+  script: String,
+  filename: String,
+  line_offset: u32,
+) -> Result<Unknown<'_>> {
+  env.run_script_with_options(
+    script,
+    ScriptOptions {
+      filename: Some(&filename),
+      line_offset,
+    },
+  )
+}
+
+#[napi]
+pub fn run_script_as_u32(env: &Env, script: String) -> Result<u32> {
+  env.run_script_as(script, ScriptOptions::default())
+}
+
 #[napi]
 pub fn get_module_file_name(env: Env) -> Result<String> {
   env.get_module_file_name()
 }
 
+#[napi]
+pub fn get_module_dir(env: Env) -> Result<String> {
+  Ok(env.module_dir()?.to_string_lossy().into_owned())
+}
+
 #[napi]
 pub fn throw_syntax_error(env: Env, error: String, code: Option<String>) {
   env.throw_syntax_error(error, code);
 }
+
+#[napi]
+pub fn get_registered_export_kind(js_mod: Option<String>, name: String) -> Option<String> {
+  registered_exports()
+    .into_iter()
+    .find(|export| export.js_mod == js_mod.as_deref() && export.name == name)
+    .map(|export| format!("{:?}", export.kind))
+}
+
+#[napi]
+pub fn get_registered_export_signature(js_mod: Option<String>, name: String) -> Option<String> {
+  registered_exports()
+    .into_iter()
+    .find(|export| export.js_mod == js_mod.as_deref() && export.name == name)
+    .and_then(|export| export.signature)
+    .map(|signature| signature.to_owned())
+}
+
+/// Registered as a `u32`, fails to register if the `NAPI_RS_FAIL_STRICT_REGISTER` env var is set,
+/// which makes the whole module fail to load under the `strict-register` feature.
+pub struct StrictRegisterProbe;
+
+impl ToNapiValue for StrictRegisterProbe {
+  unsafe fn to_napi_value(env: sys::napi_env, _val: Self) -> Result<sys::napi_value> {
+    if std::env::var_os("NAPI_RS_FAIL_STRICT_REGISTER").is_some() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "StrictRegisterProbe is asked to fail",
+      ));
+    }
+    unsafe { u32::to_napi_value(env, 1) }
+  }
+}
+
+#[napi(skip_typescript)]
+pub const STRICT_REGISTER_PROBE: StrictRegisterProbe = StrictRegisterProbe;
+
+#[napi]
+pub fn set_module_export(
+  env: &Env,
+  js_mod: Option<String>,
+  name: String,
+  value: Unknown,
+) -> Result<()> {
+  env.set_module_export(js_mod.as_deref(), &name, value)
+}
+
+#[napi]
+pub fn remove_module_export(env: &Env, js_mod: Option<String>, name: String) -> Result<bool> {
+  env.remove_module_export(js_mod.as_deref(), &name)
+}
+
+#[napi]
+pub fn create_object_with_property_keys(env: &Env) -> Result<Object<'_>> {
+  let mut obj = Object::new(env)?;
+  obj.set_property(
+    env.create_property_key("utf8")?,
+    env.create_external_string_latin1(b"latin1 value")?,
+  )?;
+  obj.set_property(
+    env.create_property_key_utf16(&[0x75, 0x74, 0x66, 0x31, 0x36])?,
+    env.create_external_string_utf16(&[0x76, 0x61, 0x6c, 0x75, 0x65])?,
+  )?;
+  obj.set_property(
+    env.create_property_key_latin1(b"caf\xe9")?,
+    env.create_string("latin1 key")?,
+  )?;
+  Ok(obj)
+}
+
+#[napi]
+pub fn strict_equals(a: Unknown, b: Unknown) -> Result<bool> {
+  a.strict_equals(b)
+}
+
+#[napi]
+pub fn same_value(a: Unknown, b: Unknown) -> Result<bool> {
+  a.same_value(b)
+}
+
+struct InstanceCallCount(u32);
+
+struct LastInstanceCaller(String);
+
+#[napi]
+pub fn record_instance_call(env: &Env, caller: String) -> Result<u32> {
+  let count = match env.with_instance(|count: &mut InstanceCallCount| {
+    count.0 += 1;
+    count.0
+  })? {
+    Some(count) => count,
+    None => {
+      env.set_instance(InstanceCallCount(1))?;
+      1
+    }
+  };
+  env.set_instance(LastInstanceCaller(caller))?;
+  Ok(count)
+}
+
+#[napi]
+pub fn get_last_instance_caller(env: &Env) -> Result<Option<String>> {
+  env.with_instance(|caller: &mut LastInstanceCaller| caller.0.clone())
+}
+
+#[napi]
+pub fn replace_borrowed_instance(env: &Env) -> Result<()> {
+  env
+    .with_instance(|_count: &mut InstanceCallCount| env.set_instance(InstanceCallCount(0)))?
+    .transpose()?;
+  Ok(())
+}
+
+static ENV_LOCAL_CALLS: EnvLocal<Cell<u32>> = EnvLocal::new(|_env| Ok(Cell::new(0)));
+
+#[napi]
+pub fn count_env_local_calls(env: &Env) -> Result<u32> {
+  let calls = ENV_LOCAL_CALLS.get(env)?;
+  calls.set(calls.get() + 1);
+  Ok(calls.get())
+}
+
+#[napi]
+pub fn reset_env_local_calls(env: &Env) -> Result<Option<u32>> {
+  Ok(ENV_LOCAL_CALLS.take(env)?.map(|calls| calls.get()))
+}
+
+static PHASED_CLEANUP_ORDER: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+#[napi]
+pub fn register_phased_cleanup_hooks(env: &Env) -> Result<()> {
+  for (phase, name) in [
+    (CleanupPhase::Last, "last"),
+    (CleanupPhase::Default, "default-1"),
+    (CleanupPhase::BeforeTsfnTeardown, "before-tsfn"),
+    (CleanupPhase::Default, "default-2"),
+  ] {
+    env.add_phased_cleanup_hook(phase, name, |name| {
+      PHASED_CLEANUP_ORDER.lock().unwrap().push(name);
+    })?;
+  }
+  Ok(())
+}
+
+#[napi]
+pub fn take_phased_cleanup_order() -> Vec<&'static str> {
+  std::mem::take(&mut *PHASED_CLEANUP_ORDER.lock().unwrap())
+}
+
+static ASYNC_CLEANUP_EVENTS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+#[napi]
+pub fn register_async_cleanup_hooks(env: &Env) -> Result<()> {
+  // removed before the env is torn down, so it never runs
+  let removed = env.add_async_cleanup_hook_with_handle(|handle| {
+    ASYNC_CLEANUP_EVENTS.lock().unwrap().push("removed");
+    handle.done();
+  })?;
+  drop(removed);
+  env
+    .add_async_cleanup_hook_with_handle(|handle| {
+      ASYNC_CLEANUP_EVENTS.lock().unwrap().push("started");
+      // the teardown waits until the handle is completed on the other thread
+      std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        ASYNC_CLEANUP_EVENTS.lock().unwrap().push("completed");
+        handle.done();
+      });
+    })?
+    .forget();
+  Ok(())
+}
+
+#[napi]
+pub fn take_async_cleanup_events() -> Vec<&'static str> {
+  std::mem::take(&mut *ASYNC_CLEANUP_EVENTS.lock().unwrap())
+}
+
+#[napi(object)]
+pub struct RuntimeInfo {
+  pub napi_version: u32,
+  pub node_version: String,
+  pub supports_external_strings: bool,
+}
+
+#[napi]
+pub fn get_runtime_info(env: &Env) -> Result<RuntimeInfo> {
+  Ok(RuntimeInfo {
+    napi_version: env.napi_version()?,
+    node_version: env.node_version()?.to_string(),
+    supports_external_strings: env.runtime_supports(Feature::ExternalString)?,
+  })
+}
+
+/// Parse `json` by `JSON.parse` and return its `structuredClone`.
+#[napi]
+pub fn parse_and_clone_json(env: &Env, json: String) -> Result<Unknown<'_>> {
+  let global = env.global_this()?;
+  let parsed: Unknown = global.json()?.parse(&json)?;
+  global.structured_clone(parsed)
+}
+
+#[napi]
+pub fn queue_native_microtask(env: &Env, callback: Function<(), Unknown>) -> Result<()> {
+  env.global_this()?.queue_microtask(callback)
+}
+
+#[napi]
+pub fn schedule_native_tasks(env: &Env, callback: Function<String, ()>) -> Result<()> {
+  let callback = std::rc::Rc::new(callback.create_ref()?);
+  env.set_immediate({
+    let callback = callback.clone();
+    move |env| callback.borrow_back(&env)?.call("immediate".to_owned())
+  })?;
+  env.queue_microtask(move |env| callback.borrow_back(&env)?.call("microtask".to_owned()))
+}
+
+#[napi]
+pub fn set_background_error_handler(env: &Env, on_error: Function<Error, ()>) -> Result<()> {
+  env.set_error_sink(ErrorHandler::new(&on_error)?)
+}
+
+#[napi]
+pub fn get_process_platform(env: &Env) -> Result<String> {
+  env.global_this()?.process()?.platform()
+}
@@ -78,3 +78,43 @@ pub fn extends_javascript_error(env: Env, error_class: Function<String>) -> Resu
   env.throw(error_object)?;
   Ok(())
 }
+
+#[napi(object)]
+pub struct LookupFailure {
+  pub code: String,
+  pub key: String,
+  pub retryable: bool,
+}
+
+pub enum LookupError {
+  NotFound(String),
+  Busy(String),
+}
+
+impl From<LookupError> for Error {
+  fn from(err: LookupError) -> Self {
+    let (code, key, retryable) = match err {
+      LookupError::NotFound(key) => ("NOT_FOUND", key, false),
+      LookupError::Busy(key) => ("BUSY", key, true),
+    };
+    Error::from_reason(format!("Lookup {} failed: {}", key, code)).with_value(LookupFailure {
+      code: code.to_owned(),
+      key,
+      retryable,
+    })
+  }
+}
+
+#[napi]
+pub async fn lookup_with_structured_error(key: String) -> std::result::Result<u32, LookupError> {
+  match key.as_str() {
+    "found" => Ok(42),
+    "busy" => Err(LookupError::Busy(key)),
+    _ => Err(LookupError::NotFound(key)),
+  }
+}
+
+#[napi]
+pub fn throw_structured_error(key: String) -> Result<()> {
+  Err(LookupError::NotFound(key).into())
+}
@@ -36,3 +36,49 @@ pub fn mutate_optional_external(external: Option<&mut External<u32>>, new_val: u
     **external = new_val;
   }
 }
+
+#[napi]
+pub struct ObjectMemo {
+  cache: JsWeakCache<u32>,
+  computed: u32,
+}
+
+#[napi]
+impl ObjectMemo {
+  #[napi(constructor)]
+  pub fn new(env: &Env) -> Result<Self> {
+    Ok(Self {
+      cache: JsWeakCache::new(env)?,
+      computed: 0,
+    })
+  }
+
+  #[napi]
+  pub fn compute(&mut self, env: &Env, key: Object) -> Result<u32> {
+    let computed = &mut self.computed;
+    self
+      .cache
+      .get_or_insert_with(env, &key, || {
+        *computed += 1;
+        Ok(*computed)
+      })
+      .copied()
+  }
+
+  #[napi]
+  pub fn forget(&mut self, env: &Env, key: Object) -> Result<bool> {
+    self.cache.remove(env, &key)
+  }
+}
+
+static COUNTER_TAG: TypeTag = TypeTag::new(0x8c6b_4f0e_12d7_4a39, 0xb1e4_57a2_c09f_3d6e);
+
+#[napi]
+pub fn create_tagged_external<'env>(env: &'env Env, size: u32) -> Result<Unknown<'env>> {
+  External::new_tagged(env, size, &COUNTER_TAG)
+}
+
+#[napi]
+pub fn get_tagged_external(external: Unknown) -> Result<u32> {
+  Ok(**External::<u32>::downcast_tagged(&external, &COUNTER_TAG)?)
+}
@@ -34,3 +34,15 @@ fn override_whole_function_type(operation: String, a: i32, b: i32) -> i32 {
     _ => panic!("Invalid operation"),
   }
 }
+
+/// Sum of two numbers.
+#[napi(since = "1.0", deprecated = "use `sumV2` instead", deprecation_warning)]
+fn sum_v1(a: u32, b: u32) -> u32 {
+  a + b
+}
+
+/// Sum of two numbers.
+#[napi(since = "2.0")]
+fn sum_v2(a: u32, b: u32) -> u32 {
+  a + b
+}
@@ -1,7 +1,9 @@
 #![allow(deprecated)]
 
 use napi::{
-  bindgen_prelude::{ClassInstance, FnArgs, Function, FunctionRef, PromiseRaw},
+  bindgen_prelude::{
+    ClassInstance, FnArgs, FromNapiValue, Function, FunctionRef, PromiseRaw, ToNapiValue,
+  },
   threadsafe_function::ThreadsafeFunctionCallMode,
   Env, Error, Result, Status,
 };
@@ -140,3 +142,20 @@ pub fn build_threadsafe_function_from_function_callee_handle(
 
   Ok(())
 }
+
+#[napi]
+pub fn wrap_with_hooks<'env>(
+  cb: Function<'env, FnArgs<(u32, u32)>, u32>,
+) -> Result<Function<'env, FnArgs<(u32, u32)>, u32>> {
+  cb.wrap_with(
+    |ctx| {
+      let first: u32 = ctx.get(0)?;
+      ctx.set(0, first * 2)?;
+      Ok(first)
+    },
+    |ctx, first, ret| {
+      let ret = u32::from_unknown(ret)?;
+      (ret + first).into_unknown(ctx.env)
+    },
+  )
+}
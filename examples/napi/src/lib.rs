@@ -74,7 +74,9 @@ mod fn_ts_override;
 mod function;
 mod generator;
 mod js_mod;
+mod loop_watchdog;
 mod map;
+mod named_thread;
 mod nullable;
 mod number;
 mod object;
@@ -84,6 +86,7 @@ mod scope;
 mod serde;
 mod set;
 mod shared;
+mod slice_borrows;
 mod stream;
 mod string;
 mod symbol;
@@ -92,3 +95,4 @@ mod threadsafe_function;
 mod transparent;
 mod r#type;
 mod typed_array;
+mod uv;
@@ -0,0 +1,42 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use napi::bindgen_prelude::*;
+
+static LOOP_WATCHDOG_REPORTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+#[napi]
+pub fn start_loop_watchdog(budget_ms: u32) {
+  set_loop_watchdog_budget(Duration::from_millis(budget_ms as u64));
+  set_loop_watchdog_callback(|report| {
+    let name = match report.source {
+      LoopWatchdogSource::Export(name) => name.to_owned(),
+      LoopWatchdogSource::ThreadsafeFunction => "ThreadsafeFunction".to_owned(),
+    };
+    LOOP_WATCHDOG_REPORTS.lock().unwrap().push(name);
+  });
+}
+
+/// Like `startLoopWatchdog`, but the callback clears itself after the first report.
+#[napi]
+pub fn start_loop_watchdog_once(budget_ms: u32) {
+  set_loop_watchdog_budget(Duration::from_millis(budget_ms as u64));
+  set_loop_watchdog_callback(|report| {
+    if let LoopWatchdogSource::Export(name) = report.source {
+      LOOP_WATCHDOG_REPORTS.lock().unwrap().push(name.to_owned());
+    }
+    clear_loop_watchdog_callback();
+  });
+}
+
+#[napi]
+pub fn stop_loop_watchdog() -> Vec<String> {
+  clear_loop_watchdog_callback();
+  set_loop_watchdog_budget(DEFAULT_LOOP_WATCHDOG_BUDGET);
+  std::mem::take(&mut *LOOP_WATCHDOG_REPORTS.lock().unwrap())
+}
+
+#[napi]
+pub fn block_event_loop(ms: u32) {
+  std::thread::sleep(Duration::from_millis(ms as u64));
+}
@@ -0,0 +1,29 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use napi::bindgen_prelude::*;
+
+thread_local! {
+  // not thread safe, only accessed from the `kv` thread
+  static KV_STORE: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+#[napi(thread = "kv")]
+pub fn kv_set(key: String, value: String) -> Option<String> {
+  KV_STORE.with_borrow_mut(|store| store.insert(key, value))
+}
+
+#[napi(thread = "kv")]
+pub fn kv_get(key: String) -> Result<String> {
+  KV_STORE.with_borrow(|store| {
+    store
+      .get(&key)
+      .cloned()
+      .ok_or_else(|| Error::new(Status::InvalidArg, format!("Key {} not found", key)))
+  })
+}
+
+#[napi(thread = "kv")]
+pub fn kv_thread_name() -> Option<String> {
+  std::thread::current().name().map(|name| name.to_owned())
+}
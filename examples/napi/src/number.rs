@@ -10,3 +10,16 @@ fn fibonacci(n: u32) -> u32 {
     _ => fibonacci(n - 1) + fibonacci(n - 2),
   }
 }
+
+#[napi(multiversion(avx2, sse4_1, neon))]
+fn sum_u32_multiversion(input: Vec<u32>) -> u32 {
+  input.iter().fold(0u32, |acc, v| acc.wrapping_add(*v))
+}
+
+#[napi]
+fn count_distinct_numbers(values: Vec<napi::JsNumber>) -> u32 {
+  values
+    .into_iter()
+    .collect::<std::collections::HashSet<_>>()
+    .len() as u32
+}
@@ -183,3 +183,78 @@ pub fn get_null_byte_property(obj: Object) -> Result<Option<String>> {
 pub fn set_null_byte_property(mut obj: Object) -> Result<()> {
   obj.set("\0virtual", "test")
 }
+
+#[napi(object, cache_keys)]
+pub struct CachedKeysObject {
+  pub id: u32,
+  pub label: String,
+  pub note: Option<String>,
+  pub children: Vec<CachedKeysObject>,
+}
+
+#[napi]
+pub fn create_cached_keys_objects(count: u32) -> Vec<CachedKeysObject> {
+  (0..count)
+    .map(|id| CachedKeysObject {
+      id,
+      label: format!("item {id}"),
+      note: (id % 2 == 0).then(|| "even".to_owned()),
+      children: vec![CachedKeysObject {
+        id: id + 100,
+        label: "child".to_owned(),
+        note: None,
+        children: vec![],
+      }],
+    })
+    .collect()
+}
+
+#[napi(object)]
+pub struct RpcMessage {
+  pub id: u32,
+  pub method: String,
+  pub params: Vec<String>,
+  pub trace_id: Option<String>,
+}
+
+static RPC_MESSAGE_SCHEMA: std::sync::LazyLock<Schema<RpcMessage>> =
+  std::sync::LazyLock::new(Schema::compile);
+
+/// Read the messages with a compiled `Schema`, and write them back with the method upper cased.
+#[napi]
+pub fn echo_rpc_messages<'env>(env: &'env Env, messages: Vec<Object>) -> Result<Vec<Object<'env>>> {
+  messages
+    .iter()
+    .map(|message| {
+      let mut message = RPC_MESSAGE_SCHEMA.read(message)?;
+      message.method = message.method.to_uppercase();
+      RPC_MESSAGE_SCHEMA.write(env, message)
+    })
+    .collect()
+}
+
+#[napi]
+pub fn rpc_message_fields() -> Vec<&'static str> {
+  RPC_MESSAGE_SCHEMA.field_names().collect()
+}
+
+/// Sum the `x` and `y` of the points, the keys are interned once per env.
+#[napi]
+pub fn sum_point_coordinates(env: &Env, points: Vec<Object>) -> Result<f64> {
+  let x = env.intern_key("x")?;
+  let y = env.intern_key("y")?;
+  points.iter().try_fold(0.0, |sum, point| {
+    Ok(
+      sum
+        + point.get_key::<f64>(&x)?.unwrap_or_default()
+        + point.get_key::<f64>(&y)?.unwrap_or_default(),
+    )
+  })
+}
+
+#[napi]
+pub fn move_point(env: &Env, mut point: Object, dx: f64) -> Result<()> {
+  let x = env.intern_key("x")?;
+  let moved = point.get_key::<f64>(&x)?.unwrap_or_default() + dx;
+  point.set_key(&x, moved)
+}
@@ -18,7 +18,7 @@ pub fn call_catch_on_promise(input: PromiseRaw<'_, u32>) -> Result<PromiseRaw<'_
 
 #[napi]
 pub fn call_finally_on_promise(
-  mut input: PromiseRaw<u32>,
+  input: PromiseRaw<u32>,
   on_finally: FunctionRef<(), ()>,
 ) -> Result<PromiseRaw<u32>> {
   input.finally(move |env| {
@@ -27,6 +27,34 @@ pub fn call_finally_on_promise(
   })
 }
 
+#[napi]
+pub fn call_then_or_catch_on_promise(input: PromiseRaw<'_, u32>) -> Result<PromiseRaw<'_, String>> {
+  input.then_or_catch(
+    |v: CallbackContext<u32>| Ok(format!("fulfilled: {}", v.value)),
+    |e: CallbackContext<String>| Ok(format!("rejected: {}", e.value)),
+  )
+}
+
+#[napi]
+pub fn call_then_typed_on_promise<'env>(
+  input: PromiseRaw<'env, Unknown<'env>>,
+) -> Result<PromiseRaw<'env, u32>> {
+  input.then_typed(|v: CallbackContext<u32>| Ok(v.value * 2))
+}
+
+#[napi]
+pub fn call_catch_typed_on_promise(input: PromiseRaw<'_, u32>) -> Result<PromiseRaw<'_, String>> {
+  input.catch_typed(|e| Ok(format!("caught {}", e.value.reason)))
+}
+
+#[napi]
+pub fn sum_all_promises<'env>(
+  env: &'env Env,
+  promises: Vec<PromiseRaw<'_, u32>>,
+) -> Result<PromiseRaw<'env, u32>> {
+  PromiseRaw::all(env, promises)?.then(|values| Ok(values.value.iter().sum::<u32>()))
+}
+
 #[napi]
 pub fn esm_resolve<'env>(
   _: &'env Env,
@@ -160,3 +160,40 @@ impl CSSStyleSheet {
     })
   }
 }
+
+#[napi(object)]
+pub struct ReferenceReleaseStats {
+  pub released_in_place: u32,
+  pub queued: u32,
+  pub released_from_queue: u32,
+}
+
+#[napi]
+pub fn get_reference_release_stats() -> ReferenceReleaseStats {
+  let metrics = reference_release_metrics();
+  ReferenceReleaseStats {
+    released_in_place: metrics.released_in_place as u32,
+    queued: metrics.queued as u32,
+    released_from_queue: metrics.released_from_queue as u32,
+  }
+}
+
+#[napi]
+impl JsRepo {
+  #[napi]
+  pub fn drop_on_other_thread(&self, reference: Reference<JsRepo>, env: Env) -> Result<()> {
+    let guard = reference.into_send_guard(&env)?;
+    std::thread::spawn(move || drop(guard))
+      .join()
+      .map_err(|_| Error::from_reason("Drop thread panicked"))
+  }
+
+  #[napi]
+  pub fn dir_through_send_guard(&self, reference: Reference<JsRepo>, env: Env) -> Result<String> {
+    let guard = reference.into_send_guard(&env)?;
+    let reference = guard.into_reference(&env)?;
+    let dir = reference.inner.dir.clone();
+    drop(reference.into_send_guard(&env)?);
+    Ok(dir)
+  }
+}
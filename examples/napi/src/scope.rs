@@ -60,3 +60,26 @@ pub fn shorter_escapable_scope<'env>(
   }
   Ok(longest_string)
 }
+
+#[napi]
+pub fn count_chars_in_scopes(env: &Env, arr: Array) -> Result<u32> {
+  let mut total = 0;
+  for i in 0..arr.len() {
+    total += env.with_handle_scope(|_| {
+      let value: JsString = arr.get_element(i)?;
+      Ok(value.into_utf8()?.as_str()?.chars().count() as u32)
+    })?;
+  }
+  Ok(total)
+}
+
+#[napi]
+pub fn join_in_escapable_scope<'env>(env: &'env Env, parts: Vec<String>) -> Result<JsString<'env>> {
+  env.with_escapable_handle_scope(|env| {
+    let mut joined = env.create_string("")?;
+    for part in &parts {
+      joined = joined.concat(&[env.create_string(part)?])?;
+    }
+    Ok(joined.to_unknown())
+  })
+}
@@ -0,0 +1,33 @@
+use std::sync::Mutex;
+
+use napi::bindgen_prelude::*;
+
+static SLICE_BORROW_REPORTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+#[napi]
+pub fn start_slice_borrow_reports() {
+  set_slice_borrow_callback(|report| {
+    SLICE_BORROW_REPORTS
+      .lock()
+      .unwrap()
+      .push(format!("{}: {}", report.export, report.slice_type));
+  });
+}
+
+#[napi]
+pub fn stop_slice_borrow_reports() -> Vec<String> {
+  clear_slice_borrow_callback();
+  std::mem::take(&mut *SLICE_BORROW_REPORTS.lock().unwrap())
+}
+
+#[napi]
+pub fn detach_while_borrowed(buf: ArrayBuffer, view: Uint8ArraySlice) -> Result<u32> {
+  buf.detach()?;
+  Ok(view.len() as u32)
+}
+
+#[allow(unused_variables)]
+#[napi]
+pub fn call_with_borrowed_slice(view: Uint8ArraySlice, callback: Function<(), ()>) -> Result<()> {
+  callback.call(())
+}
@@ -82,3 +82,118 @@ pub fn create_readable_stream_from_class<'env>(
     ReceiverStream::new(rx),
   )
 }
+
+#[napi(ts_args_type = "input: import('node:stream').Readable")]
+pub fn read_node_stream(env: &Env, input: Object) -> Result<AsyncBlock<Buffer>> {
+  use tokio::io::AsyncReadExt;
+
+  // a small high water mark to exercise the pausing of the stream
+  let mut reader = napi::streams::NodeReadable::with_high_water_mark(&input, 8)?;
+  AsyncBlockBuilder::new(async move {
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output).await?;
+    Ok(Buffer::from(output))
+  })
+  .build(env)
+}
+
+#[napi(
+  ts_args_type = "input: import('node:stream').Readable, output: import('node:stream').Writable"
+)]
+pub fn uppercase_node_stream(env: &Env, input: Object, output: Object) -> Result<AsyncBlock<u32>> {
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+  let mut reader = napi::streams::NodeReadable::new(&input)?;
+  let mut writer = napi::streams::NodeWritable::with_high_water_mark(&output, 4)?;
+  AsyncBlockBuilder::new(async move {
+    let mut buf = [0; 3];
+    let mut total = 0;
+    loop {
+      let n = reader.read(&mut buf).await?;
+      if n == 0 {
+        break;
+      }
+      buf[..n].make_ascii_uppercase();
+      writer.write_all(&buf[..n]).await?;
+      total += n as u32;
+    }
+    writer.shutdown().await?;
+    Ok(total)
+  })
+  .build(env)
+}
+
+static BYTE_CHANNEL_SENT: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+#[napi]
+pub fn produce_byte_chunks(count: u32, high_water_mark: u32) -> napi::byte_channel::ByteReceiver {
+  let (sender, receiver) = napi::byte_channel::channel(high_water_mark as usize);
+  BYTE_CHANNEL_SENT.store(0, std::sync::atomic::Ordering::SeqCst);
+  napi::bindgen_prelude::spawn(async move {
+    for i in 0..count {
+      if sender.send(vec![i as u8; 4]).await.is_err() {
+        break;
+      }
+      BYTE_CHANNEL_SENT.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+  });
+  receiver
+}
+
+#[napi]
+pub fn byte_chunks_sent() -> u32 {
+  BYTE_CHANNEL_SENT.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+#[napi]
+pub fn produce_failing_byte_chunks() -> napi::byte_channel::ByteReceiver {
+  let (sender, receiver) = napi::byte_channel::channel(16);
+  napi::bindgen_prelude::spawn(async move {
+    let _ = sender.send(b"partial".to_vec()).await;
+    sender.abort("producer failed");
+  });
+  receiver
+}
+
+#[napi]
+pub fn for_each_byte_chunk(
+  env: &Env,
+  count: u32,
+  callback: Function<Buffer, ()>,
+) -> Result<AsyncBlock<()>> {
+  produce_byte_chunks(count, 8).for_each(env, callback)
+}
+
+#[napi]
+pub fn channel_numbers_to_js(count: u32, callback: Function<u32, ()>) -> Result<AsyncBlock<()>> {
+  let (sender, receiver) = tokio::sync::mpsc::channel(4);
+  napi::bindgen_prelude::spawn(async move {
+    for i in 0..count {
+      if sender.send(i).await.is_err() {
+        break;
+      }
+    }
+  });
+  napi::channel_to_js(receiver, callback, napi::ChannelToJsOptions::default())
+}
+
+#[napi]
+pub fn channel_batches_to_js(
+  count: u32,
+  callback: Function<Vec<u32>, ()>,
+) -> Result<AsyncBlock<()>> {
+  let (sender, receiver) = tokio::sync::mpsc::channel(count.max(1) as usize);
+  for i in 0..count {
+    sender
+      .try_send(i)
+      .map_err(|err| Error::new(Status::GenericFailure, err.to_string()))?;
+  }
+  napi::channel_to_js_batched(
+    receiver,
+    callback,
+    napi::ChannelToJsOptions {
+      max_batch_size: 4,
+      ..Default::default()
+    },
+  )
+}
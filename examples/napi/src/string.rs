@@ -1,4 +1,6 @@
-use napi::bindgen_prelude::*;
+use std::{borrow::Cow, ffi::OsString, fmt::Write, path::PathBuf};
+
+use napi::{bindgen_prelude::*, JsString, JsStringBuilder};
 
 #[napi]
 fn contains(source: String, target: String) -> bool {
@@ -32,3 +34,162 @@ pub fn return_c_string() -> RawCString {
   let mock_c_string_ptr = mock_c_string.as_ptr().cast();
   RawCString::new(mock_c_string_ptr, NAPI_AUTO_LENGTH)
 }
+
+#[napi]
+pub fn count_distinct_strings(values: Vec<JsString>) -> Result<u32> {
+  let values = values
+    .into_iter()
+    .map(|value| value.into_utf16())
+    .collect::<Result<std::collections::HashSet<_>>>()?;
+  Ok(values.len() as u32)
+}
+
+#[napi]
+pub fn format_currency(env: &Env, value: f64, currency: String, locale: String) -> Result<String> {
+  env
+    .number_format(&NumberFormatOptions {
+      locales: vec![locale],
+      style: Some("currency".to_owned()),
+      currency: Some(currency),
+      ..Default::default()
+    })?
+    .format(value)
+}
+
+#[napi]
+pub fn sort_by_locale(env: &Env, mut values: Vec<String>, locale: String) -> Result<Vec<String>> {
+  env
+    .collator(&CollatorOptions {
+      locales: vec![locale],
+      numeric: Some(true),
+      ..Default::default()
+    })?
+    .sort(&mut values)?;
+  Ok(values)
+}
+
+#[napi]
+pub fn repeat_external_utf16(env: &Env, text: String, times: u32) -> Result<JsString<'_>> {
+  let chars = text.repeat(times as usize).encode_utf16().collect();
+  JsString::from_external_utf16(env, chars)
+}
+
+#[napi]
+pub fn repeat_external_latin1(env: &Env, byte: u8, times: u32) -> Result<JsString<'_>> {
+  JsString::from_external_latin1(env, vec![byte; times as usize])
+}
+
+#[napi]
+pub fn status_text(code: u16) -> Latin1Str<'static> {
+  Latin1Str::new(match code {
+    200 => b"OK",
+    404 => b"Not Found",
+    _ => b"Unknown",
+  })
+}
+
+#[napi]
+pub fn latin1_header_line(name: Latin1String, value: Latin1String) -> Latin1String {
+  let mut line = name.into_bytes();
+  line.extend_from_slice(b": ");
+  line.extend_from_slice(&value);
+  Latin1String::from_bytes(line)
+}
+
+#[napi]
+pub fn latin1_from_string(value: String) -> (Latin1String, String) {
+  let latin1 = Latin1String::from(value);
+  let text = latin1.to_string();
+  (latin1, text)
+}
+
+#[napi]
+pub fn join_path(base: PathBuf, name: OsString) -> PathBuf {
+  base.join(name)
+}
+
+#[napi]
+pub fn path_file_name(path: PathBuf) -> Option<OsString> {
+  path.file_name().map(OsString::from)
+}
+
+#[napi]
+pub fn count_words(text: JsStringRef) -> u32 {
+  text.split_whitespace().count() as u32
+}
+
+/// Replace the `\r\n` with `\n`, the text without `\r` is returned without copying in Rust.
+#[napi]
+pub fn normalize_newlines(text: Cow<'_, str>) -> Cow<'_, str> {
+  if text.contains('\r') {
+    Cow::Owned(text.replace("\r\n", "\n"))
+  } else {
+    text
+  }
+}
+
+#[napi]
+pub fn numbered_lines(prefix: String, count: u32) -> Result<JsStringBuilder> {
+  let mut lines = JsStringBuilder::with_capacity((prefix.len() + 8) * count as usize);
+  for i in 0..count {
+    writeln!(lines, "{prefix}{i}").map_err(|err| Error::from_reason(err.to_string()))?;
+  }
+  Ok(lines)
+}
+
+#[napi]
+pub fn concat_strings<'env>(first: JsString<'env>, rest: Vec<JsString>) -> Result<JsString<'env>> {
+  first.concat(&rest)
+}
+
+#[napi]
+pub fn slice_chars<'env>(
+  text: JsString<'env>,
+  start: u32,
+  end: Option<u32>,
+) -> Result<JsString<'env>> {
+  match end {
+    Some(end) => text.substring_by_chars(start as usize..end as usize),
+    None => text.substring_by_chars(start as usize..),
+  }
+}
+
+#[napi]
+pub fn string_code_points(text: JsString) -> Result<Vec<u32>> {
+  Ok(text.code_points()?.collect())
+}
+
+/// The JavaScript index of each character which isn't ASCII.
+#[napi]
+pub fn non_ascii_char_offsets(text: JsString) -> Result<Vec<u32>> {
+  Ok(
+    text
+      .char_indices()?
+      .filter(|(_, c)| !c.is_ascii())
+      .map(|(offset, _)| offset as u32)
+      .collect(),
+  )
+}
+
+/// Return `text` as it is if it's shorter than `max_len`, without copying it out of the engine.
+#[napi]
+pub fn limit_utf16_len(
+  text: Utf16Slice<'_>,
+  max_len: u32,
+) -> Result<Either<Utf16Slice<'_>, Utf16String>> {
+  if text.len()? <= max_len as usize {
+    return Ok(Either::A(text));
+  }
+  Ok(Either::B(Utf16String::from_units(
+    text.as_slice()?[..max_len as usize].to_vec(),
+  )))
+}
+
+#[napi]
+pub fn count_unpaired_surrogates(text: Utf16Slice) -> Result<u32> {
+  Ok(
+    char::decode_utf16(text.as_slice()?.iter().copied())
+      .filter(|c| c.is_err())
+      .count() as u32,
+  )
+}
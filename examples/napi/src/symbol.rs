@@ -16,3 +16,29 @@ pub fn create_symbol() -> Symbol {
 pub fn create_symbol_for(desc: String) -> Symbol {
   Symbol::for_desc(desc)
 }
+
+/// Create an object tagged by `Symbol.toStringTag`, which is disposed by `Symbol.dispose` if the runtime supports it.
+#[napi]
+pub fn create_tagged_disposable(env: &Env, tag: String) -> Result<Object<'_>> {
+  let mut obj = Object::new(env)?;
+  obj.set_symbol_property(WellKnownSymbol::ToStringTag, tag)?;
+  obj.set_named_property("disposed", false)?;
+  if Symbol::well_known(env, WellKnownSymbol::Dispose).is_ok() {
+    let dispose = env.create_function_from_closure::<(), _, _>("dispose", |ctx| {
+      let mut this = ctx.this::<Object>()?;
+      this.set_named_property("disposed", true)
+    })?;
+    obj.set_symbol_property(WellKnownSymbol::Dispose, dispose)?;
+  }
+  Ok(obj)
+}
+
+#[napi]
+pub fn get_to_string_tag(obj: Object) -> Result<Option<String>> {
+  obj.get_symbol_property(WellKnownSymbol::ToStringTag)
+}
+
+#[napi]
+pub fn get_async_iterator_symbol(env: &Env) -> Result<JsSymbol<'_>> {
+  Symbol::well_known(env, WellKnownSymbol::AsyncIterator)
+}
@@ -1,6 +1,6 @@
 use std::thread::sleep;
 
-use napi::bindgen_prelude::*;
+use napi::{bindgen_prelude::*, ErrorHandler};
 
 struct DelaySum(u32, u32);
 
@@ -29,6 +29,36 @@ fn with_abort_controller(a: u32, b: u32, signal: AbortSignal) -> AsyncTask<Delay
   AsyncTask::with_signal(DelaySum(a, b), signal)
 }
 
+struct CountUntilAborted {
+  token: CancellationToken,
+  limit: u32,
+}
+
+#[napi]
+impl napi::Task for CountUntilAborted {
+  type Output = u32;
+  type JsValue = u32;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    for _ in 0..self.limit {
+      // the started task is stopped by the token
+      self.token.check()?;
+      sleep(std::time::Duration::from_millis(10));
+    }
+    Ok(self.limit)
+  }
+
+  fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+fn count_until_aborted(limit: u32, signal: AbortSignal) -> AsyncTask<CountUntilAborted> {
+  let token = signal.token();
+  AsyncTask::with_signal(CountUntilAborted { token, limit }, signal)
+}
+
 struct AsyncTaskVoidReturn {}
 
 #[napi]
@@ -93,3 +123,71 @@ impl Task for AsyncTaskReadFile {
 pub fn async_task_read_file(path: String) -> AsyncTask<AsyncTaskReadFile> {
   AsyncTask::new(AsyncTaskReadFile { path })
 }
+
+struct CountUntilInterrupted {
+  interrupt: Interrupt,
+}
+
+#[napi]
+impl napi::Task for CountUntilInterrupted {
+  type Output = u32;
+  type JsValue = u32;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let mut checks = 0;
+    // a runaway computation, it only stops at the check point once interrupted
+    while checks < 2000 {
+      self.interrupt.check()?;
+      checks += 1;
+      sleep(std::time::Duration::from_millis(5));
+    }
+    Ok(checks)
+  }
+
+  fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi(ts_return_type = "Promise<number> & { interrupt(): void }")]
+fn count_until_interrupted() -> AsyncTask<CountUntilInterrupted> {
+  let interrupt = Interrupt::new();
+  AsyncTask::new(CountUntilInterrupted {
+    interrupt: interrupt.clone(),
+  })
+  .with_interrupt(interrupt)
+}
+
+struct FailingCleanup {}
+
+#[napi]
+impl Task for FailingCleanup {
+  type JsValue = ();
+  type Output = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(())
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn finally(self, _env: Env) -> Result<()> {
+    Err(Error::new(
+      Status::GenericFailure,
+      "Failed to clean up the task".to_owned(),
+    ))
+  }
+}
+
+#[napi]
+fn async_task_failing_cleanup(on_error: Function<Error, ()>) -> Result<AsyncTask<FailingCleanup>> {
+  Ok(AsyncTask::new(FailingCleanup {}).with_error_sink(ErrorHandler::new(&on_error)?))
+}
+
+/// The error of `finally` is delivered to the handler of the env
+#[napi]
+fn async_task_failing_cleanup_in_env() -> AsyncTask<FailingCleanup> {
+  AsyncTask::new(FailingCleanup {})
+}
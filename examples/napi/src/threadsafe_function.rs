@@ -1,9 +1,17 @@
-use std::{sync::Arc, thread, time::Duration};
+use std::{
+  sync::{Arc, Mutex},
+  thread,
+  time::Duration,
+};
 
 use napi::{
   bindgen_prelude::*,
-  threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode, UnknownReturnValue},
-  Ref,
+  event_emitter::EventEmitter,
+  threadsafe_function::{
+    Keepalive, ThreadsafeFunction, ThreadsafeFunctionCallMode, ThreadsafeFunctionCallPriority,
+    ThreadsafeFunctionStats, UnknownReturnValue,
+  },
+  ErrorHandler, Ref,
 };
 
 use crate::class::Animal;
@@ -151,6 +159,197 @@ pub fn tsfn_async_call<'env>(
   })
 }
 
+#[napi(ts_return_type = "Promise<void>")]
+pub fn tsfn_bounded_producer<'env>(
+  env: &'env Env,
+  func: Function<u32, ()>,
+  count: u32,
+) -> napi::Result<PromiseRaw<'env, ()>> {
+  let tsfn = func
+    .build_threadsafe_function()
+    .max_queue_size::<2>()
+    .build()?;
+
+  env.spawn_future(async move {
+    for i in 0..count {
+      tsfn.call_async_bounded(i).await?;
+    }
+    Ok(())
+  })
+}
+
+#[napi]
+pub fn tsfn_blocking_call_on_js_thread(callback: Function<u32, ()>) -> Result<String> {
+  let tsfn = callback
+    .build_threadsafe_function()
+    .max_queue_size::<1>()
+    .build()?;
+  // fails with `WouldDeadlock` in debug builds
+  Ok(
+    tsfn
+      .call(0, ThreadsafeFunctionCallMode::Blocking)
+      .to_string(),
+  )
+}
+
+#[napi]
+pub fn tsfn_urgent_call(
+  callback: ThreadsafeFunction<String, (), String, Status, false>,
+  count: u32,
+) {
+  for n in 0..count {
+    callback.call(format!("bulk {n}"), ThreadsafeFunctionCallMode::NonBlocking);
+  }
+  // delivered before the bulk calls queued above
+  callback.call_with_priority(
+    "shutdown".to_owned(),
+    ThreadsafeFunctionCallMode::NonBlocking,
+    ThreadsafeFunctionCallPriority::Urgent,
+  );
+}
+
+#[napi]
+pub fn tsfn_batched_producer(
+  callback: Function<Vec<u32>, ()>,
+  count: u32,
+  max_batch_size: u32,
+) -> Result<()> {
+  let tsfn = callback
+    .build_threadsafe_function()
+    .build_batched(max_batch_size as usize, Duration::from_millis(5))?;
+  thread::spawn(move || {
+    for i in 0..count {
+      tsfn.call(i);
+    }
+  });
+  Ok(())
+}
+
+#[napi]
+pub fn tsfn_keepalive_while_active(callback: Function<u32, ()>, count: u32) -> Result<()> {
+  let tsfn = callback
+    .build_threadsafe_function()
+    .with_keepalive(Keepalive::WhileActive)
+    .build()?;
+  for i in 0..count {
+    tsfn.call(i, ThreadsafeFunctionCallMode::NonBlocking);
+  }
+  // the function is never dropped, it must not hold the process open once the calls are delivered
+  thread::spawn(move || loop {
+    thread::park();
+    let _ = &tsfn;
+  });
+  Ok(())
+}
+
+#[napi(object)]
+pub struct TsfnDrainReport {
+  pub delivered: u32,
+  pub dropped: u32,
+  pub timed_out: bool,
+}
+
+#[napi]
+pub fn tsfn_close_with_drain<'env>(
+  env: &'env Env,
+  callback: Function<u32, ()>,
+  count: u32,
+  timeout_ms: u32,
+) -> Result<PromiseRaw<'env, TsfnDrainReport>> {
+  let tsfn = callback.build_threadsafe_function().build()?;
+  for i in 0..count {
+    tsfn.call(i, ThreadsafeFunctionCallMode::NonBlocking);
+  }
+  // started before the queued calls can be delivered, so they are all reported
+  let drained = tsfn.close_with_drain(Duration::from_millis(timeout_ms as u64));
+  env.spawn_future(async move {
+    let report = drained.await;
+    Ok(TsfnDrainReport {
+      delivered: report.delivered as u32,
+      dropped: report.dropped as u32,
+      timed_out: report.timed_out,
+    })
+  })
+}
+
+#[napi(object)]
+pub struct TsfnQueueStats {
+  pub queue_depth: u32,
+  pub total_calls: u32,
+  pub dropped_calls: u32,
+  pub average_latency_ms: f64,
+}
+
+impl From<ThreadsafeFunctionStats> for TsfnQueueStats {
+  fn from(stats: ThreadsafeFunctionStats) -> Self {
+    Self {
+      queue_depth: stats.queue_depth as u32,
+      total_calls: stats.total_calls as u32,
+      dropped_calls: stats.dropped_calls as u32,
+      average_latency_ms: stats.average_latency.as_secs_f64() * 1000.0,
+    }
+  }
+}
+
+/// The queue holds 4 calls, and the high-water mark is 3.
+/// Resolves with the stats at the high-water mark, after the calls are queued, and after they are delivered.
+#[napi]
+pub fn tsfn_queue_stats<'env>(
+  env: &'env Env,
+  callback: Function<u32, ()>,
+  count: u32,
+) -> Result<PromiseRaw<'env, Vec<TsfnQueueStats>>> {
+  let high_water_marks = Arc::new(Mutex::new(Vec::new()));
+  let tsfn = callback
+    .build_threadsafe_function()
+    .max_queue_size::<4>()
+    .with_high_water_mark(3, {
+      let high_water_marks = high_water_marks.clone();
+      move |stats| high_water_marks.lock().unwrap().push(stats)
+    })
+    .with_latency_tracking()
+    .build()?;
+  // the calls are queued until the JavaScript thread is free
+  for i in 0..count {
+    tsfn.call(i, ThreadsafeFunctionCallMode::NonBlocking);
+  }
+  let queued = tsfn.stats();
+  env.spawn_future(async move {
+    while tsfn.stats().queue_depth > 0 {
+      tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    let mut stats = high_water_marks.lock().unwrap().clone();
+    stats.extend([queued, tsfn.stats()]);
+    Ok(stats.into_iter().map(TsfnQueueStats::from).collect())
+  })
+}
+
+#[napi]
+pub fn tsfn_report_errors_to_handler(
+  callback: Function<u32, ()>,
+  on_error: Function<Error, ()>,
+  count: u32,
+) -> Result<()> {
+  let tsfn = callback
+    .build_threadsafe_function()
+    .with_error_sink(ErrorHandler::new(&on_error)?)
+    .build_callback(|ctx| {
+      if ctx.value % 2 == 1 {
+        return Err(Error::new(
+          Status::GenericFailure,
+          format!("odd value {}", ctx.value),
+        ));
+      }
+      Ok(ctx.value)
+    })?;
+  thread::spawn(move || {
+    for i in 0..count {
+      tsfn.call(i, ThreadsafeFunctionCallMode::NonBlocking);
+    }
+  });
+  Ok(())
+}
+
 #[napi]
 pub fn accept_threadsafe_function(func: ThreadsafeFunction<u32>) {
   thread::spawn(move || {
@@ -177,6 +376,18 @@ pub fn accept_threadsafe_function_tuple_args(
   });
 }
 
+#[napi]
+pub fn accept_threadsafe_function_tuple(
+  func: ThreadsafeFunction<(u32, String), (), (u32, String), Status, false, true>,
+) {
+  thread::spawn(move || {
+    func.call(
+      (1, "NAPI-RS".into()),
+      ThreadsafeFunctionCallMode::NonBlocking,
+    );
+  });
+}
+
 #[napi]
 pub async fn tsfn_return_promise(func: ThreadsafeFunction<u32, Promise<u32>>) -> Result<u32> {
   let val = func.call_async(Ok(1)).await?.await?;
@@ -200,6 +411,26 @@ pub async fn tsfn_return_promise_timeout(
   }
 }
 
+#[napi]
+pub async fn tsfn_handle_in_tasks(func: ThreadsafeFunction<u32, u32>, tasks: u32) -> Result<u32> {
+  // the handles keep the function alive after it's dropped
+  let handle = func.handle();
+  drop(func);
+  let tasks = (0..tasks)
+    .map(|n| {
+      let handle = handle.clone();
+      tokio::spawn(async move { handle.call_async(Ok(n)).await })
+    })
+    .collect::<Vec<_>>();
+  let mut sum = 0;
+  for task in tasks {
+    sum += task
+      .await
+      .map_err(|err| Error::new(Status::GenericFailure, err.to_string()))??;
+  }
+  Ok(sum)
+}
+
 #[napi]
 pub fn call_async_with_unknown_return_value<'env>(
   env: &'env Env,
@@ -264,3 +495,55 @@ pub fn tsfn_in_either(pet: Pet) {
     });
   }
 }
+
+#[napi]
+pub struct Ticker {
+  events: EventEmitter,
+}
+
+#[napi]
+impl Ticker {
+  #[napi(constructor)]
+  pub fn new(env: &Env) -> Result<Self> {
+    Ok(Self {
+      events: EventEmitter::new(env)?,
+    })
+  }
+
+  #[napi(ts_args_type = "event: string, listener: (payload: any) => void")]
+  pub fn on(&self, event: String, listener: Function<Unknown, ()>) -> Result<()> {
+    self.events.on(&event, &listener)
+  }
+
+  #[napi(ts_args_type = "event: string, listener: (payload: any) => void")]
+  pub fn once(&self, event: String, listener: Function<Unknown, ()>) -> Result<()> {
+    self.events.once(&event, &listener)
+  }
+
+  #[napi(ts_args_type = "event: string, listener: (payload: any) => void")]
+  pub fn off(&self, event: String, listener: Function<Unknown, ()>) -> Result<()> {
+    self.events.off(&event, &listener)
+  }
+
+  #[napi]
+  pub fn listener_count(&self, event: String) -> u32 {
+    self.events.listener_count(&event) as u32
+  }
+
+  /// Emit `tick` with `0..count` from another thread, then `done` with the count
+  #[napi]
+  pub fn start(&self, count: u32) {
+    let sender = self.events.sender();
+    thread::spawn(move || {
+      for i in 0..count {
+        sender.emit("tick", i);
+      }
+      sender.emit("done", count);
+    });
+  }
+
+  #[napi]
+  pub fn emit_now(&self, event: String, payload: String) -> Result<bool> {
+    self.events.emit(&event, payload)
+  }
+}
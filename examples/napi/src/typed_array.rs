@@ -1,3 +1,5 @@
+use std::io::Write;
+use std::ops::Bound;
 use std::sync::Arc;
 
 use napi::bindgen_prelude::*;
@@ -52,6 +54,213 @@ pub fn create_buffer_slice_from_copied_data(env: &Env) -> Result<BufferSlice> {
   BufferSlice::copy_from(env, String::from("Hello world").as_bytes())
 }
 
+#[napi]
+pub fn buffers_from_bytes() -> Vec<Buffer> {
+  let bytes = bytes::Bytes::from(b"Hello world".to_vec());
+  vec![
+    Buffer::from_bytes(bytes.slice(..5)),
+    Buffer::from_bytes(bytes.slice(6..)),
+  ]
+}
+
+#[napi]
+pub fn array_buffer_from_bytes(env: &Env) -> Result<ArrayBuffer<'_>> {
+  ArrayBuffer::from_bytes(env, bytes::Bytes::from_static(b"Hello world"))
+}
+
+static BUFFER_POOL: std::sync::LazyLock<BufferPool> =
+  std::sync::LazyLock::new(|| BufferPool::with_capacity(16, 64));
+
+#[napi]
+pub fn pooled_buffer(env: &Env, text: String) -> Result<BufferSlice<'_>> {
+  BUFFER_POOL.from_data(env, text.as_bytes())
+}
+
+#[napi]
+pub fn pooled_buffers_available() -> u32 {
+  BUFFER_POOL.available() as u32
+}
+
+fn env_buffer_pool(env: &Env) -> Result<BufferPool> {
+  if let Some(pool) = env.buffer_pool() {
+    return Ok(pool);
+  }
+  let pool = BufferPool::with_size_classes(&[16, 64, 256], 8);
+  env.set_buffer_pool(pool.clone())?;
+  Ok(pool)
+}
+
+#[napi]
+pub fn pooled_fill(env: &Env, byte: u32, len: u32) -> Result<PooledBuffer> {
+  let mut buffer = env_buffer_pool(env)?.take(len as usize);
+  buffer.fill(byte as u8);
+  Ok(buffer)
+}
+
+#[napi(object)]
+pub struct PoolStats {
+  pub size_classes: Vec<u32>,
+  pub hits: u32,
+  pub misses: u32,
+  pub oversized: u32,
+  pub recycled: u32,
+  pub discarded: u32,
+  pub available: u32,
+}
+
+#[napi]
+pub fn env_buffer_pool_stats(env: &Env) -> Result<PoolStats> {
+  let pool = env_buffer_pool(env)?;
+  let stats = pool.stats();
+  Ok(PoolStats {
+    size_classes: pool.size_classes().map(|size| size as u32).collect(),
+    hits: stats.hits as u32,
+    misses: stats.misses as u32,
+    oversized: stats.oversized as u32,
+    recycled: stats.recycled as u32,
+    discarded: stats.discarded as u32,
+    available: stats.available as u32,
+  })
+}
+
+/// Write `text` `times` times and stream the output to `on_chunk` in pooled buffers.
+#[napi]
+pub fn stream_repeated<'env>(
+  env: &'env Env,
+  text: String,
+  times: u32,
+  on_chunk: Function<'env, BufferSlice<'env>, ()>,
+) -> Result<()> {
+  let mut writer = BUFFER_POOL.writer(env, |chunk| on_chunk.call(chunk));
+  for _ in 0..times {
+    writer.write_all(text.as_bytes())?;
+  }
+  writer.finish()
+}
+
+#[napi(object)]
+pub struct TypedArraySummary {
+  pub kind: String,
+  pub length: u32,
+  pub byte_length: u32,
+  pub sum: f64,
+}
+
+#[napi]
+pub fn summarize_typed_array(input: AnyTypedArray) -> TypedArraySummary {
+  let sum = match input {
+    AnyTypedArray::Int8(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Uint8(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Uint8Clamped(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Int16(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Uint16(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Int32(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Uint32(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Float32(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::Float64(s) => s.iter().sum(),
+    AnyTypedArray::BigInt64(s) => s.iter().map(|&v| v as f64).sum(),
+    AnyTypedArray::BigUint64(s) => s.iter().map(|&v| v as f64).sum(),
+  };
+  TypedArraySummary {
+    kind: input.typed_array_type().as_ref().to_owned(),
+    length: input.len() as u32,
+    byte_length: input.byte_length() as u32,
+    sum,
+  }
+}
+
+#[napi]
+pub fn uint16_subarray<'env>(
+  input: Uint16ArraySlice<'env>,
+  begin: u32,
+  end: u32,
+) -> Result<Uint16ArraySlice<'env>> {
+  let mut subarray = input.subarray(begin as usize, end as usize)?;
+  for value in subarray.iter_mut() {
+    *value *= 2;
+  }
+  Ok(subarray)
+}
+
+#[napi]
+pub fn write_uint16_at(mut output: Uint16ArraySlice, offset: u32, values: Vec<u16>) -> Result<()> {
+  output.copy_from_slice_at(offset as usize, &values)
+}
+
+#[napi]
+pub fn write_buffer_at(mut output: BufferSlice, offset: u32, text: String) -> Result<()> {
+  output.copy_from_slice_at(offset as usize, text.as_bytes())
+}
+
+#[napi]
+pub fn debug_buffer(buf: Buffer) -> String {
+  format!("{:?}", buf)
+}
+
+#[napi]
+pub fn display_float32_array(input: Float32Array) -> String {
+  input.to_string()
+}
+
+#[napi]
+pub fn hexdump_uint8_array(input: Uint8Array, start: u32, end: u32) -> String {
+  input.hexdump(start as usize..end as usize)
+}
+
+/// Sum the bytes of every chunk, and pass the chunks to `on_chunk` with their offsets.
+///
+/// With `copy`, the chunks are taken from a copy of `input` owned by Rust.
+#[napi]
+pub fn sum_chunks(
+  env: &Env,
+  input: Uint8ArraySlice,
+  chunk_len: u32,
+  copy: bool,
+  on_chunk: Function<FnArgs<(Unknown, u32)>, ()>,
+) -> Result<Vec<u32>> {
+  let copied = Uint8Array::new(input.to_vec());
+  let chunks = if copy {
+    copied.iter_chunks(env, chunk_len as usize)?
+  } else {
+    input.iter_chunks(chunk_len as usize)?
+  };
+  let mut sums = vec![];
+  chunks.try_for_each(|chunk| {
+    on_chunk.call((chunk.to_js()?, chunk.offset() as u32).into())?;
+    sums.push(chunk.iter().map(|byte| *byte as u32).sum());
+    Ok(())
+  })?;
+  Ok(sums)
+}
+
+#[napi]
+pub fn concat_buffers(env: &Env, parts: Vec<Buffer>) -> Result<BufferSlice> {
+  Buffer::concat(env, &parts)
+}
+
+#[napi]
+pub fn build_buffer(env: &Env, parts: Vec<String>) -> Result<BufferSlice> {
+  let mut builder = BufferBuilder::with_capacity(parts.iter().map(|part| part.len() + 1).sum());
+  for part in parts.iter() {
+    builder.append(part).append(b"\n");
+  }
+  builder.build(env)
+}
+
+/// Encode `values` as LEB128 varints.
+#[napi]
+pub fn encode_varints(values: Vec<u32>) -> Uint8Array {
+  let mut builder = Uint8ArrayBuilder::new();
+  for mut value in values {
+    while value >= 0x80 {
+      builder.push((value as u8 & 0x7f) | 0x80);
+      value >>= 7;
+    }
+    builder.push(value as u8);
+  }
+  builder.into()
+}
+
 #[napi]
 fn get_empty_typed_array() -> Uint8Array {
   vec![].into()
@@ -67,6 +276,18 @@ fn create_external_typed_array() -> Uint32Array {
   Uint32Array::new(vec![1, 2, 3, 4, 5])
 }
 
+#[napi]
+fn aligned_float32_array(data: Vec<f64>) -> Result<Float32Array> {
+  let data = data.into_iter().map(|v| v as f32).collect::<Vec<_>>();
+  Float32Array::new_aligned(data, 64)
+}
+
+/// The largest power of two alignment of the data, up to 4096.
+#[napi]
+fn float32_array_alignment(input: Float32Array) -> u32 {
+  1 << (input.as_ptr() as usize).trailing_zeros().min(12)
+}
+
 #[napi]
 fn mutate_typed_array(mut input: Float32Array) {
   for item in unsafe { input.as_mut() } {
@@ -116,6 +337,30 @@ fn create_arraybuffer(env: &Env) -> Result<ArrayBuffer> {
   Ok(buf)
 }
 
+/// Take the bytes of `buf` and return them reversed in a new `ArrayBuffer`, `buf` is detached.
+#[napi]
+pub fn reverse_transferred<'env>(env: &'env Env, buf: ArrayBuffer) -> Result<ArrayBuffer<'env>> {
+  let mut bytes = buf.transfer()?;
+  bytes.reverse();
+  ArrayBuffer::adopt(env, bytes)
+}
+
+#[napi]
+fn increment_arraybuffer(mut buf: ArrayBuffer) {
+  for byte in buf.iter_mut() {
+    *byte = byte.wrapping_add(1);
+  }
+}
+
+#[napi]
+fn write_around_callback(buf: ArrayBuffer, callback: Function<(), ()>) -> Result<u32> {
+  let mut guard = buf.detach_guard();
+  guard.set(0, 1)?;
+  callback.call(())?;
+  guard.set(1, 2)?;
+  guard.len().map(|len| len as u32)
+}
+
 #[napi]
 fn u8_array_to_array(input: &[u8]) -> Vec<u8> {
   input.to_vec()
@@ -246,3 +491,603 @@ impl Reader {
     output.into_buffer_slice(env)
   }
 }
+
+#[napi]
+pub async fn sum_pinned_bytes(bytes: PinnedBytes) -> u32 {
+  tokio::task::spawn(async move {
+    // SAFETY: the caller doesn't touch the bytes until the Promise is resolved
+    unsafe { bytes.as_slice() }.iter().map(|b| *b as u32).sum()
+  })
+  .await
+  .unwrap()
+}
+
+#[napi]
+pub fn resolve_pinned_bytes_after(
+  env: &Env,
+  bytes: PinnedBytes,
+  callback: Function<(), ()>,
+) -> Result<u32> {
+  callback.call(())?;
+  Ok(bytes.resolve(env)?.len() as u32)
+}
+
+#[napi]
+fn create_data_view<'env>(
+  array_buffer: ArrayBuffer<'env>,
+  byte_offset: u32,
+  length: u32,
+) -> Result<DataView<'env>> {
+  DataView::from_arraybuffer(&array_buffer, byte_offset as usize, length as usize)
+}
+
+#[napi]
+fn write_data_view(mut view: DataView) -> Result<f64> {
+  view.set_uint16(0, 0x1234, false)?;
+  view.set_int32(2, -2, true)?;
+  view.set_float64(6, 1.5, true)?;
+  view.set_big_uint64(14, u64::MAX, false)?;
+  Ok(view.get_uint8(0)? as f64 + view.get_int32(2, true)? as f64 + view.get_float64(6, true)?)
+}
+
+#[napi]
+fn sum_shared_array_buffer(buf: SharedArrayBuffer) -> Result<u32> {
+  Ok(
+    buf
+      .as_atomic_u8()?
+      .iter()
+      .map(|byte| byte.load(std::sync::atomic::Ordering::Relaxed) as u32)
+      .sum(),
+  )
+}
+
+#[napi]
+fn create_shared_memory(env: &Env, byte_length: u32) -> Result<SharedMemory> {
+  SharedMemory::new(env, byte_length as usize)
+}
+
+#[napi]
+fn increment_shared_counter(memory: SharedMemory, threads: u32) -> Result<()> {
+  let handles = (0..threads)
+    .map(|_| {
+      let memory = memory.clone();
+      std::thread::spawn(move || -> Result<()> {
+        memory.as_atomic_i32()?[0].fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+      })
+    })
+    .collect::<Vec<_>>();
+  for handle in handles {
+    handle
+      .join()
+      .map_err(|_| Error::from_reason("Increment thread panicked"))??;
+  }
+  Ok(())
+}
+
+#[napi]
+async fn fill_shared_memory(memory: SharedMemory, value: u8) -> Result<()> {
+  for byte in memory.as_atomic_u8()? {
+    byte.store(value, std::sync::atomic::Ordering::Relaxed);
+  }
+  Ok(())
+}
+
+#[napi]
+fn create_resizable_array_buffer<'env>(
+  env: &'env Env,
+  byte_length: u32,
+  max_byte_length: u32,
+) -> Result<ResizableArrayBuffer<'env>> {
+  ArrayBuffer::new_resizable(env, byte_length as usize, max_byte_length as usize)
+}
+
+#[napi(object)]
+pub struct ArrayBufferResizeInfo {
+  pub byte_length: u32,
+  pub max_byte_length: u32,
+}
+
+#[napi]
+fn resize_array_buffer(
+  mut buf: ResizableArrayBuffer,
+  new_byte_length: u32,
+) -> Result<ArrayBufferResizeInfo> {
+  buf.resize(new_byte_length as usize)?;
+  Ok(ArrayBufferResizeInfo {
+    byte_length: buf.len() as u32,
+    max_byte_length: buf.max_byte_length()? as u32,
+  })
+}
+
+/// Shrink `buf` to `new_byte_length` bytes, then read the bytes through `old`, a handle of the same `ArrayBuffer`
+/// taken before the resize.
+#[napi]
+fn shrink_and_read_old_handle(
+  mut buf: ResizableArrayBuffer,
+  old: ResizableArrayBuffer,
+  new_byte_length: u32,
+) -> Result<Vec<u8>> {
+  buf.resize(new_byte_length as usize)?;
+  Ok(old.to_vec())
+}
+
+/// Lend the same memory to JavaScript for each frame.
+#[napi]
+pub struct FrameProducer {
+  buffer: LoanedBuffer,
+  frame: u8,
+}
+
+#[napi]
+impl FrameProducer {
+  #[napi(constructor)]
+  pub fn new(len: u32) -> Self {
+    Self {
+      buffer: LoanedBuffer::new(vec![0; len as usize]),
+      frame: 0,
+    }
+  }
+
+  /// Recall the previous frame and lend the next one.
+  #[napi]
+  pub fn next_frame<'env>(&'env mut self, env: &'env Env) -> Result<ArrayBuffer<'env>> {
+    self.buffer.recall()?;
+    self.frame = self.frame.wrapping_add(1);
+    self.buffer.data_mut()?.fill(self.frame);
+    self.buffer.lend(env)
+  }
+
+  /// Recall the current frame and return the sum of the bytes written by JavaScript.
+  #[napi]
+  pub fn recall(&mut self) -> Result<u32> {
+    self.buffer.recall()?;
+    Ok(self.buffer.data()?.iter().map(|b| *b as u32).sum())
+  }
+
+  #[napi(getter)]
+  pub fn is_lent(&self) -> bool {
+    self.buffer.is_lent()
+  }
+}
+
+#[napi]
+fn transpose_matrix(
+  input: Float64ArraySlice,
+  rows: u32,
+  cols: u32,
+) -> Result<ndarray::Array2<f64>> {
+  let matrix = input.view_as_array2((rows as usize, cols as usize))?;
+  Ok(matrix.t().to_owned())
+}
+
+#[napi]
+fn scale_matrix_rows(mut input: Float64ArraySlice, rows: u32, cols: u32) -> Result<()> {
+  let mut matrix = input.view_as_array2_mut((rows as usize, cols as usize))?;
+  for (i, mut row) in matrix.rows_mut().into_iter().enumerate() {
+    row *= (i + 1) as f64;
+  }
+  Ok(())
+}
+
+#[napi]
+fn buffer_into_uint8_array(input: Buffer) -> Uint8Array {
+  input.into_uint8array()
+}
+
+#[napi]
+fn rust_buffer_into_uint8_array_twice() -> Uint8Array {
+  // the memory of the first `Uint8Array` is freed, and is likely reused by the second `Buffer`
+  drop(Buffer::from(vec![1; 16]).into_uint8array());
+  Buffer::from(vec![2; 16]).into_uint8array()
+}
+
+#[napi]
+fn uint8_array_into_buffer(env: &Env, input: Uint8ArraySlice) -> Result<Buffer> {
+  input.into_buffer(env)
+}
+
+#[napi]
+fn convert_rust_buffers(env: &Env) -> Result<Vec<Either<Buffer, Uint8Array>>> {
+  let mut bytes = Vec::with_capacity(16);
+  bytes.extend_from_slice(b"buffer");
+  let buffer = Buffer::from(bytes);
+  let array = Uint8Array::new(b"array".to_vec());
+  Ok(vec![
+    Either::B(buffer.into_uint8array()),
+    Either::A(array.into_buffer(env)?),
+  ])
+}
+
+/// Square the elements in place, synced back to JavaScript in the wasm builds.
+#[napi]
+fn square_in_place(mut input: Float64ArraySlice) -> Result<()> {
+  let mut data = input.synced();
+  for value in data.iter_mut() {
+    *value *= *value;
+  }
+  data.finish()
+}
+
+#[napi]
+fn get_frozen_uint8_array() -> Frozen<Uint8Array> {
+  Uint8Array::new(vec![1, 2, 3]).freeze()
+}
+
+#[napi(string_enum = "lowercase")]
+pub enum MemoryPolicy {
+  Exact,
+  Batched,
+  Disabled,
+}
+
+/// Create a `BufferSlice` of `size` bytes under `policy`, and return the change of the external memory reported to the engine.
+#[napi]
+pub fn reported_external_memory(env: &Env, size: u32, policy: MemoryPolicy) -> Result<i64> {
+  let previous = env.external_memory_policy();
+  env.set_external_memory_policy(match policy {
+    MemoryPolicy::Exact => ExternalMemoryPolicy::Exact,
+    MemoryPolicy::Batched => ExternalMemoryPolicy::Batched(size as usize * 2),
+    MemoryPolicy::Disabled => ExternalMemoryPolicy::Disabled,
+  })?;
+  let before = env.adjust_external_memory(0)?;
+  let reported = {
+    let _buffer = BufferSlice::from_data(env, vec![0; size as usize])?;
+    env.adjust_external_memory(0)? - before
+  };
+  env.set_external_memory_policy(previous)?;
+  Ok(reported)
+}
+
+/// Hold `size` bytes with an `ExternalMemoryGuard` and return the external memory reported while it's held, resized and released.
+#[napi]
+pub fn external_memory_guard_changes(
+  env: &Env,
+  size: u32,
+  release_on_thread: bool,
+) -> Result<Vec<i64>> {
+  let before = env.adjust_external_memory(0)?;
+  let mut guard = ExternalMemoryGuard::new(env, size as usize)?;
+  let held = env.adjust_external_memory(0)? - before;
+  guard.resize(size as usize * 2)?;
+  let resized = env.adjust_external_memory(0)? - before;
+  if release_on_thread {
+    // released on the JavaScript thread later
+    std::thread::spawn(move || drop(guard))
+      .join()
+      .map_err(|_| Error::from_reason("Failed to drop ExternalMemoryGuard on thread"))?;
+    return Ok(vec![held, resized]);
+  }
+  drop(guard);
+  let released = env.adjust_external_memory(0)? - before;
+  Ok(vec![held, resized, released])
+}
+
+#[napi]
+fn create_sequence_arraybuffer(env: &Env, len: u32) -> Result<ArrayBuffer<'_>> {
+  let mut buffer = ArrayBuffer::new_uninit(env, len as usize)?;
+  for (i, byte) in buffer.as_uninit_mut().iter_mut().enumerate() {
+    byte.write(i as u8);
+  }
+  Ok(unsafe { buffer.assume_init() })
+}
+
+#[napi]
+fn copy_into_arraybuffer<'env>(
+  env: &'env Env,
+  input: Uint8ArraySlice,
+) -> Result<ArrayBuffer<'env>> {
+  ArrayBuffer::copy_from(env, &*input)
+}
+
+#[napi]
+fn linspace(env: &Env, start: f64, end: f64, count: u32) -> Result<Float64ArraySlice<'_>> {
+  let step = if count > 1 {
+    (end - start) / (count - 1) as f64
+  } else {
+    0.0
+  };
+  Float64ArraySlice::from_iter(env, (0..count).map(|i| start + step * i as f64))
+}
+
+#[napi]
+fn squares_below(env: &Env, limit: u32) -> Result<Uint32ArraySlice<'_>> {
+  Uint32ArraySlice::from_iter(
+    env,
+    (0..).map(|i| i * i).take_while(|&square| square < limit),
+  )
+}
+
+#[napi]
+fn even_numbers(input: Uint32Array) -> Uint32Array {
+  input.iter().copied().filter(|n| n % 2 == 0).collect()
+}
+
+#[napi]
+fn copy_float64_array<'env>(
+  env: &'env Env,
+  input: Float64ArraySlice,
+) -> Result<Float64ArraySlice<'env>> {
+  Float64ArraySlice::copy_from(env, &*input)
+}
+
+#[napi]
+fn reclaim_rust_buffer() -> bool {
+  let data = b"reclaim".to_vec();
+  let data_ptr = data.as_ptr();
+  match Buffer::from(data).try_into_vec() {
+    Ok(data) => data.as_ptr() == data_ptr && data == b"reclaim",
+    Err(_) => false,
+  }
+}
+
+#[napi]
+fn js_buffer_into_vec(input: Buffer) -> Either<u32, String> {
+  match input.try_into_vec() {
+    Ok(_) => Either::B("reclaimed".to_owned()),
+    Err(input) => Either::A(Vec::from(input).iter().map(|&b| b as u32).sum()),
+  }
+}
+
+#[napi]
+fn split_buffer(env: &Env, input: Buffer, size: u32) -> Result<Vec<Buffer>> {
+  let size = (size as usize).max(1);
+  (0..input.len())
+    .step_by(size)
+    .map(|start| input.slice(env, start..(start + size).min(input.len())))
+    .collect()
+}
+
+#[napi]
+fn slice_bytes_buffer(env: &Env, start: u32, end: u32) -> Result<Buffer> {
+  Buffer::from_bytes(bytes::Bytes::from_static(b"Hello world"))
+    .slice(env, start as usize..end as usize)
+}
+
+#[napi]
+fn slice_rust_buffer(env: &Env) -> Result<Buffer> {
+  Buffer::from(b"Hello world".to_vec()).slice(env, ..5)
+}
+
+// Parse the records of a length prefixed string, the parser only knows `impl Read`
+fn read_records(mut reader: impl std::io::Read) -> std::io::Result<Vec<String>> {
+  let mut records = Vec::new();
+  let mut len = [0; 2];
+  loop {
+    match reader.read_exact(&mut len) {
+      Ok(()) => {}
+      Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(records),
+      Err(err) => return Err(err),
+    }
+    let mut record = vec![0; u16::from_le_bytes(len) as usize];
+    reader.read_exact(&mut record)?;
+    records.push(String::from_utf8_lossy(&record).into_owned());
+  }
+}
+
+#[napi]
+fn parse_records(input: Buffer) -> Result<Vec<String>> {
+  Ok(read_records(BufferCursor::new(input))?)
+}
+
+#[napi]
+fn patch_buffer(input: BufferSlice, offset_from_end: i32, patch: String) -> Result<u32> {
+  use std::io::{Seek, SeekFrom};
+
+  let mut cursor = BufferCursor::new(input);
+  cursor.seek(SeekFrom::End(offset_from_end as i64))?;
+  let written = cursor.write(patch.as_bytes())?;
+  Ok(written as u32)
+}
+
+#[napi]
+fn compare_buffers(
+  source: Buffer,
+  target: Buffer,
+  target_start: Option<u32>,
+  source_start: Option<u32>,
+) -> Result<i32> {
+  let ordering = source.compare_range(
+    &target,
+    target_start.unwrap_or(0) as usize..,
+    source_start.unwrap_or(0) as usize..,
+  )?;
+  Ok(ordering as i32)
+}
+
+#[napi]
+fn index_of_in_buffer(
+  input: BufferSlice,
+  value: Either<String, Buffer>,
+  byte_offset: Option<i64>,
+) -> i64 {
+  let value = match &value {
+    Either::A(string) => string.as_bytes(),
+    Either::B(buffer) => buffer.as_ref(),
+  };
+  input
+    .index_of(value, byte_offset.unwrap_or(0))
+    .map_or(-1, |index| index as i64)
+}
+
+#[napi]
+fn encode_buffer(input: Buffer, encoding: String) -> Result<String> {
+  Ok(input.to_string_encoded(encoding.parse()?))
+}
+
+#[napi]
+fn decode_to_buffer<'env>(
+  env: &'env Env,
+  input: String,
+  encoding: String,
+) -> Result<BufferSlice<'env>> {
+  Buffer::from_string_encoded(env, &input, encoding.parse()?)
+}
+
+#[napi]
+fn create_text_blob<'env>(env: &'env Env, text: String) -> Result<Blob<'env>> {
+  Blob::new(env, text, Some("text/plain"))
+}
+
+#[napi]
+fn describe_blob(blob: Blob) -> Result<String> {
+  Ok(format!("{} bytes of {}", blob.size()?, blob.type_()?))
+}
+
+#[napi]
+fn slice_blob<'env>(blob: Blob<'env>, start: Option<i64>, end: Option<i64>) -> Result<Blob<'env>> {
+  blob.slice(start, end, None)
+}
+
+#[napi]
+fn read_blob_reversed(env: &Env, blob: Blob) -> Result<AsyncBlock<Buffer>> {
+  let bytes = blob.array_buffer()?;
+  AsyncBlockBuilder::new(async move {
+    let bytes = bytes.await?;
+    Ok(bytes.iter().rev().copied().collect::<Vec<u8>>().into())
+  })
+  .build(env)
+}
+
+#[napi]
+fn read_file_range(
+  env: &Env,
+  path: String,
+  start: Option<u32>,
+  end: Option<u32>,
+) -> Result<BufferSlice<'_>> {
+  let start = Bound::Included(start.unwrap_or(0) as u64);
+  let end = end.map_or(Bound::Unbounded, |end| Bound::Excluded(end as u64));
+  BufferSlice::from_file(env, path, (start, end))
+}
+
+#[napi]
+async fn read_file_from(path: String, start: u32) -> Result<Buffer> {
+  Buffer::from_file_async(path, start as u64..).await
+}
+
+static SHARED_TABLE: std::sync::LazyLock<Arc<Vec<u8>>> =
+  std::sync::LazyLock::new(|| Arc::new(b"shared table".to_vec()));
+
+#[napi]
+fn shared_table_buffer(env: &Env, start: u32) -> Result<Buffer> {
+  // SAFETY: the table is only read by JavaScript
+  unsafe { Buffer::from_shared(SHARED_TABLE.clone()) }.slice(env, start as usize..)
+}
+
+#[napi]
+fn copied_table_buffer() -> Buffer {
+  Buffer::from(SHARED_TABLE.clone())
+}
+
+#[napi]
+fn shared_table_owners() -> u32 {
+  Arc::strong_count(&SHARED_TABLE) as u32
+}
+
+#[napi]
+fn frozen_bytes_mut_buffer() -> Buffer {
+  let mut data = bytes::BytesMut::with_capacity(16);
+  data.extend_from_slice(b"frozen ");
+  data.extend_from_slice(b"BytesMut");
+  data.into()
+}
+
+#[napi]
+fn buffer_diagnostics_enabled() -> bool {
+  napi::buffer_diagnostics::is_enabled()
+}
+
+#[napi]
+fn live_external_buffer_sizes() -> Vec<u32> {
+  napi::buffer_diagnostics::live_buffers()
+    .iter()
+    .map(|buffer| buffer.len as u32)
+    .collect()
+}
+
+#[napi]
+fn buffer_diagnostics_report() -> String {
+  napi::buffer_diagnostics::report()
+}
+
+static BUFFER_EVENTS: [std::sync::atomic::AtomicU32; 4] =
+  [const { std::sync::atomic::AtomicU32::new(0) }; 4];
+
+fn count_buffer_event(event: napi::buffer_diagnostics::BufferEvent) {
+  use napi::buffer_diagnostics::BufferEvent;
+
+  let index = match event {
+    BufferEvent::Created { .. } => 0,
+    BufferEvent::Finalized { .. } => 1,
+    BufferEvent::Referenced { .. } => 2,
+    BufferEvent::Unreferenced { .. } => 3,
+    _ => return,
+  };
+  BUFFER_EVENTS[index].fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[napi(object)]
+pub struct BufferEventCounts {
+  pub created: u32,
+  pub finalized: u32,
+  pub referenced: u32,
+  pub unreferenced: u32,
+}
+
+#[napi]
+fn count_buffer_events(enabled: bool) {
+  if enabled {
+    napi::buffer_diagnostics::set_buffer_event_hook(count_buffer_event);
+  } else {
+    napi::buffer_diagnostics::remove_buffer_event_hook();
+  }
+}
+
+#[napi]
+fn buffer_event_counts() -> BufferEventCounts {
+  let [created, finalized, referenced, unreferenced] =
+    std::array::from_fn(|i| BUFFER_EVENTS[i].load(std::sync::atomic::Ordering::Relaxed));
+  BufferEventCounts {
+    created,
+    finalized,
+    referenced,
+    unreferenced,
+  }
+}
+
+#[napi]
+fn sum_in_threads(input: Buffer, threads: u32) -> u32 {
+  let shared = input.to_shared();
+  let chunk = shared.len().div_ceil(threads.max(1) as usize).max(1);
+  let handles = (0..shared.len())
+    .step_by(chunk)
+    .map(|start| {
+      let shared = shared.clone();
+      std::thread::spawn(move || {
+        shared[start..(start + chunk).min(shared.len())]
+          .iter()
+          .map(|&b| b as u32)
+          .sum::<u32>()
+      })
+    })
+    .collect::<Vec<_>>();
+  handles
+    .into_iter()
+    .map(|handle| handle.join().unwrap())
+    .sum()
+}
+
+#[napi]
+fn shared_bytes_round_trip(input: SharedBytes) -> (SharedBytes, Buffer) {
+  let owned = Buffer::from(input.to_vec()).to_shared();
+  (input, owned.into())
+}
+
+/// Return `input` to JavaScript while a clone is kept, and read the clone after `write` mutates the returned `Buffer`.
+#[napi]
+fn shared_bytes_after_write(input: SharedBytes, write: Function<Buffer, ()>) -> Result<Buffer> {
+  let kept = input.clone();
+  write.call(input.into())?;
+  Ok(Buffer::from(kept.to_vec()))
+}
@@ -0,0 +1,84 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use napi::{
+  bindgen_prelude::*,
+  uv::{UvCheck, UvIdle, UvTimer},
+};
+
+/// Tick a uv timer `ticks` times, resolved with the number of the ticks and the check callbacks called meanwhile.
+#[napi(ts_return_type = "Promise<[number, number]>")]
+pub fn count_uv_timer_ticks(env: &Env, ticks: u32) -> Result<Object<'_>> {
+  let (deferred, promise) = env.create_deferred()?;
+  let checks = Rc::new(Cell::new(0u32));
+  let check = UvCheck::new(env, {
+    let checks = checks.clone();
+    move || checks.set(checks.get() + 1)
+  })?;
+  check.start()?;
+  // dropped by the timer callback, which closes the handles
+  let handles: Rc<RefCell<Option<(UvTimer, UvCheck)>>> = Rc::default();
+  let mut deferred = Some(deferred);
+  let mut count = 0;
+  let timer = UvTimer::new(env, {
+    let handles = handles.clone();
+    move || {
+      count += 1;
+      if count < ticks {
+        return;
+      }
+      handles.borrow_mut().take();
+      let checks = checks.get();
+      if let Some(deferred) = deferred.take() {
+        deferred.resolve(move |_| Ok(vec![count, checks]));
+      }
+    }
+  })?;
+  timer.start(Duration::from_millis(1), Duration::from_millis(1))?;
+  *handles.borrow_mut() = Some((timer, check));
+  Ok(promise)
+}
+
+/// Create a uv idle handle, and return whether it refs the event loop before and after `unref`.
+#[napi]
+pub fn uv_idle_ref_states(env: &Env) -> Result<Vec<bool>> {
+  let idle = UvIdle::new(env, || {})?;
+  idle.start()?;
+  let before = idle.has_ref();
+  idle.unref();
+  let after = idle.has_ref();
+  Ok(vec![before, after])
+}
+
+/// Call `callback` from a uv timer, in the async context of this call.
+#[napi]
+pub fn call_from_uv_timer(
+  env: &Env,
+  callback: Function<(), ()>,
+  use_make_callback: bool,
+) -> Result<()> {
+  let context = AsyncContext::new(env, "UvTimerCallback")?;
+  let callback = callback.create_ref()?;
+  let env = *env;
+  // dropped by the timer callback, which closes the handle
+  let timer_slot: Rc<RefCell<Option<UvTimer>>> = Rc::default();
+  let timer = UvTimer::new(&env, {
+    let timer_slot = timer_slot.clone();
+    move || {
+      timer_slot.borrow_mut().take();
+      let _ = if use_make_callback {
+        env.with_handle_scope(|env| {
+          callback
+            .borrow_back(env)?
+            .make_callback(&context, env.get_global()?, ())
+        })
+      } else {
+        env.with_callback_scope(&context, |env| callback.borrow_back(env)?.call(()))
+      };
+    }
+  })?;
+  timer.start(Duration::ZERO, Duration::ZERO)?;
+  *timer_slot.borrow_mut() = Some(timer);
+  Ok(())
+}
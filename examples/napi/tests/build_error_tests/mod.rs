@@ -2,6 +2,7 @@
 
 pub mod assign_js_value_to_class;
 pub mod fn_outside_impl_factory;
+pub mod multiversion_unsupported_feature;
 pub mod ts_arg_type_1;
 pub mod ts_arg_type_2;
 pub mod ts_arg_type_3;
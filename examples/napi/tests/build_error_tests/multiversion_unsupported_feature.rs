@@ -0,0 +1,12 @@
+//! This is testing that `#[napi(multiversion)]` with an unknown target feature fails
+
+use napi_derive::napi;
+
+#[napi(multiversion(avx2, warp_drive))]
+pub fn sum(input: Vec<u32>) -> u32 {
+  input.iter().sum()
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
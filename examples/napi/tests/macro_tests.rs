@@ -10,4 +10,5 @@ fn run_build_error_tests() {
   t.compile_fail("tests/build_error_tests/ts_arg_type_*.rs");
   t.compile_fail("tests/build_error_tests/fn_outside_impl_factory.rs");
   t.compile_fail("tests/build_error_tests/assign_js_value_to_class.rs");
+  t.compile_fail("tests/build_error_tests/multiversion_unsupported_feature.rs");
 }